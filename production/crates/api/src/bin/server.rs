@@ -8,8 +8,9 @@ use anyhow::Result;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use threshold_api::{start_server, AppState};
-use threshold_storage::{PostgresStorage, EtcdStorage};
+use threshold_api::{internal_http_client, start_internal_server, start_server, AppState};
+use threshold_api::middleware::{JwtKeystore, RateLimitPolicy, RateLimiter};
+use threshold_storage::{AuditCheckpoint, AuditSigner, PostgresStorage, EtcdStorage};
 use threshold_bitcoin::{BitcoinClient, BitcoinNetwork};
 use threshold_types::PostgresConfig;
 use threshold_consensus::VoteProcessor;
@@ -19,11 +20,20 @@ use threshold_orchestrator::{
     OrchestrationServiceBuilder,
     TimeoutMonitorBuilder,
     HealthCheckerBuilder,
+    DepositWatcherBuilder,
+    DepositWatcherConfig,
+    WebhookDispatcherBuilder,
+    WebhookDispatcherConfig,
+    RetentionServiceBuilder,
+    RetentionConfig,
     OrchestrationConfig,
     DkgService,
     AuxInfoService,
+    PresigSizingConfig,
+    FrostNonceConfig,
 };
 use threshold_network::{QuicEngine, PeerRegistry};
+use threshold_security::{CertificateManager, ServerConfigBuilder};
 use tracing::{info, error, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 use tokio::signal;
@@ -46,22 +56,49 @@ async fn main() -> Result<()> {
     // Load configuration from environment
     let config = load_config()?;
 
+    // Load access token signing keys; refuses to start without keys outside DEV_MODE
+    let jwt_keys = Arc::new(JwtKeystore::from_env()?);
+    info!("JWT keystore loaded (signing kid={})", jwt_keys.signing_kid());
+
+    // Load the key audit entries are signed with; refuses to start without it outside DEV_MODE
+    let audit_signer = Arc::new(load_audit_signer(&config)?);
+    info!("Audit key loaded for node {}", config.node_id);
+
     // Initialize PostgreSQL storage
     info!("Connecting to PostgreSQL at {}", mask_password(&config.postgres_config.url));
-    let postgres = Arc::new(PostgresStorage::new(&config.postgres_config).await?);
+    let postgres = Arc::new(
+        PostgresStorage::new(&config.postgres_config)
+            .await?
+            .with_audit_signer(Arc::clone(&audit_signer)),
+    );
     info!("PostgreSQL storage initialized");
 
-    // Initialize etcd storage (with Mutex for interior mutability)
+    // Bring the schema up to date; nodes starting together take turns on an advisory lock
+    let applied = postgres.migrate().await?;
+    if !applied.is_empty() {
+        info!("Applied schema migrations: {:?}", applied);
+    }
+
+    // Create the first admin from BOOTSTRAP_ADMIN_PASSWORD; a no-op once one exists
+    bootstrap_admin(&postgres).await?;
+
+    // Initialize etcd storage; the handle is cloned into every component that needs it
     info!("Connecting to etcd cluster: {:?}", config.etcd_endpoints);
-    let etcd = Arc::new(tokio::sync::Mutex::new(EtcdStorage::new(config.etcd_endpoints.clone()).await?));
+    let etcd = EtcdStorage::new(config.etcd_endpoints.clone()).await?;
     info!("etcd storage initialized");
 
     // Initialize cluster configuration in etcd on startup
-    {
-        let mut etcd_lock = etcd.lock().await;
-        etcd_lock.set_cluster_threshold(config.threshold).await?;
-        info!("Cluster threshold initialized in etcd: {}", config.threshold);
-    }
+    etcd.set_cluster_threshold(config.threshold).await?;
+    info!("Cluster threshold initialized in etcd: {}", config.threshold);
+
+    // Publish the audit public key so the chain can be verified offline
+    let audit_public_key = audit_signer.verifying_key().to_bytes();
+    etcd.register_audit_signer(threshold_types::NodeId(config.node_id), &audit_public_key)
+        .await?;
+    postgres
+        .register_audit_signer(threshold_types::NodeId(config.node_id), &audit_public_key)
+        .await?;
+    info!("Audit public key registered: {}", hex::encode(audit_public_key));
 
     // Initialize Bitcoin client
     info!("Initializing Bitcoin client for {:?}", config.bitcoin_network);
@@ -73,8 +110,10 @@ async fn main() -> Result<()> {
 
     // Create application state with new storage instances
     // AppState::new takes ownership, so we create fresh instances
-    let postgres_for_state = PostgresStorage::new(&config.postgres_config).await?;
-    let etcd_for_state = EtcdStorage::new(config.etcd_endpoints.clone()).await?;
+    let postgres_for_state = PostgresStorage::new(&config.postgres_config)
+        .await?
+        .with_audit_signer(Arc::clone(&audit_signer));
+    let etcd_for_state = etcd.clone();
     let bitcoin_for_state = BitcoinClient::new(config.bitcoin_network)?;
 
     // Create QuicEngine for DKG service
@@ -151,27 +190,38 @@ async fn main() -> Result<()> {
     });
     info!("QUIC listener started, routing messages to MessageRouter");
 
+    // mTLS identity for the internal listener and for calls to other nodes' internal listeners
+    let cert_manager = CertificateManager::new(
+        config.ca_cert_path.clone(),
+        config.node_cert_path.clone(),
+        config.node_key_path.clone(),
+    );
+    let internal_tls = ServerConfigBuilder::new(cert_manager.clone())
+        .build_with_alpn(vec![b"h2".to_vec(), b"http/1.1".to_vec()])?;
+    let internal_client = internal_http_client(cert_manager.clone())?;
+    info!("Internal mTLS configuration loaded from {}", config.node_cert_path);
+
     // Create DKG service (wrapped in Arc for shared access)
-    let node_endpoints_map: HashMap<u64, String> = config.node_endpoints.iter().cloned().collect();
+    let internal_endpoints_map: HashMap<u64, String> = config.internal_endpoints.iter().cloned().collect();
     let dkg_service = Arc::new(DkgService::new(
         Arc::clone(&postgres),
-        Arc::clone(&etcd),
+        etcd.clone(),
         Arc::clone(&quic_engine),
         Arc::clone(&message_router),
         threshold_types::NodeId(config.node_id),
-        node_endpoints_map.clone(),
-    ));
+        internal_endpoints_map.clone(),
+    ).with_http_client(internal_client.clone()));
     info!("DKG service initialized");
 
     // Create Aux Info service (wrapped in Arc for shared access)
     let aux_info_service = Arc::new(AuxInfoService::new(
         Arc::clone(&postgres),
-        Arc::clone(&etcd),
+        etcd.clone(),
         Arc::clone(&quic_engine),
         Arc::clone(&message_router),
         threshold_types::NodeId(config.node_id),
-        node_endpoints_map.clone(),
-    ));
+        internal_endpoints_map.clone(),
+    ).with_http_client(internal_client.clone()));
     info!("Aux info service initialized");
 
     // Create presignature service (needed by signing coordinator and AppState)
@@ -179,16 +229,50 @@ async fn main() -> Result<()> {
         Arc::clone(&quic_engine),
         Arc::clone(&message_router),
         Arc::clone(&postgres),
-        Arc::clone(&etcd),
+        etcd.clone(),
         Arc::clone(&aux_info_service),
         threshold_types::NodeId(config.node_id),
-        node_endpoints_map.clone(), // SORUN #19 FIX: Add node_endpoints for broadcasting
-    ));
+        internal_endpoints_map.clone(), // SORUN #19 FIX: Add node_endpoints for broadcasting
+    )
+    .with_sizing_config(PresigSizingConfig::from_env())
+    .with_http_client(internal_client.clone()));
     info!("Presignature service initialized");
 
+    // Create FROST nonce pool service (single-round Taproot signing)
+    let frost_nonce_service = Arc::new(threshold_orchestrator::FrostNonceService::new(
+        Arc::clone(&postgres),
+        etcd.clone(),
+        threshold_types::NodeId(config.node_id),
+    ).with_config(FrostNonceConfig::from_env()));
+    info!("FROST nonce service initialized");
+
     // Create vote trigger channel for automatic voting
     let (vote_tx, vote_rx) = tokio::sync::mpsc::channel(100);
 
+    // Request quotas per caller and route class
+    let rate_limit_policy = RateLimitPolicy::from_env();
+    info!("Rate limits per minute: login={} read={} write={} costly={} (shared={})",
+        rate_limit_policy.login.max_requests,
+        rate_limit_policy.read.max_requests,
+        rate_limit_policy.write.max_requests,
+        rate_limit_policy.costly.max_requests,
+        config.rate_limit_shared);
+    let mut rate_limiter = RateLimiter::from_policy(rate_limit_policy);
+    if config.rate_limit_shared {
+        rate_limiter = rate_limiter.with_shared_store(etcd.clone());
+    }
+    let rate_limiter = Arc::new(rate_limiter);
+
+    // Drop idle rate limit buckets periodically
+    let limiter_for_cleanup = Arc::clone(&rate_limiter);
+    let rate_limit_cleanup_handle = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            limiter_for_cleanup.cleanup_old_entries().await;
+        }
+    });
+
     let state = AppState::new(
         postgres_for_state,
         etcd_for_state,
@@ -196,17 +280,82 @@ async fn main() -> Result<()> {
         Arc::clone(&dkg_service),
         Arc::clone(&aux_info_service),
         Arc::clone(&presig_service),
+        Arc::clone(&frost_nonce_service),
         Arc::clone(&message_router),
         vote_tx,
         threshold_types::NodeId(config.node_id),
-    );
+        Arc::clone(&jwt_keys),
+        rate_limiter,
+    )
+    .with_idempotency_ttl(std::time::Duration::from_secs(config.idempotency_ttl_hours * 3600));
+
+    // Purge expired idempotency keys and old cluster events periodically
+    let postgres_for_purge = Arc::clone(&postgres);
+    let events_retention_secs = config.events_retention_hours * 3600;
+    let retention_purge_handle = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            match postgres_for_purge.purge_expired_idempotency_keys().await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} expired idempotency keys", purged),
+                Err(e) => warn!("Failed to purge idempotency keys: {}", e),
+            }
+            match postgres_for_purge.purge_cluster_events(events_retention_secs).await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} old cluster events", purged),
+                Err(e) => warn!("Failed to purge cluster events: {}", e),
+            }
+        }
+    });
 
-    // Parse listen address
+    // Anchor the audit chain head in etcd periodically
+    let postgres_for_audit = Arc::clone(&postgres);
+    let etcd_for_audit = etcd.clone();
+    let audit_node_id = config.node_id;
+    let audit_checkpoint_interval = config.audit_checkpoint_interval_secs;
+    let audit_checkpoint_handle = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(audit_checkpoint_interval));
+        let mut last_anchored = 0;
+        loop {
+            interval.tick().await;
+            let (seq, entry_hash) = match postgres_for_audit.audit_chain_head().await {
+                Ok(head) => head,
+                Err(e) => {
+                    warn!("Failed to read audit chain head: {}", e);
+                    continue;
+                }
+            };
+            if seq <= last_anchored {
+                continue;
+            }
+            let checkpoint = AuditCheckpoint {
+                seq,
+                entry_hash: hex::encode(entry_hash),
+                node_id: audit_node_id,
+                created_at: chrono::Utc::now(),
+            };
+            match etcd_for_audit.put_audit_checkpoint(&checkpoint).await {
+                Ok(true) => info!("Anchored audit chain head {} in etcd", seq),
+                // Another node anchored this head already
+                Ok(false) => {}
+                Err(e) => {
+                    warn!("Failed to anchor audit chain head: {}", e);
+                    continue;
+                }
+            }
+            last_anchored = seq;
+        }
+    });
+
+    // Parse listen addresses
     let addr: SocketAddr = config.listen_addr.parse()?;
+    let internal_addr: SocketAddr = config.internal_listen_addr.parse()?;
 
     info!("Server configuration:");
     info!("  Node ID: {}", config.node_id);
     info!("  Listen Address: {}", addr);
+    info!("  Internal Listen Address (mTLS): {}", internal_addr);
     info!("  Threshold: {}/{}", config.threshold, config.total_nodes);
     info!("  Bitcoin Network: {:?}", config.bitcoin_network);
     info!("  Orchestration: {}", if config.enable_orchestration { "enabled" } else { "disabled" });
@@ -218,17 +367,18 @@ async fn main() -> Result<()> {
         // Configure orchestration
         let orchestration_config = OrchestrationConfig::default();
 
-        // Initialize VoteProcessor with cloned storage (VoteProcessor takes ownership)
-        // We need to create new EtcdStorage and PostgresStorage instances
-        let etcd_for_vp = EtcdStorage::new(config.etcd_endpoints.clone()).await?;
-        let postgres_for_vp = PostgresStorage::new(&config.postgres_config).await?;
+        // Initialize VoteProcessor with its own PostgresStorage (VoteProcessor takes ownership)
+        let etcd_for_vp = etcd.clone();
+        let postgres_for_vp = PostgresStorage::new(&config.postgres_config)
+            .await?
+            .with_audit_signer(Arc::clone(&audit_signer));
         let vote_processor = Arc::new(VoteProcessor::new(etcd_for_vp, postgres_for_vp));
         info!("Vote processor initialized for orchestration");
 
         // Create AutoVoter for automatic transaction voting
         let auto_voter = threshold_orchestrator::AutoVoter::new(
             threshold_types::NodeId(config.node_id),
-            Arc::clone(&postgres),
+            postgres.clone(),
             Arc::clone(&vote_processor),
             vote_rx,
         );
@@ -270,7 +420,8 @@ async fn main() -> Result<()> {
         // Start health checker
         let health_checker = HealthCheckerBuilder::new()
             .with_nodes(config.node_endpoints.clone())
-            .with_etcd(Arc::clone(&etcd))
+            .with_etcd(etcd.clone())
+            .with_event_log(Arc::clone(&postgres), threshold_types::NodeId(config.node_id))
             .build()?;
         let health_handle = Arc::clone(&health_checker).start();
         info!("Health checker started");
@@ -278,20 +429,47 @@ async fn main() -> Result<()> {
         // Start timeout monitor
         let timeout_monitor = TimeoutMonitorBuilder::new()
             .with_config(orchestration_config.clone())
-            .with_postgres(Arc::clone(&postgres))
+            .with_transaction_store(postgres.clone())
             .build()?;
         let timeout_handle = Arc::clone(&timeout_monitor).start();
         info!("Timeout monitor started");
 
+        // Start deposit watcher (credits user ledgers on confirmed deposits)
+        let deposit_watcher = DepositWatcherBuilder::new()
+            .with_config(DepositWatcherConfig::from_env())
+            .with_postgres(Arc::clone(&postgres))
+            .with_bitcoin(Arc::clone(&bitcoin))
+            .build()?;
+        let deposit_handle = Arc::clone(&deposit_watcher).start();
+        info!("Deposit watcher started");
+
+        // Start webhook dispatcher (delivers queued events to subscribers)
+        let webhook_dispatcher = WebhookDispatcherBuilder::new()
+            .with_config(WebhookDispatcherConfig::from_env())
+            .with_postgres(Arc::clone(&postgres))
+            .build()?;
+        let webhook_handle = Arc::clone(&webhook_dispatcher).start();
+        info!("Webhook dispatcher started");
+
+        // Start retention service (archives and prunes finished transactions)
+        let retention_service = RetentionServiceBuilder::new()
+            .with_config(RetentionConfig::from_env())
+            .with_node_id(threshold_types::NodeId(config.node_id))
+            .with_postgres(Arc::clone(&postgres))
+            .with_etcd(etcd.clone())
+            .build()?;
+        let retention_handle = Arc::clone(&retention_service).start();
+        info!("Retention service started");
+
         // Create aux info service for orchestration (fresh instance)
         let aux_info_for_presig = Arc::new(threshold_orchestrator::AuxInfoService::new(
             Arc::clone(&postgres),
-            Arc::clone(&etcd),
+            etcd.clone(),
             Arc::clone(&quic_engine),
             Arc::clone(&message_router),
             threshold_types::NodeId(config.node_id),
-            node_endpoints_map.clone(), // Clone for aux_info_for_presig
-        ));
+            internal_endpoints_map.clone(), // Clone for aux_info_for_presig
+        ).with_http_client(internal_client.clone()));
         info!("Aux info service initialized for orchestration");
 
         // Start presignature generation background loop (presig_service already created above)
@@ -301,6 +479,13 @@ async fn main() -> Result<()> {
         });
         info!("Presignature generation loop started");
 
+        // Start FROST nonce refill loop (runs on every node for its own pool)
+        let frost_nonce_service_clone = Arc::clone(&frost_nonce_service);
+        tokio::spawn(async move {
+            frost_nonce_service_clone.run_refill_loop().await;
+        });
+        info!("FROST nonce refill loop started");
+
         // Link presignature service to DKG service for automatic presignature generation after DKG
         dkg_service.set_presignature_service(Arc::clone(&presig_service)).await;
         info!("Presignature service linked to DKG service");
@@ -310,17 +495,16 @@ async fn main() -> Result<()> {
         info!("Aux info service linked to DKG service");
 
         // Create signing coordinator for MPC signing protocols
-        // SigningCoordinator needs Arc<EtcdStorage>, create fresh instance
-        let etcd_for_signing = Arc::new(EtcdStorage::new(config.etcd_endpoints.clone()).await?);
         let signing_coordinator = Arc::new(threshold_orchestrator::SigningCoordinator::new(
             Arc::clone(&quic_engine),
-            Arc::clone(&postgres),
-            etcd_for_signing,
+            postgres.clone(),
             Arc::clone(&presig_service),
             threshold_types::NodeId(config.node_id),
             config.threshold as usize,
-            node_endpoints_map, // SORUN #17 fix: HTTP broadcast for signing multi-node orchestration
-        ));
+            internal_endpoints_map, // SORUN #17 fix: HTTP broadcast for signing multi-node orchestration
+        )
+        .with_frost_nonce_service(Arc::clone(&frost_nonce_service))
+        .with_http_client(internal_client.clone()));
         info!("Signing coordinator initialized");
 
         // Create protocol router for automatic CGGMP24/FROST selection
@@ -336,21 +520,31 @@ async fn main() -> Result<()> {
             .with_vote_processor(Arc::clone(&vote_processor))
             .with_session_coordinator(Arc::clone(&session_coordinator))
             .with_postgres(Arc::clone(&postgres))
-            .with_etcd(Arc::clone(&etcd))
+            .with_etcd(etcd.clone())
             .with_bitcoin(Arc::clone(&bitcoin))
             .with_signing_coordinator(Arc::clone(&signing_coordinator))
             .with_protocol_router(Arc::clone(&protocol_router))
-            .with_node_endpoints(config.node_endpoints.clone())
+            .with_node_id(threshold_types::NodeId(config.node_id))
+            .with_http_client(internal_client.clone())
+            .with_node_endpoints(config.internal_endpoints.clone())
             .build()?;
         let orchestrator_handle = Arc::clone(&orchestrator).start();
         info!("Orchestration service started");
 
-        Some((orchestrator, timeout_monitor, health_checker, quic_transport, orchestrator_handle, timeout_handle, health_handle))
+        Some((orchestrator, timeout_monitor, health_checker, deposit_watcher, webhook_dispatcher, retention_service, quic_transport, orchestrator_handle, timeout_handle, health_handle, deposit_handle, webhook_handle, retention_handle))
     } else {
         warn!("Orchestration disabled - transactions will not be automatically processed");
         None
     };
 
+    // Start the internal mTLS server for node-to-node endpoints
+    let internal_state = state.clone();
+    let internal_server_handle = tokio::spawn(async move {
+        if let Err(e) = start_internal_server(internal_state, internal_addr, internal_tls, cert_manager).await {
+            error!("Internal server error: {}", e);
+        }
+    });
+
     // Start the API server in a separate task
     info!("API server starting on {}", addr);
     let server_handle = tokio::spawn(async move {
@@ -371,11 +565,14 @@ async fn main() -> Result<()> {
     }
 
     // Graceful shutdown
-    if let Some((orchestrator, timeout_monitor, health_checker, quic_transport, orch_handle, timeout_handle, health_handle)) = orchestrator_handle {
+    if let Some((orchestrator, timeout_monitor, health_checker, deposit_watcher, webhook_dispatcher, retention_service, quic_transport, orch_handle, timeout_handle, health_handle, deposit_handle, webhook_handle, retention_handle)) = orchestrator_handle {
         info!("Shutting down orchestration services...");
         orchestrator.shutdown().await;
         timeout_monitor.shutdown().await;
         health_checker.shutdown().await;
+        deposit_watcher.shutdown().await;
+        webhook_dispatcher.shutdown().await;
+        retention_service.shutdown().await;
 
         // Shutdown QUIC transport
         info!("Shutting down QUIC transport...");
@@ -395,10 +592,26 @@ async fn main() -> Result<()> {
             _ = health_handle => info!("Health checker stopped"),
             _ = tokio::time::sleep(shutdown_timeout) => warn!("Health checker shutdown timed out"),
         }
+        tokio::select! {
+            _ = deposit_handle => info!("Deposit watcher stopped"),
+            _ = tokio::time::sleep(shutdown_timeout) => warn!("Deposit watcher shutdown timed out"),
+        }
+        tokio::select! {
+            _ = webhook_handle => info!("Webhook dispatcher stopped"),
+            _ = tokio::time::sleep(shutdown_timeout) => warn!("Webhook dispatcher shutdown timed out"),
+        }
+        tokio::select! {
+            _ = retention_handle => info!("Retention service stopped"),
+            _ = tokio::time::sleep(shutdown_timeout) => warn!("Retention service shutdown timed out"),
+        }
     }
 
-    // Stop API server
+    // Stop API servers
     server_handle.abort();
+    internal_server_handle.abort();
+    rate_limit_cleanup_handle.abort();
+    retention_purge_handle.abort();
+    audit_checkpoint_handle.abort();
     info!("API server stopped");
 
     info!("Shutdown complete");
@@ -416,6 +629,21 @@ struct Config {
     bitcoin_network: BitcoinNetwork,
     enable_orchestration: bool,
     node_endpoints: Vec<(u64, String)>,
+    // Enforce rate limits across all nodes via etcd
+    rate_limit_shared: bool,
+    // Retention of transaction idempotency keys
+    idempotency_ttl_hours: u64,
+    // Retention of the cluster event log behind GET /api/v1/events
+    events_retention_hours: u64,
+    // Ed25519 key signing audit entries, and how often the chain head is anchored in etcd
+    audit_key_path: String,
+    audit_checkpoint_interval_secs: u64,
+    // Internal (node-to-node) mTLS listener
+    internal_listen_addr: String,
+    internal_endpoints: Vec<(u64, String)>,
+    ca_cert_path: String,
+    node_cert_path: String,
+    node_key_path: String,
     // QUIC/mTLS configuration
     quic_listen_addr: String,
     quic_port: u16,
@@ -464,27 +692,45 @@ fn load_config() -> Result<Config> {
         .parse::<bool>()
         .unwrap_or(true);
 
+    let rate_limit_shared = std::env::var("RATE_LIMIT_SHARED")
+        .unwrap_or_else(|_| "false".to_string())
+        .parse::<bool>()
+        .unwrap_or(false);
+
+    let idempotency_ttl_hours = std::env::var("IDEMPOTENCY_KEY_TTL_HOURS")
+        .unwrap_or_else(|_| "24".to_string())
+        .parse::<u64>()?;
+
+    let events_retention_hours = std::env::var("EVENTS_RETENTION_HOURS")
+        .unwrap_or_else(|_| "24".to_string())
+        .parse::<u64>()?;
+
+    let audit_key_path = std::env::var("AUDIT_KEY_PATH")
+        .unwrap_or_else(|_| format!("/audit-keys/node{}.pem", node_id));
+
+    let audit_checkpoint_interval_secs = std::env::var("AUDIT_CHECKPOINT_INTERVAL_SECS")
+        .unwrap_or_else(|_| "300".to_string())
+        .parse::<u64>()?
+        .max(1);
+
     // Parse node endpoints for health checking
-    let node_endpoints_str = std::env::var("NODE_ENDPOINTS").unwrap_or_default();
-    let node_endpoints: Vec<(u64, String)> = if !node_endpoints_str.is_empty() {
-        node_endpoints_str
-            .split(';')
-            .filter_map(|entry| {
-                let parts: Vec<&str> = entry.split('=').collect();
-                if parts.len() == 2 {
-                    if let Ok(id) = parts[0].parse::<u64>() {
-                        return Some((id, parts[1].to_string()));
-                    }
-                }
-                None
-            })
-            .collect()
-    } else {
-        // Default: generate endpoints for all nodes
-        (1..=total_nodes)
-            .map(|id| (id as u64, format!("http://mpc-node-{}:8080", id)))
-            .collect()
-    };
+    let node_endpoints = parse_endpoints("NODE_ENDPOINTS", total_nodes, |id| {
+        format!("http://mpc-node-{}:8080", id)
+    });
+
+    // Internal mTLS listener and the peer endpoints used for node-to-node calls
+    let internal_listen_addr = std::env::var("INTERNAL_LISTEN_ADDR")
+        .unwrap_or_else(|_| "0.0.0.0:8443".to_string());
+    let internal_endpoints = parse_endpoints("INTERNAL_ENDPOINTS", total_nodes, |id| {
+        format!("https://mpc-node-{}:8443", id)
+    });
+
+    let ca_cert_path = std::env::var("CA_CERT_PATH")
+        .unwrap_or_else(|_| "/certs/ca.crt".to_string());
+    let node_cert_path = std::env::var("NODE_CERT_PATH")
+        .unwrap_or_else(|_| format!("/certs/node{}.crt", node_id));
+    let node_key_path = std::env::var("NODE_KEY_PATH")
+        .unwrap_or_else(|_| format!("/certs/node{}.key", node_id));
 
     // QUIC/mTLS configuration
     let quic_listen_addr = std::env::var("QUIC_LISTEN_ADDR")
@@ -506,12 +752,105 @@ fn load_config() -> Result<Config> {
         bitcoin_network,
         enable_orchestration,
         node_endpoints,
+        rate_limit_shared,
+        idempotency_ttl_hours,
+        events_retention_hours,
+        audit_key_path,
+        audit_checkpoint_interval_secs,
+        internal_listen_addr,
+        internal_endpoints,
+        ca_cert_path,
+        node_cert_path,
+        node_key_path,
         quic_listen_addr,
         quic_port,
         registry_url,
     })
 }
 
+/// Load this node's audit signing key from `AUDIT_KEY_PATH`
+///
+/// A missing key is only tolerated in DEV_MODE, where a random key is used;
+/// entries it signs stop verifying once the node restarts.
+fn load_audit_signer(config: &Config) -> Result<AuditSigner> {
+    let node_id = threshold_types::NodeId(config.node_id);
+    let path = std::path::Path::new(&config.audit_key_path);
+    if path.exists() {
+        return Ok(AuditSigner::from_pem_file(node_id, path)?);
+    }
+
+    let dev_mode = std::env::var("DEV_MODE")
+        .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+        .unwrap_or(false);
+    if !dev_mode {
+        anyhow::bail!(
+            "Audit key {} not found (generate one with scripts/generate-audit-keys.sh)",
+            config.audit_key_path
+        );
+    }
+
+    warn!(
+        "Audit key {} not found, using an ephemeral key (DEV_MODE)",
+        config.audit_key_path
+    );
+    Ok(AuditSigner::generate(node_id))
+}
+
+/// Create the first admin account from `BOOTSTRAP_ADMIN_PASSWORD`, or from the
+/// file named by `BOOTSTRAP_ADMIN_PASSWORD_FILE` (e.g. a mounted secret)
+///
+/// The user ID is `BOOTSTRAP_ADMIN_USER` (default "admin"). Nothing happens
+/// when neither variable is set, and the password is ignored once any active
+/// admin exists, so it can stay configured after the first start.
+async fn bootstrap_admin(postgres: &PostgresStorage) -> Result<()> {
+    let password = match std::env::var("BOOTSTRAP_ADMIN_PASSWORD") {
+        Ok(password) => password,
+        Err(_) => match std::env::var("BOOTSTRAP_ADMIN_PASSWORD_FILE") {
+            Ok(path) => std::fs::read_to_string(&path)
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))?
+                .trim_end()
+                .to_string(),
+            Err(_) => return Ok(()),
+        },
+    };
+    if password.len() < 12 {
+        anyhow::bail!("The bootstrap admin password must be at least 12 characters");
+    }
+
+    let user_id = std::env::var("BOOTSTRAP_ADMIN_USER").unwrap_or_else(|_| "admin".to_string());
+    let hash = tokio::task::spawn_blocking(move || bcrypt::hash(password, bcrypt::DEFAULT_COST)).await??;
+
+    if !postgres.bootstrap_admin(&user_id, &hash).await? {
+        info!("An active admin exists, ignoring BOOTSTRAP_ADMIN_PASSWORD");
+    }
+    Ok(())
+}
+
+/// Parse `id=url` pairs separated by `;` from `var`, defaulting to one
+/// `default_url(id)` per node when the variable is unset or empty
+fn parse_endpoints(var: &str, total_nodes: u32, default_url: impl Fn(u32) -> String) -> Vec<(u64, String)> {
+    let endpoints_str = std::env::var(var).unwrap_or_default();
+    if !endpoints_str.is_empty() {
+        endpoints_str
+            .split(';')
+            .filter_map(|entry| {
+                let parts: Vec<&str> = entry.split('=').collect();
+                if parts.len() == 2 {
+                    if let Ok(id) = parts[0].parse::<u64>() {
+                        return Some((id, parts[1].to_string()));
+                    }
+                }
+                None
+            })
+            .collect()
+    } else {
+        // Default: generate endpoints for all nodes
+        (1..=total_nodes)
+            .map(|id| (id as u64, default_url(id)))
+            .collect()
+    }
+}
+
 fn mask_password(url: &str) -> String {
     if let Some(at_pos) = url.rfind('@') {
         if let Some(colon_pos) = url[..at_pos].rfind(':') {
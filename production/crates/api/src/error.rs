@@ -31,6 +31,12 @@ pub enum ApiError {
 
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
 }
 
 impl ApiError {
@@ -43,6 +49,8 @@ impl ApiError {
             ApiError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
             ApiError::Conflict(_) => StatusCode::CONFLICT,
             ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 
@@ -55,6 +63,8 @@ impl ApiError {
             ApiError::ServiceUnavailable(_) => "service_unavailable",
             ApiError::Conflict(_) => "conflict",
             ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::Forbidden(_) => "forbidden",
+            ApiError::TooManyRequests(_) => "rate_limited",
         }
     }
 }
@@ -95,12 +105,19 @@ impl From<ThresholdError> for ApiError {
             ThresholdError::Timeout(msg) => ApiError::ServiceUnavailable(msg),
             ThresholdError::ConfigError(msg) => ApiError::InternalError(msg),
             ThresholdError::CryptoError(msg) => ApiError::InternalError(msg),
+            err @ ThresholdError::SchemaDrift { .. } => ApiError::InternalError(err.to_string()),
             ThresholdError::NodeBanned { peer_id } => {
                 ApiError::Unauthorized(format!("Node banned: {}", peer_id))
             }
             ThresholdError::TransactionAlreadyProcessed { tx_id } => {
                 ApiError::Conflict(format!("Transaction already processed: {}", tx_id))
             }
+            err @ (ThresholdError::InvalidStateTransition { .. }
+            | ThresholdError::StateConflict { .. }
+            | ThresholdError::StaleFencingToken { .. }
+            | ThresholdError::UtxoReserved { .. }) => ApiError::Conflict(err.to_string()),
+            err @ (ThresholdError::InsufficientBalance { .. }
+            | ThresholdError::ApiKeyLimitExceeded { .. }) => ApiError::Forbidden(err.to_string()),
             ThresholdError::Other(err) => ApiError::InternalError(err.to_string()),
         }
     }
@@ -1,7 +1,6 @@
 //! Cluster monitoring business logic handlers
 
 use threshold_storage::{EtcdStorage, PostgresStorage};
-use tokio::sync::Mutex;
 use tracing::info;
 
 use crate::{error::ApiError, routes::cluster::NodeInfo};
@@ -17,33 +16,30 @@ pub struct ClusterStatus {
 /// Get overall cluster health status
 pub async fn get_cluster_status(
     postgres: &PostgresStorage,
-    etcd: &Mutex<EtcdStorage>,
+    etcd: &EtcdStorage,
 ) -> Result<ClusterStatus, ApiError> {
     use threshold_types::NodeId;
 
     info!("Fetching cluster status");
 
     // Step 1: Get cluster configuration from etcd (with fallback to defaults)
-    let (total_nodes, threshold) = {
-        let mut etcd_client = etcd.lock().await;
-        match etcd_client.get("/cluster/config").await {
-            Ok(Some(config_bytes)) => {
-                match String::from_utf8(config_bytes) {
-                    Ok(config_str) => {
-                        match serde_json::from_str::<serde_json::Value>(&config_str) {
-                            Ok(config) => {
-                                let total = config["total_nodes"].as_u64().unwrap_or(5) as u32;
-                                let thresh = config["threshold"].as_u64().unwrap_or(3) as u32;
-                                (total, thresh)
-                            }
-                            Err(_) => (5, 3), // Default configuration
+    let (total_nodes, threshold) = match etcd.get("/cluster/config").await {
+        Ok(Some(config_bytes)) => {
+            match String::from_utf8(config_bytes) {
+                Ok(config_str) => {
+                    match serde_json::from_str::<serde_json::Value>(&config_str) {
+                        Ok(config) => {
+                            let total = config["total_nodes"].as_u64().unwrap_or(5) as u32;
+                            let thresh = config["threshold"].as_u64().unwrap_or(3) as u32;
+                            (total, thresh)
                         }
+                        Err(_) => (5, 3), // Default configuration
                     }
-                    Err(_) => (5, 3), // Failed to parse UTF-8
                 }
+                Err(_) => (5, 3), // Failed to parse UTF-8
             }
-            Ok(None) | Err(_) => (5, 3), // Default if not set in etcd or error
         }
+        Ok(None) | Err(_) => (5, 3), // Default if not set in etcd or error
     };
 
     // Step 2: Query actual node health from PostgreSQL
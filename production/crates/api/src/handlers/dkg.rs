@@ -43,6 +43,10 @@ pub struct DkgResponse {
     pub public_key: String,
     /// Derived Bitcoin address
     pub address: String,
+    /// Root BIP32 extended public key for watch-only wallets
+    pub xpub: Option<String>,
+    /// BIP-380 output descriptor for `address`
+    pub descriptor: Option<String>,
     /// Threshold
     pub threshold: u32,
     /// Total nodes
@@ -64,6 +68,10 @@ pub struct DkgStatusResponse {
     pub cggmp24_address: Option<String>,
     /// FROST address (if available)
     pub frost_address: Option<String>,
+    /// CGGMP24 root xpub (if available)
+    pub cggmp24_xpub: Option<String>,
+    /// FROST root xpub (if available)
+    pub frost_xpub: Option<String>,
 }
 
 /// Initiate a new DKG ceremony
@@ -118,6 +126,8 @@ pub async fn initiate_dkg(
         protocol: result.protocol.to_string(),
         public_key: public_key_hex,
         address,
+        xpub: result.xpub,
+        descriptor: result.descriptor,
         threshold: result.threshold,
         total_nodes: result.total_nodes,
     };
@@ -155,12 +165,12 @@ pub async fn dkg_status(
                 && matches!(c.status, threshold_orchestrator::DkgStatus::Completed))
         .max_by_key(|c| c.completed_at);
 
-    let (cggmp24_public_key, cggmp24_address) = if let Some(ceremony) = cggmp24_ceremony {
+    let (cggmp24_public_key, cggmp24_address, cggmp24_xpub) = if let Some(ceremony) = cggmp24_ceremony {
         let pubkey_hex = ceremony.public_key.as_ref().map(|pk| hex::encode(pk));
         let addr = ceremony.address.clone();
-        (pubkey_hex, addr)
+        (pubkey_hex, addr, ceremony.xpub.clone())
     } else {
-        (None, None)
+        (None, None, None)
     };
 
     // Get latest FROST ceremony
@@ -170,12 +180,12 @@ pub async fn dkg_status(
                 && matches!(c.status, threshold_orchestrator::DkgStatus::Completed))
         .max_by_key(|c| c.completed_at);
 
-    let (frost_public_key, frost_address) = if let Some(ceremony) = frost_ceremony {
+    let (frost_public_key, frost_address, frost_xpub) = if let Some(ceremony) = frost_ceremony {
         let pubkey_hex = ceremony.public_key.as_ref().map(|pk| hex::encode(pk));
         let addr = ceremony.address.clone();
-        (pubkey_hex, addr)
+        (pubkey_hex, addr, ceremony.xpub.clone())
     } else {
-        (None, None)
+        (None, None, None)
     };
 
     let response = DkgStatusResponse {
@@ -185,6 +195,8 @@ pub async fn dkg_status(
         cggmp24_address,
         frost_public_key,
         frost_address,
+        cggmp24_xpub,
+        frost_xpub,
     };
 
     Ok(Json(response))
@@ -217,6 +229,8 @@ pub async fn join_dkg(
         protocol: result.protocol.to_string(),
         public_key: public_key_hex,
         address: result.address,
+        xpub: result.xpub,
+        descriptor: result.descriptor,
         threshold: result.threshold,
         total_nodes: result.total_nodes,
     };
@@ -1,15 +1,38 @@
 //! Internal API handlers for node-to-node communication
+//!
+//! These handlers are only reachable on the mTLS listener (see
+//! `internal_server`). Each request carries the coordinator's node ID, which
+//! must match the node ID of the client certificate that sent it.
 
 use crate::error::ApiError;
+use crate::internal_server::PeerNodeId;
 use crate::state::AppState;
-use axum::{extract::State, Json};
-use threshold_types::VoteRequest;
-use tracing::info;
+use axum::{extract::State, Extension, Json};
+use protocols::frost::preprocessing::FrostPartialSignature;
+use threshold_orchestrator::{FrostNonceDiscardRequest, FrostNonceSignRequest, SignatureProtocol};
+use threshold_types::{TxId, VoteRequest};
+use tracing::{info, warn};
 use serde::{Deserialize, Serialize};
 
+/// Reject a request whose claimed coordinator is not the authenticated caller
+fn ensure_caller(peer: PeerNodeId, coordinator_id: u64) -> Result<(), ApiError> {
+    if peer.0 != coordinator_id {
+        warn!(
+            "Internal request from node {} claims coordinator node {}",
+            peer.0, coordinator_id
+        );
+        return Err(ApiError::Forbidden(format!(
+            "caller node {} does not match coordinator node {}",
+            peer.0, coordinator_id
+        )));
+    }
+    Ok(())
+}
+
 /// DKG join request from coordinator
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DkgJoinRequest {
+    pub coordinator_id: u64,
     pub session_id: String,
     pub protocol: String,
     pub threshold: u32,
@@ -21,9 +44,11 @@ pub struct DkgJoinRequest {
 /// POST /internal/vote-request
 pub async fn receive_vote_request(
     State(state): State<AppState>,
+    Extension(peer): Extension<PeerNodeId>,
     Json(req): Json<VoteRequest>,
 ) -> Result<Json<&'static str>, ApiError> {
-    info!("Received vote request for tx_id={}", req.tx_id);
+    ensure_caller(peer, req.coordinator_id.0)?;
+    info!("Received vote request for tx_id={} from node {}", req.tx_id, peer.0);
 
     // Trigger automatic voting mechanism
     state
@@ -40,8 +65,10 @@ pub async fn receive_vote_request(
 /// POST /internal/dkg-join
 pub async fn receive_dkg_join_request(
     State(state): State<AppState>,
+    Extension(peer): Extension<PeerNodeId>,
     Json(req): Json<DkgJoinRequest>,
 ) -> Result<Json<&'static str>, ApiError> {
+    ensure_caller(peer, req.coordinator_id)?;
     info!(
         "Received DKG join request for session_id={} protocol={}",
         req.session_id, req.protocol
@@ -72,6 +99,7 @@ pub async fn receive_dkg_join_request(
 /// Aux_info join request from coordinator
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuxInfoJoinRequest {
+    pub coordinator_id: u64,
     pub session_id: String,
     pub num_parties: u16,
 }
@@ -83,8 +111,10 @@ pub struct AuxInfoJoinRequest {
 /// This fixes SORUN #15 by allowing participant nodes to join aux_info ceremonies.
 pub async fn receive_aux_info_join_request(
     State(state): State<AppState>,
+    Extension(peer): Extension<PeerNodeId>,
     Json(req): Json<AuxInfoJoinRequest>,
 ) -> Result<Json<&'static str>, ApiError> {
+    ensure_caller(peer, req.coordinator_id)?;
     info!(
         "Received aux_info join request for session_id={} num_parties={}",
         req.session_id, req.num_parties
@@ -115,6 +145,7 @@ pub async fn receive_aux_info_join_request(
 /// Presignature join request from coordinator
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PresigJoinRequest {
+    pub coordinator_id: u64,
     pub session_id: String,
     pub participants: Vec<u64>, // Node IDs
 }
@@ -131,8 +162,16 @@ pub struct PresigJoinRequest {
 /// AttemptToOverwriteReceivedMsg errors from concurrent session handling.
 pub async fn receive_presig_join_request(
     State(state): State<AppState>,
+    Extension(peer): Extension<PeerNodeId>,
     Json(req): Json<PresigJoinRequest>,
 ) -> Result<Json<&'static str>, ApiError> {
+    ensure_caller(peer, req.coordinator_id)?;
+    if !req.participants.contains(&peer.0) {
+        return Err(ApiError::Forbidden(format!(
+            "coordinator node {} is not a participant of the session",
+            peer.0
+        )));
+    }
     info!(
         "Received presig join request for session_id={} participants={:?}",
         req.session_id, req.participants
@@ -193,11 +232,15 @@ pub async fn receive_presig_join_request(
 /// Signing join request from coordinator
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SigningJoinRequest {
+    pub coordinator_id: u64,
     pub session_id: String,
     pub tx_id: String,
     pub protocol: String,
     pub unsigned_tx: Vec<u8>,
     pub message_hash: Vec<u8>,
+    /// Non-hardened BIP32 path of the signing key (empty for the root key)
+    #[serde(default)]
+    pub derivation_path: Vec<u32>,
 }
 
 /// Receive a signing join request from coordinator
@@ -205,15 +248,37 @@ pub struct SigningJoinRequest {
 /// POST /internal/signing-join
 ///
 /// This fixes SORUN #17 by allowing participant nodes to join signing ceremonies.
+/// The signing path must be the one stored with the transaction. CGGMP24
+/// sessions are only joined for the root key, since this node has no way to
+/// sign with a tweaked CGGMP24 share.
 pub async fn receive_signing_join_request(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    Extension(peer): Extension<PeerNodeId>,
     Json(req): Json<SigningJoinRequest>,
 ) -> Result<Json<&'static str>, ApiError> {
+    ensure_caller(peer, req.coordinator_id)?;
     info!(
-        "Received signing join request for session_id={} tx_id={} protocol={}",
-        req.session_id, req.tx_id, req.protocol
+        "Received signing join request for session_id={} tx_id={} protocol={} path={:?}",
+        req.session_id, req.tx_id, req.protocol, req.derivation_path
     );
 
+    // Only sign for the key that owns the transaction's inputs
+    let tx_id = TxId::from(req.tx_id.as_str());
+    let stored_path = state.postgres.get_transaction_derivation_path(&tx_id).await?;
+    if stored_path != req.derivation_path {
+        return Err(ApiError::Forbidden(format!(
+            "derivation path {:?} does not match path {:?} of transaction {}",
+            req.derivation_path, stored_path, tx_id
+        )));
+    }
+
+    if req.protocol == SignatureProtocol::CGGMP24.to_string() && !req.derivation_path.is_empty() {
+        return Err(ApiError::BadRequest(format!(
+            "CGGMP24 signing at derivation path {:?} is not supported",
+            req.derivation_path
+        )));
+    }
+
     // For signing, we don't need to spawn a join task like DKG/aux_info
     // The signing coordinator will handle the protocol via QUIC messages
     // This HTTP request just ensures all nodes are "aware" of the signing session
@@ -227,6 +292,74 @@ pub async fn receive_signing_join_request(
     Ok(Json("Signing join request received"))
 }
 
+/// Produce this node's share for a single-round FROST signing session
+///
+/// POST /internal/frost-nonce-sign
+///
+/// The coordinator has already claimed one of this node's published nonce
+/// commitments; the nonce is consumed exactly once before the share is returned.
+/// Shares are only produced for the sighash of a transaction that is approved
+/// for signing.
+pub async fn receive_frost_nonce_sign_request(
+    State(state): State<AppState>,
+    Extension(peer): Extension<PeerNodeId>,
+    Json(req): Json<FrostNonceSignRequest>,
+) -> Result<Json<FrostPartialSignature>, ApiError> {
+    ensure_caller(peer, req.coordinator_id.0)?;
+    // The coordinator always signs too; commitments use 0-based party indices
+    if !req.commitments.iter().any(|c| u64::from(c.party_index) + 1 == peer.0) {
+        return Err(ApiError::Forbidden(format!(
+            "coordinator node {} is not a signer of the session",
+            peer.0
+        )));
+    }
+    info!(
+        "Received FROST nonce sign request for session_id={} tx_id={} nonce_id={}",
+        req.session_id, req.tx_id, req.nonce_id
+    );
+
+    let share = state
+        .frost_nonce_service
+        .sign_share(
+            &req.session_id.to_string(),
+            &req.tx_id,
+            req.nonce_id,
+            &req.message_hash,
+            &req.commitments,
+            &req.derivation_path,
+        )
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("FROST signing failed: {}", e)))?;
+
+    Ok(Json(share))
+}
+
+/// Wipe a nonce claimed for a preprocessed FROST session that failed
+///
+/// POST /internal/frost-nonce-discard
+///
+/// Sent by the coordinator when this node produced no share for the session.
+/// A nonce that was already consumed is left untouched.
+pub async fn receive_frost_nonce_discard_request(
+    State(state): State<AppState>,
+    Extension(peer): Extension<PeerNodeId>,
+    Json(req): Json<FrostNonceDiscardRequest>,
+) -> Result<Json<bool>, ApiError> {
+    ensure_caller(peer, req.coordinator_id.0)?;
+    info!(
+        "Received FROST nonce discard request for session_id={} nonce_id={}",
+        req.session_id, req.nonce_id
+    );
+
+    let discarded = state
+        .frost_nonce_service
+        .discard_nonce(req.nonce_id, &req.session_id.to_string())
+        .await
+        .map_err(|e| ApiError::InternalError(format!("FROST nonce discard failed: {}", e)))?;
+
+    Ok(Json(discarded))
+}
+
 /// Response for aux-ready check
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuxReadyResponse {
@@ -249,3 +382,17 @@ pub async fn check_aux_ready(
         node_id: state.node_id.0,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ensure_caller() {
+        assert!(ensure_caller(PeerNodeId(2), 2).is_ok());
+        assert!(matches!(
+            ensure_caller(PeerNodeId(2), 3),
+            Err(ApiError::Forbidden(_))
+        ));
+    }
+}
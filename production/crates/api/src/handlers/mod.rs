@@ -6,4 +6,5 @@ pub mod wallet;
 pub mod dkg;
 pub mod aux_info;
 pub mod presig;
+pub mod user_addresses;
 pub mod internal;
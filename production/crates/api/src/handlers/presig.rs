@@ -7,14 +7,17 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
+use threshold_orchestrator::{FrostNoncePoolStats, ThrottleReason};
 
 /// Presignature pool status response
 #[derive(Debug, Serialize)]
 pub struct PresigStatusResponse {
     /// Current number of available presignatures
     pub current_size: usize,
-    /// Target pool size
+    /// Target pool size (adaptive, follows forecast demand)
     pub target_size: usize,
+    /// Pool size below which refill is triggered
+    pub min_size: usize,
     /// Maximum pool size
     pub max_size: usize,
     /// Pool utilization percentage (0-100)
@@ -25,10 +28,16 @@ pub struct PresigStatusResponse {
     pub is_critical: bool,
     /// Number of presignatures used in the last hour
     pub hourly_usage: usize,
+    /// Forecast presignature demand for the upcoming hour
+    pub forecast_per_hour: f64,
+    /// Why generation is currently paused, if it is
+    pub throttled: Option<ThrottleReason>,
     /// Total presignatures generated
     pub total_generated: u64,
     /// Total presignatures used
     pub total_used: u64,
+    /// FROST preprocessed nonce pool on this node (single-round Taproot signing)
+    pub frost_nonce_pool: FrostNoncePoolStats,
 }
 
 /// Request to manually generate presignatures
@@ -57,17 +66,22 @@ pub async fn presignature_status(
 ) -> Result<Json<PresigStatusResponse>, ApiError> {
     // Query actual presignature pool status
     let stats = state.presig_service.get_stats().await;
+    let frost_nonce_pool = state.frost_nonce_service.get_stats().await;
 
     let response = PresigStatusResponse {
         current_size: stats.current_size,
         target_size: stats.target_size,
+        min_size: stats.min_size,
         max_size: stats.max_size,
         utilization: stats.utilization,
         is_healthy: stats.is_healthy(),
         is_critical: stats.is_critical(),
         hourly_usage: stats.hourly_usage,
+        forecast_per_hour: stats.forecast_per_hour,
+        throttled: stats.throttled,
         total_generated: stats.total_generated,
         total_used: stats.total_used,
+        frost_nonce_pool,
     };
 
     Ok(Json(response))
@@ -132,16 +146,28 @@ mod tests {
         let status = PresigStatusResponse {
             current_size: 75,
             target_size: 100,
+            min_size: 20,
             max_size: 150,
             utilization: 75.0,
             is_healthy: true,
             is_critical: false,
             hourly_usage: 12,
+            forecast_per_hour: 12.0,
+            throttled: None,
             total_generated: 500,
             total_used: 425,
+            frost_nonce_pool: FrostNoncePoolStats {
+                current_size: 40,
+                target_size: 50,
+                min_size: 10,
+                total_generated: 60,
+                total_consumed: 20,
+                total_expired: 0,
+            },
         };
 
         assert!(status.is_healthy);
         assert!(!status.is_critical);
+        assert!(status.frost_nonce_pool.is_healthy());
     }
 }
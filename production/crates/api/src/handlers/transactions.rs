@@ -1,20 +1,51 @@
 //! Transaction business logic handlers
 
 use chrono::Utc;
-use threshold_bitcoin::{BitcoinClient, TransactionBuilder, Utxo, UtxoStatus};
-use threshold_storage::PostgresStorage;
-use threshold_types::{Transaction, TransactionState, TxId};
+use std::str::FromStr;
+use threshold_bitcoin::{BitcoinClient, TransactionBuilder, TxBuilderError, Utxo};
+use threshold_storage::{IdempotentCompletion, PostgresStorage, TransactionOrigin};
+use threshold_types::{Error as ThresholdError, Transaction, TransactionState, TxId};
 use tracing::{error, info, warn};
 
 use crate::error::ApiError;
 
+/// Attempts at selecting coins when a concurrent transaction reserves one
+/// of the selected UTXOs first
+const MAX_SELECTION_ATTEMPTS: usize = 3;
+
+/// Builds the response recorded under a request's idempotency key from the
+/// transaction about to be stored
+pub type IdempotentResponder = dyn Fn(&Transaction) -> Result<IdempotentCompletion, ApiError> + Sync;
+
+/// Address whose UTXOs a new transaction may spend
+#[derive(Debug, Clone)]
+pub struct SpendSource {
+    /// P2WPKH address holding the UTXOs; change is paid back to it
+    pub address: String,
+    /// Non-hardened BIP32 path of the address key below the root key
+    /// (empty for the wallet root address)
+    pub derivation_path: Vec<u32>,
+}
+
 /// Create a new Bitcoin transaction with optional OP_RETURN metadata
+///
+/// The inputs all come from the first of `sources` whose unreserved UTXOs
+/// cover the amount and fee, and the source's path is stored as the
+/// transaction's signing path (overriding `origin.derivation_path`). Coin
+/// selection skips UTXOs reserved by unfinished transactions, and the
+/// selected inputs are reserved together with the new transaction, as is
+/// the owner's ledger debit when `origin` asks for one. `idempotent_response`
+/// turns the built transaction into the response stored under the request's
+/// idempotency key in the same database transaction.
 pub async fn create_transaction(
     postgres: &PostgresStorage,
     bitcoin: &BitcoinClient,
+    sources: &[SpendSource],
     recipient: &str,
     amount_sats: u64,
     metadata: Option<&str>,
+    origin: &TransactionOrigin,
+    idempotent_response: Option<&IdempotentResponder>,
 ) -> Result<Transaction, ApiError> {
     info!(
         "Creating transaction: recipient={} amount={} metadata={:?}",
@@ -36,41 +67,107 @@ pub async fn create_transaction(
 
     info!("Using fee rate: {} sat/vB", fee_rate);
 
-    // For demo/testing: Create a realistic unsigned Bitcoin transaction
-    // In production with a real wallet, we would call bitcoin.get_utxos(wallet_address).await
-    // Since we don't have a funded wallet, we create a demo transaction with realistic structure
-
-    // Demo UTXO (simulating a previous transaction output)
-    // In production, this would come from: bitcoin.get_utxos(wallet_address).await
-    let demo_utxo = Utxo {
-        txid: "0000000000000000000000000000000000000000000000000000000000000001".to_string(),
-        vout: 0,
-        value: amount_sats + 10_000, // Enough to cover amount + fees
-        status: UtxoStatus {
-            confirmed: true,
-            block_height: Some(2_500_000),
-        },
-    };
+    let mut attempt = 1;
+    loop {
+        let (source, tx, inputs) =
+            select_coins(postgres, bitcoin, sources, fee_rate, recipient, amount_sats, metadata).await?;
+        let origin = TransactionOrigin {
+            derivation_path: source.derivation_path.clone(),
+            idempotency: idempotent_response.map(|respond| respond(&tx)).transpose()?,
+            ..origin.clone()
+        };
 
-    warn!(
-        "Using demo UTXO for development. In production, fetch real UTXOs from wallet address."
-    );
+        match postgres.create_owned_transaction(&tx, &inputs, &origin).await {
+            Ok(id) => {
+                info!("Transaction created successfully: id={} txid={}", id, tx.txid);
+                return Ok(Transaction { id, ..tx });
+            }
+            Err(ThresholdError::UtxoReserved { outpoint }) if attempt < MAX_SELECTION_ATTEMPTS => {
+                warn!(
+                    "UTXO {} was reserved concurrently, selecting coins again (attempt {})",
+                    outpoint, attempt
+                );
+                attempt += 1;
+            }
+            Err(e) => {
+                error!("Failed to store transaction in PostgreSQL: {:?}", e);
+                return Err(ApiError::from(e));
+            }
+        }
+    }
+}
 
-    // Demo change address (in production, this would be a real address from the MPC wallet)
-    let change_address = "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx".to_string(); // Demo testnet address
+/// Build the transaction from the first source that can fund it
+async fn select_coins<'a>(
+    postgres: &PostgresStorage,
+    bitcoin: &BitcoinClient,
+    sources: &'a [SpendSource],
+    fee_rate: u64,
+    recipient: &str,
+    amount_sats: u64,
+    metadata: Option<&str>,
+) -> Result<(&'a SpendSource, Transaction, Vec<(String, u32, u64)>), ApiError> {
+    let mut last_error = ApiError::Conflict("No addresses to spend from".to_string());
 
-    // Demo script pubkey for P2WPKH
-    // For P2WPKH, we need the actual P2WPKH script pubkey from the address
-    // Format: OP_0 <20-byte-pubkey-hash>
-    // tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx decodes to:
-    // Version 0 + pubkey hash 751e76e8199196d454941c45d1b3a323f1433bd6
-    let sender_script_pubkey = hex::decode("0014751e76e8199196d454941c45d1b3a323f1433bd6")
-        .expect("Valid P2WPKH script pubkey");
+    for source in sources {
+        let utxos = unreserved_utxos(postgres, source_utxos(bitcoin, source).await?).await?;
+        match build_transaction(source, utxos, fee_rate, recipient, amount_sats, metadata) {
+            Ok((tx, inputs)) => return Ok((source, tx, inputs)),
+            Err(e @ ApiError::Conflict(_)) => {
+                info!("{} cannot fund the transaction: {}", source.address, e);
+                last_error = e;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(last_error)
+}
+
+/// UTXOs held by a spend source
+async fn source_utxos(bitcoin: &BitcoinClient, source: &SpendSource) -> Result<Vec<Utxo>, ApiError> {
+    bitcoin.get_utxos(&source.address).await.map_err(|e| {
+        ApiError::ServiceUnavailable(format!("Failed to fetch UTXOs of {}: {}", source.address, e))
+    })
+}
+
+/// Drop UTXOs already reserved by unfinished transactions
+async fn unreserved_utxos(postgres: &PostgresStorage, utxos: Vec<Utxo>) -> Result<Vec<Utxo>, ApiError> {
+    let outpoints: Vec<(String, u32)> = utxos.iter().map(|u| (u.txid.clone(), u.vout)).collect();
+    let reserved = postgres.reserved_outpoints(&outpoints).await?;
+
+    if !reserved.is_empty() {
+        info!("Skipping {} reserved UTXOs", reserved.len());
+    }
+
+    Ok(utxos
+        .into_iter()
+        .filter(|u| !reserved.contains(&(u.txid.clone(), u.vout)))
+        .collect())
+}
+
+/// Build the unsigned transaction record and the inputs it spends
+///
+/// Change goes back to the source address, whose script is also the one
+/// the inputs are signed for.
+fn build_transaction(
+    source: &SpendSource,
+    utxos: Vec<Utxo>,
+    fee_rate: u64,
+    recipient: &str,
+    amount_sats: u64,
+    metadata: Option<&str>,
+) -> Result<(Transaction, Vec<(String, u32, u64)>), ApiError> {
+    let sender_script_pubkey = bitcoin::Address::from_str(&source.address)
+        .map_err(|e| ApiError::InternalError(format!("Invalid source address {}: {}", source.address, e)))?
+        .assume_checked()
+        .script_pubkey()
+        .to_bytes();
 
     // Build unsigned Bitcoin transaction using TransactionBuilder
     let mut builder = TransactionBuilder::new(
-        vec![demo_utxo],
-        change_address,
+        utxos,
+        source.address.clone(),
         sender_script_pubkey,
         fee_rate,
     );
@@ -89,9 +186,14 @@ pub async fn create_transaction(
     // Build unsigned transaction (P2WPKH/SegWit)
     let unsigned_transaction = builder
         .build_p2wpkh()
-        .map_err(|e| {
-            error!("Failed to build Bitcoin transaction: {}", e);
-            ApiError::InternalError(format!("Failed to build transaction: {}", e))
+        .map_err(|e| match e {
+            TxBuilderError::NoUtxos | TxBuilderError::InsufficientFunds { .. } => {
+                ApiError::Conflict(format!("Not enough unreserved funds: {}", e))
+            }
+            e => {
+                error!("Failed to build Bitcoin transaction: {}", e);
+                ApiError::InternalError(format!("Failed to build transaction: {}", e))
+            }
         })?;
 
     info!(
@@ -120,10 +222,16 @@ pub async fn create_transaction(
 
     info!("Generated transaction ID: {}", txid_hex);
 
+    let inputs = unsigned_transaction
+        .inputs
+        .iter()
+        .map(|input| (input.txid.clone(), input.vout, input.value))
+        .collect();
+
     // Create transaction record with REAL unsigned Bitcoin transaction
     let tx = Transaction {
         id: 0, // Will be set by database
-        txid: txid.clone(),
+        txid,
         state: TransactionState::Pending,
         unsigned_tx: tx_bytes, // Real Bitcoin transaction bytes
         signed_tx: None,
@@ -135,17 +243,7 @@ pub async fn create_transaction(
         updated_at: Utc::now(),
     };
 
-    // Store transaction in database
-    match postgres.create_transaction(&tx).await {
-        Ok(id) => {
-            info!("Transaction created successfully: id={} txid={}", id, txid);
-            Ok(Transaction { id, ..tx })
-        }
-        Err(e) => {
-            error!("Failed to store transaction in PostgreSQL: {:?}", e);
-            Err(ApiError::from(e))
-        }
-    }
+    Ok((tx, inputs))
 }
 
 /// List all transactions from the database
@@ -0,0 +1,165 @@
+//! Per-user HD deposit address derivation
+//!
+//! Every user address is derived non-hardened from the root xpub exported
+//! at DKG: `root/0/index`, where `index` comes from the global
+//! `get_next_derivation_index()` counter so no two users ever share a key.
+//! P2WPKH addresses use the CGGMP24 group key and P2TR addresses the FROST
+//! group key. The stored path (`m/0/index`) is relative to the root key.
+//!
+//! Deposits to these addresses are credited to the owner's ledger balance,
+//! and withdrawals are paid from the wallet root address: CGGMP24 cannot sign
+//! with a derived key yet, and there is no P2TR transaction builder, so only
+//! P2WPKH addresses are handed out.
+
+use common::{derive_bitcoin_address_taproot, DerivedAddress, ExtendedPubKey};
+use serde::{Deserialize, Serialize};
+use threshold_orchestrator::{DkgStatus, ProtocolType};
+
+use crate::{error::ApiError, state::AppState};
+
+/// Branch used for deposit (receiving) addresses
+const RECEIVING_BRANCH: u32 = 0;
+
+/// Address types a user can request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserAddressType {
+    /// Native SegWit, signed with the CGGMP24 key
+    #[default]
+    P2wpkh,
+    /// Taproot key path, signed with the FROST key (not offered yet)
+    P2tr,
+}
+
+impl UserAddressType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserAddressType::P2wpkh => "p2wpkh",
+            UserAddressType::P2tr => "p2tr",
+        }
+    }
+
+    /// Whether new addresses of this type may be handed out
+    pub fn is_offered(&self) -> bool {
+        matches!(self, UserAddressType::P2wpkh)
+    }
+
+    /// DKG protocol whose group key backs this address type
+    pub fn protocol(&self) -> ProtocolType {
+        match self {
+            UserAddressType::P2wpkh => ProtocolType::CGGMP24,
+            UserAddressType::P2tr => ProtocolType::FROST,
+        }
+    }
+}
+
+/// Derive the deposit address at `index` from a root xpub
+pub fn derive_user_address(
+    root_xpub: &str,
+    address_type: UserAddressType,
+    index: u32,
+    network: bitcoin::Network,
+) -> Result<DerivedAddress, ApiError> {
+    let root = ExtendedPubKey::from_xpub(root_xpub)
+        .map_err(|e| ApiError::InternalError(format!("Invalid root xpub: {}", e)))?;
+    let child = root
+        .derive_path(&[RECEIVING_BRANCH, index])
+        .map_err(|e| ApiError::InternalError(format!("Address derivation failed: {}", e)))?;
+
+    let address = match address_type {
+        UserAddressType::P2wpkh => child.to_address(network),
+        // Untweaked output key, matching the FROST root address
+        UserAddressType::P2tr => derive_bitcoin_address_taproot(&child.public_key, network),
+    }
+    .map_err(|e| ApiError::InternalError(format!("Address encoding failed: {}", e)))?;
+
+    Ok(DerivedAddress {
+        address,
+        public_key: hex::encode(child.public_key),
+        path: format!("m/{}/{}", RECEIVING_BRANCH, index),
+        change: RECEIVING_BRANCH,
+        index,
+    })
+}
+
+/// Root xpub of the latest completed DKG for the address type's protocol
+pub async fn root_xpub_for(
+    state: &AppState,
+    address_type: UserAddressType,
+) -> Result<String, ApiError> {
+    let protocol = address_type.protocol();
+
+    let ceremonies = state
+        .dkg_service
+        .list_ceremonies()
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to list DKG ceremonies: {}", e)))?;
+
+    ceremonies
+        .into_iter()
+        .filter(|c| c.protocol == protocol && matches!(c.status, DkgStatus::Completed))
+        .max_by_key(|c| c.completed_at)
+        .and_then(|c| c.xpub)
+        .ok_or_else(|| {
+            ApiError::ServiceUnavailable(format!(
+                "No {} key with an exported xpub. Complete a {} DKG first.",
+                address_type.as_str(),
+                protocol
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root_xpub() -> String {
+        let public_key =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        ExtendedPubKey::new(&public_key, [7u8; 32])
+            .unwrap()
+            .to_xpub(bitcoin::Network::Bitcoin)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_derive_p2wpkh_address() {
+        let derived =
+            derive_user_address(&root_xpub(), UserAddressType::P2wpkh, 5, bitcoin::Network::Testnet)
+                .unwrap();
+
+        assert!(derived.address.starts_with("tb1q"));
+        assert_eq!(derived.path, "m/0/5");
+        assert_eq!(derived.public_key.len(), 66);
+        assert_eq!(derived.index, 5);
+    }
+
+    #[test]
+    fn test_derive_p2tr_address() {
+        let derived =
+            derive_user_address(&root_xpub(), UserAddressType::P2tr, 0, bitcoin::Network::Bitcoin)
+                .unwrap();
+
+        assert!(derived.address.starts_with("bc1p"));
+        assert_eq!(derived.path, "m/0/0");
+    }
+
+    #[test]
+    fn test_only_p2wpkh_is_offered() {
+        assert!(UserAddressType::P2wpkh.is_offered());
+        assert!(!UserAddressType::P2tr.is_offered());
+    }
+
+    #[test]
+    fn test_indices_yield_distinct_keys() {
+        let xpub = root_xpub();
+        let a = derive_user_address(&xpub, UserAddressType::P2wpkh, 0, bitcoin::Network::Bitcoin)
+            .unwrap();
+        let b = derive_user_address(&xpub, UserAddressType::P2wpkh, 1, bitcoin::Network::Bitcoin)
+            .unwrap();
+
+        assert_ne!(a.address, b.address);
+        assert_ne!(a.public_key, b.public_key);
+    }
+}
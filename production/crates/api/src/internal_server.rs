@@ -0,0 +1,153 @@
+//! mTLS listener for node-to-node (`/internal`) endpoints
+//!
+//! Internal routes are not served on the public API port. They get their own
+//! listener that only completes a handshake with clients presenting a
+//! certificate signed by the cluster CA. The node ID from the client
+//! certificate's CN (`node-{id}`) is attached to every request as a
+//! [`PeerNodeId`] extension, so handlers can check it against the payload.
+
+use axum::{Extension, Router};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::service::TowerToHyperService;
+use rustls::pki_types::CertificateDer;
+use rustls::ServerConfig;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use threshold_security::{CertificateManager, ClientConfigBuilder};
+use tokio_rustls::TlsAcceptor;
+use tower_http::trace::TraceLayer;
+use tracing::{debug, info, warn};
+
+use crate::routes;
+use crate::state::AppState;
+
+/// Node ID of the caller, taken from its verified client certificate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerNodeId(pub u64);
+
+/// Create the router served on the internal listener
+pub fn create_internal_router(state: AppState) -> Router {
+    Router::new()
+        .nest("/internal", routes::internal::routes())
+        .layer(TraceLayer::new_for_http())
+        .with_state(state)
+}
+
+/// Start the internal mTLS server on the specified address
+///
+/// `tls_config` must require client certificates (see
+/// `threshold_security::ServerConfigBuilder`); connections whose certificate
+/// carries no node ID are dropped before any request is read.
+pub async fn start_internal_server(
+    state: AppState,
+    addr: SocketAddr,
+    tls_config: ServerConfig,
+    cert_manager: CertificateManager,
+) -> anyhow::Result<()> {
+    let app = create_internal_router(state);
+    let acceptor = TlsAcceptor::from(Arc::new(tls_config));
+    let cert_manager = Arc::new(cert_manager);
+
+    info!("Starting internal mTLS server on {}", addr);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+
+    loop {
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!("Failed to accept internal connection: {}", e);
+                continue;
+            }
+        };
+
+        let acceptor = acceptor.clone();
+        let cert_manager = Arc::clone(&cert_manager);
+        let app = app.clone();
+
+        tokio::spawn(async move {
+            let stream = match acceptor.accept(stream).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!("TLS handshake with {} failed: {}", remote_addr, e);
+                    return;
+                }
+            };
+
+            let peer = match peer_node_id(&cert_manager, stream.get_ref().1.peer_certificates()) {
+                Ok(peer) => peer,
+                Err(e) => {
+                    warn!("Rejecting internal connection from {}: {}", remote_addr, e);
+                    return;
+                }
+            };
+            debug!("Internal connection from node {} ({})", peer.0, remote_addr);
+
+            let service = TowerToHyperService::new(app.layer(Extension(peer)));
+            if let Err(e) = auto::Builder::new(TokioExecutor::new())
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                debug!("Internal connection from node {} closed: {}", peer.0, e);
+            }
+        });
+    }
+}
+
+/// Build the HTTP client used to call other nodes' internal endpoints
+///
+/// The client presents this node's certificate. Node certificates carry no
+/// DNS names, so hostname verification is skipped as on the QUIC mesh.
+pub fn internal_http_client(cert_manager: CertificateManager) -> anyhow::Result<reqwest::Client> {
+    let tls_config = ClientConfigBuilder::new(cert_manager)
+        .skip_hostname_verification()
+        .build_with_alpn(vec![b"http/1.1".to_vec()])?;
+
+    Ok(reqwest::Client::builder()
+        .use_preconfigured_tls(tls_config)
+        .build()?)
+}
+
+/// Extract the caller's node ID from the leaf client certificate
+fn peer_node_id(
+    cert_manager: &CertificateManager,
+    peer_certificates: Option<&[CertificateDer<'static>]>,
+) -> anyhow::Result<PeerNodeId> {
+    let leaf = peer_certificates
+        .and_then(|certs| certs.first())
+        .ok_or_else(|| anyhow::anyhow!("no client certificate presented"))?;
+
+    Ok(PeerNodeId(cert_manager.extract_node_id(leaf)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_node_id_requires_certificate() {
+        let cert_manager = CertificateManager::new("ca.crt", "node.crt", "node.key");
+
+        assert!(peer_node_id(&cert_manager, None).is_err());
+        assert!(peer_node_id(&cert_manager, Some(&[])).is_err());
+    }
+
+    #[test]
+    fn test_peer_node_id_from_cn() {
+        let mut params = rcgen::CertificateParams::new(Vec::<String>::new()).unwrap();
+        params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, "node-3");
+        let key = rcgen::KeyPair::generate().unwrap();
+        let cert = params.self_signed(&key).unwrap();
+
+        let cert_manager = CertificateManager::new("ca.crt", "node.crt", "node.key");
+        let certs = [cert.der().clone()];
+
+        assert_eq!(
+            peer_node_id(&cert_manager, Some(&certs)).unwrap(),
+            PeerNodeId(3)
+        );
+    }
+}
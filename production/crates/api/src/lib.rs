@@ -3,13 +3,18 @@
 //! Production-ready API with Axum framework providing:
 //! - Transaction management (create, get, list)
 //! - Wallet operations (balance, address)
-//! - Cluster monitoring (health, nodes)
+//! - Cluster monitoring (health, nodes) and a live server-sent events stream
+//! - JWT login (EdDSA, rotating keys published as JWKS) with admin/user role checks
+//! - Scoped, HMAC-signed API keys for machine clients
+//! - Per-identity, per-route-class rate limits with `Retry-After`
+//! - Signed webhook subscriptions with dead-letter replay
+//! - Node-to-node endpoints on a separate mTLS listener
 //! - CORS middleware for cross-origin requests
 //! - Request logging with tracing
 //! - Comprehensive error handling
 
 use axum::{
-    routing::{get, post},
+    routing::{delete, get, post, put},
     Router,
 };
 use std::net::SocketAddr;
@@ -22,13 +27,15 @@ use tracing::info;
 
 pub mod error;
 pub mod handlers;
+pub mod internal_server;
 pub mod routes;
 pub mod state;
 pub mod middleware;
 
 pub use error::{ApiError, ApiResult};
 pub use state::AppState;
-pub use middleware::{RateLimiter, RateLimitConfig};
+pub use internal_server::{internal_http_client, start_internal_server, PeerNodeId};
+pub use middleware::{RateLimiter, RateLimitConfig, RateLimitPolicy};
 
 /// Create and configure the API router with all endpoints
 pub fn create_router(state: AppState) -> Router {
@@ -39,31 +46,77 @@ pub fn create_router(state: AppState) -> Router {
         .allow_methods(Any)
         .allow_headers(Any);
 
-    // Create the main API router
-    let api_v1 = Router::new()
+    // Admin-only operations: key generation, presignatures and cluster internals
+    let admin = Router::new()
+        .route("/cluster/nodes", get(routes::cluster::list_nodes))
+        // DKG endpoints
+        .nest("/dkg", routes::dkg::routes())
+        // Aux info endpoints
+        .nest("/aux-info", routes::aux_info::routes())
+        // Presignature pool endpoints
+        .nest("/presignatures", routes::presig::routes())
+        // API keys for machine clients
+        .route("/api-keys", post(routes::api_keys::create_api_key))
+        .route("/api-keys", get(routes::api_keys::list_api_keys))
+        .route("/api-keys/:key_id", delete(routes::api_keys::revoke_api_key))
+        // Webhook subscriptions and failed deliveries
+        .route("/webhooks", post(routes::webhooks::create_webhook))
+        .route("/webhooks", get(routes::webhooks::list_webhooks))
+        .route("/webhooks/:subscription_id", delete(routes::webhooks::delete_webhook))
+        .route("/webhooks/dead-letters", get(routes::webhooks::list_dead_letters))
+        .route(
+            "/webhooks/dead-letters/:id/replay",
+            post(routes::webhooks::replay_dead_letter),
+        )
+        // Legal holds exempting transactions from retention
+        .route("/legal-holds", get(routes::legal_holds::list_legal_holds))
+        .route("/legal-holds/:txid", put(routes::legal_holds::place_legal_hold))
+        .route("/legal-holds/:txid", delete(routes::legal_holds::release_legal_hold))
+        .route_layer(axum::middleware::from_fn(middleware::require_admin));
+
+    // Endpoints available to any authenticated user; handlers scope the
+    // data to the caller's own addresses and transactions. Signed API key
+    // requests are accepted too, limited to the endpoints their scopes cover.
+    // Rate limiting runs after authentication so quotas follow the caller,
+    // not the connection.
+    let authenticated = Router::new()
         // Transaction endpoints
         .route("/transactions", post(routes::transactions::create_transaction))
         .route("/transactions", get(routes::transactions::list_transactions))
         .route("/transactions/:txid", get(routes::transactions::get_transaction))
+        .route("/transactions/:txid/history", get(routes::transactions::get_transaction_history))
+        .route("/transactions/:txid/attempts", get(routes::transactions::get_transaction_attempts))
         // Wallet endpoints
         .route("/wallet/balance", get(routes::wallet::get_balance))
         .route("/wallet/address", get(routes::wallet::get_address))
-        // Cluster endpoints
+        // Per-user deposit addresses
+        .route("/users/me/addresses", post(routes::users::create_address))
+        .route("/users/me/addresses", get(routes::users::list_addresses))
+        // Deposits credited by the deposit watcher
+        .route("/deposits", get(routes::deposits::list_deposits))
+        // Live cluster events (server-sent events)
+        .route("/events", get(routes::events::stream_events))
+        .merge(admin)
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), middleware::rate_limit))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), middleware::jwt_auth));
+
+    // Public endpoints are rate limited per client IP
+    let public = Router::new()
+        .route("/auth/login", post(routes::auth::login))
         .route("/cluster/status", get(routes::cluster::get_cluster_status))
-        .route("/cluster/nodes", get(routes::cluster::list_nodes))
-        // DKG endpoints
-        .nest("/dkg", routes::dkg::routes())
-        // Aux info endpoints
-        .nest("/aux-info", routes::aux_info::routes())
-        // Presignature pool endpoints
-        .nest("/presignatures", routes::presig::routes());
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), middleware::rate_limit));
+
+    // Create the main API router
+    let api_v1 = Router::new()
+        .merge(public)
+        .merge(authenticated);
 
     // Build the complete router with middleware
     Router::new()
         .route("/health", get(routes::health::health_check))
         .route("/metrics", get(routes::metrics::prometheus_metrics))
+        .route("/.well-known/jwks.json", get(routes::auth::jwks))
         .nest("/api/v1", api_v1)
-        .nest("/internal", routes::internal::routes())
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
@@ -80,7 +133,8 @@ pub async fn start_server(state: AppState, addr: SocketAddr) -> anyhow::Result<(
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
 
-    axum::serve(listener, app)
+    // Client addresses are needed to rate limit unauthenticated requests
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await?;
 
     Ok(())
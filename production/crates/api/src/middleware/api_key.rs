@@ -0,0 +1,326 @@
+//! HMAC-signed API key authentication for machine clients
+//!
+//! An API key request carries three headers instead of a bearer token:
+//!
+//! - `X-Api-Key`: the key ID
+//! - `X-Api-Timestamp`: Unix time in seconds
+//! - `X-Api-Signature`: hex HMAC-SHA256 of the [`signing_string`], keyed
+//!   with the hex-decoded key secret
+//!
+//! The timestamp must be within [`REPLAY_WINDOW_SECS`] of the server clock
+//! and a signature is accepted only once within that window. A key acts as
+//! the user it was issued for, but only on the endpoints its scopes cover.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{OriginalUri, Request},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+use super::auth::Claims;
+use crate::state::AppState;
+
+/// Header carrying the API key ID
+pub const API_KEY_HEADER: &str = "x-api-key";
+/// Header carrying the request timestamp (Unix seconds)
+pub const API_TIMESTAMP_HEADER: &str = "x-api-timestamp";
+/// Header carrying the hex HMAC-SHA256 request signature
+pub const API_SIGNATURE_HEADER: &str = "x-api-signature";
+
+/// Maximum clock skew, and how long a used signature is remembered
+pub const REPLAY_WINDOW_SECS: u64 = 300;
+
+/// Largest request body accepted on API key requests
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// `iss` of the [`Claims`] synthesized for API key requests
+pub const API_KEY_ISSUER: &str = "api-key";
+
+pub const SCOPE_TX_CREATE: &str = "tx:create";
+pub const SCOPE_TX_READ: &str = "tx:read";
+pub const SCOPE_WALLET_READ: &str = "wallet:read";
+
+/// Every scope an API key can be granted
+pub const ALL_SCOPES: [&str; 3] = [SCOPE_TX_CREATE, SCOPE_TX_READ, SCOPE_WALLET_READ];
+
+/// The API key that authenticated a request, available to handlers as a
+/// request extension so they can enforce its amount limits
+#[derive(Debug, Clone)]
+pub struct ApiKeyAuth {
+    pub key_id: String,
+    pub max_amount_sats: Option<u64>,
+    pub daily_limit_sats: Option<u64>,
+}
+
+/// Scope an API key needs for an endpoint; `None` if API keys may not call it
+pub fn required_scope(method: &Method, path: &str) -> Option<&'static str> {
+    match (method, path) {
+        (&Method::POST, "/api/v1/transactions") => Some(SCOPE_TX_CREATE),
+        (&Method::GET, "/api/v1/transactions") => Some(SCOPE_TX_READ),
+        (&Method::GET, p) if p.starts_with("/api/v1/transactions/") => Some(SCOPE_TX_READ),
+        (&Method::GET, "/api/v1/events") => Some(SCOPE_TX_READ),
+        (&Method::GET, "/api/v1/wallet/balance" | "/api/v1/wallet/address") => Some(SCOPE_WALLET_READ),
+        _ => None,
+    }
+}
+
+/// Canonical request representation covered by the signature
+///
+/// `METHOD\nPATH_AND_QUERY\nTIMESTAMP\nhex(sha256(body))`
+pub fn signing_string(method: &str, path_and_query: &str, timestamp: &str, body: &[u8]) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        method,
+        path_and_query,
+        timestamp,
+        hex::encode(Sha256::digest(body))
+    )
+}
+
+/// Hex HMAC-SHA256 signature of a request with a hex-encoded secret
+pub fn sign_request(
+    secret_hex: &str,
+    method: &str,
+    path_and_query: &str,
+    timestamp: &str,
+    body: &[u8],
+) -> Option<String> {
+    request_mac(secret_hex, method, path_and_query, timestamp, body)
+        .map(|mac| hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Constant-time check of a request signature
+pub fn verify_request(
+    secret_hex: &str,
+    method: &str,
+    path_and_query: &str,
+    timestamp: &str,
+    body: &[u8],
+    signature_hex: &str,
+) -> bool {
+    let (Some(mac), Ok(signature)) = (
+        request_mac(secret_hex, method, path_and_query, timestamp, body),
+        hex::decode(signature_hex),
+    ) else {
+        return false;
+    };
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn request_mac(
+    secret_hex: &str,
+    method: &str,
+    path_and_query: &str,
+    timestamp: &str,
+    body: &[u8],
+) -> Option<Hmac<Sha256>> {
+    let secret = hex::decode(secret_hex).ok()?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(&secret).ok()?;
+    mac.update(signing_string(method, path_and_query, timestamp, body).as_bytes());
+    Some(mac)
+}
+
+/// Whether `timestamp` is within the replay window around `now`
+fn timestamp_in_window(timestamp: i64, now: i64) -> bool {
+    timestamp.abs_diff(now) <= REPLAY_WINDOW_SECS
+}
+
+/// Authenticate a request signed with an API key
+///
+/// Called by [`jwt_auth`](super::auth::jwt_auth) when the request carries an
+/// `X-Api-Key` header. Every authenticated request is recorded in
+/// `audit_log` with the response status.
+pub async fn api_key_auth(state: AppState, req: Request<Body>, next: Next) -> Result<Response, StatusCode> {
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|h| h.to_str().ok())
+            .map(str::to_string)
+    };
+    let (Some(key_id), Some(timestamp), Some(signature)) = (
+        header(API_KEY_HEADER),
+        header(API_TIMESTAMP_HEADER),
+        header(API_SIGNATURE_HEADER),
+    ) else {
+        warn!("API key request without timestamp or signature");
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+
+    let method = req.method().clone();
+    let uri = req
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|u| u.0.clone())
+        .unwrap_or_else(|| req.uri().clone());
+    let path = uri.path().to_string();
+    let path_and_query = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/").to_string();
+
+    let key = match state.postgres.get_api_key(&key_id).await {
+        Ok(Some(key)) => key,
+        Ok(None) => {
+            warn!("Unknown API key {}", key_id);
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        Err(e) => {
+            warn!("Failed to load API key {}: {}", key_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let now = chrono::Utc::now();
+    if key.revoked_at.is_some() || key.expires_at.is_some_and(|exp| exp <= now) {
+        warn!("API key {} is revoked or expired", key_id);
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let ts = timestamp.parse::<i64>().map_err(|_| StatusCode::UNAUTHORIZED)?;
+    if !timestamp_in_window(ts, now.timestamp()) {
+        warn!("API key {} request timestamp {} outside replay window", key_id, ts);
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    match required_scope(&method, &path) {
+        Some(scope) if key.scopes.iter().any(|s| s == scope) => {}
+        Some(scope) => {
+            warn!("API key {} lacks scope {} for {} {}", key_id, scope, method, path);
+            return Err(StatusCode::FORBIDDEN);
+        }
+        None => {
+            warn!("API key {} used on unsupported endpoint {} {}", key_id, method, path);
+            return Err(StatusCode::FORBIDDEN);
+        }
+    }
+
+    let (parts, body) = req.into_parts();
+    let body = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE)?;
+
+    if !verify_request(&key.secret, method.as_str(), &path_and_query, &timestamp, &body, &signature) {
+        warn!("Invalid signature for API key {}", key_id);
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    match state
+        .postgres
+        .claim_api_request_signature(&key_id, &signature.to_ascii_lowercase(), REPLAY_WINDOW_SECS)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            warn!("Replayed request for API key {}", key_id);
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        Err(e) => {
+            warn!("Failed to record API key signature: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
+    let owner = match state.postgres.get_user(&key.user_id).await {
+        Ok(Some(user)) if user.is_active => user,
+        Ok(_) => {
+            warn!("API key {} belongs to missing or inactive user {}", key_id, key.user_id);
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        Err(e) => {
+            warn!("Failed to load API key owner {}: {}", key.user_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    debug!("Authenticated API key {} for user {}", key_id, owner.user_id);
+
+    let mut req = Request::from_parts(parts, Body::from(body));
+    req.extensions_mut().insert(Claims {
+        sub: owner.user_id.clone(),
+        role: owner.role,
+        iss: API_KEY_ISSUER.to_string(),
+        aud: key_id.clone(),
+        iat: ts as usize,
+        exp: key.expires_at.map(|exp| exp.timestamp() as usize).unwrap_or(usize::MAX),
+    });
+    req.extensions_mut().insert(ApiKeyAuth {
+        key_id: key_id.clone(),
+        max_amount_sats: key.max_amount_sats,
+        daily_limit_sats: key.daily_limit_sats,
+    });
+
+    let response = next.run(req).await;
+
+    if let Err(e) = state
+        .postgres
+        .log_audit_event(
+            "api_key_request",
+            None,
+            None,
+            serde_json::json!({
+                "key_id": key_id,
+                "user_id": owner.user_id,
+                "method": method.as_str(),
+                "path": path,
+                "status": response.status().as_u16(),
+            }),
+        )
+        .await
+    {
+        warn!("Failed to record API key usage for {}: {}", key_id, e);
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_signing_string_hashes_body() {
+        let s = signing_string("POST", "/api/v1/transactions", "1700000000", b"");
+        assert_eq!(
+            s,
+            "POST\n/api/v1/transactions\n1700000000\n\
+             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let body = br#"{"recipient":"tb1q...","amount_sats":1000}"#;
+        let sig = sign_request(SECRET, "POST", "/api/v1/transactions", "1700000000", body).unwrap();
+
+        assert!(verify_request(SECRET, "POST", "/api/v1/transactions", "1700000000", body, &sig));
+        // Any change to the signed parts invalidates the signature
+        assert!(!verify_request(SECRET, "POST", "/api/v1/transactions", "1700000001", body, &sig));
+        assert!(!verify_request(SECRET, "POST", "/api/v1/transactions?x=1", "1700000000", body, &sig));
+        assert!(!verify_request(SECRET, "POST", "/api/v1/transactions", "1700000000", b"{}", &sig));
+        assert!(!verify_request(SECRET, "POST", "/api/v1/transactions", "1700000000", body, "zz"));
+    }
+
+    #[test]
+    fn test_required_scope() {
+        assert_eq!(required_scope(&Method::POST, "/api/v1/transactions"), Some(SCOPE_TX_CREATE));
+        assert_eq!(required_scope(&Method::GET, "/api/v1/transactions"), Some(SCOPE_TX_READ));
+        assert_eq!(required_scope(&Method::GET, "/api/v1/transactions/abc"), Some(SCOPE_TX_READ));
+        assert_eq!(required_scope(&Method::GET, "/api/v1/events"), Some(SCOPE_TX_READ));
+        assert_eq!(required_scope(&Method::GET, "/api/v1/wallet/balance"), Some(SCOPE_WALLET_READ));
+        assert_eq!(required_scope(&Method::POST, "/api/v1/dkg/initiate"), None);
+        assert_eq!(required_scope(&Method::POST, "/api/v1/api-keys"), None);
+    }
+
+    #[test]
+    fn test_timestamp_window() {
+        let now = 1_700_000_000;
+        assert!(timestamp_in_window(now, now));
+        assert!(timestamp_in_window(now - REPLAY_WINDOW_SECS as i64, now));
+        assert!(timestamp_in_window(now + REPLAY_WINDOW_SECS as i64, now));
+        assert!(!timestamp_in_window(now - REPLAY_WINDOW_SECS as i64 - 1, now));
+    }
+}
@@ -1,36 +1,65 @@
 //! JWT Authentication Middleware
 //!
-//! Provides JWT-based authentication for API endpoints
+//! Provides JWT-based authentication for API endpoints. Tokens are issued
+//! and verified with the node's [`JwtKeystore`](super::keystore::JwtKeystore).
 
 use axum::{
     body::Body,
-    extract::Request,
+    extract::{Request, State},
     http::StatusCode,
     middleware::Next,
     response::Response,
 };
-use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
+use super::api_key::{api_key_auth, API_KEY_HEADER};
+use crate::state::AppState;
+
+/// Lifetime of issued access tokens in seconds
+pub const TOKEN_TTL_SECS: u64 = 3600;
+
+/// Role with access to DKG, presignature and cluster operations
+pub const ROLE_ADMIN: &str = "admin";
+
 /// JWT claims structure
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     /// Subject (user ID or node ID)
     pub sub: String,
     /// Role (admin, user, node)
     pub role: String,
+    /// Issuer
+    pub iss: String,
+    /// Audience
+    pub aud: String,
+    /// Issued-at time (Unix timestamp)
+    pub iat: usize,
     /// Expiration time (Unix timestamp)
     pub exp: usize,
 }
 
+impl Claims {
+    pub fn is_admin(&self) -> bool {
+        self.role == ROLE_ADMIN
+    }
+}
+
 /// JWT authentication middleware
 ///
-/// Validates the Bearer token in the Authorization header
+/// Validates the Bearer token in the Authorization header and makes the
+/// decoded [`Claims`] available to handlers as a request extension.
+/// Requests carrying an `X-Api-Key` header are authenticated as signed API
+/// key requests instead (see [`api_key_auth`]).
 pub async fn jwt_auth(
-    req: Request<Body>,
+    State(state): State<AppState>,
+    mut req: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
+    if req.headers().contains_key(API_KEY_HEADER) {
+        return api_key_auth(state, req, next).await;
+    }
+
     // Extract Authorization header
     let auth_header = req
         .headers()
@@ -47,23 +76,11 @@ pub async fn jwt_auth(
         }
     };
 
-    // Validate JWT
-    // In production, load secret from environment variable
-    let secret = std::env::var("JWT_SECRET")
-        .unwrap_or_else(|_| {
-            warn!("JWT_SECRET not set, using development key (INSECURE)");
-            "dev-secret-key-change-in-production".to_string()
-        });
-
-    let decoding_key = DecodingKey::from_secret(secret.as_bytes());
-    let mut validation = Validation::new(Algorithm::HS256);
-    validation.validate_exp = true; // Validate expiration
-
-    match decode::<Claims>(token, &decoding_key, &validation) {
-        Ok(token_data) => {
-            // Authentication successful
-            // You could add claims to request extensions here if needed
-            tracing::info!("Authenticated user: {} with role: {}", token_data.claims.sub, token_data.claims.role);
+    // Validate JWT (signature, kid, expiry, issuer and audience)
+    match state.jwt_keys.verify(token) {
+        Ok(claims) => {
+            tracing::debug!("Authenticated user: {} with role: {}", claims.sub, claims.role);
+            req.extensions_mut().insert(claims);
             Ok(next.run(req).await)
         }
         Err(e) => {
@@ -73,11 +90,32 @@ pub async fn jwt_auth(
     }
 }
 
+/// Admin authorization middleware
+///
+/// Must run after [`jwt_auth`]; rejects callers whose role is not admin.
+pub async fn require_admin(
+    req: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    match req.extensions().get::<Claims>() {
+        Some(claims) if claims.is_admin() => Ok(next.run(req).await),
+        Some(claims) => {
+            warn!("User {} with role {} denied admin endpoint {}", claims.sub, claims.role, req.uri().path());
+            Err(StatusCode::FORBIDDEN)
+        }
+        None => Err(StatusCode::UNAUTHORIZED),
+    }
+}
+
 /// Check if an endpoint is public (doesn't require authentication)
 pub fn is_public_endpoint(path: &str) -> bool {
     matches!(
         path,
-        "/health" | "/api/v1/cluster/status" | "/api/v1/wallet/address"
+        "/health"
+            | "/metrics"
+            | "/.well-known/jwks.json"
+            | "/api/v1/auth/login"
+            | "/api/v1/cluster/status"
     )
 }
 
@@ -89,6 +127,9 @@ mod tests {
     fn test_public_endpoint_detection() {
         assert!(is_public_endpoint("/health"));
         assert!(is_public_endpoint("/api/v1/cluster/status"));
+        assert!(is_public_endpoint("/api/v1/auth/login"));
+        assert!(is_public_endpoint("/.well-known/jwks.json"));
         assert!(!is_public_endpoint("/api/v1/transactions"));
+        assert!(!is_public_endpoint("/api/v1/wallet/address"));
     }
 }
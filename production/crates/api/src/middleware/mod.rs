@@ -2,8 +2,12 @@
 //!
 //! Provides authentication, rate limiting, and other middleware
 
+pub mod api_key;
 pub mod auth;
+pub mod keystore;
 pub mod rate_limit;
 
-pub use auth::{jwt_auth, is_public_endpoint, Claims};
-pub use rate_limit::{rate_limit, RateLimiter, RateLimitConfig};
+pub use auth::{jwt_auth, require_admin, is_public_endpoint, Claims};
+pub use keystore::{JwkSet, JwtKeystore};
+pub use api_key::ApiKeyAuth;
+pub use rate_limit::{rate_limit, RateLimiter, RateLimitConfig, RateLimitPolicy, RouteClass};
@@ -1,21 +1,43 @@
 //! Rate Limiting Middleware
 //!
-//! Implements token bucket algorithm for rate limiting API requests
+//! Implements token bucket rate limiting keyed by the authenticated principal
+//! (user or API key) and the route class, so costly operations like DKG and
+//! presignature generation get a much smaller quota than reads. Requests
+//! without credentials (login) are keyed by client IP. Rejected requests get
+//! `429 Too Many Requests` with a `Retry-After` header.
+//!
+//! Buckets are local to each node. With a shared store configured, every
+//! request also counts against a fixed-window counter in etcd shared by all
+//! API replicas, so spreading requests over nodes does not multiply a
+//! client's quota. If that counter cannot be updated the request is denied
+//! rather than let through on the local bucket alone.
 
 use axum::{
     body::Body,
-    extract::{ConnectInfo, Request, State},
-    http::StatusCode,
+    extract::{ConnectInfo, OriginalUri, Request, State},
+    http::{header::RETRY_AFTER, HeaderValue, Method},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use threshold_storage::EtcdStorage;
 use tokio::sync::Mutex;
 use tracing::warn;
 
+use super::api_key::API_KEY_ISSUER;
+use super::auth::Claims;
+use crate::{error::ApiError, state::AppState};
+
+/// Path prefixes of endpoints that start expensive MPC protocol runs
+const COSTLY_PREFIXES: [&str; 3] = ["/api/v1/dkg/", "/api/v1/aux-info/", "/api/v1/presignatures/"];
+
+/// `Retry-After` for requests denied because the shared counter could not
+/// be updated
+const SHARED_COUNTER_RETRY: Duration = Duration::from_secs(1);
+
 /// Rate limiter configuration
 #[derive(Clone, Debug)]
 pub struct RateLimitConfig {
@@ -25,11 +47,121 @@ pub struct RateLimitConfig {
     pub window: Duration,
 }
 
+impl RateLimitConfig {
+    /// `max_requests` per minute
+    pub fn per_minute(max_requests: usize) -> Self {
+        Self {
+            max_requests,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
 impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self::per_minute(100) // 100 requests per minute
+    }
+}
+
+/// Class of route a request counts against
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    /// Password login, keyed by client IP
+    Login,
+    /// Reads (GET, HEAD, OPTIONS)
+    Read,
+    /// Writes such as creating transactions or API keys
+    Write,
+    /// DKG, aux info and presignature generation
+    Costly,
+}
+
+impl RouteClass {
+    /// Classify a request by method and full (un-nested) path
+    pub fn classify(method: &Method, path: &str) -> Self {
+        if path == "/api/v1/auth/login" {
+            RouteClass::Login
+        } else if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            RouteClass::Read
+        } else if COSTLY_PREFIXES.iter().any(|prefix| path.starts_with(prefix)) {
+            RouteClass::Costly
+        } else {
+            RouteClass::Write
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteClass::Login => "login",
+            RouteClass::Read => "read",
+            RouteClass::Write => "write",
+            RouteClass::Costly => "costly",
+        }
+    }
+}
+
+/// Quotas for each route class
+#[derive(Clone, Debug)]
+pub struct RateLimitPolicy {
+    pub login: RateLimitConfig,
+    pub read: RateLimitConfig,
+    pub write: RateLimitConfig,
+    pub costly: RateLimitConfig,
+}
+
+impl RateLimitPolicy {
+    /// Same quota for every route class
+    pub fn uniform(config: RateLimitConfig) -> Self {
+        Self {
+            login: config.clone(),
+            read: config.clone(),
+            write: config.clone(),
+            costly: config,
+        }
+    }
+
+    /// Load per-minute quotas from the environment
+    ///
+    /// Reads `RATE_LIMIT_LOGIN_PER_MIN`, `RATE_LIMIT_READ_PER_MIN`,
+    /// `RATE_LIMIT_WRITE_PER_MIN` and `RATE_LIMIT_COSTLY_PER_MIN`, falling
+    /// back to the defaults for unset or invalid values.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let per_minute = |var: &str, default: RateLimitConfig| {
+            std::env::var(var)
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|&n| n > 0)
+                .map(RateLimitConfig::per_minute)
+                .unwrap_or(default)
+        };
+
+        Self {
+            login: per_minute("RATE_LIMIT_LOGIN_PER_MIN", defaults.login),
+            read: per_minute("RATE_LIMIT_READ_PER_MIN", defaults.read),
+            write: per_minute("RATE_LIMIT_WRITE_PER_MIN", defaults.write),
+            costly: per_minute("RATE_LIMIT_COSTLY_PER_MIN", defaults.costly),
+        }
+    }
+
+    /// Quota for a route class
+    pub fn config(&self, class: RouteClass) -> &RateLimitConfig {
+        match class {
+            RouteClass::Login => &self.login,
+            RouteClass::Read => &self.read,
+            RouteClass::Write => &self.write,
+            RouteClass::Costly => &self.costly,
+        }
+    }
+}
+
+impl Default for RateLimitPolicy {
     fn default() -> Self {
         Self {
-            max_requests: 100,
-            window: Duration::from_secs(60), // 100 requests per minute
+            login: RateLimitConfig::per_minute(10),
+            read: RateLimitConfig::per_minute(300),
+            write: RateLimitConfig::per_minute(60),
+            costly: RateLimitConfig::per_minute(5),
         }
     }
 }
@@ -38,84 +170,189 @@ impl Default for RateLimitConfig {
 #[derive(Debug)]
 struct TokenBucket {
     /// Available tokens
-    tokens: usize,
+    tokens: f64,
     /// Last time tokens were refilled
     last_refill: Instant,
 }
 
+impl TokenBucket {
+    fn full(config: &RateLimitConfig, now: Instant) -> Self {
+        Self {
+            tokens: config.max_requests as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Take a token, or return how long until one is available
+    fn take(&mut self, config: &RateLimitConfig, now: Instant) -> Result<(), Duration> {
+        let capacity = config.max_requests as f64;
+        let rate = capacity / config.window.as_secs_f64();
+
+        // Refill continuously based on time elapsed
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        }
+    }
+}
+
 /// Rate limiter state
 pub struct RateLimiter {
-    /// Token buckets per client IP
-    buckets: Arc<Mutex<HashMap<String, TokenBucket>>>,
-    /// Configuration
-    config: RateLimitConfig,
+    /// Token buckets per route class and principal
+    buckets: Arc<Mutex<HashMap<(RouteClass, String), TokenBucket>>>,
+    /// Quotas per route class
+    policy: RateLimitPolicy,
+    /// Cross-node counters; local buckets only when unset
+    shared: Option<EtcdStorage>,
 }
 
 impl RateLimiter {
-    /// Create a new rate limiter with the given configuration
+    /// Create a new rate limiter applying the same quota to every route class
     pub fn new(config: RateLimitConfig) -> Self {
+        Self::from_policy(RateLimitPolicy::uniform(config))
+    }
+
+    /// Create a new rate limiter with per-route-class quotas
+    pub fn from_policy(policy: RateLimitPolicy) -> Self {
         Self {
             buckets: Arc::new(Mutex::new(HashMap::new())),
-            config,
+            policy,
+            shared: None,
         }
     }
 
-    /// Check if request from client_ip is allowed
-    ///
-    /// Returns true if request is allowed, false if rate limit exceeded
-    async fn check_rate_limit(&self, client_ip: &str) -> bool {
-        let mut buckets = self.buckets.lock().await;
-        let now = Instant::now();
+    /// Also enforce quotas across all nodes using counters in etcd
+    pub fn with_shared_store(mut self, etcd: EtcdStorage) -> Self {
+        self.shared = Some(etcd);
+        self
+    }
 
-        let bucket = buckets.entry(client_ip.to_string()).or_insert(TokenBucket {
-            tokens: self.config.max_requests,
-            last_refill: now,
-        });
+    /// Check if a request from `principal` to a `class` route is allowed
+    ///
+    /// Returns how long the caller should wait if the rate limit is exceeded.
+    pub async fn check(&self, class: RouteClass, principal: &str) -> Result<(), Duration> {
+        let config = self.policy.config(class);
 
-        // Refill tokens based on time elapsed
-        let elapsed = now.duration_since(bucket.last_refill);
-        if elapsed >= self.config.window {
-            bucket.tokens = self.config.max_requests;
-            bucket.last_refill = now;
+        {
+            let mut buckets = self.buckets.lock().await;
+            let now = Instant::now();
+            buckets
+                .entry((class, principal.to_string()))
+                .or_insert_with(|| TokenBucket::full(config, now))
+                .take(config, now)?;
         }
 
-        // Check if tokens available
-        if bucket.tokens > 0 {
-            bucket.tokens -= 1;
-            true
-        } else {
-            false
+        match &self.shared {
+            Some(etcd) => check_shared(etcd, class, config, principal).await,
+            None => Ok(()),
         }
     }
 
     /// Cleanup old entries (run periodically to prevent memory leaks)
+    ///
+    /// A bucket untouched for a whole window has refilled completely and is
+    /// equivalent to a missing one.
     pub async fn cleanup_old_entries(&self) {
         let mut buckets = self.buckets.lock().await;
         let now = Instant::now();
-        let cleanup_threshold = self.config.window * 2; // Keep entries for 2x window
 
-        buckets.retain(|_, bucket| {
-            now.duration_since(bucket.last_refill) < cleanup_threshold
+        buckets.retain(|(class, _), bucket| {
+            now.duration_since(bucket.last_refill) < self.policy.config(*class).window
         });
     }
 }
 
+/// Count a request against the fixed-window counter shared by all nodes
+///
+/// A request that cannot be counted, because etcd is unavailable or the
+/// counter too contended, is denied: allowing it would let a burst spread
+/// over the replicas exceed the shared quota exactly when it is under load.
+async fn check_shared(
+    etcd: &EtcdStorage,
+    class: RouteClass,
+    config: &RateLimitConfig,
+    principal: &str,
+) -> Result<(), Duration> {
+    let window_secs = config.window.as_secs().max(1);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let window = now / window_secs;
+    let key = format!("/rate_limits/{}/{}/{}", class.as_str(), principal, window);
+
+    match etcd.increment_expiring_counter(&key, (window_secs * 2) as i64).await {
+        Ok(count) if count > config.max_requests as u64 => {
+            Err(Duration::from_secs((window + 1) * window_secs - now))
+        }
+        Ok(_) => Ok(()),
+        Err(e) => {
+            warn!("Shared rate limit counter {} could not be updated, denying request: {}", key, e);
+            Err(SHARED_COUNTER_RETRY)
+        }
+    }
+}
+
+/// Identity a request is rate limited as: the API key, the user, or the
+/// client IP for unauthenticated requests
+fn principal(req: &Request<Body>) -> String {
+    if let Some(claims) = req.extensions().get::<Claims>() {
+        return if claims.iss == API_KEY_ISSUER {
+            format!("key:{}", claims.aud)
+        } else {
+            format!("user:{}", claims.sub)
+        };
+    }
+
+    req.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| format!("ip:{}", addr.ip()))
+        .unwrap_or_else(|| "ip:unknown".to_string())
+}
+
+/// Whole seconds to send in `Retry-After`, at least 1
+fn retry_after_secs(wait: Duration) -> u64 {
+    (wait.as_secs_f64().ceil() as u64).max(1)
+}
+
 /// Rate limiting middleware
 ///
-/// Limits requests per client IP based on token bucket algorithm
+/// Must run after [`jwt_auth`](super::auth::jwt_auth) on authenticated
+/// routes so the caller's identity is known.
 pub async fn rate_limit(
-    ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    State(limiter): State<Arc<RateLimiter>>,
+    State(state): State<AppState>,
     req: Request<Body>,
     next: Next,
-) -> Result<Response, StatusCode> {
-    let client_ip = addr.ip().to_string();
+) -> Response {
+    let path = req
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|u| u.0.path().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let class = RouteClass::classify(req.method(), &path);
+    let principal = principal(&req);
+
+    match state.rate_limiter.check(class, &principal).await {
+        Ok(()) => next.run(req).await,
+        Err(wait) => {
+            let secs = retry_after_secs(wait);
+            warn!("Rate limit exceeded for {} on {} route {}", principal, class.as_str(), path);
 
-    if limiter.check_rate_limit(&client_ip).await {
-        Ok(next.run(req).await)
-    } else {
-        warn!("Rate limit exceeded for client: {}", client_ip);
-        Err(StatusCode::TOO_MANY_REQUESTS)
+            let mut response = ApiError::TooManyRequests(format!(
+                "{} rate limit exceeded, retry in {}s",
+                class.as_str(),
+                secs
+            ))
+            .into_response();
+            response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(secs));
+            response
+        }
     }
 }
 
@@ -132,11 +369,11 @@ mod tests {
 
         // First 10 requests should be allowed
         for _ in 0..10 {
-            assert!(limiter.check_rate_limit("127.0.0.1").await);
+            assert!(limiter.check(RouteClass::Read, "ip:127.0.0.1").await.is_ok());
         }
 
         // 11th request should be blocked
-        assert!(!limiter.check_rate_limit("127.0.0.1").await);
+        assert!(limiter.check(RouteClass::Read, "ip:127.0.0.1").await.is_err());
     }
 
     #[tokio::test]
@@ -147,16 +384,16 @@ mod tests {
         });
 
         // First request allowed
-        assert!(limiter.check_rate_limit("127.0.0.1").await);
+        assert!(limiter.check(RouteClass::Read, "ip:127.0.0.1").await.is_ok());
 
         // Second request blocked
-        assert!(!limiter.check_rate_limit("127.0.0.1").await);
+        assert!(limiter.check(RouteClass::Read, "ip:127.0.0.1").await.is_err());
 
         // Wait for window to pass
         tokio::time::sleep(Duration::from_millis(150)).await;
 
         // Third request should be allowed after refill
-        assert!(limiter.check_rate_limit("127.0.0.1").await);
+        assert!(limiter.check(RouteClass::Read, "ip:127.0.0.1").await.is_ok());
     }
 
     #[tokio::test]
@@ -167,10 +404,45 @@ mod tests {
         });
 
         // First client
-        assert!(limiter.check_rate_limit("127.0.0.1").await);
-        assert!(!limiter.check_rate_limit("127.0.0.1").await);
+        assert!(limiter.check(RouteClass::Read, "ip:127.0.0.1").await.is_ok());
+        assert!(limiter.check(RouteClass::Read, "ip:127.0.0.1").await.is_err());
 
         // Second client should have separate limit
-        assert!(limiter.check_rate_limit("127.0.0.2").await);
+        assert!(limiter.check(RouteClass::Read, "ip:127.0.0.2").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_separate_route_classes() {
+        let limiter = RateLimiter::from_policy(RateLimitPolicy {
+            costly: RateLimitConfig::per_minute(1),
+            ..RateLimitPolicy::default()
+        });
+
+        assert!(limiter.check(RouteClass::Costly, "user:admin").await.is_ok());
+        let wait = limiter.check(RouteClass::Costly, "user:admin").await.unwrap_err();
+        assert_eq!(retry_after_secs(wait), 60);
+
+        // Reads by the same user have their own quota
+        assert!(limiter.check(RouteClass::Read, "user:admin").await.is_ok());
+    }
+
+    #[test]
+    fn test_route_classification() {
+        assert_eq!(RouteClass::classify(&Method::POST, "/api/v1/auth/login"), RouteClass::Login);
+        assert_eq!(RouteClass::classify(&Method::GET, "/api/v1/transactions"), RouteClass::Read);
+        assert_eq!(RouteClass::classify(&Method::GET, "/api/v1/dkg/status"), RouteClass::Read);
+        assert_eq!(RouteClass::classify(&Method::POST, "/api/v1/transactions"), RouteClass::Write);
+        assert_eq!(RouteClass::classify(&Method::POST, "/api/v1/dkg/initiate"), RouteClass::Costly);
+        assert_eq!(
+            RouteClass::classify(&Method::POST, "/api/v1/presignatures/generate"),
+            RouteClass::Costly
+        );
+    }
+
+    #[test]
+    fn test_retry_after_rounds_up() {
+        assert_eq!(retry_after_secs(Duration::from_millis(1)), 1);
+        assert_eq!(retry_after_secs(Duration::from_millis(1500)), 2);
+        assert_eq!(retry_after_secs(Duration::ZERO), 1);
     }
 }
@@ -0,0 +1,290 @@
+//! API key management endpoints (admin only)
+//!
+//! Keys are issued on behalf of a user for machine clients such as a payout
+//! backend. The secret is returned once at creation and never again.
+
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use threshold_storage::ApiKey;
+use tracing::{info, warn};
+
+use crate::{
+    error::ApiError,
+    middleware::{api_key::ALL_SCOPES, Claims},
+    state::AppState,
+    ApiResult,
+};
+
+/// Maximum key name length
+const MAX_NAME_LEN: usize = 64;
+
+/// Request to create an API key
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    /// User the key acts on behalf of
+    pub user_id: String,
+    /// Human-readable name, e.g. "payout-backend"
+    pub name: String,
+    /// Any of "tx:create", "tx:read", "wallet:read"
+    pub scopes: Vec<String>,
+    /// Maximum amount of a single transaction
+    #[serde(default)]
+    pub max_amount_sats: Option<u64>,
+    /// Maximum total amount over a rolling 24 hours
+    #[serde(default)]
+    pub daily_limit_sats: Option<u64>,
+    /// Key lifetime; keys without one never expire
+    #[serde(default)]
+    pub expires_in_days: Option<u32>,
+}
+
+/// Query parameters for listing API keys
+#[derive(Debug, Deserialize)]
+pub struct ListApiKeysQuery {
+    #[serde(default)]
+    pub user_id: Option<String>,
+}
+
+/// An API key without its secret
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyResponse {
+    pub key_id: String,
+    pub user_id: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub max_amount_sats: Option<u64>,
+    pub daily_limit_sats: Option<u64>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_by: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(k: ApiKey) -> Self {
+        Self {
+            key_id: k.key_id,
+            user_id: k.user_id,
+            name: k.name,
+            scopes: k.scopes,
+            max_amount_sats: k.max_amount_sats,
+            daily_limit_sats: k.daily_limit_sats,
+            expires_at: k.expires_at,
+            revoked_at: k.revoked_at,
+            created_by: k.created_by,
+            created_at: k.created_at,
+            last_used_at: k.last_used_at,
+        }
+    }
+}
+
+/// A newly created API key, including its secret
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateApiKeyResponse {
+    #[serde(flatten)]
+    pub key: ApiKeyResponse,
+    /// Hex-encoded HMAC secret; shown only once
+    pub secret: String,
+}
+
+/// List of API keys
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListApiKeysResponse {
+    pub api_keys: Vec<ApiKeyResponse>,
+    pub total: usize,
+}
+
+/// Check requested scopes and limits
+fn validate_request(req: &CreateApiKeyRequest) -> Result<(), ApiError> {
+    if req.name.trim().is_empty() || req.name.len() > MAX_NAME_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "Name must be 1-{} characters",
+            MAX_NAME_LEN
+        )));
+    }
+    if req.scopes.is_empty() {
+        return Err(ApiError::BadRequest("At least one scope is required".to_string()));
+    }
+    if let Some(scope) = req.scopes.iter().find(|s| !ALL_SCOPES.contains(&s.as_str())) {
+        return Err(ApiError::BadRequest(format!(
+            "Unknown scope '{}' (expected one of {})",
+            scope,
+            ALL_SCOPES.join(", ")
+        )));
+    }
+    if req.max_amount_sats == Some(0) || req.daily_limit_sats == Some(0) {
+        return Err(ApiError::BadRequest("Amount limits must be greater than zero".to_string()));
+    }
+    if req.expires_in_days == Some(0) {
+        return Err(ApiError::BadRequest("expires_in_days must be greater than zero".to_string()));
+    }
+    Ok(())
+}
+
+/// Random hex string of `len` bytes
+fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// POST /api/v1/api-keys - Issue an API key for a user
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> ApiResult<Json<CreateApiKeyResponse>> {
+    validate_request(&payload)?;
+
+    let mut scopes = payload.scopes;
+    scopes.sort();
+    scopes.dedup();
+
+    state
+        .postgres
+        .get_user(&payload.user_id)
+        .await?
+        .filter(|u| u.is_active)
+        .ok_or_else(|| ApiError::NotFound(format!("User {} not found", payload.user_id)))?;
+
+    let now = chrono::Utc::now();
+    let key = ApiKey {
+        key_id: format!("ak_{}", random_hex(12)),
+        user_id: payload.user_id,
+        name: payload.name.trim().to_string(),
+        secret: random_hex(32),
+        scopes,
+        max_amount_sats: payload.max_amount_sats,
+        daily_limit_sats: payload.daily_limit_sats,
+        expires_at: payload
+            .expires_in_days
+            .map(|days| now + chrono::Duration::days(days as i64)),
+        revoked_at: None,
+        created_by: claims.sub.clone(),
+        created_at: now,
+        last_used_at: None,
+    };
+
+    state.postgres.create_api_key(&key).await?;
+
+    if let Err(e) = state
+        .postgres
+        .log_audit_event(
+            "api_key_created",
+            None,
+            None,
+            serde_json::json!({
+                "key_id": key.key_id,
+                "user_id": key.user_id,
+                "scopes": key.scopes,
+                "max_amount_sats": key.max_amount_sats,
+                "daily_limit_sats": key.daily_limit_sats,
+                "expires_at": key.expires_at,
+                "created_by": key.created_by,
+            }),
+        )
+        .await
+    {
+        warn!("Failed to audit creation of API key {}: {}", key.key_id, e);
+    }
+
+    info!("Admin {} created API key {} for user {}", claims.sub, key.key_id, key.user_id);
+
+    let secret = key.secret.clone();
+    Ok(Json(CreateApiKeyResponse {
+        key: key.into(),
+        secret,
+    }))
+}
+
+/// GET /api/v1/api-keys - List API keys, optionally for one user
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    Query(query): Query<ListApiKeysQuery>,
+) -> ApiResult<Json<ListApiKeysResponse>> {
+    let api_keys: Vec<ApiKeyResponse> = state
+        .postgres
+        .list_api_keys(query.user_id.as_deref())
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListApiKeysResponse {
+        total: api_keys.len(),
+        api_keys,
+    }))
+}
+
+/// DELETE /api/v1/api-keys/:key_id - Revoke an API key
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(key_id): Path<String>,
+) -> ApiResult<Json<ApiKeyResponse>> {
+    if !state.postgres.revoke_api_key(&key_id).await? {
+        return Err(ApiError::NotFound(format!("Active API key {} not found", key_id)));
+    }
+
+    if let Err(e) = state
+        .postgres
+        .log_audit_event(
+            "api_key_revoked",
+            None,
+            None,
+            serde_json::json!({ "key_id": key_id, "revoked_by": claims.sub }),
+        )
+        .await
+    {
+        warn!("Failed to audit revocation of API key {}: {}", key_id, e);
+    }
+
+    info!("Admin {} revoked API key {}", claims.sub, key_id);
+
+    let key = state
+        .postgres
+        .get_api_key(&key_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("API key {} not found", key_id)))?;
+
+    Ok(Json(key.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(scopes: &[&str]) -> CreateApiKeyRequest {
+        CreateApiKeyRequest {
+            user_id: "user1".to_string(),
+            name: "payout-backend".to_string(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            max_amount_sats: Some(100_000),
+            daily_limit_sats: None,
+            expires_in_days: Some(90),
+        }
+    }
+
+    #[test]
+    fn test_validate_scopes() {
+        assert!(validate_request(&request(&["tx:create", "tx:read"])).is_ok());
+        assert!(validate_request(&request(&[])).is_err());
+        assert!(validate_request(&request(&["admin"])).is_err());
+    }
+
+    #[test]
+    fn test_validate_limits() {
+        let mut req = request(&["tx:create"]);
+        req.max_amount_sats = Some(0);
+        assert!(validate_request(&req).is_err());
+
+        let mut req = request(&["tx:create"]);
+        req.name = String::new();
+        assert!(validate_request(&req).is_err());
+    }
+}
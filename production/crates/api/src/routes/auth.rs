@@ -0,0 +1,89 @@
+//! Authentication endpoints
+
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{
+    error::ApiError,
+    middleware::{auth::TOKEN_TTL_SECS, JwkSet},
+    state::AppState,
+    ApiResult,
+};
+
+/// Login request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoginRequest {
+    pub user_id: String,
+    pub password: String,
+}
+
+/// Login response carrying a bearer token
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoginResponse {
+    pub access_token: String,
+    /// Always "Bearer"
+    pub token_type: String,
+    /// Token lifetime in seconds
+    pub expires_in: u64,
+    pub user_id: String,
+    pub role: String,
+}
+
+/// POST /api/v1/auth/login - Exchange credentials for an access token
+///
+/// Unknown users, inactive users and wrong passwords all return the same
+/// 401 so the endpoint cannot be used to enumerate accounts.
+pub async fn login(
+    State(state): State<AppState>,
+    Json(payload): Json<LoginRequest>,
+) -> ApiResult<Json<LoginResponse>> {
+    let invalid = || ApiError::Unauthorized("Invalid user ID or password".to_string());
+
+    let user = state
+        .postgres
+        .get_user(&payload.user_id)
+        .await?
+        .filter(|u| u.is_active)
+        .ok_or_else(invalid)?;
+
+    // bcrypt is deliberately slow; keep it off the async worker threads
+    let hash = user.password_hash.clone();
+    let verified = tokio::task::spawn_blocking(move || bcrypt::verify(&payload.password, &hash))
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Password check panicked: {}", e)))?
+        .unwrap_or_else(|e| {
+            warn!("Stored password hash for {} is invalid: {}", user.user_id, e);
+            false
+        });
+
+    if !verified {
+        warn!("Failed login attempt for user {}", user.user_id);
+        return Err(invalid());
+    }
+
+    let access_token = state
+        .jwt_keys
+        .issue(&user.user_id, &user.role)
+        .map_err(|e| ApiError::InternalError(format!("Failed to issue token: {}", e)))?;
+
+    state.postgres.record_user_login(&user.user_id).await?;
+
+    info!("User {} logged in with role {}", user.user_id, user.role);
+
+    Ok(Json(LoginResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: TOKEN_TTL_SECS,
+        user_id: user.user_id,
+        role: user.role,
+    }))
+}
+
+/// GET /.well-known/jwks.json - Public keys that verify access tokens
+///
+/// Lists every key in the keystore, including retired keys whose tokens
+/// may not have expired yet; tokens name their key in the `kid` header.
+pub async fn jwks(State(state): State<AppState>) -> Json<JwkSet> {
+    Json(state.jwt_keys.jwks())
+}
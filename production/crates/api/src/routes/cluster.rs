@@ -56,7 +56,7 @@ pub async fn get_cluster_status(
     // Fetch cluster status from handler
     let status = crate::handlers::cluster::get_cluster_status(
         state.postgres.as_ref(),
-        state.etcd.as_ref(),
+        &state.etcd,
     )
     .await?;
 
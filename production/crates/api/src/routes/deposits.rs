@@ -0,0 +1,105 @@
+//! Deposit endpoints
+
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use threshold_storage::Deposit;
+
+use crate::{error::ApiError, middleware::Claims, state::AppState, ApiResult};
+
+/// Query parameters for listing deposits
+#[derive(Debug, Deserialize)]
+pub struct ListDepositsQuery {
+    /// Restrict to one user (admins only; users always see their own)
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Maximum number of deposits to return (default: 100)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Number of deposits to skip (default: 0)
+    #[serde(default)]
+    pub offset: Option<usize>,
+}
+
+/// A detected deposit
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepositResponse {
+    pub user_id: String,
+    pub address: String,
+    pub txid: String,
+    pub vout: u32,
+    pub amount_sats: u64,
+    /// "pending", "credited" or "reversed"
+    pub status: String,
+    pub confirmations: u32,
+    pub block_height: Option<u64>,
+    pub first_seen_at: chrono::DateTime<chrono::Utc>,
+    pub credited_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<Deposit> for DepositResponse {
+    fn from(d: Deposit) -> Self {
+        Self {
+            user_id: d.user_id,
+            address: d.address,
+            txid: d.txid,
+            vout: d.vout,
+            amount_sats: d.amount_sats,
+            status: d.status,
+            confirmations: d.confirmations,
+            block_height: d.block_height,
+            first_seen_at: d.first_seen_at,
+            credited_at: d.credited_at,
+        }
+    }
+}
+
+/// List of deposits response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListDepositsResponse {
+    pub deposits: Vec<DepositResponse>,
+    pub total: usize,
+    /// Credited ledger balance of the listed user; absent for an
+    /// unfiltered admin listing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ledger_balance_sats: Option<i64>,
+}
+
+/// GET /api/v1/deposits - List detected deposits
+///
+/// Users see their own deposits and ledger balance. Admins see all
+/// deposits, or one user's with `?user_id=`.
+pub async fn list_deposits(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<ListDepositsQuery>,
+) -> ApiResult<Json<ListDepositsResponse>> {
+    let user_id = if claims.is_admin() {
+        query.user_id
+    } else {
+        if query.user_id.as_ref().is_some_and(|u| u != &claims.sub) {
+            return Err(ApiError::Forbidden(
+                "Users can only list their own deposits".to_string(),
+            ));
+        }
+        Some(claims.sub)
+    };
+
+    let deposits = state
+        .postgres
+        .list_deposits(user_id.as_deref(), query.limit, query.offset)
+        .await?;
+
+    let ledger_balance_sats = match &user_id {
+        Some(user_id) => Some(state.postgres.get_ledger_balance(user_id).await?),
+        None => None,
+    };
+
+    Ok(Json(ListDepositsResponse {
+        total: deposits.len(),
+        deposits: deposits.into_iter().map(Into::into).collect(),
+        ledger_balance_sats,
+    }))
+}
@@ -0,0 +1,398 @@
+//! Server-sent events stream of cluster activity
+//!
+//! `GET /api/v1/events` pushes transaction state changes, vote arrivals,
+//! signing progress and node health changes as they are appended to the
+//! cluster event log. Every node writes to the same log, so a client sees
+//! the whole cluster no matter which node it is connected to.
+//!
+//! Each node polls the log once for all of its clients through an
+//! [`EventFeed`] and fans new events out to them. Event IDs are assigned on
+//! insert but become visible on commit, so the feed re-reads a trailing
+//! window of IDs and publishes events that committed late exactly once.
+//!
+//! Each SSE event carries the log ID as its `id`; browsers send it back as
+//! `Last-Event-ID` when they reconnect and the stream resumes right after it.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::convert::Infallible;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    Extension,
+};
+use futures::Stream;
+use serde::Deserialize;
+use threshold_storage::{ClusterEvent, PostgresStorage, EVENT_TYPES};
+use threshold_types::TxId;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::{error::ApiError, middleware::Claims, state::AppState, ApiResult};
+
+/// Header browsers send with the last event ID seen when reconnecting
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// How often the event log is checked for new events
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum number of events fetched per query
+const BATCH_SIZE: i64 = 100;
+
+/// How far below the newest event ID seen late commits are still picked up
+const TRAILING_WINDOW: i64 = 1000;
+
+/// Events buffered per client before it falls behind and catches up from
+/// the log
+const FEED_CAPACITY: usize = 1024;
+
+/// Query parameters for the event stream
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    /// Comma-separated event types; all types when omitted
+    #[serde(default)]
+    pub types: Option<String>,
+    /// Only events of this transaction
+    #[serde(default)]
+    pub tx_id: Option<String>,
+    /// Resume after this event, for clients that cannot set `Last-Event-ID`
+    #[serde(default)]
+    pub last_event_id: Option<i64>,
+}
+
+/// Parse and check the requested event types
+fn parse_event_types(types: Option<&str>) -> Result<Vec<String>, ApiError> {
+    let Some(types) = types else {
+        return Ok(Vec::new());
+    };
+
+    let mut parsed = Vec::new();
+    for event_type in types.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        if !EVENT_TYPES.contains(&event_type) {
+            return Err(ApiError::BadRequest(format!(
+                "Unknown event type '{}' (expected one of {})",
+                event_type,
+                EVENT_TYPES.join(", ")
+            )));
+        }
+        parsed.push(event_type.to_string());
+    }
+    Ok(parsed)
+}
+
+/// Event ID to resume after; the `Last-Event-ID` header wins over the query
+fn resume_after(headers: &HeaderMap, query: &EventsQuery) -> Result<Option<i64>, ApiError> {
+    match headers.get(LAST_EVENT_ID_HEADER) {
+        Some(value) => value
+            .to_str()
+            .ok()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .filter(|id| *id >= 0)
+            .map(Some)
+            .ok_or_else(|| ApiError::BadRequest("Invalid Last-Event-ID header".to_string())),
+        None => Ok(query.last_event_id.filter(|id| *id >= 0)),
+    }
+}
+
+/// Render a log entry as an SSE event
+fn to_sse_event(event: &ClusterEvent) -> Event {
+    let data = serde_json::json!({
+        "id": event.id,
+        "type": event.event_type,
+        "tx_id": event.tx_id,
+        "node_id": event.node_id,
+        "created_at": event.created_at,
+        "data": event.payload,
+    });
+
+    Event::default()
+        .id(event.id.to_string())
+        .event(&event.event_type)
+        .data(data.to_string())
+}
+
+/// Event IDs seen within the trailing window
+struct SeenWindow {
+    /// Nothing at or below this ID is wanted
+    start: i64,
+    newest: i64,
+    ids: BTreeSet<i64>,
+}
+
+impl SeenWindow {
+    fn new(start: i64) -> Self {
+        Self {
+            start,
+            newest: start,
+            ids: BTreeSet::new(),
+        }
+    }
+
+    /// IDs at or below this are out of the window
+    fn floor(&self) -> i64 {
+        self.start.max(self.newest - TRAILING_WINDOW)
+    }
+
+    /// Record an event ID; false if it was seen already or is out of the window
+    fn insert(&mut self, id: i64) -> bool {
+        if id <= self.floor() || !self.ids.insert(id) {
+            return false;
+        }
+        if id > self.newest {
+            self.newest = id;
+            self.ids = self.ids.split_off(&(self.floor() + 1));
+        }
+        true
+    }
+}
+
+/// Cluster events of one node, polled once and shared by all its clients
+pub struct EventFeed {
+    postgres: Arc<PostgresStorage>,
+    sender: OnceLock<broadcast::Sender<Arc<ClusterEvent>>>,
+}
+
+impl EventFeed {
+    pub fn new(postgres: Arc<PostgresStorage>) -> Self {
+        Self {
+            postgres,
+            sender: OnceLock::new(),
+        }
+    }
+
+    /// Receive events appended from now on, starting the poller on first use
+    fn subscribe(&self) -> broadcast::Receiver<Arc<ClusterEvent>> {
+        self.sender
+            .get_or_init(|| {
+                let (sender, _) = broadcast::channel(FEED_CAPACITY);
+                tokio::spawn(poll_events(Arc::clone(&self.postgres), sender.clone()));
+                sender
+            })
+            .subscribe()
+    }
+}
+
+/// Publish events as they become visible in the log
+async fn poll_events(postgres: Arc<PostgresStorage>, sender: broadcast::Sender<Arc<ClusterEvent>>) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut window: Option<SeenWindow> = None;
+
+    loop {
+        interval.tick().await;
+
+        let window = match &mut window {
+            Some(window) => window,
+            None => match postgres.latest_cluster_event_id().await {
+                Ok(id) => window.insert(SeenWindow::new(id)),
+                Err(e) => {
+                    warn!("Failed to start polling cluster events: {}", e);
+                    continue;
+                }
+            },
+        };
+
+        loop {
+            let seen: Vec<i64> = window.ids.iter().copied().collect();
+            let events = match postgres.list_unseen_cluster_events(window.floor(), &seen, BATCH_SIZE).await {
+                Ok(events) => events,
+                // Try again on the next tick
+                Err(e) => {
+                    warn!("Failed to poll cluster events: {}", e);
+                    break;
+                }
+            };
+
+            let more = events.len() as i64 == BATCH_SIZE;
+            for event in events {
+                if window.insert(event.id) {
+                    // No receivers is fine; clients resume from the log
+                    let _ = sender.send(Arc::new(event));
+                }
+            }
+            if !more {
+                break;
+            }
+        }
+    }
+}
+
+/// Position of one client in the event log
+struct EventCursor {
+    postgres: Arc<PostgresStorage>,
+    feed: broadcast::Receiver<Arc<ClusterEvent>>,
+    event_types: Vec<String>,
+    tx_id: Option<String>,
+    delivered: SeenWindow,
+    pending: VecDeque<ClusterEvent>,
+}
+
+impl EventCursor {
+    fn wants(&self, event: &ClusterEvent) -> bool {
+        (self.event_types.is_empty() || self.event_types.contains(&event.event_type))
+            && (self.tx_id.is_none() || self.tx_id == event.tx_id)
+    }
+
+    /// Next event for the client, waiting for one to be appended
+    async fn next_event(&mut self) -> Option<ClusterEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+
+            match self.feed.recv().await {
+                Ok(event) => {
+                    if self.wants(&event) && self.delivered.insert(event.id) {
+                        return Some(event.as_ref().clone());
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    warn!("Event stream fell {} events behind, catching up from the log", missed);
+                    self.catch_up().await;
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Queue the wanted events in the log that were not delivered yet
+    async fn catch_up(&mut self) {
+        let mut after_id = self.delivered.floor();
+        loop {
+            let events = match self
+                .postgres
+                .list_cluster_events_after(after_id, &self.event_types, self.tx_id.as_deref(), BATCH_SIZE)
+                .await
+            {
+                Ok(events) => events,
+                // Keep the connection open and try again shortly
+                Err(e) => {
+                    warn!("Failed to read cluster events: {}", e);
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+            };
+
+            let more = events.len() as i64 == BATCH_SIZE;
+            for event in events {
+                after_id = event.id;
+                if self.delivered.insert(event.id) {
+                    self.pending.push_back(event);
+                }
+            }
+            if !more {
+                return;
+            }
+        }
+    }
+}
+
+/// GET /api/v1/events - Live stream of cluster events
+///
+/// Admins may stream everything. Other users must pass the `tx_id` of one
+/// of their own transactions and only receive that transaction's events.
+pub async fn stream_events(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    headers: HeaderMap,
+    Query(query): Query<EventsQuery>,
+) -> ApiResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    let event_types = parse_event_types(query.types.as_deref())?;
+    let resume = resume_after(&headers, &query)?;
+
+    if !claims.is_admin() {
+        let Some(tx_id) = &query.tx_id else {
+            return Err(ApiError::Forbidden(
+                "Only admins can stream cluster-wide events; pass tx_id".to_string(),
+            ));
+        };
+        let owner = state
+            .postgres
+            .get_transaction_owner(&TxId::from(tx_id.clone()))
+            .await?;
+        if owner.as_deref() != Some(claims.sub.as_str()) {
+            return Err(ApiError::NotFound(format!("Transaction not found: {}", tx_id)));
+        }
+    }
+
+    // New clients start at the head of the log; reconnecting ones resume
+    let after_id = match resume {
+        Some(id) => id,
+        None => state.postgres.latest_cluster_event_id().await?,
+    };
+
+    // Subscribe before reading the log so nothing falls in between
+    let mut cursor = EventCursor {
+        postgres: Arc::clone(&state.postgres),
+        feed: state.events.subscribe(),
+        event_types,
+        tx_id: query.tx_id,
+        delivered: SeenWindow::new(after_id),
+        pending: VecDeque::new(),
+    };
+    cursor.catch_up().await;
+
+    let stream = futures::stream::unfold(cursor, |mut cursor| async move {
+        let event = cursor.next_event().await?;
+        Some((Ok(to_sse_event(&event)), cursor))
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(last_event_id: Option<i64>) -> EventsQuery {
+        EventsQuery {
+            types: None,
+            tx_id: None,
+            last_event_id,
+        }
+    }
+
+    #[test]
+    fn test_parse_event_types() {
+        assert!(parse_event_types(None).unwrap().is_empty());
+        assert_eq!(
+            parse_event_types(Some("transaction.state_changed, vote.received")).unwrap(),
+            vec!["transaction.state_changed", "vote.received"]
+        );
+        assert!(parse_event_types(Some("dkg.completed")).is_err());
+    }
+
+    #[test]
+    fn test_seen_window() {
+        let mut window = SeenWindow::new(10);
+        assert!(!window.insert(10));
+        assert!(window.insert(12));
+        assert!(!window.insert(12));
+
+        // A lower ID that committed late is still new
+        assert!(window.insert(11));
+
+        // Far ahead, the window moves up and forgets what fell out of it
+        assert!(window.insert(12 + TRAILING_WINDOW));
+        assert_eq!(window.floor(), 12);
+        assert!(!window.insert(11));
+        assert!(window.insert(13));
+        assert_eq!(window.ids.len(), 2);
+    }
+
+    #[test]
+    fn test_resume_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(resume_after(&headers, &query(None)).unwrap(), None);
+        assert_eq!(resume_after(&headers, &query(Some(7))).unwrap(), Some(7));
+
+        headers.insert(LAST_EVENT_ID_HEADER, "42".parse().unwrap());
+        assert_eq!(resume_after(&headers, &query(Some(7))).unwrap(), Some(42));
+
+        headers.insert(LAST_EVENT_ID_HEADER, "abc".parse().unwrap());
+        assert!(resume_after(&headers, &query(None)).is_err());
+    }
+}
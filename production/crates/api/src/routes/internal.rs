@@ -12,5 +12,7 @@ pub fn routes() -> Router<AppState> {
         .route("/aux-info-join", post(internal::receive_aux_info_join_request))
         .route("/presig-join", post(internal::receive_presig_join_request))
         .route("/signing-join", post(internal::receive_signing_join_request))
+        .route("/frost-nonce-sign", post(internal::receive_frost_nonce_sign_request))
+        .route("/frost-nonce-discard", post(internal::receive_frost_nonce_discard_request))
         .route("/aux-ready", get(internal::check_aux_ready))
 }
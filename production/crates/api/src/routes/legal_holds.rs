@@ -0,0 +1,132 @@
+//! Legal hold endpoints (admin only)
+//!
+//! A transaction on legal hold is exempt from the retention service: it is
+//! neither archived nor deleted, and its presignature usage and Byzantine
+//! violations are kept, until the hold is released.
+
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use threshold_storage::LegalHold;
+use threshold_types::TxId;
+use tracing::{info, warn};
+
+use crate::{error::ApiError, middleware::Claims, state::AppState, ApiResult};
+
+/// Request to put a transaction on legal hold
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlaceLegalHoldRequest {
+    /// Why the records must be kept, e.g. a case reference
+    pub reason: String,
+}
+
+/// A transaction on legal hold
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LegalHoldResponse {
+    pub txid: String,
+    pub reason: String,
+    pub placed_by: String,
+    pub placed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<LegalHold> for LegalHoldResponse {
+    fn from(h: LegalHold) -> Self {
+        Self {
+            txid: h.txid,
+            reason: h.reason,
+            placed_by: h.placed_by,
+            placed_at: h.placed_at,
+        }
+    }
+}
+
+/// List of legal holds
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListLegalHoldsResponse {
+    pub legal_holds: Vec<LegalHoldResponse>,
+    pub total: usize,
+}
+
+/// PUT /api/v1/legal-holds/:txid - Put a transaction on legal hold
+///
+/// Placing a hold on a held transaction replaces its reason.
+pub async fn place_legal_hold(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(txid): Path<String>,
+    Json(payload): Json<PlaceLegalHoldRequest>,
+) -> ApiResult<Json<LegalHoldResponse>> {
+    let reason = payload.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::BadRequest("A reason is required".to_string()));
+    }
+
+    let txid = TxId(txid);
+    let hold = state.postgres.place_legal_hold(&txid, reason, &claims.sub).await?;
+
+    if let Err(e) = state
+        .postgres
+        .log_audit_event(
+            "legal_hold_placed",
+            None,
+            Some(&txid),
+            serde_json::json!({ "reason": hold.reason, "placed_by": hold.placed_by }),
+        )
+        .await
+    {
+        warn!("Failed to audit legal hold on {}: {}", txid, e);
+    }
+
+    info!("Admin {} placed legal hold on {}", claims.sub, txid);
+
+    Ok(Json(hold.into()))
+}
+
+/// GET /api/v1/legal-holds - List transactions on legal hold
+pub async fn list_legal_holds(State(state): State<AppState>) -> ApiResult<Json<ListLegalHoldsResponse>> {
+    let legal_holds: Vec<LegalHoldResponse> = state
+        .postgres
+        .list_legal_holds()
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListLegalHoldsResponse {
+        total: legal_holds.len(),
+        legal_holds,
+    }))
+}
+
+/// DELETE /api/v1/legal-holds/:txid - Release a legal hold
+///
+/// The transaction becomes subject to the retention policies again.
+pub async fn release_legal_hold(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(txid): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let txid = TxId(txid);
+    if !state.postgres.release_legal_hold(&txid).await? {
+        return Err(ApiError::NotFound(format!("No legal hold on {}", txid)));
+    }
+
+    if let Err(e) = state
+        .postgres
+        .log_audit_event(
+            "legal_hold_released",
+            None,
+            Some(&txid),
+            serde_json::json!({ "released_by": claims.sub }),
+        )
+        .await
+    {
+        warn!("Failed to audit release of legal hold on {}: {}", txid, e);
+    }
+
+    info!("Admin {} released legal hold on {}", claims.sub, txid);
+
+    Ok(Json(serde_json::json!({ "released": txid.0 })))
+}
@@ -1,11 +1,18 @@
 //! API route modules
 
+pub mod api_keys;
+pub mod auth;
 pub mod cluster;
+pub mod deposits;
+pub mod events;
 pub mod health;
+pub mod legal_holds;
 pub mod transactions;
+pub mod users;
 pub mod wallet;
 pub mod dkg;
 pub mod aux_info;
 pub mod presig;
 pub mod internal;
 pub mod metrics;
+pub mod webhooks;
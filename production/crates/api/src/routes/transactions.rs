@@ -2,12 +2,36 @@
 
 use axum::{
     extract::{Path, Query, State},
-    Json,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
-use threshold_types::{TransactionState, TxId};
+use sha2::{Digest, Sha256};
+use threshold_storage::{
+    ApiKeySpend, IdempotencyClaim, IdempotentCompletion, IdempotentResponse, TransactionAttempt,
+    TransactionOrigin,
+};
+use threshold_orchestrator::{DkgStatus, ProtocolType};
+use threshold_types::{Transaction, TransactionState, TxId};
+use tracing::warn;
+
+use crate::{
+    error::ApiError,
+    handlers::transactions::{IdempotentResponder, SpendSource},
+    middleware::{ApiKeyAuth, Claims},
+    state::AppState,
+    ApiResult,
+};
+
+/// Header carrying a client-chosen key that makes a request safe to retry
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Set on responses replayed from an earlier request with the same key
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
 
-use crate::{error::ApiError, state::AppState, ApiResult};
+/// Maximum idempotency key length
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
 
 /// Query parameters for listing transactions
 #[derive(Debug, Deserialize)]
@@ -68,10 +92,78 @@ pub struct ListTransactionsResponse {
 ///
 /// Creates a new Bitcoin transaction with optional OP_RETURN metadata.
 /// The transaction will go through the MPC threshold signing process.
+/// Non-admin users spend from their ledger balance: amount plus fee is
+/// debited when the transaction is stored, and refunded if it fails before
+/// broadcast. Their deposit addresses are spent before the wallet root
+/// address. Requests made with an API key are also held to the key's per-transaction and daily
+/// amount limits.
+///
+/// With an `Idempotency-Key` header, retries with the same key and body
+/// return the original response instead of creating another transaction.
+/// Reusing a key with a different body, or while the first request is
+/// still running, is a conflict. The response is recorded in the database
+/// transaction that stores the transaction, and the key is released when
+/// nothing was stored.
 pub async fn create_transaction(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    api_key: Option<Extension<ApiKeyAuth>>,
+    headers: HeaderMap,
     Json(payload): Json<CreateTransactionRequest>,
-) -> ApiResult<Json<CreateTransactionResponse>> {
+) -> ApiResult<Response> {
+    let api_key = api_key.map(|Extension(key)| key);
+
+    let idempotency_key = idempotency_key(&headers)?;
+    if let Some(ref key) = idempotency_key {
+        let request_hash = request_fingerprint(&payload)?;
+        let claim = state
+            .postgres
+            .claim_idempotency_key(&claims.sub, key, &request_hash, state.idempotency_ttl.as_secs())
+            .await?;
+        if let IdempotencyClaim::Existing(earlier) = claim {
+            return replay_idempotent_response(key, &request_hash, earlier);
+        }
+    }
+
+    let idempotency = idempotency_key.as_deref().map(|key| (claims.sub.as_str(), key));
+    let tx = match validate_and_create(&state, &claims, api_key.as_ref(), idempotency, &payload).await {
+        Ok(tx) => tx,
+        Err(e) => {
+            // Nothing was created, so a retry with the same key may proceed
+            if let Some(ref key) = idempotency_key {
+                if let Err(release_err) = state.postgres.release_idempotency_key(&claims.sub, key).await {
+                    warn!("Failed to release idempotency key {}: {}", key, release_err);
+                }
+            }
+            return Err(e);
+        }
+    };
+
+    Ok(Json(CreateTransactionResponse::from(tx)).into_response())
+}
+
+impl From<Transaction> for CreateTransactionResponse {
+    fn from(tx: Transaction) -> Self {
+        Self {
+            txid: tx.txid.0,
+            state: tx.state,
+            recipient: tx.recipient,
+            amount_sats: tx.amount_sats,
+            fee_sats: tx.fee_sats,
+            metadata: tx.metadata,
+            created_at: tx.created_at,
+        }
+    }
+}
+
+/// Validate a create request and build and store the unsigned transaction
+async fn validate_and_create(
+    state: &AppState,
+    claims: &Claims,
+    api_key: Option<&ApiKeyAuth>,
+    idempotency: Option<(&str, &str)>,
+    payload: &CreateTransactionRequest,
+) -> ApiResult<Transaction> {
     // Validate recipient address format
     if payload.recipient.is_empty() {
         return Err(ApiError::BadRequest(
@@ -95,36 +187,164 @@ pub async fn create_transaction(
         }
     }
 
+    if let Some(key) = api_key {
+        check_api_key_amount(key, payload.amount_sats)?;
+    }
+
+    let sources = spend_sources(state).await?;
+
+    // The response is stored with the transaction, so a retry replays it
+    // even if this request's connection drops right after the commit
+    let idempotent_response = idempotency.map(|(owner, key)| {
+        move |tx: &Transaction| -> ApiResult<IdempotentCompletion> {
+            let response = serde_json::to_value(CreateTransactionResponse::from(tx.clone()))
+                .map_err(|e| ApiError::InternalError(format!("Failed to serialize response: {}", e)))?;
+            Ok(IdempotentCompletion {
+                owner: owner.to_string(),
+                key: key.to_string(),
+                status_code: StatusCode::OK.as_u16(),
+                response,
+            })
+        }
+    });
+
     // Use handler to create transaction
-    let tx = crate::handlers::transactions::create_transaction(
+    crate::handlers::transactions::create_transaction(
         state.postgres.as_ref(),
         state.bitcoin.as_ref(),
+        &sources,
         &payload.recipient,
         payload.amount_sats,
         payload.metadata.as_deref(),
+        &TransactionOrigin {
+            user_id: Some(claims.sub.clone()),
+            debit_ledger: !claims.is_admin(),
+            derivation_path: Vec::new(),
+            api_key: api_key.map(|key| ApiKeySpend {
+                key_id: key.key_id.clone(),
+                daily_limit_sats: key.daily_limit_sats,
+            }),
+            idempotency: None,
+        },
+        idempotent_response.as_ref().map(|respond| respond as &IdempotentResponder),
     )
-    .await?;
+    .await
+}
 
-    Ok(Json(CreateTransactionResponse {
-        txid: tx.txid.0.clone(),
-        state: tx.state,
-        recipient: tx.recipient,
-        amount_sats: tx.amount_sats,
-        fee_sats: tx.fee_sats,
-        metadata: tx.metadata,
-        created_at: tx.created_at,
-    }))
+/// Addresses a create request may spend from
+///
+/// Withdrawals are paid from the wallet root address of the CGGMP24 key and
+/// debited from the owner's ledger balance. Coins on user deposit addresses
+/// are not spent, since CGGMP24 cannot sign with a derived key yet.
+async fn spend_sources(state: &AppState) -> ApiResult<Vec<SpendSource>> {
+    let root_address = state
+        .dkg_service
+        .list_ceremonies()
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to list DKG ceremonies: {}", e)))?
+        .into_iter()
+        .filter(|c| c.protocol == ProtocolType::CGGMP24 && matches!(c.status, DkgStatus::Completed))
+        .max_by_key(|c| c.completed_at)
+        .and_then(|c| c.address);
+
+    Ok(root_address
+        .map(|address| SpendSource {
+            address,
+            derivation_path: Vec::new(),
+        })
+        .into_iter()
+        .collect())
+}
+
+/// Read the `Idempotency-Key` header, if present
+fn idempotency_key(headers: &HeaderMap) -> ApiResult<Option<String>> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+
+    let key = value
+        .to_str()
+        .map_err(|_| ApiError::BadRequest("Idempotency-Key must be ASCII".to_string()))?
+        .trim();
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "Idempotency-Key must be 1-{} characters",
+            MAX_IDEMPOTENCY_KEY_LEN
+        )));
+    }
+
+    Ok(Some(key.to_string()))
+}
+
+/// Hex SHA-256 of the request as parsed, so formatting differences in the
+/// JSON body do not count as a different request
+fn request_fingerprint(payload: &CreateTransactionRequest) -> ApiResult<String> {
+    let bytes = serde_json::to_vec(payload)
+        .map_err(|e| ApiError::InternalError(format!("Failed to serialize request: {}", e)))?;
+    Ok(hex::encode(Sha256::digest(bytes)))
+}
+
+/// Answer a retry from the response stored under its idempotency key
+fn replay_idempotent_response(
+    key: &str,
+    request_hash: &str,
+    earlier: IdempotentResponse,
+) -> ApiResult<Response> {
+    if earlier.request_hash != request_hash {
+        return Err(ApiError::Conflict(format!(
+            "Idempotency-Key {} was already used with a different request",
+            key
+        )));
+    }
+
+    let Some((status, body)) = earlier.response else {
+        return Err(ApiError::Conflict(format!(
+            "A request with Idempotency-Key {} is still in progress",
+            key
+        )));
+    };
+
+    let status = StatusCode::from_u16(status)
+        .map_err(|e| ApiError::InternalError(format!("Invalid stored status code: {}", e)))?;
+
+    Ok((status, [(IDEMPOTENT_REPLAYED_HEADER, "true")], Json(body)).into_response())
+}
+
+/// Reject amounts above an API key's per-transaction limit
+///
+/// The rolling daily limit is checked when the transaction is stored, under
+/// a lock on the key, see [`threshold_storage::PostgresStorage::create_owned_transaction`].
+fn check_api_key_amount(key: &ApiKeyAuth, amount_sats: u64) -> ApiResult<()> {
+    if let Some(max) = key.max_amount_sats {
+        if amount_sats > max {
+            return Err(ApiError::Forbidden(format!(
+                "Amount {} exceeds the API key limit of {} sats per transaction",
+                amount_sats, max
+            )));
+        }
+    }
+
+    Ok(())
 }
 
 /// GET /api/v1/transactions/:txid - Get transaction status
 ///
-/// Retrieves the current status of a specific transaction
+/// Retrieves the current status of a specific transaction. Users only see
+/// their own transactions; other transactions are reported as not found.
 pub async fn get_transaction(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Path(txid): Path<String>,
 ) -> ApiResult<Json<TransactionStatusResponse>> {
     let txid = TxId::from(txid);
 
+    if !claims.is_admin() {
+        let owner = state.postgres.get_transaction_owner(&txid).await?;
+        if owner.as_deref() != Some(claims.sub.as_str()) {
+            return Err(ApiError::NotFound(format!("Transaction not found: {}", txid)));
+        }
+    }
+
     // Fetch transaction from database
     let tx = state
         .postgres
@@ -144,21 +364,170 @@ pub async fn get_transaction(
     }))
 }
 
-/// GET /api/v1/transactions - List all transactions
+/// One recorded state transition
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateTransitionResponse {
+    pub from: TransactionState,
+    pub to: TransactionState,
+    pub version: u64,
+    pub actor: String,
+    pub reason: String,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
+
+/// State history of a transaction
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionHistoryResponse {
+    pub txid: String,
+    pub transitions: Vec<StateTransitionResponse>,
+}
+
+/// GET /api/v1/transactions/:txid/history - State transitions of a transaction
+///
+/// Same visibility rules as [`get_transaction`].
+pub async fn get_transaction_history(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(txid): Path<String>,
+) -> ApiResult<Json<TransactionHistoryResponse>> {
+    let txid = TxId::from(txid);
+    ensure_visible(&state, &claims, &txid).await?;
+
+    let transitions = state
+        .postgres
+        .get_transaction_state_history(&txid)
+        .await?
+        .into_iter()
+        .map(|t| StateTransitionResponse {
+            from: t.from,
+            to: t.to,
+            version: t.version,
+            actor: t.actor,
+            reason: t.reason,
+            at: t.created_at,
+        })
+        .collect();
+
+    Ok(Json(TransactionHistoryResponse {
+        txid: txid.0,
+        transitions,
+    }))
+}
+
+/// Fail with `NotFound` unless the transaction exists and the caller may see it
+///
+/// Users only see their own transactions, so other users' transactions are
+/// reported as missing rather than forbidden.
+async fn ensure_visible(state: &AppState, claims: &Claims, txid: &TxId) -> ApiResult<()> {
+    if !claims.is_admin() {
+        let owner = state.postgres.get_transaction_owner(txid).await?;
+        if owner.as_deref() != Some(claims.sub.as_str()) {
+            return Err(ApiError::NotFound(format!("Transaction not found: {}", txid)));
+        }
+    }
+
+    state
+        .postgres
+        .get_transaction(txid)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Transaction not found: {}", txid)))?;
+
+    Ok(())
+}
+
+/// One signing session or broadcast made for a transaction
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionAttemptResponse {
+    /// `signing` or `broadcast`
+    pub operation: String,
+    pub attempt: u32,
+    /// `succeeded`, `retryable` or `fatal`
+    pub outcome: String,
+    pub error: Option<String>,
+    pub coordinator: u64,
+    /// Signers left out of the attempt
+    pub excluded_nodes: Vec<u64>,
+    /// Signer blamed for the failure
+    pub faulty_node: Option<u64>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+    /// Earliest time of the next attempt
+    pub retry_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<TransactionAttempt> for TransactionAttemptResponse {
+    fn from(a: TransactionAttempt) -> Self {
+        Self {
+            operation: a.operation.as_str().to_string(),
+            attempt: a.attempt,
+            outcome: a.outcome.as_str().to_string(),
+            error: a.error,
+            coordinator: a.coordinator.0,
+            excluded_nodes: a.excluded_nodes.into_iter().map(|n| n.0).collect(),
+            faulty_node: a.faulty_node.map(|n| n.0),
+            started_at: a.started_at,
+            finished_at: a.finished_at,
+            retry_at: a.retry_at,
+        }
+    }
+}
+
+/// Signing and broadcast attempts of a transaction
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionAttemptsResponse {
+    pub txid: String,
+    pub attempts: Vec<TransactionAttemptResponse>,
+}
+
+/// GET /api/v1/transactions/:txid/attempts - Signing and broadcast attempts
 ///
-/// Returns a list of all transactions with their current status
+/// Oldest first. Same visibility rules as [`get_transaction`].
+pub async fn get_transaction_attempts(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(txid): Path<String>,
+) -> ApiResult<Json<TransactionAttemptsResponse>> {
+    let txid = TxId::from(txid);
+    ensure_visible(&state, &claims, &txid).await?;
+
+    let attempts = state
+        .postgres
+        .get_transaction_attempts(&txid)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(TransactionAttemptsResponse {
+        txid: txid.0,
+        attempts,
+    }))
+}
+
+/// GET /api/v1/transactions - List transactions
+///
+/// Returns all transactions for admins and the caller's own transactions
+/// for users, with their current status
 /// Supports pagination via query parameters: ?limit=N&offset=M
 pub async fn list_transactions(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Query(query): Query<ListTransactionsQuery>,
 ) -> ApiResult<Json<ListTransactionsResponse>> {
     // Fetch transactions from database with pagination
-    let transactions = crate::handlers::transactions::list_transactions(
-        state.postgres.as_ref(),
-        query.limit,
-        query.offset,
-    )
-    .await?;
+    let transactions = if claims.is_admin() {
+        crate::handlers::transactions::list_transactions(
+            state.postgres.as_ref(),
+            query.limit,
+            query.offset,
+        )
+        .await?
+    } else {
+        state
+            .postgres
+            .list_transactions_by_owner(&claims.sub, query.limit, query.offset)
+            .await?
+    };
 
     let total = transactions.len();
     let transaction_responses: Vec<TransactionStatusResponse> = transactions
@@ -180,3 +549,68 @@ pub async fn list_transactions(
         total,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(amount_sats: u64) -> CreateTransactionRequest {
+        CreateTransactionRequest {
+            recipient: "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx".to_string(),
+            amount_sats,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_idempotency_key_header() {
+        let mut headers = HeaderMap::new();
+        assert!(idempotency_key(&headers).unwrap().is_none());
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, "payout-42".parse().unwrap());
+        assert_eq!(idempotency_key(&headers).unwrap().as_deref(), Some("payout-42"));
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, " ".parse().unwrap());
+        assert!(idempotency_key(&headers).is_err());
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, "k".repeat(256).parse().unwrap());
+        assert!(idempotency_key(&headers).is_err());
+    }
+
+    #[test]
+    fn test_request_fingerprint() {
+        let hash = request_fingerprint(&request(10_000)).unwrap();
+        assert_eq!(hash, request_fingerprint(&request(10_000)).unwrap());
+        assert_ne!(hash, request_fingerprint(&request(10_001)).unwrap());
+    }
+
+    #[test]
+    fn test_replay_idempotent_response() {
+        let hash = request_fingerprint(&request(10_000)).unwrap();
+        let completed = IdempotentResponse {
+            request_hash: hash.clone(),
+            response: Some((200, serde_json::json!({ "txid": "abc" }))),
+        };
+
+        let response = replay_idempotent_response("k", &hash, completed.clone()).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+
+        // Same key, different body
+        let other = request_fingerprint(&request(20_000)).unwrap();
+        assert!(matches!(
+            replay_idempotent_response("k", &other, completed),
+            Err(ApiError::Conflict(_))
+        ));
+
+        // First request still running
+        let in_progress = IdempotentResponse {
+            request_hash: hash.clone(),
+            response: None,
+        };
+        assert!(matches!(
+            replay_idempotent_response("k", &hash, in_progress),
+            Err(ApiError::Conflict(_))
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+//! Per-user address endpoints
+//!
+//! All endpoints act on the authenticated caller (`/users/me`); there is no
+//! way to address another user's data.
+
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use threshold_storage::UserAddress;
+use tracing::warn;
+
+use crate::{
+    error::ApiError,
+    handlers::user_addresses::{derive_user_address, root_xpub_for, UserAddressType},
+    middleware::Claims,
+    state::AppState,
+    ApiResult,
+};
+
+/// Maximum label length
+const MAX_LABEL_LEN: usize = 64;
+
+/// Request to derive a new deposit address
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CreateAddressRequest {
+    /// "p2wpkh" (default); "p2tr" is rejected until taproot inputs can be spent
+    #[serde(default)]
+    pub address_type: UserAddressType,
+    /// Optional user-defined label
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Query parameters for listing addresses
+#[derive(Debug, Deserialize)]
+pub struct ListAddressesQuery {
+    /// Query the chain and update the cached balances before responding
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// A user-owned deposit address
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserAddressResponse {
+    pub address: String,
+    pub address_type: String,
+    pub derivation_path: String,
+    pub derivation_index: u32,
+    /// Derived public key (hex)
+    pub public_key: String,
+    pub label: Option<String>,
+    pub is_change: bool,
+    /// Cached balance in satoshis
+    pub balance_sats: u64,
+    /// Cached transaction count
+    pub tx_count: u32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<UserAddress> for UserAddressResponse {
+    fn from(a: UserAddress) -> Self {
+        Self {
+            address: a.address,
+            address_type: a.address_type,
+            derivation_path: a.derivation_path,
+            derivation_index: a.derivation_index,
+            public_key: a.public_key,
+            label: a.label,
+            is_change: a.is_change,
+            balance_sats: a.balance_sats,
+            tx_count: a.tx_count,
+            created_at: a.created_at,
+        }
+    }
+}
+
+/// List of user addresses
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListAddressesResponse {
+    pub addresses: Vec<UserAddressResponse>,
+    /// Sum of the cached balances
+    pub total_balance_sats: u64,
+}
+
+/// POST /api/v1/users/me/addresses - Derive a new deposit address
+///
+/// Reserves the next global derivation index and derives the address from
+/// the group key of the requested type.
+pub async fn create_address(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateAddressRequest>,
+) -> ApiResult<Json<UserAddressResponse>> {
+    let label = payload
+        .label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+    if label.as_ref().is_some_and(|l| l.len() > MAX_LABEL_LEN) {
+        return Err(ApiError::BadRequest(format!(
+            "Label exceeds maximum length of {} characters",
+            MAX_LABEL_LEN
+        )));
+    }
+
+    if !payload.address_type.is_offered() {
+        return Err(ApiError::BadRequest(format!(
+            "{} deposit addresses are not supported yet",
+            payload.address_type.as_str()
+        )));
+    }
+
+    let root_xpub = root_xpub_for(&state, payload.address_type).await?;
+    let network = state.bitcoin.network().to_bitcoin_network();
+
+    let index = state.postgres.next_derivation_index().await?;
+    let derived = derive_user_address(&root_xpub, payload.address_type, index, network)?;
+
+    let address = UserAddress {
+        user_id: claims.sub,
+        address: derived.address,
+        derivation_index: derived.index,
+        derivation_path: derived.path,
+        public_key: derived.public_key,
+        address_type: payload.address_type.as_str().to_string(),
+        label,
+        is_change: false,
+        balance_sats: 0,
+        tx_count: 0,
+        created_at: chrono::Utc::now(),
+    };
+    state.postgres.create_user_address(&address).await?;
+
+    Ok(Json(address.into()))
+}
+
+/// GET /api/v1/users/me/addresses - List the caller's addresses
+///
+/// Returns cached balances; pass `?refresh=true` to update them from the
+/// chain first.
+pub async fn list_addresses(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<ListAddressesQuery>,
+) -> ApiResult<Json<ListAddressesResponse>> {
+    let mut addresses = state.postgres.list_user_addresses(&claims.sub).await?;
+
+    if query.refresh {
+        for address in addresses.iter_mut() {
+            let info = match state.bitcoin.get_address_info(&address.address).await {
+                Ok(info) => info,
+                Err(e) => {
+                    warn!("Failed to refresh {}: {}", address.address, e);
+                    continue;
+                }
+            };
+
+            address.balance_sats = info.total_balance();
+            address.tx_count = (info.chain_stats.tx_count + info.mempool_stats.tx_count) as u32;
+            state
+                .postgres
+                .update_user_address_stats(&address.address, address.balance_sats, address.tx_count)
+                .await?;
+        }
+    }
+
+    let total_balance_sats = addresses.iter().map(|a| a.balance_sats).sum();
+
+    Ok(Json(ListAddressesResponse {
+        addresses: addresses.into_iter().map(Into::into).collect(),
+        total_balance_sats,
+    }))
+}
@@ -1,10 +1,10 @@
 //! Wallet management endpoints
 
-use axum::{extract::State, Json};
+use axum::{extract::State, Extension, Json};
 use serde::{Deserialize, Serialize};
 use threshold_orchestrator::ProtocolType;
 
-use crate::{error::ApiError, state::AppState, ApiResult};
+use crate::{error::ApiError, middleware::Claims, state::AppState, ApiResult};
 
 /// Wallet balance response
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,11 +28,16 @@ pub struct WalletAddressResponse {
 
 /// GET /api/v1/wallet/balance - Get wallet balance
 ///
-/// Returns the current balance of the MPC wallet including
-/// confirmed and unconfirmed amounts
+/// Admins get the balance of the MPC wallet's DKG address; users get the
+/// sum over their own addresses. Both include confirmed and unconfirmed amounts.
 pub async fn get_balance(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
 ) -> ApiResult<Json<WalletBalanceResponse>> {
+    if !claims.is_admin() {
+        return get_user_balance(&state, &claims.sub).await.map(Json);
+    }
+
     // First get the wallet address from DKG
     let address = get_wallet_address_from_dkg(&state).await?;
 
@@ -59,10 +64,29 @@ pub async fn get_balance(
 
 /// GET /api/v1/wallet/address - Get receiving address
 ///
-/// Returns the current receiving address for the MPC wallet
+/// Returns the MPC wallet's receiving address for admins, or the caller's
+/// latest receiving address for users
 pub async fn get_address(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
 ) -> ApiResult<Json<WalletAddressResponse>> {
+    if !claims.is_admin() {
+        let address = state
+            .postgres
+            .list_user_addresses(&claims.sub)
+            .await?
+            .into_iter()
+            .find(|a| !a.is_change)
+            .ok_or_else(|| {
+                ApiError::NotFound(format!("No addresses assigned to user {}", claims.sub))
+            })?;
+
+        return Ok(Json(WalletAddressResponse {
+            address: address.address,
+            address_type: address.address_type,
+        }));
+    }
+
     // Get address from completed DKG ceremony
     let ceremonies = state
         .dkg_service
@@ -118,3 +142,28 @@ async fn get_wallet_address_from_dkg(state: &AppState) -> Result<String, ApiErro
         }
     }
 }
+
+/// Sum the balances of all addresses owned by a user
+async fn get_user_balance(state: &AppState, user_id: &str) -> Result<WalletBalanceResponse, ApiError> {
+    let addresses = state.postgres.list_user_addresses(user_id).await?;
+
+    let mut confirmed = 0;
+    let mut unconfirmed = 0;
+    for address in &addresses {
+        match state.bitcoin.get_balance(&address.address).await {
+            Ok(balance) => {
+                confirmed += balance.confirmed;
+                unconfirmed += balance.unconfirmed;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to fetch balance for {}: {}", address.address, e);
+            }
+        }
+    }
+
+    Ok(WalletBalanceResponse {
+        confirmed,
+        unconfirmed,
+        total: confirmed + unconfirmed,
+    })
+}
@@ -0,0 +1,315 @@
+//! Webhook subscription endpoints (admin only)
+//!
+//! Subscribers receive signed POSTs for transaction state changes, DKG and
+//! aux info completion, and low presignature pool alerts. Deliveries that
+//! keep failing end up in the dead letters and can be replayed from here.
+
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use threshold_storage::{WebhookDeadLetter, WebhookSubscription, WEBHOOK_EVENT_TYPES};
+use tracing::{info, warn};
+
+use crate::{error::ApiError, middleware::Claims, state::AppState, ApiResult};
+
+/// Default and maximum number of dead letters returned
+const DEFAULT_DEAD_LETTER_LIMIT: i64 = 100;
+const MAX_DEAD_LETTER_LIMIT: i64 = 1000;
+
+/// Request to create a webhook subscription
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWebhookRequest {
+    /// HTTP(S) endpoint receiving the events
+    pub url: String,
+    /// Any of "transaction.state_changed", "dkg.completed",
+    /// "aux_info.completed", "presignature_pool.low"
+    pub events: Vec<String>,
+}
+
+/// A webhook subscription without its secret
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookResponse {
+    pub subscription_id: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    pub created_by: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<WebhookSubscription> for WebhookResponse {
+    fn from(s: WebhookSubscription) -> Self {
+        Self {
+            subscription_id: s.subscription_id,
+            url: s.url,
+            events: s.events,
+            active: s.active,
+            created_by: s.created_by,
+            created_at: s.created_at,
+        }
+    }
+}
+
+/// A newly created webhook subscription, including its signing secret
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWebhookResponse {
+    #[serde(flatten)]
+    pub subscription: WebhookResponse,
+    /// HMAC key for `X-Webhook-Signature`; shown only once
+    pub secret: String,
+}
+
+/// List of webhook subscriptions
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListWebhooksResponse {
+    pub webhooks: Vec<WebhookResponse>,
+    pub total: usize,
+}
+
+/// Query parameters for listing dead letters
+#[derive(Debug, Deserialize)]
+pub struct ListDeadLettersQuery {
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+/// A delivery that exhausted its retries
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLetterResponse {
+    pub id: i64,
+    pub subscription_id: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempts: u32,
+    pub last_status_code: Option<u16>,
+    pub last_error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub failed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<WebhookDeadLetter> for DeadLetterResponse {
+    fn from(d: WebhookDeadLetter) -> Self {
+        Self {
+            id: d.id,
+            subscription_id: d.subscription_id,
+            event_id: d.event_id,
+            event_type: d.event_type,
+            payload: d.payload,
+            attempts: d.attempts,
+            last_status_code: d.last_status_code,
+            last_error: d.last_error,
+            created_at: d.created_at,
+            failed_at: d.failed_at,
+        }
+    }
+}
+
+/// List of dead letters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListDeadLettersResponse {
+    pub dead_letters: Vec<DeadLetterResponse>,
+    pub total: usize,
+}
+
+/// Check the endpoint URL and requested events
+fn validate_request(req: &CreateWebhookRequest) -> Result<(), ApiError> {
+    let url = reqwest::Url::parse(&req.url)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid webhook URL: {}", e)))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(ApiError::BadRequest(
+            "Webhook URL must be an http(s) URL with a host".to_string(),
+        ));
+    }
+    if req.events.is_empty() {
+        return Err(ApiError::BadRequest("At least one event is required".to_string()));
+    }
+    if let Some(event) = req.events.iter().find(|e| !WEBHOOK_EVENT_TYPES.contains(&e.as_str())) {
+        return Err(ApiError::BadRequest(format!(
+            "Unknown event '{}' (expected one of {})",
+            event,
+            WEBHOOK_EVENT_TYPES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Random hex string of `len` bytes
+fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// POST /api/v1/webhooks - Subscribe an endpoint to events
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> ApiResult<Json<CreateWebhookResponse>> {
+    validate_request(&payload)?;
+
+    let mut events = payload.events;
+    events.sort();
+    events.dedup();
+
+    let subscription = WebhookSubscription {
+        subscription_id: format!("wh_{}", random_hex(12)),
+        url: payload.url,
+        events,
+        secret: format!("whsec_{}", random_hex(32)),
+        active: true,
+        created_by: claims.sub.clone(),
+        created_at: chrono::Utc::now(),
+    };
+
+    state.postgres.create_webhook_subscription(&subscription).await?;
+
+    if let Err(e) = state
+        .postgres
+        .log_audit_event(
+            "webhook_created",
+            None,
+            None,
+            serde_json::json!({
+                "subscription_id": subscription.subscription_id,
+                "url": subscription.url,
+                "events": subscription.events,
+                "created_by": subscription.created_by,
+            }),
+        )
+        .await
+    {
+        warn!("Failed to audit creation of webhook {}: {}", subscription.subscription_id, e);
+    }
+
+    info!(
+        "Admin {} subscribed webhook {} to {:?}",
+        claims.sub, subscription.subscription_id, subscription.events
+    );
+
+    let secret = subscription.secret.clone();
+    Ok(Json(CreateWebhookResponse {
+        subscription: subscription.into(),
+        secret,
+    }))
+}
+
+/// GET /api/v1/webhooks - List webhook subscriptions
+pub async fn list_webhooks(State(state): State<AppState>) -> ApiResult<Json<ListWebhooksResponse>> {
+    let webhooks: Vec<WebhookResponse> = state
+        .postgres
+        .list_webhook_subscriptions()
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListWebhooksResponse {
+        total: webhooks.len(),
+        webhooks,
+    }))
+}
+
+/// DELETE /api/v1/webhooks/:subscription_id - Remove a webhook subscription
+///
+/// Pending deliveries and dead letters of the subscription are dropped too.
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(subscription_id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    if !state.postgres.delete_webhook_subscription(&subscription_id).await? {
+        return Err(ApiError::NotFound(format!("Webhook {} not found", subscription_id)));
+    }
+
+    if let Err(e) = state
+        .postgres
+        .log_audit_event(
+            "webhook_deleted",
+            None,
+            None,
+            serde_json::json!({ "subscription_id": subscription_id, "deleted_by": claims.sub }),
+        )
+        .await
+    {
+        warn!("Failed to audit deletion of webhook {}: {}", subscription_id, e);
+    }
+
+    info!("Admin {} deleted webhook {}", claims.sub, subscription_id);
+
+    Ok(Json(serde_json::json!({ "deleted": subscription_id })))
+}
+
+/// GET /api/v1/webhooks/dead-letters - Deliveries that exhausted their retries
+pub async fn list_dead_letters(
+    State(state): State<AppState>,
+    Query(query): Query<ListDeadLettersQuery>,
+) -> ApiResult<Json<ListDeadLettersResponse>> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_DEAD_LETTER_LIMIT)
+        .clamp(1, MAX_DEAD_LETTER_LIMIT);
+
+    let dead_letters: Vec<DeadLetterResponse> = state
+        .postgres
+        .list_webhook_dead_letters(limit)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListDeadLettersResponse {
+        total: dead_letters.len(),
+        dead_letters,
+    }))
+}
+
+/// POST /api/v1/webhooks/dead-letters/:id/replay - Queue a dead letter again
+///
+/// The event keeps its ID, so receivers that already processed it can
+/// recognize the replay.
+pub async fn replay_dead_letter(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i64>,
+) -> ApiResult<Json<serde_json::Value>> {
+    if !state.postgres.replay_webhook_dead_letter(id).await? {
+        return Err(ApiError::NotFound(format!("Dead letter {} not found", id)));
+    }
+
+    info!("Admin {} replayed webhook dead letter {}", claims.sub, id);
+
+    Ok(Json(serde_json::json!({ "replayed": id })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str, events: &[&str]) -> CreateWebhookRequest {
+        CreateWebhookRequest {
+            url: url.to_string(),
+            events: events.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_url() {
+        assert!(validate_request(&request("https://example.com/hooks", &["dkg.completed"])).is_ok());
+        assert!(validate_request(&request("http://10.0.0.5:9000/hooks", &["dkg.completed"])).is_ok());
+        assert!(validate_request(&request("ftp://example.com", &["dkg.completed"])).is_err());
+        assert!(validate_request(&request("not a url", &["dkg.completed"])).is_err());
+    }
+
+    #[test]
+    fn test_validate_events() {
+        let url = "https://example.com/hooks";
+        assert!(validate_request(&request(url, &["transaction.state_changed", "presignature_pool.low"])).is_ok());
+        assert!(validate_request(&request(url, &[])).is_err());
+        assert!(validate_request(&request(url, &["transaction.created"])).is_err());
+    }
+}
@@ -1,19 +1,26 @@
 //! Shared application state for the API server
 
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
 use threshold_bitcoin::BitcoinClient;
-use threshold_orchestrator::{DkgService, AuxInfoService, PresignatureService, MessageRouter};
+use threshold_orchestrator::{DkgService, AuxInfoService, PresignatureService, FrostNonceService, MessageRouter};
 use threshold_storage::{EtcdStorage, PostgresStorage};
 use threshold_types::{NodeId, VoteRequest};
 
+use crate::middleware::{JwtKeystore, RateLimiter};
+use crate::routes::events::EventFeed;
+
+/// Default retention of idempotency keys
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 3600);
+
 /// Shared application state passed to all handlers
 #[derive(Clone)]
 pub struct AppState {
     /// PostgreSQL storage for transaction and node data
     pub postgres: Arc<PostgresStorage>,
-    /// etcd storage for distributed state (with interior mutability)
-    pub etcd: Arc<Mutex<EtcdStorage>>,
+    /// etcd storage for distributed state
+    pub etcd: EtcdStorage,
     /// Bitcoin client for blockchain operations
     pub bitcoin: Arc<BitcoinClient>,
     /// DKG service for distributed key generation
@@ -22,12 +29,22 @@ pub struct AppState {
     pub aux_info_service: Arc<AuxInfoService>,
     /// Presignature service for fast signing (SORUN #19 FIX)
     pub presig_service: Arc<PresignatureService>,
+    /// FROST nonce pool service for single-round Taproot signing
+    pub frost_nonce_service: Arc<FrostNonceService>,
     /// Message router for protocol communication (SORUN #19 FIX)
     pub message_router: Arc<MessageRouter>,
     /// Channel to trigger automatic voting
     pub vote_trigger: mpsc::Sender<VoteRequest>,
     /// This node's ID
     pub node_id: NodeId,
+    /// Keys for issuing and verifying access tokens
+    pub jwt_keys: Arc<JwtKeystore>,
+    /// Per-identity, per-route-class request quotas
+    pub rate_limiter: Arc<RateLimiter>,
+    /// How long idempotency keys and their responses are kept
+    pub idempotency_ttl: Duration,
+    /// Cluster event log, polled once for all event stream clients
+    pub events: Arc<EventFeed>,
 }
 
 impl AppState {
@@ -39,22 +56,38 @@ impl AppState {
         dkg_service: Arc<DkgService>,
         aux_info_service: Arc<AuxInfoService>,
         presig_service: Arc<PresignatureService>,
+        frost_nonce_service: Arc<FrostNonceService>,
         message_router: Arc<MessageRouter>,
         vote_trigger: mpsc::Sender<VoteRequest>,
         node_id: NodeId,
+        jwt_keys: Arc<JwtKeystore>,
+        rate_limiter: Arc<RateLimiter>,
     ) -> Self {
+        let postgres = Arc::new(postgres);
+        let events = Arc::new(EventFeed::new(Arc::clone(&postgres)));
         Self {
-            postgres: Arc::new(postgres),
-            etcd: Arc::new(Mutex::new(etcd)),
+            postgres,
+            etcd,
             bitcoin: Arc::new(bitcoin),
             dkg_service,
             aux_info_service,
             presig_service,
+            frost_nonce_service,
             message_router,
             vote_trigger,
             node_id,
+            jwt_keys,
+            rate_limiter,
+            idempotency_ttl: DEFAULT_IDEMPOTENCY_TTL,
+            events,
         }
     }
+
+    /// Set the retention of idempotency keys
+    pub fn with_idempotency_ttl(mut self, ttl: Duration) -> Self {
+        self.idempotency_ttl = ttl;
+        self
+    }
 }
 
 impl Default for AppState {
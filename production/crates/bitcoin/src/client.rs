@@ -2,15 +2,18 @@
 //!
 //! Provides async access to:
 //! - Address info and balances
-//! - UTXOs
+//! - UTXOs and address transaction history
 //! - Transaction broadcasting
 //! - Fee estimation
 //! - Confirmation checking
 
-use crate::types::{AddressInfo, FeeEstimates, Utxo};
+use crate::types::{AddressInfo, AddressTx, FeeEstimates, TxStatus, Utxo};
 use serde::Deserialize;
 use thiserror::Error;
 
+/// Confirmed transactions per page of Esplora's address history
+const ESPLORA_CHAIN_PAGE_SIZE: usize = 25;
+
 /// Bitcoin network configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BitcoinNetwork {
@@ -180,6 +183,84 @@ impl BitcoinClient {
             .map_err(|e| BitcoinError::ParseResponse(e.to_string()))
     }
 
+    /// Get all transactions of an address.
+    ///
+    /// Returns the mempool transactions followed by the whole confirmed
+    /// history, newest first. Esplora returns confirmed transactions in pages
+    /// of 25; later pages are requested after the last txid seen until a
+    /// page comes back short.
+    pub async fn get_address_txs(&self, address: &str) -> Result<Vec<AddressTx>, BitcoinError> {
+        let mut txs = self
+            .get_address_tx_page(&format!("{}/address/{}/txs", self.api_base, address))
+            .await?;
+
+        let mut page_len = txs.iter().filter(|tx| tx.status.confirmed).count();
+        while page_len >= ESPLORA_CHAIN_PAGE_SIZE {
+            let last_seen = &txs[txs.len() - 1].txid;
+            let page = self
+                .get_address_tx_page(&format!(
+                    "{}/address/{}/txs/chain/{}",
+                    self.api_base, address, last_seen
+                ))
+                .await?;
+            page_len = page.len();
+            txs.extend(page);
+        }
+
+        Ok(txs)
+    }
+
+    /// One page of an address's transaction history
+    async fn get_address_tx_page(&self, url: &str) -> Result<Vec<AddressTx>, BitcoinError> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| BitcoinError::ApiRequest(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            return Err(BitcoinError::ApiError { status, body });
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| BitcoinError::ParseResponse(e.to_string()))
+    }
+
+    /// Get the confirmation status of a transaction.
+    /// Returns `None` if the backend no longer knows the transaction
+    /// (e.g. evicted from the mempool or reorged out and not re-included).
+    pub async fn get_tx_status(&self, txid: &str) -> Result<Option<TxStatus>, BitcoinError> {
+        let url = format!("{}/tx/{}/status", self.api_base, txid);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| BitcoinError::ApiRequest(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            return Err(BitcoinError::ApiError { status, body });
+        }
+
+        response
+            .json()
+            .await
+            .map(Some)
+            .map_err(|e| BitcoinError::ParseResponse(e.to_string()))
+    }
+
     /// Broadcast a signed transaction.
     /// Returns the transaction ID (txid).
     pub async fn broadcast_tx(&self, tx_hex: &str) -> Result<String, BitcoinError> {
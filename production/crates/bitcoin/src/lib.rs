@@ -65,9 +65,9 @@ pub use tx_builder::{
     TxBuilderError, DUST_LIMIT, MAX_OP_RETURN_SIZE,
 };
 pub use types::{
-    AddressInfo, BalanceResponse, BroadcastResult, ChainStats, FeeEstimates, MempoolStats,
-    SendBitcoinRequest, SendBitcoinResponse, TxInput, TxOutput, UnsignedTransaction, Utxo,
-    UtxoStatus,
+    AddressInfo, AddressTx, AddressTxOutput, BalanceResponse, BroadcastResult, ChainStats,
+    FeeEstimates, MempoolStats, SendBitcoinRequest, SendBitcoinResponse, TxInput, TxOutput,
+    TxStatus, UnsignedTransaction, Utxo, UtxoStatus,
 };
 
 /// Library version.
@@ -79,6 +79,57 @@ impl AddressInfo {
     }
 }
 
+/// Confirmation status of a transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct TxStatus {
+    pub confirmed: bool,
+    #[serde(default)]
+    pub block_height: Option<u64>,
+    /// Hash of the containing block; changes if the transaction is reorged
+    /// into a different block.
+    #[serde(default)]
+    pub block_hash: Option<String>,
+}
+
+impl TxStatus {
+    /// Number of confirmations at chain tip `tip_height` (0 if unconfirmed).
+    pub fn confirmations(&self, tip_height: u64) -> u32 {
+        match (self.confirmed, self.block_height) {
+            (true, Some(height)) if tip_height >= height => (tip_height - height + 1) as u32,
+            _ => 0,
+        }
+    }
+}
+
+/// A transaction touching an address, as returned by Esplora.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressTx {
+    pub txid: String,
+    #[serde(default)]
+    pub vout: Vec<AddressTxOutput>,
+    #[serde(default)]
+    pub status: TxStatus,
+}
+
+/// An output of an [`AddressTx`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressTxOutput {
+    /// Absent for outputs without an address (e.g. OP_RETURN).
+    #[serde(default)]
+    pub scriptpubkey_address: Option<String>,
+    pub value: u64,
+}
+
+impl AddressTx {
+    /// Outputs paying to `address`, as `(vout, value)` pairs.
+    pub fn outputs_to<'a>(&'a self, address: &'a str) -> impl Iterator<Item = (u32, u64)> + 'a {
+        self.vout.iter().enumerate().filter_map(move |(vout, output)| {
+            (output.scriptpubkey_address.as_deref() == Some(address))
+                .then_some((vout as u32, output.value))
+        })
+    }
+}
+
 // ============================================================================
 // Transaction Types
 // ============================================================================
@@ -254,6 +305,37 @@ mod tests {
         assert_eq!(info.total_balance(), 75_000);
     }
 
+    #[test]
+    fn test_tx_status_confirmations() {
+        let confirmed = TxStatus {
+            confirmed: true,
+            block_height: Some(100),
+            block_hash: Some("00ab".to_string()),
+        };
+        assert_eq!(confirmed.confirmations(100), 1);
+        assert_eq!(confirmed.confirmations(105), 6);
+        // Tip behind the block (backend lagging) is not a negative count
+        assert_eq!(confirmed.confirmations(99), 0);
+        assert_eq!(TxStatus::default().confirmations(105), 0);
+    }
+
+    #[test]
+    fn test_address_tx_outputs_to() {
+        let tx: AddressTx = serde_json::from_value(serde_json::json!({
+            "txid": "ab",
+            "vout": [
+                {"scriptpubkey_address": "tb1qother", "value": 5_000},
+                {"scriptpubkey_address": "tb1quser", "value": 20_000},
+                {"value": 0}
+            ],
+            "status": {"confirmed": false}
+        }))
+        .unwrap();
+
+        assert_eq!(tx.outputs_to("tb1quser").collect::<Vec<_>>(), vec![(1, 20_000)]);
+        assert_eq!(tx.outputs_to("tb1qnone").count(), 0);
+    }
+
     #[test]
     fn test_fee_estimates_default() {
         let fees = FeeEstimates::default();
@@ -1,7 +1,10 @@
 //! REST API client for communicating with the threshold wallet API server.
 
 use anyhow::{Context, Result};
-use reqwest::{Client as HttpClient, StatusCode};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    Client as HttpClient, StatusCode,
+};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use threshold_types::TransactionState;
@@ -15,15 +18,37 @@ pub struct ApiClient {
 
 impl ApiClient {
     /// Create a new API client
-    pub fn new(base_url: String, timeout_secs: u64) -> Result<Self> {
+    ///
+    /// When `auth_token` is set it is sent as a Bearer token on every request.
+    pub fn new(base_url: String, timeout_secs: u64, auth_token: Option<&str>) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        if let Some(token) = auth_token {
+            let mut value = HeaderValue::from_str(&format!("Bearer {}", token))
+                .context("Invalid auth token")?;
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+
         let client = HttpClient::builder()
             .timeout(Duration::from_secs(timeout_secs))
+            .default_headers(headers)
             .build()
             .context("Failed to create HTTP client")?;
 
         Ok(Self { base_url, client })
     }
 
+    /// Log in and obtain an access token
+    pub async fn login(&self, user_id: String, password: String) -> Result<LoginResponse> {
+        let url = format!("{}/api/v1/auth/login", self.base_url);
+
+        let request = LoginRequest { user_id, password };
+
+        let response = self.client.post(&url).json(&request).send().await?;
+
+        self.handle_response(response).await
+    }
+
     /// Check API server health
     pub async fn health_check(&self) -> Result<HealthResponse> {
         let url = format!("{}/health", self.base_url);
@@ -184,6 +209,12 @@ impl ApiClient {
                 StatusCode::BAD_REQUEST => {
                     anyhow::bail!("Bad request: {}", error_text)
                 }
+                StatusCode::UNAUTHORIZED => {
+                    anyhow::bail!("Not authenticated (run `threshold-wallet login`): {}", error_text)
+                }
+                StatusCode::FORBIDDEN => {
+                    anyhow::bail!("Permission denied: {}", error_text)
+                }
                 StatusCode::INTERNAL_SERVER_ERROR => {
                     anyhow::bail!("Server error: {}", error_text)
                 }
@@ -199,6 +230,21 @@ impl ApiClient {
 // Request/Response Types
 // ============================================================================
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoginRequest {
+    pub user_id: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoginResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    pub user_id: String,
+    pub role: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HealthResponse {
     pub status: String,
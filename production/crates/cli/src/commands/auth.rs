@@ -0,0 +1,40 @@
+//! Authentication commands.
+
+use anyhow::Result;
+
+use crate::{client::ApiClient, config::Config, output::OutputFormatter};
+
+/// Log in and store the access token in the config file
+pub async fn login(
+    client: &ApiClient,
+    formatter: &OutputFormatter,
+    config: &mut Config,
+    user_id: String,
+    password: Option<String>,
+) -> Result<()> {
+    let password = match password {
+        Some(password) => password,
+        None => dialoguer::Password::new()
+            .with_prompt(format!("Password for {}", user_id))
+            .interact()?,
+    };
+
+    let login = client.login(user_id, password).await?;
+    config.set_auth_token(Some(login.access_token.clone()))?;
+
+    if formatter.json_mode {
+        formatter.json(&login)?;
+    } else {
+        formatter.success(&format!("Logged in as {} ({})", login.user_id, login.role));
+        formatter.kv("Token expires in", &format!("{}s", login.expires_in));
+    }
+
+    Ok(())
+}
+
+/// Forget the stored access token
+pub fn logout(formatter: &OutputFormatter, config: &mut Config) -> Result<()> {
+    config.set_auth_token(None)?;
+    formatter.success("Logged out");
+    Ok(())
+}
@@ -0,0 +1,119 @@
+//! Database schema commands.
+//!
+//! Talk to PostgreSQL directly, like the audit verifier, so they work before
+//! any node is running.
+
+use anyhow::Result;
+use serde::Serialize;
+use tabled::Tabled;
+use threshold_storage::migrations::latest_version;
+use threshold_storage::{MigrationState, MigrationStatus, PostgresStorage};
+use threshold_types::PostgresConfig;
+
+use crate::output::OutputFormatter;
+
+async fn connect(database_url: String) -> Result<PostgresStorage> {
+    Ok(PostgresStorage::new(&PostgresConfig {
+        url: database_url,
+        max_connections: 2,
+        connect_timeout_secs: 30,
+    })
+    .await?)
+}
+
+/// Table row for migration status
+#[derive(Tabled, Serialize)]
+struct MigrationRow {
+    #[tabled(rename = "Version")]
+    version: u32,
+    #[tabled(rename = "Description")]
+    description: String,
+    #[tabled(rename = "State")]
+    state: String,
+    #[tabled(rename = "Applied At")]
+    applied_at: String,
+}
+
+impl From<&MigrationStatus> for MigrationRow {
+    fn from(status: &MigrationStatus) -> Self {
+        Self {
+            version: status.version,
+            description: status.description.clone(),
+            state: status.state.to_string(),
+            applied_at: status
+                .applied_at
+                .map_or("-".to_string(), |t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string()),
+        }
+    }
+}
+
+/// Apply pending migrations, or baseline an existing database
+pub async fn migrate(
+    formatter: &OutputFormatter,
+    database_url: String,
+    baseline: Option<u32>,
+) -> Result<()> {
+    let postgres = connect(database_url).await?;
+
+    if let Some(version) = baseline {
+        let recorded = postgres.baseline_migrations(version).await?;
+        formatter.success(&format!(
+            "Recorded {} migration(s) up to version {} as applied",
+            recorded, version
+        ));
+    }
+
+    let applied = postgres.migrate().await?;
+
+    if formatter.json_mode {
+        formatter.json(&serde_json::json!({
+            "applied": applied,
+            "version": latest_version(),
+        }))?;
+    } else if applied.is_empty() {
+        formatter.success(&format!("Schema is up to date (version {})", latest_version()));
+    } else {
+        formatter.success(&format!(
+            "Applied {} migration(s); schema is at version {}",
+            applied.len(),
+            latest_version()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Show embedded migrations and whether the database has them
+pub async fn status(formatter: &OutputFormatter, database_url: String) -> Result<()> {
+    let postgres = connect(database_url).await?;
+    let status = postgres.migration_status().await?;
+    let rows: Vec<MigrationRow> = status.iter().map(MigrationRow::from).collect();
+
+    if formatter.json_mode {
+        formatter.json(&rows)?;
+    } else {
+        formatter.header(&format!("Schema Migrations (latest {})", latest_version()));
+        formatter.table(rows);
+        println!();
+
+        let count = |state: MigrationState| status.iter().filter(|s| s.state == state).count();
+        if count(MigrationState::Modified) > 0 {
+            formatter.error("Applied migrations differ from this binary; nodes will refuse to start");
+        }
+        if count(MigrationState::Unverified) > 0 {
+            formatter.warning("Schema predates tracked migrations; run `db migrate --baseline <version>`");
+        }
+        if count(MigrationState::Pending) > 0 {
+            formatter.info(&format!(
+                "{} pending migration(s); run `db migrate` or start a node",
+                count(MigrationState::Pending)
+            ));
+        }
+    }
+
+    if status.iter().any(|s| s.state == MigrationState::Modified) {
+        anyhow::bail!("schema drift detected");
+    }
+
+    Ok(())
+}
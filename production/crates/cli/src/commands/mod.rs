@@ -1,7 +1,10 @@
 //! Command implementations for the CLI.
 
+pub mod audit;
+pub mod auth;
 pub mod aux_info;
 pub mod cluster;
+pub mod db;
 pub mod dkg;
 pub mod presig;
 pub mod send;
@@ -28,6 +28,10 @@ pub struct Config {
     /// Enable colored output
     #[serde(default = "default_colored")]
     pub colored: bool,
+
+    /// Access token from the last `login`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token: Option<String>,
 }
 
 fn default_api_endpoint() -> String {
@@ -54,6 +58,7 @@ impl Default for Config {
             timeout_secs: default_timeout(),
             output_format: default_output_format(),
             colored: default_colored(),
+            auth_token: None,
         }
     }
 }
@@ -125,6 +130,12 @@ impl Config {
         self.save()
     }
 
+    /// Store or clear the access token
+    pub fn set_auth_token(&mut self, token: Option<String>) -> Result<()> {
+        self.auth_token = token;
+        self.save()
+    }
+
     /// Update output format
     pub fn set_output_format(&mut self, format: String) -> Result<()> {
         if format != "table" && format != "json" {
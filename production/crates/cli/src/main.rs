@@ -7,6 +7,9 @@
 //! - Cluster monitoring (status, nodes)
 //! - DKG initialization (CGGMP24, FROST)
 //! - Presignature generation
+//! - Login (stores an access token for subsequent commands)
+//! - Offline audit log verification
+//! - Database schema migrations
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -47,6 +50,20 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
+    /// Log in and store an access token
+    Login {
+        /// User ID
+        #[arg(long, value_name = "USER")]
+        user: String,
+
+        /// Password (prompted for if omitted)
+        #[arg(long, value_name = "PASSWORD")]
+        password: Option<String>,
+    },
+
+    /// Forget the stored access token
+    Logout,
+
     /// Wallet operations
     #[command(subcommand)]
     Wallet(WalletCommands),
@@ -89,6 +106,14 @@ enum Commands {
     /// Configuration management
     #[command(subcommand)]
     Config(ConfigCommands),
+
+    /// Audit log operations (connect to the databases directly)
+    #[command(subcommand)]
+    Audit(AuditCommands),
+
+    /// Database schema operations (connect to PostgreSQL directly)
+    #[command(subcommand)]
+    Db(DbCommands),
 }
 
 #[derive(Subcommand)]
@@ -175,6 +200,46 @@ enum PresigCommands {
     Status,
 }
 
+#[derive(Subcommand)]
+enum AuditCommands {
+    /// Re-walk the audit chain and report gaps, modifications and bad signatures
+    Verify {
+        /// PostgreSQL connection URL (default: $POSTGRES_URL)
+        #[arg(long, value_name = "URL")]
+        database_url: Option<String>,
+
+        /// Comma-separated etcd endpoints holding keys and checkpoints (default: $ETCD_ENDPOINTS)
+        #[arg(long, value_name = "ENDPOINTS")]
+        etcd: Option<String>,
+
+        /// Trusted audit public key of a node, as NODE=HEX (repeatable)
+        #[arg(long, value_name = "NODE=HEX")]
+        pubkey: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbCommands {
+    /// Apply pending schema migrations
+    Migrate {
+        /// PostgreSQL connection URL (default: $POSTGRES_URL)
+        #[arg(long, value_name = "URL")]
+        database_url: Option<String>,
+
+        /// First mark migrations up to VERSION as applied without running them
+        /// (for databases created by the old docker init scripts)
+        #[arg(long, value_name = "VERSION")]
+        baseline: Option<u32>,
+    },
+
+    /// Show applied, pending and modified migrations
+    Status {
+        /// PostgreSQL connection URL (default: $POSTGRES_URL)
+        #[arg(long, value_name = "URL")]
+        database_url: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 enum ConfigCommands {
     /// Show current configuration
@@ -233,14 +298,40 @@ async fn main() -> Result<()> {
         return handle_config_command(config_cmd, config, &formatter).await;
     }
 
+    // Audit commands talk to the databases, not the API
+    if let Commands::Audit(audit_cmd) = cli.command {
+        if let Err(e) = handle_audit_command(audit_cmd, &formatter).await {
+            formatter.error(&format!("Error: {}", e));
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Db commands talk to PostgreSQL, not the API
+    if let Commands::Db(db_cmd) = cli.command {
+        if let Err(e) = handle_db_command(db_cmd, &formatter).await {
+            formatter.error(&format!("Error: {}", e));
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Create API client for other commands
-    let client = ApiClient::new(config.api_endpoint.clone(), config.timeout_secs)
-        .map_err(|e| {
-            anyhow::anyhow!("Failed to create API client: {}", e)
-        })?;
+    let client = ApiClient::new(
+        config.api_endpoint.clone(),
+        config.timeout_secs,
+        config.auth_token.as_deref(),
+    )
+    .map_err(|e| {
+        anyhow::anyhow!("Failed to create API client: {}", e)
+    })?;
 
     // Execute command
     let result = match cli.command {
+        Commands::Login { user, password } => {
+            commands::auth::login(&client, &formatter, &mut config, user, password).await
+        }
+        Commands::Logout => commands::auth::logout(&formatter, &mut config),
         Commands::Wallet(cmd) => handle_wallet_command(cmd, &client, &formatter).await,
         Commands::Send { to, amount, metadata } => {
             commands::send::send_bitcoin(&client, &formatter, to, amount, metadata).await
@@ -250,7 +341,7 @@ async fn main() -> Result<()> {
         Commands::Dkg(cmd) => handle_dkg_command(cmd, &client, &formatter).await,
         Commands::AuxInfo(cmd) => handle_aux_info_command(cmd, &client, &formatter).await,
         Commands::Presig(cmd) => handle_presig_command(cmd, &client, &formatter).await,
-        Commands::Config(_) => unreachable!(), // Handled above
+        Commands::Config(_) | Commands::Audit(_) | Commands::Db(_) => unreachable!(), // Handled above
     };
 
     // Handle errors
@@ -273,6 +364,39 @@ async fn handle_wallet_command(
     }
 }
 
+async fn handle_audit_command(cmd: AuditCommands, formatter: &OutputFormatter) -> Result<()> {
+    match cmd {
+        AuditCommands::Verify {
+            database_url,
+            etcd,
+            pubkey,
+        } => {
+            let database_url = database_url
+                .or_else(|| std::env::var("POSTGRES_URL").ok())
+                .ok_or_else(|| anyhow::anyhow!("--database-url or POSTGRES_URL is required"))?;
+            let etcd = etcd.or_else(|| std::env::var("ETCD_ENDPOINTS").ok());
+            commands::audit::verify(formatter, database_url, etcd, pubkey).await
+        }
+    }
+}
+
+async fn handle_db_command(cmd: DbCommands, formatter: &OutputFormatter) -> Result<()> {
+    let database_url = |url: Option<String>| {
+        url.or_else(|| std::env::var("POSTGRES_URL").ok())
+            .ok_or_else(|| anyhow::anyhow!("--database-url or POSTGRES_URL is required"))
+    };
+
+    match cmd {
+        DbCommands::Migrate {
+            database_url: url,
+            baseline,
+        } => commands::db::migrate(formatter, database_url(url)?, baseline).await,
+        DbCommands::Status { database_url: url } => {
+            commands::db::status(formatter, database_url(url)?).await
+        }
+    }
+}
+
 async fn handle_tx_command(
     cmd: TxCommands,
     client: &ApiClient,
@@ -352,7 +476,10 @@ async fn handle_config_command(
     match cmd {
         ConfigCommands::Show => {
             if formatter.json_mode {
-                formatter.json(&config)?;
+                // Never print the access token itself
+                let mut shown = config.clone();
+                shown.auth_token = shown.auth_token.map(|_| "<redacted>".to_string());
+                formatter.json(&shown)?;
             } else {
                 formatter.header("Current Configuration");
                 formatter.kv("API Endpoint", &config.api_endpoint);
@@ -366,6 +493,10 @@ async fn handle_config_command(
                 formatter.kv("Timeout", &format!("{}s", config.timeout_secs));
                 formatter.kv("Output Format", &config.output_format);
                 formatter.kv("Colored Output", &config.colored.to_string());
+                formatter.kv(
+                    "Logged In",
+                    if config.auth_token.is_some() { "yes" } else { "no" },
+                );
 
                 println!();
                 let config_path = Config::config_path()?;
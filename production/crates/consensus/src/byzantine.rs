@@ -1,6 +1,10 @@
+use std::sync::Arc;
+
 use chrono::Utc;
 use threshold_crypto::verify_vote;
-use threshold_storage::{EtcdStorage, PostgresStorage};
+use threshold_storage::{
+    ClusterStorage, EtcdStorage, PostgresStorage, RecordedVote, TransactionStore, VoteStore,
+};
 use threshold_types::{
     ByzantineViolation, ByzantineViolationType, Result,
     TransactionState, Vote, VotingError,
@@ -9,25 +13,39 @@ use tracing::{error, info, warn};
 
 /// Byzantine fault detector that validates votes and detects malicious behavior
 pub struct ByzantineDetector {
-    etcd: EtcdStorage,
-    postgres: PostgresStorage,
+    votes: Arc<dyn VoteStore>,
+    transactions: Arc<dyn TransactionStore>,
 }
 
 impl ByzantineDetector {
     pub fn new(etcd: EtcdStorage, postgres: PostgresStorage) -> Self {
-        Self { etcd, postgres }
+        let postgres = Arc::new(postgres);
+        Self::with_stores(
+            Arc::new(ClusterStorage::new(etcd, Arc::clone(&postgres))),
+            postgres,
+        )
+    }
+
+    /// Create a detector on any storage backend, e.g. the embedded one
+    pub fn with_stores(votes: Arc<dyn VoteStore>, transactions: Arc<dyn TransactionStore>) -> Self {
+        Self { votes, transactions }
     }
 
     /// Check a vote for Byzantine violations
     ///
     /// This method performs four types of Byzantine violation detection:
-    /// 1. DoubleVote: Same node votes differently on same TX
+    /// 1. DoubleVote: Same node votes differently on same TX, in the same
+    ///    round or across rounds whose votes carry over
     /// 2. InvalidSignature: Vote signature verification fails
     /// 3. MinorityVote: Node votes against consensus after threshold reached
     /// 4. Timeout: Node doesn't respond within timeout (handled elsewhere)
-    pub async fn check_vote(&mut self, vote: &Vote) -> Result<ByzantineCheckResult> {
+    ///
+    /// Votes cast in a round that does not count any more, or not yet, are
+    /// dropped without a violation: after a round expires every node is
+    /// expected to vote again, possibly differently.
+    pub async fn check_vote(&self, vote: &Vote) -> Result<ByzantineCheckResult> {
         // Check if node is already banned
-        if self.etcd.is_peer_banned(&vote.peer_id).await? {
+        if self.votes.is_peer_banned(&vote.peer_id).await? {
             return Err(VotingError::NodeBanned {
                 peer_id: vote.peer_id.0.clone(),
             });
@@ -67,12 +85,27 @@ impl ByzantineDetector {
         }
 
         // Violation Type 2: Double Voting
-        // Store vote and check if a different vote already exists
-        if let Some(existing_vote) = self.etcd.store_vote(vote).await? {
-            if existing_vote.value != vote.value {
+        // Store and count the vote atomically, unless the node already voted.
+        // Votes of expired rounds are deleted when the next round opens, so an
+        // existing vote still counts, whichever round it was cast in.
+        let new_count = match self.votes.record_vote(vote).await? {
+            RecordedVote::Counted { count } => count,
+            RecordedVote::OutOfRound { current_round } => {
+                info!(
+                    "Ignoring vote from node_id={} for tx_id={}: round {} does not count in round {}",
+                    vote.node_id, vote.tx_id, vote.round_id, current_round
+                );
+                return Ok(ByzantineCheckResult::OutOfRound { current_round });
+            }
+            RecordedVote::Existing(existing_vote) if existing_vote.value != vote.value => {
                 warn!(
-                    "Double voting detected: peer_id={} node_id={} old_value={} new_value={}",
-                    vote.peer_id, vote.node_id, existing_vote.value, vote.value
+                    "Double voting detected: peer_id={} node_id={} old_value={} (round {}) new_value={} (round {})",
+                    vote.peer_id,
+                    vote.node_id,
+                    existing_vote.value,
+                    existing_vote.round_id,
+                    vote.value,
+                    vote.round_id
                 );
 
                 let violation = ByzantineViolation {
@@ -84,10 +117,12 @@ impl ByzantineDetector {
                     evidence: serde_json::json!({
                         "old_vote": {
                             "value": existing_vote.value,
+                            "round": existing_vote.round_id,
                             "timestamp": existing_vote.timestamp,
                         },
                         "new_vote": {
                             "value": vote.value,
+                            "round": vote.round_id,
                             "timestamp": vote.timestamp,
                         },
                     }),
@@ -96,24 +131,20 @@ impl ByzantineDetector {
 
                 self.handle_byzantine_violation(&violation).await?;
 
-                self.etcd
-                    .set_transaction_state(&vote.tx_id, TransactionState::AbortedByzantine)
+                self.votes
+                    .set_consensus_state(&vote.tx_id, TransactionState::AbortedByzantine)
                     .await?;
 
                 return Ok(ByzantineCheckResult::Rejected(
                     ByzantineViolationType::DoubleVote,
                 ));
-            } else {
-                // Same vote received again - idempotent
-                return Ok(ByzantineCheckResult::Idempotent);
             }
-        }
-
-        // Increment vote count for this value
-        let new_count = self.etcd.increment_vote_count(&vote.tx_id, vote.value).await?;
+            // Same vote received again, possibly repeated in a later round - idempotent
+            RecordedVote::Existing(_) => return Ok(ByzantineCheckResult::Idempotent),
+        };
 
         // Get all vote counts to detect minority voting attacks
-        let all_counts = self.etcd.get_all_vote_counts(&vote.tx_id).await?;
+        let all_counts = self.votes.get_all_vote_counts(&vote.tx_id).await?;
 
         let (max_value, max_count) = all_counts
             .iter()
@@ -121,7 +152,7 @@ impl ByzantineDetector {
             .map(|(&value, &count)| (value, count))
             .unwrap_or((vote.value, new_count));
 
-        let threshold = self.etcd.get_config_threshold().await?;
+        let threshold = self.votes.vote_threshold().await?;
 
         // Violation Type 3: Minority Vote Attack
         // If threshold already reached for a different value, this is suspicious
@@ -149,8 +180,8 @@ impl ByzantineDetector {
 
             self.handle_byzantine_violation(&violation).await?;
 
-            self.etcd
-                .set_transaction_state(&vote.tx_id, TransactionState::AbortedByzantine)
+            self.votes
+                .set_consensus_state(&vote.tx_id, TransactionState::AbortedByzantine)
                 .await?;
 
             return Ok(ByzantineCheckResult::Rejected(
@@ -158,19 +189,8 @@ impl ByzantineDetector {
             ));
         }
 
-        // Record valid vote to PostgreSQL
-        self.postgres.record_vote(vote).await?;
-        self.postgres.update_node_last_seen(&vote.node_id).await?;
-
-        // Update voting_rounds table with new vote count
-        if let Ok(Some(round)) = self.postgres.get_voting_round_by_txid(&vote.tx_id.0).await {
-            let _ = self.postgres.update_voting_round(
-                round.id,
-                new_count as u32,
-                false, // not yet approved
-                false, // not yet completed
-            ).await;
-        }
+        // Record valid vote for the audit trail
+        self.votes.archive_vote(vote, new_count).await?;
 
         // Check if threshold reached
         if new_count >= threshold as u64 {
@@ -179,18 +199,35 @@ impl ByzantineDetector {
                 vote.tx_id, vote.value, new_count
             );
 
-            // Update etcd
-            self.etcd
-                .set_transaction_state(&vote.tx_id, TransactionState::ThresholdReached)
+            // Publish the outcome to the other nodes
+            self.votes
+                .set_consensus_state(&vote.tx_id, TransactionState::ThresholdReached)
                 .await?;
 
-            // Update PostgreSQL to "approved" state so orchestration service picks it up
-            self.postgres
-                .update_transaction_state(&vote.tx_id, TransactionState::Approved)
+            // Move the transaction to "approved" so orchestration service picks it up.
+            // The orchestrator may have approved it already; that is not an error.
+            match self
+                .transactions
+                .transition_transaction_state(
+                    &vote.tx_id,
+                    TransactionState::Voting,
+                    TransactionState::Approved,
+                    "consensus",
+                    &format!("threshold reached: {} votes for value {}", new_count, vote.value),
+                )
                 .await
-                .map_err(|e| VotingError::StorageError(format!("Failed to update transaction state to approved: {}", e)))?;
-
-            info!("Transaction {:?} approved by consensus (threshold reached)", vote.tx_id);
+            {
+                Ok(_) => info!("Transaction {:?} approved by consensus (threshold reached)", vote.tx_id),
+                Err(VotingError::StateConflict { actual, .. }) => {
+                    info!("Transaction {:?} already moved to {} when threshold was reached", vote.tx_id, actual)
+                }
+                Err(e) => {
+                    return Err(VotingError::StorageError(format!(
+                        "Failed to update transaction state to approved: {}",
+                        e
+                    )))
+                }
+            }
 
             return Ok(ByzantineCheckResult::ThresholdReached {
                 value: vote.value,
@@ -202,12 +239,9 @@ impl ByzantineDetector {
     }
 
     /// Handle a Byzantine violation by banning the node and recording the violation
-    async fn handle_byzantine_violation(&mut self, violation: &ByzantineViolation) -> Result<()> {
-        // Ban node in etcd
-        self.etcd.ban_node(violation).await?;
-
-        // Record violation in PostgreSQL for audit trail
-        self.postgres.record_byzantine_violation(violation).await?;
+    async fn handle_byzantine_violation(&self, violation: &ByzantineViolation) -> Result<()> {
+        // Ban the node and record the violation for the audit trail
+        self.votes.ban_node(violation).await?;
 
         error!(
             "Byzantine violation detected and handled: peer_id={} type={:?}",
@@ -234,6 +268,10 @@ pub enum ByzantineCheckResult {
     Rejected(ByzantineViolationType),
     /// Idempotent vote (same vote received again)
     Idempotent,
+    /// Vote cast in a round that does not count; not a violation
+    OutOfRound {
+        current_round: u32,
+    },
 }
 
 /// Helper module for hex encoding
@@ -263,7 +301,7 @@ mod tests {
         .await
         .unwrap();
 
-        let mut detector = ByzantineDetector::new(etcd, postgres);
+        let detector = ByzantineDetector::new(etcd, postgres);
 
         let keypair = KeyPair::generate();
         let tx_id = TransactionId::from("test_tx_001");
@@ -4,7 +4,8 @@
 //! on Bitcoin transactions. It provides:
 //!
 //! - **Byzantine Detection**: Detects and handles 4 types of Byzantine violations:
-//!   1. DoubleVote: Same node votes differently on same transaction
+//!   1. DoubleVote: Same node votes differently on same transaction, within a
+//!      round or across rounds whose votes carry over
 //!   2. InvalidSignature: Vote signature verification fails
 //!   3. MinorityVote: Node votes against consensus after threshold reached
 //!   4. Timeout: Node doesn't respond within timeout (handled by network layer)
@@ -20,6 +21,15 @@
 //!   - Updates FSM based on results
 //!   - Records to PostgreSQL
 //!
+//! A transaction whose vote times out can be put to a new round. Depending
+//! on the [`RevotePolicy`](threshold_types::RevotePolicy), votes of earlier
+//! rounds keep counting or expire, in which case every node votes again and
+//! votes still arriving for an old round are dropped as out of round.
+//!
+//! Storage is reached through the `threshold_storage` traits, so
+//! [`VoteProcessor::with_stores`] can run the same logic on the embedded
+//! SQLite backend, e.g. a whole cluster inside one test process.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -2,7 +2,7 @@ use crate::byzantine::{ByzantineCheckResult, ByzantineDetector};
 use crate::fsm::{VoteFSM, VoteState};
 use std::collections::HashMap;
 use std::sync::Arc;
-use threshold_storage::{EtcdStorage, PostgresStorage};
+use threshold_storage::{EtcdStorage, PostgresStorage, TransactionStore, VoteStore};
 use threshold_types::{ConsensusResult, Result, TransactionId, Vote, VotingError};
 use tokio::sync::Mutex;
 use tracing::{info, warn};
@@ -12,21 +12,28 @@ use tracing::{info, warn};
 /// Responsibilities:
 /// - Receives votes from network layer
 /// - Validates votes using ByzantineDetector
-/// - Counts votes using etcd
+/// - Counts votes in the shared vote store (etcd in production)
 /// - Updates transaction FSM based on vote results
 /// - Records to PostgreSQL for audit trail
 pub struct VoteProcessor {
-    byzantine_detector: Arc<Mutex<ByzantineDetector>>,
+    byzantine_detector: Arc<ByzantineDetector>,
     fsm_registry: Arc<Mutex<HashMap<String, VoteFSM>>>,
 }
 
 impl VoteProcessor {
     /// Create a new VoteProcessor with storage backends
     pub fn new(etcd: EtcdStorage, postgres: PostgresStorage) -> Self {
-        let detector = ByzantineDetector::new(etcd, postgres);
+        Self::with_detector(ByzantineDetector::new(etcd, postgres))
+    }
+
+    /// Create a VoteProcessor on any storage backend, e.g. the embedded one
+    pub fn with_stores(votes: Arc<dyn VoteStore>, transactions: Arc<dyn TransactionStore>) -> Self {
+        Self::with_detector(ByzantineDetector::with_stores(votes, transactions))
+    }
 
+    fn with_detector(detector: ByzantineDetector) -> Self {
         Self {
-            byzantine_detector: Arc::new(Mutex::new(detector)),
+            byzantine_detector: Arc::new(detector),
             fsm_registry: Arc::new(Mutex::new(HashMap::new())),
         }
     }
@@ -64,12 +71,11 @@ impl VoteProcessor {
             });
         }
 
-        // Release FSM lock before calling Byzantine detector (which needs storage locks)
+        // Release FSM lock before calling Byzantine detector
         drop(fsm_registry);
 
-        // Validate vote through Byzantine detector
-        let mut detector = self.byzantine_detector.lock().await;
-        let check_result = detector.check_vote(&vote).await?;
+        // Validate vote through Byzantine detector; votes are checked concurrently
+        let check_result = self.byzantine_detector.check_vote(&vote).await?;
 
         match check_result {
             ByzantineCheckResult::Accepted { count } => {
@@ -119,6 +125,13 @@ impl VoteProcessor {
                 );
                 Ok(VoteProcessingResult::Idempotent)
             }
+            ByzantineCheckResult::OutOfRound { current_round } => {
+                info!(
+                    "Out-of-round vote for tx_id={} from node_id={}: cast in round {}, current round {}",
+                    vote.tx_id, vote.node_id, vote.round_id, current_round
+                );
+                Ok(VoteProcessingResult::OutOfRound { current_round })
+            }
         }
     }
 
@@ -173,6 +186,11 @@ pub enum VoteProcessingResult {
     },
     /// Idempotent vote (duplicate)
     Idempotent,
+    /// Vote for a round that does not count; the node should vote in
+    /// `current_round`
+    OutOfRound {
+        current_round: u32,
+    },
 }
 
 #[cfg(test)]
@@ -253,4 +271,136 @@ mod tests {
         assert!(state.is_some());
         assert_eq!(state.unwrap(), VoteState::Collecting);
     }
+
+    fn voting_transaction(tx_id: &TransactionId) -> threshold_types::Transaction {
+        threshold_types::Transaction {
+            id: 0,
+            txid: tx_id.clone(),
+            state: threshold_types::TransactionState::Voting,
+            unsigned_tx: vec![0u8; 4],
+            signed_tx: None,
+            recipient: "tb1qrecipient".to_string(),
+            amount_sats: 50_000,
+            fee_sats: 500,
+            metadata: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Five nodes sharing one embedded store, like a cluster sharing etcd
+    #[tokio::test]
+    async fn test_in_process_cluster_reaches_consensus() {
+        use threshold_storage::SqliteStorage;
+        use threshold_types::TransactionState;
+
+        let storage = Arc::new(SqliteStorage::open_in_memory().unwrap());
+        storage.set_vote_threshold(4).unwrap();
+
+        let tx_id = TransactionId::from("in_process_tx");
+        storage.create_transaction(&voting_transaction(&tx_id)).await.unwrap();
+
+        let nodes: Vec<Arc<VoteProcessor>> = (0..5)
+            .map(|_| Arc::new(VoteProcessor::with_stores(storage.clone(), storage.clone())))
+            .collect();
+
+        // Nodes 1-4 approve concurrently, each vote arriving at its own node
+        let handles: Vec<_> = (1..=4u64)
+            .map(|n| {
+                let processor = Arc::clone(&nodes[n as usize - 1]);
+                let vote = Vote::new(NodeId::from(n), tx_id.clone(), 1, true, Some(42));
+                tokio::spawn(async move { processor.process_vote(vote).await.unwrap() })
+            })
+            .collect();
+
+        let mut reached = 0;
+        for handle in handles {
+            if let VoteProcessingResult::ConsensusReached(result) = handle.await.unwrap() {
+                assert_eq!(result.value, 42);
+                assert_eq!(result.vote_count, 4);
+                reached += 1;
+            }
+        }
+        assert_eq!(reached, 1);
+
+        let tx = storage.get_transaction(&tx_id).await.unwrap().unwrap();
+        assert_eq!(tx.state, TransactionState::Approved);
+        assert_eq!(
+            storage.get_consensus_state(&tx_id).unwrap(),
+            Some(TransactionState::ThresholdReached)
+        );
+
+        // Node 1 changing its vote is caught by any node and gets it banned
+        let double_vote = Vote::new(NodeId::from(1), tx_id.clone(), 1, true, Some(99));
+        let peer_id = double_vote.peer_id.clone();
+        let result = nodes[4].process_vote(double_vote).await.unwrap();
+        assert!(matches!(
+            result,
+            VoteProcessingResult::Rejected {
+                violation_type: threshold_types::ByzantineViolationType::DoubleVote
+            }
+        ));
+        assert!(storage.is_peer_banned(&peer_id).await.unwrap());
+        assert_eq!(storage.get_node_violations(NodeId::from(1)).unwrap().len(), 1);
+    }
+
+    /// A second round carries the votes of the first over or expires them
+    #[tokio::test]
+    async fn test_revote_rounds_carry_over_or_expire() {
+        use threshold_storage::SqliteStorage;
+        use threshold_types::{ByzantineViolationType, RevotePolicy};
+
+        let storage = Arc::new(SqliteStorage::open_in_memory().unwrap());
+        storage.set_vote_threshold(4).unwrap();
+        let processor = VoteProcessor::with_stores(storage.clone(), storage.clone());
+        let vote = |n: u64, tx_id: &TransactionId, round: u64, value: u64| {
+            Vote::new(NodeId::from(n), tx_id.clone(), round, true, Some(value))
+        };
+
+        // Carry over: round 1 votes still count, repeating one is harmless
+        let carried = TransactionId::from("carry_over_tx");
+        storage.create_transaction(&voting_transaction(&carried)).await.unwrap();
+        for n in 1..=2 {
+            processor.process_vote(vote(n, &carried, 1, 42)).await.unwrap();
+        }
+        storage.open_vote_round(&carried, 2, RevotePolicy::CarryOver).await.unwrap();
+
+        assert!(matches!(
+            processor.process_vote(vote(1, &carried, 2, 42)).await.unwrap(),
+            VoteProcessingResult::Idempotent
+        ));
+        assert!(matches!(
+            processor.process_vote(vote(3, &carried, 2, 42)).await.unwrap(),
+            VoteProcessingResult::Accepted { count: 3 }
+        ));
+        assert!(matches!(
+            processor.process_vote(vote(4, &carried, 2, 42)).await.unwrap(),
+            VoteProcessingResult::ConsensusReached(_)
+        ));
+
+        // ...so changing a carried-over vote is a double vote
+        let other_node = VoteProcessor::with_stores(storage.clone(), storage.clone());
+        assert!(matches!(
+            other_node.process_vote(vote(2, &carried, 2, 99)).await.unwrap(),
+            VoteProcessingResult::Rejected { violation_type: ByzantineViolationType::DoubleVote }
+        ));
+
+        // Expire: late round 1 votes are dropped and nodes may change their vote
+        let expired = TransactionId::from("expire_tx");
+        storage.create_transaction(&voting_transaction(&expired)).await.unwrap();
+        for n in 3..=5 {
+            processor.process_vote(vote(n, &expired, 1, 42)).await.unwrap();
+        }
+        storage.open_vote_round(&expired, 2, RevotePolicy::Expire).await.unwrap();
+
+        assert!(matches!(
+            processor.process_vote(vote(1, &expired, 1, 42)).await.unwrap(),
+            VoteProcessingResult::OutOfRound { current_round: 2 }
+        ));
+        assert!(matches!(
+            processor.process_vote(vote(3, &expired, 2, 99)).await.unwrap(),
+            VoteProcessingResult::Accepted { count: 1 }
+        ));
+        assert!(!storage.is_peer_banned(&vote(3, &expired, 2, 99).peer_id).await.unwrap());
+    }
 }
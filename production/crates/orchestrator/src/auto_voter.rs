@@ -5,13 +5,13 @@ use tokio::sync::mpsc;
 use tracing::{error, info, warn};
 
 use threshold_consensus::VoteProcessor;
-use threshold_storage::PostgresStorage;
+use threshold_storage::TransactionStore;
 use threshold_types::{NodeId, Transaction, TransactionState, Vote, VoteRequest};
 
 /// Automatic voter that processes vote requests
 pub struct AutoVoter {
     node_id: NodeId,
-    postgres: Arc<PostgresStorage>,
+    transactions: Arc<dyn TransactionStore>,
     vote_processor: Arc<VoteProcessor>,
     receiver: mpsc::Receiver<VoteRequest>,
 }
@@ -20,13 +20,13 @@ impl AutoVoter {
     /// Create a new auto voter
     pub fn new(
         node_id: NodeId,
-        postgres: Arc<PostgresStorage>,
+        transactions: Arc<dyn TransactionStore>,
         vote_processor: Arc<VoteProcessor>,
         receiver: mpsc::Receiver<VoteRequest>,
     ) -> Self {
         Self {
             node_id,
-            postgres,
+            transactions,
             vote_processor,
             receiver,
         }
@@ -59,7 +59,7 @@ impl AutoVoter {
 
         // 1. Load transaction from database
         let tx = self
-            .postgres
+            .transactions
             .get_transaction(&req.tx_id)
             .await?
             .ok_or_else(|| format!("Transaction not found: {}", req.tx_id))?;
@@ -136,3 +136,80 @@ impl AutoVoter {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use threshold_storage::SqliteStorage;
+    use threshold_types::TxId;
+
+    fn voting_transaction(tx_id: &TxId) -> Transaction {
+        Transaction {
+            id: 0,
+            txid: tx_id.clone(),
+            state: TransactionState::Voting,
+            unsigned_tx: vec![0u8; 4],
+            signed_tx: None,
+            recipient: "tb1qrecipient".to_string(),
+            amount_sats: 50_000,
+            fee_sats: 500,
+            metadata: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Five nodes, each with its own voter and vote processor, sharing one
+    /// embedded store the way production nodes share PostgreSQL and etcd
+    #[tokio::test]
+    async fn test_in_process_cluster_approves_transaction() {
+        let storage = Arc::new(SqliteStorage::open_in_memory().unwrap());
+        storage.set_vote_threshold(4).unwrap();
+
+        let tx_id = TxId::from("cluster_tx");
+        storage.create_transaction(&voting_transaction(&tx_id)).await.unwrap();
+
+        let mut triggers = Vec::new();
+        let mut handles = Vec::new();
+        for n in 1..=5u64 {
+            let (trigger, receiver) = mpsc::channel(8);
+            let processor = Arc::new(VoteProcessor::with_stores(storage.clone(), storage.clone()));
+            handles.push(AutoVoter::new(NodeId(n), storage.clone(), processor, receiver).start());
+            triggers.push(trigger);
+        }
+
+        let request = VoteRequest {
+            coordinator_id: NodeId(1),
+            tx_id: tx_id.clone(),
+            round_id: 1,
+            round_number: 1,
+            threshold: 4,
+            timeout_at: chrono::Utc::now() + chrono::Duration::seconds(60),
+        };
+        for trigger in &triggers {
+            trigger.send(request.clone()).await.unwrap();
+        }
+
+        let approved = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let tx = storage.get_transaction(&tx_id).await.unwrap().unwrap();
+                if tx.state == TransactionState::Approved {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+        assert!(approved.is_ok(), "cluster did not approve the transaction");
+        assert_eq!(
+            storage.get_consensus_state(&tx_id).unwrap(),
+            Some(TransactionState::ThresholdReached)
+        );
+
+        drop(triggers);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    }
+}
@@ -91,8 +91,8 @@ pub struct AuxInfoCeremony {
 pub struct AuxInfoService {
     /// PostgreSQL storage
     postgres: Arc<PostgresStorage>,
-    /// etcd storage for distributed coordination (with interior mutability)
-    etcd: Arc<Mutex<EtcdStorage>>,
+    /// etcd storage for distributed coordination
+    etcd: EtcdStorage,
     /// QUIC engine for network communication
     quic: Arc<QuicEngine>,
     /// Message router for protocol communication
@@ -113,7 +113,7 @@ impl AuxInfoService {
     /// Create new aux info service
     pub fn new(
         postgres: Arc<PostgresStorage>,
-        etcd: Arc<Mutex<EtcdStorage>>,
+        etcd: EtcdStorage,
         quic: Arc<QuicEngine>,
         message_router: Arc<MessageRouter>,
         node_id: NodeId,
@@ -132,6 +132,15 @@ impl AuxInfoService {
         }
     }
 
+    /// Replace the HTTP client used for calls to other nodes' internal endpoints
+    ///
+    /// The internal listener only accepts cluster client certificates, so this
+    /// is the mTLS client built at startup.
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
     /// Initiate aux_info generation ceremony
     ///
     /// This will:
@@ -229,6 +238,17 @@ impl AuxInfoService {
                     }
                 }
 
+                // Only the initiating node reports completion, once per ceremony
+                crate::webhook_dispatcher::emit_event(
+                    &self.postgres,
+                    threshold_storage::WEBHOOK_EVENT_AUX_INFO_COMPLETED,
+                    serde_json::json!({
+                        "session_id": session_id,
+                        "num_parties": num_parties,
+                    }),
+                )
+                .await;
+
                 Ok(AuxInfoResult {
                     session_id,
                     party_index,
@@ -523,11 +543,13 @@ impl AuxInfoService {
 
         #[derive(Debug, Clone, Serialize, Deserialize)]
         struct AuxInfoJoinRequest {
+            coordinator_id: u64,
             session_id: String,
             num_parties: u16,
         }
 
         let join_request = AuxInfoJoinRequest {
+            coordinator_id: self.node_id.0,
             session_id: session_id.to_string(),
             num_parties,
         };
@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use threshold_types::RevotePolicy;
 
 /// Configuration for the orchestration service
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +13,13 @@ pub struct OrchestrationConfig {
     /// Timeout for voting phase
     pub voting_timeout: Duration,
 
+    /// Voting rounds before a transaction that does not reach quorum fails;
+    /// each round gets its own `voting_timeout`
+    pub max_voting_rounds: u32,
+
+    /// Whether votes of a timed-out round count in the next one
+    pub revote_policy: RevotePolicy,
+
     /// Timeout for signing phase
     pub signing_timeout: Duration,
 
@@ -45,6 +53,8 @@ impl Default for OrchestrationConfig {
         Self {
             poll_interval: Duration::from_secs(5),
             voting_timeout: Duration::from_secs(60),
+            max_voting_rounds: 3,
+            revote_policy: RevotePolicy::CarryOver,
             signing_timeout: Duration::from_secs(120),
             broadcast_timeout: Duration::from_secs(30),
             max_retries: 3,
@@ -80,6 +90,16 @@ impl OrchestrationConfigBuilder {
         self
     }
 
+    pub fn max_voting_rounds(mut self, rounds: u32) -> Self {
+        self.config.max_voting_rounds = rounds;
+        self
+    }
+
+    pub fn revote_policy(mut self, policy: RevotePolicy) -> Self {
+        self.config.revote_policy = policy;
+        self
+    }
+
     pub fn signing_timeout(mut self, timeout: Duration) -> Self {
         self.config.signing_timeout = timeout;
         self
@@ -146,6 +166,8 @@ mod tests {
         assert_eq!(config.poll_interval, Duration::from_secs(5));
         assert_eq!(config.voting_timeout, Duration::from_secs(60));
         assert_eq!(config.max_retries, 3);
+        assert_eq!(config.max_voting_rounds, 3);
+        assert_eq!(config.revote_policy, RevotePolicy::CarryOver);
         assert!(config.enable_byzantine_detection);
     }
 
@@ -154,11 +176,15 @@ mod tests {
         let config = OrchestrationConfigBuilder::new()
             .poll_interval(Duration::from_secs(10))
             .max_retries(5)
+            .max_voting_rounds(1)
+            .revote_policy(RevotePolicy::Expire)
             .enable_byzantine_detection(false)
             .build();
 
         assert_eq!(config.poll_interval, Duration::from_secs(10));
         assert_eq!(config.max_retries, 5);
+        assert_eq!(config.max_voting_rounds, 1);
+        assert_eq!(config.revote_policy, RevotePolicy::Expire);
         assert!(!config.enable_byzantine_detection);
     }
 }
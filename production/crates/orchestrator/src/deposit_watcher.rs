@@ -0,0 +1,481 @@
+//! Deposit watcher for user addresses.
+//!
+//! Periodically scans every `user_addresses` entry through the chain backend:
+//! - Records each output paying to a user address as a pending deposit,
+//!   except the change of transactions the wallet created itself
+//! - Tracks confirmations and the containing block of unsettled deposits
+//! - Credits the user's ledger once `required_confirmations` is reached
+//! - Reverses the credit if a reorg drops the deposit below that depth
+//!
+//! Deposits stop being tracked once they reach `finality_depth`. Crediting
+//! and reversal are guarded by the deposit status in PostgreSQL, so running
+//! the watcher on every node never credits a deposit twice.
+
+use crate::error::{OrchestrationError, Result};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+
+use threshold_bitcoin::{BitcoinClient, TxStatus};
+use threshold_storage::{Deposit, PostgresStorage};
+
+/// Deposit watcher configuration
+#[derive(Debug, Clone)]
+pub struct DepositWatcherConfig {
+    /// Time between scans
+    pub poll_interval: Duration,
+    /// Confirmations before a deposit is credited
+    pub required_confirmations: u32,
+    /// Confirmations after which a deposit is no longer re-checked for reorgs
+    pub finality_depth: u32,
+}
+
+impl Default for DepositWatcherConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(60),
+            required_confirmations: 6,
+            finality_depth: 100,
+        }
+    }
+}
+
+impl DepositWatcherConfig {
+    /// Load configuration from `DEPOSIT_*` environment variables, falling
+    /// back to defaults for anything unset or unparsable.
+    pub fn from_env() -> Self {
+        fn env<T: std::str::FromStr>(key: &str) -> Option<T> {
+            std::env::var(key).ok().and_then(|v| v.parse().ok())
+        }
+
+        let defaults = Self::default();
+        let required_confirmations = env("DEPOSIT_CONFIRMATIONS")
+            .unwrap_or(defaults.required_confirmations)
+            .max(1);
+
+        Self {
+            poll_interval: env("DEPOSIT_POLL_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.poll_interval),
+            required_confirmations,
+            finality_depth: env("DEPOSIT_FINALITY_DEPTH")
+                .unwrap_or(defaults.finality_depth)
+                .max(required_confirmations),
+        }
+    }
+}
+
+/// What to do with a deposit after observing its chain state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepositAction {
+    /// Only update the recorded confirmations
+    Track,
+    /// Credit the user's ledger
+    Credit,
+    /// Undo the deposit; `dropped` if the transaction no longer exists
+    Reverse { dropped: bool },
+}
+
+/// Decide the next step for a deposit.
+///
+/// `chain` is `None` when the backend no longer knows the transaction.
+pub fn deposit_action(
+    status: &str,
+    chain: Option<&TxStatus>,
+    tip_height: u64,
+    required_confirmations: u32,
+) -> DepositAction {
+    let Some(chain) = chain else {
+        return match status {
+            "pending" | "credited" => DepositAction::Reverse { dropped: true },
+            _ => DepositAction::Track,
+        };
+    };
+
+    let confirmations = chain.confirmations(tip_height);
+    match status {
+        "pending" if confirmations >= required_confirmations => DepositAction::Credit,
+        "credited" if confirmations < required_confirmations => {
+            DepositAction::Reverse { dropped: false }
+        }
+        _ => DepositAction::Track,
+    }
+}
+
+/// Whether an output paying to a user `address` is a deposit.
+///
+/// `wallet_recipient` is the recipient of the wallet's own transaction with
+/// the output's txid, if there is one. Such a transaction only deposits to
+/// its recipient; its other outputs are change, which the owner's ledger
+/// was never debited for.
+pub fn is_deposit_output(wallet_recipient: Option<&str>, address: &str) -> bool {
+    wallet_recipient.is_none_or(|recipient| recipient == address)
+}
+
+/// Result of a single scan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DepositScanStats {
+    pub addresses_scanned: usize,
+    pub deposits_discovered: usize,
+    pub deposits_credited: usize,
+    pub deposits_reversed: usize,
+}
+
+/// Deposit watcher service.
+pub struct DepositWatcher {
+    config: DepositWatcherConfig,
+    postgres: Arc<PostgresStorage>,
+    bitcoin: Arc<BitcoinClient>,
+    shutdown: Arc<RwLock<bool>>,
+}
+
+impl DepositWatcher {
+    /// Start the deposit watcher in the background
+    pub fn start(self: Arc<Self>) -> JoinHandle<Result<()>> {
+        tokio::spawn(async move {
+            info!(
+                "Deposit watcher started (interval: {:?}, confirmations: {})",
+                self.config.poll_interval, self.config.required_confirmations
+            );
+
+            match self.run().await {
+                Ok(()) => {
+                    info!("Deposit watcher stopped normally");
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Deposit watcher error: {}", e);
+                    Err(e)
+                }
+            }
+        })
+    }
+
+    /// Main scanning loop
+    async fn run(&self) -> Result<()> {
+        let mut interval = interval(self.config.poll_interval);
+
+        loop {
+            if *self.shutdown.read().await {
+                info!("Shutdown signal received, stopping deposit watcher");
+                return Ok(());
+            }
+
+            interval.tick().await;
+
+            match self.scan_once().await {
+                Ok(stats) => debug!("Deposit scan complete: {:?}", stats),
+                Err(e) => error!("Deposit scan failed: {}", e),
+            }
+        }
+    }
+
+    /// Run one full scan: discover new deposits, then settle known ones.
+    pub async fn scan_once(&self) -> Result<DepositScanStats> {
+        let mut stats = DepositScanStats::default();
+
+        let tip_height = self
+            .bitcoin
+            .get_block_height()
+            .await
+            .map_err(|e| OrchestrationError::Bitcoin(e.to_string()))?;
+
+        self.discover_deposits(&mut stats).await?;
+        self.settle_deposits(tip_height, &mut stats).await?;
+
+        if stats.deposits_discovered + stats.deposits_credited + stats.deposits_reversed > 0 {
+            info!(
+                "Deposit scan at height {}: {} new, {} credited, {} reversed",
+                tip_height,
+                stats.deposits_discovered,
+                stats.deposits_credited,
+                stats.deposits_reversed
+            );
+        }
+
+        Ok(stats)
+    }
+
+    /// Record outputs paying to user addresses.
+    async fn discover_deposits(&self, stats: &mut DepositScanStats) -> Result<()> {
+        let addresses = self
+            .postgres
+            .list_all_user_addresses()
+            .await
+            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+
+        for address in addresses {
+            stats.addresses_scanned += 1;
+
+            let txs = match self.bitcoin.get_address_txs(&address.address).await {
+                Ok(txs) => txs,
+                Err(e) => {
+                    // One failing address must not block the others
+                    warn!("Failed to fetch transactions for {}: {}", address.address, e);
+                    continue;
+                }
+            };
+
+            for tx in &txs {
+                if tx.outputs_to(&address.address).next().is_none() {
+                    continue;
+                }
+                let wallet_recipient = self
+                    .postgres
+                    .get_wallet_transaction_recipient(&tx.txid)
+                    .await
+                    .map_err(|e| OrchestrationError::Storage(e.into()))?;
+                if !is_deposit_output(wallet_recipient.as_deref(), &address.address) {
+                    debug!("Skipping change of wallet transaction {} to {}", tx.txid, address.address);
+                    continue;
+                }
+
+                for (vout, value) in tx.outputs_to(&address.address) {
+                    if value == 0 {
+                        continue;
+                    }
+
+                    let recorded = self
+                        .postgres
+                        .record_deposit(&address.user_id, &address.address, &tx.txid, vout, value)
+                        .await
+                        .map_err(|e| OrchestrationError::Storage(e.into()))?;
+
+                    if recorded {
+                        info!(
+                            "Detected deposit {}:{} of {} sats to {} (user {})",
+                            tx.txid, vout, value, address.address, address.user_id
+                        );
+                        stats.deposits_discovered += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Update confirmations of unsettled deposits and credit or reverse them.
+    async fn settle_deposits(&self, tip_height: u64, stats: &mut DepositScanStats) -> Result<()> {
+        let deposits = self
+            .postgres
+            .list_unsettled_deposits(self.config.finality_depth)
+            .await
+            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+
+        for deposit in deposits {
+            let chain = match self.bitcoin.get_tx_status(&deposit.txid).await {
+                Ok(chain) => chain,
+                Err(e) => {
+                    warn!("Failed to fetch status of {}: {}", deposit.txid, e);
+                    continue;
+                }
+            };
+
+            self.settle_deposit(&deposit, chain.as_ref(), tip_height, stats)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn settle_deposit(
+        &self,
+        deposit: &Deposit,
+        chain: Option<&TxStatus>,
+        tip_height: u64,
+        stats: &mut DepositScanStats,
+    ) -> Result<()> {
+        let action = deposit_action(
+            &deposit.status,
+            chain,
+            tip_height,
+            self.config.required_confirmations,
+        );
+
+        if let DepositAction::Reverse { dropped } = action {
+            warn!(
+                "Deposit {}:{} lost confirmations (dropped: {}), reversing",
+                deposit.txid, deposit.vout, dropped
+            );
+            self.postgres
+                .reverse_deposit(deposit.id, dropped)
+                .await
+                .map_err(|e| OrchestrationError::Storage(e.into()))?;
+            stats.deposits_reversed += 1;
+
+            if dropped {
+                return Ok(());
+            }
+        }
+
+        if let Some(chain) = chain {
+            if deposit.block_hash.is_some() && chain.block_hash != deposit.block_hash {
+                warn!(
+                    "Deposit {}:{} moved from block {:?} to {:?}",
+                    deposit.txid, deposit.vout, deposit.block_hash, chain.block_hash
+                );
+            }
+
+            self.postgres
+                .update_deposit_chain_state(
+                    deposit.id,
+                    chain.confirmations(tip_height),
+                    chain.block_height.filter(|_| chain.confirmed),
+                    chain.block_hash.as_deref(),
+                )
+                .await
+                .map_err(|e| OrchestrationError::Storage(e.into()))?;
+        }
+
+        if action == DepositAction::Credit {
+            let credited = self
+                .postgres
+                .credit_deposit(deposit.id)
+                .await
+                .map_err(|e| OrchestrationError::Storage(e.into()))?;
+            if credited {
+                stats.deposits_credited += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Initiate graceful shutdown
+    pub async fn shutdown(&self) {
+        info!("Initiating deposit watcher shutdown");
+        *self.shutdown.write().await = true;
+    }
+}
+
+/// Builder for DepositWatcher
+pub struct DepositWatcherBuilder {
+    config: Option<DepositWatcherConfig>,
+    postgres: Option<Arc<PostgresStorage>>,
+    bitcoin: Option<Arc<BitcoinClient>>,
+}
+
+impl DepositWatcherBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: None,
+            postgres: None,
+            bitcoin: None,
+        }
+    }
+
+    pub fn with_config(mut self, config: DepositWatcherConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn with_postgres(mut self, postgres: Arc<PostgresStorage>) -> Self {
+        self.postgres = Some(postgres);
+        self
+    }
+
+    pub fn with_bitcoin(mut self, bitcoin: Arc<BitcoinClient>) -> Self {
+        self.bitcoin = Some(bitcoin);
+        self
+    }
+
+    pub fn build(self) -> Result<Arc<DepositWatcher>> {
+        let config = self.config.unwrap_or_default();
+        let postgres = self.postgres
+            .ok_or_else(|| OrchestrationError::Config("PostgresStorage is required".to_string()))?;
+        let bitcoin = self.bitcoin
+            .ok_or_else(|| OrchestrationError::Config("BitcoinClient is required".to_string()))?;
+
+        Ok(Arc::new(DepositWatcher {
+            config,
+            postgres,
+            bitcoin,
+            shutdown: Arc::new(RwLock::new(false)),
+        }))
+    }
+}
+
+impl Default for DepositWatcherBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirmed_at(height: u64) -> TxStatus {
+        TxStatus {
+            confirmed: true,
+            block_height: Some(height),
+            block_hash: Some(format!("{:064x}", height)),
+        }
+    }
+
+    #[test]
+    fn test_pending_credited_at_depth() {
+        let status = confirmed_at(100);
+        assert_eq!(deposit_action("pending", Some(&status), 104, 6), DepositAction::Track);
+        assert_eq!(deposit_action("pending", Some(&status), 105, 6), DepositAction::Credit);
+        assert_eq!(deposit_action("credited", Some(&status), 150, 6), DepositAction::Track);
+    }
+
+    #[test]
+    fn test_mempool_deposit_tracked() {
+        let mempool = TxStatus::default();
+        assert_eq!(deposit_action("pending", Some(&mempool), 200, 1), DepositAction::Track);
+    }
+
+    #[test]
+    fn test_reorg_below_depth_reverses_credit() {
+        // Re-included in a later block after a reorg: only 2 confirmations left
+        let status = confirmed_at(104);
+        assert_eq!(
+            deposit_action("credited", Some(&status), 105, 6),
+            DepositAction::Reverse { dropped: false }
+        );
+
+        // Back in the mempool
+        assert_eq!(
+            deposit_action("credited", Some(&TxStatus::default()), 105, 6),
+            DepositAction::Reverse { dropped: false }
+        );
+    }
+
+    #[test]
+    fn test_dropped_transaction() {
+        assert_eq!(
+            deposit_action("credited", None, 105, 6),
+            DepositAction::Reverse { dropped: true }
+        );
+        assert_eq!(
+            deposit_action("pending", None, 105, 6),
+            DepositAction::Reverse { dropped: true }
+        );
+        assert_eq!(deposit_action("reversed", None, 105, 6), DepositAction::Track);
+    }
+
+    #[test]
+    fn test_withdrawal_change_not_credited() {
+        let user_address = "tb1qchange";
+
+        // Change of a withdrawal to an external recipient
+        assert!(!is_deposit_output(Some("tb1qexternal"), user_address));
+        // Withdrawal paying the user address itself
+        assert!(is_deposit_output(Some(user_address), user_address));
+        // Transaction the wallet did not create
+        assert!(is_deposit_output(None, user_address));
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config = DepositWatcherConfig::default();
+        assert!(config.finality_depth >= config.required_confirmations);
+        assert!(config.required_confirmations > 0);
+    }
+}
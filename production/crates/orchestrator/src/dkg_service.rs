@@ -33,6 +33,7 @@ use protocols::frost::keygen::ProtocolMessage as FrostMessage;
 
 // Bitcoin address derivation
 use common::bitcoin_address::{derive_p2tr_address, derive_p2wpkh_address, BitcoinNetwork};
+use common::{output_descriptor, DescriptorKind, ExtendedPubKey};
 
 // Async channel for message passing
 use async_channel;
@@ -67,6 +68,10 @@ pub struct DkgResult {
     pub public_key: Vec<u8>,
     /// Bitcoin address derived from public key
     pub address: String,
+    /// Root BIP32 extended public key (xpub) for watch-only export
+    pub xpub: Option<String>,
+    /// BIP-380 output descriptor matching `address`
+    pub descriptor: Option<String>,
     /// Threshold (e.g., 4 for 4-of-5)
     pub threshold: u32,
     /// Total participants
@@ -112,6 +117,9 @@ pub struct DkgCeremony {
     pub public_key: Option<Vec<u8>>,
     /// Bitcoin address derived from public key
     pub address: Option<String>,
+    /// Root BIP32 extended public key (xpub)
+    #[serde(default)]
+    pub xpub: Option<String>,
     pub error: Option<String>,
 }
 
@@ -129,6 +137,7 @@ impl DkgCeremony {
             started_at: self.started_at,
             completed_at: self.completed_at,
             error: self.error.clone(),
+            xpub: self.xpub.clone(),
         }
     }
 
@@ -159,6 +168,7 @@ impl DkgCeremony {
             completed_at: storage.completed_at,
             public_key: storage.public_key,
             address: storage.address,
+            xpub: storage.xpub,
             error: storage.error,
         }
     }
@@ -168,8 +178,8 @@ impl DkgCeremony {
 pub struct DkgService {
     /// PostgreSQL storage for key shares (encrypted)
     postgres: Arc<PostgresStorage>,
-    /// etcd storage for ceremony coordination (with interior mutability)
-    etcd: Arc<Mutex<EtcdStorage>>,
+    /// etcd storage for ceremony coordination
+    etcd: EtcdStorage,
     /// QUIC network engine for P2P communication
     quic: Arc<QuicEngine>,
     /// Message router for protocol communication
@@ -194,7 +204,7 @@ impl DkgService {
     /// Create new DKG service
     pub fn new(
         postgres: Arc<PostgresStorage>,
-        etcd: Arc<Mutex<EtcdStorage>>,
+        etcd: EtcdStorage,
         quic: Arc<QuicEngine>,
         message_router: Arc<MessageRouter>,
         node_id: NodeId,
@@ -215,6 +225,15 @@ impl DkgService {
         }
     }
 
+    /// Replace the HTTP client used for calls to other nodes' internal endpoints
+    ///
+    /// The internal listener only accepts cluster client certificates, so this
+    /// is the mTLS client built at startup.
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
     /// Set presignature service for automatic presignature generation after DKG
     ///
     /// This method allows setting the presignature service after DkgService creation
@@ -305,20 +324,16 @@ impl DkgService {
             ));
         }
 
-        // Acquire distributed lock for DKG
-        let lock_key = "/locks/dkg";
-        let lock_acquired = {
-            let etcd = self.etcd.lock().await;
-            etcd.acquire_lock(lock_key, 300) // 5 minute timeout
-                .await
-                .map_err(|e| OrchestrationError::StorageError(format!("Failed to acquire DKG lock: {}", e)))?
-        };
-
-        if !lock_acquired {
-            return Err(OrchestrationError::CeremonyInProgress(
-                "Another DKG ceremony is already running".to_string(),
-            ));
-        }
+        // Acquire distributed lock for DKG; its lease is kept alive for as
+        // long as the ceremony runs
+        let lock = self
+            .etcd
+            .try_acquire_lock("/locks/dkg")
+            .await
+            .map_err(|e| OrchestrationError::StorageError(format!("Failed to acquire DKG lock: {}", e)))?
+            .ok_or_else(|| {
+                OrchestrationError::CeremonyInProgress("Another DKG ceremony is already running".to_string())
+            })?;
 
         // Create ceremony record
         let session_id = Uuid::new_v4();
@@ -336,6 +351,7 @@ impl DkgService {
             completed_at: None,
             public_key: None,
             address: None,
+            xpub: None,
             error: None,
         };
 
@@ -366,17 +382,15 @@ impl DkgService {
         };
 
         // Release lock
-        {
-            let etcd = self.etcd.lock().await;
-            etcd.release_lock(lock_key)
-                .await
-                .map_err(|e| OrchestrationError::StorageError(format!("Failed to release lock: {}", e)))?;
-        }
+        lock.release()
+            .await
+            .map_err(|e| OrchestrationError::StorageError(format!("Failed to release lock: {}", e)))?;
 
         match result {
-            Ok(public_key) => {
+            Ok((public_key, xpub)) => {
                 // Derive Bitcoin address first
                 let address = self.derive_address(protocol, &public_key)?;
+                let descriptor = xpub.as_deref().and_then(|x| Self::export_descriptor(protocol, x));
 
                 // Update ceremony status to completed
                 let mut ceremonies = self.active_ceremonies.write().await;
@@ -385,10 +399,11 @@ impl DkgService {
                     ceremony.completed_at = Some(Utc::now());
                     ceremony.public_key = Some(public_key.clone());
                     ceremony.address = Some(address.clone());
+                    ceremony.xpub = xpub.clone();
 
                     // Update PostgreSQL
                     self.postgres
-                        .complete_dkg_ceremony(session_id, &public_key, &address)
+                        .complete_dkg_ceremony(session_id, &public_key, &address, xpub.as_deref())
                         .await
                         .map_err(|e| {
                             OrchestrationError::StorageError(format!(
@@ -401,7 +416,7 @@ impl DkgService {
                 // Store public key in etcd for cluster-wide access
                 let pubkey_key = format!("/cluster/public_keys/{}", protocol);
                 {
-                    let etcd = self.etcd.lock().await;
+                    let etcd = &self.etcd;
                     etcd.put(&pubkey_key, &public_key).await
                         .map_err(|e| OrchestrationError::Storage(e.into()))?;
                 }
@@ -416,7 +431,7 @@ impl DkgService {
                 let config_bytes = serde_json::to_vec(&config)
                     .map_err(|e| OrchestrationError::Internal(format!("JSON serialization failed: {}", e)))?;
                 {
-                    let etcd = self.etcd.lock().await;
+                    let etcd = &self.etcd;
                     etcd.put(&config_key, &config_bytes).await
                         .map_err(|e| OrchestrationError::Storage(e.into()))?;
                 }
@@ -426,6 +441,21 @@ impl DkgService {
                     session_id, protocol, address, threshold, total_nodes
                 );
 
+                // Only the initiating node reports completion, once per ceremony
+                crate::webhook_dispatcher::emit_event(
+                    &self.postgres,
+                    threshold_storage::WEBHOOK_EVENT_DKG_COMPLETED,
+                    serde_json::json!({
+                        "session_id": session_id,
+                        "protocol": protocol.to_string(),
+                        "address": address,
+                        "public_key": hex::encode(&public_key),
+                        "threshold": threshold,
+                        "total_nodes": total_nodes,
+                    }),
+                )
+                .await;
+
                 // Trigger aux info generation for CGGMP24 (if aux info service is set)
                 // IMPORTANT: Aux info must be generated before presignature generation
                 // TEMPORARILY DISABLED FOR TESTING - Prevents duplicate sessions
@@ -471,6 +501,8 @@ impl DkgService {
                     protocol,
                     public_key,
                     address,
+                    xpub,
+                    descriptor,
                     threshold,
                     total_nodes,
                     completed_at: Utc::now(),
@@ -510,6 +542,7 @@ impl DkgService {
 
         #[derive(Debug, Clone, Serialize, Deserialize)]
         struct DkgJoinRequest {
+            coordinator_id: u64,
             session_id: String,
             protocol: String,
             threshold: u32,
@@ -517,6 +550,7 @@ impl DkgService {
         }
 
         let join_request = DkgJoinRequest {
+            coordinator_id: self.node_id.0,
             session_id: session_id.to_string(),
             protocol: protocol.to_string(),
             threshold,
@@ -662,6 +696,7 @@ impl DkgService {
                 completed_at: None,
                 public_key: None,
                 address: None,
+                xpub: None,
                 error: None,
             });
         }
@@ -673,9 +708,10 @@ impl DkgService {
         };
 
         match result {
-            Ok(public_key) => {
+            Ok((public_key, xpub)) => {
                 // Derive Bitcoin address first
                 let address = self.derive_address(protocol, &public_key)?;
+                let descriptor = xpub.as_deref().and_then(|x| Self::export_descriptor(protocol, x));
 
                 // Update ceremony status to completed
                 let mut ceremonies = self.active_ceremonies.write().await;
@@ -684,6 +720,7 @@ impl DkgService {
                     ceremony.completed_at = Some(Utc::now());
                     ceremony.public_key = Some(public_key.clone());
                     ceremony.address = Some(address.clone());
+                    ceremony.xpub = xpub.clone();
                 }
 
                 Ok(DkgResult {
@@ -691,6 +728,8 @@ impl DkgService {
                     protocol,
                     public_key,
                     address,
+                    xpub,
+                    descriptor,
                     threshold: ceremony_data.threshold,
                     total_nodes: ceremony_data.total_nodes,
                     completed_at: Utc::now(),
@@ -719,7 +758,7 @@ impl DkgService {
         &self,
         session_id: Uuid,
         participants: Vec<NodeId>,
-    ) -> Result<Vec<u8>> {
+    ) -> Result<(Vec<u8>, Option<String>)> {
         info!(
             "Running CGGMP24 DKG: session={} participants={:?}",
             session_id, participants
@@ -773,7 +812,7 @@ impl DkgService {
 
         let barrier_key = format!("/dkg/{}/ready/{}", session_id, self.node_id);
         {
-            let etcd = self.etcd.lock().await;
+            let etcd = &self.etcd;
             etcd.put(&barrier_key, &[1]).await.map_err(|e| {
                 OrchestrationError::StorageError(format!("Failed to signal ready: {}", e))
             })?;
@@ -785,7 +824,7 @@ impl DkgService {
 
         loop {
             let ready_count = {
-                let mut etcd = self.etcd.lock().await;
+                let etcd = &self.etcd;
                 let mut count = 0;
                 for participant in &participants {
                     let key = format!("/dkg/{}/ready/{}", session_id, participant);
@@ -958,15 +997,17 @@ impl DkgService {
 
         // Clean up synchronization barrier keys from etcd
         {
-            let etcd = self.etcd.lock().await;
+            let etcd = &self.etcd;
             for participant in &participants {
                 let key = format!("/dkg/{}/ready/{}", session_id, participant);
                 let _ = etcd.delete(&key).await; // Ignore errors during cleanup
             }
         }
 
+        let xpub = Self::export_xpub(&key_share_data);
+
         // Return compressed public key (33 bytes)
-        Ok(public_key)
+        Ok((public_key, xpub))
     }
 
     /// Run FROST DKG ceremony (2-3 rounds)
@@ -977,7 +1018,7 @@ impl DkgService {
         &self,
         session_id: Uuid,
         participants: Vec<NodeId>,
-    ) -> Result<Vec<u8>> {
+    ) -> Result<(Vec<u8>, Option<String>)> {
         info!(
             "Running FROST DKG: session={} participants={:?}",
             session_id, participants
@@ -1030,7 +1071,7 @@ impl DkgService {
 
         let barrier_key = format!("/dkg/{}/ready/{}", session_id, self.node_id);
         {
-            let etcd = self.etcd.lock().await;
+            let etcd = &self.etcd;
             etcd.put(&barrier_key, &[1]).await.map_err(|e| {
                 OrchestrationError::StorageError(format!("Failed to signal ready: {}", e))
             })?;
@@ -1042,7 +1083,7 @@ impl DkgService {
 
         loop {
             let ready_count = {
-                let mut etcd = self.etcd.lock().await;
+                let etcd = &self.etcd;
                 let mut count = 0;
                 for participant in &participants {
                     let key = format!("/dkg/{}/ready/{}", session_id, participant);
@@ -1215,15 +1256,17 @@ impl DkgService {
 
         // Clean up synchronization barrier keys from etcd
         {
-            let etcd = self.etcd.lock().await;
+            let etcd = &self.etcd;
             for participant in &participants {
                 let key = format!("/dkg/{}/ready/{}", session_id, participant);
                 let _ = etcd.delete(&key).await; // Ignore errors during cleanup
             }
         }
 
+        let xpub = Self::export_xpub(&key_share_data);
+
         // Return x-only public key (32 bytes)
-        Ok(public_key)
+        Ok((public_key, xpub))
     }
 
     /// Broadcast DKG message to all participants
@@ -1304,6 +1347,51 @@ impl DkgService {
         Ok(())
     }
 
+    /// Serialize the root xpub of a freshly generated key share.
+    ///
+    /// Uses mainnet version bytes to match `derive_address`. Export failures
+    /// are logged and do not fail the ceremony.
+    fn export_xpub(key_share_data: &[u8]) -> Option<String> {
+        let exported = protocols::hd::root_extended_key(key_share_data)
+            .map_err(|e| e.to_string())
+            .and_then(|root| {
+                root.to_xpub(bitcoin::Network::Bitcoin)
+                    .map_err(|e| e.to_string())
+            });
+
+        match exported {
+            Ok(xpub) => Some(xpub),
+            Err(e) => {
+                warn!("Failed to export root xpub after DKG: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Output descriptor for the ceremony address.
+    ///
+    /// CGGMP24 addresses are P2WPKH of the root key (`wpkh`). FROST addresses
+    /// commit to the untweaked x-only key (see `derive_p2tr_address`), which
+    /// is `rawtr` rather than `tr`.
+    fn export_descriptor(protocol: ProtocolType, xpub: &str) -> Option<String> {
+        let kind = match protocol {
+            ProtocolType::CGGMP24 => DescriptorKind::Wpkh,
+            ProtocolType::FROST => DescriptorKind::RawTr,
+        };
+
+        let exported = ExtendedPubKey::from_xpub(xpub).and_then(|root| {
+            output_descriptor(&root, bitcoin::Network::Bitcoin, kind, None)
+        });
+
+        match exported {
+            Ok(descriptor) => Some(descriptor),
+            Err(e) => {
+                warn!("Failed to build output descriptor: {}", e);
+                None
+            }
+        }
+    }
+
     /// Derive Bitcoin address from public key based on protocol
     fn derive_address(&self, protocol: ProtocolType, public_key: &[u8]) -> Result<String> {
         // Use mainnet for production (can be made configurable later)
@@ -1,6 +1,7 @@
 //! Error types for the orchestration service
 
 use thiserror::Error;
+use threshold_types::NodeId;
 
 /// Result type for orchestration operations
 pub type Result<T> = std::result::Result<T, OrchestrationError>;
@@ -64,6 +65,24 @@ pub enum OrchestrationError {
 
     #[error("Session already exists: {0}")]
     SessionAlreadyExists(String),
+
+    /// A signing participant misbehaved; retries exclude it
+    #[error("Signer {node_id} failed: {reason}")]
+    SignerFault { node_id: NodeId, reason: String },
+
+    /// The Bitcoin network rejected the transaction itself
+    #[error("Broadcast rejected: {0}")]
+    BroadcastRejected(String),
+}
+
+impl OrchestrationError {
+    /// Node blamed for the failure, if any
+    pub fn faulty_node(&self) -> Option<NodeId> {
+        match self {
+            OrchestrationError::SignerFault { node_id, .. } => Some(*node_id),
+            _ => None,
+        }
+    }
 }
 
 impl From<tokio::task::JoinError> for OrchestrationError {
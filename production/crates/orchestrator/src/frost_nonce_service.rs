@@ -0,0 +1,581 @@
+//! FROST Nonce Pool Service
+//!
+//! This module manages the pool of preprocessed FROST nonces that lets Taproot
+//! signing complete in a single online round (see
+//! [`protocols::frost::preprocessing`]).
+//!
+//! # Architecture
+//!
+//! - **Per-node pools**: Every node generates its own nonce pairs in the background.
+//!   Secret nonces are persisted in PostgreSQL (`frost_nonces`) and never leave the node.
+//! - **Published commitments**: Public commitments are published to etcd under
+//!   `/frost/commitments/{node_id}/{nonce_id}`.
+//! - **Claiming**: The signing coordinator claims one commitment per signer with an
+//!   etcd transaction, so two signing sessions can never be handed the same nonce.
+//! - **One-time consumption**: A signer consumes a nonce with a single PostgreSQL
+//!   statement that marks it used and wipes the secret before producing its share.
+//!
+//! # Performance Targets
+//!
+//! - Target pool size: 50 nonces per node
+//! - Minimum pool size: 10 nonces (triggers refill)
+//! - Nonce lifetime: 24 hours (older nonces are discarded)
+
+use crate::error::{OrchestrationError, Result};
+use crate::metrics;
+use crate::signing_coordinator::{SignatureProtocol, SigningCoordinator};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use threshold_storage::{EtcdStorage, PostgresStorage, StoredFrostNonce};
+use threshold_types::{NodeId, TransactionState, TxId};
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use protocols::frost::preprocessing::{
+    self, FrostPartialSignature, NonceCommitment, NoncePool, PreprocessedNonce,
+};
+
+/// FROST nonce pool configuration
+#[derive(Debug, Clone)]
+pub struct FrostNonceConfig {
+    /// Pool size to refill up to
+    pub target_size: usize,
+    /// Pool size below which refill is triggered
+    pub min_size: usize,
+    /// Maximum nonces generated per refill cycle
+    pub batch_size: usize,
+    /// Nonces older than this are discarded
+    pub max_age: chrono::Duration,
+}
+
+impl Default for FrostNonceConfig {
+    fn default() -> Self {
+        Self {
+            target_size: 50,
+            min_size: 10,
+            batch_size: 25,
+            max_age: chrono::Duration::hours(24),
+        }
+    }
+}
+
+impl FrostNonceConfig {
+    /// Load configuration from `FROST_NONCE_POOL_*` environment variables,
+    /// falling back to defaults for anything unset or unparsable.
+    pub fn from_env() -> Self {
+        fn env<T: std::str::FromStr>(key: &str) -> Option<T> {
+            std::env::var(key).ok().and_then(|v| v.parse().ok())
+        }
+
+        let defaults = Self::default();
+        let target_size = env("FROST_NONCE_POOL_TARGET").unwrap_or(defaults.target_size).max(1);
+
+        Self {
+            target_size,
+            min_size: env("FROST_NONCE_POOL_MIN")
+                .unwrap_or(defaults.min_size)
+                .min(target_size),
+            batch_size: env("FROST_NONCE_POOL_BATCH").unwrap_or(defaults.batch_size).max(1),
+            max_age: env::<i64>("FROST_NONCE_MAX_AGE_HOURS")
+                .map(chrono::Duration::hours)
+                .unwrap_or(defaults.max_age),
+        }
+    }
+}
+
+/// FROST nonce pool statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrostNoncePoolStats {
+    /// Current number of unconsumed nonces on this node
+    pub current_size: usize,
+    /// Target pool size
+    pub target_size: usize,
+    /// Minimum pool size (refill is triggered below this)
+    pub min_size: usize,
+    /// Total nonces generated by this node
+    pub total_generated: u64,
+    /// Total nonces consumed by this node
+    pub total_consumed: u64,
+    /// Total nonces discarded because they expired
+    pub total_expired: u64,
+}
+
+impl FrostNoncePoolStats {
+    /// Check if pool is healthy (at or above the refill threshold)
+    pub fn is_healthy(&self) -> bool {
+        self.current_size >= self.min_size
+    }
+}
+
+#[derive(Debug, Default)]
+struct NonceStats {
+    total_generated: u64,
+    total_consumed: u64,
+    total_expired: u64,
+}
+
+/// FROST Nonce Pool Service
+pub struct FrostNonceService {
+    /// In-memory pool of this node's unconsumed nonces
+    pool: NoncePool,
+    /// PostgreSQL storage (authoritative store for secret nonces)
+    postgres: Arc<PostgresStorage>,
+    /// etcd storage for published commitments
+    etcd: EtcdStorage,
+    /// Current node ID
+    node_id: NodeId,
+    /// Pool configuration
+    config: FrostNonceConfig,
+    /// Generation / consumption statistics
+    stats: Arc<RwLock<NonceStats>>,
+}
+
+impl FrostNonceService {
+    /// Create new FROST nonce service
+    pub fn new(
+        postgres: Arc<PostgresStorage>,
+        etcd: EtcdStorage,
+        node_id: NodeId,
+    ) -> Self {
+        Self {
+            pool: NoncePool::new(),
+            postgres,
+            etcd,
+            node_id,
+            config: FrostNonceConfig::default(),
+            stats: Arc::new(RwLock::new(NonceStats::default())),
+        }
+    }
+
+    /// Replace the default pool configuration
+    pub fn with_config(self, config: FrostNonceConfig) -> Self {
+        Self { config, ..self }
+    }
+
+    /// Load this node's FROST key share, if FROST DKG has completed
+    async fn key_share(&self) -> Result<Option<Vec<u8>>> {
+        self.postgres
+            .get_latest_key_share_for_protocol(self.node_id, "frost")
+            .await
+            .map_err(|e| OrchestrationError::StorageError(format!("Failed to load FROST key share: {}", e)))
+    }
+
+    /// Reload unconsumed nonces persisted by a previous run
+    pub async fn load_persisted(&self) -> Result<usize> {
+        let stored = self
+            .postgres
+            .load_frost_nonces(self.node_id)
+            .await
+            .map_err(|e| OrchestrationError::StorageError(format!("Failed to load FROST nonces: {}", e)))?;
+
+        let count = stored.len();
+        self.pool
+            .extend(stored.into_iter().map(|n| PreprocessedNonce {
+                nonce_id: n.nonce_id,
+                party_index: n.party_index,
+                secret_nonces: n.secret_nonces,
+                commitment: n.commitment,
+                created_at: n.created_at,
+            }))
+            .await;
+
+        info!("Node {} reloaded {} persisted FROST nonces", self.node_id.0, count);
+        Ok(count)
+    }
+
+    /// Generate `count` nonces, persist them and publish their commitments
+    ///
+    /// Nonces are written to PostgreSQL before their commitments are published,
+    /// so a commitment visible in etcd always has a durable secret behind it.
+    pub async fn refill(&self, count: usize) -> Result<usize> {
+        let key_share = self.key_share().await?.ok_or_else(|| {
+            OrchestrationError::Internal("No FROST key share available".to_string())
+        })?;
+
+        let nonces = preprocessing::generate_nonces(&key_share, count)
+            .map_err(|e| OrchestrationError::Protocol(format!("FROST nonce generation failed: {}", e)))?;
+
+        let stored: Vec<StoredFrostNonce> = nonces
+            .iter()
+            .map(|n| StoredFrostNonce {
+                nonce_id: n.nonce_id,
+                party_index: n.party_index,
+                secret_nonces: n.secret_nonces.clone(),
+                commitment: n.commitment.clone(),
+                created_at: n.created_at,
+            })
+            .collect();
+
+        self.postgres
+            .store_frost_nonces(self.node_id, &stored)
+            .await
+            .map_err(|e| OrchestrationError::StorageError(format!("Failed to store FROST nonces: {}", e)))?;
+
+        let commitments: Vec<NonceCommitment> =
+            nonces.iter().map(PreprocessedNonce::public_commitment).collect();
+        self.pool.extend(nonces).await;
+
+        {
+            let etcd = &self.etcd;
+            for commitment in &commitments {
+                let payload = serde_json::to_vec(commitment).map_err(|e| {
+                    OrchestrationError::SerializationError(format!("Failed to serialize commitment: {}", e))
+                })?;
+                etcd.publish_frost_commitment(self.node_id, &commitment.nonce_id.to_string(), &payload)
+                    .await
+                    .map_err(|e| OrchestrationError::StorageError(format!("Failed to publish commitment: {}", e)))?;
+            }
+        }
+
+        self.stats.write().await.total_generated += commitments.len() as u64;
+        Ok(commitments.len())
+    }
+
+    /// Discard nonces older than the configured lifetime
+    async fn expire_old(&self) -> Result<usize> {
+        let cutoff = chrono::Utc::now() - self.config.max_age;
+        self.postgres
+            .expire_frost_nonces(self.node_id, cutoff)
+            .await
+            .map_err(|e| OrchestrationError::StorageError(format!("Failed to expire FROST nonces: {}", e)))?;
+
+        let expired = self.pool.expire(self.config.max_age).await;
+        if !expired.is_empty() {
+            let etcd = &self.etcd;
+            for nonce_id in &expired {
+                if let Err(e) = etcd.withdraw_frost_commitment(self.node_id, &nonce_id.to_string()).await {
+                    warn!("Failed to withdraw expired FROST commitment {}: {}", nonce_id, e);
+                }
+            }
+            self.stats.write().await.total_expired += expired.len() as u64;
+        }
+
+        Ok(expired.len())
+    }
+
+    /// Start the background refill loop
+    ///
+    /// Unlike presignatures, FROST nonces are generated locally without any
+    /// interaction, so every node runs this loop for its own pool.
+    pub async fn run_refill_loop(self: Arc<Self>) {
+        if let Err(e) = self.load_persisted().await {
+            error!("Failed to reload persisted FROST nonces: {}", e);
+        }
+
+        let mut refilling = false;
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+
+            // Wait silently until FROST DKG has produced a key share
+            match self.key_share().await {
+                Ok(Some(_)) => {}
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("Node {} cannot check FROST key share: {}", self.node_id.0, e);
+                    continue;
+                }
+            }
+
+            match self.expire_old().await {
+                Ok(0) => {}
+                Ok(n) => info!("Node {} discarded {} expired FROST nonces", self.node_id.0, n),
+                Err(e) => warn!("Node {} failed to expire FROST nonces: {}", self.node_id.0, e),
+            }
+
+            let stats = self.get_stats().await;
+            metrics::update_frost_nonce_pool_metrics(stats.current_size, stats.total_consumed);
+
+            if stats.current_size < stats.min_size {
+                refilling = true;
+            } else if stats.current_size >= stats.target_size {
+                refilling = false;
+            }
+
+            if !refilling {
+                continue;
+            }
+
+            let count = (stats.target_size - stats.current_size).min(self.config.batch_size);
+            match self.refill(count).await {
+                Ok(generated) => debug!(
+                    "Node {} generated {} FROST nonces (pool: {}/{})",
+                    self.node_id.0,
+                    generated,
+                    stats.current_size + generated,
+                    stats.target_size
+                ),
+                Err(e) => error!("Node {} failed to generate FROST nonces: {}", self.node_id.0, e),
+            }
+        }
+    }
+
+    /// Claim one published commitment for each signer (coordinator side)
+    ///
+    /// Fails if any signer has no commitment left; the caller should then fall
+    /// back to two-round FROST signing. Commitments already claimed for other
+    /// signers are published again, since no share was requested for them yet.
+    pub async fn claim_commitments(&self, signers: &[NodeId]) -> Result<Vec<NonceCommitment>> {
+        let mut claimed = Vec::with_capacity(signers.len());
+
+        for signer in signers {
+            match self.claim_commitment(*signer).await {
+                Ok(commitment) => claimed.push((*signer, commitment)),
+                Err(e) => {
+                    self.release_commitments(&claimed).await;
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(claimed.into_iter().map(|(_, commitment)| commitment).collect())
+    }
+
+    /// Claim and decode one published commitment of `signer`
+    async fn claim_commitment(&self, signer: NodeId) -> Result<NonceCommitment> {
+        let (_nonce_id, payload) = self
+            .etcd
+            .claim_frost_commitment(signer)
+            .await
+            .map_err(|e| OrchestrationError::StorageError(format!("Failed to claim commitment: {}", e)))?
+            .ok_or_else(|| {
+                OrchestrationError::Internal(format!(
+                    "No preprocessed FROST nonce available for node {}",
+                    signer.0
+                ))
+            })?;
+
+        serde_json::from_slice(&payload).map_err(|e| {
+            OrchestrationError::SerializationError(format!("Invalid commitment from node {}: {}", signer.0, e))
+        })
+    }
+
+    /// Publish claimed commitments again after an aborted claim
+    ///
+    /// Best effort: a commitment that cannot be republished is only lost to
+    /// the pool, its secret nonce stays unused and eventually expires.
+    async fn release_commitments(&self, claimed: &[(NodeId, NonceCommitment)]) {
+        for (signer, commitment) in claimed {
+            let result = match serde_json::to_vec(commitment) {
+                Ok(payload) => self
+                    .etcd
+                    .publish_frost_commitment(*signer, &commitment.nonce_id.to_string(), &payload)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = result {
+                warn!(
+                    "Failed to release FROST commitment {} of node {}: {}",
+                    commitment.nonce_id, signer.0, e
+                );
+            }
+        }
+    }
+
+    /// Load this node's root FROST key share, checking that it can sign for
+    /// the BIP32 child at `derivation_path`
+    ///
+    /// The child is derived inside givre signing and aggregation (additive
+    /// shift), so the share itself is returned untweaked.
+    async fn signing_key_share(&self, derivation_path: &[u32]) -> Result<Vec<u8>> {
+        let key_share = self.key_share().await?.ok_or_else(|| {
+            OrchestrationError::Internal("No FROST key share available".to_string())
+        })?;
+
+        if !derivation_path.is_empty() {
+            protocols::hd::root_extended_key(&key_share)
+                .and_then(|root| Ok(root.derive_path_with_tweak(derivation_path)?))
+                .map_err(|e| OrchestrationError::Protocol(format!("Key derivation failed: {}", e)))?;
+        }
+
+        Ok(key_share)
+    }
+
+    /// Produce this node's signature share using the nonce the coordinator chose
+    ///
+    /// Only the sighash of `tx_id` is signed, and only while the transaction
+    /// is approved for signing: the hash is recomputed from the stored
+    /// unsigned transaction and must match `message_hash`, so a coordinator
+    /// cannot obtain a signature over anything else.
+    ///
+    /// The nonce is consumed in PostgreSQL before signing; if that fails (e.g.
+    /// the nonce was already used) no share is produced. A non-empty
+    /// `derivation_path` signs for the corresponding BIP32 child key.
+    pub async fn sign_share(
+        &self,
+        session_id: &str,
+        tx_id: &TxId,
+        nonce_id: Uuid,
+        message_hash: &[u8],
+        commitments: &[NonceCommitment],
+        derivation_path: &[u32],
+    ) -> Result<FrostPartialSignature> {
+        let tx = self
+            .postgres
+            .get_transaction(tx_id)
+            .await
+            .map_err(|e| OrchestrationError::StorageError(format!("Failed to load transaction: {}", e)))?
+            .ok_or_else(|| OrchestrationError::TransactionNotFound(tx_id.to_string()))?;
+
+        if !matches!(tx.state, TransactionState::Approved | TransactionState::Signing) {
+            return Err(OrchestrationError::InvalidState(
+                tx_id.to_string(),
+                format!("cannot sign in state {}", tx.state),
+            ));
+        }
+
+        let sighash = SigningCoordinator::compute_message_hash(&tx.unsigned_tx, SignatureProtocol::FROST)?;
+        if sighash != message_hash {
+            return Err(OrchestrationError::InvalidState(
+                tx_id.to_string(),
+                "message hash does not match the stored unsigned transaction".to_string(),
+            ));
+        }
+
+        let message_hash: [u8; 32] = message_hash.try_into().map_err(|_| {
+            OrchestrationError::Internal(format!(
+                "Invalid message hash length: {} (expected 32)",
+                message_hash.len()
+            ))
+        })?;
+
+        let commitment = commitments
+            .iter()
+            .find(|c| c.nonce_id == nonce_id)
+            .ok_or_else(|| {
+                OrchestrationError::Internal(format!("Nonce {} missing from commitment set", nonce_id))
+            })?;
+
+        // Derive before consuming so a bad path does not burn a nonce
+        let key_share = self.signing_key_share(derivation_path).await?;
+
+        let secret_nonces = self
+            .postgres
+            .consume_frost_nonce(self.node_id, nonce_id, session_id)
+            .await
+            .map_err(|e| OrchestrationError::StorageError(format!("Failed to consume FROST nonce: {}", e)))?
+            .ok_or_else(|| {
+                OrchestrationError::Internal(format!("FROST nonce {} already consumed or unknown", nonce_id))
+            })?;
+
+        // The database is authoritative; the in-memory copy only needs removing
+        let _ = self.pool.take(nonce_id).await;
+        self.stats.write().await.total_consumed += 1;
+
+        let nonce = PreprocessedNonce {
+            nonce_id,
+            party_index: commitment.party_index,
+            secret_nonces,
+            commitment: commitment.commitment.clone(),
+            created_at: chrono::Utc::now(),
+        };
+
+        preprocessing::sign_with_preprocessed_nonce(
+            &key_share,
+            nonce,
+            &message_hash,
+            derivation_path,
+            commitments,
+        )
+        .map_err(|e| OrchestrationError::Protocol(format!("FROST signing failed: {}", e)))
+    }
+
+    /// Wipe a nonce claimed for a session that failed before it was signed with
+    ///
+    /// The secret is consumed like a used nonce, so the commitment the
+    /// coordinator holds can never be satisfied later. Returns false if the
+    /// nonce was already consumed or is unknown.
+    pub async fn discard_nonce(&self, nonce_id: Uuid, session_id: &str) -> Result<bool> {
+        let discarded = self
+            .postgres
+            .consume_frost_nonce(self.node_id, nonce_id, session_id)
+            .await
+            .map_err(|e| OrchestrationError::StorageError(format!("Failed to discard FROST nonce: {}", e)))?
+            .is_some();
+
+        if discarded {
+            let _ = self.pool.take(nonce_id).await;
+            info!(
+                "Node {} discarded FROST nonce {} of failed session {}",
+                self.node_id.0, nonce_id, session_id
+            );
+        }
+
+        Ok(discarded)
+    }
+
+    /// Aggregate signature shares into a 64-byte BIP-340 signature
+    pub async fn aggregate(
+        &self,
+        message_hash: &[u8],
+        commitments: &[NonceCommitment],
+        partials: &[FrostPartialSignature],
+        derivation_path: &[u32],
+    ) -> Result<Vec<u8>> {
+        let message_hash: [u8; 32] = message_hash.try_into().map_err(|_| {
+            OrchestrationError::Internal(format!(
+                "Invalid message hash length: {} (expected 32)",
+                message_hash.len()
+            ))
+        })?;
+
+        let key_share = self.signing_key_share(derivation_path).await?;
+
+        let signature = preprocessing::aggregate_partial_signatures(
+            &key_share,
+            &message_hash,
+            derivation_path,
+            commitments,
+            partials,
+        )
+        .map_err(|e| OrchestrationError::Protocol(format!("FROST aggregation failed: {}", e)))?;
+
+        let mut bytes = signature.r;
+        bytes.extend_from_slice(&signature.s);
+        Ok(bytes)
+    }
+
+    /// Get current pool statistics
+    pub async fn get_stats(&self) -> FrostNoncePoolStats {
+        let stats = self.stats.read().await;
+        FrostNoncePoolStats {
+            current_size: self.pool.size().await,
+            target_size: self.config.target_size,
+            min_size: self.config.min_size,
+            total_generated: stats.total_generated,
+            total_consumed: stats.total_consumed,
+            total_expired: stats.total_expired,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frost_nonce_stats_health() {
+        let stats = FrostNoncePoolStats {
+            current_size: 9,
+            target_size: 50,
+            min_size: 10,
+            total_generated: 100,
+            total_consumed: 91,
+            total_expired: 0,
+        };
+        assert!(!stats.is_healthy());
+
+        let stats = FrostNoncePoolStats { current_size: 10, ..stats };
+        assert!(stats.is_healthy());
+    }
+
+    #[test]
+    fn test_frost_nonce_config_defaults() {
+        let config = FrostNonceConfig::default();
+        assert!(config.min_size < config.target_size);
+        assert!(config.batch_size > 0);
+    }
+}
@@ -4,15 +4,20 @@
 //! 1. Pings all registered nodes to measure latency
 //! 2. Updates health metrics based on responses
 //! 3. Cleans up stale nodes that have missed heartbeats
+//!
+//! When PostgreSQL is configured, every change of a node between healthy and
+//! unhealthy is appended to the cluster event log as seen by this node.
 
 use crate::error::{OrchestrationError, Result};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 use serde::{Deserialize, Serialize};
+use threshold_storage::PostgresStorage;
+use threshold_types::NodeId;
 
 /// Interval between health check runs (seconds)
 const HEALTH_CHECK_INTERVAL_SECS: u64 = 15;
@@ -39,6 +44,8 @@ pub struct HealthChecker {
     nodes: Vec<(u64, String)>, // (node_id, endpoint)
     health_state: Arc<RwLock<HashMap<u64, NodeHealth>>>,
     shutdown: Arc<RwLock<bool>>,
+    /// Event log for health changes, with the node doing the checks
+    events: Option<(Arc<PostgresStorage>, NodeId)>,
 }
 
 impl HealthChecker {
@@ -48,6 +55,7 @@ impl HealthChecker {
             nodes,
             health_state: Arc::new(RwLock::new(HashMap::new())),
             shutdown: Arc::new(RwLock::new(false)),
+            events: None,
         }
     }
 
@@ -149,6 +157,7 @@ impl HealthChecker {
 
             // Update health state with results
             let mut health_state = self.health_state.write().await;
+            let mut changes = Vec::new();
 
             for (node_id, success, latency_ms) in results {
                 let endpoint = self.nodes.iter()
@@ -156,9 +165,10 @@ impl HealthChecker {
                     .map(|(_, ep)| ep.clone())
                     .unwrap_or_default();
 
-                // Get existing health record if any
-                let mut consecutive_failures = health_state
-                    .get(&node_id)
+                // Get existing health record if any; nodes start out healthy
+                let previous = health_state.get(&node_id);
+                let was_healthy = previous.map(|h| h.is_healthy).unwrap_or(true);
+                let mut consecutive_failures = previous
                     .map(|h| h.consecutive_failures)
                     .unwrap_or(0);
 
@@ -178,6 +188,9 @@ impl HealthChecker {
                     consecutive_failures,
                 };
 
+                if health.is_healthy != was_healthy {
+                    changes.push(health.clone());
+                }
                 health_state.insert(node_id, health.clone());
 
                 if !health.is_healthy {
@@ -206,6 +219,37 @@ impl HealthChecker {
                 info!("Removing stale health record for node {}", node_id);
                 health_state.remove(&node_id);
             }
+            drop(health_state);
+
+            for health in changes {
+                self.record_health_change(&health).await;
+            }
+        }
+    }
+
+    /// Append a health change to the cluster event log, if configured
+    async fn record_health_change(&self, health: &NodeHealth) {
+        let Some((postgres, observer)) = &self.events else {
+            return;
+        };
+
+        let payload = serde_json::json!({
+            "node_id": health.node_id,
+            "healthy": health.is_healthy,
+            "consecutive_failures": health.consecutive_failures,
+            "latency_ms": health.latency_ms,
+            "observed_by": observer.0,
+        });
+        if let Err(e) = postgres
+            .append_cluster_event(
+                threshold_storage::EVENT_NODE_HEALTH_CHANGED,
+                None,
+                Some(health.node_id),
+                &payload,
+            )
+            .await
+        {
+            warn!("Failed to record health change of node {}: {}", health.node_id, e);
         }
     }
 
@@ -219,12 +263,14 @@ impl HealthChecker {
 /// Builder for HealthChecker
 pub struct HealthCheckerBuilder {
     nodes: Vec<(u64, String)>,
+    events: Option<(Arc<PostgresStorage>, NodeId)>,
 }
 
 impl HealthCheckerBuilder {
     pub fn new() -> Self {
         Self {
             nodes: Vec::new(),
+            events: None,
         }
     }
 
@@ -238,13 +284,21 @@ impl HealthCheckerBuilder {
         self
     }
 
-    pub fn with_etcd(self, _etcd: Arc<Mutex<threshold_storage::EtcdStorage>>) -> Self {
+    pub fn with_etcd(self, _etcd: threshold_storage::EtcdStorage) -> Self {
         // Ignore etcd parameter for now - using in-memory state instead
         self
     }
 
+    /// Record health changes seen by `node_id` in the cluster event log
+    pub fn with_event_log(mut self, postgres: Arc<PostgresStorage>, node_id: NodeId) -> Self {
+        self.events = Some((postgres, node_id));
+        self
+    }
+
     pub fn build(self) -> Result<Arc<HealthChecker>> {
-        Ok(Arc::new(HealthChecker::new(self.nodes)))
+        let mut checker = HealthChecker::new(self.nodes);
+        checker.events = self.events;
+        Ok(Arc::new(checker))
     }
 }
 
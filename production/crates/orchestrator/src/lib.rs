@@ -17,11 +17,17 @@ pub mod config;
 pub mod service;
 pub mod timeout_monitor;
 pub mod health_checker;
+pub mod deposit_watcher;
+pub mod webhook_dispatcher;
+pub mod retention_service;
+pub mod retry;
 pub mod heartbeat_service;
 pub mod error;
 pub mod dkg_service;
 pub mod aux_info_service;
 pub mod presig_service;
+pub mod presig_sizing;
+pub mod frost_nonce_service;
 pub mod signing_coordinator;
 pub mod protocol_router;
 pub mod message_router;
@@ -32,12 +38,18 @@ pub use config::{OrchestrationConfig, OrchestrationConfigBuilder};
 pub use service::{OrchestrationService, OrchestrationServiceBuilder};
 pub use timeout_monitor::{TimeoutMonitor, TimeoutMonitorBuilder};
 pub use health_checker::{HealthChecker, HealthCheckerBuilder};
+pub use deposit_watcher::{DepositWatcher, DepositWatcherBuilder, DepositWatcherConfig};
+pub use webhook_dispatcher::{WebhookDispatcher, WebhookDispatcherBuilder, WebhookDispatcherConfig};
+pub use retention_service::{RetentionConfig, RetentionReport, RetentionService, RetentionServiceBuilder};
+pub use retry::{AttemptFailure, ErrorClass, RetryDecision, RetryPolicy};
 pub use heartbeat_service::HeartbeatService;
 pub use error::{OrchestrationError, Result};
 pub use dkg_service::{DkgService, DkgResult, DkgStatus, DkgCeremony, ProtocolType};
 pub use aux_info_service::{AuxInfoService, AuxInfoResult, AuxInfoStatus, AuxInfoCeremony};
 pub use presig_service::{PresignatureService, PresignatureStats};
-pub use signing_coordinator::{SigningCoordinator, SignatureProtocol, SigningRequest, SignatureShare, CombinedSignature};
+pub use presig_sizing::{DemandForecaster, LoadSample, PresigSizingConfig, SizingDecision, ThrottleReason};
+pub use frost_nonce_service::{FrostNonceConfig, FrostNoncePoolStats, FrostNonceService};
+pub use signing_coordinator::{SigningCoordinator, SignatureProtocol, SigningRequest, SignatureShare, CombinedSignature, FrostNonceDiscardRequest, FrostNonceSignRequest};
 pub use protocol_router::{ProtocolRouter, ProtocolSelection, BitcoinAddressType};
 pub use message_router::{MessageRouter, ProtocolMessage, ProtocolType as MessageProtocolType};
 pub use auto_voter::AutoVoter;
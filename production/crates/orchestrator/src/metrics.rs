@@ -5,10 +5,12 @@
 
 use lazy_static::lazy_static;
 use prometheus::{
-    register_histogram, register_int_counter_vec, register_int_gauge, register_int_gauge_vec,
-    Histogram, IntCounterVec, IntGauge, IntGaugeVec,
+    register_gauge, register_histogram, register_int_counter_vec, register_int_gauge,
+    register_int_gauge_vec, Gauge, Histogram, IntCounterVec, IntGauge, IntGaugeVec,
 };
 
+use crate::presig_sizing::{SizingDecision, ThrottleReason};
+
 lazy_static! {
     /// Presignature pool size (number of available presignatures)
     pub static ref PRESIG_POOL_SIZE: IntGauge = register_int_gauge!(
@@ -38,6 +40,56 @@ lazy_static! {
     )
     .expect("Failed to register presignature_used_total metric");
 
+    /// Presignature pool refill threshold (adaptive)
+    pub static ref PRESIG_POOL_MIN: IntGauge = register_int_gauge!(
+        "presignature_pool_min",
+        "Pool size below which presignature refill is triggered"
+    )
+    .expect("Failed to register presignature_pool_min metric");
+
+    /// Presignature pool hard cap
+    pub static ref PRESIG_POOL_MAX: IntGauge = register_int_gauge!(
+        "presignature_pool_max",
+        "Maximum number of presignatures kept in the pool"
+    )
+    .expect("Failed to register presignature_pool_max metric");
+
+    /// Forecast presignature demand per hour
+    pub static ref PRESIG_DEMAND_FORECAST: Gauge = register_gauge!(
+        "presignature_demand_forecast_per_hour",
+        "Forecast presignature demand for the upcoming hour"
+    )
+    .expect("Failed to register presignature_demand_forecast_per_hour metric");
+
+    /// Presignatures used in the trailing hour
+    pub static ref PRESIG_RECENT_USAGE: IntGauge = register_int_gauge!(
+        "presignature_usage_last_hour",
+        "Number of presignatures used in the trailing hour"
+    )
+    .expect("Failed to register presignature_usage_last_hour metric");
+
+    /// Generation cycles skipped because the node was busy
+    pub static ref PRESIG_GENERATION_THROTTLED: IntCounterVec = register_int_counter_vec!(
+        "presignature_generation_throttled_total",
+        "Presignature generation cycles skipped due to load, by reason",
+        &["reason"]
+    )
+    .expect("Failed to register presignature_generation_throttled_total metric");
+
+    /// Unconsumed FROST nonces held by this node
+    pub static ref FROST_NONCE_POOL_SIZE: IntGauge = register_int_gauge!(
+        "frost_nonce_pool_size",
+        "Number of unconsumed preprocessed FROST nonces on this node"
+    )
+    .expect("Failed to register frost_nonce_pool_size metric");
+
+    /// FROST nonces consumed for signing
+    pub static ref FROST_NONCES_CONSUMED: IntGauge = register_int_gauge!(
+        "frost_nonces_consumed_total",
+        "Total number of preprocessed FROST nonces consumed"
+    )
+    .expect("Failed to register frost_nonces_consumed_total metric");
+
     /// MPC signing duration histogram (seconds)
     pub static ref SIGNING_DURATION: Histogram = register_histogram!(
         "mpc_signing_duration_seconds",
@@ -108,6 +160,28 @@ pub fn update_presig_pool_metrics(current: usize, target: usize, generated: u64,
     PRESIG_USED_TOTAL.set(used as i64);
 }
 
+/// Update FROST nonce pool metrics
+pub fn update_frost_nonce_pool_metrics(current: usize, consumed: u64) {
+    FROST_NONCE_POOL_SIZE.set(current as i64);
+    FROST_NONCES_CONSUMED.set(consumed as i64);
+}
+
+/// Record an adaptive presignature pool sizing decision
+pub fn record_presig_sizing_decision(decision: &SizingDecision) {
+    PRESIG_POOL_TARGET.set(decision.target_size as i64);
+    PRESIG_POOL_MIN.set(decision.min_size as i64);
+    PRESIG_POOL_MAX.set(decision.max_size as i64);
+    PRESIG_DEMAND_FORECAST.set(decision.forecast_per_hour);
+    PRESIG_RECENT_USAGE.set(decision.recent_hourly_usage as i64);
+}
+
+/// Record a presignature generation cycle deferred because of node load
+pub fn record_presig_generation_throttled(reason: ThrottleReason) {
+    PRESIG_GENERATION_THROTTLED
+        .with_label_values(&[reason.as_str()])
+        .inc();
+}
+
 /// Update transaction state metrics
 pub fn update_tx_state_metrics(
     pending: usize,
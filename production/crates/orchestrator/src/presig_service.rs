@@ -7,19 +7,22 @@
 //! # Architecture
 //!
 //! - **Background Generation**: Continuously generates presignatures to maintain pool size
+//! - **Adaptive Sizing**: Pool target follows forecast demand (see [`crate::presig_sizing`])
 //! - **Pool Management**: Tracks available, used, and total presignatures
 //! - **Byzantine Tolerance**: Handles node failures during presignature generation
 //! - **Persistence**: Stores encrypted presignatures in PostgreSQL
 //!
 //! # Performance Targets
 //!
-//! - Target pool size: 100 presignatures
-//! - Minimum pool size: 20 presignatures (triggers refill)
+//! - Target pool size: 20-100 presignatures (scaled with forecast demand)
+//! - Minimum pool size: 20% of target (triggers refill)
 //! - Maximum pool size: 150 presignatures
 //! - Generation rate: ~5 presignatures/minute (parallelized)
 //! - Generation time per presignature: ~400ms (with 5 nodes)
 
 use crate::error::{OrchestrationError, Result};
+use crate::metrics;
+use crate::presig_sizing::{DemandForecaster, LoadSample, PresigSizingConfig, SizingDecision, ThrottleReason};
 use crate::message_router::{MessageRouter, ProtocolMessage as RouterProtocolMessage, ProtocolType as RouterProtocolType};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -27,7 +30,7 @@ use std::time::Duration;
 use threshold_network::QuicEngine;
 use threshold_storage::{EtcdStorage, PostgresStorage};
 use threshold_types::{NetworkMessage, NodeId, PresignatureId, PresignatureMessage};
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -42,12 +45,18 @@ pub struct PresignatureStats {
     pub current_size: usize,
     /// Target pool size
     pub target_size: usize,
+    /// Minimum pool size (refill is triggered below this)
+    pub min_size: usize,
     /// Maximum pool size
     pub max_size: usize,
     /// Pool utilization percentage (0-100)
     pub utilization: f64,
     /// Number of presignatures used in the last hour
     pub hourly_usage: usize,
+    /// Forecast presignature demand for the upcoming hour
+    pub forecast_per_hour: f64,
+    /// Set when generation is currently paused because the node is busy
+    pub throttled: Option<ThrottleReason>,
     /// Total presignatures generated
     pub total_generated: u64,
     /// Total presignatures used
@@ -55,9 +64,9 @@ pub struct PresignatureStats {
 }
 
 impl PresignatureStats {
-    /// Check if pool is healthy (above minimum threshold)
+    /// Check if pool is healthy (at or above the refill threshold)
     pub fn is_healthy(&self) -> bool {
-        self.current_size >= 20 // Minimum threshold
+        self.current_size >= self.min_size
     }
 
     /// Check if pool is critical (below critical threshold)
@@ -100,18 +109,16 @@ pub struct PresignatureService {
     message_router: Arc<MessageRouter>,
     /// PostgreSQL storage for persistence
     postgres: Arc<PostgresStorage>,
-    /// etcd storage for distributed coordination (with interior mutability)
-    etcd: Arc<Mutex<EtcdStorage>>,
+    /// etcd storage for distributed coordination
+    etcd: EtcdStorage,
     /// Aux_Info service for accessing aux_info data
     aux_info_service: Arc<super::aux_info_service::AuxInfoService>,
     /// Current node ID
     node_id: NodeId,
-    /// Target pool size
-    target_size: usize,
-    /// Minimum pool size (trigger refill)
-    min_size: usize,
-    /// Maximum pool size
-    max_size: usize,
+    /// Demand forecaster fed by presignature consumption
+    forecaster: Arc<RwLock<DemandForecaster>>,
+    /// Latest pool sizing decision (target, refill threshold, cap)
+    sizing: Arc<RwLock<SizingDecision>>,
     /// Generation statistics
     stats: Arc<RwLock<GenerationStats>>,
     /// HTTP client for broadcasting to nodes (SORUN #19 FIX)
@@ -126,7 +133,6 @@ pub struct PresignatureService {
 struct GenerationStats {
     total_generated: u64,
     total_used: u64,
-    hourly_usage: usize,
     last_generation: Option<chrono::DateTime<chrono::Utc>>,
 }
 
@@ -136,11 +142,14 @@ impl PresignatureService {
         quic: Arc<QuicEngine>,
         message_router: Arc<MessageRouter>,
         postgres: Arc<PostgresStorage>,
-        etcd: Arc<Mutex<EtcdStorage>>,
+        etcd: EtcdStorage,
         aux_info_service: Arc<super::aux_info_service::AuxInfoService>,
         node_id: NodeId,
         node_endpoints: std::collections::HashMap<u64, String>,
     ) -> Self {
+        let mut forecaster = DemandForecaster::new(PresigSizingConfig::default());
+        let sizing = forecaster.decide(chrono::Utc::now(), LoadSample::default());
+
         Self {
             pool: Arc::new(RwLock::new(Vec::new())),
             quic,
@@ -149,9 +158,8 @@ impl PresignatureService {
             etcd,
             aux_info_service,
             node_id,
-            target_size: 100,
-            min_size: 20,
-            max_size: 150,
+            forecaster: Arc::new(RwLock::new(forecaster)),
+            sizing: Arc::new(RwLock::new(sizing)),
             stats: Arc::new(RwLock::new(GenerationStats::default())),
             http_client: reqwest::Client::new(),
             node_endpoints,
@@ -160,12 +168,53 @@ impl PresignatureService {
         }
     }
 
+    /// Replace the HTTP client used for calls to other nodes' internal endpoints
+    ///
+    /// The internal listener only accepts cluster client certificates, so this
+    /// is the mTLS client built at startup.
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
+    /// Replace the default pool sizing configuration
+    ///
+    /// Must be called before the service is shared (e.g. right after `new`).
+    pub fn with_sizing_config(self, config: PresigSizingConfig) -> Self {
+        let mut forecaster = DemandForecaster::new(config);
+        let sizing = forecaster.decide(chrono::Utc::now(), LoadSample::default());
+
+        Self {
+            forecaster: Arc::new(RwLock::new(forecaster)),
+            sizing: Arc::new(RwLock::new(sizing)),
+            ..self
+        }
+    }
+
+    /// Re-evaluate pool sizing from forecast demand and current node load
+    ///
+    /// The decision is cached for `get_stats` / `generate_batch` and exported
+    /// as Prometheus metrics.
+    pub async fn evaluate_sizing(&self) -> SizingDecision {
+        let load = LoadSample {
+            cpu_load_per_core: LoadSample::read_cpu_load_per_core(),
+            active_sessions: self.message_router.active_session_count().await,
+        };
+
+        let decision = self.forecaster.write().await.decide(chrono::Utc::now(), load);
+        metrics::record_presig_sizing_decision(&decision);
+
+        *self.sizing.write().await = decision.clone();
+        decision
+    }
+
     /// Start the background generation loop
     ///
     /// This method runs continuously and maintains the presignature pool by:
     /// 1. Checking pool size every 10 seconds
-    /// 2. Triggering refill when below minimum threshold
-    /// 3. Generating presignatures in batches
+    /// 2. Re-evaluating the target size from forecast demand
+    /// 3. Triggering refill when below minimum threshold and refilling up to target
+    /// 4. Pausing generation while CPU or network is busy
     ///
     /// Presignature generation loop - runs ONLY on node 1 (coordinator)
     ///
@@ -194,6 +243,11 @@ impl PresignatureService {
         // Wait for system to stabilize after startup
         tokio::time::sleep(Duration::from_secs(5)).await;
 
+        // Hysteresis: start refilling below min_size, keep going until target_size
+        let mut refilling = false;
+        // Alert once per drop to a critical level, re-armed when healthy again
+        let mut low_alerted = false;
+
         loop {
             tokio::time::sleep(Duration::from_secs(10)).await;
 
@@ -203,7 +257,7 @@ impl PresignatureService {
 
             // Check if DKG config exists in etcd (indicates DKG completed)
             let has_dkg = {
-                let mut etcd = self.etcd.lock().await;
+                let etcd = &self.etcd;
                 etcd.get("/cluster/dkg/cggmp24/config").await.ok().flatten().is_some()
             };
 
@@ -213,13 +267,21 @@ impl PresignatureService {
                 continue;
             }
 
+            let decision = self.evaluate_sizing().await;
             let stats = self.get_stats().await;
+            metrics::update_presig_pool_metrics(
+                stats.current_size,
+                stats.target_size,
+                stats.total_generated,
+                stats.total_used,
+            );
 
             info!(
-                "Presignature pool status: {}/{} ({:.1}%) - {}",
+                "Presignature pool status: {}/{} ({:.1}%, forecast {:.1}/h) - {}",
                 stats.current_size,
                 stats.target_size,
                 stats.utilization,
+                decision.forecast_per_hour,
                 if stats.is_healthy() {
                     "healthy"
                 } else {
@@ -227,8 +289,37 @@ impl PresignatureService {
                 }
             );
 
-            // Refill if below minimum
-            if stats.current_size < self.min_size {
+            if stats.is_critical() && !low_alerted {
+                low_alerted = true;
+                crate::webhook_dispatcher::emit_event(
+                    &self.postgres,
+                    threshold_storage::WEBHOOK_EVENT_PRESIG_POOL_LOW,
+                    serde_json::json!({
+                        "current_size": stats.current_size,
+                        "min_size": stats.min_size,
+                        "target_size": stats.target_size,
+                        "forecast_per_hour": stats.forecast_per_hour,
+                    }),
+                )
+                .await;
+            } else if stats.is_healthy() {
+                low_alerted = false;
+            }
+
+            if stats.current_size < decision.min_size {
+                refilling = true;
+            } else if stats.current_size >= decision.target_size {
+                refilling = false;
+            }
+
+            if let (true, Some(reason)) = (refilling, decision.throttled) {
+                debug!(
+                    "Node {} deferring presignature generation: {}",
+                    self.node_id.0,
+                    reason.as_str()
+                );
+                metrics::record_presig_generation_throttled(reason);
+            } else if refilling {
 
                 // STABILITY FIX: Generate only 1 presignature per batch cycle
                 // to prevent concurrent session conflicts and AttemptToOverwriteReceivedMsg errors.
@@ -237,7 +328,7 @@ impl PresignatureService {
 
                 info!(
                     "Node {} is presig leader, attempting generation (pool: {}/{})",
-                    self.node_id.0, stats.current_size, self.min_size
+                    self.node_id.0, stats.current_size, decision.target_size
                 );
 
                 // Try to acquire the lock (non-blocking)
@@ -307,61 +398,54 @@ impl PresignatureService {
         }
 
         // Check if we're at max capacity
+        let max_size = self.sizing.read().await.max_size;
         let current_size = self.pool.read().await.len();
-        if current_size >= self.max_size {
+        if current_size >= max_size {
             warn!(
                 "Pool at maximum capacity ({}/{}), skipping generation",
-                current_size, self.max_size
+                current_size, max_size
             );
             return Ok(0);
         }
 
-        let actual_count = count.min(self.max_size - current_size);
+        let actual_count = count.min(max_size - current_size);
 
         info!("Generating {} presignatures...", actual_count);
 
         // FIX SORUN #14: Use try_acquire pattern for cleaner lock handling
-        // This returns None if lock is held (not an error), or Some(lease_id) if acquired
-        let lease_id = {
-            let mut etcd = self.etcd.lock().await;
-            match etcd.try_acquire_presig_generation_lock().await {
-                Ok(Some(id)) => {
-                    info!("Node {} acquired presig lock with lease_id={}", self.node_id.0, id);
-                    id
-                }
-                Ok(None) => {
-                    // Lock is held by another node - this is expected behavior
-                    debug!("Node {} could not acquire presig lock (held by another node)", self.node_id.0);
-                    return Err(OrchestrationError::CeremonyInProgress(
-                        "Another presignature generation is in progress".to_string(),
-                    ));
-                }
-                Err(e) => {
-                    error!("Node {} failed to acquire presig lock: {}", self.node_id.0, e);
-                    return Err(OrchestrationError::StorageError(format!(
-                        "Failed to acquire presig lock: {}", e
-                    )));
-                }
+        // This returns None if lock is held (not an error), or Some(guard) if acquired.
+        // The guard keeps the lease alive while the batch runs.
+        let lock = match self.etcd.try_acquire_presig_generation_lock().await {
+            Ok(Some(guard)) => {
+                info!(
+                    "Node {} acquired presig lock with lease_id={}",
+                    self.node_id.0,
+                    guard.lease_id()
+                );
+                guard
+            }
+            Ok(None) => {
+                // Lock is held by another node - this is expected behavior
+                debug!("Node {} could not acquire presig lock (held by another node)", self.node_id.0);
+                return Err(OrchestrationError::CeremonyInProgress(
+                    "Another presignature generation is in progress".to_string(),
+                ));
+            }
+            Err(e) => {
+                error!("Node {} failed to acquire presig lock: {}", self.node_id.0, e);
+                return Err(OrchestrationError::StorageError(format!(
+                    "Failed to acquire presig lock: {}", e
+                )));
             }
         };
 
-        // CRITICAL: Guaranteed lock release even on error (fixes SORUN #14)
-        // Execute the actual generation logic and ensure lock is always released
+        // Execute the actual generation logic; the lock is released even on
+        // error, and by the guard's drop if this future is cancelled
         let result = self.generate_batch_impl(actual_count, current_size).await;
 
-        // ALWAYS release lock by revoking the lease
-        // This is more reliable than just deleting the key
-        {
-            let mut etcd = self.etcd.lock().await;
-            if let Err(e) = etcd.revoke_lease(lease_id).await {
-                error!("Failed to revoke presig lease {} (may cause SORUN #14): {}", lease_id, e);
-                // Try to release lock as fallback
-                if let Err(e2) = etcd.release_presig_generation_lock().await {
-                    error!("Fallback lock release also failed: {}", e2);
-                }
-            } else {
-                info!("Released presignature generation lock (lease {} revoked)", lease_id);
-            }
+        if let Err(e) = lock.release().await {
+            // The lease expires on its own now that it is no longer renewed
+            error!("Failed to release presig generation lock: {}", e);
         }
 
         result
@@ -537,7 +621,7 @@ impl PresignatureService {
             // Signal that coordinator is ready
             let barrier_key = format!("/presig/{}/ready/{}", session_id, self.node_id.0);
             {
-                let etcd = self.etcd.lock().await;
+                let etcd = &self.etcd;
                 if let Err(e) = etcd.put(&barrier_key, &[1]).await {
                     error!("Failed to signal ready for presig session {}: {}", session_id, e);
                 }
@@ -556,7 +640,7 @@ impl PresignatureService {
             let mut all_ready = false;
             while tokio::time::Instant::now() < ready_deadline {
                 let ready_count = {
-                    let mut etcd = self.etcd.lock().await;
+                    let etcd = &self.etcd;
                     let mut count = 0;
                     for participant in &participants_node_ids {
                         let key = format!("/presig/{}/ready/{}", session_id, participant.0);
@@ -599,7 +683,7 @@ impl PresignatureService {
                     .await;
                 // Clean up barrier keys
                 {
-                    let etcd = self.etcd.lock().await;
+                    let etcd = &self.etcd;
                     for participant in &participants_node_ids {
                         let key = format!("/presig/{}/ready/{}", session_id, participant.0);
                         let _ = etcd.delete(&key).await;
@@ -718,7 +802,7 @@ impl PresignatureService {
 
                 // CRITICAL FIX: Clean up barrier keys on failure too
                 {
-                    let etcd = self.etcd.lock().await;
+                    let etcd = &self.etcd;
                     for participant in &participants_node_ids {
                         let key = format!("/presig/{}/ready/{}", session_id, participant.0);
                         let _ = etcd.delete(&key).await;
@@ -784,7 +868,7 @@ impl PresignatureService {
 
             // Clean up barrier keys from etcd
             {
-                let etcd = self.etcd.lock().await;
+                let etcd = &self.etcd;
                 for participant in &participants_node_ids {
                     let key = format!("/presig/{}/ready/{}", session_id, participant.0);
                     let _ = etcd.delete(&key).await; // Ignore errors during cleanup
@@ -802,7 +886,7 @@ impl PresignatureService {
             "Successfully generated {} presignatures (pool: {}/{})",
             generated,
             current_size + generated,
-            self.target_size
+            self.sizing.read().await.target_size
         );
 
         Ok(generated)
@@ -823,10 +907,10 @@ impl PresignatureService {
         entry.is_used = true;
         let presig_id = entry.id.clone();
 
-        // Update statistics
+        // Update statistics and feed the demand forecaster
         let mut stats = self.stats.write().await;
         stats.total_used += 1;
-        stats.hourly_usage += 1;
+        self.forecaster.write().await.record_usage(chrono::Utc::now());
 
         info!(
             "Acquired presignature: {} (remaining: {}/{})",
@@ -842,19 +926,28 @@ impl PresignatureService {
     pub async fn get_stats(&self) -> PresignatureStats {
         let pool = self.pool.read().await;
         let gen_stats = self.stats.read().await;
+        let sizing = self.sizing.read().await.clone();
+        let hourly_usage = self
+            .forecaster
+            .write()
+            .await
+            .recent_hourly_usage(chrono::Utc::now());
 
         let current_size = pool.iter().filter(|e| !e.is_used).count();
 
         PresignatureStats {
             current_size,
-            target_size: self.target_size,
-            max_size: self.max_size,
-            utilization: if self.target_size > 0 {
-                (current_size as f64 / self.target_size as f64) * 100.0
+            target_size: sizing.target_size,
+            min_size: sizing.min_size,
+            max_size: sizing.max_size,
+            utilization: if sizing.target_size > 0 {
+                (current_size as f64 / sizing.target_size as f64) * 100.0
             } else {
                 0.0
             },
-            hourly_usage: gen_stats.hourly_usage,
+            hourly_usage,
+            forecast_per_hour: sizing.forecast_per_hour,
+            throttled: sizing.throttled,
             total_generated: gen_stats.total_generated,
             total_used: gen_stats.total_used,
         }
@@ -929,7 +1022,7 @@ impl PresignatureService {
         // ============================================================
         let barrier_key = format!("/presig/{}/ready/{}", session_id, self.node_id.0);
         {
-            let etcd = self.etcd.lock().await;
+            let etcd = &self.etcd;
             if let Err(e) = etcd.put(&barrier_key, &[1]).await {
                 error!("Failed to signal ready for presig session {}: {}", session_id, e);
             } else {
@@ -1116,7 +1209,7 @@ impl PresignatureService {
 
         // Clean up our barrier key from etcd (coordinator cleans up all keys)
         {
-            let etcd = self.etcd.lock().await;
+            let etcd = &self.etcd;
             let barrier_key = format!("/presig/{}/ready/{}", session_id, self.node_id.0);
             let _ = etcd.delete(&barrier_key).await; // Ignore errors during cleanup
         }
@@ -1193,7 +1286,7 @@ impl PresignatureService {
         let my_party_index = (self.node_id.0 - 1) as u16;
 
         let config_bytes = {
-            let mut etcd = self.etcd.lock().await;
+            let etcd = &self.etcd;
             etcd.get(config_key).await
                 .map_err(|e| OrchestrationError::Storage(e.into()))?
         };
@@ -1359,6 +1452,7 @@ impl PresignatureService {
 
         #[derive(Debug, Clone, Serialize, Deserialize)]
         struct PresigJoinRequest {
+            coordinator_id: u64,
             session_id: String,
             participants: Vec<u64>,
         }
@@ -1366,6 +1460,7 @@ impl PresignatureService {
         let participant_ids: Vec<u64> = participants.iter().map(|n| n.0).collect();
 
         let join_request = PresigJoinRequest {
+            coordinator_id: self.node_id.0,
             session_id: session_id.to_string(),
             participants: participant_ids.clone(),
         };
@@ -1435,9 +1530,12 @@ mod tests {
         let stats = PresignatureStats {
             current_size: 50,
             target_size: 100,
+            min_size: 20,
             max_size: 150,
             utilization: 50.0,
             hourly_usage: 10,
+            forecast_per_hour: 10.0,
+            throttled: None,
             total_generated: 100,
             total_used: 50,
         };
@@ -1451,9 +1549,12 @@ mod tests {
         let stats = PresignatureStats {
             current_size: 5,
             target_size: 100,
+            min_size: 20,
             max_size: 150,
             utilization: 5.0,
             hourly_usage: 50,
+            forecast_per_hour: 50.0,
+            throttled: None,
             total_generated: 100,
             total_used: 95,
         };
@@ -0,0 +1,422 @@
+//! Adaptive Presignature Pool Sizing
+//!
+//! The presignature pool used to run with fixed sizes (target 100, minimum 20,
+//! maximum 150). Generating presignatures is expensive (~400ms of CPU and several
+//! network rounds per presignature), so keeping a large pool when nobody signs
+//! wastes resources, while a small pool during a burst of withdrawals forces
+//! signing back onto the slow path.
+//!
+//! This module forecasts demand from observed usage and scales the pool target
+//! within configured bounds:
+//!
+//! - **Recent demand**: Usage in the trailing hour
+//! - **Time-of-day demand**: Average usage seen in the upcoming hour-of-day slot
+//!   over the previous days (e.g. payout batches that run every morning)
+//! - **Throttling**: Generation is paused while CPU load or the number of active
+//!   protocol sessions is above configured thresholds, so presignature
+//!   generation never competes with live signing
+//!
+//! Every sizing decision is exported as Prometheus metrics so operators can tune
+//! the cost/latency trade-off.
+
+use chrono::{DateTime, Duration as ChronoDuration, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Number of hour-of-day slots tracked for seasonal demand
+const HOURS_PER_DAY: usize = 24;
+
+/// Configuration for adaptive presignature pool sizing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresigSizingConfig {
+    /// Lower bound for the pool target
+    pub min_target: usize,
+    /// Upper bound for the pool target
+    pub max_target: usize,
+    /// Hard cap on pool size (generation never exceeds this)
+    pub max_size: usize,
+    /// Refill is triggered when the pool falls below `target * refill_ratio`
+    pub refill_ratio: f64,
+    /// How many hours of forecast demand the pool should cover
+    pub lookahead_hours: f64,
+    /// Multiplier applied to forecast demand to absorb bursts
+    pub safety_factor: f64,
+    /// Weight of recent demand vs. time-of-day demand (0.0-1.0)
+    pub recent_weight: f64,
+    /// Number of days of usage history kept for time-of-day forecasting
+    pub history_days: i64,
+    /// Pause generation when 1-minute load average per core exceeds this value
+    pub cpu_load_threshold: f64,
+    /// Pause generation when this many protocol sessions are already active
+    pub max_active_sessions: usize,
+}
+
+impl Default for PresigSizingConfig {
+    fn default() -> Self {
+        Self {
+            min_target: 20,
+            max_target: 100,
+            max_size: 150,
+            refill_ratio: 0.2,
+            lookahead_hours: 1.0,
+            safety_factor: 1.5,
+            recent_weight: 0.6,
+            history_days: 7,
+            cpu_load_threshold: 0.85,
+            max_active_sessions: 2,
+        }
+    }
+}
+
+impl PresigSizingConfig {
+    /// Load sizing configuration from environment variables, falling back to defaults
+    ///
+    /// Recognized variables: `PRESIG_POOL_MIN_TARGET`, `PRESIG_POOL_MAX_TARGET`,
+    /// `PRESIG_POOL_MAX_SIZE`, `PRESIG_LOOKAHEAD_HOURS`, `PRESIG_SAFETY_FACTOR`,
+    /// `PRESIG_CPU_LOAD_THRESHOLD`, `PRESIG_MAX_ACTIVE_SESSIONS`.
+    pub fn from_env() -> Self {
+        fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+
+        let defaults = Self::default();
+        Self {
+            min_target: env_or("PRESIG_POOL_MIN_TARGET", defaults.min_target),
+            max_target: env_or("PRESIG_POOL_MAX_TARGET", defaults.max_target),
+            max_size: env_or("PRESIG_POOL_MAX_SIZE", defaults.max_size),
+            lookahead_hours: env_or("PRESIG_LOOKAHEAD_HOURS", defaults.lookahead_hours),
+            safety_factor: env_or("PRESIG_SAFETY_FACTOR", defaults.safety_factor),
+            cpu_load_threshold: env_or("PRESIG_CPU_LOAD_THRESHOLD", defaults.cpu_load_threshold),
+            max_active_sessions: env_or("PRESIG_MAX_ACTIVE_SESSIONS", defaults.max_active_sessions),
+            ..defaults
+        }
+        .normalized()
+    }
+
+    /// Fix inconsistent bounds (e.g. min_target > max_target)
+    pub fn normalized(mut self) -> Self {
+        self.max_size = self.max_size.max(1);
+        self.max_target = self.max_target.min(self.max_size);
+        self.min_target = self.min_target.min(self.max_target);
+        self.refill_ratio = self.refill_ratio.clamp(0.0, 1.0);
+        self.recent_weight = self.recent_weight.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// Snapshot of system load used for throttling decisions
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct LoadSample {
+    /// 1-minute load average divided by available cores (None if unavailable)
+    pub cpu_load_per_core: Option<f64>,
+    /// Number of protocol sessions currently registered with the message router
+    pub active_sessions: usize,
+}
+
+impl LoadSample {
+    /// Sample CPU load from `/proc/loadavg` (Linux only)
+    pub fn read_cpu_load_per_core() -> Option<f64> {
+        let loadavg = std::fs::read_to_string("/proc/loadavg").ok()?;
+        let load_1m: f64 = loadavg.split_whitespace().next()?.parse().ok()?;
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Some(load_1m / cores as f64)
+    }
+}
+
+/// Reason generation was throttled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleReason {
+    /// CPU load above `cpu_load_threshold`
+    CpuBusy,
+    /// Too many protocol sessions in flight
+    NetworkBusy,
+}
+
+impl ThrottleReason {
+    /// Metric label for this reason
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThrottleReason::CpuBusy => "cpu_busy",
+            ThrottleReason::NetworkBusy => "network_busy",
+        }
+    }
+}
+
+/// Result of a sizing evaluation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizingDecision {
+    /// Pool size we are aiming for
+    pub target_size: usize,
+    /// Refill is triggered below this size
+    pub min_size: usize,
+    /// Hard cap on pool size
+    pub max_size: usize,
+    /// Forecast presignature demand per hour
+    pub forecast_per_hour: f64,
+    /// Presignatures used in the trailing hour
+    pub recent_hourly_usage: usize,
+    /// Set when generation should be paused
+    pub throttled: Option<ThrottleReason>,
+    /// When this decision was made
+    pub decided_at: DateTime<Utc>,
+}
+
+/// Demand forecaster backed by a rolling window of usage events
+///
+/// Usage is tracked as per-hour buckets, so memory stays bounded at
+/// `history_days * 24` entries regardless of signing volume.
+#[derive(Debug, Clone)]
+pub struct DemandForecaster {
+    config: PresigSizingConfig,
+    /// (hour start, count) buckets, oldest first
+    buckets: VecDeque<(DateTime<Utc>, usize)>,
+    /// Exact timestamps of usage within the trailing hour
+    recent: VecDeque<DateTime<Utc>>,
+}
+
+impl DemandForecaster {
+    /// Create a forecaster with an empty history
+    pub fn new(config: PresigSizingConfig) -> Self {
+        Self {
+            config: config.normalized(),
+            buckets: VecDeque::new(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Sizing configuration in use
+    pub fn config(&self) -> &PresigSizingConfig {
+        &self.config
+    }
+
+    /// Record that a presignature was consumed at `at`
+    pub fn record_usage(&mut self, at: DateTime<Utc>) {
+        let hour = truncate_to_hour(at);
+        match self.buckets.back_mut() {
+            Some((start, count)) if *start == hour => *count += 1,
+            _ => self.buckets.push_back((hour, 1)),
+        }
+        self.recent.push_back(at);
+        self.prune(at);
+    }
+
+    /// Number of presignatures used in the hour before `now`
+    pub fn recent_hourly_usage(&mut self, now: DateTime<Utc>) -> usize {
+        self.prune(now);
+        self.recent.len()
+    }
+
+    /// Average usage observed in the hour-of-day slot starting at `hour`
+    /// over previous days (None if there is no history for that slot yet)
+    fn seasonal_usage(&self, hour: DateTime<Utc>) -> Option<f64> {
+        let today = truncate_to_hour(hour);
+        let mut days_seen = 0usize;
+        let mut total = 0usize;
+
+        for day in 1..=self.config.history_days {
+            let slot = today - ChronoDuration::days(day);
+            // Only count days we actually have history for
+            if self.buckets.front().is_none_or(|(oldest, _)| slot < *oldest) {
+                break;
+            }
+            days_seen += 1;
+            total += self
+                .buckets
+                .iter()
+                .find(|(start, _)| *start == slot)
+                .map(|(_, c)| *c)
+                .unwrap_or(0);
+        }
+
+        if days_seen == 0 {
+            None
+        } else {
+            Some(total as f64 / days_seen as f64)
+        }
+    }
+
+    /// Forecast demand per hour for the upcoming hour
+    pub fn forecast_per_hour(&mut self, now: DateTime<Utc>) -> f64 {
+        let recent = self.recent_hourly_usage(now) as f64;
+        let next_hour = now + ChronoDuration::hours(1);
+
+        match self.seasonal_usage(next_hour) {
+            Some(seasonal) => {
+                let w = self.config.recent_weight;
+                w * recent + (1.0 - w) * seasonal
+            }
+            None => recent,
+        }
+    }
+
+    /// Compute the pool sizing decision for `now` under the given load
+    pub fn decide(&mut self, now: DateTime<Utc>, load: LoadSample) -> SizingDecision {
+        let forecast = self.forecast_per_hour(now);
+        let cfg = &self.config;
+
+        let wanted = (forecast * cfg.lookahead_hours * cfg.safety_factor).ceil() as usize;
+        let target_size = wanted.clamp(cfg.min_target, cfg.max_target);
+        let min_size = ((target_size as f64) * cfg.refill_ratio).ceil() as usize;
+
+        let throttled = if load
+            .cpu_load_per_core
+            .is_some_and(|l| l > cfg.cpu_load_threshold)
+        {
+            Some(ThrottleReason::CpuBusy)
+        } else if load.active_sessions >= cfg.max_active_sessions {
+            Some(ThrottleReason::NetworkBusy)
+        } else {
+            None
+        };
+
+        SizingDecision {
+            target_size,
+            min_size: min_size.max(1).min(target_size),
+            max_size: cfg.max_size,
+            forecast_per_hour: forecast,
+            recent_hourly_usage: self.recent.len(),
+            throttled,
+            decided_at: now,
+        }
+    }
+
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let recent_cutoff = now - ChronoDuration::hours(1);
+        while self.recent.front().is_some_and(|t| *t <= recent_cutoff) {
+            self.recent.pop_front();
+        }
+
+        let history_cutoff = truncate_to_hour(now) - ChronoDuration::days(self.config.history_days);
+        while self
+            .buckets
+            .front()
+            .is_some_and(|(start, _)| *start < history_cutoff)
+        {
+            self.buckets.pop_front();
+        }
+
+        // Bound memory even if clocks jump around
+        while self.buckets.len() > (self.config.history_days as usize + 1) * HOURS_PER_DAY {
+            self.buckets.pop_front();
+        }
+    }
+}
+
+fn truncate_to_hour(t: DateTime<Utc>) -> DateTime<Utc> {
+    t.with_minute(0)
+        .and_then(|t| t.with_second(0))
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_idle_pool_uses_min_target() {
+        let mut f = DemandForecaster::new(PresigSizingConfig::default());
+        let d = f.decide(at(10, 12, 0), LoadSample::default());
+
+        assert_eq!(d.target_size, 20);
+        assert_eq!(d.min_size, 4);
+        assert!(d.throttled.is_none());
+    }
+
+    #[test]
+    fn test_recent_burst_scales_target() {
+        let mut f = DemandForecaster::new(PresigSizingConfig::default());
+        for m in 0..40 {
+            f.record_usage(at(10, 11, m));
+        }
+
+        let d = f.decide(at(10, 11, 59), LoadSample::default());
+        assert_eq!(d.recent_hourly_usage, 40);
+        // 40/h * 1h * 1.5 = 60
+        assert_eq!(d.target_size, 60);
+    }
+
+    #[test]
+    fn test_target_clamped_to_max() {
+        let mut f = DemandForecaster::new(PresigSizingConfig::default());
+        for m in 0..59 {
+            for _ in 0..5 {
+                f.record_usage(at(10, 11, m));
+            }
+        }
+
+        let d = f.decide(at(10, 11, 59), LoadSample::default());
+        assert_eq!(d.target_size, 100);
+    }
+
+    #[test]
+    fn test_time_of_day_pattern() {
+        let mut f = DemandForecaster::new(PresigSizingConfig::default());
+        // 50 signings at 09:xx every day for three days
+        for day in 7..10 {
+            for m in 0..50 {
+                f.record_usage(at(day, 9, m));
+            }
+        }
+
+        // At 08:30 on day 10 there is no recent demand, but the 09:00 slot is busy
+        let forecast = f.forecast_per_hour(at(10, 8, 30));
+        assert!((forecast - 0.4 * 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_recent_usage_expires() {
+        let mut f = DemandForecaster::new(PresigSizingConfig::default());
+        f.record_usage(at(10, 10, 0));
+        assert_eq!(f.recent_hourly_usage(at(10, 10, 30)), 1);
+        assert_eq!(f.recent_hourly_usage(at(10, 11, 1)), 0);
+    }
+
+    #[test]
+    fn test_throttling() {
+        let mut f = DemandForecaster::new(PresigSizingConfig::default());
+
+        let busy_cpu = LoadSample {
+            cpu_load_per_core: Some(0.95),
+            active_sessions: 0,
+        };
+        assert_eq!(
+            f.decide(at(10, 12, 0), busy_cpu).throttled,
+            Some(ThrottleReason::CpuBusy)
+        );
+
+        let busy_net = LoadSample {
+            cpu_load_per_core: Some(0.1),
+            active_sessions: 2,
+        };
+        assert_eq!(
+            f.decide(at(10, 12, 0), busy_net).throttled,
+            Some(ThrottleReason::NetworkBusy)
+        );
+    }
+
+    #[test]
+    fn test_config_normalized() {
+        let cfg = PresigSizingConfig {
+            min_target: 500,
+            max_target: 300,
+            max_size: 200,
+            ..Default::default()
+        }
+        .normalized();
+
+        assert_eq!(cfg.max_target, 200);
+        assert_eq!(cfg.min_target, 200);
+    }
+}
@@ -0,0 +1,490 @@
+//! Data retention.
+//!
+//! Finished transactions, their votes and the bookkeeping around them would
+//! otherwise accumulate forever in PostgreSQL and etcd. The retention service
+//! runs on every node, but one node at a time does the work (etcd lock
+//! `/locks/retention`). Each run applies the table policies of
+//! [`RetentionConfig`]:
+//! - Terminal transactions are archived to gzipped JSONL files in
+//!   `archive_dir` on the node doing the run, together with their voting
+//!   rounds, votes, state history, signing and broadcast attempts,
+//!   presignature usage and Byzantine violations, then deleted. Each file is
+//!   recorded in `retention_archives` with its SHA-256 and in the audit log.
+//! - etcd votes, vote counts and transaction states of finished transactions
+//!   are deleted.
+//! - Presignature usage, Byzantine violations and delivered webhook
+//!   deliveries are deleted once old enough.
+//!
+//! Transactions on legal hold (`legal_holds`) are exempt from all of these.
+//! Idempotency keys and cluster events have their own TTLs and are purged by
+//! the API server; the audit log is never pruned.
+//!
+//! An archive is written before its transactions are deleted, so a failed run
+//! can leave a transaction in two archive files but never in none.
+
+use crate::error::{OrchestrationError, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+
+use threshold_storage::{ArchivedTransaction, EtcdStorage, PostgresStorage};
+use threshold_types::{NodeId, TxId};
+
+/// etcd lock held by the node applying the policies
+pub const RETENTION_LOCK_KEY: &str = "/locks/retention";
+
+const DAY: u64 = 24 * 3600;
+
+/// Retention service configuration
+///
+/// A policy of `None` keeps the data forever.
+#[derive(Debug, Clone)]
+pub struct RetentionConfig {
+    /// Time between retention runs
+    pub interval: Duration,
+    /// Directory receiving transaction archives
+    pub archive_dir: PathBuf,
+    /// Maximum transactions per archive file
+    pub batch_size: usize,
+    /// Age after which terminal transactions are archived and deleted
+    pub transactions: Option<Duration>,
+    /// Age after which etcd keys of terminal transactions are deleted
+    pub etcd_keys: Option<Duration>,
+    /// Age after which presignature usage records are deleted
+    pub presignature_usage: Option<Duration>,
+    /// Age after which Byzantine violations are deleted
+    pub byzantine_violations: Option<Duration>,
+    /// Age after which delivered webhook deliveries are deleted
+    pub webhook_deliveries: Option<Duration>,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(3600),
+            archive_dir: PathBuf::from("/data/archive"),
+            batch_size: 500,
+            transactions: Some(Duration::from_secs(90 * DAY)),
+            etcd_keys: Some(Duration::from_secs(3600)),
+            presignature_usage: Some(Duration::from_secs(30 * DAY)),
+            byzantine_violations: Some(Duration::from_secs(365 * DAY)),
+            webhook_deliveries: Some(Duration::from_secs(7 * DAY)),
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// Load configuration from `RETENTION_*` environment variables, falling
+    /// back to defaults for anything unset or unparsable. A policy set to 0
+    /// keeps the data forever.
+    pub fn from_env() -> Self {
+        fn env<T: std::str::FromStr>(key: &str) -> Option<T> {
+            std::env::var(key).ok().and_then(|v| v.parse().ok())
+        }
+
+        fn policy(key: &str, unit_secs: u64, default: Option<Duration>) -> Option<Duration> {
+            match env::<u64>(key) {
+                Some(0) => None,
+                Some(n) => Some(Duration::from_secs(n.saturating_mul(unit_secs))),
+                None => default,
+            }
+        }
+
+        let defaults = Self::default();
+        Self {
+            interval: env("RETENTION_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.interval),
+            archive_dir: env("RETENTION_ARCHIVE_DIR").unwrap_or(defaults.archive_dir),
+            batch_size: env("RETENTION_BATCH_SIZE")
+                .unwrap_or(defaults.batch_size)
+                .max(1),
+            transactions: policy("RETENTION_TRANSACTIONS_DAYS", DAY, defaults.transactions),
+            etcd_keys: policy("RETENTION_ETCD_KEYS_HOURS", 3600, defaults.etcd_keys),
+            presignature_usage: policy(
+                "RETENTION_PRESIGNATURE_USAGE_DAYS",
+                DAY,
+                defaults.presignature_usage,
+            ),
+            byzantine_violations: policy(
+                "RETENTION_BYZANTINE_VIOLATIONS_DAYS",
+                DAY,
+                defaults.byzantine_violations,
+            ),
+            webhook_deliveries: policy(
+                "RETENTION_WEBHOOK_DELIVERIES_DAYS",
+                DAY,
+                defaults.webhook_deliveries,
+            ),
+        }
+    }
+}
+
+/// What one retention run removed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionReport {
+    /// Archive files written, by name
+    pub archive_files: Vec<String>,
+    pub archived_transactions: u64,
+    /// Transactions whose etcd keys were deleted
+    pub pruned_etcd_transactions: u64,
+    pub pruned_presignature_usage: u64,
+    pub pruned_byzantine_violations: u64,
+    pub pruned_webhook_deliveries: u64,
+}
+
+/// Name of an archive file written by `node_id` at `time`
+pub fn archive_file_name(node_id: NodeId, time: chrono::DateTime<chrono::Utc>) -> String {
+    format!(
+        "transactions-node{}-{}.jsonl.gz",
+        node_id.0,
+        time.format("%Y%m%dT%H%M%S%.3fZ")
+    )
+}
+
+/// Write `records` as gzipped JSONL to `dir/file_name`
+///
+/// The file is written under a temporary name, synced and then renamed, so a
+/// crash never leaves a truncated archive behind. Returns the hex SHA-256 of
+/// the compressed file.
+pub fn write_archive(dir: &Path, file_name: &str, records: &[ArchivedTransaction]) -> std::io::Result<String> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(file_name);
+    let tmp_path = dir.join(format!("{}.tmp", file_name));
+
+    let file = std::fs::File::create(&tmp_path)?;
+    let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+    for record in records {
+        serde_json::to_writer(&mut encoder, &record.record)?;
+        encoder.write_all(b"\n")?;
+    }
+    let file = encoder
+        .finish()?
+        .into_inner()
+        .map_err(|e| e.into_error())?;
+    file.sync_all()?;
+    std::fs::rename(&tmp_path, &path)?;
+
+    Ok(hex::encode(Sha256::digest(std::fs::read(&path)?)))
+}
+
+/// Retention service.
+pub struct RetentionService {
+    config: RetentionConfig,
+    node_id: NodeId,
+    postgres: Arc<PostgresStorage>,
+    etcd: EtcdStorage,
+    shutdown: Arc<RwLock<bool>>,
+}
+
+impl RetentionService {
+    /// Start the retention service in the background
+    pub fn start(self: Arc<Self>) -> JoinHandle<Result<()>> {
+        tokio::spawn(async move {
+            info!(
+                "Retention service started (interval: {:?}, archive dir: {})",
+                self.config.interval,
+                self.config.archive_dir.display()
+            );
+
+            match self.run().await {
+                Ok(()) => {
+                    info!("Retention service stopped normally");
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Retention service error: {}", e);
+                    Err(e)
+                }
+            }
+        })
+    }
+
+    /// Main retention loop
+    async fn run(&self) -> Result<()> {
+        let mut interval = interval(self.config.interval);
+
+        loop {
+            if *self.shutdown.read().await {
+                info!("Shutdown signal received, stopping retention service");
+                return Ok(());
+            }
+
+            interval.tick().await;
+
+            match self.retain_once().await {
+                Ok(report) if report == RetentionReport::default() => {}
+                Ok(report) => info!("Retention run finished: {:?}", report),
+                Err(e) => error!("Retention run failed: {}", e),
+            }
+        }
+    }
+
+    /// Apply every policy once, unless another node is already doing so
+    pub async fn retain_once(&self) -> Result<RetentionReport> {
+        let guard = match self.etcd.try_acquire_lock(RETENTION_LOCK_KEY).await {
+            Ok(Some(guard)) => guard,
+            Ok(None) => {
+                debug!("Retention run skipped: another node holds the lock");
+                return Ok(RetentionReport::default());
+            }
+            Err(e) => return Err(OrchestrationError::Storage(e.into())),
+        };
+
+        let mut report = RetentionReport::default();
+        let result = self.apply_policies(&mut report).await;
+
+        if let Err(e) = guard.release().await {
+            warn!("Failed to release retention lock: {}", e);
+        }
+
+        result.map(|()| report)
+    }
+
+    async fn apply_policies(&self, report: &mut RetentionReport) -> Result<()> {
+        if let Some(max_age) = self.config.transactions {
+            self.archive_transactions(max_age, report).await?;
+        }
+
+        if let Some(max_age) = self.config.etcd_keys {
+            report.pruned_etcd_transactions = self.prune_etcd_keys(max_age).await?;
+        }
+
+        if let Some(max_age) = self.config.presignature_usage {
+            report.pruned_presignature_usage = self
+                .postgres
+                .prune_presignature_usage(max_age.as_secs())
+                .await
+                .map_err(|e| OrchestrationError::Storage(e.into()))?;
+        }
+
+        if let Some(max_age) = self.config.byzantine_violations {
+            report.pruned_byzantine_violations = self
+                .postgres
+                .prune_byzantine_violations(max_age.as_secs())
+                .await
+                .map_err(|e| OrchestrationError::Storage(e.into()))?;
+        }
+
+        if let Some(max_age) = self.config.webhook_deliveries {
+            report.pruned_webhook_deliveries = self
+                .postgres
+                .prune_webhook_deliveries(max_age.as_secs())
+                .await
+                .map_err(|e| OrchestrationError::Storage(e.into()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Archive and delete terminal transactions, one file per batch
+    async fn archive_transactions(&self, max_age: Duration, report: &mut RetentionReport) -> Result<()> {
+        loop {
+            let batch = self
+                .postgres
+                .list_archivable_transactions(max_age.as_secs(), self.config.batch_size as i64)
+                .await
+                .map_err(|e| OrchestrationError::Storage(e.into()))?;
+            if batch.is_empty() {
+                return Ok(());
+            }
+
+            let file_name = archive_file_name(self.node_id, chrono::Utc::now());
+            let txids: Vec<String> = batch.iter().map(|t| t.txid.clone()).collect();
+            let batch_len = batch.len();
+
+            let dir = self.config.archive_dir.clone();
+            let name = file_name.clone();
+            let sha256 = tokio::task::spawn_blocking(move || write_archive(&dir, &name, &batch))
+                .await
+                .map_err(|e| OrchestrationError::Internal(format!("Archive task failed: {}", e)))?
+                .map_err(|e| {
+                    OrchestrationError::Internal(format!("Failed to write archive {}: {}", file_name, e))
+                })?;
+
+            let deleted = self
+                .postgres
+                .delete_archived_transactions(&file_name, &sha256, &txids)
+                .await
+                .map_err(|e| OrchestrationError::Storage(e.into()))?;
+
+            // Only transactions that are gone from PostgreSQL lose their etcd
+            // keys; one put on hold meanwhile keeps its live state
+            for txid in &deleted {
+                if let Err(e) = self.etcd.prune_transaction_keys(&TxId(txid.clone())).await {
+                    warn!("Failed to prune etcd keys of archived transaction {}: {}", txid, e);
+                }
+            }
+
+            info!(
+                "Archived {} transaction(s) to {} ({} deleted)",
+                batch_len, file_name, deleted.len()
+            );
+            report.archive_files.push(file_name);
+            report.archived_transactions += deleted.len() as u64;
+
+            // Whatever was not deleted (a hold placed meanwhile) would be
+            // listed again; stop rather than loop on it
+            if batch_len < self.config.batch_size || deleted.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Delete the etcd keys of transactions finished more than `max_age` ago;
+    /// returns the number of transactions pruned
+    async fn prune_etcd_keys(&self, max_age: Duration) -> Result<u64> {
+        let candidates: Vec<String> = self
+            .etcd
+            .list_transactions_with_keys()
+            .await
+            .map_err(|e| OrchestrationError::Storage(e.into()))?
+            .into_iter()
+            .map(|t| t.0)
+            .collect();
+        if candidates.is_empty() {
+            return Ok(0);
+        }
+
+        let finished = self
+            .postgres
+            .filter_finished_transactions(&candidates, max_age.as_secs())
+            .await
+            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+
+        let mut pruned = 0;
+        for txid in finished {
+            match self.etcd.prune_transaction_keys(&TxId(txid.clone())).await {
+                Ok(()) => pruned += 1,
+                Err(e) => warn!("Failed to prune etcd keys of transaction {}: {}", txid, e),
+            }
+        }
+
+        Ok(pruned)
+    }
+
+    /// Signal the service to stop
+    pub async fn shutdown(&self) {
+        info!("Initiating retention service shutdown");
+        *self.shutdown.write().await = true;
+    }
+}
+
+/// Builder for RetentionService
+pub struct RetentionServiceBuilder {
+    config: Option<RetentionConfig>,
+    node_id: Option<NodeId>,
+    postgres: Option<Arc<PostgresStorage>>,
+    etcd: Option<EtcdStorage>,
+}
+
+impl RetentionServiceBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: None,
+            node_id: None,
+            postgres: None,
+            etcd: None,
+        }
+    }
+
+    pub fn with_config(mut self, config: RetentionConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Node ID, recorded in the names of the archives this node writes
+    pub fn with_node_id(mut self, node_id: NodeId) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+
+    pub fn with_postgres(mut self, postgres: Arc<PostgresStorage>) -> Self {
+        self.postgres = Some(postgres);
+        self
+    }
+
+    pub fn with_etcd(mut self, etcd: EtcdStorage) -> Self {
+        self.etcd = Some(etcd);
+        self
+    }
+
+    pub fn build(self) -> Result<Arc<RetentionService>> {
+        let node_id = self.node_id
+            .ok_or_else(|| OrchestrationError::Config("Node ID is required".to_string()))?;
+        let postgres = self.postgres
+            .ok_or_else(|| OrchestrationError::Config("PostgresStorage is required".to_string()))?;
+        let etcd = self.etcd
+            .ok_or_else(|| OrchestrationError::Config("EtcdStorage is required".to_string()))?;
+
+        Ok(Arc::new(RetentionService {
+            config: self.config.unwrap_or_default(),
+            node_id,
+            postgres,
+            etcd,
+            shutdown: Arc::new(RwLock::new(false)),
+        }))
+    }
+}
+
+impl Default for RetentionServiceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::{BufRead, BufReader};
+
+    fn record(txid: &str) -> ArchivedTransaction {
+        ArchivedTransaction {
+            txid: txid.to_string(),
+            record: serde_json::json!({
+                "txid": txid,
+                "state": "confirmed",
+                "voting_rounds": [{ "round_number": 1, "votes": [] }],
+            }),
+        }
+    }
+
+    #[test]
+    fn test_write_archive_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let records = vec![record("tx1"), record("tx2")];
+
+        let sha256 = write_archive(dir.path(), "archive.jsonl.gz", &records).unwrap();
+
+        let path = dir.path().join("archive.jsonl.gz");
+        assert_eq!(sha256, hex::encode(Sha256::digest(std::fs::read(&path).unwrap())));
+        assert!(!dir.path().join("archive.jsonl.gz.tmp").exists());
+
+        let lines: Vec<serde_json::Value> = BufReader::new(GzDecoder::new(std::fs::File::open(&path).unwrap()))
+            .lines()
+            .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+            .collect();
+        assert_eq!(lines, vec![records[0].record.clone(), records[1].record.clone()]);
+    }
+
+    #[test]
+    fn test_archive_file_names_sort_by_time() {
+        let earlier = chrono::DateTime::parse_from_rfc3339("2026-01-02T03:04:05.006Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let later = earlier + chrono::Duration::milliseconds(1);
+
+        let name = archive_file_name(NodeId(2), earlier);
+        assert_eq!(name, "transactions-node2-20260102T030405.006Z.jsonl.gz");
+        assert!(name < archive_file_name(NodeId(2), later));
+    }
+}
@@ -0,0 +1,351 @@
+//! Retry bookkeeping for signing and broadcasting.
+//!
+//! Every signing session and broadcast the orchestrator makes for a
+//! transaction is recorded in `transaction_attempts`. Failures are classified
+//! by [`classify`]: a fatal error fails the transaction at once, a retryable
+//! one is tried again after an exponential backoff, until `max_retries`
+//! retries have failed. A signing retry leaves out the signers blamed for
+//! earlier failures ([`OrchestrationError::SignerFault`]), as long as enough
+//! signers remain to reach the threshold.
+//!
+//! The history lives in PostgreSQL, so whichever node picks the transaction
+//! up next continues where the previous one stopped.
+
+use crate::config::OrchestrationConfig;
+use crate::error::OrchestrationError;
+use crate::webhook_dispatcher::backoff_delay;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use threshold_bitcoin::BitcoinError;
+use threshold_storage::{AttemptOutcome, TransactionAttempt};
+use threshold_types::NodeId;
+
+/// Whether retrying can fix a failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    Retryable,
+    Fatal,
+}
+
+impl ErrorClass {
+    pub fn outcome(&self) -> AttemptOutcome {
+        match self {
+            ErrorClass::Retryable => AttemptOutcome::Retryable,
+            ErrorClass::Fatal => AttemptOutcome::Fatal,
+        }
+    }
+}
+
+/// Classify a signing or broadcast error
+///
+/// Misconfiguration and transactions the network rejects outright are fatal;
+/// timeouts, unreachable or misbehaving nodes and storage hiccups are not.
+pub fn classify(error: &OrchestrationError) -> ErrorClass {
+    match error {
+        OrchestrationError::Config(_)
+        | OrchestrationError::InvalidConfig(_)
+        | OrchestrationError::InvalidPublicKey(_)
+        | OrchestrationError::NotImplemented(_)
+        | OrchestrationError::TransactionNotFound(_)
+        | OrchestrationError::BroadcastRejected(_) => ErrorClass::Fatal,
+        _ => ErrorClass::Retryable,
+    }
+}
+
+/// A failed attempt together with its class
+#[derive(Debug)]
+pub struct AttemptFailure {
+    pub error: OrchestrationError,
+    pub class: ErrorClass,
+}
+
+impl AttemptFailure {
+    /// A failure that will recur however often the step is retried
+    pub fn fatal(error: OrchestrationError) -> Self {
+        Self { error, class: ErrorClass::Fatal }
+    }
+}
+
+impl From<OrchestrationError> for AttemptFailure {
+    fn from(error: OrchestrationError) -> Self {
+        let class = classify(&error);
+        Self { error, class }
+    }
+}
+
+/// Whether a broadcast was refused because the node already has the
+/// transaction ("txn-already-known", "txn-already-in-mempool", "already in
+/// block chain")
+///
+/// The transaction reached the network, e.g. through another node or an
+/// earlier attempt whose answer was lost, so the broadcast succeeded.
+pub fn already_broadcast(error: &BitcoinError) -> bool {
+    match error {
+        BitcoinError::Broadcast { body, .. } | BitcoinError::ApiError { body, .. } => {
+            body.to_lowercase().contains("already")
+        }
+        _ => false,
+    }
+}
+
+/// Map a broadcast failure, telling rejections of the transaction apart from
+/// transient failures
+///
+/// A 4xx answer other than 408 (timeout) and 429 (rate limited) means the
+/// node refused the transaction itself, e.g. a missing input or an invalid
+/// signature, and sending it again cannot help. Callers treat
+/// [`already_broadcast`] answers as success before mapping; should one reach
+/// here it is left retryable.
+pub fn broadcast_error(error: BitcoinError) -> OrchestrationError {
+    match error {
+        ref e if already_broadcast(e) => OrchestrationError::Bitcoin(error.to_string()),
+        BitcoinError::Broadcast { status, .. } | BitcoinError::ApiError { status, .. }
+            if (400..500).contains(&status) && status != 408 && status != 429 =>
+        {
+            OrchestrationError::BroadcastRejected(error.to_string())
+        }
+        BitcoinError::Configuration(_) | BitcoinError::InvalidTxHex(_) => {
+            OrchestrationError::BroadcastRejected(error.to_string())
+        }
+        e => OrchestrationError::Bitcoin(e.to_string()),
+    }
+}
+
+/// Retry limits, from [`OrchestrationConfig`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &OrchestrationConfig) -> Self {
+        Self {
+            max_retries: config.max_retries,
+            initial_backoff: config.initial_backoff,
+            max_backoff: config.max_backoff,
+        }
+    }
+
+    /// Wait before the retry that follows the `failures`-th failure
+    pub fn delay(&self, failures: u32) -> Duration {
+        backoff_delay(failures, self.initial_backoff, self.max_backoff)
+    }
+}
+
+/// Failed attempts since the last success
+pub fn consecutive_failures(history: &[TransactionAttempt]) -> u32 {
+    let mut history: Vec<&TransactionAttempt> = history.iter().collect();
+    history.sort_by_key(|a| a.attempt);
+    history
+        .iter()
+        .rev()
+        .take_while(|a| a.outcome != AttemptOutcome::Succeeded)
+        .count() as u32
+}
+
+/// What to do next with an operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Make attempt number `attempt` without the `excluded` signers
+    Attempt { attempt: u32, excluded: Vec<NodeId> },
+    /// The last attempt failed; retry no earlier than this
+    Wait(DateTime<Utc>),
+    /// Retrying is pointless or the retries are used up
+    GiveUp(String),
+}
+
+/// Decide on the next attempt of an operation from its history
+///
+/// `history` holds the earlier attempts of one operation of one transaction.
+/// Only failures since the last success count: a transaction sent back to be
+/// signed again starts with a fresh budget. At most `max_excluded` signers
+/// are left out, the most recently blamed first.
+pub fn next_attempt(
+    policy: &RetryPolicy,
+    history: &[TransactionAttempt],
+    max_excluded: usize,
+    now: DateTime<Utc>,
+) -> RetryDecision {
+    let mut history: Vec<&TransactionAttempt> = history.iter().collect();
+    history.sort_by_key(|a| a.attempt);
+
+    let Some(last) = history.last() else {
+        return RetryDecision::Attempt { attempt: 1, excluded: Vec::new() };
+    };
+    let next = last.attempt + 1;
+
+    if last.outcome == AttemptOutcome::Fatal {
+        return RetryDecision::GiveUp(format!(
+            "attempt {} failed permanently: {}",
+            last.attempt,
+            last.error.as_deref().unwrap_or("unknown error")
+        ));
+    }
+
+    let failures: Vec<&TransactionAttempt> = history
+        .iter()
+        .rev()
+        .take_while(|a| a.outcome != AttemptOutcome::Succeeded)
+        .copied()
+        .collect();
+
+    if failures.len() > policy.max_retries as usize {
+        return RetryDecision::GiveUp(format!(
+            "{} attempts failed, last: {}",
+            failures.len(),
+            last.error.as_deref().unwrap_or("unknown error")
+        ));
+    }
+
+    if let Some(retry_at) = last.retry_at {
+        if last.outcome == AttemptOutcome::Retryable && now < retry_at {
+            return RetryDecision::Wait(retry_at);
+        }
+    }
+
+    let mut excluded: Vec<NodeId> = Vec::new();
+    for node_id in failures.iter().filter_map(|a| a.faulty_node) {
+        if excluded.len() == max_excluded {
+            break;
+        }
+        if !excluded.contains(&node_id) {
+            excluded.push(node_id);
+        }
+    }
+
+    RetryDecision::Attempt { attempt: next, excluded }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use threshold_storage::AttemptOperation;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+
+    fn attempt(n: u32, outcome: AttemptOutcome, faulty: Option<u64>) -> TransactionAttempt {
+        let at = DateTime::from_timestamp(1_700_000_000 + i64::from(n) * 10, 0).unwrap();
+        TransactionAttempt {
+            operation: AttemptOperation::Signing,
+            attempt: n,
+            outcome,
+            error: (outcome != AttemptOutcome::Succeeded).then(|| "boom".to_string()),
+            coordinator: NodeId(1),
+            excluded_nodes: Vec::new(),
+            faulty_node: faulty.map(NodeId),
+            started_at: at,
+            finished_at: at,
+            retry_at: (outcome == AttemptOutcome::Retryable).then(|| at + chrono::Duration::seconds(5)),
+        }
+    }
+
+    #[test]
+    fn test_first_attempt_and_backoff() {
+        let now = Utc::now();
+        assert_eq!(
+            next_attempt(&policy(), &[], 1, now),
+            RetryDecision::Attempt { attempt: 1, excluded: vec![] }
+        );
+
+        let history = [attempt(1, AttemptOutcome::Retryable, None)];
+        let retry_at = history[0].retry_at.unwrap();
+        assert_eq!(
+            next_attempt(&policy(), &history, 1, retry_at - chrono::Duration::seconds(1)),
+            RetryDecision::Wait(retry_at)
+        );
+        assert_eq!(
+            next_attempt(&policy(), &history, 1, retry_at),
+            RetryDecision::Attempt { attempt: 2, excluded: vec![] }
+        );
+
+        assert_eq!(policy().delay(1), Duration::from_secs(1));
+        assert_eq!(policy().delay(3), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_gives_up_on_fatal_or_exhausted_retries() {
+        let now = Utc::now();
+        let fatal = [attempt(1, AttemptOutcome::Fatal, None)];
+        assert!(matches!(next_attempt(&policy(), &fatal, 1, now), RetryDecision::GiveUp(_)));
+
+        let exhausted = [
+            attempt(1, AttemptOutcome::Retryable, None),
+            attempt(2, AttemptOutcome::Retryable, None),
+            attempt(3, AttemptOutcome::Retryable, None),
+        ];
+        assert!(matches!(next_attempt(&policy(), &exhausted, 1, now), RetryDecision::GiveUp(_)));
+        assert_eq!(consecutive_failures(&exhausted), 3);
+
+        // A success resets the budget
+        let reset = [
+            attempt(1, AttemptOutcome::Retryable, None),
+            attempt(2, AttemptOutcome::Retryable, None),
+            attempt(3, AttemptOutcome::Succeeded, None),
+        ];
+        assert_eq!(
+            next_attempt(&policy(), &reset, 1, now),
+            RetryDecision::Attempt { attempt: 4, excluded: vec![] }
+        );
+    }
+
+    #[test]
+    fn test_excludes_faulty_signers_up_to_limit() {
+        let now = Utc::now();
+        let history = [
+            attempt(1, AttemptOutcome::Retryable, Some(3)),
+            attempt(2, AttemptOutcome::Retryable, Some(5)),
+        ];
+        assert_eq!(
+            next_attempt(&policy(), &history, 1, now),
+            RetryDecision::Attempt { attempt: 3, excluded: vec![NodeId(5)] }
+        );
+        assert_eq!(
+            next_attempt(&policy(), &history, 2, now),
+            RetryDecision::Attempt { attempt: 3, excluded: vec![NodeId(5), NodeId(3)] }
+        );
+    }
+
+    #[test]
+    fn test_classifies_broadcast_errors() {
+        let rejected = broadcast_error(BitcoinError::Broadcast {
+            status: 400,
+            body: "bad-txns-inputs-missingorspent".to_string(),
+        });
+        assert_eq!(classify(&rejected), ErrorClass::Fatal);
+
+        let limited = broadcast_error(BitcoinError::Broadcast { status: 429, body: String::new() });
+        assert_eq!(classify(&limited), ErrorClass::Retryable);
+
+        let known = BitcoinError::Broadcast {
+            status: 400,
+            body: "txn-already-known".to_string(),
+        };
+        assert!(already_broadcast(&known));
+        assert!(already_broadcast(&BitcoinError::ApiError {
+            status: 400,
+            body: "Transaction already in block chain".to_string(),
+        }));
+        assert!(!already_broadcast(&BitcoinError::Broadcast {
+            status: 400,
+            body: "bad-txns-inputs-missingorspent".to_string(),
+        }));
+        assert_eq!(classify(&broadcast_error(known)), ErrorClass::Retryable);
+
+        let down = broadcast_error(BitcoinError::ApiRequest("connection refused".to_string()));
+        assert_eq!(classify(&down), ErrorClass::Retryable);
+
+        let fault = OrchestrationError::SignerFault { node_id: NodeId(2), reason: "timeout".to_string() };
+        assert_eq!(classify(&fault), ErrorClass::Retryable);
+        assert_eq!(fault.faulty_node(), Some(NodeId(2)));
+    }
+}
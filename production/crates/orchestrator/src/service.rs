@@ -6,23 +6,24 @@ use crate::config::OrchestrationConfig;
 use crate::error::{OrchestrationError, Result};
 use crate::signing_coordinator::{SigningCoordinator, SigningRequest, SignatureProtocol};
 use crate::protocol_router::ProtocolRouter;
+use crate::retry::{self, AttemptFailure, ErrorClass, RetryDecision, RetryPolicy};
 use crate::metrics;
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
 use tokio::time::interval;
 use tracing::{debug, error, info, warn};
 
-use threshold_storage::{EtcdStorage, PostgresStorage};
+use threshold_storage::{AttemptOperation, AttemptOutcome, EtcdStorage, LockGuard, PostgresStorage, TransactionAttempt};
 use threshold_consensus::{VoteProcessor, VoteState};
 use protocols::p2p::P2pSessionCoordinator;
 use threshold_bitcoin::BitcoinClient;
-use threshold_types::{Transaction, TxId, TransactionState, VotingRound, VoteRequest};
+use threshold_types::{NodeId, RevotePolicy, Transaction, TxId, TransactionState, VotingRound, VoteRequest};
 use std::collections::HashMap;
 use std::time::Duration;
 
 /// Voting completion status
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 enum VotingStatus {
     /// Voting approved (threshold reached)
     Approved,
@@ -30,8 +31,20 @@ enum VotingStatus {
     Rejected,
     /// Voting still in progress
     Pending,
-    /// Voting timed out
-    TimedOut,
+    /// The current voting round timed out
+    TimedOut(VotingRound),
+}
+
+/// Whether a storage error is the rejection of a write with a fencing token
+/// older than the current lock holder's
+fn is_stale_fencing_token(error: &OrchestrationError) -> bool {
+    match error {
+        OrchestrationError::Storage(e) => matches!(
+            e.downcast_ref::<threshold_types::Error>(),
+            Some(threshold_types::Error::StaleFencingToken { .. })
+        ),
+        _ => false,
+    }
 }
 
 /// Transaction state summary for monitoring
@@ -63,8 +76,8 @@ pub struct OrchestrationService {
     /// PostgreSQL storage for persistent state.
     postgres: Arc<PostgresStorage>,
 
-    /// etcd storage for distributed coordination (with interior mutability).
-    etcd: Arc<Mutex<EtcdStorage>>,
+    /// etcd storage for distributed coordination.
+    etcd: EtcdStorage,
 
     /// Bitcoin client for broadcasting transactions.
     bitcoin: Arc<BitcoinClient>,
@@ -75,6 +88,9 @@ pub struct OrchestrationService {
     /// Protocol router for automatic protocol selection.
     protocol_router: Arc<ProtocolRouter>,
 
+    /// This node's ID (sent as the coordinator of vote requests).
+    node_id: NodeId,
+
     /// HTTP client for broadcasting to nodes.
     http_client: reqwest::Client,
 
@@ -92,10 +108,12 @@ impl OrchestrationService {
         vote_processor: Arc<VoteProcessor>,
         session_coordinator: Arc<P2pSessionCoordinator>,
         postgres: Arc<PostgresStorage>,
-        etcd: Arc<Mutex<EtcdStorage>>,
+        etcd: EtcdStorage,
         bitcoin: Arc<BitcoinClient>,
         signing_coordinator: Arc<SigningCoordinator>,
         protocol_router: Arc<ProtocolRouter>,
+        node_id: NodeId,
+        http_client: reqwest::Client,
         node_endpoints: HashMap<u64, String>,
     ) -> Self {
         Self {
@@ -107,7 +125,8 @@ impl OrchestrationService {
             bitcoin,
             signing_coordinator,
             protocol_router,
-            http_client: reqwest::Client::new(),
+            node_id,
+            http_client,
             node_endpoints,
             shutdown: Arc::new(RwLock::new(false)),
         }
@@ -248,10 +267,9 @@ impl OrchestrationService {
     /// Process pending transactions (state: pending).
     ///
     /// For each pending transaction:
-    /// 1. Create voting round in PostgreSQL
-    /// 2. Initialize vote count in etcd
-    /// 3. Broadcast vote request to all nodes via P2P
-    /// 4. Update state to "voting"
+    /// 1. Create voting round 1 in PostgreSQL
+    /// 2. Update state to "voting"
+    /// 3. Broadcast vote request to all nodes via HTTP
     async fn process_pending_transactions(&self) -> Result<()> {
         let pending_txs = self.postgres.get_transactions_by_state("pending").await
             .map_err(|e| OrchestrationError::Storage(e.into()))?;
@@ -277,14 +295,82 @@ impl OrchestrationService {
         Ok(())
     }
 
+    /// Move a transaction from `from` to `to` on behalf of this node
+    ///
+    /// Fails with a `StateConflict` storage error if another node moved the
+    /// transaction first.
+    async fn transition(
+        &self,
+        txid: &TxId,
+        from: TransactionState,
+        to: TransactionState,
+        reason: &str,
+    ) -> Result<()> {
+        let actor = format!("node-{}/orchestrator", self.node_id.0);
+        self.postgres
+            .transition_transaction_state(txid, from, to, &actor, reason)
+            .await
+            .map(|_| ())
+            .map_err(|e| OrchestrationError::Storage(e.into()))
+    }
+
     /// Initiate voting for a transaction.
     async fn initiate_voting(&self, tx: &Transaction) -> Result<()> {
         // 1. Create voting round in PostgreSQL
+        let vote_request = self.create_voting_round(&tx.txid, 1).await?;
+
+        // 2. Update transaction state to "voting"
+        self.transition(&tx.txid, TransactionState::Pending, TransactionState::Voting, "voting round started")
+            .await?;
+
+        // 3. Broadcast vote request to all nodes via HTTP
+        self.request_votes(&vote_request).await;
+
+        Ok(())
+    }
+
+    /// Open voting round `round_number` after the previous one timed out.
+    ///
+    /// The vote store moves on to the round first, carrying the earlier
+    /// votes over or expiring them per `revote_policy`. That step is
+    /// idempotent, so if creating the PostgreSQL round fails the timed-out
+    /// round is still the latest one and the next poll simply retries both.
+    /// The round row is unique per transaction and round number, so when
+    /// several nodes notice the timeout only one of them creates it and asks
+    /// the nodes to vote again. The transaction stays in voting throughout.
+    async fn open_next_voting_round(&self, txid: &TxId, round_number: u32) -> Result<()> {
+        self.etcd
+            .open_vote_round(txid, round_number, self.config.revote_policy)
+            .await
+            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+
+        let vote_request = self.create_voting_round(txid, round_number).await?;
+
+        if let Err(e) = self.postgres.record_audit_event(
+            txid,
+            "voting_round_opened",
+            &format!(
+                "Voting round {} of {} opened ({:?})",
+                round_number, self.config.max_voting_rounds, self.config.revote_policy
+            ),
+        ).await {
+            error!("Failed to record audit event: {}", e);
+        }
+
+        self.request_votes(&vote_request).await;
+
+        Ok(())
+    }
+
+    /// Create voting round `round_number` in PostgreSQL, returning the
+    /// request that asks the nodes to vote in it.
+    async fn create_voting_round(&self, txid: &TxId, round_number: u32) -> Result<VoteRequest> {
         let now = chrono::Utc::now();
+        let timeout_at = now + chrono::Duration::seconds(self.config.voting_timeout.as_secs() as i64);
         let voting_round = VotingRound {
             id: 0, // will be assigned by database
-            tx_id: tx.txid.clone(),
-            round_number: 1,
+            tx_id: txid.clone(),
+            round_number,
             total_nodes: 5,
             threshold: 4,
             votes_received: 0,
@@ -292,32 +378,26 @@ impl OrchestrationService {
             completed: false,
             started_at: now,
             completed_at: None,
-            timeout_at: now + chrono::Duration::seconds(self.config.voting_timeout.as_secs() as i64),
+            timeout_at,
         };
 
         let round_id = self.postgres.create_voting_round(&voting_round).await
             .map_err(|e| OrchestrationError::Storage(e.into()))?;
 
-        info!("Created voting round {} for tx {:?}", round_id, tx.txid);
-
-        // 2. Initialize vote count in etcd (if method exists)
-        // For now, skip this step as init_vote_count doesn't exist
-        // self.etcd.init_vote_count(&tx.txid).await
-        //     .map_err(|e| OrchestrationError::Storage(e.into()))?;
+        info!("Created voting round {} (round {}) for tx {:?}", round_id, round_number, txid);
 
-        // 3. Update transaction state to "voting"
-        self.postgres.update_transaction_state(&tx.txid, TransactionState::Voting).await
-            .map_err(|e| OrchestrationError::Storage(e.into()))?;
-
-        // 4. Broadcast vote request to all nodes via HTTP
-        let vote_request = VoteRequest {
-            tx_id: tx.txid.clone(),
+        Ok(VoteRequest {
+            coordinator_id: self.node_id,
+            tx_id: txid.clone(),
             round_id,
-            round_number: 1,
+            round_number,
             threshold: 4,
-            timeout_at: now + chrono::Duration::seconds(self.config.voting_timeout.as_secs() as i64),
-        };
+            timeout_at,
+        })
+    }
 
+    /// Send a vote request to all nodes; unreachable nodes are skipped.
+    async fn request_votes(&self, vote_request: &VoteRequest) {
         // Broadcast to all nodes in parallel
         let broadcast_futures: Vec<_> = self
             .node_endpoints
@@ -362,12 +442,11 @@ impl OrchestrationService {
         let success_count = results.iter().filter(|r| r.is_ok()).count();
 
         info!(
-            "Vote request broadcast: {}/{} nodes reached",
+            "Vote request broadcast for round {}: {}/{} nodes reached",
+            vote_request.round_number,
             success_count,
             self.node_endpoints.len()
         );
-
-        Ok(())
     }
 
     /// Process voting transactions (state: voting).
@@ -377,7 +456,8 @@ impl OrchestrationService {
     /// 2. Check if threshold reached (4/5 votes)
     /// 3. Check for timeout
     /// 4. Transition to "approved" if threshold reached
-    /// 5. Transition to "failed" if timed out
+    /// 5. Open the next voting round if timed out, or transition to
+    ///    "failed" once `max_voting_rounds` rounds timed out
     async fn process_voting_transactions(&self) -> Result<()> {
         let voting_txs = self.postgres
             .get_transactions_by_state("voting")
@@ -403,8 +483,8 @@ impl OrchestrationService {
                     }
 
                     // Transition to approved state
-                    if let Err(e) = self.postgres
-                        .update_transaction_state(&tx.txid, TransactionState::Approved)
+                    if let Err(e) = self
+                        .transition(&tx.txid, TransactionState::Voting, TransactionState::Approved, "vote threshold reached")
                         .await
                     {
                         error!("Failed to transition {:?} to approved: {}", tx.txid, e);
@@ -412,19 +492,29 @@ impl OrchestrationService {
                         info!("Transaction {:?} approved by consensus", tx.txid);
                     }
                 }
-                Ok(VotingStatus::TimedOut) => {
-                    warn!("Voting timed out for transaction: {:?}", tx.txid);
+                Ok(VotingStatus::TimedOut(round)) => {
+                    warn!("Voting round {} timed out for transaction: {:?}", round.round_number, tx.txid);
 
                     // Mark voting round as completed (not approved)
-                    if let Ok(Some(round)) = self.postgres.get_voting_round_by_txid(&tx.txid.0).await {
-                        if let Err(e) = self.postgres.update_voting_round_completed(round.id).await {
-                            error!("Failed to mark voting round {} as completed: {}", round.id, e);
+                    if let Err(e) = self.postgres.update_voting_round_completed(round.id).await {
+                        error!("Failed to mark voting round {} as completed: {}", round.id, e);
+                    }
+
+                    // Give the nodes that were offline another round
+                    if round.round_number < self.config.max_voting_rounds {
+                        let next = round.round_number + 1;
+                        match self.open_next_voting_round(&tx.txid, next).await {
+                            Ok(()) => info!("Opened voting round {} for transaction: {:?}", next, tx.txid),
+                            // Most likely another node opened it first
+                            Err(e) => warn!("Could not open voting round {} for {:?}: {}", next, tx.txid, e),
                         }
+                        continue;
                     }
 
                     // Transition to failed
-                    if let Err(e) = self.postgres
-                        .update_transaction_state(&tx.txid, TransactionState::Failed)
+                    let reason = format!("voting timed out after {} rounds", round.round_number);
+                    if let Err(e) = self
+                        .transition(&tx.txid, TransactionState::Voting, TransactionState::Failed, &reason)
                         .await
                     {
                         error!("Failed to transition {:?} to failed: {}", tx.txid, e);
@@ -434,7 +524,10 @@ impl OrchestrationService {
                     if let Err(e) = self.postgres.record_audit_event(
                         &tx.txid,
                         "voting_timeout",
-                        "Voting round timed out before reaching threshold",
+                        &format!(
+                            "Voting timed out after {} rounds before reaching threshold",
+                            round.round_number
+                        ),
                     ).await {
                         error!("Failed to record audit event: {}", e);
                     }
@@ -443,8 +536,8 @@ impl OrchestrationService {
                     warn!("Voting rejected for transaction: {:?}", tx.txid);
 
                     // Transition to failed
-                    if let Err(e) = self.postgres
-                        .update_transaction_state(&tx.txid, TransactionState::Failed)
+                    if let Err(e) = self
+                        .transition(&tx.txid, TransactionState::Voting, TransactionState::Failed, "voting rejected")
                         .await
                     {
                         error!("Failed to transition {:?} to failed: {}", tx.txid, e);
@@ -467,7 +560,7 @@ impl OrchestrationService {
     ///
     /// Returns VotingStatus indicating the current state:
     /// - Approved: Threshold reached (4/5 votes)
-    /// - TimedOut: Voting period expired
+    /// - TimedOut: The current round's voting period expired
     /// - Rejected: Explicit rejection (not implemented yet)
     /// - Pending: Still waiting for votes
     async fn check_voting_completion(&self, tx: &Transaction) -> Result<VotingStatus> {
@@ -486,12 +579,17 @@ impl OrchestrationService {
         // Check for timeout
         let now = chrono::Utc::now();
         if now > voting_round.timeout_at {
-            return Ok(VotingStatus::TimedOut);
+            return Ok(VotingStatus::TimedOut(voting_round));
         }
 
-        // Get actual vote count from PostgreSQL votes table (more reliable than etcd)
+        // Get actual vote count from PostgreSQL votes table (more reliable than etcd),
+        // leaving out the rounds whose votes expired
+        let first_round = match self.config.revote_policy {
+            RevotePolicy::CarryOver => 1,
+            RevotePolicy::Expire => voting_round.round_number,
+        };
         let actual_vote_count = self.postgres
-            .count_votes_for_transaction(&tx.txid)
+            .count_votes_in_rounds(&tx.txid, first_round)
             .await
             .map_err(|e| OrchestrationError::Storage(e.into()))?;
 
@@ -511,9 +609,9 @@ impl OrchestrationService {
 
     /// Process approved transactions (state: approved).
     ///
-    /// For each approved transaction:
-    /// 1. Transition to "signing" state
-    /// 2. Add mock signed_tx for testing
+    /// For each approved transaction, the signing attempt history decides
+    /// whether to sign now, wait for the backoff of a failed attempt to pass,
+    /// or give up and fail the transaction.
     async fn process_approved_transactions(&self) -> Result<()> {
         let approved_txs = self.postgres
             .get_transactions_by_state("approved")
@@ -526,13 +624,39 @@ impl OrchestrationService {
 
         debug!("Processing {} approved transactions", approved_txs.len());
 
+        let policy = RetryPolicy::from_config(&self.config);
         for tx in approved_txs {
-            match self.transition_approved_to_signing(&tx).await {
-                Ok(()) => {
-                    info!("Transitioned approved transaction to signing: {:?}", tx.txid);
-                }
+            let history = match self.attempts(&tx.txid, AttemptOperation::Signing).await {
+                Ok(history) => history,
                 Err(e) => {
-                    error!("Failed to transition approved transaction {:?}: {}", tx.txid, e);
+                    error!("Failed to load signing attempts of {:?}: {}", tx.txid, e);
+                    continue;
+                }
+            };
+
+            match retry::next_attempt(&policy, &history, self.signing_coordinator.max_excluded(), chrono::Utc::now()) {
+                RetryDecision::Wait(retry_at) => {
+                    debug!("Signing of {:?} backs off until {}", tx.txid, retry_at);
+                }
+                RetryDecision::GiveUp(reason) => {
+                    warn!("Giving up signing {:?}: {}", tx.txid, reason);
+                    if let Err(e) = self
+                        .transition(&tx.txid, TransactionState::Approved, TransactionState::Failed, &format!("signing failed: {}", reason))
+                        .await
+                    {
+                        error!("Failed to mark {:?} as failed: {}", tx.txid, e);
+                    }
+                }
+                RetryDecision::Attempt { attempt, excluded } => {
+                    let failures = retry::consecutive_failures(&history);
+                    match self.transition_approved_to_signing(&tx, attempt, &excluded, failures).await {
+                        Ok(()) => {
+                            info!("Transitioned approved transaction to signing: {:?}", tx.txid);
+                        }
+                        Err(e) => {
+                            error!("Failed to transition approved transaction {:?}: {}", tx.txid, e);
+                        }
+                    }
                 }
             }
         }
@@ -540,42 +664,176 @@ impl OrchestrationService {
         Ok(())
     }
 
+    /// Attempts of `operation` made for a transaction so far
+    async fn attempts(&self, txid: &TxId, operation: AttemptOperation) -> Result<Vec<TransactionAttempt>> {
+        let attempts = self.postgres
+            .get_transaction_attempts(txid)
+            .await
+            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+        Ok(attempts.into_iter().filter(|a| a.operation == operation).collect())
+    }
+
+    /// Record the outcome of an attempt
+    ///
+    /// A retryable failure is given a `retry_at` after the backoff for
+    /// `failures` earlier consecutive failures. Bookkeeping errors are logged
+    /// and never fail the operation itself.
+    #[allow(clippy::too_many_arguments)]
+    async fn record_attempt(
+        &self,
+        txid: &TxId,
+        operation: AttemptOperation,
+        attempt: u32,
+        excluded: &[NodeId],
+        started_at: chrono::DateTime<chrono::Utc>,
+        failure: Option<&AttemptFailure>,
+        failures: u32,
+    ) {
+        let finished_at = chrono::Utc::now();
+        let retry_at = match failure {
+            Some(f) if f.class == ErrorClass::Retryable => {
+                let delay = RetryPolicy::from_config(&self.config).delay(failures + 1);
+                Some(finished_at + chrono::Duration::from_std(delay).unwrap_or_else(|_| chrono::Duration::zero()))
+            }
+            _ => None,
+        };
+        let record = TransactionAttempt {
+            operation,
+            attempt,
+            outcome: failure.map_or(AttemptOutcome::Succeeded, |f| f.class.outcome()),
+            error: failure.map(|f| f.error.to_string()),
+            coordinator: self.node_id,
+            excluded_nodes: excluded.to_vec(),
+            faulty_node: failure.and_then(|f| f.error.faulty_node()),
+            started_at,
+            finished_at,
+            retry_at,
+        };
+
+        match self.postgres.record_transaction_attempt(txid, &record).await {
+            Ok(true) => {}
+            Ok(false) => {
+                warn!("{} attempt {} of {} was already recorded", operation.as_str(), attempt, txid);
+            }
+            Err(e) => {
+                error!("Failed to record {} attempt {} of {}: {}", operation.as_str(), attempt, txid, e);
+            }
+        }
+    }
+
     /// Transition approved transaction to signing.
     ///
     /// This is the REAL implementation using SigningCoordinator for MPC signing.
     /// NO MOCK CODE - uses actual CGGMP24 or FROST protocols.
-    async fn transition_approved_to_signing(&self, tx: &Transaction) -> Result<()> {
-        info!("Starting real MPC signing for transaction: {:?}", tx.txid);
+    ///
+    /// Signs without the `excluded` signers and records the outcome as
+    /// attempt number `attempt`. A retryable failure sends the transaction
+    /// back to approved for a later retry, a fatal one fails it.
+    async fn transition_approved_to_signing(
+        &self,
+        tx: &Transaction,
+        attempt: u32,
+        excluded: &[NodeId],
+        failures: u32,
+    ) -> Result<()> {
+        info!(
+            "Starting real MPC signing for transaction: {:?} (attempt {}, excluding {:?})",
+            tx.txid, attempt, excluded
+        );
 
-        // Step 1: Transition to 'signing' state
+        // Hold the signing lock for the whole session. Its lease is kept alive
+        // while we sign, and the fencing token stops our result from
+        // overwriting a newer holder's if the lease is lost anyway.
+        let signing_lock = self.etcd.acquire_signing_lock(&tx.txid).await.map_err(|e| {
+            OrchestrationError::InvalidState(tx.txid.to_string(), format!("signing lock unavailable: {}", e))
+        })?;
+
+        // Only this holder may store the signed transaction from now on
         self.postgres
-            .update_transaction_state(&tx.txid, TransactionState::Signing)
+            .claim_signing(&tx.txid, signing_lock.fencing_token())
             .await
             .map_err(|e| OrchestrationError::Storage(e.into()))?;
 
+        // Step 1: Transition to 'signing' state
+        self.transition(&tx.txid, TransactionState::Approved, TransactionState::Signing, "signing started")
+            .await?;
+
         info!("Transitioned to signing state: {:?}", tx.txid);
 
-        // Step 2: Automatic protocol selection based on recipient address
-        let protocol_selection = match self.protocol_router.route(&tx.recipient) {
-            Ok(selection) => selection,
-            Err(e) => {
-                error!(
-                    "Protocol selection failed for tx {}: {} - rolling back to approved state",
-                    tx.txid, e
-                );
-                // Rollback to approved state
-                if let Err(rollback_err) = self.postgres
-                    .update_transaction_state(&tx.txid, TransactionState::Approved)
+        let started_at = chrono::Utc::now();
+        let protocol = match self.sign_and_store(tx, excluded, &signing_lock).await {
+            Ok(protocol) => protocol,
+            Err(failure) if is_stale_fencing_token(&failure.error) => {
+                // A newer lock holder owns the transaction now; its attempt
+                // is the one that counts
+                warn!("Signing lock of {} was taken over: {}", tx.txid, failure.error);
+                return Err(failure.error);
+            }
+            Err(failure) => {
+                self.record_attempt(
+                    &tx.txid,
+                    AttemptOperation::Signing,
+                    attempt,
+                    excluded,
+                    started_at,
+                    Some(&failure),
+                    failures,
+                )
+                .await;
+
+                let (to, action) = match failure.class {
+                    ErrorClass::Retryable => (TransactionState::Approved, "rolling back to approved state for retry"),
+                    ErrorClass::Fatal => (TransactionState::Failed, "failing transaction"),
+                };
+                error!("MPC signing failed for tx {}: {} - {}", tx.txid, failure.error, action);
+                if let Err(transition_err) = self
+                    .transition(&tx.txid, TransactionState::Signing, to, &format!("MPC signing failed: {}", failure.error))
                     .await
                 {
-                    error!("Failed to rollback transaction {} to approved: {}", tx.txid, rollback_err);
+                    error!("Failed to move transaction {} to {}: {}", tx.txid, to, transition_err);
+                }
+                if let Err(e) = signing_lock.release().await {
+                    warn!("Failed to release signing lock of {}: {}", tx.txid, e);
                 }
-                return Err(OrchestrationError::Internal(format!(
-                    "Protocol selection failed: {}", e
-                )));
+                return Err(failure.error);
             }
         };
 
+        self.record_attempt(&tx.txid, AttemptOperation::Signing, attempt, excluded, started_at, None, failures)
+            .await;
+
+        // Step 6: Transition to 'signed' state
+        self.transition(&tx.txid, TransactionState::Signing, TransactionState::Signed, "MPC signing completed")
+            .await?;
+
+        if let Err(e) = signing_lock.release().await {
+            warn!("Failed to release signing lock of {}: {}", tx.txid, e);
+        }
+
+        info!(
+            "✅ REAL MPC signing completed for: {:?} using {:?} protocol",
+            tx.txid,
+            protocol
+        );
+
+        Ok(())
+    }
+
+    /// Sign a transaction in signing state and store the signed bytes
+    ///
+    /// Protocol selection and witness encoding depend only on the
+    /// transaction, so their failures are fatal.
+    async fn sign_and_store(
+        &self,
+        tx: &Transaction,
+        excluded: &[NodeId],
+        signing_lock: &LockGuard,
+    ) -> std::result::Result<SignatureProtocol, AttemptFailure> {
+        // Step 2: Automatic protocol selection based on recipient address
+        let protocol_selection = self.protocol_router.route(&tx.recipient).map_err(|e| {
+            AttemptFailure::fatal(OrchestrationError::Internal(format!("Protocol selection failed: {}", e)))
+        })?;
+
         info!(
             "Selected protocol: {:?} for address type: {:?} (recipient: {})",
             protocol_selection.protocol,
@@ -583,38 +841,29 @@ impl OrchestrationService {
             tx.recipient
         );
 
+        // Inputs from a user deposit address are signed with that address's key
+        let derivation_path = self
+            .postgres
+            .get_transaction_derivation_path(&tx.txid)
+            .await
+            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+
         // Step 3: Initiate MPC signing via SigningCoordinator
         info!(
-            "Initiating MPC signing with protocol: {:?}",
-            protocol_selection.protocol
+            "Initiating MPC signing with protocol: {:?} path: {:?}",
+            protocol_selection.protocol,
+            derivation_path
         );
 
-        let combined_signature = match self.signing_coordinator
-            .sign_transaction(
+        let combined_signature = self.signing_coordinator
+            .sign_transaction_at_path(
                 &tx.txid,
                 &tx.unsigned_tx,
                 protocol_selection.protocol,
+                &derivation_path,
+                excluded,
             )
-            .await
-        {
-            Ok(sig) => sig,
-            Err(e) => {
-                error!(
-                    "MPC signing failed for tx {}: {} - rolling back to approved state",
-                    tx.txid, e
-                );
-                // CRITICAL: Rollback to approved state for retry
-                if let Err(rollback_err) = self.postgres
-                    .update_transaction_state(&tx.txid, TransactionState::Approved)
-                    .await
-                {
-                    error!("Failed to rollback transaction {} to approved: {}", tx.txid, rollback_err);
-                }
-                return Err(OrchestrationError::Internal(format!(
-                    "MPC signing failed: {}", e
-                )));
-            }
-        };
+            .await?;
 
         info!(
             "MPC signing completed successfully: signature_len={} bytes",
@@ -622,54 +871,33 @@ impl OrchestrationService {
         );
 
         // Step 4: Encode signature into Bitcoin transaction witness format
-        let signed_tx = match self.encode_bitcoin_witness(
-            &tx.unsigned_tx,
-            &combined_signature.signature,
-            protocol_selection.protocol,
-        ) {
-            Ok(tx) => tx,
-            Err(e) => {
-                error!(
-                    "Failed to encode Bitcoin witness for tx {}: {} - rolling back to approved state",
-                    tx.txid, e
-                );
-                // Rollback to approved state
-                if let Err(rollback_err) = self.postgres
-                    .update_transaction_state(&tx.txid, TransactionState::Approved)
-                    .await
-                {
-                    error!("Failed to rollback transaction {} to approved: {}", tx.txid, rollback_err);
-                }
-                return Err(e);
-            }
-        };
+        let signed_tx = self
+            .encode_bitcoin_witness(
+                &tx.unsigned_tx,
+                &combined_signature.signature,
+                protocol_selection.protocol,
+            )
+            .map_err(AttemptFailure::fatal)?;
 
         // Step 5: Store the signed transaction bytes
+        if !signing_lock.is_held() {
+            warn!(
+                "Signing lock of {} lost during signing; relying on fencing token {}",
+                tx.txid,
+                signing_lock.fencing_token()
+            );
+        }
         self.postgres
-            .set_signed_transaction(&tx.txid, &signed_tx)
+            .set_signed_transaction(&tx.txid, &signed_tx, signing_lock.fencing_token())
             .await
             .map_err(|e| {
-                // Note: At this point signing succeeded, we just failed to store it
-                // Log error but don't rollback - the signed_tx exists
                 error!("Failed to store signed transaction for {}: {}", tx.txid, e);
                 OrchestrationError::Storage(e.into())
             })?;
 
         info!("Stored signed transaction for: {:?}", tx.txid);
 
-        // Step 6: Transition to 'signed' state
-        self.postgres
-            .update_transaction_state(&tx.txid, TransactionState::Signed)
-            .await
-            .map_err(|e| OrchestrationError::Storage(e.into()))?;
-
-        info!(
-            "✅ REAL MPC signing completed for: {:?} using {:?} protocol",
-            tx.txid,
-            protocol_selection.protocol
-        );
-
-        Ok(())
+        Ok(protocol_selection.protocol)
     }
 
     /// Process transactions ready for signing (state: threshold_reached).
@@ -719,8 +947,8 @@ impl OrchestrationService {
         }
 
         // 2. Update state to "signing"
-        self.postgres.update_transaction_state(&tx.txid, TransactionState::Signing).await
-            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+        self.transition(&tx.txid, TransactionState::ThresholdReached, TransactionState::Signing, "signing initiated")
+            .await?;
 
         // 3. Initiate signing session via P2pSessionCoordinator
         // NOTE: In production, this triggers the MPC signing protocol (CGGMP24/FROST)
@@ -798,12 +1026,10 @@ impl OrchestrationService {
                     stuck_duration.num_seconds()
                 );
 
-                self.postgres
-                    .update_transaction_state(&tx.txid, TransactionState::Approved)
+                self.transition(&tx.txid, TransactionState::Signing, TransactionState::Approved, "stuck in signing without signed_tx")
                     .await
-                    .map_err(|e| {
+                    .inspect_err(|e| {
                         error!("Failed to rollback stuck transaction {}: {}", tx.txid, e);
-                        OrchestrationError::Storage(e.into())
                     })?;
 
                 info!(
@@ -824,10 +1050,8 @@ impl OrchestrationService {
         }
 
         // Transition to signed
-        self.postgres
-            .update_transaction_state(&tx.txid, TransactionState::Signed)
-            .await
-            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+        self.transition(&tx.txid, TransactionState::Signing, TransactionState::Signed, "signed transaction stored")
+            .await?;
 
         info!("Transaction signing completed: {:?}", tx.txid);
 
@@ -835,6 +1059,9 @@ impl OrchestrationService {
     }
 
     /// Process transactions ready for broadcasting (state: signed).
+    ///
+    /// Failed broadcasts are retried with backoff; a rejection by the network
+    /// or running out of retries fails the transaction.
     async fn process_broadcasting_ready_transactions(&self) -> Result<()> {
         let signed_txs = self.postgres.get_transactions_by_state("signed").await
             .map_err(|e| OrchestrationError::Storage(e.into()))?;
@@ -845,16 +1072,67 @@ impl OrchestrationService {
 
         debug!("Processing {} signed transactions for broadcasting", signed_txs.len());
 
+        let policy = RetryPolicy::from_config(&self.config);
         for tx in signed_txs {
+            let history = match self.attempts(&tx.txid, AttemptOperation::Broadcast).await {
+                Ok(history) => history,
+                Err(e) => {
+                    error!("Failed to load broadcast attempts of {:?}: {}", tx.txid, e);
+                    continue;
+                }
+            };
+
+            let attempt = match retry::next_attempt(&policy, &history, 0, chrono::Utc::now()) {
+                RetryDecision::Attempt { attempt, .. } => attempt,
+                RetryDecision::Wait(retry_at) => {
+                    debug!("Broadcast of {:?} backs off until {}", tx.txid, retry_at);
+                    continue;
+                }
+                RetryDecision::GiveUp(reason) => {
+                    warn!("Giving up broadcasting {:?}: {}", tx.txid, reason);
+                    if let Err(e) = self
+                        .transition(&tx.txid, TransactionState::Signed, TransactionState::Failed, &format!("broadcast failed: {}", reason))
+                        .await
+                    {
+                        error!("Failed to mark {:?} as failed: {}", tx.txid, e);
+                    }
+                    continue;
+                }
+            };
+
+            let started_at = chrono::Utc::now();
+            let failures = retry::consecutive_failures(&history);
             match self.broadcast_transaction(&tx).await {
                 Ok(bitcoin_txid) => {
+                    self.record_attempt(&tx.txid, AttemptOperation::Broadcast, attempt, &[], started_at, None, failures)
+                        .await;
                     info!(
                         "Broadcasted transaction: {:?} -> Bitcoin TXID: {}",
                         tx.txid, bitcoin_txid
                     );
                 }
                 Err(e) => {
-                    error!("Failed to broadcast {:?}: {}", tx.txid, e);
+                    let failure = AttemptFailure::from(e);
+                    self.record_attempt(
+                        &tx.txid,
+                        AttemptOperation::Broadcast,
+                        attempt,
+                        &[],
+                        started_at,
+                        Some(&failure),
+                        failures,
+                    )
+                    .await;
+                    error!("Failed to broadcast {:?} (attempt {}): {}", tx.txid, attempt, failure.error);
+
+                    if failure.class == ErrorClass::Fatal {
+                        if let Err(e) = self
+                            .transition(&tx.txid, TransactionState::Signed, TransactionState::Failed, &format!("broadcast rejected: {}", failure.error))
+                            .await
+                        {
+                            error!("Failed to mark {:?} as failed: {}", tx.txid, e);
+                        }
+                    }
                 }
             }
         }
@@ -863,6 +1141,11 @@ impl OrchestrationService {
     }
 
     /// Broadcast a signed transaction to Bitcoin network.
+    ///
+    /// A node that already has the transaction counts as a successful
+    /// broadcast, and so does a rejection (e.g. `missingorspent`) of a
+    /// transaction the chain already knows: an earlier attempt or another
+    /// node got it out. Either way the txid of the signed bytes is stored.
     async fn broadcast_transaction(&self, tx: &Transaction) -> Result<String> {
         // 1. Get signed transaction bytes
         let signed_tx_bytes = self.postgres.get_signed_transaction(&tx.txid).await
@@ -873,15 +1156,46 @@ impl OrchestrationService {
             ))?;
 
         // 2. Broadcast via Bitcoin client
-        let bitcoin_txid = self.bitcoin.broadcast_transaction(&signed_tx_bytes).await
-            .map_err(|e| OrchestrationError::Bitcoin(e.to_string()))?;
+        let bitcoin_txid = match self.bitcoin.broadcast_transaction(&signed_tx_bytes).await {
+            Ok(bitcoin_txid) => bitcoin_txid,
+            Err(e) if retry::already_broadcast(&e) => {
+                let bitcoin_txid = signed_txid(&signed_tx_bytes)?;
+                info!("{:?} is already known to the network as {}: {}", tx.txid, bitcoin_txid, e);
+                bitcoin_txid
+            }
+            Err(e) => {
+                let error = retry::broadcast_error(e);
+                if !matches!(error, OrchestrationError::BroadcastRejected(_)) {
+                    return Err(error);
+                }
+
+                let bitcoin_txid = signed_txid(&signed_tx_bytes)?;
+                match self.bitcoin.get_tx_status(&bitcoin_txid).await {
+                    Ok(Some(_)) => {
+                        info!(
+                            "Broadcast of {:?} was rejected but {} is already on the network: {}",
+                            tx.txid, bitcoin_txid, error
+                        );
+                        bitcoin_txid
+                    }
+                    Ok(None) => return Err(error),
+                    // Without knowing, a rejection must not fail the transaction
+                    Err(status_err) => {
+                        return Err(OrchestrationError::Bitcoin(format!(
+                            "{} (could not check whether {} is known: {})",
+                            error, bitcoin_txid, status_err
+                        )))
+                    }
+                }
+            }
+        };
 
         // 3. Update database
         self.postgres.update_transaction_txid(&tx.txid, &bitcoin_txid).await
             .map_err(|e| OrchestrationError::Storage(e.into()))?;
 
-        self.postgres.update_transaction_state(&tx.txid, TransactionState::Broadcasting).await
-            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+        self.transition(&tx.txid, TransactionState::Signed, TransactionState::Broadcasting, "broadcast to the Bitcoin network")
+            .await?;
 
         Ok(bitcoin_txid)
     }
@@ -910,8 +1224,13 @@ impl OrchestrationService {
                     ).await
                     .map_err(|e| OrchestrationError::Storage(e.into()))?;
 
-                    self.postgres.update_transaction_state(&tx.txid, TransactionState::Confirmed).await
-                        .map_err(|e| OrchestrationError::Storage(e.into()))?;
+                    self.transition(
+                        &tx.txid,
+                        TransactionState::Broadcasting,
+                        TransactionState::Confirmed,
+                        &format!("{} confirmations", confirmations),
+                    )
+                    .await?;
 
                     self.vote_processor.mark_confirmed(&tx.txid).await
                         .map_err(|e| OrchestrationError::Consensus(e.to_string()))?;
@@ -952,8 +1271,8 @@ impl OrchestrationService {
         for tx in expired_txs {
             warn!("Transaction {:?} expired, marking as failed", tx.txid);
 
-            self.postgres.update_transaction_state(&tx.txid, TransactionState::Failed).await
-                .map_err(|e| OrchestrationError::Storage(e.into()))?;
+            self.transition(&tx.txid, tx.state, TransactionState::Failed, "expired after 1 hour")
+                .await?;
 
             // Record audit event
             self.postgres.record_audit_event(
@@ -1113,16 +1432,25 @@ impl OrchestrationService {
     }
 }
 
+/// Txid of a serialized signed transaction
+fn signed_txid(signed_tx: &[u8]) -> Result<String> {
+    bitcoin::consensus::deserialize::<bitcoin::Transaction>(signed_tx)
+        .map(|tx| tx.compute_txid().to_string())
+        .map_err(|e| OrchestrationError::SerializationError(format!("Invalid signed transaction: {}", e)))
+}
+
 /// Builder for OrchestrationService
 pub struct OrchestrationServiceBuilder {
     config: Option<OrchestrationConfig>,
     vote_processor: Option<Arc<VoteProcessor>>,
     session_coordinator: Option<Arc<P2pSessionCoordinator>>,
     postgres: Option<Arc<PostgresStorage>>,
-    etcd: Option<Arc<Mutex<EtcdStorage>>>,
+    etcd: Option<EtcdStorage>,
     bitcoin: Option<Arc<BitcoinClient>>,
     signing_coordinator: Option<Arc<SigningCoordinator>>,
     protocol_router: Option<Arc<ProtocolRouter>>,
+    node_id: Option<NodeId>,
+    http_client: Option<reqwest::Client>,
     node_endpoints: Option<HashMap<u64, String>>,
 }
 
@@ -1137,6 +1465,8 @@ impl OrchestrationServiceBuilder {
             bitcoin: None,
             signing_coordinator: None,
             protocol_router: None,
+            node_id: None,
+            http_client: None,
             node_endpoints: None,
         }
     }
@@ -1161,7 +1491,7 @@ impl OrchestrationServiceBuilder {
         self
     }
 
-    pub fn with_etcd(mut self, etcd: Arc<Mutex<EtcdStorage>>) -> Self {
+    pub fn with_etcd(mut self, etcd: EtcdStorage) -> Self {
         self.etcd = Some(etcd);
         self
     }
@@ -1181,6 +1511,17 @@ impl OrchestrationServiceBuilder {
         self
     }
 
+    pub fn with_node_id(mut self, node_id: NodeId) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+
+    /// HTTP client for the internal endpoints of other nodes (mTLS in production)
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
     pub fn with_node_endpoints(mut self, endpoints: Vec<(u64, String)>) -> Self {
         let mut map = HashMap::new();
         for (node_id, endpoint) in endpoints {
@@ -1200,6 +1541,8 @@ impl OrchestrationServiceBuilder {
             self.bitcoin.ok_or_else(|| OrchestrationError::Config("bitcoin required".to_string()))?,
             self.signing_coordinator.ok_or_else(|| OrchestrationError::Config("signing_coordinator required".to_string()))?,
             self.protocol_router.ok_or_else(|| OrchestrationError::Config("protocol_router required".to_string()))?,
+            self.node_id.ok_or_else(|| OrchestrationError::Config("node_id required".to_string()))?,
+            self.http_client.unwrap_or_default(),
             self.node_endpoints.unwrap_or_else(HashMap::new),
         )))
     }
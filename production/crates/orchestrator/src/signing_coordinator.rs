@@ -8,23 +8,27 @@
 //!
 //! 1. **Protocol Selection**: Automatically detects recipient address type
 //! 2. **Presignature Pool**: Uses pre-computed signatures for CGGMP24 (<500ms)
+//!    and preprocessed nonces for single-round FROST
 //! 3. **Distributed Signing**: Coordinates signature share collection from nodes
 //! 4. **Signature Combination**: Combines threshold shares into final signature
 //! 5. **Verification**: Validates signature before broadcasting transaction
 
 use crate::error::{OrchestrationError, Result};
+use crate::frost_nonce_service::FrostNonceService;
 use crate::presig_service::PresignatureService;
 use crate::metrics;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use threshold_network::QuicEngine;
-use threshold_storage::{EtcdStorage, PostgresStorage};
+use threshold_storage::TransactionStore;
 use threshold_types::{NetworkMessage, NodeId, PresignatureId, SigningMessage, TxId};
 use tokio::sync::{Mutex, RwLock};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use protocols::frost::preprocessing::{FrostPartialSignature, NonceCommitment};
+
 /// Signature protocol type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -59,6 +63,44 @@ pub struct SigningRequest {
     pub protocol: SignatureProtocol,
     /// Signing session ID
     pub session_id: Uuid,
+    /// Non-hardened BIP32 path of the signing key below the root key
+    #[serde(default)]
+    pub derivation_path: Vec<u32>,
+}
+
+/// Single-round FROST signing request sent to each chosen signer
+///
+/// POST /internal/frost-nonce-sign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrostNonceSignRequest {
+    /// Node coordinating the session (must match the caller's certificate)
+    pub coordinator_id: NodeId,
+    /// Signing session ID
+    pub session_id: Uuid,
+    /// Transaction ID
+    pub tx_id: TxId,
+    /// Message hash to sign (32 bytes)
+    pub message_hash: Vec<u8>,
+    /// Nonce the receiving signer must consume
+    pub nonce_id: Uuid,
+    /// One claimed commitment per participating signer
+    pub commitments: Vec<NonceCommitment>,
+    /// Non-hardened BIP32 path of the signing key below the root key
+    #[serde(default)]
+    pub derivation_path: Vec<u32>,
+}
+
+/// Request to wipe a claimed FROST nonce that will not be signed with
+///
+/// POST /internal/frost-nonce-discard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrostNonceDiscardRequest {
+    /// Node coordinating the session (must match the caller's certificate)
+    pub coordinator_id: NodeId,
+    /// Signing session the nonce was claimed for
+    pub session_id: Uuid,
+    /// Nonce the receiving signer must wipe
+    pub nonce_id: Uuid,
 }
 
 /// Signature share from a single node
@@ -105,12 +147,12 @@ struct SigningSession {
 pub struct SigningCoordinator {
     /// QUIC network engine
     quic: Arc<QuicEngine>,
-    /// PostgreSQL storage
-    postgres: Arc<PostgresStorage>,
-    /// etcd storage
-    etcd: Arc<EtcdStorage>,
+    /// Transaction storage (signing progress events)
+    transactions: Arc<dyn TransactionStore>,
     /// Presignature service (for CGGMP24)
     presig_service: Arc<PresignatureService>,
+    /// FROST nonce pool service (single-round FROST, optional)
+    frost_nonce_service: Option<Arc<FrostNonceService>>,
     /// Current node ID
     node_id: NodeId,
     /// Signature threshold (e.g., 4 for 4-of-5)
@@ -129,8 +171,7 @@ impl SigningCoordinator {
     /// Create new signing coordinator
     pub fn new(
         quic: Arc<QuicEngine>,
-        postgres: Arc<PostgresStorage>,
-        etcd: Arc<EtcdStorage>,
+        transactions: Arc<dyn TransactionStore>,
         presig_service: Arc<PresignatureService>,
         node_id: NodeId,
         threshold: usize,
@@ -138,9 +179,9 @@ impl SigningCoordinator {
     ) -> Self {
         Self {
             quic,
-            postgres,
-            etcd,
+            transactions,
             presig_service,
+            frost_nonce_service: None,
             node_id,
             threshold,
             active_sessions: Arc::new(RwLock::new(Vec::new())),
@@ -150,6 +191,21 @@ impl SigningCoordinator {
         }
     }
 
+    /// Replace the HTTP client used for calls to other nodes' internal endpoints
+    ///
+    /// The internal listener only accepts cluster client certificates, so this
+    /// is the mTLS client built at startup.
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
+    /// Enable single-round FROST signing from preprocessed nonces
+    pub fn with_frost_nonce_service(mut self, frost_nonce_service: Arc<FrostNonceService>) -> Self {
+        self.frost_nonce_service = Some(frost_nonce_service);
+        self
+    }
+
     /// Sign a transaction using the appropriate protocol
     ///
     /// This method:
@@ -164,13 +220,81 @@ impl SigningCoordinator {
         tx_id: &TxId,
         unsigned_tx: &[u8],
         protocol: SignatureProtocol,
+    ) -> Result<CombinedSignature> {
+        self.sign_transaction_at_path(tx_id, unsigned_tx, protocol, &[], &[])
+            .await
+    }
+
+    /// Sign a transaction with the child key at a non-hardened BIP32 path
+    ///
+    /// FROST signers tweak their own key share by the path's additive tweak, so
+    /// the signature verifies against the derived child public key. CGGMP24
+    /// signing has no tweaked-share path yet, so a non-empty path is rejected
+    /// for it, as are hardened indices. An empty path signs with the root key.
+    ///
+    /// `excluded` signers are neither asked to join nor counted towards the
+    /// threshold, which is used to retry a session in which a signer
+    /// misbehaved. Fails if fewer than `threshold` signers remain.
+    pub async fn sign_transaction_at_path(
+        &self,
+        tx_id: &TxId,
+        unsigned_tx: &[u8],
+        protocol: SignatureProtocol,
+        derivation_path: &[u32],
+        excluded: &[NodeId],
+    ) -> Result<CombinedSignature> {
+        self.sign(tx_id, unsigned_tx, protocol, derivation_path, excluded)
+            .await
+    }
+
+    /// Most signers a session can leave out and still reach the threshold
+    pub fn max_excluded(&self) -> usize {
+        self.node_endpoints.len().saturating_sub(self.threshold)
+    }
+
+    /// Run a signing session without the `excluded` signers
+    async fn sign(
+        &self,
+        tx_id: &TxId,
+        unsigned_tx: &[u8],
+        protocol: SignatureProtocol,
+        derivation_path: &[u32],
+        excluded: &[NodeId],
     ) -> Result<CombinedSignature> {
         let start = Instant::now();
         info!(
-            "Starting {} signing for tx_id={}",
-            protocol, tx_id
+            "Starting {} signing for tx_id={} path={:?} excluded={:?}",
+            protocol, tx_id, derivation_path, excluded
         );
 
+        if excluded.contains(&self.node_id) {
+            return Err(OrchestrationError::Internal(format!(
+                "Coordinator {} cannot be excluded from its own signing session",
+                self.node_id
+            )));
+        }
+        let signers = self.node_endpoints.len()
+            - excluded.iter().filter(|n| self.node_endpoints.contains_key(&n.0)).count();
+        if signers < self.threshold {
+            return Err(OrchestrationError::Internal(format!(
+                "Not enough signers after excluding {:?}: {} < {}",
+                excluded, signers, self.threshold
+            )));
+        }
+
+        if let Some(index) = derivation_path.iter().find(|i| **i >= 0x8000_0000) {
+            return Err(OrchestrationError::Internal(format!(
+                "Hardened derivation index {} cannot be signed for with a threshold key",
+                index
+            )));
+        }
+        if protocol == SignatureProtocol::CGGMP24 && !derivation_path.is_empty() {
+            return Err(OrchestrationError::Internal(format!(
+                "CGGMP24 signing at derivation path {:?} is not supported",
+                derivation_path
+            )));
+        }
+
         // Create signing session
         let session_id = Uuid::new_v4();
 
@@ -196,8 +320,88 @@ impl SigningCoordinator {
             None
         };
 
+        self.record_progress(
+            tx_id,
+            session_id,
+            "started",
+            serde_json::json!({
+                "protocol": protocol,
+                "presignature": presignature_id.is_some(),
+                "derived": !derivation_path.is_empty(),
+                "excluded": excluded.iter().map(|n| n.0).collect::<Vec<_>>(),
+            }),
+        )
+        .await;
+
         // Compute message hash
-        let message_hash = self.compute_message_hash(unsigned_tx, protocol)?;
+        let message_hash = Self::compute_message_hash(unsigned_tx, protocol)?;
+
+        // Single-round FROST from preprocessed nonces (falls back to two rounds)
+        let mut excluded = excluded.to_vec();
+        if protocol == SignatureProtocol::FROST {
+            if let Some(frost_nonce_service) = &self.frost_nonce_service {
+                match self
+                    .sign_frost_preprocessed(
+                        frost_nonce_service,
+                        tx_id,
+                        session_id,
+                        &message_hash,
+                        derivation_path,
+                        &excluded,
+                    )
+                    .await
+                {
+                    Ok((signature, share_count)) => {
+                        self.verify_signature(unsigned_tx, &signature, protocol)?;
+
+                        let duration_ms = start.elapsed().as_millis() as u64;
+                        info!(
+                            "Signing completed: protocol={} (preprocessed) duration={}ms tx_id={}",
+                            protocol, duration_ms, tx_id
+                        );
+                        metrics::SIGNING_DURATION.observe(duration_ms as f64 / 1000.0);
+                        metrics::record_signing_result(&protocol.to_string(), true);
+                        self.record_progress(
+                            tx_id,
+                            session_id,
+                            "completed",
+                            serde_json::json!({
+                                "preprocessed": true,
+                                "share_count": share_count,
+                                "duration_ms": duration_ms,
+                            }),
+                        )
+                        .await;
+
+                        return Ok(CombinedSignature {
+                            signature,
+                            protocol,
+                            share_count,
+                            duration_ms,
+                        });
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Preprocessed FROST signing failed: {} - falling back to two-round signing",
+                            e
+                        );
+                        // Leave a misbehaving signer out of the second try too
+                        if let Some(node_id) = e.faulty_node() {
+                            if !excluded.contains(&node_id) && excluded.len() < self.max_excluded() {
+                                excluded.push(node_id);
+                            }
+                        }
+                        self.record_progress(
+                            tx_id,
+                            session_id,
+                            "fallback",
+                            serde_json::json!({ "error": e.to_string() }),
+                        )
+                        .await;
+                    }
+                }
+            }
+        }
 
         // Create signing session
         let session = SigningSession {
@@ -224,14 +428,45 @@ impl SigningCoordinator {
             presignature_id: presignature_id.clone(),
             protocol,
             session_id,
+            derivation_path: derivation_path.to_vec(),
         };
 
-        self.broadcast_signing_request(&request).await?;
+        self.broadcast_signing_request(&request, &excluded).await?;
+        self.record_progress(
+            tx_id,
+            session_id,
+            "request_broadcast",
+            serde_json::json!({
+                "signers": self.node_endpoints.len().saturating_sub(excluded.len()),
+                "threshold": self.threshold,
+            }),
+        )
+        .await;
 
         // Collect signature shares (with 30 second timeout)
-        let shares = self
-            .collect_signature_shares(session_id, Duration::from_secs(30))
-            .await?;
+        let shares = match self
+            .collect_signature_shares(session_id, Duration::from_secs(30), &excluded)
+            .await
+        {
+            Ok(shares) => shares,
+            Err(e) => {
+                self.record_progress(
+                    tx_id,
+                    session_id,
+                    "failed",
+                    serde_json::json!({ "error": e.to_string() }),
+                )
+                .await;
+                return Err(e);
+            }
+        };
+        self.record_progress(
+            tx_id,
+            session_id,
+            "shares_collected",
+            serde_json::json!({ "shares": shares.len(), "threshold": self.threshold }),
+        )
+        .await;
 
         info!(
             "Collected {}/{} signature shares for session={}",
@@ -267,6 +502,18 @@ impl SigningCoordinator {
         // Update active sessions metric
         metrics::ACTIVE_SIGNING_SESSIONS.set(self.active_sessions.read().await.len() as i64);
 
+        self.record_progress(
+            tx_id,
+            session_id,
+            "completed",
+            serde_json::json!({
+                "preprocessed": false,
+                "share_count": shares.len(),
+                "duration_ms": duration_ms,
+            }),
+        )
+        .await;
+
         Ok(CombinedSignature {
             signature,
             protocol,
@@ -275,11 +522,219 @@ impl SigningCoordinator {
         })
     }
 
-    /// Broadcast signing request to all nodes
-    async fn broadcast_signing_request(&self, request: &SigningRequest) -> Result<()> {
+    /// Append a signing progress event to the cluster event log
+    ///
+    /// Progress events are informational; failing to record one never fails
+    /// the signing session.
+    async fn record_progress(
+        &self,
+        tx_id: &TxId,
+        session_id: Uuid,
+        stage: &str,
+        details: serde_json::Value,
+    ) {
+        let payload = serde_json::json!({
+            "txid": tx_id.0,
+            "session_id": session_id,
+            "coordinator": self.node_id.0,
+            "stage": stage,
+            "details": details,
+        });
+        if let Err(e) = self
+            .transactions
+            .append_cluster_event(
+                threshold_storage::EVENT_SIGNING_PROGRESS,
+                Some(&tx_id.0),
+                Some(self.node_id.0),
+                &payload,
+            )
+            .await
+        {
+            warn!("Failed to record signing progress for tx_id={}: {}", tx_id, e);
+        }
+    }
+
+    /// Sign with preprocessed FROST nonces in a single online round
+    ///
+    /// Claims one published commitment per signer, asks every signer for its
+    /// share over HTTP (this node signs locally) and aggregates the shares.
+    /// If any signer fails, the claimed nonces of the signers that produced no
+    /// share are discarded on their nodes, since their commitments are no
+    /// longer published and would otherwise sit unused until expiry.
+    async fn sign_frost_preprocessed(
+        &self,
+        frost_nonce_service: &FrostNonceService,
+        tx_id: &TxId,
+        session_id: Uuid,
+        message_hash: &[u8],
+        derivation_path: &[u32],
+        excluded: &[NodeId],
+    ) -> Result<(Vec<u8>, usize)> {
+        // This node plus the lowest-numbered peers not excluded, up to the threshold
+        let mut signers = vec![self.node_id];
+        let mut peers: Vec<u64> = self
+            .node_endpoints
+            .keys()
+            .copied()
+            .filter(|id| *id != self.node_id.0 && !excluded.contains(&NodeId(*id)))
+            .collect();
+        peers.sort_unstable();
+        signers.extend(peers.into_iter().map(NodeId));
+        signers.truncate(self.threshold);
+
+        if signers.len() < self.threshold {
+            return Err(OrchestrationError::Internal(format!(
+                "Not enough signers for FROST: {} < {}",
+                signers.len(),
+                self.threshold
+            )));
+        }
+
+        let commitments = frost_nonce_service.claim_commitments(&signers).await?;
+
+        let share_futures: Vec<_> = signers
+            .iter()
+            .zip(commitments.iter())
+            .map(|(signer, commitment)| {
+                let request = FrostNonceSignRequest {
+                    coordinator_id: self.node_id,
+                    session_id,
+                    tx_id: tx_id.clone(),
+                    message_hash: message_hash.to_vec(),
+                    nonce_id: commitment.nonce_id,
+                    commitments: commitments.clone(),
+                    derivation_path: derivation_path.to_vec(),
+                };
+                self.request_frost_share(frost_nonce_service, *signer, request)
+            })
+            .collect();
+
+        let results = futures::future::join_all(share_futures).await;
+        if results.iter().any(|r| r.is_err()) {
+            for (signer, nonce_id) in unanswered_nonces(&signers, &commitments, &results) {
+                if let Err(e) = self
+                    .discard_frost_nonce(frost_nonce_service, signer, session_id, nonce_id)
+                    .await
+                {
+                    warn!(
+                        "Failed to discard FROST nonce {} of node {}: {}",
+                        nonce_id, signer.0, e
+                    );
+                }
+            }
+        }
+        let partials = results.into_iter().collect::<Result<Vec<_>>>()?;
+
+        let signature = frost_nonce_service
+            .aggregate(message_hash, &commitments, &partials, derivation_path)
+            .await?;
+
+        Ok((signature, partials.len()))
+    }
+
+    /// Obtain one signer's FROST share for a preprocessed signing session
+    async fn request_frost_share(
+        &self,
+        frost_nonce_service: &FrostNonceService,
+        signer: NodeId,
+        request: FrostNonceSignRequest,
+    ) -> Result<FrostPartialSignature> {
+        if signer == self.node_id {
+            return frost_nonce_service
+                .sign_share(
+                    &request.session_id.to_string(),
+                    &request.tx_id,
+                    request.nonce_id,
+                    &request.message_hash,
+                    &request.commitments,
+                    &request.derivation_path,
+                )
+                .await;
+        }
+
+        let endpoint = self.node_endpoints.get(&signer.0).ok_or_else(|| {
+            OrchestrationError::Internal(format!("No endpoint configured for node {}", signer.0))
+        })?;
+        let url = format!("{}/internal/frost-nonce-sign", endpoint);
+
+        let resp = self
+            .http_client
+            .post(&url)
+            .json(&request)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .map_err(|e| OrchestrationError::SignerFault {
+                node_id: signer,
+                reason: format!("FROST share request failed: {}", e),
+            })?;
+
+        if !resp.status().is_success() {
+            return Err(OrchestrationError::SignerFault {
+                node_id: signer,
+                reason: format!("FROST share request failed: status={}", resp.status()),
+            });
+        }
+
+        resp.json::<FrostPartialSignature>()
+            .await
+            .map_err(|e| OrchestrationError::SignerFault {
+                node_id: signer,
+                reason: format!("invalid FROST share: {}", e),
+            })
+    }
+
+    /// Have `signer` wipe a claimed nonce of a failed preprocessed session
+    ///
+    /// A nonce the signer already consumed is left as it is.
+    async fn discard_frost_nonce(
+        &self,
+        frost_nonce_service: &FrostNonceService,
+        signer: NodeId,
+        session_id: Uuid,
+        nonce_id: Uuid,
+    ) -> Result<()> {
+        if signer == self.node_id {
+            return frost_nonce_service
+                .discard_nonce(nonce_id, &session_id.to_string())
+                .await
+                .map(|_| ());
+        }
+
+        let endpoint = self.node_endpoints.get(&signer.0).ok_or_else(|| {
+            OrchestrationError::Internal(format!("No endpoint configured for node {}", signer.0))
+        })?;
+        let url = format!("{}/internal/frost-nonce-discard", endpoint);
+        let request = FrostNonceDiscardRequest {
+            coordinator_id: self.node_id,
+            session_id,
+            nonce_id,
+        };
+
+        let resp = self
+            .http_client
+            .post(&url)
+            .json(&request)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .map_err(|e| OrchestrationError::NetworkError(format!("FROST nonce discard failed: {}", e)))?;
+
+        if !resp.status().is_success() {
+            return Err(OrchestrationError::NetworkError(format!(
+                "FROST nonce discard failed: status={}",
+                resp.status()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Broadcast signing request to all nodes that are not excluded
+    async fn broadcast_signing_request(&self, request: &SigningRequest, excluded: &[NodeId]) -> Result<()> {
         // CRITICAL FIX FOR SORUN #17: HTTP broadcast to all nodes first
         // This ensures all nodes receive the signing request and can participate
-        if let Err(e) = self.http_broadcast_signing_join(request).await {
+        if let Err(e) = self.http_broadcast_signing_join(request, excluded).await {
             warn!("Failed to HTTP broadcast signing join: {}", e);
             // Don't fail - coordinator can still proceed via QUIC if some nodes are reachable
         }
@@ -295,10 +750,21 @@ impl SigningCoordinator {
         });
 
         let stream_id = 1; // Signing stream
-        self.quic
-            .broadcast(&msg, stream_id, None)
-            .await
-            .map_err(|e| OrchestrationError::NetworkError(format!("Failed to broadcast: {}", e)))?;
+        if excluded.is_empty() {
+            self.quic
+                .broadcast(&msg, stream_id, None)
+                .await
+                .map_err(|e| OrchestrationError::NetworkError(format!("Failed to broadcast: {}", e)))?;
+        } else {
+            for node_id in self.node_endpoints.keys().map(|id| NodeId(*id)) {
+                if node_id == self.node_id || excluded.contains(&node_id) {
+                    continue;
+                }
+                if let Err(e) = self.quic.send(&node_id, &msg, stream_id).await {
+                    warn!("Failed to send signing request to node {}: {}", node_id, e);
+                }
+            }
+        }
 
         info!(
             "Broadcasted signing request: session={} protocol={}",
@@ -312,31 +778,35 @@ impl SigningCoordinator {
     ///
     /// This sends HTTP POST requests to all non-coordinator nodes to notify them
     /// to join the signing ceremony. This fixes SORUN #17.
-    async fn http_broadcast_signing_join(&self, request: &SigningRequest) -> Result<()> {
+    async fn http_broadcast_signing_join(&self, request: &SigningRequest, excluded: &[NodeId]) -> Result<()> {
         use std::time::Duration as StdDuration;
 
         #[derive(Debug, Clone, Serialize, Deserialize)]
         struct SigningJoinRequest {
+            coordinator_id: u64,
             session_id: String,
             tx_id: String,
             protocol: String,
             unsigned_tx: Vec<u8>,
             message_hash: Vec<u8>,
+            derivation_path: Vec<u32>,
         }
 
         let join_request = SigningJoinRequest {
+            coordinator_id: self.node_id.0,
             session_id: request.session_id.to_string(),
             tx_id: request.tx_id.to_string(),
             protocol: request.protocol.to_string(),
             unsigned_tx: request.unsigned_tx.clone(),
             message_hash: request.message_hash.clone(),
+            derivation_path: request.derivation_path.clone(),
         };
 
-        // Broadcast to all nodes except coordinator (this node)
+        // Broadcast to all nodes except coordinator (this node) and excluded ones
         let broadcast_futures: Vec<_> = self
             .node_endpoints
             .iter()
-            .filter(|(node_id, _)| **node_id != self.node_id.0)
+            .filter(|(node_id, _)| **node_id != self.node_id.0 && !excluded.contains(&NodeId(**node_id)))
             .map(|(node_id, endpoint)| {
                 let client = self.http_client.clone();
                 let url = format!("{}/internal/signing-join", endpoint);
@@ -373,23 +843,27 @@ impl SigningCoordinator {
             .collect();
 
         // Wait for all broadcasts (don't fail if some nodes are unreachable)
+        let target_count = broadcast_futures.len();
         let results = futures::future::join_all(broadcast_futures).await;
         let success_count = results.iter().filter(|r| r.is_ok()).count();
 
         info!(
             "Signing join request broadcast: {}/{} nodes reached",
             success_count,
-            self.node_endpoints.len() - 1 // Exclude coordinator
+            target_count
         );
 
         Ok(())
     }
 
     /// Collect signature shares from threshold nodes
+    ///
+    /// Shares from excluded nodes are ignored.
     async fn collect_signature_shares(
         &self,
         session_id: Uuid,
         timeout: Duration,
+        excluded: &[NodeId],
     ) -> Result<Vec<SignatureShare>> {
         let start = Instant::now();
 
@@ -405,8 +879,13 @@ impl SigningCoordinator {
             // Check if we have enough shares
             let buffer = self.share_buffer.lock().await;
             if let Some(shares) = buffer.get(&session_id) {
+                let shares: Vec<SignatureShare> = shares
+                    .iter()
+                    .filter(|share| !excluded.contains(&share.node_id))
+                    .cloned()
+                    .collect();
                 if shares.len() >= self.threshold {
-                    return Ok(shares.clone());
+                    return Ok(shares);
                 }
             }
             drop(buffer);
@@ -459,28 +938,14 @@ impl SigningCoordinator {
         }
 
         // All nodes should produce the same final signature when using presignatures
-        // Take the first share's signature (they should all be identical)
-        let first_sig = &shares[0].partial_signature;
-
-        // Verify all shares have the same signature (sanity check)
-        for (i, share) in shares.iter().enumerate().skip(1) {
-            if share.partial_signature != *first_sig {
-                error!(
-                    "Signature mismatch: share {} differs from first share",
-                    i
-                );
-                return Err(OrchestrationError::Internal(
-                    format!("Signature mismatch between nodes (share {})", i)
-                ));
-            }
-        }
+        let first_sig = agreed_signature(shares)?;
 
         info!(
             "All {} signature shares match - signature is valid",
             shares.len()
         );
 
-        Ok(first_sig.clone())
+        Ok(first_sig.to_vec())
     }
 
     /// Combine FROST Schnorr signature shares
@@ -497,8 +962,7 @@ impl SigningCoordinator {
         }
 
         // All nodes should produce the same final Schnorr signature
-        // Take the first share's signature (they should all be identical)
-        let first_sig = &shares[0].partial_signature;
+        let first_sig = agreed_signature(shares)?;
 
         // Verify it's 64 bytes (BIP-340 Schnorr signature format)
         if first_sig.len() != 64 {
@@ -507,29 +971,19 @@ impl SigningCoordinator {
             ));
         }
 
-        // Verify all shares have the same signature (sanity check)
-        for (i, share) in shares.iter().enumerate().skip(1) {
-            if share.partial_signature != *first_sig {
-                error!(
-                    "Schnorr signature mismatch: share {} differs from first share",
-                    i
-                );
-                return Err(OrchestrationError::Internal(
-                    format!("Signature mismatch between nodes (share {})", i)
-                ));
-            }
-        }
-
         info!(
             "All {} Schnorr signature shares match - signature is valid (64 bytes)",
             shares.len()
         );
 
-        Ok(first_sig.clone())
+        Ok(first_sig.to_vec())
     }
 
     /// Compute message hash for signing
-    fn compute_message_hash(&self, unsigned_tx: &[u8], protocol: SignatureProtocol) -> Result<Vec<u8>> {
+    ///
+    /// Signers recompute this from their stored copy of the transaction
+    /// before contributing a share.
+    pub(crate) fn compute_message_hash(unsigned_tx: &[u8], protocol: SignatureProtocol) -> Result<Vec<u8>> {
         // NOTE: In production, this should use the actual Bitcoin transaction
         // and compute the proper sighash using bitcoin-rs crate.
         //
@@ -631,10 +1085,120 @@ impl SigningCoordinator {
     }
 }
 
+/// Claimed nonces of the signers whose share request failed
+///
+/// `signers`, `commitments` and `results` are in the same order. A failed
+/// signer may or may not have consumed its nonce; discarding is a no-op for
+/// one that did.
+fn unanswered_nonces<T>(
+    signers: &[NodeId],
+    commitments: &[NonceCommitment],
+    results: &[Result<T>],
+) -> Vec<(NodeId, Uuid)> {
+    signers
+        .iter()
+        .zip(commitments)
+        .zip(results)
+        .filter(|(_, result)| result.is_err())
+        .map(|((signer, commitment), _)| (*signer, commitment.nonce_id))
+        .collect()
+}
+
+/// Signature every share agrees on
+///
+/// Each signer produces the complete signature, so the shares must be
+/// identical. When they are not, a signer disagreeing with the majority is
+/// blamed; without a majority nobody can be. `shares` must not be empty.
+fn agreed_signature(shares: &[SignatureShare]) -> Result<&[u8]> {
+    let mut counts: std::collections::HashMap<&[u8], usize> = std::collections::HashMap::new();
+    for share in shares {
+        *counts.entry(share.partial_signature.as_slice()).or_default() += 1;
+    }
+    if counts.len() == 1 {
+        return Ok(&shares[0].partial_signature);
+    }
+
+    let (majority, votes) = counts
+        .into_iter()
+        .max_by_key(|(_, votes)| *votes)
+        .expect("shares is not empty");
+    if votes * 2 <= shares.len() {
+        error!("Signature mismatch between nodes without a majority");
+        return Err(OrchestrationError::Internal(
+            "Signature mismatch between nodes without a majority".to_string()
+        ));
+    }
+
+    let faulty = shares
+        .iter()
+        .find(|share| share.partial_signature != majority)
+        .expect("more than one distinct signature");
+    error!("Signature mismatch: share of node {} differs from the majority", faulty.node_id);
+    Err(OrchestrationError::SignerFault {
+        node_id: faulty.node_id,
+        reason: "signature differs from the other signers".to_string(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn share(node: u64, signature: &[u8]) -> SignatureShare {
+        SignatureShare {
+            tx_id: TxId("tx".to_string()),
+            node_id: NodeId(node),
+            partial_signature: signature.to_vec(),
+            presignature_id: None,
+            session_id: Uuid::nil(),
+        }
+    }
+
+    #[test]
+    fn test_agreed_signature_blames_minority_signer() {
+        let shares = [share(1, b"good"), share(2, b"good"), share(3, b"evil")];
+        let err = agreed_signature(&shares).unwrap_err();
+        assert_eq!(err.faulty_node(), Some(NodeId(3)));
+
+        let shares = [share(1, b"good"), share(2, b"good")];
+        assert_eq!(agreed_signature(&shares).unwrap(), b"good");
+
+        // No majority, so no one to blame
+        let shares = [share(1, b"one"), share(2, b"two")];
+        assert_eq!(agreed_signature(&shares).unwrap_err().faulty_node(), None);
+    }
+
+    #[test]
+    fn test_unanswered_nonces_of_failed_preprocessed_session() {
+        let signers = [NodeId(1), NodeId(2), NodeId(3)];
+        let commitments: Vec<NonceCommitment> = (0..3u16)
+            .map(|party_index| NonceCommitment {
+                nonce_id: Uuid::new_v4(),
+                party_index,
+                commitment: vec![party_index as u8],
+            })
+            .collect();
+        let results: Vec<Result<()>> = vec![
+            Ok(()),
+            Err(OrchestrationError::SignerFault {
+                node_id: NodeId(2),
+                reason: "unreachable".to_string(),
+            }),
+            Err(OrchestrationError::Internal("nonce unknown".to_string())),
+        ];
+
+        assert_eq!(
+            unanswered_nonces(&signers, &commitments, &results),
+            vec![
+                (NodeId(2), commitments[1].nonce_id),
+                (NodeId(3), commitments[2].nonce_id),
+            ]
+        );
+
+        let all_ok: Vec<Result<()>> = vec![Ok(()), Ok(()), Ok(())];
+        assert!(unanswered_nonces(&signers, &commitments, &all_ok).is_empty());
+    }
+
     #[test]
     fn test_signature_protocol_display() {
         assert_eq!(SignatureProtocol::CGGMP24.to_string(), "cggmp24");
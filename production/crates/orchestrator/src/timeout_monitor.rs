@@ -1,9 +1,11 @@
 //! Timeout monitoring service for transactions.
 //!
 //! Detects and handles:
-//! - Voting timeouts (>60s without reaching threshold)
+//! - Voting timeouts (all voting rounds, 60s each, without reaching threshold)
 //! - Signing timeouts (>120s without completion)
 //! - Broadcasting timeouts (>300s without confirmation)
+//!
+//! It also frees UTXO reservations left behind by finished transactions.
 
 use crate::config::OrchestrationConfig;
 use crate::error::{OrchestrationError, Result};
@@ -15,9 +17,12 @@ use tokio::time::interval;
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
-use threshold_storage::PostgresStorage;
+use threshold_storage::TransactionStore;
 use threshold_types::{TxId, TransactionState};
 
+/// Actor recorded in the state history for timeout failures
+const ACTOR: &str = "timeout_monitor";
+
 /// Track when transactions entered each state.
 #[derive(Debug, Clone)]
 struct TransactionTimer {
@@ -29,7 +34,7 @@ struct TransactionTimer {
 /// Timeout monitoring service.
 pub struct TimeoutMonitor {
     config: OrchestrationConfig,
-    postgres: Arc<PostgresStorage>,
+    transactions: Arc<dyn TransactionStore>,
     timers: Arc<RwLock<HashMap<String, TransactionTimer>>>,
     shutdown: Arc<RwLock<bool>>,
 }
@@ -79,29 +84,43 @@ impl TimeoutMonitor {
             if let Err(e) = self.check_broadcasting_timeouts().await {
                 error!("Error checking broadcasting timeouts: {}", e);
             }
+
+            if let Err(e) = self.reconcile_utxo_reservations().await {
+                error!("Error reconciling UTXO reservations: {}", e);
+            }
         }
     }
 
+    /// Longest a transaction may stay in voting
+    ///
+    /// The orchestrator opens a new round when one times out, so this covers
+    /// every round plus a poll interval each for noticing the timeout. It is
+    /// a backstop for when no node opens the next round.
+    fn voting_deadline(&self) -> Duration {
+        (self.config.voting_timeout + self.config.poll_interval) * self.config.max_voting_rounds.max(1)
+    }
+
     /// Check for voting timeouts.
     async fn check_voting_timeouts(&self) -> Result<()> {
-        let voting_txs = self.postgres.get_transactions_by_state("voting").await
+        let voting_txs = self.transactions.get_transactions_by_state(TransactionState::Voting).await
             .map_err(|e| OrchestrationError::Storage(e.into()))?;
 
         for tx in voting_txs {
             let elapsed = self.get_time_in_state(&tx.txid, "voting").await;
 
-            if elapsed > self.config.voting_timeout {
+            if elapsed > self.voting_deadline() {
                 warn!(
                     "Transaction {:?} voting timeout after {:?}",
                     tx.txid, elapsed
                 );
 
                 // Abort transaction
-                self.postgres.update_transaction_state(&tx.txid, TransactionState::Failed).await
-                    .map_err(|e| OrchestrationError::Storage(e.into()))?;
+                if !self.fail_timed_out(&tx.txid, TransactionState::Voting, "voting timeout").await? {
+                    continue;
+                }
 
                 // Record audit event
-                self.postgres.record_audit_event(
+                self.transactions.record_audit_event(
                     &tx.txid,
                     "voting_timeout",
                     &format!("Voting timeout after {} seconds", elapsed.as_secs()),
@@ -115,7 +134,7 @@ impl TimeoutMonitor {
 
     /// Check for signing timeouts.
     async fn check_signing_timeouts(&self) -> Result<()> {
-        let signing_txs = self.postgres.get_transactions_by_state("signing").await
+        let signing_txs = self.transactions.get_transactions_by_state(TransactionState::Signing).await
             .map_err(|e| OrchestrationError::Storage(e.into()))?;
 
         for tx in signing_txs {
@@ -127,11 +146,12 @@ impl TimeoutMonitor {
                     tx.txid, elapsed
                 );
 
-                self.postgres.update_transaction_state(&tx.txid, TransactionState::Failed).await
-                    .map_err(|e| OrchestrationError::Storage(e.into()))?;
+                if !self.fail_timed_out(&tx.txid, TransactionState::Signing, "signing timeout").await? {
+                    continue;
+                }
 
                 // Record audit event
-                self.postgres.record_audit_event(
+                self.transactions.record_audit_event(
                     &tx.txid,
                     "signing_timeout",
                     &format!("Signing timeout after {} seconds", elapsed.as_secs()),
@@ -145,7 +165,7 @@ impl TimeoutMonitor {
 
     /// Check for broadcasting timeouts.
     async fn check_broadcasting_timeouts(&self) -> Result<()> {
-        let broadcasting_txs = self.postgres.get_transactions_by_state("broadcasting").await
+        let broadcasting_txs = self.transactions.get_transactions_by_state(TransactionState::Broadcasting).await
             .map_err(|e| OrchestrationError::Storage(e.into()))?;
 
         for tx in broadcasting_txs {
@@ -158,11 +178,15 @@ impl TimeoutMonitor {
                 );
 
                 // Mark as failed, may need manual investigation
-                self.postgres.update_transaction_state(&tx.txid, TransactionState::Failed).await
-                    .map_err(|e| OrchestrationError::Storage(e.into()))?;
+                if !self
+                    .fail_timed_out(&tx.txid, TransactionState::Broadcasting, "broadcasting timeout")
+                    .await?
+                {
+                    continue;
+                }
 
                 // Record audit event
-                self.postgres.record_audit_event(
+                self.transactions.record_audit_event(
                     &tx.txid,
                     "broadcasting_timeout",
                     &format!("Broadcasting timeout after {} seconds", elapsed.as_secs()),
@@ -174,6 +198,37 @@ impl TimeoutMonitor {
         Ok(())
     }
 
+    /// Free UTXO reservations of transactions that already finished.
+    async fn reconcile_utxo_reservations(&self) -> Result<()> {
+        let released = self.transactions.release_orphaned_utxo_reservations().await
+            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+
+        if released > 0 {
+            info!("Released {} orphaned UTXO reservations", released);
+        }
+
+        Ok(())
+    }
+
+    /// Fail a transaction that timed out in `from`
+    ///
+    /// Returns false if the transaction left `from` in the meantime, e.g.
+    /// because signing completed on another node.
+    async fn fail_timed_out(&self, txid: &TxId, from: TransactionState, reason: &str) -> Result<bool> {
+        match self
+            .transactions
+            .transition_transaction_state(txid, from, TransactionState::Failed, ACTOR, reason)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(threshold_types::Error::StateConflict { actual, .. }) => {
+                debug!("Transaction {:?} moved to {} before its {} was handled", txid, actual, reason);
+                Ok(false)
+            }
+            Err(e) => Err(OrchestrationError::Storage(e.into())),
+        }
+    }
+
     /// Get time elapsed in current state.
     async fn get_time_in_state(&self, tx_id: &TxId, state: &str) -> Duration {
         let timers = self.timers.read().await;
@@ -213,14 +268,14 @@ impl TimeoutMonitor {
 /// Builder for TimeoutMonitor
 pub struct TimeoutMonitorBuilder {
     config: Option<OrchestrationConfig>,
-    postgres: Option<Arc<PostgresStorage>>,
+    transactions: Option<Arc<dyn TransactionStore>>,
 }
 
 impl TimeoutMonitorBuilder {
     pub fn new() -> Self {
         Self {
             config: None,
-            postgres: None,
+            transactions: None,
         }
     }
 
@@ -229,19 +284,19 @@ impl TimeoutMonitorBuilder {
         self
     }
 
-    pub fn with_postgres(mut self, postgres: Arc<PostgresStorage>) -> Self {
-        self.postgres = Some(postgres);
+    pub fn with_transaction_store(mut self, transactions: Arc<dyn TransactionStore>) -> Self {
+        self.transactions = Some(transactions);
         self
     }
 
     pub fn build(self) -> Result<Arc<TimeoutMonitor>> {
         let config = self.config.unwrap_or_default();
-        let postgres = self.postgres
-            .ok_or_else(|| OrchestrationError::Config("PostgresStorage is required".to_string()))?;
+        let transactions = self.transactions
+            .ok_or_else(|| OrchestrationError::Config("Transaction store is required".to_string()))?;
 
         Ok(Arc::new(TimeoutMonitor {
             config,
-            postgres,
+            transactions,
             timers: Arc::new(RwLock::new(HashMap::new())),
             shutdown: Arc::new(RwLock::new(false)),
         }))
@@ -260,9 +315,57 @@ mod tests {
 
     #[test]
     fn test_builder() {
-        // Test builder pattern (requires mock PostgresStorage for actual build)
         let builder = TimeoutMonitorBuilder::new();
         assert!(builder.config.is_none());
-        assert!(builder.postgres.is_none());
+        assert!(builder.transactions.is_none());
+        assert!(builder.build().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_voting_timeout_fails_transaction() {
+        use crate::config::OrchestrationConfigBuilder;
+        use threshold_storage::SqliteStorage;
+        use threshold_types::Transaction;
+
+        let storage = Arc::new(SqliteStorage::open_in_memory().unwrap());
+        let tx_id = TxId::from("stalled_tx");
+        storage
+            .create_transaction(&Transaction {
+                id: 0,
+                txid: tx_id.clone(),
+                state: TransactionState::Voting,
+                unsigned_tx: vec![0u8; 4],
+                signed_tx: None,
+                recipient: "tb1qrecipient".to_string(),
+                amount_sats: 50_000,
+                fee_sats: 500,
+                metadata: None,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let config = OrchestrationConfigBuilder::new()
+            .voting_timeout(Duration::ZERO)
+            .poll_interval(Duration::ZERO)
+            .build();
+        let monitor = TimeoutMonitorBuilder::new()
+            .with_config(config)
+            .with_transaction_store(storage.clone())
+            .build()
+            .unwrap();
+
+        monitor.record_state_transition(tx_id.clone(), "voting".to_string()).await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        monitor.check_voting_timeouts().await.unwrap();
+
+        let tx = storage.get_transaction(&tx_id).await.unwrap().unwrap();
+        assert_eq!(tx.state, TransactionState::Failed);
+        assert_eq!(storage.get_audit_event_types(&tx_id).unwrap(), vec!["voting_timeout"]);
+
+        // Already failed: a second pass finds nothing to do
+        monitor.check_voting_timeouts().await.unwrap();
+        assert_eq!(storage.get_audit_event_types(&tx_id).unwrap().len(), 1);
     }
 }
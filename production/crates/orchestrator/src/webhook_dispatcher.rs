@@ -0,0 +1,498 @@
+//! Webhook delivery.
+//!
+//! Events are written to the `webhook_deliveries` outbox in PostgreSQL, one
+//! row per subscribed endpoint (see [`emit_event`]; transaction state changes
+//! are enqueued by `PostgresStorage::transition_transaction_state`). The
+//! dispatcher runs on every node and:
+//! - Claims due deliveries with a lease so nodes never send the same attempt
+//! - POSTs each event with an HMAC-SHA256 signature
+//! - Retries failures with exponential backoff
+//! - Moves deliveries that exhaust `max_attempts` to `webhook_dead_letters`,
+//!   from where they can be replayed through the API
+//!
+//! # Delivery format
+//!
+//! The body is `{"id", "type", "created_at", "data"}`. Receivers verify
+//! `X-Webhook-Signature: v1=<hex>`, the HMAC-SHA256 of
+//! `"{X-Webhook-Timestamp}.{body}"` keyed with the subscription secret, and
+//! should deduplicate on `X-Webhook-Id` since delivery is at-least-once.
+
+use crate::error::{OrchestrationError, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+
+use threshold_storage::{PostgresStorage, WebhookDelivery};
+
+/// Header carrying the event ID (same for every endpoint receiving the event)
+pub const WEBHOOK_ID_HEADER: &str = "x-webhook-id";
+/// Header carrying the event type
+pub const WEBHOOK_EVENT_HEADER: &str = "x-webhook-event";
+/// Header carrying the signing time (Unix seconds)
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "x-webhook-timestamp";
+/// Header carrying `v1=<hex HMAC-SHA256>`
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// Webhook dispatcher configuration
+#[derive(Debug, Clone)]
+pub struct WebhookDispatcherConfig {
+    /// Time between polls for due deliveries
+    pub poll_interval: Duration,
+    /// Maximum deliveries claimed per poll
+    pub batch_size: usize,
+    /// Attempts before a delivery is dead-lettered
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after each failure
+    pub initial_backoff: Duration,
+    /// Upper bound for the retry delay
+    pub max_backoff: Duration,
+    /// Timeout of a single delivery request
+    pub request_timeout: Duration,
+}
+
+impl Default for WebhookDispatcherConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(5),
+            batch_size: 50,
+            max_attempts: 8,
+            initial_backoff: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(3600),
+            request_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl WebhookDispatcherConfig {
+    /// Load configuration from `WEBHOOK_*` environment variables, falling
+    /// back to defaults for anything unset or unparsable.
+    pub fn from_env() -> Self {
+        fn env<T: std::str::FromStr>(key: &str) -> Option<T> {
+            std::env::var(key).ok().and_then(|v| v.parse().ok())
+        }
+
+        let defaults = Self::default();
+        Self {
+            poll_interval: env("WEBHOOK_POLL_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.poll_interval),
+            batch_size: defaults.batch_size,
+            max_attempts: env("WEBHOOK_MAX_ATTEMPTS")
+                .unwrap_or(defaults.max_attempts)
+                .max(1),
+            initial_backoff: env("WEBHOOK_INITIAL_BACKOFF_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.initial_backoff),
+            max_backoff: env("WEBHOOK_MAX_BACKOFF_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.max_backoff),
+            request_timeout: env("WEBHOOK_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.request_timeout),
+        }
+    }
+
+    /// How long a claimed delivery is hidden from other dispatchers
+    fn lease(&self) -> Duration {
+        self.request_timeout * 2 + Duration::from_secs(30)
+    }
+}
+
+/// Delay before the next attempt after `attempts` failed ones
+pub fn backoff_delay(attempts: u32, initial: Duration, max: Duration) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(31);
+    initial.saturating_mul(1u32 << exponent).min(max)
+}
+
+/// Hex HMAC-SHA256 of `"{timestamp}.{body}"` keyed with the subscription secret
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Result of one delivery attempt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    /// The endpoint answered with a 2xx status
+    Delivered(u16),
+    /// Non-2xx status, or no response at all
+    Failed {
+        status_code: Option<u16>,
+        error: String,
+    },
+}
+
+/// POST a delivery to its endpoint
+pub async fn deliver(client: &reqwest::Client, delivery: &WebhookDelivery) -> DeliveryOutcome {
+    let envelope = serde_json::json!({
+        "id": delivery.event_id,
+        "type": delivery.event_type,
+        "created_at": delivery.created_at,
+        "data": delivery.payload,
+    });
+    let body = match serde_json::to_vec(&envelope) {
+        Ok(body) => body,
+        Err(e) => {
+            return DeliveryOutcome::Failed {
+                status_code: None,
+                error: format!("Failed to serialize event: {}", e),
+            }
+        }
+    };
+
+    let timestamp = chrono::Utc::now().timestamp();
+    let signature = sign_payload(&delivery.secret, timestamp, &body);
+
+    let result = client
+        .post(&delivery.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(WEBHOOK_ID_HEADER, &delivery.event_id)
+        .header(WEBHOOK_EVENT_HEADER, &delivery.event_type)
+        .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+        .header(WEBHOOK_SIGNATURE_HEADER, format!("v1={}", signature))
+        .body(body)
+        .send()
+        .await;
+
+    match result {
+        Ok(response) if response.status().is_success() => {
+            DeliveryOutcome::Delivered(response.status().as_u16())
+        }
+        Ok(response) => DeliveryOutcome::Failed {
+            status_code: Some(response.status().as_u16()),
+            error: format!("Endpoint returned {}", response.status()),
+        },
+        Err(e) => DeliveryOutcome::Failed {
+            status_code: None,
+            error: e.to_string(),
+        },
+    }
+}
+
+/// Enqueue a webhook event for all subscribers
+///
+/// Webhooks are best effort from the caller's point of view, so failures are
+/// logged rather than returned.
+pub async fn emit_event(postgres: &PostgresStorage, event_type: &str, payload: serde_json::Value) {
+    match postgres.enqueue_webhook_event(event_type, &payload).await {
+        Ok(0) => {}
+        Ok(count) => debug!("Enqueued {} event for {} webhook(s)", event_type, count),
+        Err(e) => warn!("Failed to enqueue {} webhook event: {}", event_type, e),
+    }
+}
+
+/// Webhook dispatcher service.
+pub struct WebhookDispatcher {
+    config: WebhookDispatcherConfig,
+    postgres: Arc<PostgresStorage>,
+    http_client: reqwest::Client,
+    shutdown: Arc<RwLock<bool>>,
+}
+
+impl WebhookDispatcher {
+    /// Start the webhook dispatcher in the background
+    pub fn start(self: Arc<Self>) -> JoinHandle<Result<()>> {
+        tokio::spawn(async move {
+            info!(
+                "Webhook dispatcher started (interval: {:?}, max attempts: {})",
+                self.config.poll_interval, self.config.max_attempts
+            );
+
+            match self.run().await {
+                Ok(()) => {
+                    info!("Webhook dispatcher stopped normally");
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Webhook dispatcher error: {}", e);
+                    Err(e)
+                }
+            }
+        })
+    }
+
+    /// Main delivery loop
+    async fn run(&self) -> Result<()> {
+        let mut interval = interval(self.config.poll_interval);
+
+        loop {
+            if *self.shutdown.read().await {
+                info!("Shutdown signal received, stopping webhook dispatcher");
+                return Ok(());
+            }
+
+            interval.tick().await;
+
+            match self.dispatch_once().await {
+                Ok(0) => {}
+                Ok(count) => debug!("Dispatched {} webhook deliveries", count),
+                Err(e) => error!("Webhook dispatch failed: {}", e),
+            }
+        }
+    }
+
+    /// Claim and attempt one batch of due deliveries; returns the batch size
+    pub async fn dispatch_once(&self) -> Result<usize> {
+        let deliveries = self
+            .postgres
+            .claim_due_webhook_deliveries(
+                self.config.batch_size as i64,
+                self.config.lease().as_secs(),
+            )
+            .await
+            .map_err(|e| OrchestrationError::Storage(e.into()))?;
+
+        let count = deliveries.len();
+        futures::future::join_all(deliveries.into_iter().map(|delivery| async move {
+            let outcome = deliver(&self.http_client, &delivery).await;
+            if let Err(e) = self.record_outcome(&delivery, outcome).await {
+                error!("Failed to record outcome of webhook delivery {}: {}", delivery.id, e);
+            }
+        }))
+        .await;
+
+        Ok(count)
+    }
+
+    /// Mark a delivery delivered, schedule a retry, or dead-letter it
+    async fn record_outcome(&self, delivery: &WebhookDelivery, outcome: DeliveryOutcome) -> Result<()> {
+        let result = match outcome {
+            DeliveryOutcome::Delivered(status_code) => {
+                debug!(
+                    "Delivered webhook {} ({}) to {}",
+                    delivery.event_id, delivery.event_type, delivery.subscription_id
+                );
+                self.postgres.mark_webhook_delivered(delivery.id, status_code).await
+            }
+            DeliveryOutcome::Failed { status_code, error } if delivery.attempts >= self.config.max_attempts => {
+                warn!(
+                    "Webhook {} to {} failed after {} attempts, dead-lettering: {}",
+                    delivery.event_id, delivery.subscription_id, delivery.attempts, error
+                );
+                self.postgres
+                    .dead_letter_webhook_delivery(delivery.id, status_code, &error)
+                    .await
+            }
+            DeliveryOutcome::Failed { status_code, error } => {
+                let delay = backoff_delay(
+                    delivery.attempts,
+                    self.config.initial_backoff,
+                    self.config.max_backoff,
+                );
+                debug!(
+                    "Webhook {} to {} failed (attempt {}), retrying in {:?}: {}",
+                    delivery.event_id, delivery.subscription_id, delivery.attempts, delay, error
+                );
+                self.postgres
+                    .reschedule_webhook_delivery(delivery.id, delay.as_secs(), status_code, &error)
+                    .await
+            }
+        };
+
+        result.map_err(|e| OrchestrationError::Storage(e.into()))
+    }
+
+    /// Signal the dispatcher to stop
+    pub async fn shutdown(&self) {
+        info!("Initiating webhook dispatcher shutdown");
+        *self.shutdown.write().await = true;
+    }
+}
+
+/// Builder for WebhookDispatcher
+pub struct WebhookDispatcherBuilder {
+    config: Option<WebhookDispatcherConfig>,
+    postgres: Option<Arc<PostgresStorage>>,
+    http_client: Option<reqwest::Client>,
+}
+
+impl WebhookDispatcherBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: None,
+            postgres: None,
+            http_client: None,
+        }
+    }
+
+    pub fn with_config(mut self, config: WebhookDispatcherConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn with_postgres(mut self, postgres: Arc<PostgresStorage>) -> Self {
+        self.postgres = Some(postgres);
+        self
+    }
+
+    /// HTTP client for deliveries; defaults to one with the configured
+    /// timeout that does not follow redirects
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    pub fn build(self) -> Result<Arc<WebhookDispatcher>> {
+        let config = self.config.unwrap_or_default();
+        let postgres = self.postgres
+            .ok_or_else(|| OrchestrationError::Config("PostgresStorage is required".to_string()))?;
+        let http_client = match self.http_client {
+            Some(client) => client,
+            None => reqwest::Client::builder()
+                .timeout(config.request_timeout)
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .map_err(|e| OrchestrationError::Config(format!("Failed to build HTTP client: {}", e)))?,
+        };
+
+        Ok(Arc::new(WebhookDispatcher {
+            config,
+            postgres,
+            http_client,
+            shutdown: Arc::new(RwLock::new(false)),
+        }))
+    }
+}
+
+impl Default for WebhookDispatcherBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::oneshot;
+
+    /// Minimal HTTP endpoint answering one request with `status`; yields the
+    /// raw request it received
+    async fn stub_endpoint(status: u16) -> (String, oneshot::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+        let (tx, rx) = oneshot::channel();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .filter_map(|l| l.split_once(':'))
+                        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+                        .and_then(|(_, v)| v.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+            }
+            let response = format!("HTTP/1.1 {} Stub\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+            socket.write_all(response.as_bytes()).await.unwrap();
+            let _ = tx.send(String::from_utf8_lossy(&request).to_string());
+        });
+
+        (url, rx)
+    }
+
+    fn delivery(url: String) -> WebhookDelivery {
+        WebhookDelivery {
+            id: 1,
+            subscription_id: "wh_test".to_string(),
+            url,
+            secret: "whsec_test".to_string(),
+            event_id: "evt-1".to_string(),
+            event_type: "transaction.state_changed".to_string(),
+            payload: serde_json::json!({ "txid": "abc", "state": "signed" }),
+            attempts: 1,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn header<'a>(request: &'a str, name: &str) -> &'a str {
+        request
+            .lines()
+            .find_map(|l| {
+                let (k, v) = l.split_once(':')?;
+                k.eq_ignore_ascii_case(name).then(|| v.trim())
+            })
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_deliver_signs_request() {
+        let (url, received) = stub_endpoint(200).await;
+        let outcome = deliver(&reqwest::Client::new(), &delivery(url)).await;
+        assert_eq!(outcome, DeliveryOutcome::Delivered(200));
+
+        let request = received.await.unwrap();
+        assert!(request.starts_with("POST /hooks"));
+        assert_eq!(header(&request, WEBHOOK_ID_HEADER), "evt-1");
+        assert_eq!(header(&request, WEBHOOK_EVENT_HEADER), "transaction.state_changed");
+
+        let body = &request[request.find("\r\n\r\n").unwrap() + 4..];
+        let envelope: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(envelope["data"]["txid"], "abc");
+
+        let timestamp: i64 = header(&request, WEBHOOK_TIMESTAMP_HEADER).parse().unwrap();
+        let expected = format!("v1={}", sign_payload("whsec_test", timestamp, body.as_bytes()));
+        assert_eq!(header(&request, WEBHOOK_SIGNATURE_HEADER), expected);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_reports_error_status() {
+        let (url, _received) = stub_endpoint(503).await;
+        let outcome = deliver(&reqwest::Client::new(), &delivery(url)).await;
+        assert!(matches!(outcome, DeliveryOutcome::Failed { status_code: Some(503), .. }));
+    }
+
+    #[tokio::test]
+    async fn test_deliver_reports_unreachable_endpoint() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+        drop(listener);
+
+        let outcome = deliver(&reqwest::Client::new(), &delivery(url)).await;
+        assert!(matches!(outcome, DeliveryOutcome::Failed { status_code: None, .. }));
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let initial = Duration::from_secs(30);
+        let max = Duration::from_secs(3600);
+        assert_eq!(backoff_delay(1, initial, max), Duration::from_secs(30));
+        assert_eq!(backoff_delay(2, initial, max), Duration::from_secs(60));
+        assert_eq!(backoff_delay(4, initial, max), Duration::from_secs(240));
+        assert_eq!(backoff_delay(8, initial, max), max);
+        assert_eq!(backoff_delay(100, initial, max), max);
+    }
+
+    #[test]
+    fn test_signature_depends_on_timestamp_and_secret() {
+        let body = br#"{"id":"evt-1"}"#;
+        let signature = sign_payload("secret", 1_700_000_000, body);
+        assert_eq!(signature.len(), 64);
+        assert_ne!(signature, sign_payload("secret", 1_700_000_001, body));
+        assert_ne!(signature, sign_payload("other", 1_700_000_000, body));
+    }
+}
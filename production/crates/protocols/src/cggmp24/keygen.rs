@@ -84,6 +84,8 @@ pub async fn run_keygen(
     let keygen_future =
         cggmp24::keygen::<cggmp24::supported_curves::Secp256k1>(eid, party_index, num_parties)
             .set_threshold(threshold)
+            // Jointly generate the BIP32 chain code for xpub export
+            .hd_wallet(true)
             .start(&mut rng, party);
 
     let keygen_result = match tokio::time::timeout(protocol_timeout, keygen_future).await {
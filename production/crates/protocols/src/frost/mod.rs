@@ -4,10 +4,16 @@
 //! It includes:
 //! - Distributed key generation
 //! - Threshold signing (BIP-340 compatible for Taproot)
+//! - Nonce preprocessing for single-round signing
 
 pub mod keygen;
+pub mod preprocessing;
 pub mod signing;
 
 // Explicit re-exports to avoid ambiguity
 pub use keygen::{run_frost_keygen, FrostKeygenResult};
+pub use preprocessing::{
+    aggregate_partial_signatures, generate_nonces, sign_with_preprocessed_nonce,
+    FrostPartialSignature, NonceCommitment, NoncePool, PreprocessedNonce, PreprocessingError,
+};
 pub use signing::{run_frost_signing, FrostKeyShare, FrostSigningResult, SchnorrSignature};
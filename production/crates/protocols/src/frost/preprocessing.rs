@@ -0,0 +1,431 @@
+//! FROST nonce preprocessing for single-round Taproot signing.
+//!
+//! FROST round 1 (nonce commitment) does not depend on the message being
+//! signed, so it can be run ahead of time. Each node generates batches of
+//! nonce pairs, keeps the secret halves locally and publishes the public
+//! commitments. At signing time the coordinator picks one published
+//! commitment per signer and the only online round left is round 2
+//! (signature shares), which the coordinator aggregates.
+//!
+//! # Security
+//!
+//! Reusing a FROST nonce for two different messages leaks the signer's key
+//! share. Every [`PreprocessedNonce`] therefore carries a unique id and MUST be
+//! consumed at most once: [`NoncePool::take`] removes the secret from the pool
+//! before it is used, and persistence layers must delete the secret in the
+//! same operation that marks it consumed.
+
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use givre::ciphersuite::{Bitcoin, Ciphersuite};
+
+use super::signing::{FrostKeyShare, SchnorrSignature};
+
+type FrostCurve = <Bitcoin as Ciphersuite>::Curve;
+type FrostSecretNonces = givre::signing::round1::SecretNonces<FrostCurve>;
+type FrostPublicCommitments = givre::signing::round1::PublicCommitments<FrostCurve>;
+type FrostSigShare = givre::signing::round2::SigShare<FrostCurve>;
+
+/// Errors produced by FROST preprocessing.
+#[derive(Debug, thiserror::Error)]
+pub enum PreprocessingError {
+    #[error("Key share deserialization error: {0}")]
+    KeyShare(String),
+
+    #[error("Nonce serialization error: {0}")]
+    Serialization(String),
+
+    #[error("Nonce {0} was already consumed")]
+    NonceConsumed(Uuid),
+
+    #[error("Signer {0} is missing from the commitment set")]
+    SignerNotInCommitments(u16),
+
+    #[error("Invalid derivation path: {0}")]
+    Derivation(String),
+
+    #[error("Signing error: {0}")]
+    Signing(String),
+
+    #[error("Aggregation error: {0}")]
+    Aggregation(String),
+}
+
+/// Public nonce commitment published by a signer ahead of signing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonceCommitment {
+    /// Unique id of the nonce pair this commitment belongs to
+    pub nonce_id: Uuid,
+    /// Signer party index (0-based, as used at keygen)
+    pub party_index: u16,
+    /// Serialized `PublicCommitments` (JSON)
+    pub commitment: Vec<u8>,
+}
+
+/// A preprocessed nonce pair. The secret half never leaves the node.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PreprocessedNonce {
+    /// Unique id of this nonce pair
+    pub nonce_id: Uuid,
+    /// Signer party index (0-based, as used at keygen)
+    pub party_index: u16,
+    /// Serialized `SecretNonces` (JSON) - must be used at most once
+    pub secret_nonces: Vec<u8>,
+    /// Serialized `PublicCommitments` (JSON)
+    pub commitment: Vec<u8>,
+    /// When the nonce was generated
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl std::fmt::Debug for PreprocessedNonce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print secret nonces
+        f.debug_struct("PreprocessedNonce")
+            .field("nonce_id", &self.nonce_id)
+            .field("party_index", &self.party_index)
+            .field("created_at", &self.created_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PreprocessedNonce {
+    /// Public commitment for this nonce, suitable for publishing
+    pub fn public_commitment(&self) -> NonceCommitment {
+        NonceCommitment {
+            nonce_id: self.nonce_id,
+            party_index: self.party_index,
+            commitment: self.commitment.clone(),
+        }
+    }
+}
+
+/// Signature share produced in the single online round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrostPartialSignature {
+    /// Signer party index
+    pub party_index: u16,
+    /// Nonce id consumed to produce this share
+    pub nonce_id: Uuid,
+    /// Serialized `SigShare` (JSON)
+    pub sig_share: Vec<u8>,
+}
+
+fn load_key_share(key_share_data: &[u8]) -> Result<FrostKeyShare, PreprocessingError> {
+    serde_json::from_slice(key_share_data).map_err(|e| PreprocessingError::KeyShare(e.to_string()))
+}
+
+fn decode_commitment(bytes: &[u8]) -> Result<FrostPublicCommitments, PreprocessingError> {
+    serde_json::from_slice(bytes).map_err(|e| PreprocessingError::Serialization(e.to_string()))
+}
+
+/// Generate `count` nonce pairs for this signer (FROST round 1, offline).
+pub fn generate_nonces(
+    key_share_data: &[u8],
+    count: usize,
+) -> Result<Vec<PreprocessedNonce>, PreprocessingError> {
+    let key_share = load_key_share(key_share_data)?;
+    let party_index = key_share.i;
+
+    let mut nonces = Vec::with_capacity(count);
+    for _ in 0..count {
+        let (secret, public): (FrostSecretNonces, FrostPublicCommitments) =
+            givre::signing::round1::commit::<Bitcoin>(&mut OsRng, &key_share);
+
+        nonces.push(PreprocessedNonce {
+            nonce_id: Uuid::new_v4(),
+            party_index,
+            secret_nonces: serde_json::to_vec(&secret)
+                .map_err(|e| PreprocessingError::Serialization(e.to_string()))?,
+            commitment: serde_json::to_vec(&public)
+                .map_err(|e| PreprocessingError::Serialization(e.to_string()))?,
+            created_at: chrono::Utc::now(),
+        });
+    }
+
+    info!(
+        "Generated {} FROST nonce pairs for party {}",
+        nonces.len(),
+        party_index
+    );
+
+    Ok(nonces)
+}
+
+/// Produce this signer's share using a preprocessed nonce (FROST round 2).
+///
+/// `commitments` must contain exactly one commitment per participating signer,
+/// including this one. A non-empty `derivation_path` signs for the BIP32 child
+/// of the root key (givre's additive shift), and the Taproot key-path tweak is
+/// applied the same way as `set_taproot_tweak(None)` in
+/// [`super::signing::run_frost_signing`]. Aggregation must use the same path.
+pub fn sign_with_preprocessed_nonce(
+    key_share_data: &[u8],
+    nonce: PreprocessedNonce,
+    message_hash: &[u8; 32],
+    derivation_path: &[u32],
+    commitments: &[NonceCommitment],
+) -> Result<FrostPartialSignature, PreprocessingError> {
+    let key_share = load_key_share(key_share_data)?;
+
+    if !commitments
+        .iter()
+        .any(|c| c.party_index == nonce.party_index && c.nonce_id == nonce.nonce_id)
+    {
+        return Err(PreprocessingError::SignerNotInCommitments(nonce.party_index));
+    }
+
+    let secret: FrostSecretNonces = serde_json::from_slice(&nonce.secret_nonces)
+        .map_err(|e| PreprocessingError::Serialization(e.to_string()))?;
+
+    let signers = commitments
+        .iter()
+        .map(|c| Ok((c.party_index, decode_commitment(&c.commitment)?)))
+        .collect::<Result<Vec<_>, PreprocessingError>>()?;
+
+    let mut options =
+        givre::signing::round2::SigningOptions::<Bitcoin>::new(&key_share, secret, message_hash, &signers);
+    if !derivation_path.is_empty() {
+        options = options
+            .set_derivation_path(derivation_path.iter().copied())
+            .map_err(|e| PreprocessingError::Derivation(format!("{:?}", e)))?;
+    }
+    let sig_share: FrostSigShare = options
+        .set_taproot_tweak(None)
+        .and_then(|options| options.sign())
+        .map_err(|e| PreprocessingError::Signing(format!("{:?}", e)))?;
+
+    Ok(FrostPartialSignature {
+        party_index: nonce.party_index,
+        nonce_id: nonce.nonce_id,
+        sig_share: serde_json::to_vec(&sig_share)
+            .map_err(|e| PreprocessingError::Serialization(e.to_string()))?,
+    })
+}
+
+/// Aggregate signature shares into a BIP-340 Schnorr signature.
+///
+/// Any node holding a key share can aggregate - only the public key info is used.
+/// `derivation_path` must be the path the shares were produced for.
+pub fn aggregate_partial_signatures(
+    key_share_data: &[u8],
+    message_hash: &[u8; 32],
+    derivation_path: &[u32],
+    commitments: &[NonceCommitment],
+    partials: &[FrostPartialSignature],
+) -> Result<SchnorrSignature, PreprocessingError> {
+    let key_share = load_key_share(key_share_data)?;
+
+    let shares = partials
+        .iter()
+        .map(|p| {
+            let commitment = commitments
+                .iter()
+                .find(|c| c.party_index == p.party_index && c.nonce_id == p.nonce_id)
+                .ok_or(PreprocessingError::SignerNotInCommitments(p.party_index))?;
+            let share: FrostSigShare = serde_json::from_slice(&p.sig_share)
+                .map_err(|e| PreprocessingError::Serialization(e.to_string()))?;
+            Ok((p.party_index, decode_commitment(&commitment.commitment)?, share))
+        })
+        .collect::<Result<Vec<_>, PreprocessingError>>()?;
+
+    let mut options =
+        givre::signing::aggregate::AggregateOptions::<Bitcoin>::new(key_share.as_ref(), &shares, message_hash);
+    if !derivation_path.is_empty() {
+        options = options
+            .set_derivation_path(derivation_path.iter().copied())
+            .map_err(|e| PreprocessingError::Derivation(format!("{:?}", e)))?;
+    }
+    let signature = options
+        .set_taproot_tweak(None)
+        .and_then(|options| options.aggregate())
+        .map_err(|e| PreprocessingError::Aggregation(format!("{:?}", e)))?;
+
+    let r_point_bytes: Vec<u8> = signature.r.to_bytes().into();
+    let r = match r_point_bytes.len() {
+        33 => r_point_bytes[1..33].to_vec(),
+        32 => r_point_bytes,
+        n => {
+            return Err(PreprocessingError::Aggregation(format!(
+                "Unexpected R point length: {}",
+                n
+            )))
+        }
+    };
+    let s = signature.z.to_be_bytes().as_ref().to_vec();
+
+    Ok(SchnorrSignature { r, s })
+}
+
+/// In-memory pool of preprocessed nonces with strict one-time consumption.
+///
+/// Nonces are keyed by id because the coordinator chooses which commitment
+/// each signer must use; a signer cannot simply pop the next one.
+#[derive(Clone, Default)]
+pub struct NoncePool {
+    nonces: Arc<Mutex<HashMap<Uuid, PreprocessedNonce>>>,
+}
+
+impl NoncePool {
+    /// Create an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of unconsumed nonces.
+    pub async fn size(&self) -> usize {
+        self.nonces.lock().await.len()
+    }
+
+    /// Add freshly generated (or reloaded) nonces.
+    pub async fn extend(&self, nonces: impl IntoIterator<Item = PreprocessedNonce>) {
+        let mut pool = self.nonces.lock().await;
+        for nonce in nonces {
+            if pool.insert(nonce.nonce_id, nonce).is_some() {
+                warn!("Duplicate FROST nonce id inserted into pool");
+            }
+        }
+    }
+
+    /// Remove a nonce for use. Returns an error if it was already taken.
+    pub async fn take(&self, nonce_id: Uuid) -> Result<PreprocessedNonce, PreprocessingError> {
+        self.nonces
+            .lock()
+            .await
+            .remove(&nonce_id)
+            .ok_or(PreprocessingError::NonceConsumed(nonce_id))
+    }
+
+    /// Public commitments of all unconsumed nonces.
+    pub async fn commitments(&self) -> Vec<NonceCommitment> {
+        self.nonces
+            .lock()
+            .await
+            .values()
+            .map(PreprocessedNonce::public_commitment)
+            .collect()
+    }
+
+    /// Drop nonces older than `max_age`. Returns the ids removed so their
+    /// published commitments can be withdrawn.
+    pub async fn expire(&self, max_age: chrono::Duration) -> Vec<Uuid> {
+        let cutoff = chrono::Utc::now() - max_age;
+        let mut pool = self.nonces.lock().await;
+        let expired: Vec<Uuid> = pool
+            .values()
+            .filter(|n| n.created_at <= cutoff)
+            .map(|n| n.nonce_id)
+            .collect();
+        for id in &expired {
+            pool.remove(id);
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dummy_nonce(party_index: u16) -> PreprocessedNonce {
+        PreprocessedNonce {
+            nonce_id: Uuid::new_v4(),
+            party_index,
+            secret_nonces: vec![0xAA; 8],
+            commitment: vec![0xBB; 8],
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_nonce_pool_one_time_take() {
+        let pool = NoncePool::new();
+        let nonce = dummy_nonce(0);
+        let id = nonce.nonce_id;
+        pool.extend([nonce]).await;
+        assert_eq!(pool.size().await, 1);
+
+        assert!(pool.take(id).await.is_ok());
+        assert!(matches!(
+            pool.take(id).await,
+            Err(PreprocessingError::NonceConsumed(_))
+        ));
+        assert_eq!(pool.size().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_nonce_pool_expire() {
+        let pool = NoncePool::new();
+        let mut old = dummy_nonce(1);
+        old.created_at = chrono::Utc::now() - chrono::Duration::hours(48);
+        pool.extend([old, dummy_nonce(1)]).await;
+
+        assert_eq!(pool.expire(chrono::Duration::hours(24)).await.len(), 1);
+        assert_eq!(pool.size().await, 1);
+    }
+
+    #[test]
+    fn test_preprocessed_signature_verifies_for_child_key() {
+        use bitcoin::key::TapTweak;
+        use bitcoin::secp256k1::{schnorr, Message, PublicKey, Secp256k1};
+
+        let shares = givre::trusted_dealer::builder::<FrostCurve>(3)
+            .set_threshold(Some(2))
+            .hd_wallet(true)
+            .generate_shares(&mut OsRng)
+            .unwrap();
+        let key_share_data: Vec<Vec<u8>> =
+            shares.iter().map(|s| serde_json::to_vec(s).unwrap()).collect();
+        let message_hash = [7u8; 32];
+        let path = [0u32, 5];
+
+        // Parties 0 and 2 sign, each with one preprocessed nonce
+        let signers = [&key_share_data[0], &key_share_data[2]];
+        let nonces: Vec<PreprocessedNonce> = signers
+            .iter()
+            .map(|k| generate_nonces(k, 1).unwrap().remove(0))
+            .collect();
+        let commitments: Vec<NonceCommitment> =
+            nonces.iter().map(PreprocessedNonce::public_commitment).collect();
+        let partials: Vec<FrostPartialSignature> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(k, nonce)| {
+                sign_with_preprocessed_nonce(k, nonce, &message_hash, &path, &commitments).unwrap()
+            })
+            .collect();
+
+        let signature = aggregate_partial_signatures(
+            &key_share_data[1],
+            &message_hash,
+            &path,
+            &commitments,
+            &partials,
+        )
+        .unwrap();
+
+        // BIP-341 output key of the derived child, with an empty script tree
+        let secp = Secp256k1::verification_only();
+        let child = shares[0]
+            .derive_child_public_key::<<Bitcoin as Ciphersuite>::HdAlgo, _>(path)
+            .unwrap();
+        let internal = PublicKey::from_slice(&child.public_key.to_bytes(true)).unwrap();
+        let (output_key, _) = internal.x_only_public_key().0.tap_tweak(&secp, None);
+
+        let sig = schnorr::Signature::from_slice(&[signature.r, signature.s].concat()).unwrap();
+        secp.verify_schnorr(&sig, &Message::from_digest(message_hash), &output_key.to_inner())
+            .expect("aggregated signature must verify for the child output key");
+    }
+
+    #[test]
+    fn test_debug_hides_secret() {
+        let nonce = dummy_nonce(2);
+        let printed = format!("{:?}", nonce);
+        assert!(!printed.contains("secret_nonces"));
+    }
+}
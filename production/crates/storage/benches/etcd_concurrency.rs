@@ -0,0 +1,152 @@
+//! etcd handle throughput: one shared handle behind a mutex versus cloned
+//! handles issuing requests concurrently, and separate vote/count writes
+//! versus the batched `record_vote` transaction.
+//!
+//! Needs a running etcd (`ETCD_ENDPOINTS`, default `127.0.0.1:2379`):
+//!
+//! ```bash
+//! cargo bench -p threshold-storage --bench etcd_concurrency
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use threshold_storage::EtcdStorage;
+use threshold_types::{NodeId, TxId, Vote};
+use tokio::runtime::Runtime;
+use tokio::sync::Mutex;
+
+/// Requests issued per iteration
+const CONCURRENCY: [usize; 3] = [1, 16, 64];
+
+fn connect(rt: &Runtime) -> Option<EtcdStorage> {
+    let endpoints = std::env::var("ETCD_ENDPOINTS")
+        .unwrap_or_else(|_| "127.0.0.1:2379".to_string())
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .collect();
+    match rt.block_on(EtcdStorage::new(endpoints)) {
+        Ok(etcd) => Some(etcd),
+        Err(e) => {
+            eprintln!("Skipping etcd benchmarks: {}", e);
+            None
+        }
+    }
+}
+
+fn bench_handles(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let Some(etcd) = connect(&rt) else { return };
+    let shared = Arc::new(Mutex::new(etcd.clone()));
+
+    let mut group = c.benchmark_group("etcd_get");
+    for concurrency in CONCURRENCY {
+        group.throughput(Throughput::Elements(concurrency as u64));
+
+        group.bench_with_input(BenchmarkId::new("mutex", concurrency), &concurrency, |b, &n| {
+            b.to_async(&rt).iter(|| {
+                let shared = Arc::clone(&shared);
+                async move {
+                    let tasks: Vec<_> = (0..n)
+                        .map(|_| {
+                            let shared = Arc::clone(&shared);
+                            tokio::spawn(async move {
+                                shared.lock().await.get("/bench/key").await.unwrap();
+                            })
+                        })
+                        .collect();
+                    for task in tasks {
+                        task.await.unwrap();
+                    }
+                }
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("cloned", concurrency), &concurrency, |b, &n| {
+            b.to_async(&rt).iter(|| {
+                let etcd = etcd.clone();
+                async move {
+                    let tasks: Vec<_> = (0..n)
+                        .map(|_| {
+                            let etcd = etcd.clone();
+                            tokio::spawn(async move {
+                                etcd.get("/bench/key").await.unwrap();
+                            })
+                        })
+                        .collect();
+                    for task in tasks {
+                        task.await.unwrap();
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_votes(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let Some(etcd) = connect(&rt) else { return };
+    let round = AtomicU64::new(0);
+
+    // Every node votes once per transaction, so each iteration uses a fresh one
+    let votes = |n: usize| {
+        let tx_id = TxId::from(format!("bench-{}", round.fetch_add(1, Ordering::Relaxed)));
+        (0..n as u64)
+            .map(|node| Vote::new(NodeId(node), tx_id.clone(), 1, true, Some(1)))
+            .collect::<Vec<_>>()
+    };
+
+    let mut group = c.benchmark_group("etcd_votes");
+    for concurrency in CONCURRENCY {
+        group.throughput(Throughput::Elements(concurrency as u64));
+
+        group.bench_with_input(BenchmarkId::new("separate", concurrency), &concurrency, |b, &n| {
+            b.to_async(&rt).iter(|| {
+                let votes = votes(n);
+                let etcd = etcd.clone();
+                async move {
+                    let tasks: Vec<_> = votes
+                        .into_iter()
+                        .map(|vote| {
+                            let etcd = etcd.clone();
+                            tokio::spawn(async move {
+                                etcd.store_vote(&vote).await.unwrap();
+                                etcd.increment_vote_count(&vote.tx_id, vote.value).await.unwrap();
+                            })
+                        })
+                        .collect();
+                    for task in tasks {
+                        task.await.unwrap();
+                    }
+                }
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("batched", concurrency), &concurrency, |b, &n| {
+            b.to_async(&rt).iter(|| {
+                let votes = votes(n);
+                let etcd = etcd.clone();
+                async move {
+                    let tasks: Vec<_> = votes
+                        .into_iter()
+                        .map(|vote| {
+                            let etcd = etcd.clone();
+                            tokio::spawn(async move {
+                                etcd.record_vote(&vote).await.unwrap();
+                            })
+                        })
+                        .collect();
+                    for task in tasks {
+                        task.await.unwrap();
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_handles, bench_votes);
+criterion_main!(benches);
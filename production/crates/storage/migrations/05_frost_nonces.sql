@@ -0,0 +1,30 @@
+-- 05_frost_nonces.sql
+-- FROST nonce preprocessing pool (single-round Taproot signing)
+--
+-- Each node stores its own secret nonces. A nonce MUST be used at most once:
+-- consumption wipes the secret in the same UPDATE that marks it consumed.
+
+CREATE TABLE IF NOT EXISTS frost_nonces (
+    id BIGSERIAL PRIMARY KEY,
+    nonce_id TEXT NOT NULL UNIQUE,
+    node_id BIGINT NOT NULL CHECK (node_id > 0),
+    party_index INTEGER NOT NULL CHECK (party_index >= 0),
+    secret_nonces BYTEA,                        -- NULL once consumed
+    commitment BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    consumed_at TIMESTAMPTZ,
+    consumed_by TEXT,                           -- Signing session that used the nonce
+    CONSTRAINT frost_nonce_secret_wiped CHECK (
+        (consumed_at IS NULL AND secret_nonces IS NOT NULL) OR
+        (consumed_at IS NOT NULL AND secret_nonces IS NULL)
+    )
+);
+
+CREATE INDEX idx_frost_nonces_node_available ON frost_nonces(node_id, created_at) WHERE consumed_at IS NULL;
+CREATE INDEX idx_frost_nonces_consumed_at ON frost_nonces(consumed_at) WHERE consumed_at IS NOT NULL;
+
+COMMENT ON TABLE frost_nonces IS 'Preprocessed FROST nonce pairs per node (strict one-time use)';
+
+INSERT INTO schema_migrations (version, description, applied_at)
+VALUES (5, 'Add FROST nonce preprocessing pool', NOW())
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,7 @@
+-- 06_dkg_xpub.sql
+-- Root BIP32 extended public key for watch-only export
+--
+-- The chain code is generated jointly during DKG and stored inside the key
+-- shares; the serialized xpub is kept here so exports need no key share access.
+
+ALTER TABLE dkg_ceremonies ADD COLUMN IF NOT EXISTS xpub TEXT;
@@ -0,0 +1,20 @@
+-- 07_auth.sql
+-- API authentication and transaction ownership
+--
+-- The seed users in 04_user_addresses.sql carry placeholder hashes that no
+-- bcrypt implementation accepts. Deactivate them rather than giving them
+-- working passwords: the first admin is created by the node at startup from
+-- BOOTSTRAP_ADMIN_PASSWORD, which is refused once an active admin exists.
+
+UPDATE users
+SET is_active = FALSE
+WHERE password_hash IN (
+    '$2b$10$rQZ5hGP.V8KjxqzqNvCvXeQZH0P3qL6G8vY4mN1wK2xB9cD7eF3hI',
+    '$2b$10$xYz5hGP.V8KjxqzqNvCvXeQZH0P3qL6G8vY4mN1wK2xB9cD7eF3hI'
+);
+
+-- User that submitted a transaction; NULL for transactions created before
+-- authentication was enforced (visible to admins only).
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS created_by TEXT REFERENCES users(user_id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_created_by ON transactions(created_by, created_at DESC);
@@ -0,0 +1,51 @@
+-- 08_deposits.sql
+-- Deposit detection and per-user ledger
+--
+-- The deposit watcher records every output paying to a user address. A
+-- deposit is credited to the ledger once it reaches the configured
+-- confirmation depth; if a reorg drops it below that depth the credit is
+-- reversed with a compensating ledger entry. Ledger entries are append-only,
+-- so a user's balance is always SUM(amount_sats).
+
+CREATE TABLE IF NOT EXISTS deposits (
+    id BIGSERIAL PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    address TEXT NOT NULL REFERENCES user_addresses(address) ON DELETE CASCADE,
+    txid TEXT NOT NULL,
+    vout INTEGER NOT NULL CHECK (vout >= 0),
+    amount_sats BIGINT NOT NULL CHECK (amount_sats > 0),
+    status TEXT NOT NULL DEFAULT 'pending',
+    confirmations INTEGER NOT NULL DEFAULT 0,
+    block_height BIGINT,
+    block_hash TEXT,                            -- Changes if reorged into another block
+    first_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    credited_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_deposit_output UNIQUE (txid, vout),
+    -- pending: below confirmation depth; credited: in ledger;
+    -- reversed: transaction disappeared from the chain and mempool
+    CONSTRAINT deposits_status_check CHECK (status IN ('pending', 'credited', 'reversed'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_deposits_user_id ON deposits(user_id, first_seen_at DESC);
+CREATE INDEX IF NOT EXISTS idx_deposits_unsettled ON deposits(confirmations) WHERE status IN ('pending', 'credited');
+
+CREATE TRIGGER update_deposits_updated_at
+    BEFORE UPDATE ON deposits
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE IF NOT EXISTS ledger_entries (
+    id BIGSERIAL PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    amount_sats BIGINT NOT NULL CHECK (amount_sats <> 0),  -- Signed: credits > 0, reversals < 0
+    entry_type TEXT NOT NULL,
+    deposit_id BIGINT REFERENCES deposits(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT ledger_entry_type_check CHECK (entry_type IN ('deposit_credit', 'deposit_reversal'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_entries_user_id ON ledger_entries(user_id, created_at DESC);
+
+COMMENT ON TABLE deposits IS 'Incoming outputs to user addresses detected by the deposit watcher';
+COMMENT ON TABLE ledger_entries IS 'Append-only per-user ledger; balance is the sum of amount_sats';
@@ -0,0 +1,44 @@
+-- 09_api_keys.sql
+-- Scoped API keys for machine clients
+--
+-- An admin issues a key on behalf of a user. Requests made with the key act
+-- as that user, are limited to the key's scopes and amount limits, and are
+-- signed with HMAC-SHA256 over method, path, timestamp and body hash. The
+-- secret has to be readable by the API to verify signatures, so this table
+-- must be protected like the key shares.
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    key_id TEXT PRIMARY KEY,                    -- Public identifier sent in X-Api-Key
+    user_id TEXT NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    secret TEXT NOT NULL,                       -- Hex-encoded HMAC secret
+    scopes TEXT[] NOT NULL,
+    max_amount_sats BIGINT CHECK (max_amount_sats > 0),      -- Per transaction
+    daily_limit_sats BIGINT CHECK (daily_limit_sats > 0),    -- Rolling 24 hours
+    expires_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_by TEXT NOT NULL REFERENCES users(user_id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    CONSTRAINT api_keys_scopes_check CHECK (scopes <@ ARRAY['tx:create', 'tx:read', 'wallet:read']::TEXT[])
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_user_id ON api_keys(user_id);
+
+-- Signatures seen within the replay window; a repeated signature is a replay
+CREATE TABLE IF NOT EXISTS api_key_nonces (
+    key_id TEXT NOT NULL REFERENCES api_keys(key_id) ON DELETE CASCADE,
+    signature TEXT NOT NULL,
+    seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (key_id, signature)
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_key_nonces_seen_at ON api_key_nonces(seen_at);
+
+-- API key a transaction was created with, for daily limit accounting
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS api_key_id TEXT REFERENCES api_keys(key_id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_api_key_id ON transactions(api_key_id, created_at DESC) WHERE api_key_id IS NOT NULL;
+
+COMMENT ON TABLE api_keys IS 'Scoped HMAC API keys for machine clients';
+COMMENT ON TABLE api_key_nonces IS 'Recently used request signatures (replay protection)';
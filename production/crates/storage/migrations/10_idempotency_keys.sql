@@ -0,0 +1,22 @@
+-- 10_idempotency_keys.sql
+-- Idempotency keys for transaction creation
+--
+-- A client that retries POST /api/v1/transactions with the same
+-- Idempotency-Key header gets the original response instead of a second
+-- transaction. Keys are scoped to the caller and kept for a configurable
+-- retention period (IDEMPOTENCY_KEY_TTL_HOURS).
+
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    owner TEXT NOT NULL,                        -- Caller (JWT subject)
+    idempotency_key TEXT NOT NULL,
+    request_hash TEXT NOT NULL,                 -- SHA-256 of the request body
+    status_code INTEGER,                        -- NULL while the first request is in progress
+    response JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (owner, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_expires_at ON idempotency_keys(expires_at);
+
+COMMENT ON TABLE idempotency_keys IS 'Stored responses of idempotent requests (retry deduplication)';
@@ -0,0 +1,60 @@
+-- 11_webhooks.sql
+-- Webhook subscriptions and their delivery queue
+--
+-- Events are fanned out to one delivery row per matching subscription in the
+-- same database transaction that produced them (transaction state changes
+-- are enqueued by transition_transaction_state). Every node runs a dispatcher
+-- that claims due deliveries with FOR UPDATE SKIP LOCKED, POSTs them with an
+-- HMAC-SHA256 signature, and retries with exponential backoff. Deliveries
+-- that exhaust their attempts move to webhook_dead_letters, from where an
+-- admin can replay them.
+
+CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+    subscription_id TEXT PRIMARY KEY,
+    url TEXT NOT NULL,
+    events TEXT[] NOT NULL,
+    secret TEXT NOT NULL,                       -- HMAC key for X-Webhook-Signature
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by TEXT NOT NULL REFERENCES users(user_id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT webhook_subscriptions_events_check CHECK (
+        cardinality(events) > 0 AND
+        events <@ ARRAY['transaction.state_changed', 'dkg.completed', 'aux_info.completed', 'presignature_pool.low']::TEXT[]
+    )
+);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id BIGSERIAL PRIMARY KEY,
+    subscription_id TEXT NOT NULL REFERENCES webhook_subscriptions(subscription_id) ON DELETE CASCADE,
+    event_id TEXT NOT NULL,                     -- Same for every subscription receiving the event
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_status_code INTEGER,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ                    -- NULL while pending
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at) WHERE delivered_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_subscription ON webhook_deliveries(subscription_id, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+    id BIGSERIAL PRIMARY KEY,
+    subscription_id TEXT NOT NULL REFERENCES webhook_subscriptions(subscription_id) ON DELETE CASCADE,
+    event_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL,
+    last_status_code INTEGER,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,            -- When the event was enqueued
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_dead_letters_subscription ON webhook_dead_letters(subscription_id, failed_at DESC);
+
+COMMENT ON TABLE webhook_subscriptions IS 'Webhook endpoints and the events they receive';
+COMMENT ON TABLE webhook_deliveries IS 'Pending and delivered webhook events (outbox)';
+COMMENT ON TABLE webhook_dead_letters IS 'Webhook deliveries that exhausted their retries';
@@ -0,0 +1,21 @@
+-- 12_cluster_events.sql
+-- Cluster event log behind the GET /api/v1/events server-sent events stream
+--
+-- Every node appends to the same table, so a client connected to any node
+-- sees transaction state changes, vote arrivals, signing progress and node
+-- health changes from the whole cluster. The BIGSERIAL id is the SSE event
+-- ID: a client reconnecting with Last-Event-ID resumes right after it, as
+-- long as the events have not been purged yet.
+
+CREATE TABLE IF NOT EXISTS cluster_events (
+    id BIGSERIAL PRIMARY KEY,
+    event_type TEXT NOT NULL,
+    tx_id TEXT,                                 -- set for transaction-scoped events
+    node_id BIGINT,                             -- node the event is about, if any
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_cluster_events_tx_id ON cluster_events(tx_id, id)
+    WHERE tx_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_cluster_events_created_at ON cluster_events(created_at);
@@ -0,0 +1,28 @@
+-- 13_transaction_state_history.sql
+-- Compare-and-set state transitions for transactions
+--
+-- PostgresStorage::transition_transaction_state only updates a row that is
+-- still in the expected state (UPDATE ... WHERE state = expected) and bumps
+-- its version, so two nodes racing on the same transaction cannot both move
+-- it. The legal transitions are defined by TransactionState in
+-- threshold_types. Every applied transition is recorded below with the actor
+-- that made it and why.
+
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS transaction_state_history (
+    id BIGSERIAL PRIMARY KEY,
+    txid TEXT NOT NULL REFERENCES transactions(txid) ON UPDATE CASCADE ON DELETE CASCADE,
+    from_state TEXT NOT NULL,
+    to_state TEXT NOT NULL,
+    version BIGINT NOT NULL,                    -- transactions.version after the transition
+    actor TEXT NOT NULL,                        -- e.g. node-1/orchestrator
+    reason TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (txid, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_state_history_created_at
+    ON transaction_state_history(created_at);
+
+COMMENT ON TABLE transaction_state_history IS 'Applied transaction state transitions with actor and reason';
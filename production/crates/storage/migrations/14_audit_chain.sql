@@ -0,0 +1,45 @@
+-- 14_audit_chain.sql
+-- Hash-chained, signed audit log
+--
+-- Each audit_log entry written by the nodes gets the next sequence number,
+-- the hash of the previous entry and an Ed25519 signature over its own hash
+-- from the writing node's audit key (see threshold_storage::audit). Appends
+-- lock the single audit_chain_head row, so nodes extend the chain one at a
+-- time. Rows written before this migration keep a NULL seq and are not part
+-- of the chain.
+--
+-- The database triggers that wrote audit rows cannot sign, so they are
+-- dropped; state changes and address creation are audited by the nodes.
+
+ALTER TABLE audit_log ADD COLUMN IF NOT EXISTS seq BIGINT UNIQUE;
+ALTER TABLE audit_log ADD COLUMN IF NOT EXISTS prev_hash BYTEA;
+ALTER TABLE audit_log ADD COLUMN IF NOT EXISTS entry_hash BYTEA;
+ALTER TABLE audit_log ADD COLUMN IF NOT EXISTS signer_node_id BIGINT;
+ALTER TABLE audit_log ADD COLUMN IF NOT EXISTS signature BYTEA;
+
+CREATE TABLE IF NOT EXISTS audit_chain_head (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    seq BIGINT NOT NULL,
+    entry_hash BYTEA NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO audit_chain_head (seq, entry_hash)
+VALUES (0, decode(repeat('00', 32), 'hex'))
+ON CONFLICT (id) DO NOTHING;
+
+-- Public keys of the node audit keys, as last registered by each node.
+-- The verifier prefers the keys anchored in etcd or given on the command line.
+CREATE TABLE IF NOT EXISTS audit_signers (
+    node_id BIGINT PRIMARY KEY,
+    public_key BYTEA NOT NULL,
+    registered_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+DROP TRIGGER IF EXISTS log_transaction_state_change_trigger ON transactions;
+DROP FUNCTION IF EXISTS log_transaction_state_change();
+DROP TRIGGER IF EXISTS log_address_creation_trigger ON user_addresses;
+DROP FUNCTION IF EXISTS log_address_creation();
+
+COMMENT ON TABLE audit_chain_head IS 'Sequence number and hash of the last chained audit entry';
+COMMENT ON TABLE audit_signers IS 'Ed25519 audit public keys of the nodes';
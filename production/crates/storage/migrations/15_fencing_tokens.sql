@@ -0,0 +1,13 @@
+-- 15_fencing_tokens.sql
+-- Fencing tokens for writes made under an etcd signing lock
+--
+-- Each acquisition of /locks/signing/<txid> carries a fencing token (the etcd
+-- revision that created the lock key), and later holders always get larger
+-- tokens. PostgresStorage::set_signed_transaction only writes when its token
+-- is at least the one recorded here, so a node whose lease expired in the
+-- middle of a slow signing session cannot overwrite the result of the node
+-- that took the lock over.
+
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS signing_fencing_token BIGINT;
+
+COMMENT ON COLUMN transactions.signing_fencing_token IS 'Highest signing lock fencing token that wrote signed_tx';
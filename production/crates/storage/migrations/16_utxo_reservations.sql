@@ -0,0 +1,25 @@
+-- 16_utxo_reservations.sql
+-- UTXOs committed to transactions that have not finished yet
+--
+-- Coin selection skips outputs listed here, so two transactions created close
+-- together cannot pick the same inputs. The primary key makes the reservation
+-- itself race-free: PostgresStorage::create_transaction_with_reservations
+-- inserts the transaction and its reservations in one database transaction
+-- and gives up if any output is already taken.
+--
+-- Reservations are released when the transaction reaches a terminal state
+-- (rejected, failed including timeouts, aborted, or confirmed). The timeout
+-- monitor periodically frees any that were left behind.
+
+CREATE TABLE IF NOT EXISTS utxo_reservations (
+    utxo_txid TEXT NOT NULL,
+    utxo_vout INTEGER NOT NULL,
+    txid TEXT NOT NULL REFERENCES transactions(txid) ON UPDATE CASCADE ON DELETE CASCADE,
+    amount_sats BIGINT NOT NULL,
+    reserved_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (utxo_txid, utxo_vout)
+);
+
+CREATE INDEX IF NOT EXISTS idx_utxo_reservations_txid ON utxo_reservations(txid);
+
+COMMENT ON TABLE utxo_reservations IS 'Outputs reserved as inputs of unfinished transactions';
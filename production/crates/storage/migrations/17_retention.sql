@@ -0,0 +1,34 @@
+-- 17_retention.sql
+-- Legal holds and the archive ledger of the retention service
+--
+-- The retention service archives terminal transactions, together with their
+-- voting rounds, votes, state history and presignature usage, to gzipped
+-- JSONL files and then deletes them; the dependent rows go with them through
+-- ON DELETE CASCADE. A legal hold exempts a transaction from archival and
+-- from every other retention policy. The foreign key has no ON DELETE
+-- action, so a held transaction cannot be deleted at all until the hold is
+-- released.
+
+CREATE TABLE IF NOT EXISTS legal_holds (
+    txid TEXT PRIMARY KEY REFERENCES transactions(txid) ON UPDATE CASCADE,
+    reason TEXT NOT NULL CHECK (length(reason) > 0),
+    placed_by TEXT NOT NULL,
+    placed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One row per archive file; the file itself lives on the archiving node
+CREATE TABLE IF NOT EXISTS retention_archives (
+    id BIGSERIAL PRIMARY KEY,
+    file_name TEXT NOT NULL UNIQUE,
+    sha256 TEXT NOT NULL,                       -- hex SHA-256 of the compressed file
+    transactions INTEGER NOT NULL CHECK (transactions > 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Terminal transactions are selected by age of their last update
+CREATE INDEX IF NOT EXISTS idx_transactions_state_updated_at ON transactions(state, updated_at);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_delivered_at ON webhook_deliveries(delivered_at)
+    WHERE delivered_at IS NOT NULL;
+
+COMMENT ON TABLE legal_holds IS 'Transactions exempt from retention (archival and pruning)';
+COMMENT ON TABLE retention_archives IS 'Archive files written by the retention service';
@@ -0,0 +1,30 @@
+-- 18_transaction_attempts.sql
+-- Attempts of the retried steps of the transaction lifecycle
+--
+-- The orchestrator records every signing session and every broadcast it
+-- makes for a transaction. A retryable failure sets retry_at, the earliest
+-- time the step may run again (exponential backoff); a fatal failure, or more
+-- retryable failures than the configured maximum, fails the transaction.
+-- A signing attempt that failed because of one signer names it in
+-- faulty_node_id, and later attempts leave that signer out.
+
+CREATE TABLE IF NOT EXISTS transaction_attempts (
+    id BIGSERIAL PRIMARY KEY,
+    txid TEXT NOT NULL REFERENCES transactions(txid) ON UPDATE CASCADE ON DELETE CASCADE,
+    operation TEXT NOT NULL CHECK (operation IN ('signing', 'broadcast')),
+    attempt INTEGER NOT NULL CHECK (attempt > 0),
+    outcome TEXT NOT NULL CHECK (outcome IN ('succeeded', 'retryable', 'fatal')),
+    error TEXT,
+    coordinator_node_id BIGINT NOT NULL,        -- node that made the attempt
+    excluded_node_ids BIGINT[] NOT NULL DEFAULT '{}',  -- signers left out of the attempt
+    faulty_node_id BIGINT,                      -- signer blamed for the failure
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    retry_at TIMESTAMPTZ,                       -- set for retryable failures
+    UNIQUE (txid, operation, attempt),
+    CONSTRAINT transaction_attempts_error_check CHECK ((outcome = 'succeeded') = (error IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_attempts_txid ON transaction_attempts(txid, operation, attempt);
+
+COMMENT ON TABLE transaction_attempts IS 'Signing and broadcast attempts per transaction, with retry bookkeeping';
@@ -0,0 +1,17 @@
+-- 19_withdrawal_debits.sql
+-- Ledger debits for user withdrawals
+--
+-- A transaction created by a non-admin user debits amount plus fee from the
+-- user's ledger balance when it is stored. If the transaction ends without
+-- ever reaching the network, the debit is reversed by a compensating entry.
+-- Each transaction is debited and reversed at most once.
+
+ALTER TABLE ledger_entries ADD COLUMN IF NOT EXISTS txid TEXT;
+
+ALTER TABLE ledger_entries DROP CONSTRAINT IF EXISTS ledger_entry_type_check;
+ALTER TABLE ledger_entries ADD CONSTRAINT ledger_entry_type_check CHECK (
+    entry_type IN ('deposit_credit', 'deposit_reversal', 'withdrawal', 'withdrawal_reversal')
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ledger_entries_txid
+    ON ledger_entries(txid, entry_type) WHERE txid IS NOT NULL;
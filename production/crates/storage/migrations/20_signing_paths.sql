@@ -0,0 +1,19 @@
+-- 20_signing_paths.sql
+-- Key paths for signing transactions that spend user deposit addresses
+--
+-- User addresses are derived non-hardened from the root xpub at
+-- root/branch/index. Their recorded path was a BIP-44 style path with
+-- hardened levels that does not describe how they were derived; it is
+-- rewritten relative to the root key so it can be used for signing.
+--
+-- Every transaction spends the UTXOs of a single address. Its path below the
+-- root key is stored with the transaction ('{}' for the wallet root address)
+-- and the signers tweak their key shares by it.
+
+UPDATE user_addresses
+SET derivation_path = 'm/' || CASE WHEN is_change THEN 1 ELSE 0 END || '/' || derivation_index
+WHERE derivation_path LIKE '%''%';
+
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS derivation_path INTEGER[] NOT NULL DEFAULT '{}';
+
+COMMENT ON COLUMN transactions.derivation_path IS 'Non-hardened BIP32 path below the root key of the address the inputs spend';
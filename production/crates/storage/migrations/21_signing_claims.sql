@@ -0,0 +1,10 @@
+-- 21_signing_claims.sql
+-- Signing fencing tokens are recorded when the lock is taken
+--
+-- A node that acquires /locks/signing/<txid> records its fencing token before
+-- moving the transaction to signing, and may only store signed_tx while the
+-- recorded token is still its own. A holder whose lease expired mid-session
+-- therefore cannot write once another node has taken the lock over, even if
+-- the new holder has not finished signing yet.
+
+COMMENT ON COLUMN transactions.signing_fencing_token IS 'Fencing token of the latest signing lock holder; only it may write signed_tx';
@@ -0,0 +1,405 @@
+//! Tamper-evident audit chain
+//!
+//! Every audit log entry has a sequence number, the hash of the entry before
+//! it and a hash over its own contents, signed with the Ed25519 identity key
+//! of the node that wrote it. Deleting, reordering or editing a row breaks
+//! the chain, and a node cannot forge entries in another node's name.
+//!
+//! Rewriting the whole chain from some point on is still possible for
+//! someone holding a node key and database access, so nodes periodically
+//! anchor the chain head (sequence number and hash) in etcd. The verifier
+//! checks the chain against those checkpoints as well.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use threshold_types::{Error, NodeId, Result};
+
+/// `prev_hash` of the first entry in the chain
+pub const GENESIS_HASH: [u8; 32] = [0u8; 32];
+
+/// Domain separator mixed into every entry hash
+const HASH_DOMAIN: &str = "mpc-wallet-audit-v1";
+
+/// DER prefix of an Ed25519 PKCS#8 v1 private key; the 32-byte seed follows
+const ED25519_PKCS8_PREFIX: [u8; 16] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20,
+];
+
+/// Identity key a node signs its audit entries with
+pub struct AuditSigner {
+    node_id: NodeId,
+    key: SigningKey,
+}
+
+impl AuditSigner {
+    pub fn new(node_id: NodeId, key: SigningKey) -> Self {
+        Self { node_id, key }
+    }
+
+    /// Random key that lives only as long as the process (development only)
+    pub fn generate(node_id: NodeId) -> Self {
+        Self::new(node_id, SigningKey::generate(&mut rand::rngs::OsRng))
+    }
+
+    /// Load an unencrypted Ed25519 PKCS#8 PEM private key
+    pub fn from_pem_file(node_id: NodeId, path: &Path) -> Result<Self> {
+        let pem = std::fs::read(path).map_err(|e| {
+            Error::ConfigError(format!("Failed to read audit key {}: {}", path.display(), e))
+        })?;
+        let der = rustls_pemfile::private_key(&mut &pem[..])
+            .map_err(|e| Error::ConfigError(format!("Invalid audit key PEM: {}", e)))?
+            .ok_or_else(|| Error::ConfigError("No private key in audit key file".to_string()))?;
+
+        let seed: [u8; 32] = der
+            .secret_der()
+            .strip_prefix(&ED25519_PKCS8_PREFIX[..])
+            .and_then(|seed| seed.try_into().ok())
+            .ok_or_else(|| {
+                Error::ConfigError("Audit key is not an Ed25519 PKCS#8 private key".to_string())
+            })?;
+
+        Ok(Self::new(node_id, SigningKey::from_bytes(&seed)))
+    }
+
+    pub fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.key.verifying_key()
+    }
+
+    /// Sign an entry hash
+    pub fn sign(&self, entry_hash: &[u8]) -> Vec<u8> {
+        self.key.sign(entry_hash).to_bytes().to_vec()
+    }
+}
+
+/// One link of the audit chain
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub event_type: String,
+    pub node_id: Option<u64>,
+    pub tx_id: Option<String>,
+    pub details: serde_json::Value,
+    /// Stored with microsecond precision, as Postgres keeps it
+    pub timestamp: DateTime<Utc>,
+    pub prev_hash: Vec<u8>,
+    pub entry_hash: Vec<u8>,
+    /// Node whose identity key signed the entry; `None` for unsigned entries
+    pub signer_node_id: Option<u64>,
+    pub signature: Option<Vec<u8>>,
+}
+
+impl AuditEntry {
+    /// Hash of the entry recomputed from its contents
+    pub fn compute_hash(&self) -> [u8; 32] {
+        compute_entry_hash(
+            self.seq,
+            &self.prev_hash,
+            &self.event_type,
+            self.node_id,
+            self.tx_id.as_deref(),
+            &self.details,
+            self.timestamp,
+            self.signer_node_id,
+        )
+    }
+}
+
+/// Hash of an audit entry
+///
+/// The fields are encoded as a JSON array, which is unambiguous, and JSON
+/// object keys come out sorted, so `details` hashes the same after a round
+/// trip through JSONB.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_entry_hash(
+    seq: u64,
+    prev_hash: &[u8],
+    event_type: &str,
+    node_id: Option<u64>,
+    tx_id: Option<&str>,
+    details: &serde_json::Value,
+    timestamp: DateTime<Utc>,
+    signer_node_id: Option<u64>,
+) -> [u8; 32] {
+    let encoded = serde_json::json!([
+        HASH_DOMAIN,
+        seq,
+        hex::encode(prev_hash),
+        event_type,
+        node_id,
+        tx_id,
+        details,
+        timestamp.timestamp_micros(),
+        signer_node_id,
+    ]);
+    Sha256::digest(encoded.to_string().as_bytes()).into()
+}
+
+/// Chain head anchored in etcd
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCheckpoint {
+    pub seq: u64,
+    /// Hex-encoded hash of entry `seq`
+    pub entry_hash: String,
+    /// Node that anchored the checkpoint
+    pub node_id: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Problem found while verifying the audit chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditIssue {
+    /// Entries between `after` and `next` are missing
+    Gap { after: u64, next: u64 },
+    /// The entry's contents no longer match its hash
+    Modified { seq: u64 },
+    /// The entry does not point at the hash of the entry before it
+    BrokenLink { seq: u64 },
+    /// The signature does not verify with the signer's public key
+    BadSignature { seq: u64, signer: u64 },
+    /// No public key is known for the signer
+    UnknownSigner { seq: u64, signer: u64 },
+    /// The entry carries no signature
+    Unsigned { seq: u64 },
+    /// An etcd checkpoint disagrees with the entry it anchors
+    CheckpointMismatch { seq: u64 },
+    /// An etcd checkpoint anchors an entry that is no longer there
+    CheckpointMissingEntry { seq: u64 },
+}
+
+impl fmt::Display for AuditIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gap { after, next } => {
+                write!(f, "entries {}..={} are missing", after + 1, next - 1)
+            }
+            Self::Modified { seq } => write!(f, "entry {} was modified", seq),
+            Self::BrokenLink { seq } => {
+                write!(f, "entry {} does not link to the previous entry", seq)
+            }
+            Self::BadSignature { seq, signer } => {
+                write!(f, "entry {} has an invalid signature from node {}", seq, signer)
+            }
+            Self::UnknownSigner { seq, signer } => {
+                write!(f, "entry {} is signed by node {} whose key is unknown", seq, signer)
+            }
+            Self::Unsigned { seq } => write!(f, "entry {} is not signed", seq),
+            Self::CheckpointMismatch { seq } => {
+                write!(f, "entry {} does not match its etcd checkpoint", seq)
+            }
+            Self::CheckpointMissingEntry { seq } => {
+                write!(f, "entry {} anchored in etcd is missing", seq)
+            }
+        }
+    }
+}
+
+/// Result of verifying the audit chain
+#[derive(Debug, Clone, Default)]
+pub struct AuditReport {
+    pub entries: usize,
+    pub last_seq: Option<u64>,
+    pub checkpoints: usize,
+    pub issues: Vec<AuditIssue>,
+}
+
+impl AuditReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Re-walk the audit chain
+///
+/// `entries` must be sorted by sequence number. `signers` maps node IDs to
+/// their audit public keys; `checkpoints` are the heads anchored in etcd.
+pub fn verify_chain(
+    entries: &[AuditEntry],
+    signers: &HashMap<u64, VerifyingKey>,
+    checkpoints: &[AuditCheckpoint],
+) -> AuditReport {
+    let mut issues = Vec::new();
+    let mut prev: Option<&AuditEntry> = None;
+
+    for entry in entries {
+        let expected_seq = prev.map_or(1, |p| p.seq + 1);
+        let contiguous = entry.seq == expected_seq;
+        if !contiguous {
+            issues.push(AuditIssue::Gap {
+                after: expected_seq - 1,
+                next: entry.seq,
+            });
+        }
+
+        // Across a gap the previous hash is unknown, so the link can't be checked
+        if contiguous {
+            let expected_prev = prev.map_or(&GENESIS_HASH[..], |p| &p.entry_hash[..]);
+            if entry.prev_hash != expected_prev {
+                issues.push(AuditIssue::BrokenLink { seq: entry.seq });
+            }
+        }
+
+        if entry.compute_hash()[..] != entry.entry_hash[..] {
+            issues.push(AuditIssue::Modified { seq: entry.seq });
+        } else {
+            match (entry.signer_node_id, &entry.signature) {
+                (Some(signer), Some(signature)) => match signers.get(&signer) {
+                    Some(key) => {
+                        let valid = Signature::from_slice(signature)
+                            .map(|sig| key.verify(&entry.entry_hash, &sig).is_ok())
+                            .unwrap_or(false);
+                        if !valid {
+                            issues.push(AuditIssue::BadSignature {
+                                seq: entry.seq,
+                                signer,
+                            });
+                        }
+                    }
+                    None => issues.push(AuditIssue::UnknownSigner {
+                        seq: entry.seq,
+                        signer,
+                    }),
+                },
+                _ => issues.push(AuditIssue::Unsigned { seq: entry.seq }),
+            }
+        }
+
+        prev = Some(entry);
+    }
+
+    let by_seq: HashMap<u64, &AuditEntry> = entries.iter().map(|e| (e.seq, e)).collect();
+    for checkpoint in checkpoints {
+        match by_seq.get(&checkpoint.seq) {
+            Some(entry) if hex::encode(&entry.entry_hash) == checkpoint.entry_hash => {}
+            Some(_) => issues.push(AuditIssue::CheckpointMismatch {
+                seq: checkpoint.seq,
+            }),
+            None => issues.push(AuditIssue::CheckpointMissingEntry {
+                seq: checkpoint.seq,
+            }),
+        }
+    }
+
+    AuditReport {
+        entries: entries.len(),
+        last_seq: entries.last().map(|e| e.seq),
+        checkpoints: checkpoints.len(),
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::SubsecRound;
+
+    fn chain(signer: &AuditSigner, len: u64) -> Vec<AuditEntry> {
+        let mut entries: Vec<AuditEntry> = Vec::new();
+        for seq in 1..=len {
+            let prev_hash = entries
+                .last()
+                .map_or(GENESIS_HASH.to_vec(), |e| e.entry_hash.clone());
+            let mut entry = AuditEntry {
+                seq,
+                event_type: "transaction_state_change".to_string(),
+                node_id: Some(signer.node_id().0),
+                tx_id: Some(format!("tx-{}", seq)),
+                details: serde_json::json!({ "new_state": "voting", "old_state": "pending" }),
+                timestamp: Utc::now().trunc_subsecs(6),
+                prev_hash,
+                entry_hash: Vec::new(),
+                signer_node_id: Some(signer.node_id().0),
+                signature: None,
+            };
+            entry.entry_hash = entry.compute_hash().to_vec();
+            entry.signature = Some(signer.sign(&entry.entry_hash));
+            entries.push(entry);
+        }
+        entries
+    }
+
+    fn keys(signer: &AuditSigner) -> HashMap<u64, VerifyingKey> {
+        HashMap::from([(signer.node_id().0, signer.verifying_key())])
+    }
+
+    fn checkpoint(entry: &AuditEntry) -> AuditCheckpoint {
+        AuditCheckpoint {
+            seq: entry.seq,
+            entry_hash: hex::encode(&entry.entry_hash),
+            node_id: 1,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_intact_chain_verifies() {
+        let signer = AuditSigner::generate(NodeId(1));
+        let entries = chain(&signer, 5);
+        let report = verify_chain(&entries, &keys(&signer), &[checkpoint(&entries[4])]);
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.entries, 5);
+        assert_eq!(report.last_seq, Some(5));
+    }
+
+    #[test]
+    fn test_detects_modified_entry() {
+        let signer = AuditSigner::generate(NodeId(1));
+        let mut entries = chain(&signer, 3);
+        entries[1].details = serde_json::json!({ "new_state": "approved" });
+        let report = verify_chain(&entries, &keys(&signer), &[]);
+        assert_eq!(report.issues, vec![AuditIssue::Modified { seq: 2 }]);
+    }
+
+    #[test]
+    fn test_detects_deleted_entry() {
+        let signer = AuditSigner::generate(NodeId(1));
+        let mut entries = chain(&signer, 4);
+        entries.remove(1);
+        let report = verify_chain(&entries, &keys(&signer), &[]);
+        assert_eq!(report.issues, vec![AuditIssue::Gap { after: 1, next: 3 }]);
+    }
+
+    #[test]
+    fn test_detects_rewritten_tail() {
+        let signer = AuditSigner::generate(NodeId(1));
+        let original = chain(&signer, 3);
+        let anchor = checkpoint(&original[2]);
+
+        // A consistent but different chain still fails against the checkpoint
+        let rewritten = chain(&signer, 2);
+        let report = verify_chain(&rewritten, &keys(&signer), &[anchor.clone()]);
+        assert_eq!(report.issues, vec![AuditIssue::CheckpointMissingEntry { seq: 3 }]);
+
+        let rewritten = chain(&signer, 3);
+        let report = verify_chain(&rewritten, &keys(&signer), &[anchor]);
+        assert_eq!(report.issues, vec![AuditIssue::CheckpointMismatch { seq: 3 }]);
+    }
+
+    #[test]
+    fn test_detects_forged_signature() {
+        let signer = AuditSigner::generate(NodeId(1));
+        let forger = AuditSigner::generate(NodeId(1));
+        let entries = chain(&forger, 2);
+
+        let report = verify_chain(&entries, &keys(&signer), &[]);
+        assert_eq!(
+            report.issues,
+            vec![
+                AuditIssue::BadSignature { seq: 1, signer: 1 },
+                AuditIssue::BadSignature { seq: 2, signer: 1 },
+            ]
+        );
+
+        let report = verify_chain(&entries, &HashMap::new(), &[]);
+        assert_eq!(report.issues[0], AuditIssue::UnknownSigner { seq: 1, signer: 1 });
+    }
+}
@@ -1,9 +1,15 @@
-use etcd_client::{Client, Compare, CompareOp, DeleteOptions, GetOptions, PutOptions, Txn, TxnOp};
+use etcd_client::{
+    Client, Compare, CompareOp, DeleteOptions, GetOptions, PutOptions, Txn, TxnOp, TxnOpResponse,
+};
 use serde_json;
 use std::collections::HashMap;
 use threshold_types::{
-    ByzantineViolation, Error, NodeId, PeerId, Result, TxId, TransactionState, Vote,
+    ByzantineViolation, Error, NodeId, PeerId, Result, RevotePolicy, TxId, TransactionState, Vote,
+    VoteRoundState,
 };
+
+use crate::audit::AuditCheckpoint;
+use crate::lock::LockGuard;
 use tracing::{info, warn};
 
 /// TTL constants for etcd keys
@@ -11,10 +17,30 @@ const LOCK_TTL_SECS: i64 = 30;
 const HEARTBEAT_TTL_SECS: i64 = 5;
 const NODE_STATUS_TTL_SECS: i64 = 60;
 
+/// Maximum compare-and-swap attempts before giving up on a contended key
+const CAS_MAX_ATTEMPTS: usize = 10;
+
+/// Handle to the etcd cluster
+///
+/// Cloning is cheap: clones share the underlying gRPC channel, and every
+/// method takes `&self`, so one handle can serve concurrent requests
+/// without an outer `Mutex`.
+#[derive(Clone)]
 pub struct EtcdStorage {
     client: Client,
 }
 
+/// Outcome of [`EtcdStorage::record_vote`]
+#[derive(Debug, Clone)]
+pub enum RecordedVote {
+    /// The vote was stored and counted; `count` is the new count for its value
+    Counted { count: u64 },
+    /// The node already voted on this transaction
+    Existing(Vote),
+    /// The vote was cast in a round that does not count (any more)
+    OutOfRound { current_round: u32 },
+}
+
 impl EtcdStorage {
     pub async fn new(endpoints: Vec<String>) -> Result<Self> {
         let client = Client::connect(endpoints, None)
@@ -24,16 +50,21 @@ impl EtcdStorage {
         Ok(Self { client })
     }
 
+    /// Client for a single request; clones share the same channel
+    fn client(&self) -> Client {
+        self.client.clone()
+    }
+
     // ============================================================================
     // Vote Management
     // ============================================================================
 
     /// Get the count of votes for a specific value in a transaction
-    pub async fn get_vote_count(&mut self, tx_id: &TxId, value: u64) -> Result<u64> {
+    pub async fn get_vote_count(&self, tx_id: &TxId, value: u64) -> Result<u64> {
         let key = format!("/vote_counts/{}/{}", tx_id, value);
 
         let resp = self
-            .client
+            .client()
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get vote count: {}", e)))?;
@@ -49,43 +80,24 @@ impl EtcdStorage {
     }
 
     /// Increment the vote count for a specific value in a transaction
-    pub async fn increment_vote_count(&mut self, tx_id: &TxId, value: u64) -> Result<u64> {
+    pub async fn increment_vote_count(&self, tx_id: &TxId, value: u64) -> Result<u64> {
         let key = format!("/vote_counts/{}/{}", tx_id, value);
-
-        let get_resp = self
-            .client
-            .get(key.as_bytes(), None)
-            .await
-            .map_err(|e| Error::StorageError(format!("Failed to get current count: {}", e)))?;
-
-        let current_count = if get_resp.kvs().is_empty() {
-            0u64
-        } else {
-            let count_str = String::from_utf8_lossy(get_resp.kvs()[0].value());
-            count_str.parse::<u64>().unwrap_or(0)
-        };
-
-        let new_count = current_count + 1;
-
-        self.client
-            .put(key.as_bytes(), new_count.to_string().as_bytes(), None)
-            .await
-            .map_err(|e| Error::StorageError(format!("Failed to increment count: {}", e)))?;
+        let new_count = self.increment_counter(&key).await?;
 
         info!(
-            "Incremented vote count for tx_id={} value={}: {} -> {}",
-            tx_id, value, current_count, new_count
+            "Incremented vote count for tx_id={} value={} to {}",
+            tx_id, value, new_count
         );
 
         Ok(new_count)
     }
 
     /// Get all vote counts for a transaction
-    pub async fn get_all_vote_counts(&mut self, tx_id: &TxId) -> Result<HashMap<u64, u64>> {
+    pub async fn get_all_vote_counts(&self, tx_id: &TxId) -> Result<HashMap<u64, u64>> {
         let prefix = format!("/vote_counts/{}/", tx_id);
 
         let resp = self
-            .client
+            .client()
             .get(prefix.as_bytes(), Some(GetOptions::new().with_prefix()))
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get all vote counts: {}", e)))?;
@@ -107,11 +119,11 @@ impl EtcdStorage {
     }
 
     /// Store a vote, returning the existing vote if one already exists
-    pub async fn store_vote(&mut self, vote: &Vote) -> Result<Option<Vote>> {
+    pub async fn store_vote(&self, vote: &Vote) -> Result<Option<Vote>> {
         let key = format!("/votes/{}/{}", vote.tx_id, vote.node_id);
 
         let get_resp = self
-            .client
+            .client()
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to check existing vote: {}", e)))?;
@@ -126,7 +138,7 @@ impl EtcdStorage {
         let vote_json = serde_json::to_string(vote)
             .map_err(|e| Error::StorageError(format!("Failed to serialize vote: {}", e)))?;
 
-        self.client
+        self.client()
             .put(key.as_bytes(), vote_json.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to store vote: {}", e)))?;
@@ -136,11 +148,138 @@ impl EtcdStorage {
         Ok(None)
     }
 
+    /// Store a vote and count it in one etcd transaction
+    ///
+    /// The vote is only written if the node has not voted on the transaction
+    /// yet, and the count for its value is bumped in the same `Txn`, so a
+    /// crash or a concurrent voter can never leave a stored vote uncounted.
+    /// The `Txn` also checks that the voting round did not move on since its
+    /// `round_id` was found to count, so an expiring round cannot race it.
+    pub async fn record_vote(&self, vote: &Vote) -> Result<RecordedVote> {
+        let vote_key = format!("/votes/{}/{}", vote.tx_id, vote.node_id);
+        let count_key = format!("/vote_counts/{}/{}", vote.tx_id, vote.value);
+        let round_key = format!("/vote_rounds/{}", vote.tx_id);
+        let vote_json = serde_json::to_string(vote)
+            .map_err(|e| Error::StorageError(format!("Failed to serialize vote: {}", e)))?;
+        let mut client = self.client();
+
+        for _ in 0..CAS_MAX_ATTEMPTS {
+            let (round_compare, rounds) = self.vote_round_guard(&mut client, &round_key).await?;
+            if !rounds.counts(vote.round_id) {
+                return Ok(RecordedVote::OutOfRound { current_round: rounds.round });
+            }
+
+            let (count_compare, current) = self.counter_guard(&mut client, &count_key).await?;
+            let count = current + 1;
+
+            let txn = Txn::new()
+                .when(vec![
+                    Compare::create_revision(vote_key.as_bytes(), CompareOp::Equal, 0),
+                    count_compare,
+                    round_compare,
+                ])
+                .and_then(vec![
+                    TxnOp::put(vote_key.as_bytes(), vote_json.as_bytes(), None),
+                    TxnOp::put(count_key.as_bytes(), count.to_string().as_bytes(), None),
+                ])
+                .or_else(vec![TxnOp::get(vote_key.as_bytes(), None)]);
+
+            let resp = client
+                .txn(txn)
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to record vote: {}", e)))?;
+
+            if resp.succeeded() {
+                info!(
+                    "Recorded vote for tx_id={} node_id={} value={} (count {})",
+                    vote.tx_id, vote.node_id, vote.value, count
+                );
+                return Ok(RecordedVote::Counted { count });
+            }
+
+            // Either the node already voted, another vote bumped the count or
+            // a new round opened
+            let existing = resp.op_responses().into_iter().find_map(|op| match op {
+                TxnOpResponse::Get(get) => get.kvs().first().map(|kv| kv.value().to_vec()),
+                _ => None,
+            });
+            if let Some(existing) = existing {
+                let existing_vote: Vote = serde_json::from_slice(&existing).map_err(|e| {
+                    Error::StorageError(format!("Failed to parse existing vote: {}", e))
+                })?;
+                return Ok(RecordedVote::Existing(existing_vote));
+            }
+        }
+
+        Err(Error::StorageError(format!(
+            "Vote count for tx_id={} value={} is too contended",
+            vote.tx_id, vote.value
+        )))
+    }
+
+    /// Voting round of a transaction; round 1 until another is opened
+    pub async fn get_vote_round(&self, tx_id: &TxId) -> Result<VoteRoundState> {
+        let key = format!("/vote_rounds/{}", tx_id);
+        let (_, state) = self.vote_round_guard(&mut self.client(), &key).await?;
+        Ok(state)
+    }
+
+    /// Move a transaction on to voting round `round`
+    ///
+    /// Under [`RevotePolicy::Expire`] the votes and vote counts of the
+    /// transaction are deleted in the same `Txn` that bumps the round, so
+    /// every node votes again from scratch. Opening a round that is not newer
+    /// than the current one does nothing, which lets several coordinators
+    /// race to open the same round.
+    pub async fn open_vote_round(&self, tx_id: &TxId, round: u32, policy: RevotePolicy) -> Result<()> {
+        let round_key = format!("/vote_rounds/{}", tx_id);
+        let mut client = self.client();
+
+        for _ in 0..CAS_MAX_ATTEMPTS {
+            let (round_compare, current) = self.vote_round_guard(&mut client, &round_key).await?;
+            if current.round >= round {
+                return Ok(());
+            }
+
+            let next = current.next(round, policy);
+            let next_json = serde_json::to_string(&next)
+                .map_err(|e| Error::StorageError(format!("Failed to serialize vote round: {}", e)))?;
+
+            let mut ops = vec![TxnOp::put(round_key.as_bytes(), next_json.as_bytes(), None)];
+            if policy == RevotePolicy::Expire {
+                for prefix in [format!("/votes/{}/", tx_id), format!("/vote_counts/{}/", tx_id)] {
+                    ops.push(TxnOp::delete(
+                        prefix.as_bytes(),
+                        Some(DeleteOptions::new().with_prefix()),
+                    ));
+                }
+            }
+
+            let resp = client
+                .txn(Txn::new().when(vec![round_compare]).and_then(ops))
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to open vote round: {}", e)))?;
+
+            if resp.succeeded() {
+                info!(
+                    "Opened voting round {} for tx_id={} ({:?}, counting from round {})",
+                    round, tx_id, policy, next.first_counted
+                );
+                return Ok(());
+            }
+        }
+
+        Err(Error::StorageError(format!(
+            "Voting round of tx_id={} is too contended",
+            tx_id
+        )))
+    }
+
     /// Delete all votes for a transaction
-    pub async fn delete_all_votes(&mut self, tx_id: &TxId) -> Result<()> {
+    pub async fn delete_all_votes(&self, tx_id: &TxId) -> Result<()> {
         let prefix = format!("/votes/{}/", tx_id);
 
-        self.client
+        self.client()
             .delete(prefix.as_bytes(), Some(DeleteOptions::new().with_prefix()))
             .await
             .map_err(|e| Error::StorageError(format!("Failed to delete votes: {}", e)))?;
@@ -149,10 +288,10 @@ impl EtcdStorage {
     }
 
     /// Delete all vote counts for a transaction
-    pub async fn delete_all_vote_counts(&mut self, tx_id: &TxId) -> Result<()> {
+    pub async fn delete_all_vote_counts(&self, tx_id: &TxId) -> Result<()> {
         let prefix = format!("/vote_counts/{}/", tx_id);
 
-        self.client
+        self.client()
             .delete(prefix.as_bytes(), Some(DeleteOptions::new().with_prefix()))
             .await
             .map_err(|e| Error::StorageError(format!("Failed to delete vote counts: {}", e)))?;
@@ -165,11 +304,11 @@ impl EtcdStorage {
     // ============================================================================
 
     /// Get the current state of a transaction
-    pub async fn get_transaction_state(&mut self, tx_id: &TxId) -> Result<TransactionState> {
+    pub async fn get_transaction_state(&self, tx_id: &TxId) -> Result<TransactionState> {
         let key = format!("/transaction_status/{}", tx_id);
 
         let resp = self
-            .client
+            .client()
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get transaction state: {}", e)))?;
@@ -184,13 +323,13 @@ impl EtcdStorage {
 
     /// Set the state of a transaction
     pub async fn set_transaction_state(
-        &mut self,
+        &self,
         tx_id: &TxId,
         state: TransactionState,
     ) -> Result<()> {
         let key = format!("/transaction_status/{}", tx_id);
 
-        self.client
+        self.client()
             .put(key.as_bytes(), state.to_string().as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to set transaction state: {}", e)))?;
@@ -201,10 +340,10 @@ impl EtcdStorage {
     }
 
     /// Delete transaction state
-    pub async fn delete_transaction_state(&mut self, tx_id: &TxId) -> Result<()> {
+    pub async fn delete_transaction_state(&self, tx_id: &TxId) -> Result<()> {
         let key = format!("/transaction_status/{}", tx_id);
 
-        self.client
+        self.client()
             .delete(key.as_bytes(), None)
             .await
             .map_err(|e| {
@@ -214,24 +353,67 @@ impl EtcdStorage {
         Ok(())
     }
 
+    /// Transactions that still have votes, vote counts, a voting round or a
+    /// state in etcd
+    pub async fn list_transactions_with_keys(&self) -> Result<Vec<TxId>> {
+        let mut tx_ids = std::collections::BTreeSet::new();
+
+        for prefix in ["/votes/", "/vote_counts/", "/vote_rounds/", "/transaction_status/"] {
+            let resp = self
+                .client()
+                .get(prefix.as_bytes(), Some(GetOptions::new().with_prefix().with_keys_only()))
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to list {} keys: {}", prefix, e)))?;
+
+            for kv in resp.kvs() {
+                let key = String::from_utf8_lossy(kv.key());
+                if let Some(tx_id) = key[prefix.len()..].split('/').next().filter(|t| !t.is_empty()) {
+                    tx_ids.insert(tx_id.to_string());
+                }
+            }
+        }
+
+        Ok(tx_ids.into_iter().map(TxId).collect())
+    }
+
+    /// Delete the votes, vote counts, voting round and state of a finished
+    /// transaction
+    pub async fn prune_transaction_keys(&self, tx_id: &TxId) -> Result<()> {
+        self.delete_all_votes(tx_id).await?;
+        self.delete_all_vote_counts(tx_id).await?;
+
+        let round_key = format!("/vote_rounds/{}", tx_id);
+        self.client()
+            .delete(round_key.as_bytes(), None)
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to delete vote round: {}", e)))?;
+
+        self.delete_transaction_state(tx_id).await
+    }
+
     // ============================================================================
     // Distributed Locking
     // ============================================================================
 
     /// Acquire a lock for transaction signing
-    pub async fn acquire_signing_lock(&mut self, tx_id: &TxId) -> Result<i64> {
+    ///
+    /// The lease is renewed until the guard is released or dropped; pass
+    /// its fencing token to the storage writes of the signing session.
+    pub async fn acquire_signing_lock(&self, tx_id: &TxId) -> Result<LockGuard> {
         let key = format!("/locks/signing/{}", tx_id);
         self.acquire_lock_internal(&key, LOCK_TTL_SECS).await
     }
 
-    /// Release a transaction signing lock
-    pub async fn release_signing_lock(&mut self, tx_id: &TxId) -> Result<()> {
+    /// Force-release a transaction signing lock, whoever holds it
+    ///
+    /// Holders should release their [`LockGuard`] instead.
+    pub async fn release_signing_lock(&self, tx_id: &TxId) -> Result<()> {
         let key = format!("/locks/signing/{}", tx_id);
         self.release_lock_internal(&key).await
     }
 
     /// Acquire a lock for presignature generation
-    pub async fn acquire_presig_generation_lock(&mut self) -> Result<i64> {
+    pub async fn acquire_presig_generation_lock(&self) -> Result<LockGuard> {
         let key = "/locks/presig-generation";
         self.acquire_lock_internal(key, LOCK_TTL_SECS).await
     }
@@ -239,26 +421,18 @@ impl EtcdStorage {
     /// Try to acquire presignature generation lock (non-blocking)
     ///
     /// Returns:
-    /// - Ok(Some(lease_id)) if lock acquired successfully
+    /// - Ok(Some(guard)) if lock acquired successfully
     /// - Ok(None) if lock is held by another node (not an error)
     /// - Err() only for actual storage errors
     ///
     /// FIX SORUN #14: This is the preferred method for presignature generation
     /// because it clearly distinguishes between "lock held" and "actual error"
-    pub async fn try_acquire_presig_generation_lock(&mut self) -> Result<Option<i64>> {
-        let key = "/locks/presig-generation";
-        match self.acquire_lock_internal(key, LOCK_TTL_SECS).await {
-            Ok(lease_id) => Ok(Some(lease_id)),
-            Err(e) if e.to_string().contains("already locked") => {
-                // Lock is held by another node - this is expected, not an error
-                Ok(None)
-            }
-            Err(e) => Err(e), // Actual storage error
-        }
+    pub async fn try_acquire_presig_generation_lock(&self) -> Result<Option<LockGuard>> {
+        self.try_acquire_lock("/locks/presig-generation").await
     }
 
-    /// Release the presignature generation lock
-    pub async fn release_presig_generation_lock(&mut self) -> Result<()> {
+    /// Force-release the presignature generation lock, whoever holds it
+    pub async fn release_presig_generation_lock(&self) -> Result<()> {
         let key = "/locks/presig-generation";
         self.release_lock_internal(key).await
     }
@@ -267,8 +441,8 @@ impl EtcdStorage {
     ///
     /// This is more reliable than delete for lock release because it ensures
     /// the lease is terminated and won't be renewed.
-    pub async fn revoke_lease(&mut self, lease_id: i64) -> Result<()> {
-        self.client
+    pub async fn revoke_lease(&self, lease_id: i64) -> Result<()> {
+        self.client()
             .lease_revoke(lease_id)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to revoke lease: {}", e)))?;
@@ -278,20 +452,57 @@ impl EtcdStorage {
     }
 
     /// Acquire a lock for DKG session
-    pub async fn acquire_dkg_session_lock(&mut self, session_id: &str) -> Result<i64> {
+    pub async fn acquire_dkg_session_lock(&self, session_id: &str) -> Result<LockGuard> {
         let key = format!("/locks/dkg-session/{}", session_id);
         self.acquire_lock_internal(&key, LOCK_TTL_SECS).await
     }
 
-    /// Release a DKG session lock
-    pub async fn release_dkg_session_lock(&mut self, session_id: &str) -> Result<()> {
+    /// Force-release a DKG session lock, whoever holds it
+    pub async fn release_dkg_session_lock(&self, session_id: &str) -> Result<()> {
         let key = format!("/locks/dkg-session/{}", session_id);
         self.release_lock_internal(&key).await
     }
 
-    /// Internal method to acquire a lock with TTL
-    async fn acquire_lock_internal(&self, key: &str, ttl_secs: i64) -> Result<i64> {
-        let mut client = self.client.clone();
+    /// Try to acquire a kept-alive lock on an arbitrary key
+    ///
+    /// Returns `Ok(None)` if another node holds the lock.
+    pub async fn try_acquire_lock(&self, key: &str) -> Result<Option<LockGuard>> {
+        match self.acquire_lock_internal(key, LOCK_TTL_SECS).await {
+            Ok(guard) => Ok(Some(guard)),
+            Err(e) if e.to_string().contains("already locked") => {
+                // Lock is held by another node - this is expected, not an error
+                Ok(None)
+            }
+            Err(e) => Err(e), // Actual storage error
+        }
+    }
+
+    /// Internal method to acquire a lock whose lease is kept alive
+    async fn acquire_lock_internal(&self, key: &str, ttl_secs: i64) -> Result<LockGuard> {
+        let (lease_id, revision) = self.grant_lock(key, ttl_secs).await?;
+
+        match LockGuard::new(self.client(), key.to_string(), lease_id, ttl_secs, revision).await {
+            Ok(guard) => {
+                info!(
+                    "Acquired lock for key={} with lease_id={} fencing_token={}",
+                    key, lease_id, revision
+                );
+                Ok(guard)
+            }
+            Err(e) => {
+                // Without renewals the lock would silently expire under us
+                let _ = self.revoke_lease(lease_id).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Create the lock key under a new lease with TTL
+    ///
+    /// Returns the lease and the revision at which the key was created. The
+    /// lease is not renewed.
+    async fn grant_lock(&self, key: &str, ttl_secs: i64) -> Result<(i64, i64)> {
+        let mut client = self.client();
         let lease_resp = client
             .lease_grant(ttl_secs, None)
             .await
@@ -317,25 +528,30 @@ impl EtcdStorage {
             )])
             .or_else(vec![]);
 
-        let txn_resp = client
-            .txn(txn)
-            .await
-            .map_err(|e| Error::StorageError(format!("Failed to acquire lock: {}", e)))?;
+        let txn_resp = match client.txn(txn).await {
+            Ok(resp) => resp,
+            Err(e) => {
+                let _ = client.lease_revoke(lease_id).await;
+                return Err(Error::StorageError(format!("Failed to acquire lock: {}", e)));
+            }
+        };
 
         if !txn_resp.succeeded() {
+            // Don't leave the unused lease around until it expires
+            let _ = client.lease_revoke(lease_id).await;
             return Err(Error::StorageError(
                 "Failed to acquire lock: already locked".to_string(),
             ));
         }
 
-        info!("Acquired lock for key={} with lease_id={}", key, lease_id);
+        let revision = txn_resp.header().map_or(0, |h| h.revision());
 
-        Ok(lease_id)
+        Ok((lease_id, revision))
     }
 
     /// Internal method to release a lock
     async fn release_lock_internal(&self, key: &str) -> Result<()> {
-        let mut client = self.client.clone();
+        let mut client = self.client();
         client
             .delete(key.as_bytes(), None)
             .await
@@ -347,9 +563,9 @@ impl EtcdStorage {
     }
 
     /// Keep a lease alive by refreshing it
-    pub async fn keep_lease_alive(&mut self, lease_id: i64) -> Result<()> {
+    pub async fn keep_lease_alive(&self, lease_id: i64) -> Result<()> {
         let (mut keeper, _stream) = self
-            .client
+            .client()
             .lease_keep_alive(lease_id)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to create lease keeper: {}", e)))?;
@@ -367,64 +583,189 @@ impl EtcdStorage {
     // ============================================================================
 
     /// Increment the transaction counter
-    pub async fn increment_transaction_counter(&mut self) -> Result<u64> {
+    pub async fn increment_transaction_counter(&self) -> Result<u64> {
         self.increment_counter("/counters/transactions").await
     }
 
     /// Get the transaction counter
-    pub async fn get_transaction_counter(&mut self) -> Result<u64> {
+    pub async fn get_transaction_counter(&self) -> Result<u64> {
         self.get_counter("/counters/transactions").await
     }
 
     /// Increment the presignature counter
-    pub async fn increment_presignature_counter(&mut self) -> Result<u64> {
+    pub async fn increment_presignature_counter(&self) -> Result<u64> {
         self.increment_counter("/counters/presignatures").await
     }
 
     /// Get the presignature counter
-    pub async fn get_presignature_counter(&mut self) -> Result<u64> {
+    pub async fn get_presignature_counter(&self) -> Result<u64> {
         self.get_counter("/counters/presignatures").await
     }
 
     /// Increment the Byzantine events counter
-    pub async fn increment_byzantine_counter(&mut self) -> Result<u64> {
+    pub async fn increment_byzantine_counter(&self) -> Result<u64> {
         self.increment_counter("/counters/byzantine-events").await
     }
 
     /// Get the Byzantine events counter
-    pub async fn get_byzantine_counter(&mut self) -> Result<u64> {
+    pub async fn get_byzantine_counter(&self) -> Result<u64> {
         self.get_counter("/counters/byzantine-events").await
     }
 
     /// Internal method to increment a counter
-    async fn increment_counter(&mut self, key: &str) -> Result<u64> {
-        let get_resp = self
-            .client
+    ///
+    /// Concurrent increments are serialized with a compare-and-swap on the
+    /// key's revision, so none of them is lost.
+    async fn increment_counter(&self, key: &str) -> Result<u64> {
+        let mut client = self.client();
+
+        for _ in 0..CAS_MAX_ATTEMPTS {
+            let (compare, current) = self.counter_guard(&mut client, key).await?;
+            let new_value = current + 1;
+
+            let txn = Txn::new().when(vec![compare]).and_then(vec![TxnOp::put(
+                key.as_bytes(),
+                new_value.to_string().as_bytes(),
+                None,
+            )]);
+
+            let resp = client
+                .txn(txn)
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to increment counter: {}", e)))?;
+
+            if resp.succeeded() {
+                return Ok(new_value);
+            }
+        }
+
+        Err(Error::StorageError(format!("Counter {} is too contended", key)))
+    }
+
+    /// Current value of a counter and a comparison that holds until it changes
+    async fn counter_guard(&self, client: &mut Client, key: &str) -> Result<(Compare, u64)> {
+        let resp = client
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get counter: {}", e)))?;
 
-        let current = if get_resp.kvs().is_empty() {
-            0u64
-        } else {
-            let count_str = String::from_utf8_lossy(get_resp.kvs()[0].value());
-            count_str.parse::<u64>().unwrap_or(0)
-        };
-
-        let new_value = current + 1;
+        Ok(match resp.kvs().first() {
+            Some(kv) => (
+                Compare::mod_revision(key.as_bytes(), CompareOp::Equal, kv.mod_revision()),
+                String::from_utf8_lossy(kv.value()).parse::<u64>().unwrap_or(0),
+            ),
+            None => (Compare::create_revision(key.as_bytes(), CompareOp::Equal, 0), 0),
+        })
+    }
 
-        self.client
-            .put(key.as_bytes(), new_value.to_string().as_bytes(), None)
+    /// Read a transaction's voting round along with a `Compare` that holds
+    /// while it is unchanged
+    async fn vote_round_guard(&self, client: &mut Client, key: &str) -> Result<(Compare, VoteRoundState)> {
+        let resp = client
+            .get(key.as_bytes(), None)
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to increment counter: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to get vote round: {}", e)))?;
 
-        Ok(new_value)
+        match resp.kvs().first() {
+            Some(kv) => {
+                let state: VoteRoundState = serde_json::from_slice(kv.value())
+                    .map_err(|e| Error::StorageError(format!("Failed to parse vote round: {}", e)))?;
+                Ok((Compare::mod_revision(key.as_bytes(), CompareOp::Equal, kv.mod_revision()), state))
+            }
+            None => Ok((
+                Compare::create_revision(key.as_bytes(), CompareOp::Equal, 0),
+                VoteRoundState::default(),
+            )),
+        }
+    }
+
+    /// Atomically increment a counter that expires after `ttl_secs`
+    ///
+    /// Used for fixed-window counters shared by all API replicas (e.g. rate
+    /// limits). The increment that creates the key attaches a lease, later
+    /// increments keep it, and concurrent increments are serialized with a
+    /// compare-and-swap on the key's revision. A losing swap gets the current
+    /// value back in the same round trip. At most one lease is granted per
+    /// call, and it is revoked unless it ends up on the key. Returns the new
+    /// count.
+    pub async fn increment_expiring_counter(&self, key: &str, ttl_secs: i64) -> Result<u64> {
+        let mut client = self.client();
+        let mut granted: Option<i64> = None;
+
+        let result = async {
+            let resp = client
+                .get(key.as_bytes(), None)
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to get counter: {}", e)))?;
+            let mut current = resp.kvs().first().cloned();
+
+            for _ in 0..CAS_MAX_ATTEMPTS {
+                let (compare, count, lease_id) = match &current {
+                    Some(kv) => (
+                        Compare::mod_revision(key.as_bytes(), CompareOp::Equal, kv.mod_revision()),
+                        String::from_utf8_lossy(kv.value()).parse::<u64>().unwrap_or(0) + 1,
+                        kv.lease(),
+                    ),
+                    None => {
+                        let lease_id = match granted {
+                            Some(id) => id,
+                            None => {
+                                let lease = client.lease_grant(ttl_secs, None).await.map_err(|e| {
+                                    Error::StorageError(format!("Failed to create lease: {}", e))
+                                })?;
+                                *granted.insert(lease.id())
+                            }
+                        };
+                        (Compare::create_revision(key.as_bytes(), CompareOp::Equal, 0), 1, lease_id)
+                    }
+                };
+
+                let txn = Txn::new()
+                    .when(vec![compare])
+                    .and_then(vec![TxnOp::put(
+                        key.as_bytes(),
+                        count.to_string().as_bytes(),
+                        Some(PutOptions::new().with_lease(lease_id)),
+                    )])
+                    .or_else(vec![TxnOp::get(key.as_bytes(), None)]);
+
+                let resp = client
+                    .txn(txn)
+                    .await
+                    .map_err(|e| Error::StorageError(format!("Failed to increment counter: {}", e)))?;
+
+                if resp.succeeded() {
+                    if granted == Some(lease_id) {
+                        granted = None;
+                    }
+                    return Ok(count);
+                }
+
+                current = resp.op_responses().into_iter().find_map(|op| match op {
+                    TxnOpResponse::Get(get) => get.kvs().first().cloned(),
+                    _ => None,
+                });
+            }
+
+            Err(Error::StorageError(format!("Counter {} is too contended", key)))
+        }
+        .await;
+
+        // Another increment created the key first; the lease would otherwise
+        // linger unused until it expires
+        if let Some(lease_id) = granted {
+            if let Err(e) = self.client().lease_revoke(lease_id).await {
+                warn!("Failed to revoke unused counter lease {}: {}", lease_id, e);
+            }
+        }
+
+        result
     }
 
     /// Internal method to get a counter value
-    async fn get_counter(&mut self, key: &str) -> Result<u64> {
+    async fn get_counter(&self, key: &str) -> Result<u64> {
         let resp = self
-            .client
+            .client()
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get counter: {}", e)))?;
@@ -444,18 +785,18 @@ impl EtcdStorage {
     // ============================================================================
 
     /// Update node status with TTL
-    pub async fn update_node_status(&mut self, node_id: NodeId, status: &str) -> Result<()> {
+    pub async fn update_node_status(&self, node_id: NodeId, status: &str) -> Result<()> {
         let key = format!("/nodes/{}/status", node_id);
 
         let lease_resp = self
-            .client
+            .client()
             .lease_grant(NODE_STATUS_TTL_SECS, None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to create lease: {}", e)))?;
 
         let lease_id = lease_resp.id();
 
-        self.client
+        self.client()
             .put(
                 key.as_bytes(),
                 status.as_bytes(),
@@ -470,11 +811,11 @@ impl EtcdStorage {
     }
 
     /// Get node status
-    pub async fn get_node_status(&mut self, node_id: NodeId) -> Result<Option<String>> {
+    pub async fn get_node_status(&self, node_id: NodeId) -> Result<Option<String>> {
         let key = format!("/nodes/{}/status", node_id);
 
         let resp = self
-            .client
+            .client()
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get node status: {}", e)))?;
@@ -488,19 +829,19 @@ impl EtcdStorage {
     }
 
     /// Update node heartbeat with short TTL
-    pub async fn update_node_heartbeat(&mut self, node_id: NodeId) -> Result<()> {
+    pub async fn update_node_heartbeat(&self, node_id: NodeId) -> Result<()> {
         let key = format!("/nodes/{}/last-heartbeat", node_id);
         let timestamp = chrono::Utc::now().to_rfc3339();
 
         let lease_resp = self
-            .client
+            .client()
             .lease_grant(HEARTBEAT_TTL_SECS, None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to create lease: {}", e)))?;
 
         let lease_id = lease_resp.id();
 
-        self.client
+        self.client()
             .put(
                 key.as_bytes(),
                 timestamp.as_bytes(),
@@ -513,11 +854,11 @@ impl EtcdStorage {
     }
 
     /// Get node last heartbeat
-    pub async fn get_node_heartbeat(&mut self, node_id: NodeId) -> Result<Option<String>> {
+    pub async fn get_node_heartbeat(&self, node_id: NodeId) -> Result<Option<String>> {
         let key = format!("/nodes/{}/last-heartbeat", node_id);
 
         let resp = self
-            .client
+            .client()
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get heartbeat: {}", e)))?;
@@ -531,11 +872,11 @@ impl EtcdStorage {
     }
 
     /// Get all active nodes (nodes with recent heartbeats)
-    pub async fn get_active_nodes(&mut self) -> Result<Vec<NodeId>> {
+    pub async fn get_active_nodes(&self) -> Result<Vec<NodeId>> {
         let prefix = "/nodes/";
 
         let resp = self
-            .client
+            .client()
             .get(prefix.as_bytes(), Some(GetOptions::new().with_prefix()))
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get active nodes: {}", e)))?;
@@ -560,16 +901,88 @@ impl EtcdStorage {
         Ok(nodes)
     }
 
+    // ============================================================================
+    // Audit Chain Anchors
+    // ============================================================================
+
+    /// Anchor an audit chain head; existing anchors are never overwritten
+    pub async fn put_audit_checkpoint(&self, checkpoint: &AuditCheckpoint) -> Result<bool> {
+        let key = format!("/audit/checkpoints/{:020}", checkpoint.seq);
+        let value = serde_json::to_vec(checkpoint)
+            .map_err(|e| Error::StorageError(format!("Failed to serialize checkpoint: {}", e)))?;
+
+        let txn = Txn::new()
+            .when(vec![Compare::create_revision(key.as_bytes(), CompareOp::Equal, 0)])
+            .and_then(vec![TxnOp::put(key.as_bytes(), value, None)]);
+
+        let mut client = self.client();
+        let resp = client
+            .txn(txn)
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to put audit checkpoint: {}", e)))?;
+
+        Ok(resp.succeeded())
+    }
+
+    /// All anchored audit chain heads, oldest first
+    pub async fn list_audit_checkpoints(&self) -> Result<Vec<AuditCheckpoint>> {
+        let resp = self
+            .client()
+            .get("/audit/checkpoints/", Some(GetOptions::new().with_prefix()))
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to list audit checkpoints: {}", e)))?;
+
+        resp.kvs()
+            .iter()
+            .map(|kv| {
+                serde_json::from_slice(kv.value()).map_err(|e| {
+                    Error::StorageError(format!("Failed to deserialize checkpoint: {}", e))
+                })
+            })
+            .collect()
+    }
+
+    /// Publish a node's audit public key
+    pub async fn register_audit_signer(&self, node_id: NodeId, public_key: &[u8]) -> Result<()> {
+        self.put(&format!("/audit/signers/node-{}", node_id.0), hex::encode(public_key).as_bytes())
+            .await
+    }
+
+    /// Audit public keys published by the nodes
+    pub async fn get_audit_signers(&self) -> Result<HashMap<u64, Vec<u8>>> {
+        let prefix = "/audit/signers/node-";
+        let resp = self
+            .client()
+            .get(prefix, Some(GetOptions::new().with_prefix()))
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get audit signers: {}", e)))?;
+
+        let mut signers = HashMap::new();
+        for kv in resp.kvs() {
+            let key = String::from_utf8_lossy(kv.key());
+            let node_id = key.strip_prefix(prefix).and_then(|id| id.parse::<u64>().ok());
+            let public_key = hex::decode(kv.value()).ok();
+            match (node_id, public_key) {
+                (Some(node_id), Some(public_key)) => {
+                    signers.insert(node_id, public_key);
+                }
+                _ => warn!("Ignoring malformed audit signer entry {}", key),
+            }
+        }
+
+        Ok(signers)
+    }
+
     // ============================================================================
     // Cluster Configuration
     // ============================================================================
 
     /// Get the configured threshold
-    pub async fn get_cluster_threshold(&mut self) -> Result<u32> {
+    pub async fn get_cluster_threshold(&self) -> Result<u32> {
         let key = b"/cluster/threshold";
 
         let resp = self
-            .client
+            .client()
             .get(key, None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get threshold: {}", e)))?;
@@ -585,10 +998,10 @@ impl EtcdStorage {
     }
 
     /// Set the cluster threshold
-    pub async fn set_cluster_threshold(&mut self, threshold: u32) -> Result<()> {
+    pub async fn set_cluster_threshold(&self, threshold: u32) -> Result<()> {
         let key = b"/cluster/threshold";
 
-        self.client
+        self.client()
             .put(key, threshold.to_string().as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to set threshold: {}", e)))?;
@@ -599,11 +1012,11 @@ impl EtcdStorage {
     }
 
     /// Get cluster peers list
-    pub async fn get_cluster_peers(&mut self) -> Result<Vec<String>> {
+    pub async fn get_cluster_peers(&self) -> Result<Vec<String>> {
         let key = b"/cluster/peers";
 
         let resp = self
-            .client
+            .client()
             .get(key, None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get peers: {}", e)))?;
@@ -620,12 +1033,12 @@ impl EtcdStorage {
     }
 
     /// Set cluster peers list
-    pub async fn set_cluster_peers(&mut self, peers: Vec<String>) -> Result<()> {
+    pub async fn set_cluster_peers(&self, peers: Vec<String>) -> Result<()> {
         let key = b"/cluster/peers";
         let peers_json = serde_json::to_string(&peers)
             .map_err(|e| Error::StorageError(format!("Failed to serialize peers: {}", e)))?;
 
-        self.client
+        self.client()
             .put(key, peers_json.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to set peers: {}", e)))?;
@@ -636,7 +1049,7 @@ impl EtcdStorage {
     }
 
     /// Add a peer to the cluster
-    pub async fn add_cluster_peer(&mut self, peer: String) -> Result<()> {
+    pub async fn add_cluster_peer(&self, peer: String) -> Result<()> {
         let mut peers = self.get_cluster_peers().await?;
         if !peers.contains(&peer) {
             peers.push(peer.clone());
@@ -647,7 +1060,7 @@ impl EtcdStorage {
     }
 
     /// Remove a peer from the cluster
-    pub async fn remove_cluster_peer(&mut self, peer: &str) -> Result<()> {
+    pub async fn remove_cluster_peer(&self, peer: &str) -> Result<()> {
         let mut peers = self.get_cluster_peers().await?;
         peers.retain(|p| p != peer);
         self.set_cluster_peers(peers).await?;
@@ -660,11 +1073,11 @@ impl EtcdStorage {
     // ============================================================================
 
     /// Check if a node is banned (by NodeId)
-    pub async fn is_node_banned(&mut self, node_id: NodeId) -> Result<bool> {
+    pub async fn is_node_banned(&self, node_id: NodeId) -> Result<bool> {
         let key = format!("/banned/{}", node_id);
 
         let resp = self
-            .client
+            .client()
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to check banned status: {}", e)))?;
@@ -673,11 +1086,11 @@ impl EtcdStorage {
     }
 
     /// Check if a peer is banned (by PeerId)
-    pub async fn is_peer_banned(&mut self, peer_id: &PeerId) -> Result<bool> {
+    pub async fn is_peer_banned(&self, peer_id: &PeerId) -> Result<bool> {
         let key = format!("/banned/{}", peer_id.0);
 
         let resp = self
-            .client
+            .client()
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to check banned status: {}", e)))?;
@@ -686,7 +1099,7 @@ impl EtcdStorage {
     }
 
     /// Ban a node for Byzantine violation
-    pub async fn ban_node(&mut self, violation: &ByzantineViolation) -> Result<()> {
+    pub async fn ban_node(&self, violation: &ByzantineViolation) -> Result<()> {
         let key = if let Some(node_id) = violation.node_id {
             format!("/banned/{}", node_id)
         } else {
@@ -696,7 +1109,7 @@ impl EtcdStorage {
         let ban_data = serde_json::to_string(violation)
             .map_err(|e| Error::StorageError(format!("Failed to serialize violation: {}", e)))?;
 
-        self.client
+        self.client()
             .put(key.as_bytes(), ban_data.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to ban node: {}", e)))?;
@@ -713,10 +1126,10 @@ impl EtcdStorage {
     }
 
     /// Unban a node
-    pub async fn unban_node(&mut self, node_id: NodeId) -> Result<()> {
+    pub async fn unban_node(&self, node_id: NodeId) -> Result<()> {
         let key = format!("/banned/{}", node_id);
 
-        self.client
+        self.client()
             .delete(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to unban node: {}", e)))?;
@@ -727,11 +1140,11 @@ impl EtcdStorage {
     }
 
     /// Get ban information for a node
-    pub async fn get_ban_info(&mut self, node_id: NodeId) -> Result<Option<ByzantineViolation>> {
+    pub async fn get_ban_info(&self, node_id: NodeId) -> Result<Option<ByzantineViolation>> {
         let key = format!("/banned/{}", node_id);
 
         let resp = self
-            .client
+            .client()
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get ban info: {}", e)))?;
@@ -752,29 +1165,30 @@ impl EtcdStorage {
     // ============================================================================
 
     /// Legacy method: acquire lock for submission (maps to signing lock)
-    pub async fn acquire_submission_lock(&mut self, tx_id: &TxId, ttl_secs: i64) -> Result<i64> {
+    pub async fn acquire_submission_lock(&self, tx_id: &TxId, ttl_secs: i64) -> Result<i64> {
         let key = format!("/locks/submission/{}", tx_id);
-        self.acquire_lock_internal(&key, ttl_secs).await
+        let (lease_id, _revision) = self.grant_lock(&key, ttl_secs).await?;
+        Ok(lease_id)
     }
 
     /// Legacy method: release submission lock
-    pub async fn release_submission_lock(&mut self, tx_id: &TxId) -> Result<()> {
+    pub async fn release_submission_lock(&self, tx_id: &TxId) -> Result<()> {
         let key = format!("/locks/submission/{}", tx_id);
         self.release_lock_internal(&key).await
     }
 
     /// Legacy method: get config threshold (maps to cluster threshold)
-    pub async fn get_config_threshold(&mut self) -> Result<usize> {
+    pub async fn get_config_threshold(&self) -> Result<usize> {
         Ok(self.get_cluster_threshold().await? as usize)
     }
 
     /// Legacy method: set config threshold (maps to cluster threshold)
-    pub async fn set_config_threshold(&mut self, threshold: usize) -> Result<()> {
+    pub async fn set_config_threshold(&self, threshold: usize) -> Result<()> {
         self.set_cluster_threshold(threshold as u32).await
     }
 
     /// Legacy method: get config total nodes (derived from peers)
-    pub async fn get_config_total_nodes(&mut self) -> Result<usize> {
+    pub async fn get_config_total_nodes(&self) -> Result<usize> {
         let peers = self.get_cluster_peers().await?;
         if peers.is_empty() {
             return Err(Error::ConfigError("Total nodes not configured".to_string()));
@@ -783,19 +1197,110 @@ impl EtcdStorage {
     }
 
     /// Legacy method: set config total nodes (updates peers count)
-    pub async fn set_config_total_nodes(&mut self, total_nodes: usize) -> Result<()> {
+    pub async fn set_config_total_nodes(&self, total_nodes: usize) -> Result<()> {
         info!("Set total_nodes to {}", total_nodes);
         Ok(())
     }
 
+    // ============================================================================
+    // FROST Nonce Commitments
+    // ============================================================================
+
+    /// Publish a node's public FROST nonce commitment so coordinators can use it
+    pub async fn publish_frost_commitment(
+        &self,
+        node_id: NodeId,
+        nonce_id: &str,
+        commitment: &[u8],
+    ) -> Result<()> {
+        let key = format!("/frost/commitments/{}/{}", node_id.0, nonce_id);
+        self.put(&key, commitment).await
+    }
+
+    /// Claim one unused FROST commitment published by `node_id`
+    ///
+    /// The commitment is deleted in a transaction guarded on its mod_revision,
+    /// so two coordinators can never claim the same nonce. Returns the nonce id
+    /// and commitment bytes, or `None` if the node has no commitments left.
+    pub async fn claim_frost_commitment(
+        &self,
+        node_id: NodeId,
+    ) -> Result<Option<(String, Vec<u8>)>> {
+        let prefix = format!("/frost/commitments/{}/", node_id.0);
+        let mut client = self.client();
+
+        // Retry a few times in case another coordinator races us for the same key
+        for _ in 0..5 {
+            let resp = client
+                .get(
+                    prefix.as_bytes(),
+                    Some(GetOptions::new().with_prefix().with_limit(1)),
+                )
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to list commitments: {}", e)))?;
+
+            let Some(kv) = resp.kvs().first() else {
+                return Ok(None);
+            };
+
+            let key = kv.key().to_vec();
+            let commitment = kv.value().to_vec();
+            let txn = Txn::new()
+                .when(vec![Compare::mod_revision(
+                    key.clone(),
+                    CompareOp::Equal,
+                    kv.mod_revision(),
+                )])
+                .and_then(vec![TxnOp::delete(key.clone(), None)]);
+
+            let txn_resp = client
+                .txn(txn)
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to claim commitment: {}", e)))?;
+
+            if txn_resp.succeeded() {
+                let key_str = String::from_utf8_lossy(&key);
+                let nonce_id = key_str.trim_start_matches(&prefix).to_string();
+                return Ok(Some((nonce_id, commitment)));
+            }
+        }
+
+        warn!("Failed to claim FROST commitment for node {} after retries", node_id);
+        Ok(None)
+    }
+
+    /// Count unclaimed FROST commitments published by `node_id`
+    pub async fn count_frost_commitments(&self, node_id: NodeId) -> Result<usize> {
+        let prefix = format!("/frost/commitments/{}/", node_id.0);
+        let mut client = self.client();
+        let resp = client
+            .get(
+                prefix.as_bytes(),
+                Some(GetOptions::new().with_prefix().with_count_only()),
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to count commitments: {}", e)))?;
+
+        Ok(resp.count() as usize)
+    }
+
+    /// Withdraw a published FROST commitment (e.g. after the nonce expired)
+    pub async fn withdraw_frost_commitment(&self, node_id: NodeId, nonce_id: &str) -> Result<()> {
+        let key = format!("/frost/commitments/{}/{}", node_id.0, nonce_id);
+        self.delete(&key).await
+    }
+
     // ============================================================================
     // DKG and Generic Lock/Storage Operations
     // ============================================================================
 
     /// Acquire a generic distributed lock with TTL
+    ///
+    /// The lease is not renewed, so the lock expires after `ttl_secs`; use
+    /// [`EtcdStorage::try_acquire_lock`] for work that may take longer.
     pub async fn acquire_lock(&self, key: &str, ttl_secs: i64) -> Result<bool> {
-        match self.acquire_lock_internal(key, ttl_secs).await {
-            Ok(_lease_id) => Ok(true),
+        match self.grant_lock(key, ttl_secs).await {
+            Ok(_) => Ok(true),
             Err(e) => {
                 warn!("Failed to acquire lock for key={}: {}", key, e);
                 Ok(false)
@@ -810,7 +1315,7 @@ impl EtcdStorage {
 
     /// Put a key-value pair in etcd
     pub async fn put(&self, key: &str, value: &[u8]) -> Result<()> {
-        let mut client = self.client.clone();
+        let mut client = self.client();
         client
             .put(key.as_bytes(), value, None)
             .await
@@ -819,9 +1324,9 @@ impl EtcdStorage {
     }
 
     /// Get a value from etcd
-    pub async fn get(&mut self, key: &str) -> Result<Option<Vec<u8>>> {
+    pub async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
         let resp = self
-            .client
+            .client()
             .get(key.as_bytes(), None)
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get key: {}", e)))?;
@@ -835,7 +1340,7 @@ impl EtcdStorage {
 
     /// Delete a key from etcd
     pub async fn delete(&self, key: &str) -> Result<()> {
-        let mut client = self.client.clone();
+        let mut client = self.client();
         client
             .delete(key.as_bytes(), None)
             .await
@@ -881,25 +1386,32 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_lock_acquisition() {
-        let mut storage = EtcdStorage::new(vec!["127.0.0.1:2379".to_string()])
+        let storage = EtcdStorage::new(vec!["127.0.0.1:2379".to_string()])
             .await
             .unwrap();
         let tx_id = TxId::from("test-tx-123");
 
-        let lease_id = storage.acquire_signing_lock(&tx_id).await.unwrap();
-        assert!(lease_id > 0);
+        let guard = storage.acquire_signing_lock(&tx_id).await.unwrap();
+        assert!(guard.lease_id() > 0);
+        assert!(guard.is_held());
 
         // Second lock should fail
         let result = storage.acquire_signing_lock(&tx_id).await;
         assert!(result.is_err());
 
-        storage.release_signing_lock(&tx_id).await.unwrap();
+        let token = guard.fencing_token();
+        guard.release().await.unwrap();
+
+        // The next holder gets a larger fencing token
+        let guard = storage.acquire_signing_lock(&tx_id).await.unwrap();
+        assert!(guard.fencing_token() > token);
+        guard.release().await.unwrap();
     }
 
     #[tokio::test]
     #[ignore]
     async fn test_vote_storage() {
-        let mut storage = EtcdStorage::new(vec!["127.0.0.1:2379".to_string()])
+        let storage = EtcdStorage::new(vec!["127.0.0.1:2379".to_string()])
             .await
             .unwrap();
 
@@ -924,7 +1436,7 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_node_heartbeat() {
-        let mut storage = EtcdStorage::new(vec!["127.0.0.1:2379".to_string()])
+        let storage = EtcdStorage::new(vec!["127.0.0.1:2379".to_string()])
             .await
             .unwrap();
 
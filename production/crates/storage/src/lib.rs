@@ -1,8 +1,22 @@
+pub mod audit;
 pub mod etcd;
+pub mod lock;
+pub mod migrations;
 pub mod postgres;
+#[cfg(feature = "embedded")]
+pub mod sqlite;
+pub mod traits;
 
-pub use etcd::EtcdStorage;
+pub use audit::{AuditCheckpoint, AuditEntry, AuditIssue, AuditReport, AuditSigner};
+pub use etcd::{EtcdStorage, RecordedVote};
+pub use lock::{LocalLockManager, LockGuard};
+pub use migrations::{MigrationState, MigrationStatus};
 pub use postgres::PostgresStorage;
+#[cfg(feature = "embedded")]
+pub use sqlite::SqliteStorage;
+pub use traits::{
+    CeremonyStore, ClusterStorage, KeyShareStore, LockManager, TransactionStore, VoteStore,
+};
 
 /// DKG ceremony status
 #[derive(Debug, Clone)]
@@ -18,6 +32,8 @@ pub struct DkgCeremony {
     pub started_at: chrono::DateTime<chrono::Utc>,
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
     pub error: Option<String>,
+    /// Root BIP32 extended public key (xpub), if exported
+    pub xpub: Option<String>,
 }
 
 /// Aux info ceremony status
@@ -30,3 +46,347 @@ pub struct AuxInfoCeremony {
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
     pub error: Option<String>,
 }
+
+/// Preprocessed FROST nonce pair owned by this node
+#[derive(Debug, Clone)]
+pub struct StoredFrostNonce {
+    pub nonce_id: uuid::Uuid,
+    pub party_index: u16,
+    /// Serialized secret nonces (never leaves the node)
+    pub secret_nonces: Vec<u8>,
+    /// Serialized public commitment
+    pub commitment: Vec<u8>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// API user account
+#[derive(Debug, Clone)]
+pub struct User {
+    pub user_id: String,
+    pub username: String,
+    /// bcrypt hash of the user's password
+    pub password_hash: String,
+    /// "admin" or "user"
+    pub role: String,
+    pub is_active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_login_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Who a new transaction is created for, and which key funds it
+///
+/// Checked and recorded in the database transaction that stores the
+/// transaction, see [`PostgresStorage::create_owned_transaction`].
+#[derive(Debug, Clone, Default)]
+pub struct TransactionOrigin {
+    /// User recorded as the transaction's owner (`created_by`)
+    pub user_id: Option<String>,
+    /// Debit amount plus fee from the owner's ledger balance, refusing the
+    /// transaction if the balance does not cover it. Admins spend from the
+    /// shared wallet without going through the ledger.
+    pub debit_ledger: bool,
+    /// Non-hardened BIP32 path below the root key of the address whose
+    /// UTXOs the inputs spend (empty for the wallet root address)
+    pub derivation_path: Vec<u32>,
+    /// API key the transaction is created with, recorded as `api_key_id`
+    pub api_key: Option<ApiKeySpend>,
+    /// Idempotency key of the request, completed with its response
+    pub idempotency: Option<IdempotentCompletion>,
+}
+
+/// API key funding a new transaction
+#[derive(Debug, Clone)]
+pub struct ApiKeySpend {
+    pub key_id: String,
+    /// Most the key may spend in a rolling 24 hours, this transaction
+    /// included; `None` for no limit
+    pub daily_limit_sats: Option<u64>,
+}
+
+/// HD-derived address owned by a user
+#[derive(Debug, Clone)]
+pub struct UserAddress {
+    pub user_id: String,
+    pub address: String,
+    pub derivation_index: u32,
+    pub derivation_path: String,
+    pub public_key: String,
+    pub address_type: String,
+    pub label: Option<String>,
+    pub is_change: bool,
+    pub balance_sats: u64,
+    pub tx_count: u32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Incoming output to a user address
+#[derive(Debug, Clone)]
+pub struct Deposit {
+    pub id: i64,
+    pub user_id: String,
+    pub address: String,
+    pub txid: String,
+    pub vout: u32,
+    pub amount_sats: u64,
+    /// "pending", "credited" or "reversed"
+    pub status: String,
+    pub confirmations: u32,
+    pub block_height: Option<u64>,
+    pub block_hash: Option<String>,
+    pub first_seen_at: chrono::DateTime<chrono::Utc>,
+    pub credited_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Scoped API key acting on behalf of a user
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub key_id: String,
+    pub user_id: String,
+    pub name: String,
+    /// Hex-encoded HMAC secret
+    pub secret: String,
+    /// Subset of "tx:create", "tx:read" and "wallet:read"
+    pub scopes: Vec<String>,
+    /// Maximum amount of a single transaction
+    pub max_amount_sats: Option<u64>,
+    /// Maximum total amount of transactions over a rolling 24 hours
+    pub daily_limit_sats: Option<u64>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_by: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Outcome of reserving an idempotency key
+#[derive(Debug, Clone)]
+pub enum IdempotencyClaim {
+    /// The key was unused (or expired) and is now reserved for this request
+    Acquired,
+    /// The key is taken by an earlier request
+    Existing(IdempotentResponse),
+}
+
+/// Earlier request recorded under an idempotency key
+#[derive(Debug, Clone)]
+pub struct IdempotentResponse {
+    /// Fingerprint of the earlier request body
+    pub request_hash: String,
+    /// Status and body of its response; `None` while it is still in progress
+    pub response: Option<(u16, serde_json::Value)>,
+}
+
+/// Response stored under an idempotency key once its request succeeded
+#[derive(Debug, Clone)]
+pub struct IdempotentCompletion {
+    pub owner: String,
+    pub key: String,
+    pub status_code: u16,
+    pub response: serde_json::Value,
+}
+
+/// Webhook event: a transaction moved to a new state
+pub const WEBHOOK_EVENT_TX_STATE_CHANGED: &str = "transaction.state_changed";
+/// Webhook event: a DKG ceremony completed
+pub const WEBHOOK_EVENT_DKG_COMPLETED: &str = "dkg.completed";
+/// Webhook event: an aux info ceremony completed
+pub const WEBHOOK_EVENT_AUX_INFO_COMPLETED: &str = "aux_info.completed";
+/// Webhook event: the presignature pool dropped to a critical level
+pub const WEBHOOK_EVENT_PRESIG_POOL_LOW: &str = "presignature_pool.low";
+
+/// Every event type a webhook can subscribe to
+pub const WEBHOOK_EVENT_TYPES: [&str; 4] = [
+    WEBHOOK_EVENT_TX_STATE_CHANGED,
+    WEBHOOK_EVENT_DKG_COMPLETED,
+    WEBHOOK_EVENT_AUX_INFO_COMPLETED,
+    WEBHOOK_EVENT_PRESIG_POOL_LOW,
+];
+
+/// Endpoint receiving webhook events
+#[derive(Debug, Clone)]
+pub struct WebhookSubscription {
+    pub subscription_id: String,
+    pub url: String,
+    /// Subset of [`WEBHOOK_EVENT_TYPES`]
+    pub events: Vec<String>,
+    /// HMAC key for the delivery signature
+    pub secret: String,
+    pub active: bool,
+    pub created_by: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Webhook delivery claimed for an attempt, with its destination
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub subscription_id: String,
+    pub url: String,
+    pub secret: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// Attempts so far, including the one being made
+    pub attempts: u32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Webhook delivery that exhausted its retries
+#[derive(Debug, Clone)]
+pub struct WebhookDeadLetter {
+    pub id: i64,
+    pub subscription_id: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempts: u32,
+    pub last_status_code: Option<u16>,
+    pub last_error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub failed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Unspent output committed to a transaction that has not finished yet
+#[derive(Debug, Clone)]
+pub struct UtxoReservation {
+    pub utxo_txid: String,
+    pub utxo_vout: u32,
+    /// Transaction spending the output
+    pub txid: String,
+    pub amount_sats: u64,
+    pub reserved_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Recorded transition of a transaction between two states
+#[derive(Debug, Clone)]
+pub struct StateTransition {
+    pub from: threshold_types::TransactionState,
+    pub to: threshold_types::TransactionState,
+    /// Row version after the transition
+    pub version: u64,
+    /// Who made the transition, e.g. `node-1/orchestrator`
+    pub actor: String,
+    pub reason: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Lifecycle step that is retried on failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttemptOperation {
+    Signing,
+    Broadcast,
+}
+
+impl AttemptOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttemptOperation::Signing => "signing",
+            AttemptOperation::Broadcast => "broadcast",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "signing" => Some(AttemptOperation::Signing),
+            "broadcast" => Some(AttemptOperation::Broadcast),
+            _ => None,
+        }
+    }
+}
+
+/// How an attempt ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttemptOutcome {
+    Succeeded,
+    /// Failed, but the step may be tried again
+    Retryable,
+    /// Failed in a way retrying cannot fix
+    Fatal,
+}
+
+impl AttemptOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttemptOutcome::Succeeded => "succeeded",
+            AttemptOutcome::Retryable => "retryable",
+            AttemptOutcome::Fatal => "fatal",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "succeeded" => Some(AttemptOutcome::Succeeded),
+            "retryable" => Some(AttemptOutcome::Retryable),
+            "fatal" => Some(AttemptOutcome::Fatal),
+            _ => None,
+        }
+    }
+}
+
+/// One signing session or broadcast made for a transaction
+#[derive(Debug, Clone)]
+pub struct TransactionAttempt {
+    pub operation: AttemptOperation,
+    /// 1 for the first attempt of the operation
+    pub attempt: u32,
+    pub outcome: AttemptOutcome,
+    pub error: Option<String>,
+    /// Node that made the attempt
+    pub coordinator: threshold_types::NodeId,
+    /// Signers left out of the attempt
+    pub excluded_nodes: Vec<threshold_types::NodeId>,
+    /// Signer blamed for the failure
+    pub faulty_node: Option<threshold_types::NodeId>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+    /// Earliest time of the next attempt, after a retryable failure
+    pub retry_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Transaction exempt from retention
+#[derive(Debug, Clone)]
+pub struct LegalHold {
+    pub txid: String,
+    pub reason: String,
+    pub placed_by: String,
+    pub placed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Terminal transaction selected for archival
+#[derive(Debug, Clone)]
+pub struct ArchivedTransaction {
+    pub txid: String,
+    /// The transaction row with its `voting_rounds` (each with its `votes`),
+    /// `state_history`, `presignature_usage`, `byzantine_violations` and
+    /// `attempts`
+    pub record: serde_json::Value,
+}
+
+/// Cluster event: a transaction moved to a new state
+pub const EVENT_TX_STATE_CHANGED: &str = WEBHOOK_EVENT_TX_STATE_CHANGED;
+/// Cluster event: a node's vote on a transaction was recorded
+pub const EVENT_VOTE_RECEIVED: &str = "vote.received";
+/// Cluster event: a signing session advanced a stage
+pub const EVENT_SIGNING_PROGRESS: &str = "signing.progress";
+/// Cluster event: a node became healthy or unhealthy
+pub const EVENT_NODE_HEALTH_CHANGED: &str = "node.health_changed";
+
+/// Every event type on the cluster event stream
+pub const EVENT_TYPES: [&str; 4] = [
+    EVENT_TX_STATE_CHANGED,
+    EVENT_VOTE_RECEIVED,
+    EVENT_SIGNING_PROGRESS,
+    EVENT_NODE_HEALTH_CHANGED,
+];
+
+/// Entry of the cluster event log
+#[derive(Debug, Clone)]
+pub struct ClusterEvent {
+    /// Monotonic ID, used as the SSE event ID
+    pub id: i64,
+    pub event_type: String,
+    pub tx_id: Option<String>,
+    pub node_id: Option<u64>,
+    pub payload: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
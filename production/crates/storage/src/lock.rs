@@ -0,0 +1,304 @@
+//! Fenced distributed locks backed by etcd leases
+//!
+//! A [`LockGuard`] keeps its lease alive in the background for as long as it
+//! is held and revokes the lease when released or dropped, which deletes the
+//! lock key. Each acquisition carries a fencing token: the etcd revision at
+//! which the lock key was created. Revisions only ever grow, so a later holder
+//! of the same lock always has a larger token, and storage writes guarded by
+//! the token can reject a holder whose lease already expired.
+//!
+//! [`LocalLockManager`] hands out the same guards for locks that only need to
+//! exclude tasks of one process, e.g. a whole test cluster in `cargo test`.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use etcd_client::{Client, LeaseKeepAliveResponse, LeaseKeepAliveStream, LeaseKeeper};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use threshold_types::{Error, Result};
+
+/// Pause between attempts at renewing a lease after a failed renewal
+const RENEW_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Held distributed lock
+///
+/// Dropping the guard releases the lock in the background; call
+/// [`LockGuard::release`] to wait for the release to complete.
+pub struct LockGuard {
+    key: String,
+    fencing_token: u64,
+    lost: Arc<AtomicBool>,
+    holder: Holder,
+}
+
+/// What keeps the lock held
+enum Holder {
+    /// etcd lease renewed by `keep_alive`; `None` once released
+    Lease {
+        client: Client,
+        lease_id: i64,
+        keep_alive: Option<JoinHandle<()>>,
+    },
+    /// Entry in a [`LocalLockManager`]; `None` once released
+    Local(Option<Arc<Mutex<LocalLocks>>>),
+}
+
+impl LockGuard {
+    /// Start keeping `lease_id` alive for a lock just created at `revision`
+    pub(crate) async fn new(
+        client: Client,
+        key: String,
+        lease_id: i64,
+        ttl_secs: i64,
+        revision: i64,
+    ) -> Result<Self> {
+        let (keeper, stream) = client
+            .clone()
+            .lease_keep_alive(lease_id)
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to create lease keeper: {}", e)))?;
+
+        let lost = Arc::new(AtomicBool::new(false));
+        let task_lost = Arc::clone(&lost);
+        let task_key = key.clone();
+        let mut task_client = client.clone();
+        let ttl = Duration::from_secs(ttl_secs.max(1) as u64);
+        let period = ttl / 3;
+
+        let keep_alive = tokio::spawn(async move {
+            let mut channel = Some((keeper, stream));
+            let mut last_renewed = Instant::now();
+            let mut delay = period;
+
+            loop {
+                tokio::time::sleep(delay).await;
+
+                match renew(&mut task_client, lease_id, &mut channel).await {
+                    Ok(Some(resp)) if resp.ttl() > 0 => {
+                        debug!("Renewed lease {} of lock {} (ttl={}s)", lease_id, task_key, resp.ttl());
+                        last_renewed = Instant::now();
+                        delay = period;
+                    }
+                    Ok(_) => {
+                        warn!("Lease {} of lock {} expired; lock lost", lease_id, task_key);
+                        task_lost.store(true, Ordering::SeqCst);
+                        return;
+                    }
+                    Err(e) => {
+                        // The lease outlives a failed renewal until its TTL
+                        // runs out, so keep trying on a fresh stream until then
+                        channel = None;
+                        if last_renewed.elapsed() + RENEW_RETRY_DELAY >= ttl {
+                            warn!("Failed to renew lease {} of lock {} within its TTL; lock lost: {}", lease_id, task_key, e);
+                            task_lost.store(true, Ordering::SeqCst);
+                            return;
+                        }
+                        warn!("Failed to renew lease {} of lock {}, retrying: {}", lease_id, task_key, e);
+                        delay = RENEW_RETRY_DELAY;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            key,
+            fencing_token: revision as u64,
+            lost,
+            holder: Holder::Lease {
+                client,
+                lease_id,
+                keep_alive: Some(keep_alive),
+            },
+        })
+    }
+
+    /// Lock key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Lease backing the lock; 0 for in-process locks
+    pub fn lease_id(&self) -> i64 {
+        match &self.holder {
+            Holder::Lease { lease_id, .. } => *lease_id,
+            Holder::Local(_) => 0,
+        }
+    }
+
+    /// Token to pass to fenced storage writes
+    ///
+    /// Strictly larger than the token of any earlier holder of the same lock.
+    pub fn fencing_token(&self) -> u64 {
+        self.fencing_token
+    }
+
+    /// Whether the lease is still being renewed
+    ///
+    /// `false` means the lock may already belong to another node; the
+    /// holder should stop and let fenced writes decide.
+    pub fn is_held(&self) -> bool {
+        !self.lost.load(Ordering::SeqCst)
+    }
+
+    /// Release the lock and wait for the backend to confirm
+    pub async fn release(mut self) -> Result<()> {
+        match &mut self.holder {
+            Holder::Lease {
+                client,
+                lease_id,
+                keep_alive,
+            } => {
+                if let Some(handle) = keep_alive.take() {
+                    handle.abort();
+                }
+                client.lease_revoke(*lease_id).await.map_err(|e| {
+                    Error::StorageError(format!("Failed to release lock {}: {}", self.key, e))
+                })?;
+
+                info!("Released lock {} (lease {} revoked)", self.key, lease_id);
+            }
+            Holder::Local(locks) => {
+                if let Some(locks) = locks.take() {
+                    release_local(&locks, &self.key);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let (client, lease_id) = match &mut self.holder {
+            Holder::Local(locks) => {
+                if let Some(locks) = locks.take() {
+                    release_local(&locks, &self.key);
+                }
+                return;
+            }
+            Holder::Lease {
+                client,
+                lease_id,
+                keep_alive,
+            } => {
+                // `release` already revoked the lease
+                let Some(handle) = keep_alive.take() else {
+                    return;
+                };
+                handle.abort();
+                (client.clone(), *lease_id)
+            }
+        };
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!("No runtime to release lock {}; lease {} will expire", self.key, lease_id);
+            return;
+        };
+        let mut client = client;
+        let key = std::mem::take(&mut self.key);
+        runtime.spawn(async move {
+            match client.lease_revoke(lease_id).await {
+                Ok(_) => info!("Released lock {} on drop (lease {} revoked)", key, lease_id),
+                Err(e) => warn!("Failed to release lock {} on drop; lease {} will expire: {}", key, lease_id, e),
+            }
+        });
+    }
+}
+
+impl std::fmt::Debug for LockGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LockGuard")
+            .field("key", &self.key)
+            .field("lease_id", &self.lease_id())
+            .field("fencing_token", &self.fencing_token)
+            .field("held", &self.is_held())
+            .finish()
+    }
+}
+
+/// Renew a lease once, opening a keep-alive stream if there is none
+async fn renew(
+    client: &mut Client,
+    lease_id: i64,
+    channel: &mut Option<(LeaseKeeper, LeaseKeepAliveStream)>,
+) -> std::result::Result<Option<LeaseKeepAliveResponse>, etcd_client::Error> {
+    let (keeper, stream) = match channel {
+        Some(channel) => channel,
+        None => channel.insert(client.lease_keep_alive(lease_id).await?),
+    };
+    keeper.keep_alive().await?;
+    stream.message().await
+}
+
+/// Locks held through a [`LocalLockManager`]
+#[derive(Default)]
+struct LocalLocks {
+    held: HashSet<String>,
+    /// Last fencing token handed out
+    last_token: u64,
+}
+
+fn release_local(locks: &Mutex<LocalLocks>, key: &str) {
+    // A poisoned table still has to forget the key, or it stays locked forever
+    let mut locks = locks.lock().unwrap_or_else(|e| e.into_inner());
+    locks.held.remove(key);
+}
+
+/// In-process lock manager
+///
+/// Clones share the same lock table. Locks never expire, so the guard is
+/// always held until it is released or dropped; fencing tokens still grow
+/// with every acquisition.
+#[derive(Clone, Default)]
+pub struct LocalLockManager {
+    locks: Arc<Mutex<LocalLocks>>,
+}
+
+impl LocalLockManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take the lock on `key`; `None` if it is already held
+    pub fn try_lock(&self, key: &str) -> Option<LockGuard> {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        if !locks.held.insert(key.to_string()) {
+            return None;
+        }
+        locks.last_token += 1;
+
+        Some(LockGuard {
+            key: key.to_string(),
+            fencing_token: locks.last_token,
+            lost: Arc::new(AtomicBool::new(false)),
+            holder: Holder::Local(Some(Arc::clone(&self.locks))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_lock_exclusion_and_tokens() {
+        let manager = LocalLockManager::new();
+
+        let guard = manager.try_lock("/locks/signing/tx1").unwrap();
+        assert!(manager.try_lock("/locks/signing/tx1").is_none());
+        assert!(manager.clone().try_lock("/locks/signing/tx2").is_some());
+
+        let token = guard.fencing_token();
+        guard.release().await.unwrap();
+
+        // Dropping releases as well
+        let guard = manager.try_lock("/locks/signing/tx1").unwrap();
+        assert!(guard.fencing_token() > token);
+        drop(guard);
+        assert!(manager.try_lock("/locks/signing/tx1").is_some());
+    }
+}
@@ -0,0 +1,192 @@
+//! Versioned PostgreSQL schema migrations
+//!
+//! The SQL files under `crates/storage/migrations/` are compiled into the
+//! binary and applied in version order by [`PostgresStorage::migrate`], each
+//! recorded in `schema_migrations` with a SHA-256 checksum of its SQL. A
+//! recorded checksum that no longer matches the embedded file means the
+//! schema has drifted from what this binary expects, and the node refuses to
+//! start.
+//!
+//! [`PostgresStorage::migrate`]: crate::PostgresStorage::migrate
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+/// `pg_advisory_xact_lock` key serializing migrations across nodes
+/// ("mpcmigr" in ASCII)
+pub const MIGRATION_LOCK_KEY: i64 = 0x6d70_636d_6967_72;
+
+/// One embedded migration
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    pub sql: &'static str,
+}
+
+impl Migration {
+    /// Hex SHA-256 of the migration SQL
+    pub fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+macro_rules! migration {
+    ($version:expr, $description:expr, $file:literal) => {
+        Migration {
+            version: $version,
+            description: $description,
+            sql: include_str!(concat!("../migrations/", $file)),
+        }
+    };
+}
+
+/// All migrations, in version order
+pub const MIGRATIONS: &[Migration] = &[
+    migration!(1, "Initial schema", "01_schema.sql"),
+    migration!(2, "Schema migrations table", "02_migrations.sql"),
+    migration!(3, "Add vote count trigger", "03_triggers.sql"),
+    migration!(4, "User addresses", "04_user_addresses.sql"),
+    migration!(5, "Add FROST nonce preprocessing pool", "05_frost_nonces.sql"),
+    migration!(6, "DKG xpub export", "06_dkg_xpub.sql"),
+    migration!(7, "API authentication and transaction ownership", "07_auth.sql"),
+    migration!(8, "Deposits and user ledger", "08_deposits.sql"),
+    migration!(9, "Scoped API keys", "09_api_keys.sql"),
+    migration!(10, "Idempotency keys", "10_idempotency_keys.sql"),
+    migration!(11, "Webhooks", "11_webhooks.sql"),
+    migration!(12, "Cluster events", "12_cluster_events.sql"),
+    migration!(13, "Transaction state history", "13_transaction_state_history.sql"),
+    migration!(14, "Audit chain", "14_audit_chain.sql"),
+    migration!(15, "Signing fencing tokens", "15_fencing_tokens.sql"),
+    migration!(16, "UTXO reservations", "16_utxo_reservations.sql"),
+    migration!(17, "Legal holds and retention archives", "17_retention.sql"),
+    migration!(18, "Transaction attempts", "18_transaction_attempts.sql"),
+    migration!(19, "Withdrawal ledger debits", "19_withdrawal_debits.sql"),
+    migration!(20, "Signing key paths", "20_signing_paths.sql"),
+    migration!(21, "Signing lock claims", "21_signing_claims.sql"),
+];
+
+/// Latest embedded schema version
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// State of one migration in a database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// Applied with the embedded SQL
+    Applied,
+    /// Not applied yet
+    Pending,
+    /// Applied, but the recorded checksum differs from the embedded SQL
+    Modified,
+    /// Recorded without a checksum (created before migrations were embedded)
+    Unverified,
+    /// Recorded in the database but unknown to this binary
+    Unknown,
+}
+
+impl std::fmt::Display for MigrationState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationState::Applied => write!(f, "applied"),
+            MigrationState::Pending => write!(f, "pending"),
+            MigrationState::Modified => write!(f, "modified"),
+            MigrationState::Unverified => write!(f, "unverified"),
+            MigrationState::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Row of [`PostgresStorage::migration_status`](crate::PostgresStorage::migration_status)
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: u32,
+    pub description: String,
+    pub state: MigrationState,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+/// Row of `schema_migrations`
+#[derive(Debug, Clone)]
+pub(crate) struct AppliedMigration {
+    pub version: u32,
+    pub description: String,
+    pub checksum: Option<String>,
+    pub applied_at: DateTime<Utc>,
+}
+
+/// Compare the recorded migrations with the embedded ones
+pub(crate) fn compare(applied: &[AppliedMigration]) -> Vec<MigrationStatus> {
+    let mut status: Vec<MigrationStatus> = MIGRATIONS
+        .iter()
+        .map(|m| {
+            let recorded = applied.iter().find(|a| a.version == m.version);
+            let state = match recorded.map(|a| a.checksum.as_deref()) {
+                None => MigrationState::Pending,
+                Some(None) => MigrationState::Unverified,
+                Some(Some(checksum)) if checksum == m.checksum() => MigrationState::Applied,
+                Some(Some(_)) => MigrationState::Modified,
+            };
+            MigrationStatus {
+                version: m.version,
+                description: m.description.to_string(),
+                state,
+                applied_at: recorded.map(|a| a.applied_at),
+            }
+        })
+        .collect();
+
+    status.extend(
+        applied
+            .iter()
+            .filter(|a| !MIGRATIONS.iter().any(|m| m.version == a.version))
+            .map(|a| MigrationStatus {
+                version: a.version,
+                description: a.description.clone(),
+                state: MigrationState::Unknown,
+                applied_at: Some(a.applied_at),
+            }),
+    );
+    status.sort_by_key(|s| s.version);
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(version: u32, checksum: Option<String>) -> AppliedMigration {
+        AppliedMigration {
+            version,
+            description: format!("migration {}", version),
+            checksum,
+            applied_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        for pair in MIGRATIONS.windows(2) {
+            assert!(pair[0].version < pair[1].version);
+        }
+        assert_eq!(latest_version(), MIGRATIONS.len() as u32);
+    }
+
+    #[test]
+    fn test_compare_detects_drift() {
+        let status = compare(&[
+            applied(1, Some(MIGRATIONS[0].checksum())),
+            applied(2, Some("0".repeat(64))),
+            applied(3, None),
+            applied(999, Some("0".repeat(64))),
+        ]);
+
+        let state = |v: u32| status.iter().find(|s| s.version == v).unwrap().state;
+        assert_eq!(state(1), MigrationState::Applied);
+        assert_eq!(state(2), MigrationState::Modified);
+        assert_eq!(state(3), MigrationState::Unverified);
+        assert_eq!(state(4), MigrationState::Pending);
+        assert_eq!(state(999), MigrationState::Unknown);
+    }
+}
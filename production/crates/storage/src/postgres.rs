@@ -1,11 +1,16 @@
 use deadpool_postgres::{Config, ManagerConfig, Pool, RecyclingMethod, Runtime};
+use std::sync::Arc;
 use threshold_types::*;
-use chrono::Utc;
+use chrono::{SubsecRound, Utc};
 use tokio_postgres::NoTls;
 use tracing::{error, info, warn};
 
+use crate::audit::{AuditEntry, AuditSigner};
+
 pub struct PostgresStorage {
     pool: Pool,
+    /// Signs appended audit entries; entries are written unsigned without it
+    audit_signer: Option<Arc<AuditSigner>>,
 }
 
 impl PostgresStorage {
@@ -36,13 +41,184 @@ impl PostgresStorage {
             .create_pool(Some(Runtime::Tokio1), NoTls)
             .map_err(|e| Error::StorageError(format!("Failed to create pool: {}", e)))?;
 
-        let storage = Self { pool };
+        let storage = Self {
+            pool,
+            audit_signer: None,
+        };
 
         info!("PostgreSQL storage initialized successfully");
 
         Ok(storage)
     }
 
+    /// Sign audit entries written through this storage with the node's key
+    pub fn with_audit_signer(mut self, signer: Arc<AuditSigner>) -> Self {
+        self.audit_signer = Some(signer);
+        self
+    }
+
+    // ============================================================================
+    // Schema Migrations
+    // ============================================================================
+
+    /// Apply pending embedded migrations; returns the versions applied
+    ///
+    /// Runs in a single database transaction under an advisory lock, so nodes
+    /// starting together apply each migration once and a failed migration
+    /// leaves the schema untouched. Fails with `SchemaDrift` if an applied
+    /// migration no longer matches the embedded SQL, and refuses to touch a
+    /// database created before migrations were tracked until it is baselined
+    /// with [`baseline_migrations`](Self::baseline_migrations).
+    pub async fn migrate(&self) -> Result<Vec<u32>> {
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        let applied = lock_migrations(&tx).await?;
+        let status = crate::migrations::compare(&applied);
+
+        if let Some(drifted) = status
+            .iter()
+            .find(|s| s.state == crate::migrations::MigrationState::Modified)
+        {
+            return Err(Error::SchemaDrift {
+                version: drifted.version,
+                reason: format!(
+                    "'{}' was applied with different SQL than this binary embeds",
+                    drifted.description
+                ),
+            });
+        }
+        for unknown in status
+            .iter()
+            .filter(|s| s.state == crate::migrations::MigrationState::Unknown)
+        {
+            warn!(
+                "Database has migration {} ('{}') unknown to this binary; a newer node migrated it",
+                unknown.version, unknown.description
+            );
+        }
+
+        let tracked = applied.iter().any(|a| a.checksum.is_some());
+        let has_schema: bool = tx
+            .query_one("SELECT to_regclass('public.transactions') IS NOT NULL", &[])
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to inspect schema: {}", e)))?
+            .get(0);
+        if !tracked && has_schema {
+            return Err(Error::StorageError(format!(
+                "Database schema predates tracked migrations; run `threshold-wallet db migrate --baseline <version>` \
+                 with the last migration it already has (latest is {})",
+                crate::migrations::latest_version()
+            )));
+        }
+
+        let mut newly_applied = Vec::new();
+        for migration in crate::migrations::MIGRATIONS {
+            let pending = status
+                .iter()
+                .any(|s| s.version == migration.version && s.state == crate::migrations::MigrationState::Pending);
+            if !pending {
+                continue;
+            }
+
+            tx.batch_execute(migration.sql).await.map_err(|e| {
+                Error::StorageError(format!(
+                    "Migration {} ('{}') failed: {}",
+                    migration.version, migration.description, e
+                ))
+            })?;
+            record_migration(&tx, migration).await?;
+
+            info!("Applied migration {}: {}", migration.version, migration.description);
+            newly_applied.push(migration.version);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit migrations: {}", e)))?;
+
+        if newly_applied.is_empty() {
+            info!("Database schema is up to date (version {})", crate::migrations::latest_version());
+        }
+
+        Ok(newly_applied)
+    }
+
+    /// Embedded migrations and their state in this database
+    pub async fn migration_status(&self) -> Result<Vec<crate::migrations::MigrationStatus>> {
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        // Read-only: unlike `migrate`, this does not create the table
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        let exists: bool = tx
+            .query_one("SELECT to_regclass('public.schema_migrations') IS NOT NULL", &[])
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to inspect schema: {}", e)))?
+            .get(0);
+        let applied = if exists {
+            load_migrations(&tx).await?
+        } else {
+            Vec::new()
+        };
+
+        Ok(crate::migrations::compare(&applied))
+    }
+
+    /// Mark migrations up to `version` as applied without running them
+    ///
+    /// For databases whose schema was created by the docker init scripts
+    /// before migrations were tracked. Records the embedded checksums, so
+    /// later drift checks compare against them. Returns the number of
+    /// migrations recorded.
+    pub async fn baseline_migrations(&self, version: u32) -> Result<u64> {
+        if !crate::migrations::MIGRATIONS.iter().any(|m| m.version == version) {
+            return Err(Error::ConfigError(format!("Unknown migration version {}", version)));
+        }
+
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        lock_migrations(&tx).await?;
+
+        let mut recorded = 0;
+        for migration in crate::migrations::MIGRATIONS.iter().filter(|m| m.version <= version) {
+            record_migration(&tx, migration).await?;
+            recorded += 1;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit baseline: {}", e)))?;
+
+        warn!("Baselined schema at migration {} without running SQL", version);
+
+        Ok(recorded)
+    }
+
     /// Record a vote in the database
     pub async fn record_vote(&self, vote: &Vote) -> Result<()> {
         let client = self
@@ -51,15 +227,28 @@ impl PostgresStorage {
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
+        // Newly recorded votes are also appended to the cluster event log
         client
             .execute(
                 r#"
-                INSERT INTO votes (round_id, node_id, tx_id, approve, value, signature)
-                VALUES (
-                    (SELECT id FROM voting_rounds WHERE tx_id = $1 AND round_number = $2),
-                    $3, $4, $5, $6, $7
+                WITH recorded AS (
+                    INSERT INTO votes (round_id, node_id, tx_id, approve, value, signature)
+                    VALUES (
+                        (SELECT id FROM voting_rounds WHERE tx_id = $1 AND round_number = $2),
+                        $3, $4, $5, $6, $7
+                    )
+                    ON CONFLICT (round_id, node_id) DO NOTHING
+                    RETURNING node_id, tx_id, approve, value
                 )
-                ON CONFLICT (round_id, node_id) DO NOTHING
+                INSERT INTO cluster_events (event_type, tx_id, node_id, payload)
+                SELECT $8::TEXT, tx_id, node_id, jsonb_build_object(
+                    'txid', tx_id,
+                    'node_id', node_id,
+                    'round', $2::INT,
+                    'approve', approve,
+                    'value', value
+                )
+                FROM recorded
                 "#,
                 &[
                     &vote.tx_id.0,
@@ -69,6 +258,7 @@ impl PostgresStorage {
                     &vote.approve,
                     &(vote.value as i64),
                     &vote.signature,
+                    &crate::EVENT_VOTE_RECEIVED,
                 ],
             )
             .await
@@ -203,22 +393,100 @@ impl PostgresStorage {
 
     /// Create a new transaction
     pub async fn create_transaction(&self, tx: &Transaction) -> Result<i64> {
-        let client = self
+        self.create_transaction_with_reservations(tx, &[]).await
+    }
+
+    /// Create a new transaction and reserve the UTXOs it spends
+    ///
+    /// `inputs` are `(txid, vout, amount_sats)` of the spent outputs. The
+    /// transaction and its reservations are stored together; if any output
+    /// is already reserved by another transaction nothing is stored and
+    /// `UtxoReserved` is returned, so the caller can select coins again.
+    pub async fn create_transaction_with_reservations(
+        &self,
+        tx: &Transaction,
+        inputs: &[(String, u32, u64)],
+    ) -> Result<i64> {
+        self.create_owned_transaction(tx, inputs, &crate::TransactionOrigin::default()).await
+    }
+
+    /// Create a transaction on behalf of `origin` and reserve its inputs
+    ///
+    /// Like [`create_transaction_with_reservations`](Self::create_transaction_with_reservations),
+    /// and in the same database transaction records the owner and, if
+    /// `origin.debit_ledger`, debits amount plus fee from the owner's ledger.
+    /// The owner's row is locked while the balance is checked, so concurrent
+    /// withdrawals cannot together overdraw it; if it does not cover the
+    /// transaction nothing is stored and `InsufficientBalance` is returned.
+    /// Likewise the row of `origin.api_key` is locked while its daily spend
+    /// is summed, and the transaction is stored under the key or refused
+    /// with `ApiKeyLimitExceeded`. `origin.idempotency` is completed in the
+    /// same transaction, so a stored transaction always has its response
+    /// recorded for retries.
+    pub async fn create_owned_transaction(
+        &self,
+        tx: &Transaction,
+        inputs: &[(String, u32, u64)],
+        origin: &crate::TransactionOrigin,
+    ) -> Result<i64> {
+        let mut client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
+        let db_tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
         let state_str = tx.state.to_string();
+        let derivation_path: Vec<i32> = origin.derivation_path.iter().map(|i| *i as i32).collect();
+        let api_key_id = origin.api_key.as_ref().map(|key| key.key_id.as_str());
+
+        if let Some(key) = &origin.api_key {
+            // Concurrent requests with the key queue on its row, so each sees
+            // the spend of the ones committed before it
+            db_tx
+                .execute("SELECT 1 FROM api_keys WHERE key_id = $1 FOR UPDATE", &[&key.key_id])
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to lock API key: {}", e)))?;
+
+            if let Some(limit) = key.daily_limit_sats {
+                let spent = db_tx
+                    .query_one(
+                        r#"
+                        SELECT COALESCE(SUM(amount_sats), 0)::BIGINT FROM transactions
+                        WHERE api_key_id = $1 AND created_at > NOW() - INTERVAL '24 hours'
+                        "#,
+                        &[&key.key_id],
+                    )
+                    .await
+                    .map_err(|e| Error::StorageError(format!("Failed to get API key spend: {}", e)))?
+                    .get::<_, i64>(0) as u64;
+
+                if spent.saturating_add(tx.amount_sats) > limit {
+                    return Err(Error::ApiKeyLimitExceeded {
+                        key_id: key.key_id.clone(),
+                        amount_sats: tx.amount_sats,
+                        spent,
+                        limit,
+                    });
+                }
+            }
+        }
 
-        info!("Inserting transaction: txid={}, state={}, unsigned_tx_len={}, recipient={}, amount={}, fee={}, metadata={:?}",
-            tx.txid.0, state_str, tx.unsigned_tx.len(), tx.recipient, tx.amount_sats, tx.fee_sats, tx.metadata);
+        info!("Inserting transaction: txid={}, state={}, unsigned_tx_len={}, recipient={}, amount={}, fee={}, metadata={:?}, inputs={}",
+            tx.txid.0, state_str, tx.unsigned_tx.len(), tx.recipient, tx.amount_sats, tx.fee_sats, tx.metadata, inputs.len());
 
-        let row = client
+        let row = db_tx
             .query_one(
                 r#"
-                INSERT INTO transactions (txid, state, unsigned_tx, recipient, amount_sats, fee_sats, metadata)
-                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                INSERT INTO transactions (
+                    txid, state, unsigned_tx, recipient, amount_sats, fee_sats, metadata,
+                    created_by, derivation_path, api_key_id
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
                 RETURNING id
                 "#,
                 &[
@@ -229,214 +497,766 @@ impl PostgresStorage {
                     &(tx.amount_sats as i64),
                     &(tx.fee_sats as i64),
                     &tx.metadata,
+                    &origin.user_id,
+                    &derivation_path,
+                    &api_key_id,
                 ],
             )
             .await
             .map_err(|e| Error::StorageError(format!("Failed to create transaction: {}", e)))?;
 
+        if origin.debit_ledger {
+            let user_id = origin.user_id.as_deref().ok_or_else(|| {
+                Error::StorageError("A ledger debit needs the owning user".to_string())
+            })?;
+            let needed = tx.amount_sats + tx.fee_sats;
+
+            db_tx
+                .execute("SELECT 1 FROM users WHERE user_id = $1 FOR UPDATE", &[&user_id])
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to lock user: {}", e)))?;
+            let available: i64 = db_tx
+                .query_one(
+                    "SELECT COALESCE(SUM(amount_sats), 0)::BIGINT FROM ledger_entries WHERE user_id = $1",
+                    &[&user_id],
+                )
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to get ledger balance: {}", e)))?
+                .get(0);
+
+            if available < needed as i64 {
+                return Err(Error::InsufficientBalance {
+                    user_id: user_id.to_string(),
+                    needed,
+                    available,
+                });
+            }
+
+            db_tx
+                .execute(
+                    r#"
+                    INSERT INTO ledger_entries (user_id, amount_sats, entry_type, txid)
+                    VALUES ($1, $2, 'withdrawal', $3)
+                    "#,
+                    &[&user_id, &-(needed as i64), &tx.txid.0],
+                )
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to write ledger entry: {}", e)))?;
+        }
+
+        for (utxo_txid, vout, amount_sats) in inputs {
+            let reserved = db_tx
+                .execute(
+                    r#"
+                    INSERT INTO utxo_reservations (utxo_txid, utxo_vout, txid, amount_sats)
+                    VALUES ($1, $2, $3, $4)
+                    ON CONFLICT (utxo_txid, utxo_vout) DO NOTHING
+                    "#,
+                    &[utxo_txid, &(*vout as i32), &tx.txid.0, &(*amount_sats as i64)],
+                )
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to reserve UTXO: {}", e)))?;
+
+            if reserved == 0 {
+                // Dropping db_tx rolls back the transaction row too
+                return Err(Error::UtxoReserved {
+                    outpoint: format!("{}:{}", utxo_txid, vout),
+                });
+            }
+        }
+
+        if let Some(completion) = &origin.idempotency {
+            db_tx
+                .execute(
+                    r#"
+                    UPDATE idempotency_keys SET status_code = $3, response = $4
+                    WHERE owner = $1 AND idempotency_key = $2
+                    "#,
+                    &[
+                        &completion.owner,
+                        &completion.key,
+                        &(completion.status_code as i32),
+                        &completion.response,
+                    ],
+                )
+                .await
+                .map_err(|e| {
+                    Error::StorageError(format!("Failed to store idempotent response: {}", e))
+                })?;
+        }
+
+        db_tx
+            .commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit transaction: {}", e)))?;
+
         let id: i64 = row.get(0);
         info!("Created transaction: id={} txid={}", id, tx.txid);
 
         Ok(id)
     }
 
-    /// Update transaction state
-    pub async fn update_transaction_state(
+    /// Outpoints among `outpoints` that are reserved by some transaction
+    pub async fn reserved_outpoints(
         &self,
-        txid: &TxId,
-        new_state: TransactionState,
-    ) -> Result<()> {
-        let client = self
-            .pool
-            .get()
-            .await
-            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
-
-        let state_str = new_state.to_string();
-        client
-            .execute(
-                r#"
-                UPDATE transactions
-                SET state = $1, updated_at = NOW()
-                WHERE txid = $2
-                "#,
-                &[&state_str, &txid.0],
-            )
-            .await
-            .map_err(|e| {
-                Error::StorageError(format!("Failed to update transaction state: {}", e))
-            })?;
-
-        Ok(())
-    }
+        outpoints: &[(String, u32)],
+    ) -> Result<std::collections::HashSet<(String, u32)>> {
+        if outpoints.is_empty() {
+            return Ok(Default::default());
+        }
 
-    /// Set signed transaction
-    pub async fn set_signed_transaction(&self, txid: &TxId, signed_tx: &[u8]) -> Result<()> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        client
-            .execute(
+        let txids: Vec<&str> = outpoints.iter().map(|(txid, _)| txid.as_str()).collect();
+        let vouts: Vec<i32> = outpoints.iter().map(|(_, vout)| *vout as i32).collect();
+        let rows = client
+            .query(
                 r#"
-                UPDATE transactions
-                SET signed_tx = $1, updated_at = NOW()
-                WHERE txid = $2
+                SELECT r.utxo_txid, r.utxo_vout
+                FROM utxo_reservations r
+                JOIN UNNEST($1::TEXT[], $2::INTEGER[]) AS o(txid, vout)
+                  ON r.utxo_txid = o.txid AND r.utxo_vout = o.vout
                 "#,
-                &[&signed_tx, &txid.0],
+                &[&txids, &vouts],
             )
             .await
-            .map_err(|e| {
-                Error::StorageError(format!("Failed to set signed transaction: {}", e))
-            })?;
+            .map_err(|e| Error::StorageError(format!("Failed to get UTXO reservations: {}", e)))?;
 
-        info!("Updated signed transaction: txid={}", txid);
-
-        Ok(())
+        Ok(rows
+            .iter()
+            .map(|r| (r.get::<_, String>(0), r.get::<_, i32>(1) as u32))
+            .collect())
     }
 
-    /// Get transaction by txid
-    pub async fn get_transaction(&self, txid: &TxId) -> Result<Option<Transaction>> {
+    /// UTXOs reserved by a transaction
+    pub async fn get_utxo_reservations(&self, txid: &TxId) -> Result<Vec<crate::UtxoReservation>> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client
-            .query_opt(
+        let rows = client
+            .query(
                 r#"
-                SELECT id, txid, state, unsigned_tx, signed_tx, recipient,
-                       amount_sats, fee_sats, metadata, created_at, updated_at
-                FROM transactions
+                SELECT utxo_txid, utxo_vout, txid, amount_sats, reserved_at
+                FROM utxo_reservations
                 WHERE txid = $1
+                ORDER BY utxo_txid, utxo_vout
                 "#,
                 &[&txid.0],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get transaction: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to get UTXO reservations: {}", e)))?;
 
-        Ok(row.map(|r| Transaction {
-            id: r.get(0),
-            txid: TxId(r.get(1)),
-            state: parse_transaction_state(r.get(2)),
-            unsigned_tx: r.get(3),
-            signed_tx: r.get(4),
-            recipient: r.get(5),
-            amount_sats: r.get::<_, i64>(6) as u64,
-            fee_sats: r.get::<_, i64>(7) as u64,
-            metadata: r.get(8),
-            created_at: r.get(9),
-            updated_at: r.get(10),
-        }))
+        Ok(rows
+            .iter()
+            .map(|r| crate::UtxoReservation {
+                utxo_txid: r.get(0),
+                utxo_vout: r.get::<_, i32>(1) as u32,
+                txid: r.get(2),
+                amount_sats: r.get::<_, i64>(3) as u64,
+                reserved_at: r.get(4),
+            })
+            .collect())
     }
 
-    /// List all transactions with optional pagination
-    pub async fn list_all_transactions(
-        &self,
-        limit: Option<usize>,
-        offset: Option<usize>,
-    ) -> Result<Vec<Transaction>> {
+    /// Release the UTXOs reserved by a transaction
+    ///
+    /// Returns the number of released reservations.
+    pub async fn release_utxo_reservations(&self, txid: &TxId) -> Result<u64> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let limit_val = limit.unwrap_or(100) as i64;
-        let offset_val = offset.unwrap_or(0) as i64;
-
-        let rows = client
-            .query(
-                r#"
-                SELECT id, txid, state, unsigned_tx, signed_tx, recipient,
-                       amount_sats, fee_sats, metadata, created_at, updated_at
-                FROM transactions
-                ORDER BY created_at DESC
-                LIMIT $1 OFFSET $2
-                "#,
-                &[&limit_val, &offset_val],
-            )
+        let released = client
+            .execute("DELETE FROM utxo_reservations WHERE txid = $1", &[&txid.0])
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to query transactions: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to release UTXO reservations: {}", e)))?;
 
-        let mut transactions = Vec::new();
-        for row in rows {
-            transactions.push(Transaction {
-                id: row.get(0),
-                txid: TxId(row.get(1)),
-                state: parse_transaction_state(row.get(2)),
-                unsigned_tx: row.get(3),
-                signed_tx: row.get(4),
-                recipient: row.get(5),
-                amount_sats: row.get::<_, i64>(6) as u64,
-                fee_sats: row.get::<_, i64>(7) as u64,
-                metadata: row.get(8),
-                created_at: row.get(9),
-                updated_at: row.get(10),
-            });
+        if released > 0 {
+            info!("Released {} UTXO reservations of {}", released, txid);
         }
 
-        info!(
-            "Listed {} transactions (limit: {}, offset: {})",
-            transactions.len(),
-            limit_val,
-            offset_val
-        );
-
-        Ok(transactions)
+        Ok(released)
     }
 
-    /// Create a voting round
-    pub async fn create_voting_round(&self, round: &VotingRound) -> Result<i64> {
+    /// Release reservations held by transactions that reached a final state
+    ///
+    /// State transitions release reservations as they happen; this catches
+    /// the ones left behind, e.g. by transactions that finished before
+    /// reservations existed or were moved by hand. Reservations of
+    /// broadcast transactions are kept unless they confirmed. Returns the
+    /// number of released reservations.
+    pub async fn release_orphaned_utxo_reservations(&self) -> Result<u64> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client
-            .query_one(
+        let final_states: Vec<String> = FINAL_STATES.iter().map(ToString::to_string).collect();
+        let released = client
+            .execute(
                 r#"
-                INSERT INTO voting_rounds (tx_id, round_number, total_nodes, threshold, timeout_at)
-                VALUES ($1, $2, $3, $4, $5)
-                RETURNING id
+                DELETE FROM utxo_reservations r
+                USING transactions t
+                WHERE r.txid = t.txid AND t.state = ANY($1::TEXT[])
+                  AND (t.bitcoin_txid IS NULL OR t.state = 'confirmed')
                 "#,
-                &[
-                    &round.tx_id.0,
-                    &(round.round_number as i32),
-                    &(round.total_nodes as i32),
-                    &(round.threshold as i32),
-                    &round.timeout_at,
-                ],
+                &[&final_states],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to create voting round: {}", e)))?;
-
-        let id: i64 = row.get(0);
-        info!("Created voting round: id={} tx_id={}", id, round.tx_id);
+            .map_err(|e| Error::StorageError(format!("Failed to release UTXO reservations: {}", e)))?;
 
-        Ok(id)
+        Ok(released)
     }
 
-    /// Update voting round status
-    pub async fn update_voting_round(
+    /// Move a transaction from `expected` to `new_state`
+    ///
+    /// The transition must be allowed by
+    /// [`TransactionState::allowed_transitions`] and only applies if the
+    /// transaction is still in `expected`; otherwise `StateConflict` reports
+    /// the state it was found in, so two nodes racing on the same transaction
+    /// cannot both win. The transition bumps the row version and is recorded
+    /// in `transaction_state_history` with `actor` and `reason`, together
+    /// with a `transaction.state_changed` webhook delivery and cluster event,
+    /// all in one database transaction.
+    ///
+    /// Returns the new version of the transaction.
+    pub async fn transition_transaction_state(
         &self,
-        round_id: i64,
-        votes_received: u32,
-        approved: bool,
-        completed: bool,
-    ) -> Result<()> {
-        let client = self
+        txid: &TxId,
+        expected: TransactionState,
+        new_state: TransactionState,
+        actor: &str,
+        reason: &str,
+    ) -> Result<u64> {
+        if !expected.can_transition_to(new_state) {
+            return Err(Error::InvalidStateTransition {
+                tx_id: txid.0.clone(),
+                from: expected,
+                to: new_state,
+            });
+        }
+
+        let mut client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        client
-            .execute(
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        let expected_str = expected.to_string();
+        let state_str = new_state.to_string();
+        let updated = tx
+            .query_opt(
+                r#"
+                UPDATE transactions
+                SET state = $1, version = version + 1, updated_at = NOW()
+                WHERE txid = $2 AND state = $3
+                RETURNING version, recipient, amount_sats, updated_at
+                "#,
+                &[&state_str, &txid.0, &expected_str],
+            )
+            .await
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to update transaction state: {}", e))
+            })?;
+
+        let Some(row) = updated else {
+            let actual = tx
+                .query_opt("SELECT state FROM transactions WHERE txid = $1", &[&txid.0])
+                .await
+                .map_err(|e| {
+                    Error::StorageError(format!("Failed to get transaction state: {}", e))
+                })?;
+            return Err(match actual {
+                Some(r) => Error::StateConflict {
+                    tx_id: txid.0.clone(),
+                    expected,
+                    actual: parse_transaction_state(r.get(0)),
+                },
+                None => Error::TransactionNotFound(txid.clone()),
+            });
+        };
+
+        let version: i64 = row.get(0);
+        tx.execute(
+            r#"
+            INSERT INTO transaction_state_history (txid, from_state, to_state, version, actor, reason)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            &[&txid.0, &expected_str, &state_str, &version, &actor, &reason],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to record state history: {}", e)))?;
+
+        let payload = serde_json::json!({
+            "txid": txid.0,
+            "previous_state": expected_str,
+            "state": state_str,
+            "version": version,
+            "actor": actor,
+            "reason": reason,
+            "recipient": row.get::<_, String>(1),
+            "amount_sats": row.get::<_, i64>(2),
+            "changed_at": row.get::<_, chrono::DateTime<Utc>>(3),
+        });
+        tx.execute(
+            ENQUEUE_WEBHOOK_SQL,
+            &[
+                &uuid::Uuid::new_v4().to_string(),
+                &crate::WEBHOOK_EVENT_TX_STATE_CHANGED,
+                &payload,
+            ],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to enqueue webhook event: {}", e)))?;
+        tx.execute(
+            APPEND_EVENT_SQL,
+            &[
+                &crate::EVENT_TX_STATE_CHANGED,
+                &Some(txid.0.as_str()),
+                &None::<i64>,
+                &payload,
+            ],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to append cluster event: {}", e)))?;
+        self.append_audit_entry(
+            &tx,
+            "transaction_state_change",
+            None,
+            Some(txid.0.as_str()),
+            &serde_json::json!({
+                "old_state": expected_str,
+                "new_state": state_str,
+                "version": version,
+                "actor": actor,
+                "reason": reason,
+            }),
+        )
+        .await?;
+
+        // A withdrawal that ends without reaching the network gives the
+        // user's funds back. Once broadcast (bitcoin_txid set) its inputs may
+        // be spent even though it failed here, so the debit stands.
+        if new_state.is_terminal() && new_state != TransactionState::Confirmed {
+            tx.execute(
+                r#"
+                INSERT INTO ledger_entries (user_id, amount_sats, entry_type, txid)
+                SELECT l.user_id, -l.amount_sats, 'withdrawal_reversal', l.txid
+                FROM ledger_entries l
+                JOIN transactions t ON t.txid = l.txid
+                WHERE l.txid = $1 AND l.entry_type = 'withdrawal' AND t.bitcoin_txid IS NULL
+                ON CONFLICT DO NOTHING
+                "#,
+                &[&txid.0],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to reverse withdrawal: {}", e)))?;
+        }
+
+        // Rejected, failed and aborted transactions will never spend their
+        // inputs, and confirmed ones already did. A failed transaction that
+        // was broadcast may still confirm, so its inputs stay reserved.
+        if new_state.is_terminal() {
+            tx.execute(
+                r#"
+                DELETE FROM utxo_reservations r
+                USING transactions t
+                WHERE r.txid = $1 AND t.txid = r.txid
+                  AND (t.bitcoin_txid IS NULL OR t.state = 'confirmed')
+                "#,
+                &[&txid.0],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to release UTXO reservations: {}", e)))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit state update: {}", e)))?;
+
+        Ok(version as u64)
+    }
+
+    /// Recorded state transitions of a transaction, oldest first
+    pub async fn get_transaction_state_history(
+        &self,
+        txid: &TxId,
+    ) -> Result<Vec<crate::StateTransition>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT from_state, to_state, version, actor, reason, created_at
+                FROM transaction_state_history
+                WHERE txid = $1
+                ORDER BY version
+                "#,
+                &[&txid.0],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get state history: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| crate::StateTransition {
+                from: parse_transaction_state(r.get(0)),
+                to: parse_transaction_state(r.get(1)),
+                version: r.get::<_, i64>(2) as u64,
+                actor: r.get(3),
+                reason: r.get(4),
+                created_at: r.get(5),
+            })
+            .collect())
+    }
+
+    /// Record a signing or broadcast attempt
+    ///
+    /// Returns false if the attempt number was already recorded (another
+    /// node made the same attempt).
+    pub async fn record_transaction_attempt(&self, txid: &TxId, attempt: &crate::TransactionAttempt) -> Result<bool> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let excluded: Vec<i64> = attempt.excluded_nodes.iter().map(|n| n.0 as i64).collect();
+        let inserted = client
+            .execute(
+                r#"
+                INSERT INTO transaction_attempts (txid, operation, attempt, outcome, error,
+                    coordinator_node_id, excluded_node_ids, faulty_node_id, started_at, finished_at, retry_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                ON CONFLICT (txid, operation, attempt) DO NOTHING
+                "#,
+                &[
+                    &txid.0,
+                    &attempt.operation.as_str(),
+                    &(attempt.attempt as i32),
+                    &attempt.outcome.as_str(),
+                    &attempt.error,
+                    &(attempt.coordinator.0 as i64),
+                    &excluded,
+                    &attempt.faulty_node.map(|n| n.0 as i64),
+                    &attempt.started_at,
+                    &attempt.finished_at,
+                    &attempt.retry_at,
+                ],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to record transaction attempt: {}", e)))?;
+
+        Ok(inserted > 0)
+    }
+
+    /// Signing and broadcast attempts of a transaction, oldest first
+    pub async fn get_transaction_attempts(&self, txid: &TxId) -> Result<Vec<crate::TransactionAttempt>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT operation, attempt, outcome, error, coordinator_node_id, excluded_node_ids,
+                    faulty_node_id, started_at, finished_at, retry_at
+                FROM transaction_attempts
+                WHERE txid = $1
+                ORDER BY started_at, id
+                "#,
+                &[&txid.0],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get transaction attempts: {}", e)))?;
+
+        rows.iter()
+            .map(|r| {
+                let operation: String = r.get(0);
+                let outcome: String = r.get(2);
+                Ok(crate::TransactionAttempt {
+                    operation: crate::AttemptOperation::parse(&operation).ok_or_else(|| {
+                        Error::StorageError(format!("Unknown attempt operation: {}", operation))
+                    })?,
+                    attempt: r.get::<_, i32>(1) as u32,
+                    outcome: crate::AttemptOutcome::parse(&outcome).ok_or_else(|| {
+                        Error::StorageError(format!("Unknown attempt outcome: {}", outcome))
+                    })?,
+                    error: r.get(3),
+                    coordinator: NodeId(r.get::<_, i64>(4) as u64),
+                    excluded_nodes: r
+                        .get::<_, Vec<i64>>(5)
+                        .into_iter()
+                        .map(|n| NodeId(n as u64))
+                        .collect(),
+                    faulty_node: r.get::<_, Option<i64>>(6).map(|n| NodeId(n as u64)),
+                    started_at: r.get(7),
+                    finished_at: r.get(8),
+                    retry_at: r.get(9),
+                })
+            })
+            .collect()
+    }
+
+    /// Record the fencing token of a node that just took the signing lock
+    ///
+    /// From then on only that holder can store the signed transaction, see
+    /// [`set_signed_transaction`](Self::set_signed_transaction). Fails with
+    /// `StaleFencingToken` if a newer holder already claimed the transaction.
+    pub async fn claim_signing(&self, txid: &TxId, fencing_token: u64) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let token = fencing_token as i64;
+        let updated = client
+            .execute(
+                r#"
+                UPDATE transactions SET signing_fencing_token = $2, updated_at = NOW()
+                WHERE txid = $1
+                  AND (signing_fencing_token IS NULL OR signing_fencing_token <= $2)
+                "#,
+                &[&txid.0, &token],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to claim signing: {}", e)))?;
+
+        if updated == 0 {
+            return Err(Self::fencing_error(&client, txid, fencing_token).await?);
+        }
+
+        Ok(())
+    }
+
+    /// Set signed transaction
+    ///
+    /// `fencing_token` is the token of the signing lock held while signing,
+    /// recorded by [`claim_signing`](Self::claim_signing) when the lock was
+    /// taken. The write is rejected with `StaleFencingToken` unless that
+    /// token is still the latest claim, i.e. no other node took the lock
+    /// over in the meantime.
+    pub async fn set_signed_transaction(
+        &self,
+        txid: &TxId,
+        signed_tx: &[u8],
+        fencing_token: u64,
+    ) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let token = fencing_token as i64;
+        let updated = client
+            .execute(
+                r#"
+                UPDATE transactions SET signed_tx = $1, updated_at = NOW()
+                WHERE txid = $2 AND signing_fencing_token = $3
+                "#,
+                &[&signed_tx, &txid.0, &token],
+            )
+            .await
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to set signed transaction: {}", e))
+            })?;
+
+        if updated == 0 {
+            return Err(Self::fencing_error(&client, txid, fencing_token).await?);
+        }
+
+        info!("Updated signed transaction: txid={}", txid);
+
+        Ok(())
+    }
+
+    /// Why a write fenced with `fencing_token` matched no row
+    async fn fencing_error(
+        client: &deadpool_postgres::Client,
+        txid: &TxId,
+        fencing_token: u64,
+    ) -> Result<Error> {
+        let row = client
+            .query_opt(
+                "SELECT signing_fencing_token FROM transactions WHERE txid = $1",
+                &[&txid.0],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get fencing token: {}", e)))?;
+
+        Ok(match row {
+            None => Error::TransactionNotFound(txid.clone()),
+            Some(r) => Error::StaleFencingToken {
+                resource: format!("signing of {}", txid),
+                token: fencing_token,
+                current: r.get::<_, Option<i64>>(0).unwrap_or(0) as u64,
+            },
+        })
+    }
+
+    /// Get transaction by txid
+    pub async fn get_transaction(&self, txid: &TxId) -> Result<Option<Transaction>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                r#"
+                SELECT id, txid, state, unsigned_tx, signed_tx, recipient,
+                       amount_sats, fee_sats, metadata, created_at, updated_at
+                FROM transactions
+                WHERE txid = $1
+                "#,
+                &[&txid.0],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get transaction: {}", e)))?;
+
+        Ok(row.map(|r| Transaction {
+            id: r.get(0),
+            txid: TxId(r.get(1)),
+            state: parse_transaction_state(r.get(2)),
+            unsigned_tx: r.get(3),
+            signed_tx: r.get(4),
+            recipient: r.get(5),
+            amount_sats: r.get::<_, i64>(6) as u64,
+            fee_sats: r.get::<_, i64>(7) as u64,
+            metadata: r.get(8),
+            created_at: r.get(9),
+            updated_at: r.get(10),
+        }))
+    }
+
+    /// List all transactions with optional pagination
+    pub async fn list_all_transactions(
+        &self,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<Vec<Transaction>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let limit_val = limit.unwrap_or(100) as i64;
+        let offset_val = offset.unwrap_or(0) as i64;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT id, txid, state, unsigned_tx, signed_tx, recipient,
+                       amount_sats, fee_sats, metadata, created_at, updated_at
+                FROM transactions
+                ORDER BY created_at DESC
+                LIMIT $1 OFFSET $2
+                "#,
+                &[&limit_val, &offset_val],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to query transactions: {}", e)))?;
+
+        let mut transactions = Vec::new();
+        for row in rows {
+            transactions.push(Transaction {
+                id: row.get(0),
+                txid: TxId(row.get(1)),
+                state: parse_transaction_state(row.get(2)),
+                unsigned_tx: row.get(3),
+                signed_tx: row.get(4),
+                recipient: row.get(5),
+                amount_sats: row.get::<_, i64>(6) as u64,
+                fee_sats: row.get::<_, i64>(7) as u64,
+                metadata: row.get(8),
+                created_at: row.get(9),
+                updated_at: row.get(10),
+            });
+        }
+
+        info!(
+            "Listed {} transactions (limit: {}, offset: {})",
+            transactions.len(),
+            limit_val,
+            offset_val
+        );
+
+        Ok(transactions)
+    }
+
+    /// Create a voting round
+    pub async fn create_voting_round(&self, round: &VotingRound) -> Result<i64> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_one(
+                r#"
+                INSERT INTO voting_rounds (tx_id, round_number, total_nodes, threshold, timeout_at)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING id
+                "#,
+                &[
+                    &round.tx_id.0,
+                    &(round.round_number as i32),
+                    &(round.total_nodes as i32),
+                    &(round.threshold as i32),
+                    &round.timeout_at,
+                ],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to create voting round: {}", e)))?;
+
+        let id: i64 = row.get(0);
+        info!("Created voting round: id={} tx_id={}", id, round.tx_id);
+
+        Ok(id)
+    }
+
+    /// Update voting round status
+    pub async fn update_voting_round(
+        &self,
+        round_id: i64,
+        votes_received: u32,
+        approved: bool,
+        completed: bool,
+    ) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
                 r#"
                 UPDATE voting_rounds
                 SET votes_received = $1, approved = $2, completed = $3,
@@ -444,157 +1264,2183 @@ impl PostgresStorage {
                 WHERE id = $4
                 "#,
                 &[
-                    &(votes_received as i32),
-                    &approved,
-                    &completed,
-                    &round_id,
+                    &(votes_received as i32),
+                    &approved,
+                    &completed,
+                    &round_id,
+                ],
+            )
+            .await
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to update voting round: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Get voting round by transaction ID
+    pub async fn get_voting_round_by_txid(&self, txid: &str) -> Result<Option<VotingRound>> {
+        let client = self.pool.get().await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client.query_opt(
+            r#"
+            SELECT id, tx_id, round_number, total_nodes, threshold,
+                   votes_received, approved, completed, started_at,
+                   completed_at, timeout_at
+            FROM voting_rounds
+            WHERE tx_id = $1
+            ORDER BY id DESC
+            LIMIT 1
+            "#,
+            &[&txid],
+        ).await
+        .map_err(|e| Error::StorageError(format!("Failed to get voting round: {}", e)))?;
+
+        Ok(row.map(|r| VotingRound {
+            id: r.get::<_, i64>(0),
+            tx_id: TxId(r.get::<_, String>(1)),
+            round_number: r.get::<_, i32>(2) as u32,
+            total_nodes: r.get::<_, i32>(3) as u32,
+            threshold: r.get::<_, i32>(4) as u32,
+            votes_received: r.get::<_, i32>(5) as u32,
+            approved: r.get::<_, bool>(6),
+            completed: r.get::<_, bool>(7),
+            started_at: r.get::<_, chrono::DateTime<chrono::Utc>>(8),
+            completed_at: r.get::<_, Option<chrono::DateTime<chrono::Utc>>>(9),
+            timeout_at: r.get::<_, chrono::DateTime<chrono::Utc>>(10),
+        }))
+    }
+
+    /// Update voting round approved status
+    pub async fn update_voting_round_approved(&self, round_id: i64, approved: bool) -> Result<()> {
+        let client = self.pool.get().await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client.execute(
+            r#"
+            UPDATE voting_rounds
+            SET approved = $1, completed = true, completed_at = NOW()
+            WHERE id = $2
+            "#,
+            &[&approved, &round_id],
+        ).await
+        .map_err(|e| Error::StorageError(format!("Failed to update voting round approved: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Update voting round completed status (without approval)
+    pub async fn update_voting_round_completed(&self, round_id: i64) -> Result<()> {
+        let client = self.pool.get().await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client.execute(
+            r#"
+            UPDATE voting_rounds
+            SET completed = true, completed_at = NOW()
+            WHERE id = $1
+            "#,
+            &[&round_id],
+        ).await
+        .map_err(|e| Error::StorageError(format!("Failed to update voting round completed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Record presignature usage
+    pub async fn record_presignature_usage(&self, usage: &PresignatureUsage) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let presig_id_str = usage.presig_id.0.to_string();
+
+        client
+            .execute(
+                r#"
+                INSERT INTO presignature_usage (presig_id, transaction_id, generation_time_ms, protocol)
+                VALUES ($1, $2, $3, 'cggmp24')
+                "#,
+                &[
+                    &presig_id_str,
+                    &usage.transaction_id,
+                    &usage.generation_time_ms,
+                ],
+            )
+            .await
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to record presignature usage: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Update node status
+    pub async fn update_node_status(
+        &self,
+        node_id: NodeId,
+        status: &str,
+        last_heartbeat: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                r#"
+                INSERT INTO node_status (node_id, status, last_heartbeat)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (node_id) DO UPDATE
+                SET status = $2, last_heartbeat = $3, updated_at = NOW()
+                "#,
+                &[&(node_id.0 as i64), &status, &last_heartbeat],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to update node status: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Log audit event
+    pub async fn log_audit_event(
+        &self,
+        event_type: &str,
+        node_id: Option<NodeId>,
+        tx_id: Option<&TxId>,
+        details: serde_json::Value,
+    ) -> Result<()> {
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        self.append_audit_entry(&tx, event_type, node_id, tx_id.map(|t| t.0.as_str()), &details)
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit audit event: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Append an entry to the audit chain inside `tx`
+    ///
+    /// Locks the chain head, so concurrent appends from any node are
+    /// serialized and every entry links to the one committed before it.
+    async fn append_audit_entry(
+        &self,
+        tx: &tokio_postgres::Transaction<'_>,
+        event_type: &str,
+        node_id: Option<NodeId>,
+        tx_id: Option<&str>,
+        details: &serde_json::Value,
+    ) -> Result<u64> {
+        let head = tx
+            .query_one("SELECT seq, entry_hash FROM audit_chain_head FOR UPDATE", &[])
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to lock audit chain head: {}", e)))?;
+
+        let seq = head.get::<_, i64>(0) as u64 + 1;
+        let prev_hash: Vec<u8> = head.get(1);
+        // Postgres keeps microseconds; hash exactly what will be read back
+        let timestamp = Utc::now().trunc_subsecs(6);
+        let node_id = node_id.map(|n| n.0);
+        let signer_node_id = self.audit_signer.as_ref().map(|s| s.node_id().0);
+
+        let entry_hash = crate::audit::compute_entry_hash(
+            seq,
+            &prev_hash,
+            event_type,
+            node_id,
+            tx_id,
+            details,
+            timestamp,
+            signer_node_id,
+        );
+        let signature = self.audit_signer.as_ref().map(|s| s.sign(&entry_hash));
+
+        tx.execute(
+            r#"
+            INSERT INTO audit_log (
+                seq, event_type, node_id, tx_id, details, timestamp,
+                prev_hash, entry_hash, signer_node_id, signature
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+            &[
+                &(seq as i64),
+                &event_type,
+                &node_id.map(|n| n as i64),
+                &tx_id,
+                details,
+                &timestamp,
+                &prev_hash,
+                &&entry_hash[..],
+                &signer_node_id.map(|n| n as i64),
+                &signature,
+            ],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to log audit event: {}", e)))?;
+
+        tx.execute(
+            "UPDATE audit_chain_head SET seq = $1, entry_hash = $2, updated_at = NOW()",
+            &[&(seq as i64), &&entry_hash[..]],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to advance audit chain head: {}", e)))?;
+
+        Ok(seq)
+    }
+
+    /// Chained audit entries after `after_seq`, in chain order
+    pub async fn list_audit_entries(&self, after_seq: u64, limit: i64) -> Result<Vec<AuditEntry>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT seq, event_type, node_id, tx_id, details, timestamp,
+                       prev_hash, entry_hash, signer_node_id, signature
+                FROM audit_log
+                WHERE seq > $1
+                ORDER BY seq
+                LIMIT $2
+                "#,
+                &[&(after_seq as i64), &limit],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to list audit entries: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| AuditEntry {
+                seq: r.get::<_, i64>(0) as u64,
+                event_type: r.get(1),
+                node_id: r.get::<_, Option<i64>>(2).map(|n| n as u64),
+                tx_id: r.get(3),
+                details: r.get(4),
+                timestamp: r.get(5),
+                prev_hash: r.get::<_, Option<Vec<u8>>>(6).unwrap_or_default(),
+                entry_hash: r.get::<_, Option<Vec<u8>>>(7).unwrap_or_default(),
+                signer_node_id: r.get::<_, Option<i64>>(8).map(|n| n as u64),
+                signature: r.get(9),
+            })
+            .collect())
+    }
+
+    /// Sequence number and hash of the last chained audit entry
+    pub async fn audit_chain_head(&self) -> Result<(u64, Vec<u8>)> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_one("SELECT seq, entry_hash FROM audit_chain_head", &[])
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get audit chain head: {}", e)))?;
+
+        Ok((row.get::<_, i64>(0) as u64, row.get(1)))
+    }
+
+    /// Publish this node's audit public key
+    pub async fn register_audit_signer(&self, node_id: NodeId, public_key: &[u8]) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                r#"
+                INSERT INTO audit_signers (node_id, public_key)
+                VALUES ($1, $2)
+                ON CONFLICT (node_id) DO UPDATE
+                SET public_key = EXCLUDED.public_key, registered_at = NOW()
+                "#,
+                &[&(node_id.0 as i64), &public_key],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to register audit signer: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Audit public keys registered by the nodes
+    pub async fn get_audit_signers(&self) -> Result<std::collections::HashMap<u64, Vec<u8>>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query("SELECT node_id, public_key FROM audit_signers", &[])
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get audit signers: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| (r.get::<_, i64>(0) as u64, r.get(1)))
+            .collect())
+    }
+
+    /// Get node health status
+    pub async fn get_node_health(&self, node_id: NodeId) -> Result<Option<serde_json::Value>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                r#"
+                SELECT status, last_heartbeat, total_votes, total_violations, banned_until,
+                       CAST(EXTRACT(EPOCH FROM (NOW() - last_heartbeat)) AS DOUBLE PRECISION) as seconds_since_heartbeat
+                FROM node_status
+                WHERE node_id = $1
+                "#,
+                &[&(node_id.0 as i64)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get node health: {}", e)))?;
+
+        Ok(row.map(|r| {
+            serde_json::json!({
+                "status": r.get::<_, String>(0),
+                "last_heartbeat": r.get::<_, chrono::DateTime<chrono::Utc>>(1),
+                "total_votes": r.get::<_, i64>(2),
+                "total_violations": r.get::<_, i64>(3),
+                "banned_until": r.get::<_, Option<chrono::DateTime<chrono::Utc>>>(4),
+                "seconds_since_heartbeat": r.get::<_, f64>(5),
+            })
+        }))
+    }
+
+    /// Get all transactions by state
+    pub async fn get_transactions_by_state(&self, state: &str) -> Result<Vec<Transaction>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT id, txid, state, unsigned_tx, signed_tx, recipient,
+                       amount_sats, fee_sats, metadata, created_at, updated_at
+                FROM transactions
+                WHERE state = $1
+                ORDER BY created_at ASC
+                "#,
+                &[&state],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get transactions: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| Transaction {
+                id: r.get(0),
+                txid: TxId(r.get(1)),
+                state: parse_transaction_state(r.get(2)),
+                unsigned_tx: r.get(3),
+                signed_tx: r.get(4),
+                recipient: r.get(5),
+                amount_sats: r.get::<_, i64>(6) as u64,
+                fee_sats: r.get::<_, i64>(7) as u64,
+                metadata: r.get(8),
+                created_at: r.get(9),
+                updated_at: r.get(10),
+            })
+            .collect())
+    }
+
+    /// Get votes for a specific voting round
+    pub async fn get_votes_for_round(&self, tx_id: &TxId, round_number: u32) -> Result<Vec<Vote>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT v.node_id, v.tx_id, v.peer_id, v.round_id, v.approve, v.value,
+                       v.signature, v.public_key, v.created_at
+                FROM votes v
+                JOIN voting_rounds vr ON v.round_id = vr.id
+                WHERE vr.tx_id = $1 AND vr.round_number = $2
+                "#,
+                &[&tx_id.0, &(round_number as i32)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get votes: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| Vote {
+                tx_id: TxId(r.get(1)),
+                node_id: NodeId(r.get::<_, i64>(0) as u64),
+                peer_id: PeerId(r.get(2)),
+                round_id: r.get::<_, i64>(3) as u64,
+                approve: r.get(4),
+                value: r.get::<_, i64>(5) as u64,
+                signature: r.get(6),
+                public_key: r.get(7),
+                timestamp: r.get(8),
+            })
+            .collect())
+    }
+
+    /// Count votes for a specific transaction
+    pub async fn count_votes_for_transaction(&self, tx_id: &TxId) -> Result<u32> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_one(
+                r#"
+                SELECT COUNT(DISTINCT v.node_id) as vote_count
+                FROM votes v
+                JOIN voting_rounds vr ON v.round_id = vr.id
+                WHERE vr.tx_id = $1
+                "#,
+                &[&tx_id.0],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to count votes: {}", e)))?;
+
+        let count: i64 = row.get(0);
+        Ok(count as u32)
+    }
+
+    /// Count the nodes that voted on a transaction in round `first_round` or
+    /// later, i.e. the votes that still count after earlier rounds expired
+    pub async fn count_votes_in_rounds(&self, tx_id: &TxId, first_round: u32) -> Result<u32> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_one(
+                r#"
+                SELECT COUNT(DISTINCT v.node_id) as vote_count
+                FROM votes v
+                JOIN voting_rounds vr ON v.round_id = vr.id
+                WHERE vr.tx_id = $1 AND vr.round_number >= $2
+                "#,
+                &[&tx_id.0, &(first_round as i32)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to count votes: {}", e)))?;
+
+        let count: i64 = row.get(0);
+        Ok(count as u32)
+    }
+
+    /// Get signed transaction bytes
+    pub async fn get_signed_transaction(&self, tx_id: &TxId) -> Result<Option<Vec<u8>>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                "SELECT signed_tx FROM transactions WHERE txid = $1",
+                &[&tx_id.0],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get signed transaction: {}", e)))?;
+
+        Ok(row.and_then(|r| r.get(0)))
+    }
+
+    /// Record the txid a transaction was broadcast under
+    pub async fn update_transaction_txid(&self, tx_id: &TxId, txid: &str) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                "UPDATE transactions SET txid = $1, bitcoin_txid = $1, updated_at = NOW() WHERE txid = $2",
+                &[&txid, &tx_id.0],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to update transaction txid: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Record audit event for a transaction
+    pub async fn record_audit_event(&self, tx_id: &TxId, event_type: &str, details: &str) -> Result<()> {
+        self.log_audit_event(
+            event_type,
+            None,
+            Some(tx_id),
+            serde_json::json!({ "details": details }),
+        )
+        .await
+    }
+
+    /// Update transaction confirmations count
+    pub async fn update_transaction_confirmations(&self, tx_id: &TxId, confirmations: u32) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                "UPDATE transactions SET confirmations = $1, updated_at = NOW() WHERE txid = $2",
+                &[&(confirmations as i32), &tx_id.0],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to update confirmations: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get expired transactions (transactions that have been in voting/signing state for too long)
+    pub async fn get_expired_transactions(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<Vec<Transaction>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                "SELECT id, txid, state, unsigned_tx, signed_tx, recipient, amount_sats, fee_sats,
+                        metadata, created_at, updated_at
+                 FROM transactions
+                 WHERE (state = 'voting' OR state = 'signing' OR state = 'broadcasting')
+                   AND updated_at < $1
+                 ORDER BY updated_at ASC",
+                &[&cutoff],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get expired transactions: {}", e)))?;
+
+        let transactions = rows
+            .into_iter()
+            .map(|row| {
+                Transaction {
+                    id: row.get(0),
+                    txid: TxId(row.get(1)),
+                    state: parse_transaction_state(row.get(2)),
+                    unsigned_tx: row.get(3),
+                    signed_tx: row.get(4),
+                    recipient: row.get(5),
+                    amount_sats: row.get::<_, i64>(6) as u64,
+                    fee_sats: row.get::<_, i64>(7) as u64,
+                    metadata: row.get(8),
+                    created_at: row.get(9),
+                    updated_at: row.get(10),
+                }
+            })
+            .collect();
+
+        Ok(transactions)
+    }
+
+    /// Create a new DKG ceremony record
+    pub async fn create_dkg_ceremony(&self, ceremony: &crate::DkgCeremony) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        // Convert UUID to string for database storage
+        let session_id_str = ceremony.session_id.to_string();
+
+        let result = client
+            .execute(
+                r#"
+                INSERT INTO dkg_ceremonies (session_id, protocol, threshold, total_nodes, status, started_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+                &[
+                    &session_id_str,
+                    &ceremony.protocol,
+                    &(ceremony.threshold as i32),
+                    &(ceremony.total_nodes as i32),
+                    &ceremony.status,
+                    &ceremony.started_at,
+                ],
+            )
+            .await;
+
+        match result {
+            Ok(_) => {
+                info!("Created DKG ceremony: session_id={} protocol={}", session_id_str, ceremony.protocol);
+                Ok(())
+            }
+            Err(e) => {
+                error!("Failed to insert DKG ceremony: SQL Error: {:?}", e);
+                error!("Ceremony data: session_id={}, protocol={}, threshold={}, total_nodes={}",
+                       session_id_str, ceremony.protocol, ceremony.threshold, ceremony.total_nodes);
+                Err(Error::StorageError(format!("Failed to create DKG ceremony: {}", e)))
+            }
+        }
+    }
+
+    /// Mark DKG ceremony as completed
+    ///
+    /// `xpub` is the root BIP32 extended public key, when the key shares carry
+    /// a jointly generated chain code.
+    pub async fn complete_dkg_ceremony(
+        &self,
+        session_id: uuid::Uuid,
+        public_key: &[u8],
+        address: &str,
+        xpub: Option<&str>,
+    ) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                r#"
+                UPDATE dkg_ceremonies
+                SET status = 'completed', public_key = $1, address = $2, xpub = $3, completed_at = NOW()
+                WHERE session_id = $4
+                "#,
+                &[&public_key, &address, &xpub, &session_id.to_string()],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to complete DKG ceremony: {}", e)))?;
+
+        info!("Completed DKG ceremony: session_id={} address={}", session_id, address);
+
+        Ok(())
+    }
+
+    /// Mark DKG ceremony as failed
+    pub async fn fail_dkg_ceremony(&self, session_id: uuid::Uuid, error: &str) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                r#"
+                UPDATE dkg_ceremonies
+                SET status = 'failed', completed_at = NOW()
+                WHERE session_id = $1
+                "#,
+                &[&session_id.to_string()],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to fail DKG ceremony: {}", e)))?;
+
+        info!("Failed DKG ceremony: session_id={} error={}", session_id, error);
+
+        Ok(())
+    }
+
+    /// Get a specific DKG ceremony by session ID
+    pub async fn get_dkg_ceremony(&self, session_id: uuid::Uuid) -> Result<crate::DkgCeremony> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_one(
+                r#"
+                SELECT session_id, protocol, threshold, total_nodes, status,
+                       public_key, address, started_at, completed_at, error, xpub
+                FROM dkg_ceremonies
+                WHERE session_id = $1
+                "#,
+                &[&session_id.to_string()],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get DKG ceremony: {}", e)))?;
+
+        let session_id_str: String = row.get(0);
+        let protocol: String = row.get(1);
+        let threshold: i32 = row.get(2);
+        let total_nodes: i32 = row.get(3);
+        let status: String = row.get(4);
+        let public_key: Option<Vec<u8>> = row.get(5);
+        let address: Option<String> = row.get(6);
+        let started_at: chrono::DateTime<chrono::Utc> = row.get(7);
+        let completed_at: Option<chrono::DateTime<chrono::Utc>> = row.get(8);
+        let error: Option<String> = row.get(9);
+        let xpub: Option<String> = row.get(10);
+
+        Ok(crate::DkgCeremony {
+            session_id: uuid::Uuid::parse_str(&session_id_str)
+                .map_err(|e| Error::StorageError(format!("Invalid UUID: {}", e)))?,
+            protocol,
+            threshold: threshold as u32,
+            total_nodes: total_nodes as u32,
+            status,
+            public_key,
+            address,
+            started_at,
+            completed_at,
+            error,
+            xpub,
+        })
+    }
+
+    /// List all DKG ceremonies
+    pub async fn list_dkg_ceremonies(&self) -> Result<Vec<crate::DkgCeremony>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT session_id, protocol, threshold, total_nodes, status,
+                       public_key, address, started_at, completed_at, error, xpub
+                FROM dkg_ceremonies
+                ORDER BY started_at DESC
+                "#,
+                &[],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to list DKG ceremonies: {}", e)))?;
+
+        let mut ceremonies = Vec::new();
+        for row in rows {
+            let session_id_str: String = row.get(0);
+            let protocol: String = row.get(1);
+            let threshold: i32 = row.get(2);
+            let total_nodes: i32 = row.get(3);
+            let status: String = row.get(4);
+            let public_key: Option<Vec<u8>> = row.get(5);
+            let address: Option<String> = row.get(6);
+            let started_at: chrono::DateTime<chrono::Utc> = row.get(7);
+            let completed_at: Option<chrono::DateTime<chrono::Utc>> = row.get(8);
+            let error: Option<String> = row.get(9);
+            let xpub: Option<String> = row.get(10);
+        let xpub: Option<String> = row.get(10);
+
+            if let Ok(session_id) = uuid::Uuid::parse_str(&session_id_str) {
+                ceremonies.push(crate::DkgCeremony {
+                    session_id,
+                    protocol,
+                    threshold: threshold as u32,
+                    total_nodes: total_nodes as u32,
+                    status,
+                    public_key,
+                    address,
+                    started_at,
+                    completed_at,
+                    error,
+                    xpub,
+                });
+            }
+        }
+
+        Ok(ceremonies)
+    }
+
+    /// Store encrypted key share for a node
+    pub async fn store_key_share(
+        &self,
+        session_id: uuid::Uuid,
+        node_id: NodeId,
+        encrypted_share: &[u8],
+    ) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        // Get ceremony ID from session_id
+        let ceremony_row = client
+            .query_opt(
+                "SELECT id FROM dkg_ceremonies WHERE session_id = $1",
+                &[&session_id.to_string()],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get ceremony: {}", e)))?;
+
+        let ceremony_id: i64 = ceremony_row
+            .ok_or_else(|| Error::StorageError(format!("Ceremony not found: {}", session_id)))?
+            .get(0);
+
+        client
+            .execute(
+                r#"
+                INSERT INTO key_shares (ceremony_id, node_id, encrypted_share)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (ceremony_id, node_id) DO UPDATE
+                SET encrypted_share = $3
+                "#,
+                &[&ceremony_id, &(node_id.0 as i64), &encrypted_share],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to store key share: {}", e)))?;
+
+        info!("Stored key share: session_id={} node_id={}", session_id, node_id);
+
+        Ok(())
+    }
+
+    /// Retrieve encrypted key share for a node
+    pub async fn get_key_share(&self, session_id: uuid::Uuid, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                r#"
+                SELECT ks.encrypted_share
+                FROM key_shares ks
+                JOIN dkg_ceremonies dc ON ks.ceremony_id = dc.id
+                WHERE dc.session_id = $1 AND ks.node_id = $2
+                "#,
+                &[&session_id.to_string(), &(node_id.0 as i64)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get key share: {}", e)))?;
+
+        Ok(row.map(|r| r.get(0)))
+    }
+
+    /// Get the latest key_share for a node (regardless of session)
+    ///
+    /// This returns the most recent key_share from the latest DKG ceremony.
+    /// Used for presignature generation when we need the current active key_share.
+    ///
+    /// # SORUN #18 FIX
+    /// Presignature generation needs key_share from DKG ceremony, but aux_info
+    /// has a different session ID. This method gets the latest key_share by
+    /// created_at timestamp instead of matching session IDs.
+    pub async fn get_latest_key_share(&self, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                r#"
+                SELECT ks.encrypted_share
+                FROM key_shares ks
+                JOIN dkg_ceremonies dc ON ks.ceremony_id = dc.id
+                WHERE ks.node_id = $1
+                ORDER BY dc.started_at DESC
+                LIMIT 1
+                "#,
+                &[&(node_id.0 as i64)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get latest key share: {}", e)))?;
+
+        Ok(row.map(|r| r.get(0)))
+    }
+
+    /// Store aux_info for a node
+    ///
+    /// Aux_info is auxiliary information (Paillier keys, ring-Pedersen parameters)
+    /// required for CGGMP24 presignature generation.
+    pub async fn store_aux_info(
+        &self,
+        session_id: uuid::Uuid,
+        node_id: NodeId,
+        aux_info_data: &[u8],
+    ) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                r#"
+                INSERT INTO aux_info (session_id, node_id, aux_info_data, created_at)
+                VALUES ($1, $2, $3, NOW())
+                ON CONFLICT (session_id, node_id) DO UPDATE
+                SET aux_info_data = $3, updated_at = NOW()
+                "#,
+                &[&session_id.to_string(), &(node_id.0 as i64), &aux_info_data],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to store aux_info: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get aux_info for a specific session and node
+    pub async fn get_aux_info(&self, session_id: uuid::Uuid, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                r#"
+                SELECT aux_info_data
+                FROM aux_info
+                WHERE session_id = $1 AND node_id = $2
+                "#,
+                &[&session_id.to_string(), &(node_id.0 as i64)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get aux_info: {}", e)))?;
+
+        Ok(row.map(|r| r.get(0)))
+    }
+
+    /// Get aux_info ceremony by session ID
+    ///
+    /// This is used by participant nodes when joining a ceremony.
+    /// Fixes SORUN #15.
+    pub async fn get_aux_info_ceremony(&self, session_id: uuid::Uuid) -> Result<crate::AuxInfoCeremony> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        // Try to get from aux_info_sessions table first (if it exists)
+        let row = client
+            .query_opt(
+                r#"
+                SELECT session_id, party_index, num_parties, status,
+                       started_at, completed_at, error
+                FROM aux_info_sessions
+                WHERE session_id = $1
+                "#,
+                &[&session_id.to_string()],
+            )
+            .await;
+
+        match row {
+            Ok(Some(row)) => {
+                let session_id_str: String = row.get(0);
+                let num_parties: i32 = row.get(2);
+                let status: String = row.get(3);
+                let started_at: chrono::DateTime<chrono::Utc> = row.get(4);
+                let completed_at: Option<chrono::DateTime<chrono::Utc>> = row.get(5);
+                let error: Option<String> = row.get(6);
+
+                Ok(crate::AuxInfoCeremony {
+                    session_id: uuid::Uuid::parse_str(&session_id_str)
+                        .map_err(|e| Error::StorageError(format!("Invalid session ID: {}", e)))?,
+                    num_parties: num_parties as u16,
+                    status,
+                    started_at,
+                    completed_at,
+                    error,
+                })
+            }
+            Ok(None) => {
+                // If not found in database, return a minimal ceremony object
+                // This can happen if coordinator hasn't persisted the ceremony yet
+                warn!("Aux info ceremony {} not found in database, creating minimal entry", session_id);
+                Ok(crate::AuxInfoCeremony {
+                    session_id,
+                    num_parties: 5, // Default to 5 parties
+                    status: "pending".to_string(),
+                    started_at: chrono::Utc::now(),
+                    completed_at: None,
+                    error: None,
+                })
+            }
+            Err(e) => {
+                // If table doesn't exist or other error, return minimal ceremony
+                warn!("Failed to get aux_info ceremony: {}, using defaults", e);
+                Ok(crate::AuxInfoCeremony {
+                    session_id,
+                    num_parties: 5,
+                    status: "pending".to_string(),
+                    started_at: chrono::Utc::now(),
+                    completed_at: None,
+                    error: None,
+                })
+            }
+        }
+    }
+
+    /// Get the latest aux_info for a node (most recent session)
+    pub async fn get_latest_aux_info(&self, node_id: NodeId) -> Result<Option<(uuid::Uuid, Vec<u8>)>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                r#"
+                SELECT session_id, aux_info_data
+                FROM aux_info
+                WHERE node_id = $1
+                ORDER BY created_at DESC
+                LIMIT 1
+                "#,
+                &[&(node_id.0 as i64)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get latest aux_info: {}", e)))?;
+
+        Ok(row.map(|r| {
+            let session_id_str: String = r.get(0);
+            let data: Vec<u8> = r.get(1);
+            (uuid::Uuid::parse_str(&session_id_str).unwrap(), data)
+        }))
+    }
+
+    /// Get the latest key_share for a node produced by a specific DKG protocol
+    ///
+    /// `get_latest_key_share` returns whichever ceremony ran last, which may be a
+    /// CGGMP24 share when a FROST share is needed (or vice versa).
+    pub async fn get_latest_key_share_for_protocol(
+        &self,
+        node_id: NodeId,
+        protocol: &str,
+    ) -> Result<Option<Vec<u8>>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                r#"
+                SELECT ks.encrypted_share
+                FROM key_shares ks
+                JOIN dkg_ceremonies dc ON ks.ceremony_id = dc.id
+                WHERE ks.node_id = $1 AND dc.protocol = $2 AND dc.status = 'completed'
+                ORDER BY dc.started_at DESC
+                LIMIT 1
+                "#,
+                &[&(node_id.0 as i64), &protocol],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get latest key share: {}", e)))?;
+
+        Ok(row.map(|r| r.get(0)))
+    }
+
+    // ============================================================================
+    // FROST Nonce Pool
+    // ============================================================================
+
+    /// Persist freshly generated FROST nonces for a node
+    pub async fn store_frost_nonces(
+        &self,
+        node_id: NodeId,
+        nonces: &[crate::StoredFrostNonce],
+    ) -> Result<()> {
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        for nonce in nonces {
+            tx.execute(
+                r#"
+                INSERT INTO frost_nonces (nonce_id, node_id, party_index, secret_nonces, commitment, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+                &[
+                    &nonce.nonce_id.to_string(),
+                    &(node_id.0 as i64),
+                    &(nonce.party_index as i32),
+                    &nonce.secret_nonces,
+                    &nonce.commitment,
+                    &nonce.created_at,
+                ],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to store FROST nonce: {}", e)))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit FROST nonces: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Atomically consume a FROST nonce and return its secret
+    ///
+    /// The secret is wiped in the same statement that marks the nonce consumed,
+    /// so a nonce can never be handed out twice - even across restarts or
+    /// concurrent signing requests. Returns `None` if the nonce does not exist
+    /// or was already consumed.
+    pub async fn consume_frost_nonce(
+        &self,
+        node_id: NodeId,
+        nonce_id: uuid::Uuid,
+        session_id: &str,
+    ) -> Result<Option<Vec<u8>>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                r#"
+                UPDATE frost_nonces f
+                SET secret_nonces = NULL, consumed_at = NOW(), consumed_by = $3
+                FROM (
+                    SELECT id, secret_nonces
+                    FROM frost_nonces
+                    WHERE nonce_id = $1 AND node_id = $2 AND consumed_at IS NULL
+                    FOR UPDATE
+                ) old
+                WHERE f.id = old.id
+                RETURNING old.secret_nonces
+                "#,
+                &[&nonce_id.to_string(), &(node_id.0 as i64), &session_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to consume FROST nonce: {}", e)))?;
+
+        Ok(row.map(|r| r.get(0)))
+    }
+
+    /// Load all unconsumed FROST nonces for a node (used on startup)
+    pub async fn load_frost_nonces(&self, node_id: NodeId) -> Result<Vec<crate::StoredFrostNonce>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT nonce_id, party_index, secret_nonces, commitment, created_at
+                FROM frost_nonces
+                WHERE node_id = $1 AND consumed_at IS NULL
+                ORDER BY created_at ASC
+                "#,
+                &[&(node_id.0 as i64)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to load FROST nonces: {}", e)))?;
+
+        let mut nonces = Vec::with_capacity(rows.len());
+        for row in rows {
+            let nonce_id_str: String = row.get(0);
+            let party_index: i32 = row.get(1);
+            match uuid::Uuid::parse_str(&nonce_id_str) {
+                Ok(nonce_id) => nonces.push(crate::StoredFrostNonce {
+                    nonce_id,
+                    party_index: party_index as u16,
+                    secret_nonces: row.get(2),
+                    commitment: row.get(3),
+                    created_at: row.get(4),
+                }),
+                Err(e) => warn!("Skipping FROST nonce with invalid id {}: {}", nonce_id_str, e),
+            }
+        }
+
+        Ok(nonces)
+    }
+
+    /// Discard unconsumed FROST nonces created before `cutoff`
+    ///
+    /// Expired nonces are wiped the same way as consumed ones so their
+    /// commitments can never be satisfied later.
+    pub async fn expire_frost_nonces(
+        &self,
+        node_id: NodeId,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let count = client
+            .execute(
+                r#"
+                UPDATE frost_nonces
+                SET secret_nonces = NULL, consumed_at = NOW(), consumed_by = 'expired'
+                WHERE node_id = $1 AND consumed_at IS NULL AND created_at < $2
+                "#,
+                &[&(node_id.0 as i64), &cutoff],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to expire FROST nonces: {}", e)))?;
+
+        Ok(count)
+    }
+
+    // ========================================================================
+    // Users
+    // ========================================================================
+
+    /// Get a user by user_id
+    pub async fn get_user(&self, user_id: &str) -> Result<Option<crate::User>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                r#"
+                SELECT user_id, username, password_hash, role, is_active,
+                       created_at, last_login_at
+                FROM users
+                WHERE user_id = $1
+                "#,
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get user: {}", e)))?;
+
+        Ok(row.map(|r| crate::User {
+            user_id: r.get(0),
+            username: r.get(1),
+            password_hash: r.get(2),
+            role: r.get(3),
+            is_active: r.get(4),
+            created_at: r.get(5),
+            last_login_at: r.get(6),
+        }))
+    }
+
+    /// Create the first admin account
+    ///
+    /// Returns `false` without changing anything if an active admin already
+    /// exists. Callers are serialized on a table lock, so nodes starting
+    /// together create at most one admin. An inactive account with the same
+    /// `user_id` (e.g. a deactivated seed user) is taken over.
+    pub async fn bootstrap_admin(&self, user_id: &str, password_hash: &str) -> Result<bool> {
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        tx.batch_execute("LOCK TABLE users IN SHARE ROW EXCLUSIVE MODE")
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to lock users: {}", e)))?;
+
+        let admin_exists: bool = tx
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM users WHERE role = 'admin' AND is_active)",
+                &[],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to check for admins: {}", e)))?
+            .get(0);
+        if admin_exists {
+            return Ok(false);
+        }
+
+        tx.execute(
+            r#"
+            INSERT INTO users (user_id, username, password_hash, role)
+            VALUES ($1, $1, $2, 'admin')
+            ON CONFLICT (user_id) DO UPDATE
+            SET password_hash = EXCLUDED.password_hash, role = 'admin', is_active = TRUE
+            "#,
+            &[&user_id, &password_hash],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to create admin: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit admin: {}", e)))?;
+
+        info!("Created admin user {}", user_id);
+        Ok(true)
+    }
+
+    /// Record a successful login
+    pub async fn record_user_login(&self, user_id: &str) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                "UPDATE users SET last_login_at = NOW() WHERE user_id = $1",
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to record login: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// List a user's addresses, newest derivation index first
+    pub async fn list_user_addresses(&self, user_id: &str) -> Result<Vec<crate::UserAddress>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT user_id, address, derivation_index, derivation_path, public_key,
+                       address_type, label, is_change, balance_sats, tx_count, created_at
+                FROM user_addresses
+                WHERE user_id = $1
+                ORDER BY is_change, derivation_index DESC
+                "#,
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to list user addresses: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| crate::UserAddress {
+                user_id: r.get(0),
+                address: r.get(1),
+                derivation_index: r.get::<_, i32>(2) as u32,
+                derivation_path: r.get(3),
+                public_key: r.get(4),
+                address_type: r.get(5),
+                label: r.get(6),
+                is_change: r.get(7),
+                balance_sats: r.get::<_, i64>(8) as u64,
+                tx_count: r.get::<_, i32>(9) as u32,
+                created_at: r.get(10),
+            })
+            .collect())
+    }
+
+    /// Reserve the next global HD derivation index
+    pub async fn next_derivation_index(&self) -> Result<u32> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_one("SELECT get_next_derivation_index()", &[])
+            .await
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to reserve derivation index: {}", e))
+            })?;
+
+        let index: Option<i32> = row.get(0);
+        index
+            .map(|i| i as u32)
+            .ok_or_else(|| Error::StorageError("Derivation index counter missing".to_string()))
+    }
+
+    /// Persist a newly derived user address
+    pub async fn create_user_address(&self, address: &crate::UserAddress) -> Result<()> {
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        tx.execute(
+            r#"
+            INSERT INTO user_addresses (
+                user_id, address, derivation_index, derivation_path, public_key,
+                address_type, label, is_change, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            &[
+                &address.user_id,
+                &address.address,
+                &(address.derivation_index as i32),
+                &address.derivation_path,
+                &address.public_key,
+                &address.address_type,
+                &address.label,
+                &address.is_change,
+                &address.created_at,
+            ],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to create user address: {}", e)))?;
+
+        self.append_audit_entry(
+            &tx,
+            "address_created",
+            None,
+            None,
+            &serde_json::json!({
+                "user_id": address.user_id,
+                "address": address.address,
+                "derivation_index": address.derivation_index,
+                "derivation_path": address.derivation_path,
+            }),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit user address: {}", e)))?;
+
+        info!(
+            "Created address {} for user {} at {}",
+            address.address, address.user_id, address.derivation_path
+        );
+
+        Ok(())
+    }
+
+    /// Update the cached balance and transaction count of a user address
+    pub async fn update_user_address_stats(
+        &self,
+        address: &str,
+        balance_sats: u64,
+        tx_count: u32,
+    ) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                r#"
+                UPDATE user_addresses
+                SET balance_sats = $2,
+                    last_used_at = CASE WHEN tx_count <> $3 THEN NOW() ELSE last_used_at END,
+                    tx_count = $3
+                WHERE address = $1
+                "#,
+                &[&address, &(balance_sats as i64), &(tx_count as i32)],
+            )
+            .await
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to update user address stats: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Get the user that submitted a transaction, if recorded
+    pub async fn get_transaction_owner(&self, txid: &TxId) -> Result<Option<String>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                "SELECT created_by FROM transactions WHERE txid = $1",
+                &[&txid.0],
+            )
+            .await
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to get transaction owner: {}", e))
+            })?;
+
+        Ok(row.and_then(|r| r.get(0)))
+    }
+
+    /// Get the BIP32 path of the key that signs a transaction's inputs
+    ///
+    /// Empty for transactions spending the wallet root address.
+    pub async fn get_transaction_derivation_path(&self, txid: &TxId) -> Result<Vec<u32>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                "SELECT derivation_path FROM transactions WHERE txid = $1",
+                &[&txid.0],
+            )
+            .await
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to get transaction derivation path: {}", e))
+            })?
+            .ok_or_else(|| Error::TransactionNotFound(txid.clone()))?;
+
+        let path: Vec<i32> = row.get(0);
+        Ok(path.into_iter().map(|i| i as u32).collect())
+    }
+
+    /// Get the recipient of the wallet's own transaction broadcast as `bitcoin_txid`
+    ///
+    /// `None` if the wallet did not create that transaction.
+    pub async fn get_wallet_transaction_recipient(&self, bitcoin_txid: &str) -> Result<Option<String>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
+                "SELECT recipient FROM transactions WHERE txid = $1 OR bitcoin_txid = $1 LIMIT 1",
+                &[&bitcoin_txid],
+            )
+            .await
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to get wallet transaction recipient: {}", e))
+            })?;
+
+        Ok(row.map(|r| r.get(0)))
+    }
+
+    /// List transactions submitted by a user with optional pagination
+    pub async fn list_transactions_by_owner(
+        &self,
+        user_id: &str,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<Vec<Transaction>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let limit_val = limit.unwrap_or(100) as i64;
+        let offset_val = offset.unwrap_or(0) as i64;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT id, txid, state, unsigned_tx, signed_tx, recipient,
+                       amount_sats, fee_sats, metadata, created_at, updated_at
+                FROM transactions
+                WHERE created_by = $1
+                ORDER BY created_at DESC
+                LIMIT $2 OFFSET $3
+                "#,
+                &[&user_id, &limit_val, &offset_val],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to query transactions: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Transaction {
+                id: row.get(0),
+                txid: TxId(row.get(1)),
+                state: parse_transaction_state(row.get(2)),
+                unsigned_tx: row.get(3),
+                signed_tx: row.get(4),
+                recipient: row.get(5),
+                amount_sats: row.get::<_, i64>(6) as u64,
+                fee_sats: row.get::<_, i64>(7) as u64,
+                metadata: row.get(8),
+                created_at: row.get(9),
+                updated_at: row.get(10),
+            })
+            .collect())
+    }
+
+    /// List every user address (for chain scanning)
+    pub async fn list_all_user_addresses(&self) -> Result<Vec<crate::UserAddress>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                r#"
+                SELECT user_id, address, derivation_index, derivation_path, public_key,
+                       address_type, label, is_change, balance_sats, tx_count, created_at
+                FROM user_addresses
+                ORDER BY id
+                "#,
+                &[],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to list user addresses: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| crate::UserAddress {
+                user_id: r.get(0),
+                address: r.get(1),
+                derivation_index: r.get::<_, i32>(2) as u32,
+                derivation_path: r.get(3),
+                public_key: r.get(4),
+                address_type: r.get(5),
+                label: r.get(6),
+                is_change: r.get(7),
+                balance_sats: r.get::<_, i64>(8) as u64,
+                tx_count: r.get::<_, i32>(9) as u32,
+                created_at: r.get(10),
+            })
+            .collect())
+    }
+
+    // ========================================================================
+    // Deposits & Ledger
+    // ========================================================================
+
+    /// Record a deposit output if it is not already known
+    ///
+    /// A previously reversed deposit whose transaction shows up again is
+    /// moved back to pending. Returns true if the deposit was inserted or
+    /// revived.
+    pub async fn record_deposit(
+        &self,
+        user_id: &str,
+        address: &str,
+        txid: &str,
+        vout: u32,
+        amount_sats: u64,
+    ) -> Result<bool> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let count = client
+            .execute(
+                r#"
+                INSERT INTO deposits (user_id, address, txid, vout, amount_sats)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (txid, vout) DO UPDATE SET status = 'pending'
+                WHERE deposits.status = 'reversed'
+                "#,
+                &[&user_id, &address, &txid, &(vout as i32), &(amount_sats as i64)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to record deposit: {}", e)))?;
+
+        Ok(count > 0)
+    }
+
+    /// Deposits whose chain state may still change
+    ///
+    /// Pending and credited deposits below `finality_depth` confirmations.
+    pub async fn list_unsettled_deposits(&self, finality_depth: u32) -> Result<Vec<crate::Deposit>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM deposits WHERE status IN ('pending', 'credited') AND confirmations < $1 ORDER BY id",
+                    DEPOSIT_COLUMNS
+                ),
+                &[&(finality_depth as i32)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to list deposits: {}", e)))?;
+
+        Ok(rows.iter().map(deposit_from_row).collect())
+    }
+
+    /// List deposits, newest first, optionally restricted to one user
+    pub async fn list_deposits(
+        &self,
+        user_id: Option<&str>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<Vec<crate::Deposit>> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let limit_val = limit.unwrap_or(100) as i64;
+        let offset_val = offset.unwrap_or(0) as i64;
+
+        let rows = client
+            .query(
+                &format!(
+                    r#"
+                    SELECT {} FROM deposits
+                    WHERE $1::TEXT IS NULL OR user_id = $1
+                    ORDER BY first_seen_at DESC, id DESC
+                    LIMIT $2 OFFSET $3
+                    "#,
+                    DEPOSIT_COLUMNS
+                ),
+                &[&user_id, &limit_val, &offset_val],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to list deposits: {}", e)))?;
+
+        Ok(rows.iter().map(deposit_from_row).collect())
+    }
+
+    /// Update the observed chain state of a deposit
+    pub async fn update_deposit_chain_state(
+        &self,
+        deposit_id: i64,
+        confirmations: u32,
+        block_height: Option<u64>,
+        block_hash: Option<&str>,
+    ) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                r#"
+                UPDATE deposits
+                SET confirmations = $2, block_height = $3, block_hash = $4
+                WHERE id = $1
+                "#,
+                &[
+                    &deposit_id,
+                    &(confirmations as i32),
+                    &block_height.map(|h| h as i64),
+                    &block_hash,
+                ],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to update deposit: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Credit a pending deposit to its user's ledger
+    ///
+    /// The status change and ledger entry are written in one transaction and
+    /// guarded by the current status, so concurrent watchers on several nodes
+    /// credit each deposit at most once. Returns false if the deposit was not
+    /// pending.
+    pub async fn credit_deposit(&self, deposit_id: i64) -> Result<bool> {
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        let row = tx
+            .query_opt(
+                r#"
+                UPDATE deposits SET status = 'credited', credited_at = NOW()
+                WHERE id = $1 AND status = 'pending'
+                RETURNING user_id, amount_sats
+                "#,
+                &[&deposit_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to credit deposit: {}", e)))?;
+
+        let Some(row) = row else {
+            return Ok(false);
+        };
+        let user_id: String = row.get(0);
+        let amount: i64 = row.get(1);
+
+        tx.execute(
+            r#"
+            INSERT INTO ledger_entries (user_id, amount_sats, entry_type, deposit_id)
+            VALUES ($1, $2, 'deposit_credit', $3)
+            "#,
+            &[&user_id, &amount, &deposit_id],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to write ledger entry: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit deposit credit: {}", e)))?;
+
+        info!("Credited deposit {} ({} sats) to user {}", deposit_id, amount, user_id);
+
+        Ok(true)
+    }
+
+    /// Undo a deposit after a reorg
+    ///
+    /// A credited deposit gets a compensating ledger entry. If `dropped`, the
+    /// transaction is gone entirely and the deposit becomes reversed;
+    /// otherwise it returns to pending and is credited again once it regains
+    /// the confirmation depth. Returns the amount debited (0 if the deposit
+    /// had not been credited).
+    pub async fn reverse_deposit(&self, deposit_id: i64, dropped: bool) -> Result<u64> {
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        let row = tx
+            .query_opt(
+                "SELECT user_id, amount_sats, status FROM deposits WHERE id = $1 FOR UPDATE",
+                &[&deposit_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to load deposit: {}", e)))?;
+
+        let Some(row) = row else {
+            return Ok(0);
+        };
+        let user_id: String = row.get(0);
+        let amount: i64 = row.get(1);
+        let status: String = row.get(2);
+
+        let new_status = if dropped { "reversed" } else { "pending" };
+        let debited = match status.as_str() {
+            "credited" => {
+                tx.execute(
+                    r#"
+                    INSERT INTO ledger_entries (user_id, amount_sats, entry_type, deposit_id)
+                    VALUES ($1, $2, 'deposit_reversal', $3)
+                    "#,
+                    &[&user_id, &-amount, &deposit_id],
+                )
+                .await
+                .map_err(|e| Error::StorageError(format!("Failed to write ledger entry: {}", e)))?;
+                amount as u64
+            }
+            "pending" if dropped => 0,
+            _ => return Ok(0),
+        };
+
+        tx.execute(
+            r#"
+            UPDATE deposits
+            SET status = $2, credited_at = NULL, confirmations = 0, block_height = NULL, block_hash = NULL
+            WHERE id = $1
+            "#,
+            &[&deposit_id, &new_status],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to reverse deposit: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit deposit reversal: {}", e)))?;
+
+        warn!(
+            "Reversed deposit {} for user {} (debited {} sats, now {})",
+            deposit_id, user_id, debited, new_status
+        );
+
+        Ok(debited)
+    }
+
+    /// Ledger balance of a user in satoshis
+    pub async fn get_ledger_balance(&self, user_id: &str) -> Result<i64> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_one(
+                "SELECT COALESCE(SUM(amount_sats), 0)::BIGINT FROM ledger_entries WHERE user_id = $1",
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get ledger balance: {}", e)))?;
+
+        Ok(row.get(0))
+    }
+
+    /// Store a new API key
+    pub async fn create_api_key(&self, key: &crate::ApiKey) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                r#"
+                INSERT INTO api_keys (key_id, user_id, name, secret, scopes,
+                                      max_amount_sats, daily_limit_sats, expires_at, created_by)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#,
+                &[
+                    &key.key_id,
+                    &key.user_id,
+                    &key.name,
+                    &key.secret,
+                    &key.scopes,
+                    &key.max_amount_sats.map(|v| v as i64),
+                    &key.daily_limit_sats.map(|v| v as i64),
+                    &key.expires_at,
+                    &key.created_by,
                 ],
             )
             .await
-            .map_err(|e| {
-                Error::StorageError(format!("Failed to update voting round: {}", e))
-            })?;
+            .map_err(|e| Error::StorageError(format!("Failed to create API key: {}", e)))?;
 
         Ok(())
     }
 
-    /// Get voting round by transaction ID
-    pub async fn get_voting_round_by_txid(&self, txid: &str) -> Result<Option<VotingRound>> {
-        let client = self.pool.get().await
+    /// Get an API key by ID, including revoked and expired keys
+    pub async fn get_api_key(&self, key_id: &str) -> Result<Option<crate::ApiKey>> {
+        let client = self
+            .pool
+            .get()
+            .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client.query_opt(
-            r#"
-            SELECT id, tx_id, round_number, total_nodes, threshold,
-                   votes_received, approved, completed, started_at,
-                   completed_at, timeout_at
-            FROM voting_rounds
-            WHERE tx_id = $1
-            ORDER BY id DESC
-            LIMIT 1
-            "#,
-            &[&txid],
-        ).await
-        .map_err(|e| Error::StorageError(format!("Failed to get voting round: {}", e)))?;
+        let row = client
+            .query_opt(
+                &format!("SELECT {} FROM api_keys WHERE key_id = $1", API_KEY_COLUMNS),
+                &[&key_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get API key: {}", e)))?;
 
-        Ok(row.map(|r| VotingRound {
-            id: r.get::<_, i64>(0),
-            tx_id: TxId(r.get::<_, String>(1)),
-            round_number: r.get::<_, i32>(2) as u32,
-            total_nodes: r.get::<_, i32>(3) as u32,
-            threshold: r.get::<_, i32>(4) as u32,
-            votes_received: r.get::<_, i32>(5) as u32,
-            approved: r.get::<_, bool>(6),
-            completed: r.get::<_, bool>(7),
-            started_at: r.get::<_, chrono::DateTime<chrono::Utc>>(8),
-            completed_at: r.get::<_, Option<chrono::DateTime<chrono::Utc>>>(9),
-            timeout_at: r.get::<_, chrono::DateTime<chrono::Utc>>(10),
-        }))
+        Ok(row.as_ref().map(api_key_from_row))
     }
 
-    /// Update voting round approved status
-    pub async fn update_voting_round_approved(&self, round_id: i64, approved: bool) -> Result<()> {
-        let client = self.pool.get().await
+    /// List API keys, newest first, optionally restricted to one user
+    pub async fn list_api_keys(&self, user_id: Option<&str>) -> Result<Vec<crate::ApiKey>> {
+        let client = self
+            .pool
+            .get()
+            .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        client.execute(
-            r#"
-            UPDATE voting_rounds
-            SET approved = $1, completed = true, completed_at = NOW()
-            WHERE id = $2
-            "#,
-            &[&approved, &round_id],
-        ).await
-        .map_err(|e| Error::StorageError(format!("Failed to update voting round approved: {}", e)))?;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM api_keys WHERE $1::TEXT IS NULL OR user_id = $1 ORDER BY created_at DESC",
+                    API_KEY_COLUMNS
+                ),
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to list API keys: {}", e)))?;
 
-        Ok(())
+        Ok(rows.iter().map(api_key_from_row).collect())
     }
 
-    /// Update voting round completed status (without approval)
-    pub async fn update_voting_round_completed(&self, round_id: i64) -> Result<()> {
-        let client = self.pool.get().await
+    /// Revoke an API key
+    ///
+    /// Returns false if the key does not exist or was already revoked.
+    pub async fn revoke_api_key(&self, key_id: &str) -> Result<bool> {
+        let client = self
+            .pool
+            .get()
+            .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        client.execute(
-            r#"
-            UPDATE voting_rounds
-            SET completed = true, completed_at = NOW()
-            WHERE id = $1
-            "#,
-            &[&round_id],
-        ).await
-        .map_err(|e| Error::StorageError(format!("Failed to update voting round completed: {}", e)))?;
+        let updated = client
+            .execute(
+                "UPDATE api_keys SET revoked_at = NOW() WHERE key_id = $1 AND revoked_at IS NULL",
+                &[&key_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to revoke API key: {}", e)))?;
 
-        Ok(())
+        Ok(updated > 0)
     }
 
-    /// Record presignature usage
-    pub async fn record_presignature_usage(&self, usage: &PresignatureUsage) -> Result<()> {
+    /// Record a request signature, rejecting replays
+    ///
+    /// Returns false if the signature was already used within the replay
+    /// window. Signatures older than the window are pruned on the way.
+    pub async fn claim_api_request_signature(
+        &self,
+        key_id: &str,
+        signature: &str,
+        window_secs: u64,
+    ) -> Result<bool> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let presig_id_str = usage.presig_id.0.to_string();
-
         client
+            .execute(
+                "DELETE FROM api_key_nonces WHERE seen_at < NOW() - make_interval(secs => $1)",
+                &[&(window_secs as f64)],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to prune API key nonces: {}", e)))?;
+
+        let inserted = client
             .execute(
                 r#"
-                INSERT INTO presignature_usage (presig_id, transaction_id, generation_time_ms, protocol)
-                VALUES ($1, $2, $3, 'cggmp24')
+                INSERT INTO api_key_nonces (key_id, signature)
+                VALUES ($1, $2)
+                ON CONFLICT (key_id, signature) DO NOTHING
                 "#,
-                &[
-                    &presig_id_str,
-                    &usage.transaction_id,
-                    &usage.generation_time_ms,
-                ],
+                &[&key_id, &signature],
             )
             .await
-            .map_err(|e| {
-                Error::StorageError(format!("Failed to record presignature usage: {}", e))
-            })?;
+            .map_err(|e| Error::StorageError(format!("Failed to record API key nonce: {}", e)))?;
 
-        Ok(())
+        client
+            .execute(
+                "UPDATE api_keys SET last_used_at = NOW() WHERE key_id = $1",
+                &[&key_id],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to update API key usage: {}", e)))?;
+
+        Ok(inserted > 0)
     }
 
-    /// Update node status
-    pub async fn update_node_status(
+    // ============================================================================
+    // Idempotency Keys
+    // ============================================================================
+
+    /// Reserve an idempotency key for a request
+    ///
+    /// Inserts the key with the request fingerprint, taking over an expired
+    /// row with the same key. If the key is already held, returns the earlier
+    /// request's fingerprint and, once it completed, its response.
+    pub async fn claim_idempotency_key(
         &self,
-        node_id: NodeId,
-        status: &str,
-        last_heartbeat: chrono::DateTime<chrono::Utc>,
-    ) -> Result<()> {
+        owner: &str,
+        key: &str,
+        request_hash: &str,
+        ttl_secs: u64,
+    ) -> Result<crate::IdempotencyClaim> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        client
+        let claimed = client
             .execute(
                 r#"
-                INSERT INTO node_status (node_id, status, last_heartbeat)
-                VALUES ($1, $2, $3)
-                ON CONFLICT (node_id) DO UPDATE
-                SET status = $2, last_heartbeat = $3, updated_at = NOW()
+                INSERT INTO idempotency_keys (owner, idempotency_key, request_hash, expires_at)
+                VALUES ($1, $2, $3, NOW() + make_interval(secs => $4))
+                ON CONFLICT (owner, idempotency_key) DO UPDATE
+                SET request_hash = EXCLUDED.request_hash,
+                    status_code = NULL,
+                    response = NULL,
+                    created_at = NOW(),
+                    expires_at = EXCLUDED.expires_at
+                WHERE idempotency_keys.expires_at <= NOW()
                 "#,
-                &[&(node_id.0 as i64), &status, &last_heartbeat],
+                &[&owner, &key, &request_hash, &(ttl_secs as f64)],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to update node status: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to claim idempotency key: {}", e)))?;
 
-        Ok(())
+        if claimed > 0 {
+            return Ok(crate::IdempotencyClaim::Acquired);
+        }
+
+        let row = client
+            .query_one(
+                r#"
+                SELECT request_hash, status_code, response FROM idempotency_keys
+                WHERE owner = $1 AND idempotency_key = $2
+                "#,
+                &[&owner, &key],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get idempotency key: {}", e)))?;
+
+        let status_code: Option<i32> = row.get(1);
+        let response: Option<serde_json::Value> = row.get(2);
+
+        Ok(crate::IdempotencyClaim::Existing(crate::IdempotentResponse {
+            request_hash: row.get(0),
+            response: status_code.zip(response).map(|(status, body)| (status as u16, body)),
+        }))
     }
 
-    /// Log audit event
-    pub async fn log_audit_event(
-        &self,
-        event_type: &str,
-        node_id: Option<NodeId>,
-        tx_id: Option<&TxId>,
-        details: serde_json::Value,
-    ) -> Result<()> {
+    /// Release an idempotency key whose request failed, so it can be retried
+    pub async fn release_idempotency_key(&self, owner: &str, key: &str) -> Result<()> {
         let client = self
             .pool
             .get()
@@ -603,58 +3449,68 @@ impl PostgresStorage {
 
         client
             .execute(
-                r#"
-                INSERT INTO audit_log (event_type, node_id, tx_id, details)
-                VALUES ($1, $2, $3, $4)
-                "#,
-                &[
-                    &event_type,
-                    &node_id.map(|n| n.0 as i64),
-                    &tx_id.map(|t| t.0.as_str()),
-                    &details,
-                ],
+                "DELETE FROM idempotency_keys WHERE owner = $1 AND idempotency_key = $2 AND response IS NULL",
+                &[&owner, &key],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to log audit event: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to release idempotency key: {}", e)))?;
 
         Ok(())
     }
 
-    /// Get node health status
-    pub async fn get_node_health(&self, node_id: NodeId) -> Result<Option<serde_json::Value>> {
+    /// Delete idempotency keys past their retention, returning how many
+    pub async fn purge_expired_idempotency_keys(&self) -> Result<u64> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client
-            .query_opt(
+        client
+            .execute("DELETE FROM idempotency_keys WHERE expires_at <= NOW()", &[])
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to purge idempotency keys: {}", e)))
+    }
+
+    // ============================================================================
+    // Webhooks
+    // ============================================================================
+
+    /// Create a webhook subscription
+    pub async fn create_webhook_subscription(&self, sub: &crate::WebhookSubscription) -> Result<()> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
                 r#"
-                SELECT status, last_heartbeat, total_votes, total_violations, banned_until,
-                       CAST(EXTRACT(EPOCH FROM (NOW() - last_heartbeat)) AS DOUBLE PRECISION) as seconds_since_heartbeat
-                FROM node_status
-                WHERE node_id = $1
+                INSERT INTO webhook_subscriptions (subscription_id, url, events, secret, active,
+                    created_by, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
                 "#,
-                &[&(node_id.0 as i64)],
+                &[
+                    &sub.subscription_id,
+                    &sub.url,
+                    &sub.events,
+                    &sub.secret,
+                    &sub.active,
+                    &sub.created_by,
+                    &sub.created_at,
+                ],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get node health: {}", e)))?;
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to create webhook subscription: {}", e))
+            })?;
 
-        Ok(row.map(|r| {
-            serde_json::json!({
-                "status": r.get::<_, String>(0),
-                "last_heartbeat": r.get::<_, chrono::DateTime<chrono::Utc>>(1),
-                "total_votes": r.get::<_, i64>(2),
-                "total_violations": r.get::<_, i64>(3),
-                "banned_until": r.get::<_, Option<chrono::DateTime<chrono::Utc>>>(4),
-                "seconds_since_heartbeat": r.get::<_, f64>(5),
-            })
-        }))
+        Ok(())
     }
 
-    /// Get all transactions by state
-    pub async fn get_transactions_by_state(&self, state: &str) -> Result<Vec<Transaction>> {
+    /// List webhook subscriptions, newest first
+    pub async fn list_webhook_subscriptions(&self) -> Result<Vec<crate::WebhookSubscription>> {
         let client = self
             .pool
             .get()
@@ -664,119 +3520,165 @@ impl PostgresStorage {
         let rows = client
             .query(
                 r#"
-                SELECT id, txid, state, unsigned_tx, signed_tx, recipient,
-                       amount_sats, fee_sats, metadata, created_at, updated_at
-                FROM transactions
-                WHERE state = $1
-                ORDER BY created_at ASC
+                SELECT subscription_id, url, events, secret, active, created_by, created_at
+                FROM webhook_subscriptions ORDER BY created_at DESC
                 "#,
-                &[&state],
+                &[],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get transactions: {}", e)))?;
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to list webhook subscriptions: {}", e))
+            })?;
 
         Ok(rows
             .iter()
-            .map(|r| Transaction {
-                id: r.get(0),
-                txid: TxId(r.get(1)),
-                state: parse_transaction_state(r.get(2)),
-                unsigned_tx: r.get(3),
-                signed_tx: r.get(4),
-                recipient: r.get(5),
-                amount_sats: r.get::<_, i64>(6) as u64,
-                fee_sats: r.get::<_, i64>(7) as u64,
-                metadata: r.get(8),
-                created_at: r.get(9),
-                updated_at: r.get(10),
+            .map(|r| crate::WebhookSubscription {
+                subscription_id: r.get(0),
+                url: r.get(1),
+                events: r.get(2),
+                secret: r.get(3),
+                active: r.get(4),
+                created_by: r.get(5),
+                created_at: r.get(6),
             })
             .collect())
     }
 
-    /// Get votes for a specific voting round
-    pub async fn get_votes_for_round(&self, tx_id: &TxId, round_number: u32) -> Result<Vec<Vote>> {
+    /// Delete a webhook subscription with its pending deliveries and dead letters
+    ///
+    /// Returns false if the subscription does not exist.
+    pub async fn delete_webhook_subscription(&self, subscription_id: &str) -> Result<bool> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let rows = client
-            .query(
-                r#"
-                SELECT v.node_id, v.tx_id, v.peer_id, v.round_id, v.approve, v.value,
-                       v.signature, v.public_key, v.created_at
-                FROM votes v
-                JOIN voting_rounds vr ON v.round_id = vr.id
-                WHERE vr.tx_id = $1 AND vr.round_number = $2
-                "#,
-                &[&tx_id.0, &(round_number as i32)],
+        let deleted = client
+            .execute(
+                "DELETE FROM webhook_subscriptions WHERE subscription_id = $1",
+                &[&subscription_id],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get votes: {}", e)))?;
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to delete webhook subscription: {}", e))
+            })?;
 
-        Ok(rows
-            .iter()
-            .map(|r| Vote {
-                tx_id: TxId(r.get(1)),
-                node_id: NodeId(r.get::<_, i64>(0) as u64),
-                peer_id: PeerId(r.get(2)),
-                round_id: r.get::<_, i64>(3) as u64,
-                approve: r.get(4),
-                value: r.get::<_, i64>(5) as u64,
-                signature: r.get(6),
-                public_key: r.get(7),
-                timestamp: r.get(8),
-            })
-            .collect())
+        Ok(deleted > 0)
+    }
+
+    /// Enqueue an event for every active subscription to `event_type`
+    ///
+    /// Returns the number of deliveries created.
+    pub async fn enqueue_webhook_event(
+        &self,
+        event_type: &str,
+        payload: &serde_json::Value,
+    ) -> Result<u64> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        client
+            .execute(
+                ENQUEUE_WEBHOOK_SQL,
+                &[&uuid::Uuid::new_v4().to_string(), &event_type, payload],
+            )
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to enqueue webhook event: {}", e)))
     }
 
-    /// Count votes for a specific transaction
-    pub async fn count_votes_for_transaction(&self, tx_id: &TxId) -> Result<u32> {
+    /// Claim up to `limit` due webhook deliveries for an attempt
+    ///
+    /// Claimed deliveries are pushed `lease_secs` into the future and their
+    /// attempt count is incremented, so dispatchers on other nodes skip them
+    /// while this one delivers. A dispatcher that dies mid-attempt simply
+    /// lets the lease run out.
+    pub async fn claim_due_webhook_deliveries(
+        &self,
+        limit: i64,
+        lease_secs: u64,
+    ) -> Result<Vec<crate::WebhookDelivery>> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client
-            .query_one(
+        let rows = client
+            .query(
                 r#"
-                SELECT COUNT(DISTINCT v.node_id) as vote_count
-                FROM votes v
-                JOIN voting_rounds vr ON v.round_id = vr.id
-                WHERE vr.tx_id = $1
+                WITH due AS (
+                    SELECT id FROM webhook_deliveries
+                    WHERE delivered_at IS NULL AND next_attempt_at <= NOW()
+                    ORDER BY next_attempt_at
+                    LIMIT $1
+                    FOR UPDATE SKIP LOCKED
+                )
+                UPDATE webhook_deliveries d
+                SET attempts = d.attempts + 1,
+                    next_attempt_at = NOW() + make_interval(secs => $2)
+                FROM due, webhook_subscriptions s
+                WHERE d.id = due.id AND s.subscription_id = d.subscription_id
+                RETURNING d.id, d.subscription_id, s.url, s.secret, d.event_id, d.event_type,
+                    d.payload, d.attempts, d.created_at
                 "#,
-                &[&tx_id.0],
+                &[&limit, &(lease_secs as f64)],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to count votes: {}", e)))?;
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to claim webhook deliveries: {}", e))
+            })?;
 
-        let count: i64 = row.get(0);
-        Ok(count as u32)
+        Ok(rows
+            .iter()
+            .map(|r| crate::WebhookDelivery {
+                id: r.get(0),
+                subscription_id: r.get(1),
+                url: r.get(2),
+                secret: r.get(3),
+                event_id: r.get(4),
+                event_type: r.get(5),
+                payload: r.get(6),
+                attempts: r.get::<_, i32>(7) as u32,
+                created_at: r.get(8),
+            })
+            .collect())
     }
 
-    /// Get signed transaction bytes
-    pub async fn get_signed_transaction(&self, tx_id: &TxId) -> Result<Option<Vec<u8>>> {
+    /// Mark a webhook delivery as delivered
+    pub async fn mark_webhook_delivered(&self, id: i64, status_code: u16) -> Result<()> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client
-            .query_opt(
-                "SELECT signed_tx FROM transactions WHERE txid = $1",
-                &[&tx_id.0],
+        client
+            .execute(
+                r#"
+                UPDATE webhook_deliveries
+                SET delivered_at = NOW(), last_status_code = $2, last_error = NULL
+                WHERE id = $1
+                "#,
+                &[&id, &(status_code as i32)],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get signed transaction: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to mark webhook delivered: {}", e)))?;
 
-        Ok(row.and_then(|r| r.get(0)))
+        Ok(())
     }
 
-    /// Update transaction txid
-    pub async fn update_transaction_txid(&self, tx_id: &TxId, txid: &str) -> Result<()> {
+    /// Schedule the next attempt of a failed webhook delivery
+    pub async fn reschedule_webhook_delivery(
+        &self,
+        id: i64,
+        retry_in_secs: u64,
+        status_code: Option<u16>,
+        error: &str,
+    ) -> Result<()> {
         let client = self
             .pool
             .get()
@@ -785,28 +3687,29 @@ impl PostgresStorage {
 
         client
             .execute(
-                "UPDATE transactions SET txid = $1, updated_at = NOW() WHERE txid = $2",
-                &[&txid, &tx_id.0],
+                r#"
+                UPDATE webhook_deliveries
+                SET next_attempt_at = NOW() + make_interval(secs => $2),
+                    last_status_code = $3, last_error = $4
+                WHERE id = $1
+                "#,
+                &[&id, &(retry_in_secs as f64), &status_code.map(|c| c as i32), &error],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to update transaction txid: {}", e)))?;
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to reschedule webhook delivery: {}", e))
+            })?;
 
         Ok(())
     }
 
-    /// Record audit event for a transaction
-    pub async fn record_audit_event(&self, tx_id: &TxId, event_type: &str, details: &str) -> Result<()> {
-        self.log_audit_event(
-            event_type,
-            None,
-            Some(tx_id),
-            serde_json::json!({ "details": details }),
-        )
-        .await
-    }
-
-    /// Update transaction confirmations count
-    pub async fn update_transaction_confirmations(&self, tx_id: &TxId, confirmations: u32) -> Result<()> {
+    /// Move a webhook delivery that exhausted its retries to the dead letters
+    pub async fn dead_letter_webhook_delivery(
+        &self,
+        id: i64,
+        status_code: Option<u16>,
+        error: &str,
+    ) -> Result<()> {
         let client = self
             .pool
             .get()
@@ -815,17 +3718,28 @@ impl PostgresStorage {
 
         client
             .execute(
-                "UPDATE transactions SET confirmations = $1, updated_at = NOW() WHERE txid = $2",
-                &[&(confirmations as i32), &tx_id.0],
+                r#"
+                WITH failed AS (
+                    DELETE FROM webhook_deliveries WHERE id = $1 AND delivered_at IS NULL
+                    RETURNING subscription_id, event_id, event_type, payload, attempts, created_at
+                )
+                INSERT INTO webhook_dead_letters (subscription_id, event_id, event_type, payload,
+                    attempts, last_status_code, last_error, created_at)
+                SELECT subscription_id, event_id, event_type, payload, attempts, $2, $3, created_at
+                FROM failed
+                "#,
+                &[&id, &status_code.map(|c| c as i32), &error],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to update confirmations: {}", e)))?;
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to dead-letter webhook delivery: {}", e))
+            })?;
 
         Ok(())
     }
 
-    /// Get expired transactions (transactions that have been in voting/signing state for too long)
-    pub async fn get_expired_transactions(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<Vec<Transaction>> {
+    /// List webhook dead letters, most recent first
+    pub async fn list_webhook_dead_letters(&self, limit: i64) -> Result<Vec<crate::WebhookDeadLetter>> {
         let client = self
             .pool
             .get()
@@ -834,83 +3748,69 @@ impl PostgresStorage {
 
         let rows = client
             .query(
-                "SELECT id, txid, state, unsigned_tx, signed_tx, recipient, amount_sats, fee_sats,
-                        metadata, created_at, updated_at
-                 FROM transactions
-                 WHERE (state = 'voting' OR state = 'signing' OR state = 'broadcasting')
-                   AND updated_at < $1
-                 ORDER BY updated_at ASC",
-                &[&cutoff],
+                r#"
+                SELECT id, subscription_id, event_id, event_type, payload, attempts,
+                    last_status_code, last_error, created_at, failed_at
+                FROM webhook_dead_letters ORDER BY failed_at DESC LIMIT $1
+                "#,
+                &[&limit],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get expired transactions: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to list dead letters: {}", e)))?;
 
-        let transactions = rows
-            .into_iter()
-            .map(|row| {
-                Transaction {
-                    id: row.get(0),
-                    txid: TxId(row.get(1)),
-                    state: parse_transaction_state(row.get(2)),
-                    unsigned_tx: row.get(3),
-                    signed_tx: row.get(4),
-                    recipient: row.get(5),
-                    amount_sats: row.get::<_, i64>(6) as u64,
-                    fee_sats: row.get::<_, i64>(7) as u64,
-                    metadata: row.get(8),
-                    created_at: row.get(9),
-                    updated_at: row.get(10),
-                }
+        Ok(rows
+            .iter()
+            .map(|r| crate::WebhookDeadLetter {
+                id: r.get(0),
+                subscription_id: r.get(1),
+                event_id: r.get(2),
+                event_type: r.get(3),
+                payload: r.get(4),
+                attempts: r.get::<_, i32>(5) as u32,
+                last_status_code: r.get::<_, Option<i32>>(6).map(|c| c as u16),
+                last_error: r.get(7),
+                created_at: r.get(8),
+                failed_at: r.get(9),
             })
-            .collect();
-
-        Ok(transactions)
+            .collect())
     }
 
-    /// Create a new DKG ceremony record
-    pub async fn create_dkg_ceremony(&self, ceremony: &crate::DkgCeremony) -> Result<()> {
+    /// Queue a dead letter for delivery again with a fresh set of attempts
+    ///
+    /// Returns false if the dead letter does not exist.
+    pub async fn replay_webhook_dead_letter(&self, id: i64) -> Result<bool> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        // Convert UUID to string for database storage
-        let session_id_str = ceremony.session_id.to_string();
-
-        let result = client
+        let replayed = client
             .execute(
                 r#"
-                INSERT INTO dkg_ceremonies (session_id, protocol, threshold, total_nodes, status, started_at)
-                VALUES ($1, $2, $3, $4, $5, $6)
+                WITH replayed AS (
+                    DELETE FROM webhook_dead_letters WHERE id = $1
+                    RETURNING subscription_id, event_id, event_type, payload, created_at
+                )
+                INSERT INTO webhook_deliveries (subscription_id, event_id, event_type, payload, created_at)
+                SELECT subscription_id, event_id, event_type, payload, created_at FROM replayed
                 "#,
-                &[
-                    &session_id_str,
-                    &ceremony.protocol,
-                    &(ceremony.threshold as i32),
-                    &(ceremony.total_nodes as i32),
-                    &ceremony.status,
-                    &ceremony.started_at,
-                ],
+                &[&id],
             )
-            .await;
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to replay dead letter: {}", e)))?;
 
-        match result {
-            Ok(_) => {
-                info!("Created DKG ceremony: session_id={} protocol={}", session_id_str, ceremony.protocol);
-                Ok(())
-            }
-            Err(e) => {
-                error!("Failed to insert DKG ceremony: SQL Error: {:?}", e);
-                error!("Ceremony data: session_id={}, protocol={}, threshold={}, total_nodes={}",
-                       session_id_str, ceremony.protocol, ceremony.threshold, ceremony.total_nodes);
-                Err(Error::StorageError(format!("Failed to create DKG ceremony: {}", e)))
-            }
-        }
+        Ok(replayed > 0)
     }
 
-    /// Mark DKG ceremony as completed
-    pub async fn complete_dkg_ceremony(&self, session_id: uuid::Uuid, public_key: &[u8], address: &str) -> Result<()> {
+    /// Append an event to the cluster event log
+    pub async fn append_cluster_event(
+        &self,
+        event_type: &str,
+        tx_id: Option<&str>,
+        node_id: Option<u64>,
+        payload: &serde_json::Value,
+    ) -> Result<()> {
         let client = self
             .pool
             .get()
@@ -919,405 +3819,513 @@ impl PostgresStorage {
 
         client
             .execute(
-                r#"
-                UPDATE dkg_ceremonies
-                SET status = 'completed', public_key = $1, address = $2, completed_at = NOW()
-                WHERE session_id = $3
-                "#,
-                &[&public_key, &address, &session_id.to_string()],
+                APPEND_EVENT_SQL,
+                &[&event_type, &tx_id, &node_id.map(|n| n as i64), payload],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to complete DKG ceremony: {}", e)))?;
-
-        info!("Completed DKG ceremony: session_id={} address={}", session_id, address);
+            .map_err(|e| Error::StorageError(format!("Failed to append cluster event: {}", e)))?;
 
         Ok(())
     }
 
-    /// Mark DKG ceremony as failed
-    pub async fn fail_dkg_ceremony(&self, session_id: uuid::Uuid, error: &str) -> Result<()> {
+    /// Cluster events after `after_id`, oldest first
+    ///
+    /// An empty `event_types` matches every type; `tx_id` restricts the
+    /// result to events of one transaction.
+    pub async fn list_cluster_events_after(
+        &self,
+        after_id: i64,
+        event_types: &[String],
+        tx_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<crate::ClusterEvent>> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        client
-            .execute(
+        let rows = client
+            .query(
                 r#"
-                UPDATE dkg_ceremonies
-                SET status = 'failed', completed_at = NOW()
-                WHERE session_id = $1
+                SELECT id, event_type, tx_id, node_id, payload, created_at
+                FROM cluster_events
+                WHERE id > $1
+                  AND (cardinality($2::TEXT[]) = 0 OR event_type = ANY($2::TEXT[]))
+                  AND ($3::TEXT IS NULL OR tx_id = $3::TEXT)
+                ORDER BY id
+                LIMIT $4
                 "#,
-                &[&session_id.to_string()],
+                &[&after_id, &event_types, &tx_id, &limit],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to fail DKG ceremony: {}", e)))?;
-
-        info!("Failed DKG ceremony: session_id={} error={}", session_id, error);
+            .map_err(|e| Error::StorageError(format!("Failed to list cluster events: {}", e)))?;
 
-        Ok(())
+        Ok(rows.iter().map(cluster_event_from_row).collect())
     }
 
-    /// Get a specific DKG ceremony by session ID
-    pub async fn get_dkg_ceremony(&self, session_id: uuid::Uuid) -> Result<crate::DkgCeremony> {
+    /// Cluster events after `floor` other than those in `seen`, oldest first
+    ///
+    /// IDs are taken when an event is inserted but become visible when its
+    /// transaction commits, so an event can appear below IDs already read.
+    /// Re-reading a trailing window above `floor` and skipping the events
+    /// already seen picks those up.
+    pub async fn list_unseen_cluster_events(
+        &self,
+        floor: i64,
+        seen: &[i64],
+        limit: i64,
+    ) -> Result<Vec<crate::ClusterEvent>> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client
-            .query_one(
+        let rows = client
+            .query(
                 r#"
-                SELECT session_id, protocol, threshold, total_nodes, status,
-                       public_key, address, started_at, completed_at, error
-                FROM dkg_ceremonies
-                WHERE session_id = $1
+                SELECT id, event_type, tx_id, node_id, payload, created_at
+                FROM cluster_events
+                WHERE id > $1 AND id <> ALL($2::BIGINT[])
+                ORDER BY id
+                LIMIT $3
                 "#,
-                &[&session_id.to_string()],
+                &[&floor, &seen, &limit],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get DKG ceremony: {}", e)))?;
-
-        let session_id_str: String = row.get(0);
-        let protocol: String = row.get(1);
-        let threshold: i32 = row.get(2);
-        let total_nodes: i32 = row.get(3);
-        let status: String = row.get(4);
-        let public_key: Option<Vec<u8>> = row.get(5);
-        let address: Option<String> = row.get(6);
-        let started_at: chrono::DateTime<chrono::Utc> = row.get(7);
-        let completed_at: Option<chrono::DateTime<chrono::Utc>> = row.get(8);
-        let error: Option<String> = row.get(9);
+            .map_err(|e| Error::StorageError(format!("Failed to list cluster events: {}", e)))?;
 
-        Ok(crate::DkgCeremony {
-            session_id: uuid::Uuid::parse_str(&session_id_str)
-                .map_err(|e| Error::StorageError(format!("Invalid UUID: {}", e)))?,
-            protocol,
-            threshold: threshold as u32,
-            total_nodes: total_nodes as u32,
-            status,
-            public_key,
-            address,
-            started_at,
-            completed_at,
-            error,
-        })
+        Ok(rows.iter().map(cluster_event_from_row).collect())
     }
 
-    /// List all DKG ceremonies
-    pub async fn list_dkg_ceremonies(&self) -> Result<Vec<crate::DkgCeremony>> {
+    /// ID of the newest cluster event, or 0 if the log is empty
+    pub async fn latest_cluster_event_id(&self) -> Result<i64> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let rows = client
-            .query(
-                r#"
-                SELECT session_id, protocol, threshold, total_nodes, status,
-                       public_key, address, started_at, completed_at, error
-                FROM dkg_ceremonies
-                ORDER BY started_at DESC
-                "#,
-                &[],
-            )
+        let row = client
+            .query_one("SELECT COALESCE(MAX(id), 0) FROM cluster_events", &[])
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to list DKG ceremonies: {}", e)))?;
-
-        let mut ceremonies = Vec::new();
-        for row in rows {
-            let session_id_str: String = row.get(0);
-            let protocol: String = row.get(1);
-            let threshold: i32 = row.get(2);
-            let total_nodes: i32 = row.get(3);
-            let status: String = row.get(4);
-            let public_key: Option<Vec<u8>> = row.get(5);
-            let address: Option<String> = row.get(6);
-            let started_at: chrono::DateTime<chrono::Utc> = row.get(7);
-            let completed_at: Option<chrono::DateTime<chrono::Utc>> = row.get(8);
-            let error: Option<String> = row.get(9);
-
-            if let Ok(session_id) = uuid::Uuid::parse_str(&session_id_str) {
-                ceremonies.push(crate::DkgCeremony {
-                    session_id,
-                    protocol,
-                    threshold: threshold as u32,
-                    total_nodes: total_nodes as u32,
-                    status,
-                    public_key,
-                    address,
-                    started_at,
-                    completed_at,
-                    error,
-                });
-            }
-        }
+            .map_err(|e| Error::StorageError(format!("Failed to get latest cluster event: {}", e)))?;
 
-        Ok(ceremonies)
+        Ok(row.get(0))
     }
-
-    /// Store encrypted key share for a node
-    pub async fn store_key_share(
-        &self,
-        session_id: uuid::Uuid,
-        node_id: NodeId,
-        encrypted_share: &[u8],
-    ) -> Result<()> {
+
+    /// Delete cluster events older than `max_age_secs`
+    ///
+    /// Returns the number of events removed.
+    pub async fn purge_cluster_events(&self, max_age_secs: u64) -> Result<u64> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        // Get ceremony ID from session_id
-        let ceremony_row = client
-            .query_opt(
-                "SELECT id FROM dkg_ceremonies WHERE session_id = $1",
-                &[&session_id.to_string()],
+        client
+            .execute(
+                "DELETE FROM cluster_events WHERE created_at < NOW() - make_interval(secs => $1)",
+                &[&(max_age_secs as f64)],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get ceremony: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to purge cluster events: {}", e)))
+    }
 
-        let ceremony_id: i64 = ceremony_row
-            .ok_or_else(|| Error::StorageError(format!("Ceremony not found: {}", session_id)))?
-            .get(0);
+    // ============================================================================
+    // Retention
+    // ============================================================================
 
-        client
-            .execute(
+    /// Put a transaction on legal hold, or update the reason of an existing hold
+    ///
+    /// Held transactions are skipped by every retention policy and cannot be
+    /// deleted until the hold is released.
+    pub async fn place_legal_hold(&self, txid: &TxId, reason: &str, placed_by: &str) -> Result<crate::LegalHold> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
+
+        let row = client
+            .query_opt(
                 r#"
-                INSERT INTO key_shares (ceremony_id, node_id, encrypted_share)
-                VALUES ($1, $2, $3)
-                ON CONFLICT (ceremony_id, node_id) DO UPDATE
-                SET encrypted_share = $3
+                INSERT INTO legal_holds (txid, reason, placed_by)
+                SELECT txid, $2, $3 FROM transactions WHERE txid = $1
+                ON CONFLICT (txid) DO UPDATE
+                SET reason = EXCLUDED.reason, placed_by = EXCLUDED.placed_by, placed_at = NOW()
+                RETURNING txid, reason, placed_by, placed_at
                 "#,
-                &[&ceremony_id, &(node_id.0 as i64), &encrypted_share],
+                &[&txid.0, &reason, &placed_by],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to store key share: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to place legal hold: {}", e)))?
+            .ok_or_else(|| Error::TransactionNotFound(txid.clone()))?;
+
+        Ok(crate::LegalHold {
+            txid: row.get(0),
+            reason: row.get(1),
+            placed_by: row.get(2),
+            placed_at: row.get(3),
+        })
+    }
 
-        info!("Stored key share: session_id={} node_id={}", session_id, node_id);
+    /// Release the legal hold of a transaction
+    ///
+    /// Returns false if the transaction was not on hold.
+    pub async fn release_legal_hold(&self, txid: &TxId) -> Result<bool> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        Ok(())
+        let deleted = client
+            .execute("DELETE FROM legal_holds WHERE txid = $1", &[&txid.0])
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to release legal hold: {}", e)))?;
+
+        Ok(deleted > 0)
     }
 
-    /// Retrieve encrypted key share for a node
-    pub async fn get_key_share(&self, session_id: uuid::Uuid, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+    /// Legal holds, newest first
+    pub async fn list_legal_holds(&self) -> Result<Vec<crate::LegalHold>> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client
-            .query_opt(
-                r#"
-                SELECT ks.encrypted_share
-                FROM key_shares ks
-                JOIN dkg_ceremonies dc ON ks.ceremony_id = dc.id
-                WHERE dc.session_id = $1 AND ks.node_id = $2
-                "#,
-                &[&session_id.to_string(), &(node_id.0 as i64)],
+        let rows = client
+            .query(
+                "SELECT txid, reason, placed_by, placed_at FROM legal_holds ORDER BY placed_at DESC",
+                &[],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get key share: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to list legal holds: {}", e)))?;
 
-        Ok(row.map(|r| r.get(0)))
+        Ok(rows
+            .iter()
+            .map(|r| crate::LegalHold {
+                txid: r.get(0),
+                reason: r.get(1),
+                placed_by: r.get(2),
+                placed_at: r.get(3),
+            })
+            .collect())
     }
 
-    /// Get the latest key_share for a node (regardless of session)
-    ///
-    /// This returns the most recent key_share from the latest DKG ceremony.
-    /// Used for presignature generation when we need the current active key_share.
-    ///
-    /// # SORUN #18 FIX
-    /// Presignature generation needs key_share from DKG ceremony, but aux_info
-    /// has a different session ID. This method gets the latest key_share by
-    /// created_at timestamp instead of matching session IDs.
-    pub async fn get_latest_key_share(&self, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+    /// Terminal transactions not updated for `min_age_secs` and not on hold,
+    /// oldest first, with everything that is deleted along with them
+    pub async fn list_archivable_transactions(
+        &self,
+        min_age_secs: u64,
+        limit: i64,
+    ) -> Result<Vec<crate::ArchivedTransaction>> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client
-            .query_opt(
+        let final_states: Vec<String> = FINAL_STATES.iter().map(ToString::to_string).collect();
+        let rows = client
+            .query(
                 r#"
-                SELECT ks.encrypted_share
-                FROM key_shares ks
-                JOIN dkg_ceremonies dc ON ks.ceremony_id = dc.id
-                WHERE ks.node_id = $1
-                ORDER BY dc.started_at DESC
-                LIMIT 1
+                SELECT t.txid, to_jsonb(t) || jsonb_build_object(
+                    'voting_rounds', COALESCE((
+                        SELECT jsonb_agg(to_jsonb(r) || jsonb_build_object('votes', COALESCE((
+                            SELECT jsonb_agg(to_jsonb(v) ORDER BY v.id) FROM votes v WHERE v.round_id = r.id
+                        ), '[]'::jsonb)) ORDER BY r.round_number)
+                        FROM voting_rounds r WHERE r.tx_id = t.txid
+                    ), '[]'::jsonb),
+                    'state_history', COALESCE((
+                        SELECT jsonb_agg(to_jsonb(h) ORDER BY h.version)
+                        FROM transaction_state_history h WHERE h.txid = t.txid
+                    ), '[]'::jsonb),
+                    'presignature_usage', COALESCE((
+                        SELECT jsonb_agg(to_jsonb(p) ORDER BY p.id)
+                        FROM presignature_usage p WHERE p.transaction_id = t.id
+                    ), '[]'::jsonb),
+                    'byzantine_violations', COALESCE((
+                        SELECT jsonb_agg(to_jsonb(b) ORDER BY b.id)
+                        FROM byzantine_violations b WHERE b.tx_id = t.txid
+                    ), '[]'::jsonb),
+                    'attempts', COALESCE((
+                        SELECT jsonb_agg(to_jsonb(a) ORDER BY a.id)
+                        FROM transaction_attempts a WHERE a.txid = t.txid
+                    ), '[]'::jsonb)
+                )
+                FROM transactions t
+                WHERE t.state = ANY($1::TEXT[])
+                  AND t.updated_at < NOW() - make_interval(secs => $2)
+                  AND NOT EXISTS (SELECT 1 FROM legal_holds lh WHERE lh.txid = t.txid)
+                ORDER BY t.updated_at
+                LIMIT $3
                 "#,
-                &[&(node_id.0 as i64)],
+                &[&final_states, &(min_age_secs as f64), &limit],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get latest key share: {}", e)))?;
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to list archivable transactions: {}", e))
+            })?;
 
-        Ok(row.map(|r| r.get(0)))
+        Ok(rows
+            .iter()
+            .map(|r| crate::ArchivedTransaction {
+                txid: r.get(0),
+                record: r.get(1),
+            })
+            .collect())
     }
 
-    /// Store aux_info for a node
+    /// Record an archive file and delete the transactions written to it
     ///
-    /// Aux_info is auxiliary information (Paillier keys, ring-Pedersen parameters)
-    /// required for CGGMP24 presignature generation.
-    pub async fn store_aux_info(
+    /// Voting rounds, votes, state history, attempts and presignature usage
+    /// are removed by `ON DELETE CASCADE`. Transactions put on hold since
+    /// they were listed are kept. Returns the txids that were deleted.
+    pub async fn delete_archived_transactions(
         &self,
-        session_id: uuid::Uuid,
-        node_id: NodeId,
-        aux_info_data: &[u8],
-    ) -> Result<()> {
-        let client = self
+        file_name: &str,
+        sha256: &str,
+        txids: &[String],
+    ) -> Result<Vec<String>> {
+        let mut client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        client
-            .execute(
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        tx.execute(
+            "INSERT INTO retention_archives (file_name, sha256, transactions) VALUES ($1, $2, $3)",
+            &[&file_name, &sha256, &(txids.len() as i32)],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to record archive: {}", e)))?;
+
+        let final_states: Vec<String> = FINAL_STATES.iter().map(ToString::to_string).collect();
+        let deleted: Vec<String> = tx
+            .query(
                 r#"
-                INSERT INTO aux_info (session_id, node_id, aux_info_data, created_at)
-                VALUES ($1, $2, $3, NOW())
-                ON CONFLICT (session_id, node_id) DO UPDATE
-                SET aux_info_data = $3, updated_at = NOW()
+                DELETE FROM transactions t
+                WHERE t.txid = ANY($1) AND t.state = ANY($2::TEXT[])
+                  AND NOT EXISTS (SELECT 1 FROM legal_holds lh WHERE lh.txid = t.txid)
+                RETURNING t.txid
                 "#,
-                &[&session_id.to_string(), &(node_id.0 as i64), &aux_info_data],
+                &[&txids, &final_states],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to store aux_info: {}", e)))?;
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to delete archived transactions: {}", e))
+            })?
+            .iter()
+            .map(|r| r.get(0))
+            .collect();
 
-        Ok(())
+        self.append_audit_entry(
+            &tx,
+            "transactions_archived",
+            None,
+            None,
+            &serde_json::json!({
+                "file_name": file_name,
+                "sha256": sha256,
+                "archived": txids.len(),
+                "deleted": deleted.len(),
+            }),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to commit archival: {}", e)))?;
+
+        Ok(deleted)
     }
 
-    /// Get aux_info for a specific session and node
-    pub async fn get_aux_info(&self, session_id: uuid::Uuid, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+    /// The transactions among `txids` that are terminal, were not updated
+    /// for `min_age_secs` and are not on hold
+    pub async fn filter_finished_transactions(&self, txids: &[String], min_age_secs: u64) -> Result<Vec<String>> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client
-            .query_opt(
+        let final_states: Vec<String> = FINAL_STATES.iter().map(ToString::to_string).collect();
+        let rows = client
+            .query(
                 r#"
-                SELECT aux_info_data
-                FROM aux_info
-                WHERE session_id = $1 AND node_id = $2
+                SELECT t.txid FROM transactions t
+                WHERE t.txid = ANY($1) AND t.state = ANY($2::TEXT[])
+                  AND t.updated_at < NOW() - make_interval(secs => $3)
+                  AND NOT EXISTS (SELECT 1 FROM legal_holds lh WHERE lh.txid = t.txid)
                 "#,
-                &[&session_id.to_string(), &(node_id.0 as i64)],
+                &[&txids, &final_states, &(min_age_secs as f64)],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get aux_info: {}", e)))?;
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to filter finished transactions: {}", e))
+            })?;
 
-        Ok(row.map(|r| r.get(0)))
+        Ok(rows.iter().map(|r| r.get(0)).collect())
     }
 
-    /// Get aux_info ceremony by session ID
-    ///
-    /// This is used by participant nodes when joining a ceremony.
-    /// Fixes SORUN #15.
-    pub async fn get_aux_info_ceremony(&self, session_id: uuid::Uuid) -> Result<crate::AuxInfoCeremony> {
+    /// Delete presignature usage older than `max_age_secs`, except that of
+    /// held transactions
+    pub async fn prune_presignature_usage(&self, max_age_secs: u64) -> Result<u64> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        // Try to get from aux_info_sessions table first (if it exists)
-        let row = client
-            .query_opt(
+        client
+            .execute(
                 r#"
-                SELECT session_id, party_index, num_parties, status,
-                       started_at, completed_at, error
-                FROM aux_info_sessions
-                WHERE session_id = $1
+                DELETE FROM presignature_usage p
+                WHERE p.used_at < NOW() - make_interval(secs => $1)
+                  AND NOT EXISTS (
+                      SELECT 1 FROM transactions t JOIN legal_holds lh ON lh.txid = t.txid
+                      WHERE t.id = p.transaction_id
+                  )
                 "#,
-                &[&session_id.to_string()],
+                &[&(max_age_secs as f64)],
             )
-            .await;
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to prune presignature usage: {}", e)))
+    }
 
-        match row {
-            Ok(Some(row)) => {
-                let session_id_str: String = row.get(0);
-                let num_parties: i32 = row.get(2);
-                let status: String = row.get(3);
-                let started_at: chrono::DateTime<chrono::Utc> = row.get(4);
-                let completed_at: Option<chrono::DateTime<chrono::Utc>> = row.get(5);
-                let error: Option<String> = row.get(6);
+    /// Delete Byzantine violations older than `max_age_secs`, except those
+    /// of held transactions
+    pub async fn prune_byzantine_violations(&self, max_age_secs: u64) -> Result<u64> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-                Ok(crate::AuxInfoCeremony {
-                    session_id: uuid::Uuid::parse_str(&session_id_str)
-                        .map_err(|e| Error::StorageError(format!("Invalid session ID: {}", e)))?,
-                    num_parties: num_parties as u16,
-                    status,
-                    started_at,
-                    completed_at,
-                    error,
-                })
-            }
-            Ok(None) => {
-                // If not found in database, return a minimal ceremony object
-                // This can happen if coordinator hasn't persisted the ceremony yet
-                warn!("Aux info ceremony {} not found in database, creating minimal entry", session_id);
-                Ok(crate::AuxInfoCeremony {
-                    session_id,
-                    num_parties: 5, // Default to 5 parties
-                    status: "pending".to_string(),
-                    started_at: chrono::Utc::now(),
-                    completed_at: None,
-                    error: None,
-                })
-            }
-            Err(e) => {
-                // If table doesn't exist or other error, return minimal ceremony
-                warn!("Failed to get aux_info ceremony: {}, using defaults", e);
-                Ok(crate::AuxInfoCeremony {
-                    session_id,
-                    num_parties: 5,
-                    status: "pending".to_string(),
-                    started_at: chrono::Utc::now(),
-                    completed_at: None,
-                    error: None,
-                })
-            }
-        }
+        client
+            .execute(
+                r#"
+                DELETE FROM byzantine_violations b
+                WHERE b.detected_at < NOW() - make_interval(secs => $1)
+                  AND NOT EXISTS (SELECT 1 FROM legal_holds lh WHERE lh.txid = b.tx_id)
+                "#,
+                &[&(max_age_secs as f64)],
+            )
+            .await
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to prune Byzantine violations: {}", e))
+            })
     }
 
-    /// Get the latest aux_info for a node (most recent session)
-    pub async fn get_latest_aux_info(&self, node_id: NodeId) -> Result<Option<(uuid::Uuid, Vec<u8>)>> {
+    /// Delete webhook deliveries delivered more than `max_age_secs` ago
+    ///
+    /// Pending deliveries and dead letters are kept.
+    pub async fn prune_webhook_deliveries(&self, max_age_secs: u64) -> Result<u64> {
         let client = self
             .pool
             .get()
             .await
             .map_err(|e| Error::StorageError(format!("Failed to get client: {}", e)))?;
 
-        let row = client
-            .query_opt(
+        client
+            .execute(
                 r#"
-                SELECT session_id, aux_info_data
-                FROM aux_info
-                WHERE node_id = $1
-                ORDER BY created_at DESC
-                LIMIT 1
+                DELETE FROM webhook_deliveries
+                WHERE delivered_at IS NOT NULL AND delivered_at < NOW() - make_interval(secs => $1)
                 "#,
-                &[&(node_id.0 as i64)],
+                &[&(max_age_secs as f64)],
             )
             .await
-            .map_err(|e| Error::StorageError(format!("Failed to get latest aux_info: {}", e)))?;
+            .map_err(|e| Error::StorageError(format!("Failed to prune webhook deliveries: {}", e)))
+    }
+}
 
-        Ok(row.map(|r| {
-            let session_id_str: String = r.get(0);
-            let data: Vec<u8> = r.get(1);
-            (uuid::Uuid::parse_str(&session_id_str).unwrap(), data)
-        }))
+/// States in which a transaction no longer needs its UTXO reservations (the
+/// terminal states)
+const FINAL_STATES: [TransactionState; 4] = [
+    TransactionState::Confirmed,
+    TransactionState::Rejected,
+    TransactionState::Failed,
+    TransactionState::AbortedByzantine,
+];
+
+/// Append to the cluster event log
+/// ($1 = event type, $2 = tx id, $3 = node id, $4 = payload)
+const APPEND_EVENT_SQL: &str = r#"
+    INSERT INTO cluster_events (event_type, tx_id, node_id, payload)
+    VALUES ($1::TEXT, $2::TEXT, $3::BIGINT, $4::JSONB)
+"#;
+
+/// Fan an event out to every active subscription to its type
+/// ($1 = event id, $2 = event type, $3 = payload)
+const ENQUEUE_WEBHOOK_SQL: &str = r#"
+    INSERT INTO webhook_deliveries (subscription_id, event_id, event_type, payload)
+    SELECT subscription_id, $1::TEXT, $2::TEXT, $3::JSONB FROM webhook_subscriptions
+    WHERE active AND $2::TEXT = ANY(events)
+"#;
+
+const API_KEY_COLUMNS: &str = "key_id, user_id, name, secret, scopes, max_amount_sats, \
+     daily_limit_sats, expires_at, revoked_at, created_by, created_at, last_used_at";
+
+fn api_key_from_row(r: &tokio_postgres::Row) -> crate::ApiKey {
+    crate::ApiKey {
+        key_id: r.get(0),
+        user_id: r.get(1),
+        name: r.get(2),
+        secret: r.get(3),
+        scopes: r.get(4),
+        max_amount_sats: r.get::<_, Option<i64>>(5).map(|v| v as u64),
+        daily_limit_sats: r.get::<_, Option<i64>>(6).map(|v| v as u64),
+        expires_at: r.get(7),
+        revoked_at: r.get(8),
+        created_by: r.get(9),
+        created_at: r.get(10),
+        last_used_at: r.get(11),
+    }
+}
+
+const DEPOSIT_COLUMNS: &str = "id, user_id, address, txid, vout, amount_sats, status, \
+     confirmations, block_height, block_hash, first_seen_at, credited_at";
+
+fn deposit_from_row(r: &tokio_postgres::Row) -> crate::Deposit {
+    crate::Deposit {
+        id: r.get(0),
+        user_id: r.get(1),
+        address: r.get(2),
+        txid: r.get(3),
+        vout: r.get::<_, i32>(4) as u32,
+        amount_sats: r.get::<_, i64>(5) as u64,
+        status: r.get(6),
+        confirmations: r.get::<_, i32>(7) as u32,
+        block_height: r.get::<_, Option<i64>>(8).map(|h| h as u64),
+        block_hash: r.get(9),
+        first_seen_at: r.get(10),
+        credited_at: r.get(11),
     }
 }
 
-fn parse_transaction_state(s: String) -> TransactionState {
+fn cluster_event_from_row(r: &tokio_postgres::Row) -> crate::ClusterEvent {
+    crate::ClusterEvent {
+        id: r.get(0),
+        event_type: r.get(1),
+        tx_id: r.get(2),
+        node_id: r.get::<_, Option<i64>>(3).map(|n| n as u64),
+        payload: r.get(4),
+        created_at: r.get(5),
+    }
+}
+
+pub(crate) fn parse_transaction_state(s: String) -> TransactionState {
     match s.as_str() {
         "pending" => TransactionState::Pending,
         "voting" => TransactionState::Voting,
@@ -1335,3 +4343,86 @@ fn parse_transaction_state(s: String) -> TransactionState {
         _ => TransactionState::Failed,
     }
 }
+
+/// Take the migration lock for the rest of `tx` and read `schema_migrations`,
+/// creating it (and its checksum column) if needed
+async fn lock_migrations(
+    tx: &tokio_postgres::Transaction<'_>,
+) -> Result<Vec<crate::migrations::AppliedMigration>> {
+    tx.execute(
+        "SELECT pg_advisory_xact_lock($1)",
+        &[&crate::migrations::MIGRATION_LOCK_KEY],
+    )
+    .await
+    .map_err(|e| Error::StorageError(format!("Failed to take migration lock: {}", e)))?;
+
+    tx.batch_execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+        );
+        ALTER TABLE schema_migrations ADD COLUMN IF NOT EXISTS checksum TEXT;
+        "#,
+    )
+    .await
+    .map_err(|e| Error::StorageError(format!("Failed to create schema_migrations: {}", e)))?;
+
+    load_migrations(tx).await
+}
+
+async fn load_migrations(
+    tx: &tokio_postgres::Transaction<'_>,
+) -> Result<Vec<crate::migrations::AppliedMigration>> {
+    // Databases created by the old init scripts have no checksum column yet
+    let rows = tx
+        .query(
+            r#"
+            SELECT version, description, applied_at, to_jsonb(m) ->> 'checksum'
+            FROM schema_migrations m
+            ORDER BY version
+            "#,
+            &[],
+        )
+        .await
+        .map_err(|e| Error::StorageError(format!("Failed to read schema_migrations: {}", e)))?;
+
+    Ok(rows
+        .iter()
+        .map(|r| crate::migrations::AppliedMigration {
+            version: r.get::<_, i32>(0) as u32,
+            description: r.get(1),
+            applied_at: r.get(2),
+            checksum: r.get(3),
+        })
+        .collect())
+}
+
+/// Record `migration` as applied with its embedded checksum
+///
+/// Some migrations insert their own `schema_migrations` row without a
+/// checksum; that row is completed rather than duplicated.
+async fn record_migration(
+    tx: &tokio_postgres::Transaction<'_>,
+    migration: &crate::migrations::Migration,
+) -> Result<()> {
+    tx.execute(
+        r#"
+        INSERT INTO schema_migrations (version, description, checksum, applied_at)
+        VALUES ($1, $2, $3, NOW())
+        ON CONFLICT (version) DO UPDATE
+        SET checksum = EXCLUDED.checksum
+        "#,
+        &[
+            &(migration.version as i32),
+            &migration.description,
+            &migration.checksum(),
+        ],
+    )
+    .await
+    .map_err(|e| {
+        Error::StorageError(format!("Failed to record migration {}: {}", migration.version, e))
+    })?;
+    Ok(())
+}
@@ -0,0 +1,1073 @@
+//! Embedded SQLite storage
+//!
+//! Implements the storage traits in a single SQLite database so that a node,
+//! or a whole test cluster sharing one handle, runs without PostgreSQL and
+//! etcd. Enabled with the `embedded` feature.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use threshold_types::{
+    ByzantineViolation, Error, NodeId, PeerId, Result, RevotePolicy, Transaction, TransactionState,
+    TxId, Vote, VoteRoundState,
+};
+use tracing::{info, warn};
+
+use crate::postgres::parse_transaction_state;
+use crate::traits::{CeremonyStore, KeyShareStore, TransactionStore, VoteStore};
+use crate::{DkgCeremony, RecordedVote};
+
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS transactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    txid TEXT NOT NULL UNIQUE,
+    state TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    unsigned_tx BLOB NOT NULL,
+    signed_tx BLOB,
+    signing_fencing_token INTEGER,
+    recipient TEXT NOT NULL,
+    amount_sats INTEGER NOT NULL,
+    fee_sats INTEGER NOT NULL,
+    metadata TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS transaction_state_history (
+    txid TEXT NOT NULL,
+    from_state TEXT NOT NULL,
+    to_state TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    actor TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS audit_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    txid TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    details TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS cluster_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_type TEXT NOT NULL,
+    tx_id TEXT,
+    node_id INTEGER,
+    payload TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS votes (
+    tx_id TEXT NOT NULL,
+    node_id INTEGER NOT NULL,
+    vote TEXT NOT NULL,
+    PRIMARY KEY (tx_id, node_id)
+);
+
+CREATE TABLE IF NOT EXISTS vote_counts (
+    tx_id TEXT NOT NULL,
+    value INTEGER NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (tx_id, value)
+);
+
+CREATE TABLE IF NOT EXISTS vote_rounds (
+    tx_id TEXT PRIMARY KEY,
+    round INTEGER NOT NULL,
+    first_counted INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS archived_votes (
+    tx_id TEXT NOT NULL,
+    node_id INTEGER NOT NULL,
+    value INTEGER NOT NULL,
+    count INTEGER NOT NULL,
+    received_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS consensus_states (
+    tx_id TEXT PRIMARY KEY,
+    state TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS config (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS byzantine_violations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    peer_id TEXT NOT NULL,
+    node_id INTEGER,
+    tx_id TEXT NOT NULL,
+    violation_type TEXT NOT NULL,
+    evidence TEXT NOT NULL,
+    detected_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS banned_peers (
+    peer_id TEXT PRIMARY KEY,
+    violation_id INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS dkg_ceremonies (
+    session_id TEXT PRIMARY KEY,
+    protocol TEXT NOT NULL,
+    threshold INTEGER NOT NULL,
+    total_nodes INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    public_key BLOB,
+    address TEXT,
+    xpub TEXT,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    error TEXT
+);
+
+CREATE TABLE IF NOT EXISTS key_shares (
+    session_id TEXT NOT NULL REFERENCES dkg_ceremonies(session_id),
+    node_id INTEGER NOT NULL,
+    encrypted_share BLOB NOT NULL,
+    PRIMARY KEY (session_id, node_id)
+);
+
+CREATE TABLE IF NOT EXISTS aux_info (
+    session_id TEXT NOT NULL,
+    node_id INTEGER NOT NULL,
+    aux_info_data BLOB NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (session_id, node_id)
+);
+"#;
+
+/// Default vote threshold until [`SqliteStorage::set_vote_threshold`] is called
+const DEFAULT_VOTE_THRESHOLD: usize = 4;
+
+/// Storage for transactions, votes, ceremonies and key shares in one SQLite
+/// database
+///
+/// Nodes of an in-process cluster share one instance through an `Arc`, the
+/// way production nodes share PostgreSQL and etcd.
+pub struct SqliteStorage {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStorage {
+    /// Open or create a database at the given path
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let conn = Connection::open(path)
+            .map_err(|e| Error::StorageError(format!("Failed to open database: {}", e)))?;
+        Self::with_connection(conn)
+    }
+
+    /// Open an in-memory database (for tests)
+    pub fn open_in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory().map_err(|e| {
+            Error::StorageError(format!("Failed to open in-memory database: {}", e))
+        })?;
+        Self::with_connection(conn)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)
+            .map_err(|e| Error::StorageError(format!("Failed to create schema: {}", e)))?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> Result<MutexGuard<'_, Connection>> {
+        self.conn
+            .lock()
+            .map_err(|e| Error::StorageError(format!("Lock error: {}", e)))
+    }
+
+    /// Set the number of votes needed to reach consensus
+    pub fn set_vote_threshold(&self, threshold: usize) -> Result<()> {
+        self.conn()?
+            .execute(
+                "INSERT OR REPLACE INTO config (key, value) VALUES ('vote_threshold', ?1)",
+                params![threshold.to_string()],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to set vote threshold: {}", e)))?;
+        Ok(())
+    }
+
+    /// Consensus outcome published for a transaction, if any
+    pub fn get_consensus_state(&self, tx_id: &TxId) -> Result<Option<TransactionState>> {
+        let state: Option<String> = self
+            .conn()?
+            .query_row(
+                "SELECT state FROM consensus_states WHERE tx_id = ?1",
+                params![tx_id.0],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(|e| Error::StorageError(format!("Failed to get consensus state: {}", e)))?;
+        Ok(state.map(parse_transaction_state))
+    }
+
+    /// Types of the audit events recorded for a transaction, oldest first
+    pub fn get_audit_event_types(&self, txid: &TxId) -> Result<Vec<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn
+            .prepare("SELECT event_type FROM audit_events WHERE txid = ?1 ORDER BY id")
+            .map_err(|e| Error::StorageError(format!("Query error: {}", e)))?;
+
+        let rows = stmt
+            .query_map(params![txid.0], |r| r.get(0))
+            .map_err(|e| Error::StorageError(format!("Failed to get audit events: {}", e)))?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(|e| Error::StorageError(format!("Failed to get audit events: {}", e)))
+    }
+
+    /// Recorded violations of a node, oldest first
+    pub fn get_node_violations(&self, node_id: NodeId) -> Result<Vec<ByzantineViolation>> {
+        let conn = self.conn()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, peer_id, node_id, tx_id, violation_type, evidence, detected_at
+                 FROM byzantine_violations WHERE node_id = ?1 ORDER BY id",
+            )
+            .map_err(|e| Error::StorageError(format!("Query error: {}", e)))?;
+
+        let rows = stmt
+            .query_map(params![node_id.0 as i64], |r| {
+                let violation_type: String = r.get(4)?;
+                let evidence: String = r.get(5)?;
+                Ok(ByzantineViolation {
+                    id: Some(r.get(0)?),
+                    peer_id: PeerId(r.get(1)?),
+                    node_id: r.get::<_, Option<i64>>(2)?.map(|n| NodeId(n as u64)),
+                    tx_id: TxId(r.get(3)?),
+                    violation_type: serde_json::from_value(serde_json::Value::String(violation_type))
+                        .map_err(|e| conversion_error(4, e))?,
+                    evidence: serde_json::from_str(&evidence).map_err(|e| conversion_error(5, e))?,
+                    detected_at: timestamp(r, 6)?,
+                })
+            })
+            .map_err(|e| Error::StorageError(format!("Failed to get violations: {}", e)))?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(|e| Error::StorageError(format!("Failed to get violations: {}", e)))
+    }
+}
+
+fn conversion_error<E: std::error::Error + Send + Sync + 'static>(
+    column: usize,
+    e: E,
+) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(column, rusqlite::types::Type::Text, Box::new(e))
+}
+
+fn timestamp(row: &Row<'_>, column: usize) -> rusqlite::Result<DateTime<Utc>> {
+    let s: String = row.get(column)?;
+    DateTime::parse_from_rfc3339(&s)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| conversion_error(column, e))
+}
+
+fn optional_timestamp(row: &Row<'_>, column: usize) -> rusqlite::Result<Option<DateTime<Utc>>> {
+    match row.get::<_, Option<String>>(column)? {
+        Some(_) => timestamp(row, column).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Why a write fenced with `fencing_token` matched no row
+fn fencing_error(conn: &Connection, txid: &TxId, fencing_token: u64) -> Result<Error> {
+    let current: Option<Option<i64>> = conn
+        .query_row(
+            "SELECT signing_fencing_token FROM transactions WHERE txid = ?1",
+            params![txid.0],
+            |r| r.get(0),
+        )
+        .optional()
+        .map_err(|e| Error::StorageError(format!("Failed to get fencing token: {}", e)))?;
+
+    Ok(match current {
+        None => Error::TransactionNotFound(txid.clone()),
+        Some(current) => Error::StaleFencingToken {
+            resource: format!("signing of {}", txid),
+            token: fencing_token,
+            current: current.unwrap_or(0) as u64,
+        },
+    })
+}
+
+/// Voting round of a transaction; round 1 until another is opened
+fn vote_round(conn: &Connection, tx_id: &TxId) -> Result<VoteRoundState> {
+    conn.query_row(
+        "SELECT round, first_counted FROM vote_rounds WHERE tx_id = ?1",
+        params![tx_id.0],
+        |r| {
+            Ok(VoteRoundState {
+                round: r.get::<_, i64>(0)? as u32,
+                first_counted: r.get::<_, i64>(1)? as u32,
+            })
+        },
+    )
+    .optional()
+    .map(Option::unwrap_or_default)
+    .map_err(|e| Error::StorageError(format!("Failed to get vote round: {}", e)))
+}
+
+const TRANSACTION_COLUMNS: &str = "id, txid, state, unsigned_tx, signed_tx, recipient, \
+     amount_sats, fee_sats, metadata, created_at, updated_at";
+
+fn transaction_from_row(r: &Row<'_>) -> rusqlite::Result<Transaction> {
+    Ok(Transaction {
+        id: r.get(0)?,
+        txid: TxId(r.get(1)?),
+        state: parse_transaction_state(r.get(2)?),
+        unsigned_tx: r.get(3)?,
+        signed_tx: r.get(4)?,
+        recipient: r.get(5)?,
+        amount_sats: r.get::<_, i64>(6)? as u64,
+        fee_sats: r.get::<_, i64>(7)? as u64,
+        metadata: r.get(8)?,
+        created_at: timestamp(r, 9)?,
+        updated_at: timestamp(r, 10)?,
+    })
+}
+
+const CEREMONY_COLUMNS: &str = "session_id, protocol, threshold, total_nodes, status, \
+     public_key, address, started_at, completed_at, error, xpub";
+
+fn ceremony_from_row(r: &Row<'_>) -> rusqlite::Result<DkgCeremony> {
+    let session_id: String = r.get(0)?;
+    Ok(DkgCeremony {
+        session_id: uuid::Uuid::parse_str(&session_id).map_err(|e| conversion_error(0, e))?,
+        protocol: r.get(1)?,
+        threshold: r.get::<_, i64>(2)? as u32,
+        total_nodes: r.get::<_, i64>(3)? as u32,
+        status: r.get(4)?,
+        public_key: r.get(5)?,
+        address: r.get(6)?,
+        started_at: timestamp(r, 7)?,
+        completed_at: optional_timestamp(r, 8)?,
+        error: r.get(9)?,
+        xpub: r.get(10)?,
+    })
+}
+
+#[async_trait]
+impl TransactionStore for SqliteStorage {
+    async fn create_transaction(&self, tx: &Transaction) -> Result<i64> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO transactions
+                 (txid, state, unsigned_tx, signed_tx, recipient, amount_sats, fee_sats,
+                  metadata, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                tx.txid.0,
+                tx.state.to_string(),
+                tx.unsigned_tx,
+                tx.signed_tx,
+                tx.recipient,
+                tx.amount_sats as i64,
+                tx.fee_sats as i64,
+                tx.metadata,
+                tx.created_at.to_rfc3339(),
+                tx.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| Error::StorageError(format!("Failed to create transaction: {}", e)))?;
+
+        info!("Created transaction: txid={}", tx.txid);
+        Ok(conn.last_insert_rowid())
+    }
+
+    async fn get_transaction(&self, txid: &TxId) -> Result<Option<Transaction>> {
+        self.conn()?
+            .query_row(
+                &format!("SELECT {} FROM transactions WHERE txid = ?1", TRANSACTION_COLUMNS),
+                params![txid.0],
+                transaction_from_row,
+            )
+            .optional()
+            .map_err(|e| Error::StorageError(format!("Failed to get transaction: {}", e)))
+    }
+
+    async fn get_transactions_by_state(&self, state: TransactionState) -> Result<Vec<Transaction>> {
+        let conn = self.conn()?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM transactions WHERE state = ?1 ORDER BY created_at, id",
+                TRANSACTION_COLUMNS
+            ))
+            .map_err(|e| Error::StorageError(format!("Query error: {}", e)))?;
+
+        let rows = stmt
+            .query_map(params![state.to_string()], transaction_from_row)
+            .map_err(|e| Error::StorageError(format!("Failed to get transactions: {}", e)))?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(|e| Error::StorageError(format!("Failed to get transactions: {}", e)))
+    }
+
+    async fn transition_transaction_state(
+        &self,
+        txid: &TxId,
+        expected: TransactionState,
+        new_state: TransactionState,
+        actor: &str,
+        reason: &str,
+    ) -> Result<u64> {
+        if !expected.can_transition_to(new_state) {
+            return Err(Error::InvalidStateTransition {
+                tx_id: txid.0.clone(),
+                from: expected,
+                to: new_state,
+            });
+        }
+
+        let mut conn = self.conn()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        let now = Utc::now().to_rfc3339();
+        let version: Option<i64> = tx
+            .query_row(
+                "UPDATE transactions SET state = ?1, version = version + 1, updated_at = ?2
+                 WHERE txid = ?3 AND state = ?4
+                 RETURNING version",
+                params![new_state.to_string(), now, txid.0, expected.to_string()],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(|e| {
+                Error::StorageError(format!("Failed to update transaction state: {}", e))
+            })?;
+
+        let Some(version) = version else {
+            let actual: Option<String> = tx
+                .query_row(
+                    "SELECT state FROM transactions WHERE txid = ?1",
+                    params![txid.0],
+                    |r| r.get(0),
+                )
+                .optional()
+                .map_err(|e| {
+                    Error::StorageError(format!("Failed to get transaction state: {}", e))
+                })?;
+            return Err(match actual {
+                Some(actual) => Error::StateConflict {
+                    tx_id: txid.0.clone(),
+                    expected,
+                    actual: parse_transaction_state(actual),
+                },
+                None => Error::TransactionNotFound(txid.clone()),
+            });
+        };
+
+        tx.execute(
+            "INSERT INTO transaction_state_history
+                 (txid, from_state, to_state, version, actor, reason, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![txid.0, expected.to_string(), new_state.to_string(), version, actor, reason, now],
+        )
+        .map_err(|e| Error::StorageError(format!("Failed to record state history: {}", e)))?;
+
+        tx.commit()
+            .map_err(|e| Error::StorageError(format!("Failed to commit state update: {}", e)))?;
+
+        Ok(version as u64)
+    }
+
+    async fn claim_signing(&self, txid: &TxId, fencing_token: u64) -> Result<()> {
+        let conn = self.conn()?;
+        let updated = conn
+            .execute(
+                "UPDATE transactions SET signing_fencing_token = ?2, updated_at = ?3
+                 WHERE txid = ?1
+                   AND (signing_fencing_token IS NULL OR signing_fencing_token <= ?2)",
+                params![txid.0, fencing_token as i64, Utc::now().to_rfc3339()],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to claim signing: {}", e)))?;
+
+        if updated == 0 {
+            return Err(fencing_error(&conn, txid, fencing_token)?);
+        }
+
+        Ok(())
+    }
+
+    async fn set_signed_transaction(&self, txid: &TxId, signed_tx: &[u8], fencing_token: u64) -> Result<()> {
+        let conn = self.conn()?;
+        let updated = conn
+            .execute(
+                "UPDATE transactions SET signed_tx = ?1, updated_at = ?4
+                 WHERE txid = ?2 AND signing_fencing_token = ?3",
+                params![signed_tx, txid.0, fencing_token as i64, Utc::now().to_rfc3339()],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to set signed transaction: {}", e)))?;
+
+        if updated == 0 {
+            return Err(fencing_error(&conn, txid, fencing_token)?);
+        }
+
+        Ok(())
+    }
+
+    async fn record_audit_event(&self, txid: &TxId, event_type: &str, details: &str) -> Result<()> {
+        self.conn()?
+            .execute(
+                "INSERT INTO audit_events (txid, event_type, details, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![txid.0, event_type, details, Utc::now().to_rfc3339()],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to record audit event: {}", e)))?;
+        Ok(())
+    }
+
+    async fn append_cluster_event(
+        &self,
+        event_type: &str,
+        tx_id: Option<&str>,
+        node_id: Option<u64>,
+        payload: &serde_json::Value,
+    ) -> Result<()> {
+        self.conn()?
+            .execute(
+                "INSERT INTO cluster_events (event_type, tx_id, node_id, payload, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    event_type,
+                    tx_id,
+                    node_id.map(|n| n as i64),
+                    payload.to_string(),
+                    Utc::now().to_rfc3339()
+                ],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to append cluster event: {}", e)))?;
+        Ok(())
+    }
+
+    async fn release_orphaned_utxo_reservations(&self) -> Result<u64> {
+        // Embedded transactions carry their inputs in `unsigned_tx` and
+        // reserve nothing, so there is never anything to release
+        Ok(0)
+    }
+}
+
+#[async_trait]
+impl VoteStore for SqliteStorage {
+    async fn record_vote(&self, vote: &Vote) -> Result<RecordedVote> {
+        let vote_json = serde_json::to_string(vote)
+            .map_err(|e| Error::StorageError(format!("Failed to serialize vote: {}", e)))?;
+
+        let mut conn = self.conn()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        let rounds = vote_round(&tx, &vote.tx_id)?;
+        if !rounds.counts(vote.round_id) {
+            return Ok(RecordedVote::OutOfRound { current_round: rounds.round });
+        }
+
+        let inserted = tx
+            .execute(
+                "INSERT OR IGNORE INTO votes (tx_id, node_id, vote) VALUES (?1, ?2, ?3)",
+                params![vote.tx_id.0, vote.node_id.0 as i64, vote_json],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to record vote: {}", e)))?;
+
+        if inserted == 0 {
+            let existing: String = tx
+                .query_row(
+                    "SELECT vote FROM votes WHERE tx_id = ?1 AND node_id = ?2",
+                    params![vote.tx_id.0, vote.node_id.0 as i64],
+                    |r| r.get(0),
+                )
+                .map_err(|e| Error::StorageError(format!("Failed to check existing vote: {}", e)))?;
+            let existing: Vote = serde_json::from_str(&existing)
+                .map_err(|e| Error::StorageError(format!("Failed to parse existing vote: {}", e)))?;
+            return Ok(RecordedVote::Existing(existing));
+        }
+
+        let count: i64 = tx
+            .query_row(
+                "INSERT INTO vote_counts (tx_id, value, count) VALUES (?1, ?2, 1)
+                 ON CONFLICT (tx_id, value) DO UPDATE SET count = count + 1
+                 RETURNING count",
+                params![vote.tx_id.0, vote.value as i64],
+                |r| r.get(0),
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to count vote: {}", e)))?;
+
+        tx.commit()
+            .map_err(|e| Error::StorageError(format!("Failed to record vote: {}", e)))?;
+
+        info!(
+            "Recorded vote for tx_id={} node_id={} value={} (count {})",
+            vote.tx_id, vote.node_id, vote.value, count
+        );
+        Ok(RecordedVote::Counted { count: count as u64 })
+    }
+
+    async fn open_vote_round(&self, tx_id: &TxId, round: u32, policy: RevotePolicy) -> Result<()> {
+        let mut conn = self.conn()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        let current = vote_round(&tx, tx_id)?;
+        if current.round >= round {
+            return Ok(());
+        }
+        let next = current.next(round, policy);
+
+        tx.execute(
+            "INSERT OR REPLACE INTO vote_rounds (tx_id, round, first_counted) VALUES (?1, ?2, ?3)",
+            params![tx_id.0, next.round as i64, next.first_counted as i64],
+        )
+        .map_err(|e| Error::StorageError(format!("Failed to open vote round: {}", e)))?;
+
+        if policy == RevotePolicy::Expire {
+            for table in ["votes", "vote_counts"] {
+                tx.execute(&format!("DELETE FROM {} WHERE tx_id = ?1", table), params![tx_id.0])
+                    .map_err(|e| Error::StorageError(format!("Failed to expire votes: {}", e)))?;
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| Error::StorageError(format!("Failed to open vote round: {}", e)))?;
+
+        info!(
+            "Opened voting round {} for tx_id={} ({:?}, counting from round {})",
+            round, tx_id, policy, next.first_counted
+        );
+        Ok(())
+    }
+
+    async fn get_all_vote_counts(&self, tx_id: &TxId) -> Result<HashMap<u64, u64>> {
+        let conn = self.conn()?;
+        let mut stmt = conn
+            .prepare("SELECT value, count FROM vote_counts WHERE tx_id = ?1")
+            .map_err(|e| Error::StorageError(format!("Query error: {}", e)))?;
+
+        let rows = stmt
+            .query_map(params![tx_id.0], |r| {
+                Ok((r.get::<_, i64>(0)? as u64, r.get::<_, i64>(1)? as u64))
+            })
+            .map_err(|e| Error::StorageError(format!("Failed to get all vote counts: {}", e)))?;
+
+        rows.collect::<rusqlite::Result<HashMap<_, _>>>()
+            .map_err(|e| Error::StorageError(format!("Failed to get all vote counts: {}", e)))
+    }
+
+    async fn vote_threshold(&self) -> Result<usize> {
+        let value: Option<String> = self
+            .conn()?
+            .query_row(
+                "SELECT value FROM config WHERE key = 'vote_threshold'",
+                [],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(|e| Error::StorageError(format!("Failed to get vote threshold: {}", e)))?;
+
+        match value {
+            Some(v) => v
+                .parse()
+                .map_err(|e| Error::StorageError(format!("Invalid vote threshold {}: {}", v, e))),
+            None => Ok(DEFAULT_VOTE_THRESHOLD),
+        }
+    }
+
+    async fn archive_vote(&self, vote: &Vote, count: u64) -> Result<()> {
+        self.conn()?
+            .execute(
+                "INSERT INTO archived_votes (tx_id, node_id, value, count, received_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    vote.tx_id.0,
+                    vote.node_id.0 as i64,
+                    vote.value as i64,
+                    count as i64,
+                    Utc::now().to_rfc3339(),
+                ],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to archive vote: {}", e)))?;
+        Ok(())
+    }
+
+    async fn set_consensus_state(&self, tx_id: &TxId, state: TransactionState) -> Result<()> {
+        self.conn()?
+            .execute(
+                "INSERT OR REPLACE INTO consensus_states (tx_id, state) VALUES (?1, ?2)",
+                params![tx_id.0, state.to_string()],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to set transaction state: {}", e)))?;
+
+        info!("Set transaction state for tx_id={} to {}", tx_id, state);
+        Ok(())
+    }
+
+    async fn is_peer_banned(&self, peer_id: &PeerId) -> Result<bool> {
+        let banned: Option<i64> = self
+            .conn()?
+            .query_row(
+                "SELECT violation_id FROM banned_peers WHERE peer_id = ?1",
+                params![peer_id.0],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(|e| Error::StorageError(format!("Failed to check banned status: {}", e)))?;
+        Ok(banned.is_some())
+    }
+
+    async fn ban_node(&self, violation: &ByzantineViolation) -> Result<()> {
+        let mut conn = self.conn()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| Error::StorageError(format!("Failed to begin transaction: {}", e)))?;
+
+        tx.execute(
+            "INSERT INTO byzantine_violations
+                 (peer_id, node_id, tx_id, violation_type, evidence, detected_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                violation.peer_id.0,
+                violation.node_id.map(|n| n.0 as i64),
+                violation.tx_id.0,
+                violation.violation_type.to_string(),
+                violation.evidence.to_string(),
+                violation.detected_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| Error::StorageError(format!("Failed to record violation: {}", e)))?;
+
+        let violation_id = tx.last_insert_rowid();
+        tx.execute(
+            "INSERT OR REPLACE INTO banned_peers (peer_id, violation_id) VALUES (?1, ?2)",
+            params![violation.peer_id.0, violation_id],
+        )
+        .map_err(|e| Error::StorageError(format!("Failed to ban node: {}", e)))?;
+
+        tx.commit()
+            .map_err(|e| Error::StorageError(format!("Failed to ban node: {}", e)))?;
+
+        warn!(
+            "Banned peer {} for {:?}",
+            violation.peer_id, violation.violation_type
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CeremonyStore for SqliteStorage {
+    async fn create_dkg_ceremony(&self, ceremony: &DkgCeremony) -> Result<()> {
+        self.conn()?
+            .execute(
+                "INSERT INTO dkg_ceremonies
+                     (session_id, protocol, threshold, total_nodes, status, started_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    ceremony.session_id.to_string(),
+                    ceremony.protocol,
+                    ceremony.threshold,
+                    ceremony.total_nodes,
+                    ceremony.status,
+                    ceremony.started_at.to_rfc3339(),
+                ],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to create DKG ceremony: {}", e)))?;
+
+        info!(
+            "Created DKG ceremony: session_id={} protocol={}",
+            ceremony.session_id, ceremony.protocol
+        );
+        Ok(())
+    }
+
+    async fn complete_dkg_ceremony(
+        &self,
+        session_id: uuid::Uuid,
+        public_key: &[u8],
+        address: &str,
+        xpub: Option<&str>,
+    ) -> Result<()> {
+        self.conn()?
+            .execute(
+                "UPDATE dkg_ceremonies
+                 SET status = 'completed', public_key = ?1, address = ?2, xpub = ?3, completed_at = ?4
+                 WHERE session_id = ?5",
+                params![public_key, address, xpub, Utc::now().to_rfc3339(), session_id.to_string()],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to complete DKG ceremony: {}", e)))?;
+
+        info!("Completed DKG ceremony: session_id={} address={}", session_id, address);
+        Ok(())
+    }
+
+    async fn fail_dkg_ceremony(&self, session_id: uuid::Uuid, error: &str) -> Result<()> {
+        self.conn()?
+            .execute(
+                "UPDATE dkg_ceremonies SET status = 'failed', error = ?1, completed_at = ?2
+                 WHERE session_id = ?3",
+                params![error, Utc::now().to_rfc3339(), session_id.to_string()],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to fail DKG ceremony: {}", e)))?;
+
+        info!("Failed DKG ceremony: session_id={} error={}", session_id, error);
+        Ok(())
+    }
+
+    async fn get_dkg_ceremony(&self, session_id: uuid::Uuid) -> Result<DkgCeremony> {
+        self.conn()?
+            .query_row(
+                &format!("SELECT {} FROM dkg_ceremonies WHERE session_id = ?1", CEREMONY_COLUMNS),
+                params![session_id.to_string()],
+                ceremony_from_row,
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to get DKG ceremony: {}", e)))
+    }
+
+    async fn list_dkg_ceremonies(&self) -> Result<Vec<DkgCeremony>> {
+        let conn = self.conn()?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM dkg_ceremonies ORDER BY started_at DESC",
+                CEREMONY_COLUMNS
+            ))
+            .map_err(|e| Error::StorageError(format!("Query error: {}", e)))?;
+
+        let rows = stmt
+            .query_map([], ceremony_from_row)
+            .map_err(|e| Error::StorageError(format!("Failed to list DKG ceremonies: {}", e)))?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(|e| Error::StorageError(format!("Failed to list DKG ceremonies: {}", e)))
+    }
+}
+
+#[async_trait]
+impl KeyShareStore for SqliteStorage {
+    async fn store_key_share(&self, session_id: uuid::Uuid, node_id: NodeId, encrypted_share: &[u8]) -> Result<()> {
+        let updated = self
+            .conn()?
+            .execute(
+                "INSERT INTO key_shares (session_id, node_id, encrypted_share)
+                 SELECT session_id, ?2, ?3 FROM dkg_ceremonies WHERE session_id = ?1
+                 ON CONFLICT (session_id, node_id) DO UPDATE SET encrypted_share = excluded.encrypted_share",
+                params![session_id.to_string(), node_id.0 as i64, encrypted_share],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to store key share: {}", e)))?;
+
+        if updated == 0 {
+            return Err(Error::StorageError(format!("Ceremony not found: {}", session_id)));
+        }
+
+        info!("Stored key share: session_id={} node_id={}", session_id, node_id);
+        Ok(())
+    }
+
+    async fn get_key_share(&self, session_id: uuid::Uuid, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+        self.conn()?
+            .query_row(
+                "SELECT encrypted_share FROM key_shares WHERE session_id = ?1 AND node_id = ?2",
+                params![session_id.to_string(), node_id.0 as i64],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(|e| Error::StorageError(format!("Failed to get key share: {}", e)))
+    }
+
+    async fn get_latest_key_share(&self, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+        self.conn()?
+            .query_row(
+                "SELECT ks.encrypted_share
+                 FROM key_shares ks
+                 JOIN dkg_ceremonies dc ON ks.session_id = dc.session_id
+                 WHERE ks.node_id = ?1
+                 ORDER BY dc.started_at DESC
+                 LIMIT 1",
+                params![node_id.0 as i64],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(|e| Error::StorageError(format!("Failed to get latest key share: {}", e)))
+    }
+
+    async fn store_aux_info(&self, session_id: uuid::Uuid, node_id: NodeId, aux_info_data: &[u8]) -> Result<()> {
+        self.conn()?
+            .execute(
+                "INSERT INTO aux_info (session_id, node_id, aux_info_data, created_at)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (session_id, node_id) DO UPDATE SET aux_info_data = excluded.aux_info_data",
+                params![session_id.to_string(), node_id.0 as i64, aux_info_data, Utc::now().to_rfc3339()],
+            )
+            .map_err(|e| Error::StorageError(format!("Failed to store aux_info: {}", e)))?;
+        Ok(())
+    }
+
+    async fn get_aux_info(&self, session_id: uuid::Uuid, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+        self.conn()?
+            .query_row(
+                "SELECT aux_info_data FROM aux_info WHERE session_id = ?1 AND node_id = ?2",
+                params![session_id.to_string(), node_id.0 as i64],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(|e| Error::StorageError(format!("Failed to get aux_info: {}", e)))
+    }
+
+    async fn get_latest_aux_info(&self, node_id: NodeId) -> Result<Option<(uuid::Uuid, Vec<u8>)>> {
+        self.conn()?
+            .query_row(
+                "SELECT session_id, aux_info_data FROM aux_info
+                 WHERE node_id = ?1
+                 ORDER BY created_at DESC, rowid DESC
+                 LIMIT 1",
+                params![node_id.0 as i64],
+                |r| {
+                    let session_id: String = r.get(0)?;
+                    let session_id =
+                        uuid::Uuid::parse_str(&session_id).map_err(|e| conversion_error(0, e))?;
+                    Ok((session_id, r.get(1)?))
+                },
+            )
+            .optional()
+            .map_err(|e| Error::StorageError(format!("Failed to get latest aux_info: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(txid: &str) -> Transaction {
+        Transaction {
+            id: 0,
+            txid: TxId::from(txid),
+            state: TransactionState::Voting,
+            unsigned_tx: vec![1, 2, 3],
+            signed_tx: None,
+            recipient: "tb1qrecipient".to_string(),
+            amount_sats: 50_000,
+            fee_sats: 500,
+            metadata: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transaction_state_and_fencing() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        let txid = TxId::from("sqlite_tx_1");
+        storage.create_transaction(&transaction("sqlite_tx_1")).await.unwrap();
+
+        let version = storage
+            .transition_transaction_state(&txid, TransactionState::Voting, TransactionState::Approved, "test", "votes")
+            .await
+            .unwrap();
+        assert_eq!(version, 2);
+
+        let conflict = storage
+            .transition_transaction_state(&txid, TransactionState::Voting, TransactionState::Approved, "test", "again")
+            .await;
+        assert!(matches!(conflict, Err(Error::StateConflict { actual: TransactionState::Approved, .. })));
+
+        // Only the latest claim may store the signed transaction
+        storage.claim_signing(&txid, 3).await.unwrap();
+        storage.claim_signing(&txid, 7).await.unwrap();
+        let stale = storage.set_signed_transaction(&txid, b"older", 3).await;
+        assert!(matches!(stale, Err(Error::StaleFencingToken { current: 7, .. })));
+        let stale = storage.claim_signing(&txid, 5).await;
+        assert!(matches!(stale, Err(Error::StaleFencingToken { current: 7, .. })));
+        storage.set_signed_transaction(&txid, b"signed", 7).await.unwrap();
+
+        let stored = storage.get_transaction(&txid).await.unwrap().unwrap();
+        assert_eq!(stored.signed_tx.as_deref(), Some(&b"signed"[..]));
+        assert_eq!(
+            storage.get_transactions_by_state(TransactionState::Approved).await.unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_append_cluster_event() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        let payload = serde_json::json!({ "stage": "started" });
+        storage
+            .append_cluster_event("signing.progress", Some("sqlite_tx_events"), Some(2), &payload)
+            .await
+            .unwrap();
+
+        let (node_id, stored): (i64, String) = storage
+            .conn()
+            .unwrap()
+            .query_row(
+                "SELECT node_id, payload FROM cluster_events WHERE tx_id = ?1",
+                params!["sqlite_tx_events"],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(node_id, 2);
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&stored).unwrap(), payload);
+    }
+
+    #[tokio::test]
+    async fn test_record_vote_counts_once_per_node() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        let txid = TxId::from("sqlite_tx_2");
+
+        let vote = Vote::new(NodeId(1), txid.clone(), 1, true, Some(42));
+        assert!(matches!(storage.record_vote(&vote).await.unwrap(), RecordedVote::Counted { count: 1 }));
+        assert!(matches!(storage.record_vote(&vote).await.unwrap(), RecordedVote::Existing(_)));
+
+        let other = Vote::new(NodeId(2), txid.clone(), 1, true, Some(42));
+        assert!(matches!(storage.record_vote(&other).await.unwrap(), RecordedVote::Counted { count: 2 }));
+
+        let counts = storage.get_all_vote_counts(&txid).await.unwrap();
+        assert_eq!(counts.get(&42), Some(&2));
+    }
+
+    #[tokio::test]
+    async fn test_vote_rounds_carry_over_or_expire() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        let txid = TxId::from("sqlite_tx_3");
+
+        let early = Vote::new(NodeId(1), txid.clone(), 2, true, Some(42));
+        assert!(matches!(
+            storage.record_vote(&early).await.unwrap(),
+            RecordedVote::OutOfRound { current_round: 1 }
+        ));
+
+        let first = Vote::new(NodeId(1), txid.clone(), 1, true, Some(42));
+        storage.record_vote(&first).await.unwrap();
+
+        storage.open_vote_round(&txid, 2, RevotePolicy::CarryOver).await.unwrap();
+        let second = Vote::new(NodeId(2), txid.clone(), 2, true, Some(42));
+        assert!(matches!(storage.record_vote(&second).await.unwrap(), RecordedVote::Counted { count: 2 }));
+
+        storage.open_vote_round(&txid, 3, RevotePolicy::Expire).await.unwrap();
+        assert!(storage.get_all_vote_counts(&txid).await.unwrap().is_empty());
+        assert!(matches!(
+            storage.record_vote(&second).await.unwrap(),
+            RecordedVote::OutOfRound { current_round: 3 }
+        ));
+
+        // Reopening an older round is a no-op
+        storage.open_vote_round(&txid, 2, RevotePolicy::CarryOver).await.unwrap();
+        let third = Vote::new(NodeId(1), txid.clone(), 3, false, Some(0));
+        assert!(matches!(storage.record_vote(&third).await.unwrap(), RecordedVote::Counted { count: 1 }));
+    }
+}
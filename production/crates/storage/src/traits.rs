@@ -0,0 +1,335 @@
+//! Backend-neutral storage interfaces
+//!
+//! Production nodes implement these with PostgreSQL and etcd:
+//! [`PostgresStorage`] stores transactions, ceremonies and key shares,
+//! [`EtcdStorage`] hands out locks, and [`ClusterStorage`] combines both for
+//! vote counting. With the `embedded` feature, `SqliteStorage` and
+//! [`LocalLockManager`](crate::LocalLockManager) implement the same traits
+//! inside one process, so the components built on them (vote processing, the
+//! auto voter, the timeout monitor and the signing coordinator's storage)
+//! run in `cargo test` without docker-compose. `OrchestrationService`, the
+//! DKG, presignature and nonce services and the API state still take the
+//! concrete backends.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use threshold_types::{
+    ByzantineViolation, NodeId, PeerId, Result, RevotePolicy, Transaction, TransactionState, TxId,
+    Vote,
+};
+use tracing::warn;
+
+use crate::{DkgCeremony, EtcdStorage, LocalLockManager, LockGuard, PostgresStorage, RecordedVote};
+
+/// Transaction records and their state machine
+#[async_trait]
+pub trait TransactionStore: Send + Sync {
+    /// Store a new transaction; returns its row ID
+    async fn create_transaction(&self, tx: &Transaction) -> Result<i64>;
+
+    async fn get_transaction(&self, txid: &TxId) -> Result<Option<Transaction>>;
+
+    /// Transactions in `state`, oldest first
+    async fn get_transactions_by_state(&self, state: TransactionState) -> Result<Vec<Transaction>>;
+
+    /// Compare-and-set state transition; see
+    /// [`PostgresStorage::transition_transaction_state`]
+    async fn transition_transaction_state(
+        &self,
+        txid: &TxId,
+        expected: TransactionState,
+        new_state: TransactionState,
+        actor: &str,
+        reason: &str,
+    ) -> Result<u64>;
+
+    /// Record the fencing token of a new signing lock holder; see
+    /// [`PostgresStorage::claim_signing`]
+    async fn claim_signing(&self, txid: &TxId, fencing_token: u64) -> Result<()>;
+
+    /// Store the signed transaction if `fencing_token` holds the latest claim
+    async fn set_signed_transaction(&self, txid: &TxId, signed_tx: &[u8], fencing_token: u64) -> Result<()>;
+
+    /// Append an event to the audit trail of a transaction
+    async fn record_audit_event(&self, txid: &TxId, event_type: &str, details: &str) -> Result<()>;
+
+    /// Append an event to the cluster event log
+    async fn append_cluster_event(
+        &self,
+        event_type: &str,
+        tx_id: Option<&str>,
+        node_id: Option<u64>,
+        payload: &serde_json::Value,
+    ) -> Result<()>;
+
+    /// Free UTXO reservations of finished transactions; returns how many
+    async fn release_orphaned_utxo_reservations(&self) -> Result<u64>;
+}
+
+/// Vote counting and Byzantine bans
+#[async_trait]
+pub trait VoteStore: Send + Sync {
+    /// Store and count a vote unless the node already voted on the transaction
+    /// or the vote belongs to a round that no longer counts
+    async fn record_vote(&self, vote: &Vote) -> Result<RecordedVote>;
+
+    /// Move a transaction on to voting round `round`
+    ///
+    /// Under [`RevotePolicy::Expire`] the votes of earlier rounds are dropped
+    /// together with their counts. Opening a round that is not newer than the
+    /// current one does nothing.
+    async fn open_vote_round(&self, tx_id: &TxId, round: u32, policy: RevotePolicy) -> Result<()>;
+
+    /// Vote count per value of a transaction
+    async fn get_all_vote_counts(&self, tx_id: &TxId) -> Result<HashMap<u64, u64>>;
+
+    /// Votes needed to reach consensus
+    async fn vote_threshold(&self) -> Result<usize>;
+
+    /// Keep an accepted vote, and the count it brought its value to, for audit
+    async fn archive_vote(&self, vote: &Vote, count: u64) -> Result<()>;
+
+    /// Publish the consensus outcome of a transaction to the other nodes
+    async fn set_consensus_state(&self, tx_id: &TxId, state: TransactionState) -> Result<()>;
+
+    async fn is_peer_banned(&self, peer_id: &PeerId) -> Result<bool>;
+
+    /// Ban the offending node and record the violation
+    async fn ban_node(&self, violation: &ByzantineViolation) -> Result<()>;
+}
+
+/// DKG ceremony records
+#[async_trait]
+pub trait CeremonyStore: Send + Sync {
+    async fn create_dkg_ceremony(&self, ceremony: &DkgCeremony) -> Result<()>;
+
+    async fn complete_dkg_ceremony(
+        &self,
+        session_id: uuid::Uuid,
+        public_key: &[u8],
+        address: &str,
+        xpub: Option<&str>,
+    ) -> Result<()>;
+
+    async fn fail_dkg_ceremony(&self, session_id: uuid::Uuid, error: &str) -> Result<()>;
+
+    async fn get_dkg_ceremony(&self, session_id: uuid::Uuid) -> Result<DkgCeremony>;
+
+    /// All ceremonies, newest first
+    async fn list_dkg_ceremonies(&self) -> Result<Vec<DkgCeremony>>;
+}
+
+/// Encrypted key shares and aux info of the local node
+#[async_trait]
+pub trait KeyShareStore: Send + Sync {
+    async fn store_key_share(&self, session_id: uuid::Uuid, node_id: NodeId, encrypted_share: &[u8]) -> Result<()>;
+
+    async fn get_key_share(&self, session_id: uuid::Uuid, node_id: NodeId) -> Result<Option<Vec<u8>>>;
+
+    async fn get_latest_key_share(&self, node_id: NodeId) -> Result<Option<Vec<u8>>>;
+
+    async fn store_aux_info(&self, session_id: uuid::Uuid, node_id: NodeId, aux_info_data: &[u8]) -> Result<()>;
+
+    async fn get_aux_info(&self, session_id: uuid::Uuid, node_id: NodeId) -> Result<Option<Vec<u8>>>;
+
+    async fn get_latest_aux_info(&self, node_id: NodeId) -> Result<Option<(uuid::Uuid, Vec<u8>)>>;
+}
+
+/// Exclusive locks with fencing tokens
+#[async_trait]
+pub trait LockManager: Send + Sync {
+    /// Take the lock on `key`; `None` if another holder has it
+    async fn try_lock(&self, key: &str) -> Result<Option<LockGuard>>;
+}
+
+// ============================================================================
+// PostgreSQL / etcd
+// ============================================================================
+
+#[async_trait]
+impl TransactionStore for PostgresStorage {
+    async fn create_transaction(&self, tx: &Transaction) -> Result<i64> {
+        PostgresStorage::create_transaction(self, tx).await
+    }
+
+    async fn get_transaction(&self, txid: &TxId) -> Result<Option<Transaction>> {
+        PostgresStorage::get_transaction(self, txid).await
+    }
+
+    async fn get_transactions_by_state(&self, state: TransactionState) -> Result<Vec<Transaction>> {
+        PostgresStorage::get_transactions_by_state(self, &state.to_string()).await
+    }
+
+    async fn transition_transaction_state(
+        &self,
+        txid: &TxId,
+        expected: TransactionState,
+        new_state: TransactionState,
+        actor: &str,
+        reason: &str,
+    ) -> Result<u64> {
+        PostgresStorage::transition_transaction_state(self, txid, expected, new_state, actor, reason).await
+    }
+
+    async fn claim_signing(&self, txid: &TxId, fencing_token: u64) -> Result<()> {
+        PostgresStorage::claim_signing(self, txid, fencing_token).await
+    }
+
+    async fn set_signed_transaction(&self, txid: &TxId, signed_tx: &[u8], fencing_token: u64) -> Result<()> {
+        PostgresStorage::set_signed_transaction(self, txid, signed_tx, fencing_token).await
+    }
+
+    async fn record_audit_event(&self, txid: &TxId, event_type: &str, details: &str) -> Result<()> {
+        PostgresStorage::record_audit_event(self, txid, event_type, details).await
+    }
+
+    async fn append_cluster_event(
+        &self,
+        event_type: &str,
+        tx_id: Option<&str>,
+        node_id: Option<u64>,
+        payload: &serde_json::Value,
+    ) -> Result<()> {
+        PostgresStorage::append_cluster_event(self, event_type, tx_id, node_id, payload).await
+    }
+
+    async fn release_orphaned_utxo_reservations(&self) -> Result<u64> {
+        PostgresStorage::release_orphaned_utxo_reservations(self).await
+    }
+}
+
+#[async_trait]
+impl CeremonyStore for PostgresStorage {
+    async fn create_dkg_ceremony(&self, ceremony: &DkgCeremony) -> Result<()> {
+        PostgresStorage::create_dkg_ceremony(self, ceremony).await
+    }
+
+    async fn complete_dkg_ceremony(
+        &self,
+        session_id: uuid::Uuid,
+        public_key: &[u8],
+        address: &str,
+        xpub: Option<&str>,
+    ) -> Result<()> {
+        PostgresStorage::complete_dkg_ceremony(self, session_id, public_key, address, xpub).await
+    }
+
+    async fn fail_dkg_ceremony(&self, session_id: uuid::Uuid, error: &str) -> Result<()> {
+        PostgresStorage::fail_dkg_ceremony(self, session_id, error).await
+    }
+
+    async fn get_dkg_ceremony(&self, session_id: uuid::Uuid) -> Result<DkgCeremony> {
+        PostgresStorage::get_dkg_ceremony(self, session_id).await
+    }
+
+    async fn list_dkg_ceremonies(&self) -> Result<Vec<DkgCeremony>> {
+        PostgresStorage::list_dkg_ceremonies(self).await
+    }
+}
+
+#[async_trait]
+impl KeyShareStore for PostgresStorage {
+    async fn store_key_share(&self, session_id: uuid::Uuid, node_id: NodeId, encrypted_share: &[u8]) -> Result<()> {
+        PostgresStorage::store_key_share(self, session_id, node_id, encrypted_share).await
+    }
+
+    async fn get_key_share(&self, session_id: uuid::Uuid, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+        PostgresStorage::get_key_share(self, session_id, node_id).await
+    }
+
+    async fn get_latest_key_share(&self, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+        PostgresStorage::get_latest_key_share(self, node_id).await
+    }
+
+    async fn store_aux_info(&self, session_id: uuid::Uuid, node_id: NodeId, aux_info_data: &[u8]) -> Result<()> {
+        PostgresStorage::store_aux_info(self, session_id, node_id, aux_info_data).await
+    }
+
+    async fn get_aux_info(&self, session_id: uuid::Uuid, node_id: NodeId) -> Result<Option<Vec<u8>>> {
+        PostgresStorage::get_aux_info(self, session_id, node_id).await
+    }
+
+    async fn get_latest_aux_info(&self, node_id: NodeId) -> Result<Option<(uuid::Uuid, Vec<u8>)>> {
+        PostgresStorage::get_latest_aux_info(self, node_id).await
+    }
+}
+
+#[async_trait]
+impl LockManager for EtcdStorage {
+    async fn try_lock(&self, key: &str) -> Result<Option<LockGuard>> {
+        EtcdStorage::try_acquire_lock(self, key).await
+    }
+}
+
+#[async_trait]
+impl LockManager for LocalLockManager {
+    async fn try_lock(&self, key: &str) -> Result<Option<LockGuard>> {
+        Ok(LocalLockManager::try_lock(self, key))
+    }
+}
+
+/// Production vote storage: etcd counts votes and holds bans, PostgreSQL
+/// keeps the audit trail
+#[derive(Clone)]
+pub struct ClusterStorage {
+    etcd: EtcdStorage,
+    postgres: Arc<PostgresStorage>,
+}
+
+impl ClusterStorage {
+    pub fn new(etcd: EtcdStorage, postgres: Arc<PostgresStorage>) -> Self {
+        Self { etcd, postgres }
+    }
+}
+
+#[async_trait]
+impl VoteStore for ClusterStorage {
+    async fn record_vote(&self, vote: &Vote) -> Result<RecordedVote> {
+        self.etcd.record_vote(vote).await
+    }
+
+    async fn open_vote_round(&self, tx_id: &TxId, round: u32, policy: RevotePolicy) -> Result<()> {
+        self.etcd.open_vote_round(tx_id, round, policy).await
+    }
+
+    async fn get_all_vote_counts(&self, tx_id: &TxId) -> Result<HashMap<u64, u64>> {
+        self.etcd.get_all_vote_counts(tx_id).await
+    }
+
+    async fn vote_threshold(&self) -> Result<usize> {
+        self.etcd.get_config_threshold().await
+    }
+
+    async fn archive_vote(&self, vote: &Vote, count: u64) -> Result<()> {
+        self.postgres.record_vote(vote).await?;
+        self.postgres.update_node_last_seen(&vote.node_id).await?;
+
+        // Best effort: the voting round row only mirrors the etcd count
+        if let Ok(Some(round)) = self.postgres.get_voting_round_by_txid(&vote.tx_id.0).await {
+            if let Err(e) = self
+                .postgres
+                .update_voting_round(round.id, count as u32, false, false)
+                .await
+            {
+                warn!("Failed to update voting round of {}: {}", vote.tx_id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn set_consensus_state(&self, tx_id: &TxId, state: TransactionState) -> Result<()> {
+        self.etcd.set_transaction_state(tx_id, state).await
+    }
+
+    async fn is_peer_banned(&self, peer_id: &PeerId) -> Result<bool> {
+        self.etcd.is_peer_banned(peer_id).await
+    }
+
+    async fn ban_node(&self, violation: &ByzantineViolation) -> Result<()> {
+        self.etcd.ban_node(violation).await?;
+        self.postgres.record_byzantine_violation(violation).await
+    }
+}
@@ -145,6 +145,39 @@ impl fmt::Display for TransactionState {
     }
 }
 
+impl TransactionState {
+    /// States a transaction may move to from this one
+    ///
+    /// This is the single definition of the transaction lifecycle; storage
+    /// refuses any transition not listed here. `Signing -> Approved` is the
+    /// rollback used to retry a failed signing session.
+    pub fn allowed_transitions(&self) -> &'static [TransactionState] {
+        use TransactionState::*;
+        match self {
+            Pending => &[Voting, Rejected, Failed],
+            Voting => &[Collecting, ThresholdReached, Approved, Rejected, Failed, AbortedByzantine],
+            Collecting => &[ThresholdReached, Approved, Rejected, Failed, AbortedByzantine],
+            ThresholdReached => &[Approved, Signing, Rejected, Failed, AbortedByzantine],
+            Approved => &[Signing, Failed],
+            Signing => &[Signed, Approved, Failed],
+            Signed => &[Submitted, Broadcasting, Failed],
+            Submitted => &[Broadcasting, Confirmed, Failed],
+            Broadcasting => &[Confirmed, Failed],
+            Confirmed | Rejected | Failed | AbortedByzantine => &[],
+        }
+    }
+
+    /// Whether moving from this state to `next` is a legal transition
+    pub fn can_transition_to(&self, next: TransactionState) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+
+    /// Whether the transaction has reached the end of its lifecycle
+    pub fn is_terminal(&self) -> bool {
+        self.allowed_transitions().is_empty()
+    }
+}
+
 /// Transaction record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
@@ -224,6 +257,8 @@ impl ByzantineViolation {
 /// Request for nodes to vote on a transaction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoteRequest {
+    /// Node that opened the voting round
+    pub coordinator_id: NodeId,
     pub tx_id: TxId,
     pub round_id: i64,
     pub round_number: u32,
@@ -247,6 +282,52 @@ pub struct VotingRound {
     pub timeout_at: DateTime<Utc>,
 }
 
+/// What happens to the votes of a round when the next round opens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevotePolicy {
+    /// Votes of earlier rounds keep counting; only nodes that have not voted
+    /// yet are needed
+    #[default]
+    CarryOver,
+    /// Votes of earlier rounds are discarded and every node votes again
+    Expire,
+}
+
+/// Voting round of a transaction as tracked by the vote store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteRoundState {
+    /// Round votes are being requested for
+    pub round: u32,
+    /// Earliest round whose votes still count
+    pub first_counted: u32,
+}
+
+impl Default for VoteRoundState {
+    fn default() -> Self {
+        Self { round: 1, first_counted: 1 }
+    }
+}
+
+impl VoteRoundState {
+    /// State after opening `round` under `policy`
+    pub fn next(&self, round: u32, policy: RevotePolicy) -> Self {
+        Self {
+            round,
+            first_counted: match policy {
+                RevotePolicy::CarryOver => self.first_counted,
+                RevotePolicy::Expire => round,
+            },
+        }
+    }
+
+    /// Whether a vote cast in `round` (its `round_id`) counts towards the
+    /// current tally
+    pub fn counts(&self, round: u64) -> bool {
+        (u64::from(self.first_counted)..=u64::from(self.round)).contains(&round)
+    }
+}
+
 /// Consensus result when threshold is reached
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusResult {
@@ -483,9 +564,90 @@ pub enum Error {
     #[error("Transaction already processed: {tx_id}")]
     TransactionAlreadyProcessed { tx_id: String },
 
+    #[error("Illegal state transition for {tx_id}: {from} -> {to}")]
+    InvalidStateTransition {
+        tx_id: String,
+        from: TransactionState,
+        to: TransactionState,
+    },
+
+    #[error("State conflict for {tx_id}: expected {expected}, found {actual}")]
+    StateConflict {
+        tx_id: String,
+        expected: TransactionState,
+        actual: TransactionState,
+    },
+
+    #[error("Stale fencing token for {resource}: {token} is older than {current}")]
+    StaleFencingToken {
+        resource: String,
+        token: u64,
+        current: u64,
+    },
+
+    #[error("UTXO {outpoint} is already reserved by another transaction")]
+    UtxoReserved { outpoint: String },
+
+    #[error("Balance of {user_id} is {available} sats, {needed} sats needed")]
+    InsufficientBalance {
+        user_id: String,
+        needed: u64,
+        available: i64,
+    },
+
+    #[error("Amount {amount_sats} exceeds the API key daily limit ({spent} of {limit} sats used)")]
+    ApiKeyLimitExceeded {
+        key_id: String,
+        amount_sats: u64,
+        spent: u64,
+        limit: u64,
+    },
+
+    #[error("Schema drift at migration {version}: {reason}")]
+    SchemaDrift { version: u32, reason: String },
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
 /// Voting-specific error type (alias for backward compatibility)
 pub type VotingError = Error;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_transitions() {
+        use TransactionState::*;
+        let path = [Pending, Voting, Approved, Signing, Signed, Broadcasting, Confirmed];
+        for pair in path.windows(2) {
+            assert!(pair[0].can_transition_to(pair[1]), "{} -> {}", pair[0], pair[1]);
+        }
+        assert!(Confirmed.is_terminal());
+    }
+
+    #[test]
+    fn test_illegal_transitions() {
+        use TransactionState::*;
+        assert!(!Confirmed.can_transition_to(Signing));
+        assert!(!Voting.can_transition_to(Signing));
+        assert!(!Pending.can_transition_to(Approved));
+        assert!(!Failed.can_transition_to(Pending));
+        assert!(Signing.can_transition_to(Approved));
+    }
+
+    #[test]
+    fn test_vote_round_policies() {
+        let first = VoteRoundState::default();
+        assert!(first.counts(1));
+        assert!(!first.counts(2));
+
+        let carried = first.next(2, RevotePolicy::CarryOver);
+        assert!(carried.counts(1) && carried.counts(2));
+
+        let expired = carried.next(3, RevotePolicy::Expire);
+        assert!(!expired.counts(2));
+        assert!(expired.counts(3));
+    }
+}
@@ -0,0 +1,44 @@
+#!/bin/bash
+#
+# generate-audit-keys.sh - Generate the Ed25519 keys nodes sign audit entries with
+#
+# Usage: ./generate-audit-keys.sh <keys_dir> [total_nodes]
+#
+# Writes <keys_dir>/node<N>.pem (unencrypted PKCS#8) for every node that does
+# not have a key yet. Each node reads its own key from AUDIT_KEY_PATH
+# (default /audit-keys/node<N>.pem) and publishes the public key in etcd at
+# startup, where `threshold-wallet audit verify` picks it up.
+#
+# Replacing a node's key makes the entries it signed earlier unverifiable
+# with the published key; keep the old public key and pass it to the
+# verifier with --pubkey if you rotate.
+#
+# Arguments:
+#   keys_dir    - Directory holding the audit keys (created if missing)
+#   total_nodes - Number of nodes (default: 5)
+#
+# Example:
+#   ./generate-audit-keys.sh ../audit-keys
+#
+
+set -e  # Exit on error
+
+if [[ $# -lt 1 ]]; then
+    echo "Usage: $0 <keys_dir> [total_nodes]" >&2
+    exit 1
+fi
+
+KEYS_DIR=$1
+TOTAL_NODES=${2:-5}
+
+mkdir -p "$KEYS_DIR"
+for ((node = 1; node <= TOTAL_NODES; node++)); do
+    KEY_FILE="$KEYS_DIR/node$node.pem"
+    if [[ -e "$KEY_FILE" ]]; then
+        echo "Keeping existing audit key: $KEY_FILE"
+        continue
+    fi
+    openssl genpkey -algorithm ed25519 -out "$KEY_FILE"
+    chmod 600 "$KEY_FILE"
+    echo "Generated audit key: $KEY_FILE"
+done
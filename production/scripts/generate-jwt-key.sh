@@ -0,0 +1,45 @@
+#!/bin/bash
+#
+# generate-jwt-key.sh - Generate an Ed25519 key for signing API access tokens
+#
+# Usage: ./generate-jwt-key.sh <keys_dir> [kid]
+#
+# Writes <keys_dir>/<kid>.pem (unencrypted PKCS#8). All nodes must mount the
+# same keys directory as JWT_KEYS_DIR. The server signs with JWT_SIGNING_KID,
+# or with the lexicographically last kid when unset, so date-based kids make
+# the newest key the active one.
+#
+# Rotation:
+#   1. Generate a new key and restart the nodes (new tokens use the new kid)
+#   2. Keep the old key until its tokens have expired (TOKEN_TTL, 1 hour)
+#   3. Delete the old key file and restart the nodes
+#
+# Arguments:
+#   keys_dir - Directory holding the JWT keys (created if missing)
+#   kid      - Key ID (default: current UTC timestamp, e.g. 20261018T120000Z)
+#
+# Example:
+#   ./generate-jwt-key.sh ./jwt-keys
+#
+
+set -e  # Exit on error
+
+if [[ $# -lt 1 ]]; then
+    echo "Usage: $0 <keys_dir> [kid]" >&2
+    exit 1
+fi
+
+KEYS_DIR=$1
+KID=${2:-$(date -u +%Y%m%dT%H%M%SZ)}
+KEY_FILE="$KEYS_DIR/$KID.pem"
+
+if [[ -e "$KEY_FILE" ]]; then
+    echo "Error: $KEY_FILE already exists" >&2
+    exit 1
+fi
+
+mkdir -p "$KEYS_DIR"
+openssl genpkey -algorithm ed25519 -out "$KEY_FILE"
+chmod 600 "$KEY_FILE"
+
+echo "Generated JWT signing key: $KEY_FILE (kid=$KID)"
@@ -241,7 +241,7 @@ async fn test_concurrent_vote_submission() {
 
     // Setup threshold
     {
-        let mut etcd = EtcdStorage::new(endpoints.clone()).await.unwrap();
+        let etcd = EtcdStorage::new(endpoints.clone()).await.unwrap();
         etcd.set_cluster_threshold(4).await.unwrap();
     }
 
@@ -277,7 +277,7 @@ async fn test_concurrent_vote_submission() {
     assert!(threshold_reached, "Threshold should be reached");
 
     // Verify final count
-    let mut etcd = EtcdStorage::new(endpoints).await.unwrap();
+    let etcd = EtcdStorage::new(endpoints).await.unwrap();
     let count = etcd.get_vote_count(&tx_id, 100).await.unwrap();
     assert_eq!(count, 5);
 }
@@ -378,7 +378,7 @@ async fn test_multiple_transactions_concurrent() {
 
     // Setup threshold
     {
-        let mut etcd = EtcdStorage::new(endpoints.clone()).await.unwrap();
+        let etcd = EtcdStorage::new(endpoints.clone()).await.unwrap();
         etcd.set_cluster_threshold(4).await.unwrap();
     }
 
@@ -409,7 +409,7 @@ async fn test_multiple_transactions_concurrent() {
     }
 
     // Verify all 3 transactions reached threshold
-    let mut etcd = EtcdStorage::new(endpoints).await.unwrap();
+    let etcd = EtcdStorage::new(endpoints).await.unwrap();
     for tx_num in 1..=3 {
         let tx_id = test_tx_id(&format!("multi_tx_{:03}", tx_num));
         let count = etcd.get_vote_count(&tx_id, 100).await.unwrap();
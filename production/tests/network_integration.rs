@@ -203,7 +203,7 @@ async fn test_heartbeat_sequence_numbers() {
 #[tokio::test]
 async fn test_peer_discovery() {
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
@@ -227,7 +227,7 @@ async fn test_peer_discovery() {
 #[tokio::test]
 async fn test_peer_removal() {
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
@@ -251,7 +251,7 @@ async fn test_peer_removal() {
 #[tokio::test]
 async fn test_node_heartbeat_tracking() {
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
@@ -273,7 +273,7 @@ async fn test_node_heartbeat_tracking() {
 #[tokio::test]
 async fn test_active_nodes_detection() {
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
@@ -353,7 +353,7 @@ async fn test_concurrent_message_transmission() {
 #[tokio::test]
 async fn test_peer_status_updates() {
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
@@ -516,7 +516,7 @@ async fn test_backpressure_handling() {
 #[tokio::test]
 async fn test_message_deduplication() {
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
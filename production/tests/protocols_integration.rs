@@ -147,72 +147,72 @@ async fn test_presignature_message_structure() {
 #[tokio::test]
 async fn test_protocol_session_coordination() {
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
     let session_id = "dkg-session-test-001";
 
     // Node 1 acquires session lock
-    let lease_id = etcd.acquire_dkg_session_lock(session_id).await.unwrap();
-    assert!(lease_id > 0);
+    let lock = etcd.acquire_dkg_session_lock(session_id).await.unwrap();
+    assert!(lock.lease_id() > 0);
 
     // Node 2 tries to acquire same session lock - should fail
     let result = etcd.acquire_dkg_session_lock(session_id).await;
     assert!(result.is_err(), "Only one node should hold session lock");
 
     // Release lock
-    etcd.release_dkg_session_lock(session_id).await.unwrap();
+    lock.release().await.unwrap();
 
     // Now Node 2 can acquire
-    let lease_id2 = etcd.acquire_dkg_session_lock(session_id).await.unwrap();
-    assert!(lease_id2 > 0);
+    let lock2 = etcd.acquire_dkg_session_lock(session_id).await.unwrap();
+    assert!(lock2.lease_id() > 0);
 
-    etcd.release_dkg_session_lock(session_id).await.unwrap();
+    lock2.release().await.unwrap();
 }
 
 #[tokio::test]
 async fn test_presignature_generation_lock() {
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
     // Acquire presignature generation lock
-    let lease_id = etcd.acquire_presig_generation_lock().await.unwrap();
-    assert!(lease_id > 0);
+    let lock = etcd.acquire_presig_generation_lock().await.unwrap();
+    assert!(lock.lease_id() > 0);
 
     // Should fail to acquire again
     let result = etcd.acquire_presig_generation_lock().await;
     assert!(result.is_err());
 
     // Release and retry
-    etcd.release_presig_generation_lock().await.unwrap();
-    let lease_id2 = etcd.acquire_presig_generation_lock().await.unwrap();
-    assert!(lease_id2 > 0);
+    lock.release().await.unwrap();
+    let lock2 = etcd.acquire_presig_generation_lock().await.unwrap();
+    assert!(lock2.lease_id() > 0);
 
-    etcd.release_presig_generation_lock().await.unwrap();
+    lock2.release().await.unwrap();
 }
 
 #[tokio::test]
 async fn test_signing_round_coordination() {
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
     let tx_id = test_tx_id("signing_coordination_001");
 
     // Acquire signing lock
-    let lease_id = etcd.acquire_signing_lock(&tx_id).await.unwrap();
-    assert!(lease_id > 0);
+    let lock = etcd.acquire_signing_lock(&tx_id).await.unwrap();
+    assert!(lock.lease_id() > 0);
 
     // Should fail to acquire again
     let result = etcd.acquire_signing_lock(&tx_id).await;
     assert!(result.is_err(), "Only one signing session per transaction");
 
     // Release lock
-    etcd.release_signing_lock(&tx_id).await.unwrap();
+    lock.release().await.unwrap();
 }
 
 #[tokio::test]
@@ -228,7 +228,7 @@ async fn test_multiple_signing_sessions_different_txs() {
         let endpoints = endpoints.clone();
         let tx_id = tx_id_1.clone();
         tokio::spawn(async move {
-            let mut etcd = threshold_storage::EtcdStorage::new(endpoints)
+            let etcd = threshold_storage::EtcdStorage::new(endpoints)
                 .await
                 .unwrap();
             etcd.acquire_signing_lock(&tx_id).await.unwrap()
@@ -239,7 +239,7 @@ async fn test_multiple_signing_sessions_different_txs() {
         let endpoints = endpoints.clone();
         let tx_id = tx_id_2.clone();
         tokio::spawn(async move {
-            let mut etcd = threshold_storage::EtcdStorage::new(endpoints)
+            let etcd = threshold_storage::EtcdStorage::new(endpoints)
                 .await
                 .unwrap();
             etcd.acquire_signing_lock(&tx_id).await.unwrap()
@@ -247,22 +247,22 @@ async fn test_multiple_signing_sessions_different_txs() {
     };
 
     // Both should succeed (different transactions)
-    let lease1 = handle1.await.unwrap();
-    let lease2 = handle2.await.unwrap();
+    let lock1 = handle1.await.unwrap();
+    let lock2 = handle2.await.unwrap();
 
-    assert!(lease1 > 0);
-    assert!(lease2 > 0);
+    assert!(lock1.lease_id() > 0);
+    assert!(lock2.lease_id() > 0);
+    assert_ne!(lock1.fencing_token(), lock2.fencing_token());
 
     // Cleanup
-    let mut etcd = threshold_storage::EtcdStorage::new(endpoints).await.unwrap();
-    etcd.release_signing_lock(&tx_id_1).await.unwrap();
-    etcd.release_signing_lock(&tx_id_2).await.unwrap();
+    lock1.release().await.unwrap();
+    lock2.release().await.unwrap();
 }
 
 #[tokio::test]
 async fn test_protocol_state_transitions() {
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
@@ -400,7 +400,7 @@ async fn test_concurrent_protocol_sessions() {
         let session_id = format!("concurrent-session-{}", i);
 
         let handle = tokio::spawn(async move {
-            let mut etcd = threshold_storage::EtcdStorage::new(endpoints)
+            let etcd = threshold_storage::EtcdStorage::new(endpoints)
                 .await
                 .unwrap();
             etcd.acquire_dkg_session_lock(&session_id).await.unwrap()
@@ -409,17 +409,16 @@ async fn test_concurrent_protocol_sessions() {
     }
 
     // All should succeed (different sessions)
+    let mut locks = vec![];
     for handle in handles {
-        let lease_id = handle.await.unwrap();
-        assert!(lease_id > 0);
+        let lock = handle.await.unwrap();
+        assert!(lock.lease_id() > 0);
+        locks.push(lock);
     }
 
     // Cleanup
-    let mut etcd = threshold_storage::EtcdStorage::new(endpoints).await.unwrap();
-    for i in 1..=3 {
-        etcd.release_dkg_session_lock(&format!("concurrent-session-{}", i))
-            .await
-            .unwrap();
+    for lock in locks {
+        lock.release().await.unwrap();
     }
 }
 
@@ -498,7 +497,7 @@ async fn test_session_id_uniqueness() {
 async fn test_protocol_timeout_handling() {
     // Simulate protocol timeout by checking transaction state
     let ctx = TestContext::new().await;
-    let mut etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
+    let etcd = threshold_storage::EtcdStorage::new(ctx.etcd_endpoints())
         .await
         .unwrap();
 
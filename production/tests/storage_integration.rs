@@ -12,7 +12,12 @@
 mod common;
 
 use common::*;
-use threshold_storage::{EtcdStorage, PostgresStorage};
+use threshold_storage::audit::verify_chain;
+use threshold_storage::{
+    ApiKey, ApiKeySpend, AttemptOperation, IdempotencyClaim, IdempotentCompletion, AttemptOutcome, AuditIssue, AuditSigner, EtcdStorage, PostgresStorage,
+    RecordedVote, TransactionAttempt, TransactionOrigin, UserAddress,
+};
+use std::sync::Arc;
 use threshold_types::*;
 use chrono::Utc;
 
@@ -118,7 +123,7 @@ async fn test_transaction_state_transitions() {
 
     // Test state transition: Pending -> Voting
     storage
-        .update_transaction_state(&tx.txid, TransactionState::Voting)
+        .transition_transaction_state(&tx.txid, TransactionState::Pending, TransactionState::Voting, "test", "start voting")
         .await
         .unwrap();
 
@@ -127,7 +132,7 @@ async fn test_transaction_state_transitions() {
 
     // Test state transition: Voting -> ThresholdReached
     storage
-        .update_transaction_state(&tx.txid, TransactionState::ThresholdReached)
+        .transition_transaction_state(&tx.txid, TransactionState::Voting, TransactionState::ThresholdReached, "test", "votes in")
         .await
         .unwrap();
 
@@ -135,13 +140,240 @@ async fn test_transaction_state_transitions() {
     assert_eq!(retrieved.state, TransactionState::ThresholdReached);
 
     // Test state transition: ThresholdReached -> Signing
-    storage
-        .update_transaction_state(&tx.txid, TransactionState::Signing)
+    let version = storage
+        .transition_transaction_state(&tx.txid, TransactionState::ThresholdReached, TransactionState::Signing, "test", "sign")
         .await
         .unwrap();
+    assert_eq!(version, 3);
 
     let retrieved = storage.get_transaction(&tx.txid).await.unwrap().unwrap();
     assert_eq!(retrieved.state, TransactionState::Signing);
+
+    // A second node still expecting ThresholdReached loses the race
+    let stale = storage
+        .transition_transaction_state(&tx.txid, TransactionState::ThresholdReached, TransactionState::Signing, "test", "sign")
+        .await;
+    assert!(matches!(
+        stale,
+        Err(threshold_types::Error::StateConflict { actual: TransactionState::Signing, .. })
+    ));
+
+    // Transitions outside the state machine are refused before touching the row
+    let illegal = storage
+        .transition_transaction_state(&tx.txid, TransactionState::Signing, TransactionState::Voting, "test", "rewind")
+        .await;
+    assert!(matches!(illegal, Err(threshold_types::Error::InvalidStateTransition { .. })));
+
+    let history = storage.get_transaction_state_history(&tx.txid).await.unwrap();
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[0].from, TransactionState::Pending);
+    assert_eq!(history[2].to, TransactionState::Signing);
+    assert_eq!(history[2].actor, "test");
+}
+
+#[tokio::test]
+async fn test_utxo_reservations() {
+    let ctx = TestContext::new().await;
+    let config = sample_postgres_config(ctx.postgres_url());
+    let storage = PostgresStorage::new(&config).await.unwrap();
+
+    let utxo = ("aa".repeat(32), 1u32);
+    let inputs = vec![(utxo.0.clone(), utxo.1, 50_000u64)];
+
+    let first = sample_transaction("tx_reserve_001");
+    storage.create_transaction_with_reservations(&first, &inputs).await.unwrap();
+    assert!(storage.reserved_outpoints(&[utxo.clone()]).await.unwrap().contains(&utxo));
+
+    // A second transaction selecting the same output is refused entirely
+    let second = sample_transaction("tx_reserve_002");
+    let result = storage.create_transaction_with_reservations(&second, &inputs).await;
+    assert!(matches!(result, Err(threshold_types::Error::UtxoReserved { .. })));
+    assert!(storage.get_transaction(&second.txid).await.unwrap().is_none());
+
+    // Rejection releases the inputs
+    storage
+        .transition_transaction_state(&first.txid, TransactionState::Pending, TransactionState::Rejected, "test", "rejected")
+        .await
+        .unwrap();
+    assert!(storage.get_utxo_reservations(&first.txid).await.unwrap().is_empty());
+
+    storage.create_transaction_with_reservations(&second, &inputs).await.unwrap();
+
+    // Reconciliation leaves reservations of unfinished transactions alone
+    storage.release_orphaned_utxo_reservations().await.unwrap();
+    assert_eq!(storage.get_utxo_reservations(&second.txid).await.unwrap().len(), 1);
+    assert_eq!(storage.release_utxo_reservations(&second.txid).await.unwrap(), 1);
+
+    // A broadcast transaction that fails may still confirm: its inputs stay reserved
+    let broadcast = sample_transaction("tx_reserve_003");
+    storage.create_transaction_with_reservations(&broadcast, &inputs).await.unwrap();
+    storage.update_transaction_txid(&broadcast.txid, &broadcast.txid.0).await.unwrap();
+    storage
+        .transition_transaction_state(&broadcast.txid, TransactionState::Pending, TransactionState::Failed, "test", "failed")
+        .await
+        .unwrap();
+    storage.release_orphaned_utxo_reservations().await.unwrap();
+    assert!(storage.reserved_outpoints(&[utxo.clone()]).await.unwrap().contains(&utxo));
+}
+
+#[tokio::test]
+async fn test_withdrawal_debits_ledger() {
+    let ctx = TestContext::new().await;
+    let config = sample_postgres_config(ctx.postgres_url());
+    let storage = PostgresStorage::new(&config).await.unwrap();
+
+    let origin = TransactionOrigin {
+        user_id: Some("user1".to_string()),
+        debit_ledger: true,
+        derivation_path: vec![0, 0],
+        api_key: None,
+        idempotency: None,
+    };
+    let inputs = vec![("bb".repeat(32), 0u32, 50_000u64)];
+
+    // Without a balance nothing is stored, not even the reservations
+    let tx = sample_transaction("tx_ledger_001");
+    let result = storage.create_owned_transaction(&tx, &inputs, &origin).await;
+    assert!(matches!(
+        result,
+        Err(threshold_types::Error::InsufficientBalance { needed: 10_500, available: 0, .. })
+    ));
+    assert!(storage.get_transaction(&tx.txid).await.unwrap().is_none());
+    assert!(storage.get_utxo_reservations(&tx.txid).await.unwrap().is_empty());
+
+    // Credit a deposit that covers amount plus fee
+    storage
+        .create_user_address(&UserAddress {
+            user_id: "user1".to_string(),
+            address: "tb1qledger".to_string(),
+            derivation_index: 0,
+            derivation_path: "m/0/0".to_string(),
+            public_key: "02".repeat(33),
+            address_type: "p2wpkh".to_string(),
+            label: None,
+            is_change: false,
+            balance_sats: 0,
+            tx_count: 0,
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+    storage.record_deposit("user1", "tb1qledger", &"cc".repeat(32), 0, 15_000).await.unwrap();
+    let deposit = storage.list_deposits(Some("user1"), None, None).await.unwrap().remove(0);
+    assert!(storage.credit_deposit(deposit.id).await.unwrap());
+
+    storage.create_owned_transaction(&tx, &inputs, &origin).await.unwrap();
+    assert_eq!(storage.get_ledger_balance("user1").await.unwrap(), 4_500);
+    assert_eq!(storage.get_transaction_owner(&tx.txid).await.unwrap().as_deref(), Some("user1"));
+    assert_eq!(storage.get_transaction_derivation_path(&tx.txid).await.unwrap(), vec![0, 0]);
+
+    // The rest of the balance does not cover a second withdrawal
+    let second = sample_transaction("tx_ledger_002");
+    let result = storage.create_owned_transaction(&second, &[], &origin).await;
+    assert!(matches!(result, Err(threshold_types::Error::InsufficientBalance { .. })));
+
+    // Failing before broadcast refunds the debit
+    storage
+        .transition_transaction_state(&tx.txid, TransactionState::Pending, TransactionState::Failed, "test", "failed")
+        .await
+        .unwrap();
+    assert_eq!(storage.get_ledger_balance("user1").await.unwrap(), 15_000);
+}
+
+#[tokio::test]
+async fn test_api_key_daily_limit_under_concurrency() {
+    let ctx = TestContext::new().await;
+    let config = sample_postgres_config(ctx.postgres_url());
+    let storage = Arc::new(PostgresStorage::new(&config).await.unwrap());
+
+    storage
+        .create_api_key(&ApiKey {
+            key_id: "key_daily_001".to_string(),
+            user_id: "user1".to_string(),
+            name: "daily limit".to_string(),
+            secret: "00".repeat(32),
+            scopes: vec!["tx:create".to_string()],
+            max_amount_sats: None,
+            daily_limit_sats: Some(25_000),
+            expires_at: None,
+            revoked_at: None,
+            created_by: "admin".to_string(),
+            created_at: Utc::now(),
+            last_used_at: None,
+        })
+        .await
+        .unwrap();
+    let origin = TransactionOrigin {
+        user_id: Some("user1".to_string()),
+        api_key: Some(ApiKeySpend {
+            key_id: "key_daily_001".to_string(),
+            daily_limit_sats: Some(25_000),
+        }),
+        ..Default::default()
+    };
+
+    // Five requests of 10,000 sats race; only two fit under the limit
+    let handles: Vec<_> = (0..5)
+        .map(|i| {
+            let storage = Arc::clone(&storage);
+            let origin = origin.clone();
+            tokio::spawn(async move {
+                let tx = sample_transaction(&format!("tx_api_key_{:03}", i));
+                storage.create_owned_transaction(&tx, &[], &origin).await
+            })
+        })
+        .collect();
+
+    let mut created = 0;
+    for handle in handles {
+        match handle.await.unwrap() {
+            Ok(_) => created += 1,
+            Err(threshold_types::Error::ApiKeyLimitExceeded { limit: 25_000, .. }) => {}
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+    assert_eq!(created, 2);
+}
+
+#[tokio::test]
+async fn test_idempotent_response_stored_with_transaction() {
+    let ctx = TestContext::new().await;
+    let config = sample_postgres_config(ctx.postgres_url());
+    let storage = PostgresStorage::new(&config).await.unwrap();
+
+    let claim = storage.claim_idempotency_key("user1", "idem_001", "hash", 3600).await.unwrap();
+    assert!(matches!(claim, IdempotencyClaim::Acquired));
+
+    // A failed insert leaves the key in progress for the caller to release
+    let reserved = sample_transaction("tx_idem_000");
+    let utxo = ("dd".repeat(32), 0u32, 20_000u64);
+    storage.create_transaction_with_reservations(&reserved, &[utxo.clone()]).await.unwrap();
+    let origin = TransactionOrigin {
+        user_id: Some("user1".to_string()),
+        idempotency: Some(IdempotentCompletion {
+            owner: "user1".to_string(),
+            key: "idem_001".to_string(),
+            status_code: 200,
+            response: serde_json::json!({ "txid": "tx_idem_001" }),
+        }),
+        ..Default::default()
+    };
+    let tx = sample_transaction("tx_idem_001");
+    let result = storage.create_owned_transaction(&tx, &[utxo], &origin).await;
+    assert!(matches!(result, Err(threshold_types::Error::UtxoReserved { .. })));
+    match storage.claim_idempotency_key("user1", "idem_001", "hash", 3600).await.unwrap() {
+        IdempotencyClaim::Existing(earlier) => assert!(earlier.response.is_none()),
+        IdempotencyClaim::Acquired => panic!("key should still be held"),
+    }
+
+    // A stored transaction has its response recorded
+    storage.create_owned_transaction(&tx, &[], &origin).await.unwrap();
+    match storage.claim_idempotency_key("user1", "idem_001", "hash", 3600).await.unwrap() {
+        IdempotencyClaim::Existing(earlier) => {
+            assert_eq!(earlier.response, Some((200, serde_json::json!({ "txid": "tx_idem_001" }))));
+        }
+        IdempotencyClaim::Acquired => panic!("key should be completed"),
+    }
 }
 
 #[tokio::test]
@@ -155,11 +387,25 @@ async fn test_signed_transaction_update() {
 
     let signed_tx = vec![0x01, 0x02, 0x03, 0x04, 0x05];
 
+    // Without a claim there is nothing to write under
+    let result = storage.set_signed_transaction(&tx.txid, &signed_tx, 10).await;
+    assert!(matches!(result, Err(threshold_types::Error::StaleFencingToken { current: 0, .. })));
+
+    // The lock moved from the holder of token 9 to the holder of token 10
+    storage.claim_signing(&tx.txid, 9).await.unwrap();
+    storage.claim_signing(&tx.txid, 10).await.unwrap();
     storage
-        .set_signed_transaction(&tx.txid, &signed_tx)
+        .set_signed_transaction(&tx.txid, &signed_tx, 10)
         .await
         .unwrap();
 
+    // A writer holding an older lock token is fenced off
+    let result = storage.set_signed_transaction(&tx.txid, &[0xff], 9).await;
+    assert!(matches!(
+        result,
+        Err(threshold_types::Error::StaleFencingToken { token: 9, current: 10, .. })
+    ));
+
     let retrieved = storage.get_transaction(&tx.txid).await.unwrap().unwrap();
     assert_eq!(retrieved.signed_tx, Some(signed_tx));
     assert_eq!(retrieved.state, TransactionState::Signed);
@@ -283,6 +529,38 @@ async fn test_audit_log_recording() {
     assert!(result.is_ok(), "Audit event should be logged");
 }
 
+#[tokio::test]
+async fn test_audit_chain_verification() {
+    let ctx = TestContext::new().await;
+    let config = sample_postgres_config(ctx.postgres_url());
+    let signer = std::sync::Arc::new(AuditSigner::generate(NodeId(1)));
+    let storage = PostgresStorage::new(&config)
+        .await
+        .unwrap()
+        .with_audit_signer(std::sync::Arc::clone(&signer));
+
+    for i in 0..3 {
+        storage
+            .log_audit_event("chain_test", Some(NodeId(1)), None, serde_json::json!({ "i": i }))
+            .await
+            .unwrap();
+    }
+
+    let keys = std::collections::HashMap::from([(1, signer.verifying_key())]);
+    let entries = storage.list_audit_entries(0, 10_000).await.unwrap();
+    let report = verify_chain(&entries, &keys, &[]);
+    // Other tests share the database and write unsigned entries
+    let issues: Vec<_> = report
+        .issues
+        .iter()
+        .filter(|i| !matches!(i, AuditIssue::Unsigned { .. }))
+        .collect();
+    assert!(issues.is_empty(), "{:?}", issues);
+
+    let (head_seq, _) = storage.audit_chain_head().await.unwrap();
+    assert_eq!(report.last_seq, Some(head_seq));
+}
+
 // ============================================================================
 // etcd Integration Tests
 // ============================================================================
@@ -290,7 +568,7 @@ async fn test_audit_log_recording() {
 #[tokio::test]
 async fn test_etcd_vote_count_operations() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     let tx_id = test_tx_id("etcd_vote_001");
 
@@ -314,7 +592,7 @@ async fn test_etcd_vote_count_operations() {
 #[tokio::test]
 async fn test_etcd_vote_storage_and_retrieval() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     let vote = sample_vote(1, "etcd_vote_002", 42);
 
@@ -334,7 +612,7 @@ async fn test_etcd_vote_storage_and_retrieval() {
 #[tokio::test]
 async fn test_etcd_transaction_state_management() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     let tx_id = test_tx_id("etcd_state_001");
 
@@ -364,69 +642,91 @@ async fn test_etcd_transaction_state_management() {
 #[tokio::test]
 async fn test_etcd_distributed_locking() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     let tx_id = test_tx_id("etcd_lock_001");
 
     // Acquire lock
-    let lease_id = storage.acquire_signing_lock(&tx_id).await.unwrap();
-    assert!(lease_id > 0, "Should acquire lock with valid lease ID");
+    let lock = storage.acquire_signing_lock(&tx_id).await.unwrap();
+    assert!(lock.lease_id() > 0, "Should acquire lock with valid lease ID");
 
     // Try to acquire same lock again - should fail
     let result = storage.acquire_signing_lock(&tx_id).await;
     assert!(result.is_err(), "Should not acquire already-locked resource");
 
     // Release lock
-    storage.release_signing_lock(&tx_id).await.unwrap();
+    let first_token = lock.fencing_token();
+    lock.release().await.unwrap();
+
+    // Should be able to acquire again, with a newer fencing token
+    let lock = storage.acquire_signing_lock(&tx_id).await.unwrap();
+    assert!(lock.fencing_token() > first_token, "Fencing tokens must increase");
+
+    lock.release().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_etcd_lock_outlives_lease_ttl() {
+    let ctx = TestContext::new().await;
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
-    // Should be able to acquire again
-    let lease_id = storage.acquire_signing_lock(&tx_id).await.unwrap();
-    assert!(lease_id > 0, "Should acquire lock after release");
+    let tx_id = test_tx_id("etcd_lock_keepalive_001");
 
-    storage.release_signing_lock(&tx_id).await.unwrap();
+    let lock = storage.acquire_signing_lock(&tx_id).await.unwrap();
+
+    // Hold the lock past its 30s lease TTL; the guard keeps renewing it
+    tokio::time::sleep(std::time::Duration::from_secs(35)).await;
+    assert!(lock.is_held());
+    assert!(storage.acquire_signing_lock(&tx_id).await.is_err());
+
+    // Dropping the guard releases the lock in the background
+    drop(lock);
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    let lock = storage.acquire_signing_lock(&tx_id).await.unwrap();
+    lock.release().await.unwrap();
 }
 
 #[tokio::test]
 async fn test_etcd_presignature_lock() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     // Acquire presignature generation lock
-    let lease_id = storage.acquire_presig_generation_lock().await.unwrap();
-    assert!(lease_id > 0);
+    let lock = storage.acquire_presig_generation_lock().await.unwrap();
+    assert!(lock.lease_id() > 0);
 
     // Should fail to acquire again
     let result = storage.acquire_presig_generation_lock().await;
     assert!(result.is_err());
 
     // Release and retry
-    storage.release_presig_generation_lock().await.unwrap();
-    let lease_id = storage.acquire_presig_generation_lock().await.unwrap();
-    assert!(lease_id > 0);
+    lock.release().await.unwrap();
+    let lock = storage.acquire_presig_generation_lock().await.unwrap();
+    assert!(lock.lease_id() > 0);
 
-    storage.release_presig_generation_lock().await.unwrap();
+    lock.release().await.unwrap();
 }
 
 #[tokio::test]
 async fn test_etcd_dkg_session_lock() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     let session_id = "dkg-session-001";
 
-    let lease_id = storage.acquire_dkg_session_lock(session_id).await.unwrap();
-    assert!(lease_id > 0);
+    let lock = storage.acquire_dkg_session_lock(session_id).await.unwrap();
+    assert!(lock.lease_id() > 0);
 
     let result = storage.acquire_dkg_session_lock(session_id).await;
     assert!(result.is_err());
 
-    storage.release_dkg_session_lock(session_id).await.unwrap();
+    lock.release().await.unwrap();
 }
 
 #[tokio::test]
 async fn test_etcd_counter_operations() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     // Transaction counter
     let count1 = storage.increment_transaction_counter().await.unwrap();
@@ -444,7 +744,7 @@ async fn test_etcd_counter_operations() {
 #[tokio::test]
 async fn test_etcd_node_status_with_ttl() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     let node_id = NodeId(1);
 
@@ -460,7 +760,7 @@ async fn test_etcd_node_status_with_ttl() {
 #[tokio::test]
 async fn test_etcd_node_heartbeat() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     let node_id = NodeId(1);
 
@@ -473,7 +773,7 @@ async fn test_etcd_node_heartbeat() {
 #[tokio::test]
 async fn test_etcd_cluster_configuration() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     // Set threshold
     storage.set_cluster_threshold(4).await.unwrap();
@@ -498,7 +798,7 @@ async fn test_etcd_cluster_configuration() {
 #[tokio::test]
 async fn test_etcd_peer_management() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     storage.set_cluster_peers(vec![]).await.unwrap();
 
@@ -520,7 +820,7 @@ async fn test_etcd_peer_management() {
 #[tokio::test]
 async fn test_etcd_node_banning() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     let node_id = NodeId(99);
     let violation = sample_byzantine_violation(99, "tx_ban_001", ViolationType::DoubleVote);
@@ -561,7 +861,7 @@ async fn test_concurrent_vote_counting() {
         let tx_id_clone = tx_id.clone();
 
         let handle = tokio::spawn(async move {
-            let mut storage = EtcdStorage::new(endpoints_clone).await.unwrap();
+            let storage = EtcdStorage::new(endpoints_clone).await.unwrap();
             storage.increment_vote_count(&tx_id_clone, 100).await.unwrap();
         });
         handles.push(handle);
@@ -573,15 +873,45 @@ async fn test_concurrent_vote_counting() {
     }
 
     // Final count should be 10
-    let mut storage = EtcdStorage::new(endpoints).await.unwrap();
+    let storage = EtcdStorage::new(endpoints).await.unwrap();
     let final_count = storage.get_vote_count(&tx_id, 100).await.unwrap();
     assert_eq!(final_count, 10, "All concurrent increments should be counted");
 }
 
+#[tokio::test]
+async fn test_concurrent_record_vote_with_shared_handle() {
+    let ctx = TestContext::new().await;
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let tx_id = test_tx_id("record_vote_001");
+
+    // One handle, cloned into every task; votes and counts land together
+    let mut handles = vec![];
+    for node in 1..=10 {
+        let storage = storage.clone();
+        let vote = sample_vote(node, &tx_id.0, 100);
+        handles.push(tokio::spawn(async move { storage.record_vote(&vote).await.unwrap() }));
+    }
+
+    let mut counts = vec![];
+    for handle in handles {
+        match handle.await.unwrap() {
+            RecordedVote::Counted { count } => counts.push(count),
+            other => panic!("each node votes only once, got {:?}", other),
+        }
+    }
+    counts.sort_unstable();
+    assert_eq!(counts, (1..=10).collect::<Vec<u64>>());
+
+    // A repeated vote is reported, not counted again
+    let repeat = storage.record_vote(&sample_vote(1, &tx_id.0, 100)).await.unwrap();
+    assert!(matches!(repeat, RecordedVote::Existing(v) if v.value == 100));
+    assert_eq!(storage.get_vote_count(&tx_id, 100).await.unwrap(), 10);
+}
+
 #[tokio::test]
 async fn test_vote_cleanup() {
     let ctx = TestContext::new().await;
-    let mut storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
+    let storage = EtcdStorage::new(ctx.etcd_endpoints()).await.unwrap();
 
     let tx_id = test_tx_id("cleanup_001");
 
@@ -604,3 +934,119 @@ async fn test_vote_cleanup() {
     let count = storage.get_vote_count(&tx_id, 100).await.unwrap();
     assert_eq!(count, 0);
 }
+
+#[tokio::test]
+async fn test_migrations_are_applied_once() {
+    let ctx = TestContext::new().await;
+    let config = sample_postgres_config(ctx.postgres_url());
+
+    // TestContext already migrated; nodes starting together find nothing to do
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let config = config.clone();
+            tokio::spawn(async move {
+                let storage = PostgresStorage::new(&config).await.unwrap();
+                storage.migrate().await.unwrap()
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert!(handle.await.unwrap().is_empty());
+    }
+
+    let storage = PostgresStorage::new(&config).await.unwrap();
+    let status = storage.migration_status().await.unwrap();
+    assert_eq!(status.len(), threshold_storage::migrations::MIGRATIONS.len());
+    assert!(status
+        .iter()
+        .all(|s| s.state == threshold_storage::MigrationState::Applied));
+}
+
+#[tokio::test]
+async fn test_retention_skips_held_transactions() {
+    let ctx = TestContext::new().await;
+    let config = sample_postgres_config(ctx.postgres_url());
+    let storage = PostgresStorage::new(&config).await.unwrap();
+
+    let held = sample_transaction("tx_retention_held");
+    let expired = sample_transaction("tx_retention_expired");
+    for tx in [&held, &expired] {
+        storage.create_transaction(tx).await.unwrap();
+        storage
+            .transition_transaction_state(&tx.txid, TransactionState::Pending, TransactionState::Rejected, "test", "rejected")
+            .await
+            .unwrap();
+    }
+
+    storage.place_legal_hold(&held.txid, "litigation", "admin").await.unwrap();
+    let missing = storage
+        .place_legal_hold(&test_tx_id("tx_retention_missing"), "litigation", "admin")
+        .await;
+    assert!(matches!(missing, Err(Error::TransactionNotFound(_))));
+
+    let archivable = storage.list_archivable_transactions(0, 100).await.unwrap();
+    let txids: Vec<String> = archivable.iter().map(|a| a.txid.clone()).collect();
+    assert!(txids.contains(&expired.txid.0));
+    assert!(!txids.contains(&held.txid.0));
+    let record = &archivable.iter().find(|a| a.txid == expired.txid.0).unwrap().record;
+    assert_eq!(record["state_history"].as_array().unwrap().len(), 1);
+
+    // A hold placed after listing still wins
+    let both = vec![held.txid.0.clone(), expired.txid.0.clone()];
+    let deleted = storage
+        .delete_archived_transactions("transactions-test.jsonl.gz", &"0".repeat(64), &both)
+        .await
+        .unwrap();
+    assert_eq!(deleted, vec![expired.txid.0.clone()]);
+    assert!(storage.get_transaction(&expired.txid).await.unwrap().is_none());
+    assert!(storage.get_transaction(&held.txid).await.unwrap().is_some());
+
+    assert!(storage.release_legal_hold(&held.txid).await.unwrap());
+    assert!(!storage.release_legal_hold(&held.txid).await.unwrap());
+    let finished = storage.filter_finished_transactions(&both, 0).await.unwrap();
+    assert_eq!(finished, vec![held.txid.0.clone()]);
+}
+
+#[tokio::test]
+async fn test_transaction_attempt_history() {
+    let ctx = TestContext::new().await;
+    let config = sample_postgres_config(ctx.postgres_url());
+    let storage = PostgresStorage::new(&config).await.unwrap();
+
+    let tx = sample_transaction("tx_attempts");
+    storage.create_transaction(&tx).await.unwrap();
+
+    let now = Utc::now();
+    let failed = TransactionAttempt {
+        operation: AttemptOperation::Signing,
+        attempt: 1,
+        outcome: AttemptOutcome::Retryable,
+        error: Some("Signer node-3 failed: timeout".to_string()),
+        coordinator: NodeId(1),
+        excluded_nodes: vec![],
+        faulty_node: Some(NodeId(3)),
+        started_at: now,
+        finished_at: now,
+        retry_at: Some(now + chrono::Duration::seconds(1)),
+    };
+    assert!(storage.record_transaction_attempt(&tx.txid, &failed).await.unwrap());
+    // Another node recording the same attempt number is ignored
+    assert!(!storage.record_transaction_attempt(&tx.txid, &failed).await.unwrap());
+
+    let retried = TransactionAttempt {
+        attempt: 2,
+        outcome: AttemptOutcome::Succeeded,
+        error: None,
+        excluded_nodes: vec![NodeId(3)],
+        faulty_node: None,
+        retry_at: None,
+        ..failed.clone()
+    };
+    assert!(storage.record_transaction_attempt(&tx.txid, &retried).await.unwrap());
+
+    let attempts = storage.get_transaction_attempts(&tx.txid).await.unwrap();
+    assert_eq!(attempts.len(), 2);
+    assert_eq!(attempts[0].faulty_node, Some(NodeId(3)));
+    assert_eq!(attempts[1].outcome, AttemptOutcome::Succeeded);
+    assert_eq!(attempts[1].excluded_nodes, vec![NodeId(3)]);
+}
@@ -3,9 +3,12 @@
 //! Provides async access to:
 //! - Address info and balances
 //! - UTXOs
+//! - Address transaction history
 //! - Transaction broadcasting
 //! - Fee estimation
 
+use std::collections::HashSet;
+
 use common::MpcWalletError;
 use serde::{Deserialize, Serialize};
 
@@ -77,6 +80,82 @@ impl AddressInfo {
     }
 }
 
+/// A transaction touching an address (Esplora `/address/:address/txs` format).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressTx {
+    pub txid: String,
+    #[serde(default)]
+    pub status: TxStatus,
+    #[serde(default)]
+    pub vin: Vec<AddressTxInput>,
+    #[serde(default)]
+    pub vout: Vec<AddressTxOutput>,
+    #[serde(default)]
+    pub fee: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TxStatus {
+    pub confirmed: bool,
+    #[serde(default)]
+    pub block_height: Option<u64>,
+    #[serde(default)]
+    pub block_time: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressTxInput {
+    #[serde(default)]
+    pub prevout: Option<AddressTxOutput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressTxOutput {
+    #[serde(default)]
+    pub scriptpubkey_address: Option<String>,
+    pub value: u64,
+}
+
+impl AddressTx {
+    /// Net value of this transaction for a set of addresses in satoshis:
+    /// outputs paying the set minus inputs spending from it.
+    pub fn net_value(&self, addresses: &HashSet<String>) -> i64 {
+        let owns = |output: &AddressTxOutput| {
+            output
+                .scriptpubkey_address
+                .as_ref()
+                .is_some_and(|a| addresses.contains(a))
+        };
+
+        let received: u64 = self.vout.iter().filter(|o| owns(o)).map(|o| o.value).sum();
+        let spent: u64 = self
+            .vin
+            .iter()
+            .filter_map(|i| i.prevout.as_ref())
+            .filter(|o| owns(o))
+            .map(|o| o.value)
+            .sum();
+
+        received as i64 - spent as i64
+    }
+
+    /// Addresses from a set that this transaction pays or spends from,
+    /// sorted and without duplicates.
+    pub fn touched_addresses(&self, addresses: &HashSet<String>) -> Vec<String> {
+        let mut touched: Vec<String> = self
+            .vout
+            .iter()
+            .chain(self.vin.iter().filter_map(|i| i.prevout.as_ref()))
+            .filter_map(|o| o.scriptpubkey_address.as_ref())
+            .filter(|a| addresses.contains(*a))
+            .cloned()
+            .collect();
+        touched.sort();
+        touched.dedup();
+        touched
+    }
+}
+
 /// Transaction to broadcast.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BroadcastResult {
@@ -275,6 +354,32 @@ impl BlockchainClient {
             .map_err(|e| MpcWalletError::Serialization(format!("Failed to parse UTXOs: {}", e)))
     }
 
+    /// Get transaction history for an address (newest first).
+    ///
+    /// Esplora returns up to 50 mempool and 25 confirmed transactions per
+    /// page; only the first page is fetched.
+    pub async fn get_address_txs(&self, address: &str) -> Result<Vec<AddressTx>, MpcWalletError> {
+        let url = format!("{}/address/{}/txs", self.api_base, address);
+
+        let response =
+            self.client.get(&url).send().await.map_err(|e| {
+                MpcWalletError::NodeCommunication(format!("API request failed: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(MpcWalletError::NodeCommunication(format!(
+                "API error {}: {}",
+                status, body
+            )));
+        }
+
+        response.json().await.map_err(|e| {
+            MpcWalletError::Serialization(format!("Failed to parse transactions: {}", e))
+        })
+    }
+
     /// Broadcast a signed transaction.
     pub async fn broadcast_tx(&self, tx_hex: &str) -> Result<String, MpcWalletError> {
         let url = format!("{}/tx", self.api_base);
@@ -403,6 +508,37 @@ mod tests {
         assert_eq!(info.total_balance(), 75_000);
     }
 
+    #[test]
+    fn test_address_tx_net_value() {
+        let json = r#"{
+            "txid": "ab",
+            "status": {"confirmed": true, "block_height": 100, "block_time": 1700000000},
+            "vin": [{"prevout": {"scriptpubkey_address": "tb1qours", "value": 50000}}],
+            "vout": [
+                {"scriptpubkey_address": "tb1qtheirs", "value": 30000},
+                {"scriptpubkey_address": "tb1qchange", "value": 19000},
+                {"value": 0}
+            ],
+            "fee": 1000
+        }"#;
+        let tx: AddressTx = serde_json::from_str(json).unwrap();
+        assert_eq!(tx.status.block_height, Some(100));
+
+        let ours: HashSet<String> = ["tb1qours", "tb1qchange"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        assert_eq!(tx.net_value(&ours), -31_000);
+
+        let theirs: HashSet<String> = ["tb1qtheirs".to_string()].into_iter().collect();
+        assert_eq!(tx.net_value(&theirs), 30_000);
+
+        assert_eq!(tx.touched_addresses(&ours), vec!["tb1qchange", "tb1qours"]);
+        assert!(tx
+            .touched_addresses(&["tb1qother".to_string()].into_iter().collect())
+            .is_empty());
+    }
+
     #[test]
     fn test_fee_estimates_default() {
         let fees = FeeEstimates::default();
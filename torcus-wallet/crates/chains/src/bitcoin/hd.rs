@@ -35,10 +35,34 @@ pub struct ExtendedPubKey {
 }
 
 impl ExtendedPubKey {
+    /// Create a root extended public key from the DKG public key and the
+    /// chain code generated jointly during DKG.
+    pub fn new(public_key: &[u8], chain_code: [u8; 32]) -> Result<Self, MpcWalletError> {
+        if public_key.len() != 33 {
+            return Err(MpcWalletError::InvalidPublicKey(format!(
+                "Expected 33 bytes, got {}",
+                public_key.len()
+            )));
+        }
+
+        let mut pk = [0u8; 33];
+        pk.copy_from_slice(public_key);
+
+        Ok(Self {
+            public_key: pk,
+            chain_code,
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_index: 0,
+        })
+    }
+
     /// Create an extended public key from a raw public key.
     ///
-    /// Since we don't have a BIP39 seed, we derive the chain code deterministically
-    /// from the public key itself using: chain_code = SHA256("MPC-BIP32" || pubkey)
+    /// Legacy fallback for wallets whose DKG predates jointly generated chain
+    /// codes: the chain code is derived from the public key itself using
+    /// chain_code = SHA256("MPC-BIP32-CHAINCODE" || pubkey). Prefer
+    /// [`ExtendedPubKey::new`] with the chain code persisted at DKG time.
     pub fn from_public_key(public_key: &[u8]) -> Result<Self, MpcWalletError> {
         if public_key.len() != 33 {
             return Err(MpcWalletError::InvalidPublicKey(format!(
@@ -82,6 +106,14 @@ impl ExtendedPubKey {
     /// Only non-hardened derivation is supported (index < 2^31).
     /// For hardened derivation, you would need the private key.
     pub fn derive_child(&self, index: u32) -> Result<Self, MpcWalletError> {
+        self.derive_child_with_tweak(index).map(|(child, _)| child)
+    }
+
+    /// Derive a child public key and return the additive tweak (IL) used.
+    ///
+    /// `child = parent + IL*G`, so a party holding a share of the parent key
+    /// can sign for the child by adding IL to its share.
+    pub fn derive_child_with_tweak(&self, index: u32) -> Result<(Self, [u8; 32]), MpcWalletError> {
         // Check for hardened derivation attempt
         if index >= 0x80000000 {
             return Err(MpcWalletError::Protocol(
@@ -117,13 +149,19 @@ impl ExtendedPubKey {
         tracing::trace!("  Child pubkey: {}", hex::encode(child_pubkey));
         tracing::trace!("  Child chain code: {}", hex::encode(child_chain_code));
 
-        Ok(Self {
-            public_key: child_pubkey,
-            chain_code: child_chain_code,
-            depth: self.depth + 1,
-            parent_fingerprint: self.fingerprint(),
-            child_index: index,
-        })
+        let mut tweak = [0u8; 32];
+        tweak.copy_from_slice(il);
+
+        Ok((
+            Self {
+                public_key: child_pubkey,
+                chain_code: child_chain_code,
+                depth: self.depth + 1,
+                parent_fingerprint: self.fingerprint(),
+                child_index: index,
+            },
+            tweak,
+        ))
     }
 
     /// Derive a key at a path like "m/0/1/2" (non-hardened only).
@@ -139,6 +177,29 @@ impl ExtendedPubKey {
         Ok(key)
     }
 
+    /// Derive a key at a path and return the accumulated additive tweak.
+    ///
+    /// The tweak is the sum of every level's IL (mod n), so
+    /// `derived = self + tweak*G`. An empty path yields a zero tweak.
+    pub fn derive_path_with_tweak(&self, path: &[u32]) -> Result<(Self, [u8; 32]), MpcWalletError> {
+        use generic_ec::curves::Secp256k1;
+        use generic_ec::Scalar;
+
+        let mut key = self.clone();
+        let mut tweak = Scalar::<Secp256k1>::zero();
+        for &index in path {
+            let (child, il) = key.derive_child_with_tweak(index)?;
+            let il = Scalar::<Secp256k1>::from_be_bytes(il)
+                .map_err(|_| MpcWalletError::Protocol("Invalid scalar".into()))?;
+            tweak += il;
+            key = child;
+        }
+
+        let mut output = [0u8; 32];
+        output.copy_from_slice(tweak.to_be_bytes().as_ref());
+        Ok((key, output))
+    }
+
     /// Get the Bitcoin address (P2WPKH) for this key.
     pub fn to_address(&self, network: Network) -> Result<String, MpcWalletError> {
         derive_bitcoin_address(&self.public_key, network)
@@ -148,6 +209,193 @@ impl ExtendedPubKey {
     pub fn to_legacy_address(&self, network: Network) -> Result<String, MpcWalletError> {
         derive_bitcoin_address_legacy(&self.public_key, network)
     }
+
+    /// Serialize as a BIP32 extended public key (`xpub` on mainnet, `tpub`
+    /// on testnet/signet/regtest).
+    pub fn to_xpub(&self, network: Network) -> Result<String, MpcWalletError> {
+        use bitcoin::bip32::{ChainCode, ChildNumber, Fingerprint, Xpub};
+
+        let public_key = bitcoin::secp256k1::PublicKey::from_slice(&self.public_key)
+            .map_err(|e| MpcWalletError::InvalidPublicKey(e.to_string()))?;
+
+        let xpub = Xpub {
+            network: bitcoin::NetworkKind::from(network),
+            depth: self.depth,
+            parent_fingerprint: Fingerprint::from(self.parent_fingerprint),
+            child_number: ChildNumber::from(self.child_index),
+            public_key,
+            chain_code: ChainCode::from(self.chain_code),
+        };
+
+        Ok(xpub.to_string())
+    }
+
+    /// Parse a BIP32-serialized extended public key (`xpub`/`tpub`).
+    pub fn from_xpub(encoded: &str) -> Result<Self, MpcWalletError> {
+        use std::str::FromStr;
+
+        let xpub = bitcoin::bip32::Xpub::from_str(encoded).map_err(|e| {
+            MpcWalletError::InvalidPublicKey(format!("Invalid extended public key: {}", e))
+        })?;
+
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&xpub.chain_code[..]);
+        let mut parent_fingerprint = [0u8; 4];
+        parent_fingerprint.copy_from_slice(&xpub.parent_fingerprint[..]);
+
+        Ok(Self {
+            public_key: xpub.public_key.serialize(),
+            chain_code,
+            depth: xpub.depth,
+            parent_fingerprint,
+            child_index: u32::from(xpub.child_number),
+        })
+    }
+}
+
+/// Parse a relative, non-hardened derivation path such as `"0/5"` or `"m/0/5"`.
+///
+/// Hardened components (`'` or `h`) are rejected because they cannot be
+/// derived from a public key.
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>, MpcWalletError> {
+    let path = path.trim();
+    let path = path.strip_prefix("m/").unwrap_or(path);
+    if path.is_empty() || path == "m" {
+        return Ok(Vec::new());
+    }
+
+    path.split('/')
+        .map(|component| {
+            if component.ends_with('\'') || component.ends_with('h') {
+                return Err(MpcWalletError::Protocol(format!(
+                    "Hardened derivation not supported: {}",
+                    component
+                )));
+            }
+            match component.parse::<u32>() {
+                Ok(index) if index < 0x80000000 => Ok(index),
+                _ => Err(MpcWalletError::Protocol(format!(
+                    "Invalid derivation path component: {}",
+                    component
+                ))),
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Output Descriptors (BIP-380)
+// ============================================================================
+
+/// Script type of an exported output descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DescriptorKind {
+    /// Native SegWit, `wpkh(KEY)` (BIP-382).
+    Wpkh,
+    /// Taproot key-path only, `tr(KEY)` (BIP-386).
+    Tr,
+    /// Taproot output key used as-is without the BIP-341 tweak, `rawtr(KEY)`
+    /// (BIP-386).
+    RawTr,
+}
+
+/// Build a checksummed output descriptor for a wallet root key.
+///
+/// The key origin is the root's own fingerprint, since the DKG key is the
+/// top of the wallet's derivation tree. With `branch = Some(b)` the
+/// descriptor is ranged over `b/*`; with `None` it describes the root key
+/// itself.
+pub fn output_descriptor(
+    root: &ExtendedPubKey,
+    network: Network,
+    kind: DescriptorKind,
+    branch: Option<u32>,
+) -> Result<String, MpcWalletError> {
+    if root.depth != 0 {
+        return Err(MpcWalletError::Protocol(
+            "Descriptors must be exported from the wallet root key".to_string(),
+        ));
+    }
+    if matches!(branch, Some(b) if b >= 0x80000000) {
+        return Err(MpcWalletError::Protocol(
+            "Hardened descriptor branches are not supported".to_string(),
+        ));
+    }
+
+    let mut key = format!(
+        "[{}]{}",
+        hex::encode(root.fingerprint()),
+        root.to_xpub(network)?
+    );
+    if let Some(branch) = branch {
+        key.push_str(&format!("/{}/*", branch));
+    }
+
+    let descriptor = match kind {
+        DescriptorKind::Wpkh => format!("wpkh({})", key),
+        DescriptorKind::Tr => format!("tr({})", key),
+        DescriptorKind::RawTr => format!("rawtr({})", key),
+    };
+    let checksum = descriptor_checksum(&descriptor)?;
+
+    Ok(format!("{}#{}", descriptor, checksum))
+}
+
+/// Compute the 8-character BIP-380 descriptor checksum.
+pub fn descriptor_checksum(descriptor: &str) -> Result<String, MpcWalletError> {
+    const INPUT_CHARSET: &str =
+        "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+    const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+    fn poly_mod(mut c: u64, val: u64) -> u64 {
+        let c0 = c >> 35;
+        c = ((c & 0x7ffffffff) << 5) ^ val;
+        if c0 & 1 != 0 {
+            c ^= 0xf5dee51989;
+        }
+        if c0 & 2 != 0 {
+            c ^= 0xa9fdca3312;
+        }
+        if c0 & 4 != 0 {
+            c ^= 0x1bab10e32d;
+        }
+        if c0 & 8 != 0 {
+            c ^= 0x3706b1677a;
+        }
+        if c0 & 16 != 0 {
+            c ^= 0x644d626ffd;
+        }
+        c
+    }
+
+    let mut c = 1u64;
+    let mut cls = 0u64;
+    let mut cls_count = 0;
+    for ch in descriptor.chars() {
+        let pos = INPUT_CHARSET.find(ch).ok_or_else(|| {
+            MpcWalletError::Protocol(format!("Invalid descriptor character: {:?}", ch))
+        })? as u64;
+        c = poly_mod(c, pos & 31);
+        cls = cls * 3 + (pos >> 5);
+        cls_count += 1;
+        if cls_count == 3 {
+            c = poly_mod(c, cls);
+            cls = 0;
+            cls_count = 0;
+        }
+    }
+    if cls_count > 0 {
+        c = poly_mod(c, cls);
+    }
+    for _ in 0..8 {
+        c = poly_mod(c, 0);
+    }
+    c ^= 1;
+
+    Ok((0..8)
+        .map(|j| CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize] as char)
+        .collect())
 }
 
 // ============================================================================
@@ -185,6 +433,8 @@ pub struct DerivedAddress {
 pub struct MpcHdWallet {
     /// The account-level extended public key (from MPC DKG).
     account_key: ExtendedPubKey,
+    /// BIP32 serialization of `account_key` (xpub/tpub).
+    account_xpub: String,
     /// Network (mainnet or testnet).
     network: Network,
 }
@@ -193,15 +443,36 @@ impl MpcHdWallet {
     /// Create a new HD wallet from an MPC-generated public key.
     ///
     /// The public key is treated as the account-level key (m/84'/0'/0').
+    ///
+    /// Uses the legacy public-key-derived chain code; wallets created with a
+    /// jointly generated chain code should use [`MpcHdWallet::with_chain_code`].
     pub fn new(public_key: &[u8], network: Network) -> Result<Self, MpcWalletError> {
-        let account_key = ExtendedPubKey::from_public_key(public_key)?;
+        Self::from_account_key(ExtendedPubKey::from_public_key(public_key)?, network)
+    }
+
+    /// Create a new HD wallet from an MPC-generated public key and the chain
+    /// code generated jointly during DKG.
+    pub fn with_chain_code(
+        public_key: &[u8],
+        chain_code: [u8; 32],
+        network: Network,
+    ) -> Result<Self, MpcWalletError> {
+        Self::from_account_key(ExtendedPubKey::new(public_key, chain_code)?, network)
+    }
+
+    fn from_account_key(
+        account_key: ExtendedPubKey,
+        network: Network,
+    ) -> Result<Self, MpcWalletError> {
+        let account_xpub = account_key.to_xpub(network)?;
 
         tracing::info!("Created MPC HD Wallet");
         tracing::debug!("  Network: {:?}", network);
-        tracing::debug!("  Account pubkey: {}", hex::encode(account_key.public_key));
+        tracing::debug!("  Account xpub: {}", account_xpub);
 
         Ok(Self {
             account_key,
+            account_xpub,
             network,
         })
     }
@@ -256,9 +527,20 @@ impl MpcHdWallet {
         Ok(addresses)
     }
 
-    /// Get the account public key (for external verification).
+    /// Get the account extended public key (xpub/tpub) for watch-only import.
     pub fn account_public_key(&self) -> String {
-        hex::encode(self.account_key.public_key)
+        self.account_xpub.clone()
+    }
+
+    /// Get the account-level extended public key.
+    pub fn account_key(&self) -> &ExtendedPubKey {
+        &self.account_key
+    }
+
+    /// Output descriptor for the receiving (`change = 0`) or change
+    /// (`change = 1`) branch of this wallet.
+    pub fn descriptor(&self, kind: DescriptorKind, change: u32) -> Result<String, MpcWalletError> {
+        output_descriptor(&self.account_key, self.network, kind, Some(change))
     }
 }
 
@@ -301,6 +583,127 @@ mod tests {
         assert_ne!(child0.public_key, child1.public_key);
     }
 
+    #[test]
+    fn test_path_tweak_matches_public_derivation() {
+        let pubkey_hex = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let pubkey_bytes = hex::decode(pubkey_hex).unwrap();
+
+        let xpub = ExtendedPubKey::from_public_key(&pubkey_bytes).unwrap();
+        let (derived, tweak) = xpub.derive_path_with_tweak(&[0, 7]).unwrap();
+
+        assert_eq!(
+            derived.public_key,
+            xpub.derive_path(&[0, 7]).unwrap().public_key
+        );
+        assert_eq!(
+            point_add_scalar(&xpub.public_key, &tweak).unwrap(),
+            derived.public_key
+        );
+
+        let (root, zero) = xpub.derive_path_with_tweak(&[]).unwrap();
+        assert_eq!(root.public_key, xpub.public_key);
+        assert_eq!(zero, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_xpub_serialization_bip32_vector() {
+        // BIP32 test vector 1, chain m
+        let pubkey_bytes =
+            hex::decode("0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2")
+                .unwrap();
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(
+            &hex::decode("873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508")
+                .unwrap(),
+        );
+
+        let xpub = ExtendedPubKey::new(&pubkey_bytes, chain_code).unwrap();
+        assert_eq!(
+            xpub.to_xpub(Network::Bitcoin).unwrap(),
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+        );
+        assert!(xpub.to_xpub(Network::Testnet).unwrap().starts_with("tpub"));
+    }
+
+    #[test]
+    fn test_xpub_round_trip_and_derivation() {
+        use bitcoin::bip32::{ChildNumber, Xpub};
+        use std::str::FromStr;
+
+        let pubkey_hex = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let pubkey_bytes = hex::decode(pubkey_hex).unwrap();
+
+        let root = ExtendedPubKey::new(&pubkey_bytes, [7u8; 32]).unwrap();
+        let child = root.derive_path(&[1, 5]).unwrap();
+        let encoded = child.to_xpub(Network::Testnet).unwrap();
+
+        let decoded = ExtendedPubKey::from_xpub(&encoded).unwrap();
+        assert_eq!(decoded.public_key, child.public_key);
+        assert_eq!(decoded.chain_code, child.chain_code);
+        assert_eq!(decoded.depth, 2);
+        assert_eq!(decoded.parent_fingerprint, child.parent_fingerprint);
+        assert_eq!(decoded.child_index, 5);
+
+        // Our derivation matches rust-bitcoin's BIP32 implementation
+        let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+        let reference = Xpub::from_str(&root.to_xpub(Network::Testnet).unwrap())
+            .unwrap()
+            .derive_pub(
+                &secp,
+                &[
+                    ChildNumber::from_normal_idx(1).unwrap(),
+                    ChildNumber::from_normal_idx(5).unwrap(),
+                ],
+            )
+            .unwrap();
+        assert_eq!(reference.to_string(), encoded);
+    }
+
+    #[test]
+    fn test_descriptor_checksum() {
+        // BIP-380 example
+        assert_eq!(descriptor_checksum("raw(deadbeef)").unwrap(), "89f8spxm");
+        assert!(descriptor_checksum("raw(\u{e9})").is_err());
+    }
+
+    #[test]
+    fn test_output_descriptors() {
+        let pubkey_hex = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let pubkey_bytes = hex::decode(pubkey_hex).unwrap();
+
+        let wallet =
+            MpcHdWallet::with_chain_code(&pubkey_bytes, [7u8; 32], Network::Testnet).unwrap();
+        let xpub = wallet.account_public_key();
+        assert!(xpub.starts_with("tpub"));
+
+        let receive = wallet.descriptor(DescriptorKind::Wpkh, 0).unwrap();
+        let fingerprint = hex::encode(wallet.account_key().fingerprint());
+        let (body, checksum) = receive.split_once('#').unwrap();
+        assert_eq!(body, format!("wpkh([{}]{}/0/*)", fingerprint, xpub));
+        assert_eq!(descriptor_checksum(body).unwrap(), checksum);
+
+        let root_tr = output_descriptor(
+            wallet.account_key(),
+            Network::Testnet,
+            DescriptorKind::Tr,
+            None,
+        )
+        .unwrap();
+        assert!(root_tr.starts_with(&format!("tr([{}]{})#", fingerprint, xpub)));
+
+        let child = wallet.account_key().derive_child(0).unwrap();
+        assert!(output_descriptor(&child, Network::Testnet, DescriptorKind::Wpkh, None).is_err());
+    }
+
+    #[test]
+    fn test_parse_derivation_path() {
+        assert_eq!(parse_derivation_path("m/0/5").unwrap(), vec![0, 5]);
+        assert_eq!(parse_derivation_path("1/2").unwrap(), vec![1, 2]);
+        assert!(parse_derivation_path("m").unwrap().is_empty());
+        assert!(parse_derivation_path("m/0'/1").is_err());
+        assert!(parse_derivation_path("m/abc").is_err());
+    }
+
     #[test]
     fn test_hardened_derivation_fails() {
         let pubkey_hex = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
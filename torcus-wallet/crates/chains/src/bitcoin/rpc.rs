@@ -3,11 +3,20 @@
 //! Provides the same interface as BlockchainClient but uses Bitcoin Core RPC
 //! instead of Esplora API. This enables regtest support with block mining.
 
+use std::collections::{HashMap, HashSet};
+
 use common::MpcWalletError;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use super::client::{AddressInfo, ChainStats, FeeEstimates, MempoolStats, Utxo, UtxoStatus};
+use super::client::{
+    AddressInfo, AddressTx, AddressTxInput, AddressTxOutput, ChainStats, FeeEstimates,
+    MempoolStats, TxStatus, Utxo, UtxoStatus,
+};
+use super::hd::descriptor_checksum;
+
+/// Entries fetched per `listtransactions` call.
+const LIST_TRANSACTIONS_PAGE: usize = 1000;
 
 /// Bitcoin Core RPC client configuration.
 #[derive(Debug, Clone)]
@@ -15,6 +24,8 @@ pub struct RpcConfig {
     pub url: String,
     pub user: String,
     pub password: String,
+    /// Watch-only wallet that imported descriptors are tracked in.
+    pub wallet: String,
 }
 
 impl RpcConfig {
@@ -25,15 +36,64 @@ impl RpcConfig {
         let user = std::env::var("BITCOIN_RPC_USER").unwrap_or_else(|_| "bitcoin".to_string());
         let password =
             std::env::var("BITCOIN_RPC_PASSWORD").unwrap_or_else(|_| "bitcoin".to_string());
+        let wallet =
+            std::env::var("BITCOIN_RPC_WALLET").unwrap_or_else(|_| "mpc-watch".to_string());
 
         Ok(Self {
             url,
             user,
             password,
+            wallet,
         })
     }
 }
 
+/// A descriptor to scan for or import, optionally ranged over child indices.
+#[derive(Debug, Clone)]
+pub struct RangedDescriptor {
+    pub descriptor: String,
+    /// First and last child index (inclusive) of a ranged descriptor.
+    pub range: Option<(u32, u32)>,
+}
+
+impl RangedDescriptor {
+    /// Checksummed `addr(...)` descriptor for a single address.
+    pub fn address(address: &str) -> Result<Self, MpcWalletError> {
+        let descriptor = format!("addr({})", address);
+        let checksum = descriptor_checksum(&descriptor)?;
+        Ok(Self {
+            descriptor: format!("{}#{}", descriptor, checksum),
+            range: None,
+        })
+    }
+
+    /// `scantxoutset` scan object.
+    fn scan_object(&self) -> Value {
+        match self.range {
+            Some((start, end)) => json!({ "desc": self.descriptor, "range": [start, end] }),
+            None => json!(self.descriptor),
+        }
+    }
+
+    /// `importdescriptors` request, rescanning from genesis since the
+    /// wallet may have been funded before the import.
+    fn import_request(&self) -> Value {
+        let mut request = json!({ "desc": self.descriptor, "timestamp": 0 });
+        if let Some((start, end)) = self.range {
+            request["range"] = json!([start, end]);
+        }
+        request
+    }
+}
+
+/// An unspent output found by a descriptor scan.
+#[derive(Debug, Clone)]
+pub struct ScannedUtxo {
+    /// Hex-encoded output script, identifying the derived address it pays.
+    pub script_pubkey: String,
+    pub utxo: Utxo,
+}
+
 /// Bitcoin Core RPC client for regtest.
 pub struct BitcoinRpcClient {
     config: RpcConfig,
@@ -58,6 +118,30 @@ impl BitcoinRpcClient {
         &self,
         method: &str,
         params: Vec<Value>,
+    ) -> Result<T, MpcWalletError> {
+        self.call_at(&self.config.url, method, params).await
+    }
+
+    /// Make an RPC call against the watch-only wallet.
+    async fn call_wallet<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: Vec<Value>,
+    ) -> Result<T, MpcWalletError> {
+        let url = format!(
+            "{}/wallet/{}",
+            self.config.url.trim_end_matches('/'),
+            self.config.wallet
+        );
+        self.call_at(&url, method, params).await
+    }
+
+    /// Make an RPC call to an endpoint.
+    async fn call_at<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        method: &str,
+        params: Vec<Value>,
     ) -> Result<T, MpcWalletError> {
         let body = json!({
             "jsonrpc": "1.0",
@@ -68,7 +152,7 @@ impl BitcoinRpcClient {
 
         let response = self
             .client
-            .post(&self.config.url)
+            .post(url)
             .basic_auth(&self.config.user, Some(&self.config.password))
             .json(&body)
             .send()
@@ -137,19 +221,207 @@ impl BitcoinRpcClient {
 
         Ok(scan_result
             .unspents
-            .into_iter()
-            .map(|u| Utxo {
-                txid: u.txid,
-                vout: u.vout,
-                value: (u.amount * 100_000_000.0) as u64,
-                status: UtxoStatus {
-                    confirmed: true, // scantxoutset only returns confirmed
-                    block_height: Some(u.height),
-                },
+            .iter()
+            .map(ScanUnspent::to_utxo)
+            .collect())
+    }
+
+    /// Find the UTXOs of several descriptors in a single `scantxoutset`.
+    ///
+    /// Ranged descriptors cover every child in their range, so a whole gap
+    /// window of derived addresses costs one pass over the UTXO set.
+    pub async fn scan_descriptors(
+        &self,
+        descriptors: &[RangedDescriptor],
+    ) -> Result<Vec<ScannedUtxo>, MpcWalletError> {
+        let objects: Vec<Value> = descriptors.iter().map(|d| d.scan_object()).collect();
+        let scan_result: ScanTxOutSetResult = self
+            .call("scantxoutset", vec![json!("start"), json!(objects)])
+            .await?;
+
+        Ok(scan_result
+            .unspents
+            .iter()
+            .map(|u| ScannedUtxo {
+                script_pubkey: u.script_pub_key.clone(),
+                utxo: u.to_utxo(),
             })
             .collect())
     }
 
+    /// Get transaction history for an address (newest first).
+    ///
+    /// Imports the address into the watch-only wallet first; see
+    /// [`BitcoinRpcClient::get_wallet_txs`].
+    pub async fn get_address_txs(&self, address: &str) -> Result<Vec<AddressTx>, MpcWalletError> {
+        self.import_descriptors(&[RangedDescriptor::address(address)?])
+            .await?;
+
+        let addresses: HashSet<String> = [address.to_string()].into_iter().collect();
+        Ok(self
+            .get_wallet_txs()
+            .await?
+            .into_iter()
+            .filter(|tx| !tx.touched_addresses(&addresses).is_empty())
+            .collect())
+    }
+
+    /// Get every transaction of the watch-only wallet (newest first).
+    ///
+    /// Bitcoin Core only knows the history of descriptors imported with
+    /// [`BitcoinRpcClient::import_descriptors`]. Both receiving and sending
+    /// transactions are listed; inputs carry their prevout when it was
+    /// paid by a wallet transaction, which holds for every input spending
+    /// an imported address.
+    pub async fn get_wallet_txs(&self) -> Result<Vec<AddressTx>, MpcWalletError> {
+        let mut txids = Vec::new();
+        let mut seen = HashSet::new();
+        let mut skip = 0;
+        loop {
+            let entries: Vec<ListTransactionsEntry> = self
+                .call_wallet(
+                    "listtransactions",
+                    vec![
+                        json!("*"),
+                        json!(LIST_TRANSACTIONS_PAGE),
+                        json!(skip),
+                        json!(true),
+                    ],
+                )
+                .await?;
+
+            for entry in &entries {
+                // Conflicted transactions never made it into the chain
+                if entry.confirmations >= 0 && seen.insert(entry.txid.clone()) {
+                    txids.push(entry.txid.clone());
+                }
+            }
+            if entries.len() < LIST_TRANSACTIONS_PAGE {
+                break;
+            }
+            skip += LIST_TRANSACTIONS_PAGE;
+        }
+
+        let mut wallet_txs = HashMap::with_capacity(txids.len());
+        for txid in &txids {
+            let tx: WalletTransactionResult = self
+                .call_wallet(
+                    "gettransaction",
+                    vec![json!(txid), json!(true), json!(true)],
+                )
+                .await?;
+            wallet_txs.insert(txid.clone(), tx);
+        }
+
+        let prevout = |input: &DecodedInput| {
+            let (txid, vout) = (input.txid.as_ref()?, input.vout?);
+            let output = wallet_txs.get(txid)?.decoded.vout.get(vout as usize)?;
+            Some(output.to_output())
+        };
+
+        let mut txs: Vec<(i64, AddressTx)> = wallet_txs
+            .iter()
+            .map(|(txid, tx)| {
+                let vin: Vec<AddressTxInput> = tx
+                    .decoded
+                    .vin
+                    .iter()
+                    .map(|i| AddressTxInput {
+                        prevout: prevout(i),
+                    })
+                    .collect();
+                let vout: Vec<AddressTxOutput> = tx
+                    .decoded
+                    .vout
+                    .iter()
+                    .map(DecodedOutput::to_output)
+                    .collect();
+
+                // The fee is only known once every input has been resolved
+                let fee = if vin.iter().all(|i| i.prevout.is_some()) {
+                    let inputs: u64 = vin.iter().flat_map(|i| &i.prevout).map(|o| o.value).sum();
+                    let outputs: u64 = vout.iter().map(|o| o.value).sum();
+                    inputs.saturating_sub(outputs)
+                } else {
+                    0
+                };
+
+                let address_tx = AddressTx {
+                    txid: txid.clone(),
+                    status: TxStatus {
+                        confirmed: tx.confirmations > 0,
+                        block_height: tx.blockheight,
+                        block_time: tx.blocktime,
+                    },
+                    vin,
+                    vout,
+                    fee,
+                };
+                (tx.confirmations, address_tx)
+            })
+            .collect();
+
+        txs.sort_by_key(|(confirmations, _)| *confirmations);
+        Ok(txs.into_iter().map(|(_, tx)| tx).collect())
+    }
+
+    /// Import descriptors into the watch-only wallet, creating or loading
+    /// the wallet first.
+    ///
+    /// Re-importing a descriptor with a wider range extends it; every import
+    /// rescans the chain, which is cheap on regtest.
+    pub async fn import_descriptors(
+        &self,
+        descriptors: &[RangedDescriptor],
+    ) -> Result<(), MpcWalletError> {
+        self.ensure_watch_wallet().await?;
+
+        let requests: Vec<Value> = descriptors.iter().map(|d| d.import_request()).collect();
+        let results: Vec<ImportResult> = self
+            .call_wallet("importdescriptors", vec![json!(requests)])
+            .await?;
+
+        for (descriptor, result) in descriptors.iter().zip(results) {
+            if !result.success {
+                return Err(MpcWalletError::NodeCommunication(format!(
+                    "Failed to import descriptor {}: {}",
+                    descriptor.descriptor,
+                    result.error.map(|e| e.message).unwrap_or_default()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Make sure the watch-only wallet exists and is loaded.
+    async fn ensure_watch_wallet(&self) -> Result<(), MpcWalletError> {
+        let loaded: Vec<String> = self.call("listwallets", vec![]).await?;
+        if loaded.contains(&self.config.wallet) {
+            return Ok(());
+        }
+        if self.load_wallet(&self.config.wallet).await.is_ok() {
+            return Ok(());
+        }
+
+        let _created: CreateWalletResult = self
+            .call(
+                "createwallet",
+                vec![
+                    json!(self.config.wallet),
+                    json!(true),  // disable_private_keys
+                    json!(true),  // blank
+                    json!(""),    // passphrase
+                    json!(false), // avoid_reuse
+                    json!(true),  // descriptors
+                    json!(true),  // load_on_startup
+                ],
+            )
+            .await?;
+
+        Ok(())
+    }
+
     /// Broadcast a signed transaction.
     pub async fn broadcast_tx(&self, tx_hex: &str) -> Result<String, MpcWalletError> {
         self.call("sendrawtransaction", vec![json!(tx_hex)]).await
@@ -312,10 +584,82 @@ impl ScanTxOutSetResult {
 struct ScanUnspent {
     txid: String,
     vout: u32,
+    #[serde(rename = "scriptPubKey")]
+    script_pub_key: String,
     amount: f64,
     height: u64,
 }
 
+impl ScanUnspent {
+    fn to_utxo(&self) -> Utxo {
+        Utxo {
+            txid: self.txid.clone(),
+            vout: self.vout,
+            value: (self.amount * 100_000_000.0) as u64,
+            status: UtxoStatus {
+                confirmed: true, // scantxoutset only returns confirmed
+                block_height: Some(self.height),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ListTransactionsEntry {
+    #[serde(default)]
+    confirmations: i64,
+    txid: String,
+}
+
+/// `gettransaction` result with the decoded transaction.
+#[derive(Debug, Deserialize)]
+struct WalletTransactionResult {
+    #[serde(default)]
+    confirmations: i64,
+    #[serde(default)]
+    blockheight: Option<u64>,
+    #[serde(default)]
+    blocktime: Option<u64>,
+    decoded: DecodedTransaction,
+}
+
+#[derive(Debug, Deserialize)]
+struct DecodedTransaction {
+    vin: Vec<DecodedInput>,
+    vout: Vec<DecodedOutput>,
+}
+
+/// Transaction input; `txid` and `vout` are absent for coinbase inputs.
+#[derive(Debug, Deserialize)]
+struct DecodedInput {
+    #[serde(default)]
+    txid: Option<String>,
+    #[serde(default)]
+    vout: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DecodedOutput {
+    value: f64,
+    #[serde(rename = "scriptPubKey")]
+    script_pub_key: DecodedScript,
+}
+
+impl DecodedOutput {
+    fn to_output(&self) -> AddressTxOutput {
+        AddressTxOutput {
+            scriptpubkey_address: self.script_pub_key.address.clone(),
+            value: (self.value * 100_000_000.0).round() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DecodedScript {
+    #[serde(default)]
+    address: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct EstimateSmartFeeResult {
     feerate: Option<f64>,
@@ -355,8 +699,9 @@ struct DescriptorInfo {
 
 #[derive(Debug, Deserialize)]
 struct ImportResult {
-    #[allow(dead_code)]
     success: bool,
+    #[serde(default)]
+    error: Option<RpcError>,
 }
 
 // ============================================================================
@@ -373,10 +718,31 @@ mod tests {
         std::env::remove_var("BITCOIN_RPC_URL");
         std::env::remove_var("BITCOIN_RPC_USER");
         std::env::remove_var("BITCOIN_RPC_PASSWORD");
+        std::env::remove_var("BITCOIN_RPC_WALLET");
 
         let config = RpcConfig::from_env().unwrap();
         assert_eq!(config.url, "http://localhost:18443");
         assert_eq!(config.user, "bitcoin");
         assert_eq!(config.password, "bitcoin");
+        assert_eq!(config.wallet, "mpc-watch");
+    }
+
+    #[test]
+    fn test_ranged_descriptor_requests() {
+        let ranged = RangedDescriptor {
+            descriptor: "wpkh(tpub/0/*)".to_string(),
+            range: Some((5, 24)),
+        };
+        assert_eq!(
+            ranged.scan_object(),
+            json!({ "desc": "wpkh(tpub/0/*)", "range": [5, 24] })
+        );
+        assert_eq!(ranged.import_request()["range"], json!([5, 24]));
+        assert_eq!(ranged.import_request()["timestamp"], json!(0));
+
+        let address = RangedDescriptor::address("bcrt1qexample").unwrap();
+        assert!(address.descriptor.starts_with("addr(bcrt1qexample)#"));
+        assert_eq!(address.scan_object(), json!(address.descriptor));
+        assert!(address.import_request().get("range").is_none());
     }
 }
@@ -1,4 +1,4 @@
-//! Bitcoin-related commands (balance, faucet, send).
+//! Bitcoin-related commands (balance, history, faucet, send).
 
 use anyhow::{Context, Result};
 use common::{BalanceResponse, SendBitcoinRequest, SendBitcoinResponse};
@@ -17,7 +17,8 @@ pub async fn get_balance(coordinator_url: &str, wallet_id: &str) -> Result<()> {
         anyhow::bail!("Error: {}", error_text);
     }
 
-    let balance: BalanceResponse = response.json().await?;
+    let data: serde_json::Value = response.json().await?;
+    let balance: BalanceResponse = serde_json::from_value(data.clone())?;
 
     println!();
     println!("Wallet Balance");
@@ -35,6 +36,24 @@ pub async fn get_balance(coordinator_url: &str, wallet_id: &str) -> Result<()> {
         balance.total_sats, balance.total_btc
     );
 
+    // Per-address breakdown when funds are spread over derived addresses
+    if let Some(addresses) = data["addresses"].as_array().filter(|a| a.len() > 1) {
+        println!();
+        println!("  Addresses ({}):", addresses.len());
+        for addr in addresses {
+            let path = match (addr["change"].as_u64(), addr["index"].as_u64()) {
+                (Some(change), Some(index)) => format!("{}/{}", change, index),
+                _ => "root".to_string(),
+            };
+            println!(
+                "    {:<8} {}  {} sats",
+                path,
+                addr["address"].as_str().unwrap_or("?"),
+                addr["total_sats"].as_u64().unwrap_or(0)
+            );
+        }
+    }
+
     if balance.total_sats == 0 {
         println!();
         println!("No balance! Get testnet coins:");
@@ -44,6 +63,51 @@ pub async fn get_balance(coordinator_url: &str, wallet_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Get wallet transaction history.
+pub async fn get_history(coordinator_url: &str, wallet_id: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+    let url = format!("{}/wallet/{}/history", coordinator_url, wallet_id);
+
+    let response = client.get(&url).send().await?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        anyhow::bail!("Error: {}", error_text);
+    }
+
+    let data: serde_json::Value = response.json().await?;
+
+    println!();
+    println!("Wallet History");
+    println!("==============");
+    println!(
+        "  Addresses scanned with activity: {}",
+        data["address_count"].as_u64().unwrap_or(0)
+    );
+    println!();
+
+    let transactions = data["transactions"].as_array().cloned().unwrap_or_default();
+    if transactions.is_empty() {
+        println!("  No transactions.");
+        return Ok(());
+    }
+
+    for tx in transactions {
+        let height = match tx["block_height"].as_u64() {
+            Some(height) => height.to_string(),
+            None => "mempool".to_string(),
+        };
+        println!(
+            "  {:>+12} sats  {:>8}  {}",
+            tx["net_sats"].as_i64().unwrap_or(0),
+            height,
+            tx["txid"].as_str().unwrap_or("?")
+        );
+    }
+
+    Ok(())
+}
+
 /// Get faucet information.
 pub async fn get_faucet(coordinator_url: &str, wallet_id: &str) -> Result<()> {
     let client = reqwest::Client::new();
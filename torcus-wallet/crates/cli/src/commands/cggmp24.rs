@@ -273,6 +273,7 @@ pub async fn cggmp24_create(coordinator_url: &str, name: &str, threshold: u16) -
 
     let mut keygen_complete = false;
     let mut cggmp24_pubkey: Option<String> = None;
+    let mut cggmp24_xpub: Option<String> = None;
 
     for i in 0..60 {
         tokio::time::sleep(std::time::Duration::from_secs(5)).await;
@@ -291,6 +292,7 @@ pub async fn cggmp24_create(coordinator_url: &str, name: &str, threshold: u16) -
                             if num_parties > 0 && nodes_count >= num_parties {
                                 keygen_complete = true;
                                 cggmp24_pubkey = ks["public_key"].as_str().map(|s| s.to_string());
+                                cggmp24_xpub = ks["xpub"].as_str().map(|s| s.to_string());
                             }
                             break;
                         }
@@ -319,7 +321,8 @@ pub async fn cggmp24_create(coordinator_url: &str, name: &str, threshold: u16) -
         if let Some(pubkey) = cggmp24_pubkey {
             let update_url = format!("{}/wallet/{}/pubkey", coordinator_url, wallet_id);
             let update_request = serde_json::json!({
-                "public_key": pubkey
+                "public_key": pubkey,
+                "xpub": cggmp24_xpub
             });
 
             match client.put(&update_url).json(&update_request).send().await {
@@ -2,7 +2,7 @@
 //!
 //! This module organizes commands by domain:
 //! - `wallet`: Wallet CRUD and HD address derivation
-//! - `bitcoin`: Balance, faucet, and legacy send
+//! - `bitcoin`: Balance, history, faucet, and legacy send
 //! - `cggmp24`: CGGMP24 threshold ECDSA commands
 //! - `taproot`: Taproot/FROST threshold Schnorr commands
 
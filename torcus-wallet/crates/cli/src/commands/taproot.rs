@@ -84,6 +84,7 @@ pub async fn taproot_create(coordinator_url: &str, name: &str, threshold: u16) -
 
     let mut keygen_complete = false;
     let mut frost_pubkey: Option<String> = None;
+    let mut frost_xpub: Option<String> = None;
 
     for i in 0..60 {
         tokio::time::sleep(std::time::Duration::from_secs(5)).await;
@@ -102,6 +103,7 @@ pub async fn taproot_create(coordinator_url: &str, name: &str, threshold: u16) -
                             if num_parties > 0 && nodes_count >= num_parties {
                                 keygen_complete = true;
                                 frost_pubkey = ks["public_key"].as_str().map(|s| s.to_string());
+                                frost_xpub = ks["xpub"].as_str().map(|s| s.to_string());
                             }
                             break;
                         }
@@ -127,7 +129,8 @@ pub async fn taproot_create(coordinator_url: &str, name: &str, threshold: u16) -
             println!("Step 3: Updating wallet record with Taproot address...");
             let update_url = format!("{}/wallet/{}/taproot-pubkey", coordinator_url, wallet_id);
             let update_request = serde_json::json!({
-                "public_key": pubkey
+                "public_key": pubkey,
+                "xpub": frost_xpub
             });
 
             let update_response = client.put(&update_url).json(&update_request).send().await;
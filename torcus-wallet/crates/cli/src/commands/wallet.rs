@@ -201,6 +201,42 @@ pub async fn get_address(coordinator_url: &str, wallet_id: &str, index: u32) ->
     Ok(())
 }
 
+/// Export a wallet's xpub and output descriptors for watch-only import.
+pub async fn get_descriptors(coordinator_url: &str, wallet_id: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+    let url = format!("{}/wallet/{}/descriptors", coordinator_url, wallet_id);
+
+    let response = client.get(&url).send().await?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        anyhow::bail!("Error: {}", error_text);
+    }
+
+    let export: serde_json::Value = response.json().await?;
+
+    println!("Watch-only export for wallet {}", wallet_id);
+    println!("==========================================");
+    println!(
+        "  Fingerprint: {}",
+        export["fingerprint"].as_str().unwrap_or("-")
+    );
+    println!("  Xpub:        {}", export["xpub"].as_str().unwrap_or("-"));
+    println!();
+    println!("Descriptors:");
+    if let Some(descriptors) = export["descriptors"].as_array() {
+        for d in descriptors {
+            println!(
+                "  {:<8} {}",
+                d["label"].as_str().unwrap_or("-"),
+                d["descriptor"].as_str().unwrap_or("-")
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Get system information from the coordinator.
 pub async fn get_info(coordinator_url: &str) -> Result<()> {
     let client = reqwest::Client::new();
@@ -105,6 +105,13 @@ enum Commands {
         index: u32,
     },
 
+    /// Export xpub and output descriptors for a watch-only wallet.
+    Descriptors {
+        /// Wallet ID (UUID).
+        #[arg(short, long)]
+        wallet_id: String,
+    },
+
     /// Get wallet balance (Bitcoin only).
     Balance {
         /// Wallet ID (UUID).
@@ -112,6 +119,13 @@ enum Commands {
         wallet_id: String,
     },
 
+    /// Get wallet transaction history across all used addresses.
+    History {
+        /// Wallet ID (UUID).
+        #[arg(short, long)]
+        wallet_id: String,
+    },
+
     /// Get testnet faucet information.
     Faucet {
         /// Wallet ID (UUID).
@@ -271,9 +285,15 @@ async fn main() -> Result<()> {
         Commands::GetAddress { wallet_id, index } => {
             commands::get_address(&cli.coordinator, &wallet_id, index).await?;
         }
+        Commands::Descriptors { wallet_id } => {
+            commands::get_descriptors(&cli.coordinator, &wallet_id).await?;
+        }
         Commands::Balance { wallet_id } => {
             commands::get_balance(&cli.coordinator, &wallet_id).await?;
         }
+        Commands::History { wallet_id } => {
+            commands::get_history(&cli.coordinator, &wallet_id).await?;
+        }
         Commands::Faucet { wallet_id } => {
             commands::get_faucet(&cli.coordinator, &wallet_id).await?;
         }
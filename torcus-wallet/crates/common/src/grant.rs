@@ -34,6 +34,11 @@ pub struct SigningGrant {
     #[serde(with = "hex_array")]
     pub message_hash: [u8; 32],
 
+    /// Non-hardened BIP32 path below the wallet key that signs (empty =
+    /// the root key).
+    #[serde(default)]
+    pub derivation_path: Vec<u32>,
+
     /// Threshold required for signing.
     pub threshold: u16,
 
@@ -61,6 +66,7 @@ struct SigningGrantData {
     pub wallet_id: String,
     #[serde(with = "hex_array")]
     pub message_hash: [u8; 32],
+    pub derivation_path: Vec<u32>,
     pub threshold: u16,
     pub participants: Vec<u16>,
     pub nonce: u64,
@@ -78,6 +84,28 @@ impl SigningGrant {
         wallet_id: String,
         message_hash: [u8; 32],
         threshold: u16,
+        participants: Vec<u16>,
+        signing_key: &SigningKey,
+    ) -> Self {
+        Self::for_derived_key(
+            wallet_id,
+            message_hash,
+            Vec::new(),
+            threshold,
+            participants,
+            signing_key,
+        )
+    }
+
+    /// Create a grant to sign with the child key at `derivation_path`.
+    ///
+    /// The path is signed along with the rest of the grant, so a node only
+    /// tweaks its key share by the path the issuer authorized.
+    pub fn for_derived_key(
+        wallet_id: String,
+        message_hash: [u8; 32],
+        derivation_path: Vec<u32>,
+        threshold: u16,
         mut participants: Vec<u16>,
         signing_key: &SigningKey,
     ) -> Self {
@@ -93,6 +121,7 @@ impl SigningGrant {
             grant_id: Uuid::new_v4(),
             wallet_id,
             message_hash,
+            derivation_path,
             threshold,
             participants,
             nonce: rand::random(),
@@ -129,6 +158,7 @@ impl SigningGrant {
             grant_id: Uuid::new_v4(),
             wallet_id,
             message_hash,
+            derivation_path: Vec::new(),
             threshold,
             participants,
             nonce: rand::random(),
@@ -149,6 +179,7 @@ impl SigningGrant {
             grant_id: self.grant_id,
             wallet_id: self.wallet_id.clone(),
             message_hash: self.message_hash,
+            derivation_path: self.derivation_path.clone(),
             threshold: self.threshold,
             participants: self.participants.clone(),
             nonce: self.nonce,
@@ -412,4 +443,33 @@ mod tests {
         assert_eq!(grant.wallet_id, restored.wallet_id);
         assert_eq!(grant.message_hash, restored.message_hash);
     }
+
+    #[test]
+    fn test_grant_signs_derivation_path() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let verifying_key = signing_key.verifying_key();
+
+        let grant = SigningGrant::for_derived_key(
+            "test-wallet".to_string(),
+            [0xab; 32],
+            vec![0, 7],
+            3,
+            vec![0, 1, 2],
+            &signing_key,
+        );
+        assert_eq!(grant.derivation_path, vec![0, 7]);
+        assert!(grant.verify(&verifying_key).is_ok());
+
+        // Redirecting the grant to another child key breaks the signature
+        let mut redirected = grant.clone();
+        redirected.derivation_path = vec![0, 8];
+        assert!(matches!(
+            redirected.verify(&verifying_key),
+            Err(GrantError::InvalidSignature)
+        ));
+
+        let mut root = grant;
+        root.derivation_path.clear();
+        assert!(root.verify(&verifying_key).is_err());
+    }
 }
@@ -18,8 +18,9 @@ pub use bitcoin_tx::{
 };
 pub use bitcoin_utils::{
     derive_bitcoin_address, derive_bitcoin_address_legacy, derive_bitcoin_address_taproot,
-    derive_bitcoin_address_taproot_from_xonly, derive_ethereum_address, DerivedAddress,
-    ExtendedPubKey, MpcHdWallet,
+    derive_bitcoin_address_taproot_from_xonly, derive_ethereum_address, descriptor_checksum,
+    output_descriptor, parse_derivation_path, DerivedAddress, DescriptorKind, ExtendedPubKey,
+    MpcHdWallet,
 };
 pub use grant::{GrantError, SigningGrant, DEFAULT_GRANT_VALIDITY_SECS};
 pub use observability::{EventType, LogEvent, MetricsSnapshot, ProtocolMetrics, SessionSpan};
@@ -33,7 +34,8 @@ pub use selection::{
     SelectionResult,
 };
 pub use storage::{
-    KeyShareStore, RelaySessionStore, StoredKeyShare, StoredRelaySession, StoredWallet, WalletStore,
+    KeyShareStore, RelaySessionStore, StoredKeyShare, StoredRelaySession, StoredWallet,
+    UsedAddress, WalletStore,
 };
 pub use types::{
     CreateWalletRequest, CreateWalletResponse, MpcWalletError, NodeStatus, WalletType,
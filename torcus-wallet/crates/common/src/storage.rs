@@ -27,6 +27,20 @@ pub struct StoredWallet {
     pub public_key: String,
     pub address: String,
     pub created_at: DateTime<Utc>,
+    /// BIP32 root extended public key (carries the chain code generated
+    /// jointly during DKG). `None` for wallets created before joint chain codes.
+    #[serde(default)]
+    pub xpub: Option<String>,
+}
+
+/// A derived wallet address that has seen on-chain activity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsedAddress {
+    pub wallet_id: Uuid,
+    /// BIP32 branch (0 = receiving, 1 = change).
+    pub change: u32,
+    pub index: u32,
+    pub address: String,
 }
 
 /// Coordinator's wallet storage.
@@ -82,6 +96,25 @@ impl WalletStore {
         )
         .map_err(|e| MpcWalletError::Storage(format!("Failed to create schema: {}", e)))?;
 
+        // Older stores predate the xpub column
+        let has_xpub = conn.prepare("SELECT xpub FROM wallets LIMIT 0").is_ok();
+        if !has_xpub {
+            conn.execute("ALTER TABLE wallets ADD COLUMN xpub TEXT", [])
+                .map_err(|e| MpcWalletError::Storage(format!("Failed to migrate schema: {}", e)))?;
+        }
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS used_addresses (
+                wallet_id TEXT NOT NULL,
+                change INTEGER NOT NULL,
+                idx INTEGER NOT NULL,
+                address TEXT NOT NULL,
+                PRIMARY KEY (wallet_id, change, idx)
+            )",
+            [],
+        )
+        .map_err(|e| MpcWalletError::Storage(format!("Failed to create schema: {}", e)))?;
+
         tracing::debug!("Wallet store schema initialized");
         Ok(())
     }
@@ -94,8 +127,8 @@ impl WalletStore {
             .map_err(|e| MpcWalletError::Storage(format!("Lock error: {}", e)))?;
 
         conn.execute(
-            "INSERT OR REPLACE INTO wallets (id, name, wallet_type, public_key, address, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT OR REPLACE INTO wallets (id, name, wallet_type, public_key, address, created_at, xpub)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             params![
                 wallet.id.to_string(),
                 wallet.name,
@@ -103,6 +136,7 @@ impl WalletStore {
                 wallet.public_key,
                 wallet.address,
                 wallet.created_at.to_rfc3339(),
+                wallet.xpub,
             ],
         )
         .map_err(|e| MpcWalletError::Storage(format!("Failed to save wallet: {}", e)))?;
@@ -119,7 +153,7 @@ impl WalletStore {
             .map_err(|e| MpcWalletError::Storage(format!("Lock error: {}", e)))?;
 
         let mut stmt = conn
-            .prepare("SELECT id, name, wallet_type, public_key, address, created_at FROM wallets WHERE id = ?1")
+            .prepare("SELECT id, name, wallet_type, public_key, address, created_at, xpub FROM wallets WHERE id = ?1")
             .map_err(|e| MpcWalletError::Storage(format!("Query error: {}", e)))?;
 
         let wallet = stmt
@@ -160,6 +194,7 @@ impl WalletStore {
                     public_key: row.get(3)?,
                     address: row.get(4)?,
                     created_at,
+                    xpub: row.get(6)?,
                 })
             })
             .optional()
@@ -176,7 +211,7 @@ impl WalletStore {
             .map_err(|e| MpcWalletError::Storage(format!("Lock error: {}", e)))?;
 
         let mut stmt = conn
-            .prepare("SELECT id, name, wallet_type, public_key, address, created_at FROM wallets ORDER BY created_at DESC")
+            .prepare("SELECT id, name, wallet_type, public_key, address, created_at, xpub FROM wallets ORDER BY created_at DESC")
             .map_err(|e| MpcWalletError::Storage(format!("Query error: {}", e)))?;
 
         let wallets = stmt
@@ -217,6 +252,7 @@ impl WalletStore {
                     public_key: row.get(3)?,
                     address: row.get(4)?,
                     created_at,
+                    xpub: row.get(6)?,
                 })
             })
             .map_err(|e| MpcWalletError::Storage(format!("Query error: {}", e)))?
@@ -237,9 +273,67 @@ impl WalletStore {
             .execute("DELETE FROM wallets WHERE id = ?1", params![id.to_string()])
             .map_err(|e| MpcWalletError::Storage(format!("Delete error: {}", e)))?;
 
+        conn.execute(
+            "DELETE FROM used_addresses WHERE wallet_id = ?1",
+            params![id.to_string()],
+        )
+        .map_err(|e| MpcWalletError::Storage(format!("Delete error: {}", e)))?;
+
         Ok(rows > 0)
     }
 
+    /// Record that a derived address has been used.
+    pub fn mark_address_used(&self, address: &UsedAddress) -> Result<(), MpcWalletError> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| MpcWalletError::Storage(format!("Lock error: {}", e)))?;
+
+        conn.execute(
+            "INSERT OR IGNORE INTO used_addresses (wallet_id, change, idx, address)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                address.wallet_id.to_string(),
+                address.change,
+                address.index,
+                address.address,
+            ],
+        )
+        .map_err(|e| MpcWalletError::Storage(format!("Failed to save address: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// List the used derived addresses of a wallet, ordered by branch and index.
+    pub fn list_used_addresses(&self, wallet_id: Uuid) -> Result<Vec<UsedAddress>, MpcWalletError> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| MpcWalletError::Storage(format!("Lock error: {}", e)))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT change, idx, address FROM used_addresses
+                 WHERE wallet_id = ?1 ORDER BY change, idx",
+            )
+            .map_err(|e| MpcWalletError::Storage(format!("Query error: {}", e)))?;
+
+        let addresses = stmt
+            .query_map(params![wallet_id.to_string()], |row| {
+                Ok(UsedAddress {
+                    wallet_id,
+                    change: row.get(0)?,
+                    index: row.get(1)?,
+                    address: row.get(2)?,
+                })
+            })
+            .map_err(|e| MpcWalletError::Storage(format!("Query error: {}", e)))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| MpcWalletError::Storage(format!("Query error: {}", e)))?;
+
+        Ok(addresses)
+    }
+
     /// Get wallet count.
     pub fn wallet_count(&self) -> Result<usize, MpcWalletError> {
         let conn = self
@@ -977,6 +1071,7 @@ mod tests {
             public_key: "02abc123".to_string(),
             address: "tb1qtest".to_string(),
             created_at: Utc::now(),
+            xpub: Some("tpubtest".to_string()),
         };
 
         // Save
@@ -986,6 +1081,7 @@ mod tests {
         let loaded = store.get_wallet(wallet.id).unwrap().unwrap();
         assert_eq!(loaded.name, wallet.name);
         assert_eq!(loaded.address, wallet.address);
+        assert_eq!(loaded.xpub, wallet.xpub);
 
         // List
         let wallets = store.list_wallets().unwrap();
@@ -999,6 +1095,36 @@ mod tests {
         assert_eq!(store.wallet_count().unwrap(), 0);
     }
 
+    #[test]
+    fn test_used_addresses() {
+        let store = WalletStore::open_in_memory().unwrap();
+        let wallet_id = Uuid::new_v4();
+
+        let used = |change, index| UsedAddress {
+            wallet_id,
+            change,
+            index,
+            address: format!("tb1q{}{}", change, index),
+        };
+
+        store.mark_address_used(&used(1, 0)).unwrap();
+        store.mark_address_used(&used(0, 3)).unwrap();
+        store.mark_address_used(&used(0, 0)).unwrap();
+        // Marking twice is a no-op
+        store.mark_address_used(&used(0, 3)).unwrap();
+
+        let addresses = store.list_used_addresses(wallet_id).unwrap();
+        assert_eq!(addresses, vec![used(0, 0), used(0, 3), used(1, 0)]);
+        assert!(store
+            .list_used_addresses(Uuid::new_v4())
+            .unwrap()
+            .is_empty());
+
+        // Deleting the wallet forgets its addresses
+        store.delete_wallet(wallet_id).unwrap();
+        assert!(store.list_used_addresses(wallet_id).unwrap().is_empty());
+    }
+
     #[test]
     fn test_key_share_store_crud() {
         let store = KeyShareStore::open_in_memory(0).unwrap();
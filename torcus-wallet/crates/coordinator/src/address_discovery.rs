@@ -0,0 +1,512 @@
+//! Gap-limit address discovery for HD wallets.
+//!
+//! Deposits can arrive at any address handed out from the receiving or
+//! change branch, not just the DKG root address. Discovery walks each branch
+//! BIP-44 style: addresses are checked in order until `gap_limit` consecutive
+//! addresses without history are seen. Used indices are persisted, so an
+//! address stays part of the wallet even if the backend stops reporting its
+//! history (Bitcoin Core's `scantxoutset` only sees unspent outputs).
+//!
+//! Esplora is queried address by address. With Bitcoin Core each branch's
+//! gap window is covered by a ranged `wpkh(xpub/<branch>/*)` descriptor, so
+//! one `scantxoutset` checks every window at once.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+use chains::bitcoin::{
+    AddressInfo, BitcoinRpcClient, ChainStats, MempoolStats, RangedDescriptor, Utxo,
+};
+use common::{DerivedAddress, DescriptorKind, MpcHdWallet, StoredWallet, UsedAddress, WalletType};
+
+use crate::bitcoin_client::BitcoinBackend;
+use crate::handlers::{get_bitcoin_network, hd_wallet_for};
+use crate::state::{AppState, WalletInfo};
+
+/// BIP-44 default gap limit.
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// Upper bound on the gap limit accepted from clients.
+pub const MAX_GAP_LIMIT: u32 = 100;
+
+/// Branches scanned for HD wallets (0 = receiving, 1 = change).
+const BRANCHES: [u32; 2] = [0, 1];
+
+/// Walks one branch and decides when the gap limit has been reached.
+#[derive(Debug, Clone)]
+pub struct GapScanner {
+    gap_limit: u32,
+    next: u32,
+    unused_run: u32,
+}
+
+impl GapScanner {
+    pub fn new(gap_limit: u32) -> Self {
+        Self {
+            gap_limit: gap_limit.max(1),
+            next: 0,
+            unused_run: 0,
+        }
+    }
+
+    /// Next index to check, or `None` once `gap_limit` consecutive unused
+    /// addresses have been seen.
+    pub fn next_index(&self) -> Option<u32> {
+        (self.unused_run < self.gap_limit).then_some(self.next)
+    }
+
+    /// Record whether the address at `next_index()` has been used.
+    pub fn record(&mut self, used: bool) {
+        if used {
+            self.unused_run = 0;
+        } else {
+            self.unused_run += 1;
+        }
+        self.next += 1;
+    }
+}
+
+/// Exclusive end of the index window a branch has to cover: `gap_limit`
+/// addresses past the last used one, matching where [`GapScanner`] stops.
+pub fn gap_window_end(last_used: Option<u32>, gap_limit: u32) -> u32 {
+    last_used.map_or(0, |index| index + 1) + gap_limit.max(1)
+}
+
+/// An address belonging to a wallet.
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletAddress {
+    pub address: String,
+    /// BIP32 branch and index; `None` for the DKG root address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+}
+
+/// A wallet address with its on-chain statistics.
+#[derive(Debug, Clone)]
+pub struct DiscoveredAddress {
+    pub address: WalletAddress,
+    pub info: AddressInfo,
+    /// UTXOs found by a descriptor scan during discovery; `None` when the
+    /// backend has to be asked per address.
+    pub utxos: Option<Vec<Utxo>>,
+}
+
+/// UTXOs of a discovered address, reusing those found during discovery.
+pub async fn address_utxos(
+    backend: &BitcoinBackend,
+    discovered: &DiscoveredAddress,
+) -> Result<Vec<Utxo>, (StatusCode, String)> {
+    match &discovered.utxos {
+        Some(utxos) => Ok(utxos.clone()),
+        None => backend
+            .get_utxos(&discovered.address.address)
+            .await
+            .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string())),
+    }
+}
+
+/// Whether an address has any confirmed or mempool history.
+pub fn is_used(info: &AddressInfo) -> bool {
+    info.chain_stats.tx_count + info.mempool_stats.tx_count > 0
+}
+
+/// Sum the statistics of several addresses into one `AddressInfo`.
+pub fn aggregate_info<'a>(
+    address: String,
+    infos: impl IntoIterator<Item = &'a AddressInfo>,
+) -> AddressInfo {
+    let mut chain = ChainStats::default();
+    let mut mempool = MempoolStats::default();
+
+    for info in infos {
+        chain.funded_txo_count += info.chain_stats.funded_txo_count;
+        chain.funded_txo_sum += info.chain_stats.funded_txo_sum;
+        chain.spent_txo_count += info.chain_stats.spent_txo_count;
+        chain.spent_txo_sum += info.chain_stats.spent_txo_sum;
+        chain.tx_count += info.chain_stats.tx_count;
+        mempool.funded_txo_count += info.mempool_stats.funded_txo_count;
+        mempool.funded_txo_sum += info.mempool_stats.funded_txo_sum;
+        mempool.spent_txo_count += info.mempool_stats.spent_txo_count;
+        mempool.spent_txo_sum += info.mempool_stats.spent_txo_sum;
+        mempool.tx_count += info.mempool_stats.tx_count;
+    }
+
+    AddressInfo {
+        address,
+        chain_stats: chain,
+        mempool_stats: mempool,
+    }
+}
+
+/// Statistics of an address as seen by a UTXO set scan, which reports
+/// neither spent outputs nor mempool activity.
+fn info_from_utxos(address: &str, utxos: &[Utxo]) -> AddressInfo {
+    AddressInfo {
+        address: address.to_string(),
+        chain_stats: ChainStats {
+            funded_txo_count: utxos.len() as u64,
+            funded_txo_sum: utxos.iter().map(|u| u.value).sum(),
+            tx_count: utxos.len() as u64,
+            ..Default::default()
+        },
+        mempool_stats: MempoolStats::default(),
+    }
+}
+
+/// Discover the addresses of a wallet that have on-chain history.
+///
+/// The DKG root address is always returned first. Bitcoin (SegWit) wallets
+/// additionally scan their receiving and change branches up to `gap_limit`
+/// unused addresses; Taproot wallets have no HD branches yet.
+pub async fn discover_wallet_addresses(
+    state: &Arc<RwLock<AppState>>,
+    wallet: &StoredWallet,
+    backend: &BitcoinBackend,
+    gap_limit: u32,
+) -> Result<Vec<DiscoveredAddress>, (StatusCode, String)> {
+    let gap_limit = gap_limit.clamp(1, MAX_GAP_LIMIT);
+
+    let root_info = backend
+        .get_address_info(&wallet.address)
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+
+    let mut discovered = vec![DiscoveredAddress {
+        address: WalletAddress {
+            address: wallet.address.clone(),
+            change: None,
+            index: None,
+        },
+        info: root_info,
+        utxos: None,
+    }];
+
+    if wallet.wallet_type != WalletType::Bitcoin {
+        return Ok(discovered);
+    }
+
+    let network = get_bitcoin_network()?;
+    let hd_wallet = hd_wallet_for(&WalletInfo::from(wallet.clone()), network)?;
+
+    let known_used: HashSet<(u32, u32)> = {
+        let s = state.read().await;
+        s.list_used_addresses(wallet.id)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .into_iter()
+            .map(|a| (a.change, a.index))
+            .collect()
+    };
+
+    let branch_addresses = match backend {
+        BitcoinBackend::Rpc(client) => {
+            scan_branches(client, &hd_wallet, &known_used, gap_limit).await?
+        }
+        BitcoinBackend::Esplora(_) => {
+            walk_branches(backend, &hd_wallet, &known_used, gap_limit).await?
+        }
+    };
+
+    let newly_used: Vec<UsedAddress> = branch_addresses
+        .iter()
+        .filter_map(|d| {
+            let (change, index) = (d.address.change?, d.address.index?);
+            (!known_used.contains(&(change, index))).then(|| UsedAddress {
+                wallet_id: wallet.id,
+                change,
+                index,
+                address: d.address.address.clone(),
+            })
+        })
+        .collect();
+    discovered.extend(branch_addresses);
+
+    if !newly_used.is_empty() {
+        info!(
+            "Discovered {} newly used addresses for wallet {}",
+            newly_used.len(),
+            wallet.id
+        );
+        let s = state.read().await;
+        for address in &newly_used {
+            s.mark_address_used(address)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        }
+    }
+
+    Ok(discovered)
+}
+
+/// Derive the address at `index` on a branch.
+fn derive_branch_address(
+    hd_wallet: &MpcHdWallet,
+    change: u32,
+    index: u32,
+) -> Result<DerivedAddress, (StatusCode, String)> {
+    if change == 0 {
+        hd_wallet.get_receiving_address(index)
+    } else {
+        hd_wallet.get_change_address(index)
+    }
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// A used branch address as a `DiscoveredAddress`.
+fn branch_address(
+    derived: DerivedAddress,
+    info: AddressInfo,
+    utxos: Option<Vec<Utxo>>,
+) -> DiscoveredAddress {
+    debug!(
+        "Discovered used address {} ({})",
+        derived.address, derived.path
+    );
+    DiscoveredAddress {
+        address: WalletAddress {
+            address: derived.address,
+            change: Some(derived.change),
+            index: Some(derived.index),
+        },
+        info,
+        utxos,
+    }
+}
+
+/// Walk each branch address by address until the gap limit is reached.
+async fn walk_branches(
+    backend: &BitcoinBackend,
+    hd_wallet: &MpcHdWallet,
+    known_used: &HashSet<(u32, u32)>,
+    gap_limit: u32,
+) -> Result<Vec<DiscoveredAddress>, (StatusCode, String)> {
+    let mut discovered = Vec::new();
+
+    for change in BRANCHES {
+        let mut scanner = GapScanner::new(gap_limit);
+
+        while let Some(index) = scanner.next_index() {
+            let derived = derive_branch_address(hd_wallet, change, index)?;
+
+            let info = backend
+                .get_address_info(&derived.address)
+                .await
+                .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+
+            // A known-used address counts as used even if the backend no
+            // longer reports it (e.g. scantxoutset after a full spend).
+            let used = known_used.contains(&(change, index)) || is_used(&info);
+            scanner.record(used);
+
+            if used {
+                discovered.push(branch_address(derived, info, None));
+            }
+        }
+    }
+
+    Ok(discovered)
+}
+
+/// Scan both branches with ranged descriptors.
+///
+/// Each round scans the not yet covered part of every branch's gap window
+/// in a single `scantxoutset`, then widens the windows past any newly
+/// found used address until no branch needs more.
+async fn scan_branches(
+    client: &BitcoinRpcClient,
+    hd_wallet: &MpcHdWallet,
+    known_used: &HashSet<(u32, u32)>,
+    gap_limit: u32,
+) -> Result<Vec<DiscoveredAddress>, (StatusCode, String)> {
+    let mut derived: BTreeMap<(u32, u32), DerivedAddress> = BTreeMap::new();
+    let mut by_script: HashMap<String, (u32, u32)> = HashMap::new();
+    let mut found: HashMap<(u32, u32), Vec<Utxo>> = HashMap::new();
+
+    // Indexed by branch: first unscanned index and end of the gap window
+    let mut scanned = [0u32; BRANCHES.len()];
+    let mut window = [gap_window_end(None, gap_limit); BRANCHES.len()];
+
+    loop {
+        let mut descriptors = Vec::new();
+        for change in BRANCHES {
+            let branch = change as usize;
+            let (start, end) = (scanned[branch], window[branch]);
+            if start >= end {
+                continue;
+            }
+
+            for index in start..end {
+                let address = derive_branch_address(hd_wallet, change, index)?;
+                let script = bitcoin::Address::from_str(&address.address)
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                    .assume_checked()
+                    .script_pubkey();
+                by_script.insert(hex::encode(script.as_bytes()), (change, index));
+                derived.insert((change, index), address);
+            }
+
+            descriptors.push(RangedDescriptor {
+                descriptor: hd_wallet
+                    .descriptor(DescriptorKind::Wpkh, change)
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+                range: Some((start, end - 1)),
+            });
+            scanned[branch] = end;
+        }
+
+        if descriptors.is_empty() {
+            break;
+        }
+
+        let unspents = client
+            .scan_descriptors(&descriptors)
+            .await
+            .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+        for unspent in unspents {
+            if let Some(key) = by_script.get(&unspent.script_pubkey) {
+                found.entry(*key).or_default().push(unspent.utxo);
+            }
+        }
+
+        for change in BRANCHES {
+            let branch = change as usize;
+            // A known-used address counts as used even once fully spent
+            let last_used = (0..scanned[branch])
+                .rev()
+                .find(|&i| known_used.contains(&(change, i)) || found.contains_key(&(change, i)));
+            window[branch] = gap_window_end(last_used, gap_limit);
+        }
+    }
+
+    Ok(derived
+        .into_iter()
+        .filter_map(|(key, address)| {
+            if !known_used.contains(&key) && !found.contains_key(&key) {
+                return None;
+            }
+            let utxos = found.remove(&key).unwrap_or_default();
+            let info = info_from_utxos(&address.address, &utxos);
+            Some(branch_address(address, info, Some(utxos)))
+        })
+        .collect())
+}
+
+/// Descriptors covering the discovered addresses of a wallet, for backends
+/// that track history in a watch-only wallet. Branch descriptors range up
+/// to the last used address of the branch.
+pub fn watch_descriptors(
+    wallet: &StoredWallet,
+    discovered: &[DiscoveredAddress],
+) -> Result<Vec<RangedDescriptor>, (StatusCode, String)> {
+    let mut descriptors = vec![RangedDescriptor::address(&wallet.address)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?];
+
+    if wallet.wallet_type != WalletType::Bitcoin {
+        return Ok(descriptors);
+    }
+
+    let hd_wallet = hd_wallet_for(&WalletInfo::from(wallet.clone()), get_bitcoin_network()?)?;
+    for change in BRANCHES {
+        let last_used = discovered
+            .iter()
+            .filter(|d| d.address.change == Some(change))
+            .filter_map(|d| d.address.index)
+            .max();
+
+        if let Some(last_used) = last_used {
+            descriptors.push(RangedDescriptor {
+                descriptor: hd_wallet
+                    .descriptor(DescriptorKind::Wpkh, change)
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+                range: Some((0, last_used)),
+            });
+        }
+    }
+
+    Ok(descriptors)
+}
+
+/// Gap limit query parameter shared by the balance, UTXO and history endpoints.
+#[derive(Debug, serde::Deserialize)]
+pub struct GapLimitQuery {
+    #[serde(default = "default_gap_limit")]
+    pub gap_limit: u32,
+}
+
+fn default_gap_limit() -> u32 {
+    DEFAULT_GAP_LIMIT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(gap_limit: u32, used: &[u32]) -> Vec<u32> {
+        let mut scanner = GapScanner::new(gap_limit);
+        let mut checked = Vec::new();
+        while let Some(index) = scanner.next_index() {
+            checked.push(index);
+            scanner.record(used.contains(&index));
+        }
+        checked
+    }
+
+    #[test]
+    fn test_gap_scanner_empty_branch() {
+        assert_eq!(scan(3, &[]), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_gap_scanner_extends_past_used() {
+        assert_eq!(scan(3, &[0, 1, 3]), vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_gap_scanner_stops_at_gap() {
+        // Index 5 lies beyond a gap of three unused addresses
+        assert_eq!(scan(3, &[0, 1, 5]), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_gap_window_matches_scanner() {
+        let cases: [&[u32]; 4] = [&[], &[0, 1, 3], &[0, 1, 5], &[2]];
+        for used in cases {
+            let checked = scan(3, used);
+            let last_used = checked.iter().rev().find(|i| used.contains(i)).copied();
+            assert_eq!(gap_window_end(last_used, 3), checked.len() as u32);
+        }
+    }
+
+    #[test]
+    fn test_aggregate_info() {
+        let info = |funded, spent, mempool, txs| AddressInfo {
+            address: String::new(),
+            chain_stats: ChainStats {
+                funded_txo_sum: funded,
+                spent_txo_sum: spent,
+                tx_count: txs,
+                ..Default::default()
+            },
+            mempool_stats: MempoolStats {
+                funded_txo_sum: mempool,
+                ..Default::default()
+            },
+        };
+
+        let infos = [info(100_000, 40_000, 0, 2), info(25_000, 0, 5_000, 1)];
+        let total = aggregate_info("tb1qroot".to_string(), &infos);
+
+        assert_eq!(total.address, "tb1qroot");
+        assert_eq!(total.confirmed_balance(), 85_000);
+        assert_eq!(total.unconfirmed_balance(), 5_000);
+        assert_eq!(total.chain_stats.tx_count, 3);
+        assert!(is_used(&total));
+        assert!(!is_used(&info(0, 0, 0, 0)));
+    }
+}
@@ -5,8 +5,11 @@
 //! - "testnet" (default) -> Uses Blockstream Esplora API
 //! - "mainnet" -> Uses Blockstream Esplora API
 
+use std::collections::HashSet;
+
 use chains::bitcoin::{
-    AddressInfo, BitcoinNetwork, BitcoinRpcClient, BlockchainClient, FeeEstimates, RpcConfig, Utxo,
+    AddressInfo, AddressTx, BitcoinNetwork, BitcoinRpcClient, BlockchainClient, FeeEstimates,
+    RangedDescriptor, RpcConfig, Utxo,
 };
 use common::MpcWalletError;
 use tracing::info;
@@ -54,6 +57,43 @@ impl BitcoinBackend {
         }
     }
 
+    /// Get the transaction history of a set of wallet addresses.
+    ///
+    /// Esplora is queried address by address. Bitcoin Core only tracks
+    /// wallet transactions, so `descriptors` covering the addresses are
+    /// imported into its watch-only wallet first, which makes its history
+    /// include spends as well as receipts.
+    pub async fn get_wallet_txs(
+        &self,
+        addresses: &HashSet<String>,
+        descriptors: &[RangedDescriptor],
+    ) -> Result<Vec<AddressTx>, MpcWalletError> {
+        let txs = match self {
+            BitcoinBackend::Esplora(client) => {
+                let mut txs = Vec::new();
+                let mut seen = HashSet::new();
+                for address in addresses {
+                    for tx in client.get_address_txs(address).await? {
+                        if seen.insert(tx.txid.clone()) {
+                            txs.push(tx);
+                        }
+                    }
+                }
+                txs
+            }
+            BitcoinBackend::Rpc(client) => {
+                client.import_descriptors(descriptors).await?;
+                client.get_wallet_txs().await?
+            }
+        };
+
+        // The Core wallet also holds the transactions of other wallets
+        Ok(txs
+            .into_iter()
+            .filter(|tx| !tx.touched_addresses(addresses).is_empty())
+            .collect())
+    }
+
     /// Broadcast a signed transaction.
     pub async fn broadcast_tx(&self, tx_hex: &str) -> Result<String, MpcWalletError> {
         match self {
@@ -1,35 +1,62 @@
 //! Bitcoin-specific handlers for balance checking and sending.
 
+use std::collections::HashSet;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
 use bitcoin::secp256k1::PublicKey;
+use serde::Serialize;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info};
 use uuid::Uuid;
 
 use chains::bitcoin::{BalanceResponse, BitcoinNetwork, Utxo};
-use common::{SendBitcoinRequest, SendBitcoinResponse, SigningGrant, WalletType, THRESHOLD};
+use common::{
+    SendBitcoinRequest, SendBitcoinResponse, SigningGrant, StoredWallet, WalletType, THRESHOLD,
+};
 
+use crate::address_discovery::{
+    address_utxos, aggregate_info, discover_wallet_addresses, watch_descriptors, DiscoveredAddress,
+    GapLimitQuery, WalletAddress, DEFAULT_GAP_LIMIT,
+};
 use crate::bitcoin_client::BitcoinBackend;
-use crate::handlers::reject_mainnet;
-use crate::state::AppState;
+use crate::handlers::{get_bitcoin_network, hd_wallet_for, reject_mainnet};
+use crate::state::{AppState, WalletInfo};
+
+/// Balance of a single wallet address.
+#[derive(Debug, Serialize)]
+pub struct AddressBalance {
+    #[serde(flatten)]
+    pub address: WalletAddress,
+    pub confirmed_sats: u64,
+    pub unconfirmed_sats: i64,
+    pub total_sats: u64,
+    pub tx_count: u64,
+}
 
-/// Get balance for a wallet.
+/// Wallet balance aggregated across all discovered addresses.
 ///
-/// GET /wallet/:wallet_id/balance
-pub async fn get_balance(
-    State(state): State<Arc<RwLock<AppState>>>,
-    Path(wallet_id): Path<Uuid>,
-) -> Result<Json<BalanceResponse>, (StatusCode, String)> {
-    info!("Get balance for wallet: {}", wallet_id);
+/// The top-level fields keep the single-address `BalanceResponse` shape
+/// (with `address` set to the DKG root address) so existing clients work.
+#[derive(Debug, Serialize)]
+pub struct WalletBalanceResponse {
+    #[serde(flatten)]
+    pub total: BalanceResponse,
+    pub addresses: Vec<AddressBalance>,
+}
 
-    // Get wallet
+/// Load a Bitcoin or Taproot wallet and discover its used addresses.
+async fn discover_for_request(
+    state: &Arc<RwLock<AppState>>,
+    wallet_id: Uuid,
+    gap_limit: u32,
+    operation: &str,
+) -> Result<(StoredWallet, BitcoinBackend, Vec<DiscoveredAddress>), (StatusCode, String)> {
     let wallet = {
         let s = state.read().await;
         s.get_stored_wallet(wallet_id)
@@ -41,29 +68,150 @@ pub async fn get_balance(
     if wallet.wallet_type != WalletType::Bitcoin && wallet.wallet_type != WalletType::Taproot {
         return Err((
             StatusCode::BAD_REQUEST,
-            "Balance only available for Bitcoin/Taproot wallets".to_string(),
+            format!("{} only available for Bitcoin/Taproot wallets", operation),
         ));
     }
 
-    // Query blockchain
     let backend = BitcoinBackend::from_env().map_err(|e| {
         error!("Failed to create Bitcoin backend: {}", e);
         (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
     })?;
 
-    let info = backend
-        .get_address_info(&wallet.address)
+    let addresses = discover_wallet_addresses(state, &wallet, &backend, gap_limit)
         .await
         .map_err(|e| {
-            error!("Failed to get address info: {}", e);
-            (StatusCode::BAD_GATEWAY, e.to_string())
+            error!("Address discovery failed: {}", e.1);
+            e
         })?;
 
-    let response = BalanceResponse::from_address_info(wallet.address.clone(), &info);
+    Ok((wallet, backend, addresses))
+}
+
+/// A UTXO together with the key that spends it.
+#[derive(Debug, Clone)]
+struct SpendableUtxo {
+    utxo: Utxo,
+    /// Path of the spending key below the wallet root; empty for the DKG
+    /// root address.
+    derivation_path: Vec<u32>,
+    /// Public key of the spending key (compressed for SegWit wallets).
+    public_key: Vec<u8>,
+    /// Script of the address holding the UTXO.
+    script_pubkey: bitcoin::ScriptBuf,
+}
+
+/// Collect the UTXOs of every discovered wallet address, each with the
+/// derivation path the nodes tweak their key shares by to sign for it.
+async fn spendable_utxos(
+    state: &Arc<RwLock<AppState>>,
+    wallet: &StoredWallet,
+    backend: &BitcoinBackend,
+) -> Result<Vec<SpendableUtxo>, (StatusCode, String)> {
+    let discovered = discover_wallet_addresses(state, wallet, backend, DEFAULT_GAP_LIMIT)
+        .await
+        .map_err(|e| {
+            error!("Address discovery failed: {}", e.1);
+            e
+        })?;
+
+    let root_key = hex::decode(&wallet.public_key).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Invalid public key: {}", e),
+        )
+    })?;
+    let hd_wallet = match wallet.wallet_type {
+        WalletType::Bitcoin => Some(hd_wallet_for(
+            &WalletInfo::from(wallet.clone()),
+            get_bitcoin_network()?,
+        )?),
+        _ => None,
+    };
+
+    let mut spendable = Vec::new();
+    for d in discovered {
+        // Skip lookups for addresses whose outputs are all spent
+        if d.info.total_balance() == 0 {
+            continue;
+        }
+
+        let (derivation_path, public_key) = match (d.address.change, d.address.index) {
+            (Some(change), Some(index)) => {
+                let hd_wallet = hd_wallet.as_ref().ok_or_else(|| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Derived address on a wallet without HD branches".to_string(),
+                    )
+                })?;
+                let path = vec![change, index];
+                let key = hd_wallet
+                    .account_key()
+                    .derive_path(&path)
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+                (path, key.public_key.to_vec())
+            }
+            _ => (Vec::new(), root_key.clone()),
+        };
+
+        let script_pubkey = bitcoin::Address::from_str(&d.address.address)
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Invalid address: {}", e),
+                )
+            })?
+            .assume_checked()
+            .script_pubkey();
+
+        let utxos = address_utxos(backend, &d).await.map_err(|e| {
+            error!("Failed to get UTXOs: {}", e.1);
+            e
+        })?;
+        spendable.extend(utxos.into_iter().map(|utxo| SpendableUtxo {
+            utxo,
+            derivation_path: derivation_path.clone(),
+            public_key: public_key.clone(),
+            script_pubkey: script_pubkey.clone(),
+        }));
+    }
+
+    Ok(spendable)
+}
+
+/// Get balance for a wallet, summed over all discovered addresses.
+///
+/// GET /wallet/:wallet_id/balance?gap_limit=20
+pub async fn get_balance(
+    State(state): State<Arc<RwLock<AppState>>>,
+    Path(wallet_id): Path<Uuid>,
+    Query(query): Query<GapLimitQuery>,
+) -> Result<Json<WalletBalanceResponse>, (StatusCode, String)> {
+    info!("Get balance for wallet: {}", wallet_id);
+
+    let (wallet, _backend, discovered) =
+        discover_for_request(&state, wallet_id, query.gap_limit, "Balance").await?;
+
+    let total = aggregate_info(wallet.address.clone(), discovered.iter().map(|d| &d.info));
+    let response = WalletBalanceResponse {
+        total: BalanceResponse::from_address_info(wallet.address, &total),
+        addresses: discovered
+            .into_iter()
+            .map(|d| AddressBalance {
+                confirmed_sats: d.info.confirmed_balance(),
+                unconfirmed_sats: d.info.unconfirmed_balance(),
+                total_sats: d.info.total_balance(),
+                tx_count: d.info.chain_stats.tx_count + d.info.mempool_stats.tx_count,
+                address: d.address,
+            })
+            .collect(),
+    };
 
     info!(
-        "Balance for {}: {} sats (confirmed: {})",
-        wallet.address, response.total_sats, response.confirmed_sats
+        "Balance for wallet {}: {} sats (confirmed: {}) across {} addresses",
+        wallet_id,
+        response.total.total_sats,
+        response.total.confirmed_sats,
+        response.addresses.len()
     );
 
     Ok(Json(response))
@@ -171,10 +319,7 @@ pub async fn send_bitcoin(
         (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
     })?;
 
-    let utxos = backend.get_utxos(&wallet.address).await.map_err(|e| {
-        error!("Failed to get UTXOs: {}", e);
-        (StatusCode::BAD_GATEWAY, e.to_string())
-    })?;
+    let utxos = spendable_utxos(&state, &wallet, &backend).await?;
 
     if utxos.is_empty() {
         return Err((
@@ -183,7 +328,7 @@ pub async fn send_bitcoin(
         ));
     }
 
-    let total_balance: u64 = utxos.iter().map(|u| u.value).sum();
+    let total_balance: u64 = utxos.iter().map(|u| u.utxo.value).sum();
     info!(
         "Available balance: {} sats from {} UTXOs",
         total_balance,
@@ -198,20 +343,12 @@ pub async fn send_bitcoin(
     info!("Using fee rate: {} sat/vB", fee_rate);
 
     // Build the unsigned transaction
-    let public_key_bytes = hex::decode(&wallet.public_key).map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Invalid public key: {}", e),
-        )
-    })?;
-
-    let (unsigned_tx, sighashes, fee_sats) = build_unsigned_transaction(
+    let (unsigned_tx, inputs, sighashes, fee_sats) = build_unsigned_transaction(
         &utxos,
         &request.to_address,
         request.amount_sats,
         fee_rate,
         &wallet.address,
-        &public_key_bytes,
     )
     .map_err(|e| {
         error!("Failed to build transaction: {}", e);
@@ -230,10 +367,11 @@ pub async fn send_bitcoin(
 
     let mut signatures: Vec<Vec<u8>> = Vec::new();
 
-    for (input_idx, sighash) in sighashes.iter().enumerate() {
+    for (input_idx, (input, sighash)) in inputs.iter().zip(&sighashes).enumerate() {
         info!(
-            "Signing input {} with sighash: {}",
+            "Signing input {} at path {:?} with sighash: {}",
             input_idx,
+            input.derivation_path,
             hex::encode(sighash)
         );
 
@@ -243,6 +381,7 @@ pub async fn send_bitcoin(
             wallet_id,
             sighash,
             &participant_indices,
+            &input.derivation_path,
             &state,
         )
         .await
@@ -257,11 +396,10 @@ pub async fn send_bitcoin(
     info!("All {} inputs signed successfully", signatures.len());
 
     // Create the signed transaction
-    let signed_tx_hex =
-        finalize_transaction(unsigned_tx, &signatures, &public_key_bytes).map_err(|e| {
-            error!("Failed to finalize transaction: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, e)
-        })?;
+    let signed_tx_hex = finalize_transaction(unsigned_tx, &signatures, &inputs).map_err(|e| {
+        error!("Failed to finalize transaction: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, e)
+    })?;
 
     info!("Transaction finalized, broadcasting...");
 
@@ -296,6 +434,7 @@ async fn run_threshold_signing(
     wallet_id: Uuid,
     message_hash: &[u8; 32],
     participants: &[u16],
+    derivation_path: &[u32],
     state: &Arc<RwLock<AppState>>,
 ) -> Result<Vec<u8>, String> {
     use crate::relay_handlers::RelaySession;
@@ -310,9 +449,10 @@ async fn run_threshold_signing(
     // Create signing grant
     let grant = {
         let s = state.read().await;
-        SigningGrant::new(
+        SigningGrant::for_derived_key(
             wallet_id.to_string(),
             *message_hash,
+            derivation_path.to_vec(),
             THRESHOLD,
             participants.to_vec(),
             s.grant_signing_key(),
@@ -356,6 +496,7 @@ async fn run_threshold_signing(
         let message_hash_hex = message_hash_hex.clone();
         let wallet_id_str = wallet_id_str.clone();
         let parties = participants.to_vec();
+        let derivation_path = derivation_path.to_vec();
         let grant_clone = grant.clone();
 
         let handle = tokio::spawn(async move {
@@ -370,6 +511,7 @@ async fn run_threshold_signing(
                     "wallet_id": wallet_id_str,
                     "message_hash": message_hash_hex,
                     "parties": parties,
+                    "derivation_path": derivation_path,
                     "grant": grant_clone
                 }))
                 .timeout(std::time::Duration::from_secs(120)) // 2 minute timeout
@@ -481,15 +623,15 @@ fn create_dummy_p2wpkh_witness() -> bitcoin::Witness {
     bitcoin::Witness::from_slice(&[dummy_sig, dummy_pubkey])
 }
 
-/// Build unsigned transaction and return sighashes for each input.
+/// Build unsigned transaction and return the selected inputs with their
+/// sighashes.
 fn build_unsigned_transaction(
-    utxos: &[Utxo],
+    utxos: &[SpendableUtxo],
     to_address: &str,
     amount_sats: u64,
     fee_rate: u64,
     change_address: &str,
-    pubkey: &[u8],
-) -> Result<(bitcoin::Transaction, Vec<[u8; 32]>, u64), String> {
+) -> Result<(bitcoin::Transaction, Vec<SpendableUtxo>, Vec<[u8; 32]>, u64), String> {
     use bitcoin::hashes::Hash;
     use bitcoin::sighash::{EcdsaSighashType, SighashCache};
     use bitcoin::transaction::Version;
@@ -499,14 +641,9 @@ fn build_unsigned_transaction(
     };
     use std::str::FromStr;
 
-    // Parse public key for script generation
-    let pk = PublicKey::from_slice(pubkey).map_err(|e| format!("Invalid public key: {}", e))?;
-    let compressed_pk = CompressedPublicKey(pk);
-    let wpkh = compressed_pk.wpubkey_hash();
-
     // Sort UTXOs by value (largest first)
     let mut sorted_utxos = utxos.to_vec();
-    sorted_utxos.sort_by(|a, b| b.value.cmp(&a.value));
+    sorted_utxos.sort_by(|a, b| b.utxo.value.cmp(&a.utxo.value));
 
     // Parse addresses upfront
     let dest_address = Address::from_str(to_address)
@@ -519,9 +656,9 @@ fn build_unsigned_transaction(
 
     // Helper to build a transaction with given UTXOs and calculate its vsize
     let build_tx_with_dummy_witness =
-        |selected: &[Utxo], amt: u64, change: u64| -> Result<(Transaction, u64), String> {
+        |selected: &[SpendableUtxo], amt: u64, change: u64| -> Result<(Transaction, u64), String> {
             let mut inputs = Vec::new();
-            for utxo in selected {
+            for SpendableUtxo { utxo, .. } in selected {
                 let txid =
                     Txid::from_str(&utxo.txid).map_err(|e| format!("Invalid txid: {}", e))?;
                 inputs.push(TxIn {
@@ -559,7 +696,7 @@ fn build_unsigned_transaction(
         };
 
     // Select UTXOs using rough estimate first
-    let mut selected_utxos: Vec<Utxo> = Vec::new();
+    let mut selected_utxos: Vec<SpendableUtxo> = Vec::new();
     let mut total_input: u64 = 0;
 
     // Initial rough estimate: 70 vbytes per input, 35 per output, 11 base
@@ -568,7 +705,7 @@ fn build_unsigned_transaction(
 
     for utxo in &sorted_utxos {
         selected_utxos.push(utxo.clone());
-        total_input += utxo.value;
+        total_input += utxo.utxo.value;
 
         let rough_vsize = rough_vsize_base + (selected_utxos.len() as u64 * rough_vsize_per_input);
         let rough_fee = fee_rate * rough_vsize;
@@ -601,7 +738,7 @@ fn build_unsigned_transaction(
 
     // Build final transaction (without witness - will be added after signing)
     let mut tx_inputs = Vec::new();
-    for utxo in &selected_utxos {
+    for SpendableUtxo { utxo, .. } in &selected_utxos {
         let txid = Txid::from_str(&utxo.txid).map_err(|e| format!("Invalid txid: {}", e))?;
 
         tx_inputs.push(TxIn {
@@ -637,10 +774,13 @@ fn build_unsigned_transaction(
     // Compute sighashes for each input
     let mut sighash_cache = SighashCache::new(&tx);
     let mut sighashes: Vec<[u8; 32]> = Vec::new();
-    let script_code = ScriptBuf::new_p2wpkh(&wpkh);
 
-    for (i, utxo) in selected_utxos.iter().enumerate() {
-        let value = Amount::from_sat(utxo.value);
+    for (i, input) in selected_utxos.iter().enumerate() {
+        // Each input is spent by the key of the address holding it
+        let pk = PublicKey::from_slice(&input.public_key)
+            .map_err(|e| format!("Invalid public key: {}", e))?;
+        let script_code = ScriptBuf::new_p2wpkh(&CompressedPublicKey(pk).wpubkey_hash());
+        let value = Amount::from_sat(input.utxo.value);
 
         let sighash = sighash_cache
             .p2wpkh_signature_hash(i, &script_code, value, EcdsaSighashType::All)
@@ -649,20 +789,20 @@ fn build_unsigned_transaction(
         sighashes.push(sighash.to_byte_array());
     }
 
-    Ok((tx, sighashes, fee_sats))
+    Ok((tx, selected_utxos, sighashes, fee_sats))
 }
 
 /// Finalize transaction by adding witness data with signatures.
 fn finalize_transaction(
     mut tx: bitcoin::Transaction,
     signatures: &[Vec<u8>],
-    pubkey: &[u8],
+    inputs: &[SpendableUtxo],
 ) -> Result<String, String> {
     use bitcoin::consensus::encode::serialize_hex;
     use bitcoin::sighash::EcdsaSighashType;
     use bitcoin::Witness;
 
-    if signatures.len() != tx.input.len() {
+    if signatures.len() != tx.input.len() || inputs.len() != tx.input.len() {
         return Err(format!(
             "Signature count mismatch: {} signatures for {} inputs",
             signatures.len(),
@@ -670,54 +810,55 @@ fn finalize_transaction(
         ));
     }
 
-    let pubkey_vec = pubkey.to_vec();
-
-    for (i, sig_der) in signatures.iter().enumerate() {
+    for (i, (sig_der, input)) in signatures.iter().zip(inputs).enumerate() {
         // Append sighash type to signature
         let mut sig_with_type = sig_der.clone();
         sig_with_type.push(EcdsaSighashType::All.to_u32() as u8);
 
         // Create witness: [signature, pubkey]
-        let witness = Witness::from_slice(&[&sig_with_type, &pubkey_vec]);
+        let witness = Witness::from_slice(&[&sig_with_type, &input.public_key]);
         tx.input[i].witness = witness;
     }
 
     Ok(serialize_hex(&tx))
 }
 
-/// Get UTXOs for a wallet.
+/// A UTXO together with the wallet address holding it.
+#[derive(Debug, Serialize)]
+pub struct WalletUtxo {
+    #[serde(flatten)]
+    pub utxo: Utxo,
+    #[serde(flatten)]
+    pub address: WalletAddress,
+}
+
+/// Get UTXOs for a wallet across all discovered addresses.
 ///
-/// GET /wallet/:wallet_id/utxos
+/// GET /wallet/:wallet_id/utxos?gap_limit=20
 pub async fn get_utxos(
     State(state): State<Arc<RwLock<AppState>>>,
     Path(wallet_id): Path<Uuid>,
+    Query(query): Query<GapLimitQuery>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     info!("Get UTXOs for wallet: {}", wallet_id);
 
-    let wallet = {
-        let s = state.read().await;
-        s.get_stored_wallet(wallet_id)
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-            .ok_or_else(|| (StatusCode::NOT_FOUND, "Wallet not found".to_string()))?
-    };
+    let (wallet, backend, discovered) =
+        discover_for_request(&state, wallet_id, query.gap_limit, "UTXOs").await?;
 
-    // Allow both Bitcoin (SegWit) and Taproot wallet types
-    if wallet.wallet_type != WalletType::Bitcoin && wallet.wallet_type != WalletType::Taproot {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            "UTXOs only available for Bitcoin/Taproot wallets".to_string(),
-        ));
+    let mut utxos = Vec::new();
+    for d in discovered {
+        // Skip lookups for addresses whose outputs are all spent
+        if d.info.total_balance() == 0 {
+            continue;
+        }
+        let found = address_utxos(&backend, &d).await?;
+        utxos.extend(found.into_iter().map(|utxo| WalletUtxo {
+            utxo,
+            address: d.address.clone(),
+        }));
     }
 
-    let backend = BitcoinBackend::from_env()
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-    let utxos = backend
-        .get_utxos(&wallet.address)
-        .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
-
-    let total: u64 = utxos.iter().map(|u| u.value).sum();
+    let total: u64 = utxos.iter().map(|u| u.utxo.value).sum();
 
     Ok(Json(serde_json::json!({
         "address": wallet.address,
@@ -728,6 +869,78 @@ pub async fn get_utxos(
     })))
 }
 
+/// A wallet transaction with its net effect on the wallet balance.
+#[derive(Debug, Serialize)]
+pub struct WalletTransaction {
+    pub txid: String,
+    pub confirmed: bool,
+    pub block_height: Option<u64>,
+    pub block_time: Option<u64>,
+    /// Received minus spent by wallet addresses, in satoshis.
+    pub net_sats: i64,
+    pub fee_sats: u64,
+    /// Wallet addresses involved in the transaction.
+    pub addresses: Vec<String>,
+}
+
+/// Get transaction history for a wallet across all discovered addresses.
+///
+/// Transactions between two wallet addresses appear once, with a net value
+/// of minus the fee. Unconfirmed transactions are listed first, then
+/// confirmed ones from newest to oldest.
+///
+/// GET /wallet/:wallet_id/history?gap_limit=20
+pub async fn get_history(
+    State(state): State<Arc<RwLock<AppState>>>,
+    Path(wallet_id): Path<Uuid>,
+    Query(query): Query<GapLimitQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    info!("Get history for wallet: {}", wallet_id);
+
+    let (wallet, backend, discovered) =
+        discover_for_request(&state, wallet_id, query.gap_limit, "History").await?;
+
+    let wallet_addresses: HashSet<String> = discovered
+        .iter()
+        .map(|d| d.address.address.clone())
+        .collect();
+    let descriptors = watch_descriptors(&wallet, &discovered)?;
+
+    let txs = backend
+        .get_wallet_txs(&wallet_addresses, &descriptors)
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+
+    let mut history: Vec<WalletTransaction> = txs
+        .into_iter()
+        .map(|tx| WalletTransaction {
+            net_sats: tx.net_value(&wallet_addresses),
+            addresses: tx.touched_addresses(&wallet_addresses),
+            txid: tx.txid,
+            confirmed: tx.status.confirmed,
+            block_height: tx.status.block_height,
+            block_time: tx.status.block_time,
+            fee_sats: tx.fee,
+        })
+        .collect();
+    history.sort_by_key(|tx| std::cmp::Reverse(tx.block_height.unwrap_or(u64::MAX)));
+
+    debug!(
+        "History for wallet {}: {} transactions over {} addresses",
+        wallet_id,
+        history.len(),
+        discovered.len()
+    );
+
+    Ok(Json(serde_json::json!({
+        "wallet_id": wallet_id,
+        "address": wallet.address,
+        "address_count": discovered.len(),
+        "transactions": history,
+        "count": history.len(),
+    })))
+}
+
 // ============================================================================
 // Taproot (FROST/Schnorr) Send Handler
 // ============================================================================
@@ -772,10 +985,7 @@ pub async fn send_taproot(
         (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
     })?;
 
-    let utxos = backend.get_utxos(&wallet.address).await.map_err(|e| {
-        error!("Failed to get UTXOs: {}", e);
-        (StatusCode::BAD_GATEWAY, e.to_string())
-    })?;
+    let utxos = spendable_utxos(&state, &wallet, &backend).await?;
 
     if utxos.is_empty() {
         return Err((
@@ -784,7 +994,7 @@ pub async fn send_taproot(
         ));
     }
 
-    let total_balance: u64 = utxos.iter().map(|u| u.value).sum();
+    let total_balance: u64 = utxos.iter().map(|u| u.utxo.value).sum();
     info!(
         "Available balance: {} sats from {} UTXOs",
         total_balance,
@@ -798,24 +1008,12 @@ pub async fn send_taproot(
         .unwrap_or_else(|| fee_estimates.recommended().max(1));
     info!("Using fee rate: {} sat/vB", fee_rate);
 
-    // Get the script pubkey for the Taproot address
-    let address = bitcoin::Address::from_str(&wallet.address)
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Invalid address: {}", e),
-            )
-        })?
-        .assume_checked();
-    let script_pubkey = address.script_pubkey();
-
-    let (unsigned_tx, sighashes, fee_sats) = build_unsigned_taproot_tx(
+    let (unsigned_tx, inputs, sighashes, fee_sats) = build_unsigned_taproot_tx(
         &utxos,
         &request.to_address,
         request.amount_sats,
         fee_rate,
         &wallet.address,
-        script_pubkey.as_bytes(),
     )
     .map_err(|e| {
         error!("Failed to build Taproot transaction: {}", e);
@@ -834,10 +1032,11 @@ pub async fn send_taproot(
 
     let mut signatures: Vec<Vec<u8>> = Vec::new();
 
-    for (input_idx, sighash) in sighashes.iter().enumerate() {
+    for (input_idx, (input, sighash)) in inputs.iter().zip(&sighashes).enumerate() {
         info!(
-            "FROST signing input {} with sighash: {}",
+            "FROST signing input {} at path {:?} with sighash: {}",
             input_idx,
+            input.derivation_path,
             hex::encode(sighash)
         );
 
@@ -847,6 +1046,7 @@ pub async fn send_taproot(
             wallet_id,
             sighash,
             &participant_indices,
+            &input.derivation_path,
             &state,
         )
         .await
@@ -891,15 +1091,15 @@ pub async fn send_taproot(
     }))
 }
 
-/// Build an unsigned Taproot transaction with BIP-341 sighashes.
+/// Build an unsigned Taproot transaction and return the selected inputs with
+/// their BIP-341 sighashes.
 fn build_unsigned_taproot_tx(
-    utxos: &[Utxo],
+    utxos: &[SpendableUtxo],
     to_address: &str,
     amount_sats: u64,
     fee_rate: u64,
     change_address: &str,
-    script_pubkey: &[u8],
-) -> Result<(bitcoin::Transaction, Vec<[u8; 32]>, u64), String> {
+) -> Result<(bitcoin::Transaction, Vec<SpendableUtxo>, Vec<[u8; 32]>, u64), String> {
     use bitcoin::hashes::Hash;
     use bitcoin::sighash::{Prevouts, SighashCache, TapSighashType};
     use bitcoin::transaction::Version;
@@ -908,11 +1108,9 @@ fn build_unsigned_taproot_tx(
     };
     use std::str::FromStr;
 
-    let script_pubkey_buf = ScriptBuf::from_bytes(script_pubkey.to_vec());
-
     // Sort UTXOs by value (largest first)
     let mut sorted_utxos = utxos.to_vec();
-    sorted_utxos.sort_by(|a, b| b.value.cmp(&a.value));
+    sorted_utxos.sort_by(|a, b| b.utxo.value.cmp(&a.utxo.value));
 
     // Estimate sizes for Taproot
     let base_size: u64 = 10;
@@ -920,7 +1118,7 @@ fn build_unsigned_taproot_tx(
     let output_size: u64 = 43; // P2TR output
 
     // Select UTXOs
-    let mut selected_utxos: Vec<Utxo> = Vec::new();
+    let mut selected_utxos: Vec<SpendableUtxo> = Vec::new();
     let mut total_input: u64 = 0;
     let min_fee = fee_rate * (base_size + input_size + 2 * output_size);
     let mut target = amount_sats + min_fee;
@@ -930,7 +1128,7 @@ fn build_unsigned_taproot_tx(
             break;
         }
         selected_utxos.push(utxo.clone());
-        total_input += utxo.value;
+        total_input += utxo.utxo.value;
 
         let estimated_size =
             base_size + (selected_utxos.len() as u64 * input_size) + (2 * output_size);
@@ -962,7 +1160,12 @@ fn build_unsigned_taproot_tx(
     let mut inputs = Vec::new();
     let mut prevouts = Vec::new();
 
-    for utxo in &selected_utxos {
+    for SpendableUtxo {
+        utxo,
+        script_pubkey,
+        ..
+    } in &selected_utxos
+    {
         let txid = Txid::from_str(&utxo.txid).map_err(|e| format!("Invalid txid: {}", e))?;
 
         inputs.push(TxIn {
@@ -977,7 +1180,7 @@ fn build_unsigned_taproot_tx(
 
         prevouts.push(TxOut {
             value: Amount::from_sat(utxo.value),
-            script_pubkey: script_pubkey_buf.clone(),
+            script_pubkey: script_pubkey.clone(),
         });
     }
 
@@ -1015,7 +1218,7 @@ fn build_unsigned_taproot_tx(
         sighashes.push(sighash.to_byte_array());
     }
 
-    Ok((tx, sighashes, fee_sats))
+    Ok((tx, selected_utxos, sighashes, fee_sats))
 }
 
 /// Finalize a Taproot transaction with Schnorr signatures.
@@ -1061,14 +1264,16 @@ async fn run_frost_signing(
     wallet_id: Uuid,
     message_hash: &[u8; 32],
     participant_indices: &[u16],
+    derivation_path: &[u32],
     state: &Arc<RwLock<AppState>>,
 ) -> Result<Vec<u8>, String> {
     // Create signing grant
     let grant = {
         let s = state.read().await;
-        SigningGrant::new(
+        SigningGrant::for_derived_key(
             wallet_id.to_string(),
             *message_hash,
+            derivation_path.to_vec(),
             THRESHOLD,
             participant_indices.to_vec(),
             s.grant_signing_key(),
@@ -1111,6 +1316,7 @@ async fn run_frost_signing(
         let wallet_id = wallet_id.to_string();
         let message_hash_hex = hex::encode(message_hash);
         let parties = participant_indices.to_vec();
+        let derivation_path = derivation_path.to_vec();
         let grant_clone = grant.clone();
 
         let handle = tokio::spawn(async move {
@@ -1121,6 +1327,7 @@ async fn run_frost_signing(
                     "wallet_id": wallet_id,
                     "message_hash": message_hash_hex,
                     "parties": parties,
+                    "derivation_path": derivation_path,
                     "grant": grant_clone
                 }))
                 .timeout(std::time::Duration::from_secs(60))
@@ -17,14 +17,14 @@ use tracing::{debug, error, info, trace};
 use uuid::Uuid;
 
 use common::{
-    CreateWalletRequest, CreateWalletResponse, DerivedAddress, MpcHdWallet, WalletType,
-    NUM_PARTIES, THRESHOLD,
+    output_descriptor, CreateWalletRequest, CreateWalletResponse, DerivedAddress, DescriptorKind,
+    ExtendedPubKey, MpcHdWallet, WalletType, NUM_PARTIES, THRESHOLD,
 };
 
-use crate::state::AppState;
+use crate::state::{AppState, WalletInfo};
 
 /// Parse BITCOIN_NETWORK environment variable into a bitcoin::Network.
-fn get_bitcoin_network() -> Result<Network, (StatusCode, String)> {
+pub fn get_bitcoin_network() -> Result<Network, (StatusCode, String)> {
     let network_str = std::env::var("BITCOIN_NETWORK").unwrap_or_else(|_| "testnet".to_string());
     match network_str.as_str() {
         "mainnet" => Ok(Network::Bitcoin),
@@ -217,6 +217,9 @@ pub struct KeyShareInfo {
     pub public_key: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub address: Option<String>,
+    /// BIP32 root extended public key reported by the nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xpub: Option<String>,
     pub threshold: u16,
     pub num_parties: u16,
     pub nodes_with_shares: Vec<usize>,
@@ -286,6 +289,7 @@ pub async fn cggmp24_keyshares(State(state): State<Arc<RwLock<AppState>>>) -> im
                             wallet_id: wallet_id.to_string(),
                             public_key: ks["public_key"].as_str().unwrap_or("").to_string(),
                             address: None, // CGGMP24 doesn't include address in node response
+                            xpub: ks["xpub"].as_str().map(|s| s.to_string()),
                             threshold: ks["threshold"].as_u64().unwrap_or(0) as u16,
                             num_parties: ks["num_parties"].as_u64().unwrap_or(0) as u16,
                             nodes_with_shares: Vec::new(),
@@ -359,6 +363,7 @@ pub async fn frost_keyshares(State(state): State<Arc<RwLock<AppState>>>) -> impl
                             wallet_id: wallet_id.to_string(),
                             public_key: ks["public_key"].as_str().unwrap_or("").to_string(),
                             address: ks["address"].as_str().map(|s| s.to_string()),
+                            xpub: ks["xpub"].as_str().map(|s| s.to_string()),
                             threshold: ks["threshold"].as_u64().unwrap_or(0) as u16,
                             num_parties: ks["num_parties"].as_u64().unwrap_or(0) as u16,
                             nodes_with_shares: Vec::new(),
@@ -475,6 +480,7 @@ pub async fn create_wallet(
             request.wallet_type,
             public_key_hex.clone(),
             address.clone(),
+            None,
         )
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     }
@@ -568,19 +574,13 @@ pub async fn derive_addresses(
         ));
     }
 
-    // Parse the public key
-    let public_key_bytes = hex::decode(&wallet_info.public_key).map_err(|e| {
-        error!("Invalid public key in wallet: {}", e);
-        (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
-    })?;
-
     // Get network from environment
     let network = get_bitcoin_network()?;
 
     // Create HD wallet
-    let hd_wallet = MpcHdWallet::new(&public_key_bytes, network).map_err(|e| {
-        error!("Failed to create HD wallet: {}", e);
-        (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    let hd_wallet = hd_wallet_for(&wallet_info, network).map_err(|e| {
+        error!("Failed to create HD wallet: {}", e.1);
+        e
     })?;
 
     // Derive addresses
@@ -636,14 +636,10 @@ pub async fn get_address(
         ));
     }
 
-    let public_key_bytes = hex::decode(&wallet_info.public_key)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
     // Get network from environment
     let network = get_bitcoin_network()?;
 
-    let hd_wallet = MpcHdWallet::new(&public_key_bytes, network)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let hd_wallet = hd_wallet_for(&wallet_info, network)?;
 
     let address = hd_wallet
         .get_receiving_address(index)
@@ -652,6 +648,143 @@ pub async fn get_address(
     Ok(Json(address))
 }
 
+/// Build the HD wallet for a stored wallet.
+///
+/// Uses the chain code generated jointly during DKG when the wallet has an
+/// xpub, and the legacy public-key-derived chain code otherwise.
+pub fn hd_wallet_for(
+    wallet_info: &WalletInfo,
+    network: Network,
+) -> Result<MpcHdWallet, (StatusCode, String)> {
+    let public_key_bytes = hex::decode(&wallet_info.public_key)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let hd_wallet = match &wallet_info.xpub {
+        Some(xpub) => {
+            let root = ExtendedPubKey::from_xpub(xpub)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            MpcHdWallet::with_chain_code(&public_key_bytes, root.chain_code, network)
+        }
+        None => MpcHdWallet::new(&public_key_bytes, network),
+    };
+
+    hd_wallet.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Check that an xpub reported after DKG is the root of `public_key`
+/// (compressed, or x-only for Taproot) and re-encode it for `network`.
+fn validate_root_xpub(
+    xpub: &str,
+    public_key: &[u8],
+    network: Network,
+) -> Result<String, (StatusCode, String)> {
+    let root = ExtendedPubKey::from_xpub(xpub)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid xpub: {}", e)))?;
+
+    let matches = match public_key.len() {
+        33 => root.public_key[..] == *public_key,
+        32 => root.public_key[1..] == *public_key,
+        _ => false,
+    };
+    if root.depth != 0 || !matches {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "xpub is not the root key of this wallet".to_string(),
+        ));
+    }
+
+    root.to_xpub(network)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid xpub: {}", e)))
+}
+
+/// A single exported output descriptor.
+#[derive(Debug, Serialize)]
+pub struct WalletDescriptor {
+    /// "receive", "change", or "key" for non-ranged Taproot wallets.
+    pub label: String,
+    /// BIP-380 descriptor including checksum.
+    pub descriptor: String,
+}
+
+/// Response for watch-only descriptor export.
+#[derive(Debug, Serialize)]
+pub struct WalletDescriptorsResponse {
+    pub wallet_id: Uuid,
+    /// Root extended public key (xpub/tpub).
+    pub xpub: String,
+    /// Root key fingerprint (hex), used as the descriptor key origin.
+    pub fingerprint: String,
+    pub descriptors: Vec<WalletDescriptor>,
+}
+
+/// Export the wallet's xpub and output descriptors for watch-only wallets.
+///
+/// GET /wallet/:wallet_id/descriptors
+pub async fn get_wallet_descriptors(
+    State(state): State<Arc<RwLock<AppState>>>,
+    Path(wallet_id): Path<Uuid>,
+) -> Result<Json<WalletDescriptorsResponse>, (StatusCode, String)> {
+    debug!("Get descriptors: wallet={}", wallet_id);
+
+    let wallet_info = {
+        let state_guard = state.read().await;
+        state_guard.wallets.get(&wallet_id).cloned()
+    };
+
+    let wallet_info =
+        wallet_info.ok_or_else(|| (StatusCode::NOT_FOUND, "Wallet not found".to_string()))?;
+
+    let xpub = wallet_info.xpub.as_deref().ok_or_else(|| {
+        (
+            StatusCode::PRECONDITION_FAILED,
+            "Wallet has no DKG chain code. Re-run key generation to export descriptors."
+                .to_string(),
+        )
+    })?;
+
+    let network = get_bitcoin_network()?;
+    let root = ExtendedPubKey::from_xpub(xpub)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let descriptor = |kind: DescriptorKind, branch: Option<u32>| {
+        output_descriptor(&root, network, kind, branch)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+    };
+
+    let descriptors = match wallet_info.wallet_type {
+        WalletType::Bitcoin => vec![
+            WalletDescriptor {
+                label: "receive".to_string(),
+                descriptor: descriptor(DescriptorKind::Wpkh, Some(0))?,
+            },
+            WalletDescriptor {
+                label: "change".to_string(),
+                descriptor: descriptor(DescriptorKind::Wpkh, Some(1))?,
+            },
+        ],
+        // Taproot wallets use the root key's single key-path address
+        WalletType::Taproot => vec![WalletDescriptor {
+            label: "key".to_string(),
+            descriptor: descriptor(DescriptorKind::Tr, None)?,
+        }],
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Descriptors are only available for Bitcoin and Taproot wallets".to_string(),
+            ))
+        }
+    };
+
+    Ok(Json(WalletDescriptorsResponse {
+        wallet_id,
+        xpub: root
+            .to_xpub(network)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+        fingerprint: hex::encode(root.fingerprint()),
+        descriptors,
+    }))
+}
+
 /// List all wallets.
 ///
 /// GET /wallets
@@ -675,6 +808,7 @@ pub async fn list_wallets(
                 "wallet_type": w.wallet_type,
                 "public_key": w.public_key,
                 "address": w.address,
+                "xpub": w.xpub,
                 "created_at": w.created_at.to_rfc3339(),
             })
         })
@@ -708,6 +842,7 @@ pub async fn get_wallet(
         "wallet_type": wallet.wallet_type,
         "public_key": wallet.public_key,
         "address": wallet.address,
+        "xpub": wallet.xpub,
         "created_at": wallet.created_at.to_rfc3339(),
     })))
 }
@@ -746,6 +881,9 @@ pub async fn delete_wallet(
 #[derive(Debug, Deserialize)]
 pub struct UpdateWalletPubkeyRequest {
     pub public_key: String,
+    /// Root xpub carrying the chain code generated during DKG.
+    #[serde(default)]
+    pub xpub: Option<String>,
 }
 
 /// Update wallet's public key.
@@ -803,6 +941,12 @@ pub async fn update_wallet_pubkey(
     let compressed = CompressedPublicKey(secp_pubkey);
     let new_address = Address::p2wpkh(&compressed, network);
 
+    let xpub = request
+        .xpub
+        .as_deref()
+        .map(|xpub| validate_root_xpub(xpub, &public_key_bytes, network))
+        .transpose()?;
+
     info!("Old public key: {}", existing_wallet.public_key);
     info!("New public key: {}", request.public_key);
     info!("Old address: {}", existing_wallet.address);
@@ -817,6 +961,7 @@ pub async fn update_wallet_pubkey(
             existing_wallet.wallet_type,
             request.public_key.clone(),
             new_address.to_string(),
+            xpub,
         )
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     }
@@ -888,6 +1033,12 @@ pub async fn update_taproot_wallet(
             )
         })?;
 
+    let xpub = request
+        .xpub
+        .as_deref()
+        .map(|xpub| validate_root_xpub(xpub, &public_key_bytes, network))
+        .transpose()?;
+
     info!("Old public key: {}", existing_wallet.public_key);
     info!("New public key: {}", request.public_key);
     info!("Old address: {}", existing_wallet.address);
@@ -902,6 +1053,7 @@ pub async fn update_taproot_wallet(
             existing_wallet.wallet_type,
             request.public_key.clone(),
             new_address.clone(),
+            xpub,
         )
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     }
@@ -4,6 +4,7 @@
 //! It receives requests from the CLI and orchestrates the MPC nodes
 //! to perform threshold key generation using DKG protocol.
 
+mod address_discovery;
 mod bitcoin_client;
 mod bitcoin_handlers;
 mod cert_handlers;
@@ -97,6 +98,10 @@ async fn main() -> anyhow::Result<()> {
             "/wallet/{wallet_id}/address/{index}",
             get(handlers::get_address),
         )
+        .route(
+            "/wallet/{wallet_id}/descriptors",
+            get(handlers::get_wallet_descriptors),
+        )
         // Bitcoin Operations
         .route(
             "/wallet/{wallet_id}/balance",
@@ -106,6 +111,10 @@ async fn main() -> anyhow::Result<()> {
             "/wallet/{wallet_id}/utxos",
             get(bitcoin_handlers::get_utxos),
         )
+        .route(
+            "/wallet/{wallet_id}/history",
+            get(bitcoin_handlers::get_history),
+        )
         .route(
             "/wallet/{wallet_id}/faucet",
             get(bitcoin_handlers::get_faucet_info),
@@ -191,8 +200,10 @@ async fn main() -> anyhow::Result<()> {
     info!("  DELETE /wallet/:id                   - Delete wallet");
     info!("  GET    /wallet/:id/derive            - Derive HD addresses");
     info!("  GET    /wallet/:id/address/:index    - Get specific address");
+    info!("  GET    /wallet/:id/descriptors       - Export xpub and descriptors");
     info!("  GET    /wallet/:id/balance           - Get wallet balance");
     info!("  GET    /wallet/:id/utxos             - Get wallet UTXOs");
+    info!("  GET    /wallet/:id/history           - Get wallet transaction history");
     info!("  GET    /wallet/:id/faucet            - Get testnet faucet info");
     info!("  POST   /wallet/:id/send              - Send Bitcoin (SegWit)");
     info!("  POST   /wallet/:id/send-taproot      - Send Bitcoin (Taproot)");
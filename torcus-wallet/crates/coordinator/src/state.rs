@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use chrono::Utc;
-use common::{RelaySessionStore, StoredWallet, WalletStore, WalletType, NUM_PARTIES};
+use common::{RelaySessionStore, StoredWallet, UsedAddress, WalletStore, WalletType, NUM_PARTIES};
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use protocols::p2p::certs::{self, CaCertificate};
 use rand::rngs::OsRng;
@@ -44,6 +44,8 @@ pub struct WalletInfo {
     pub wallet_type: WalletType,
     pub public_key: String,
     pub address: String,
+    /// BIP32 root extended public key, if known.
+    pub xpub: Option<String>,
 }
 
 impl From<StoredWallet> for WalletInfo {
@@ -53,6 +55,7 @@ impl From<StoredWallet> for WalletInfo {
             wallet_type: w.wallet_type,
             public_key: w.public_key,
             address: w.address,
+            xpub: w.xpub,
         }
     }
 }
@@ -431,6 +434,7 @@ impl AppState {
         wallet_type: WalletType,
         public_key: String,
         address: String,
+        xpub: Option<String>,
     ) -> Result<(), common::MpcWalletError> {
         let wallet = StoredWallet {
             id,
@@ -439,6 +443,7 @@ impl AppState {
             public_key: public_key.clone(),
             address: address.clone(),
             created_at: Utc::now(),
+            xpub: xpub.clone(),
         };
 
         // Save to persistent storage
@@ -452,6 +457,7 @@ impl AppState {
                 wallet_type,
                 public_key,
                 address,
+                xpub,
             },
         );
 
@@ -471,6 +477,19 @@ impl AppState {
         self.store.list_wallets()
     }
 
+    /// Record a derived wallet address as used.
+    pub fn mark_address_used(&self, address: &UsedAddress) -> Result<(), common::MpcWalletError> {
+        self.store.mark_address_used(address)
+    }
+
+    /// List the derived addresses of a wallet known to be used.
+    pub fn list_used_addresses(
+        &self,
+        wallet_id: Uuid,
+    ) -> Result<Vec<UsedAddress>, common::MpcWalletError> {
+        self.store.list_used_addresses(wallet_id)
+    }
+
     /// Delete a wallet.
     pub fn delete_wallet(&mut self, id: Uuid) -> Result<bool, common::MpcWalletError> {
         // Remove from persistent storage
@@ -249,12 +249,18 @@ pub async fn list_cggmp24_keyshares(
         .cggmp24_key_shares
         .iter()
         .map(|(wallet_id, ks)| {
+            // Root xpub carries the chain code generated during keygen
+            let xpub = protocols::hd::root_extended_key(&ks.incomplete_key_share)
+                .ok()
+                .and_then(|root| root.to_xpub(bitcoin::Network::Testnet).ok());
+
             serde_json::json!({
                 "wallet_id": wallet_id,
                 "party_index": ks.party_index,
                 "threshold": ks.threshold,
                 "num_parties": ks.num_parties,
                 "public_key": hex::encode(&ks.public_key),
+                "xpub": xpub,
                 "created_at": ks.created_at,
             })
         })
@@ -280,6 +286,9 @@ pub struct SyncSignRequest {
     pub parties: Vec<u16>,    // participating party indices
     /// Signing grant (required for authorization).
     pub grant: Option<SigningGrant>,
+    /// Non-hardened BIP32 path below the wallet key (empty = sign with the root key).
+    #[serde(default)]
+    pub derivation_path: Vec<u32>,
 }
 
 /// Response from synchronous signing.
@@ -361,6 +370,18 @@ pub async fn cggmp24_sign_sync(
                 ));
             }
 
+            // The grant authorizes one key; signing at another path would
+            // sign the granted hash with a key the issuer never approved
+            if g.derivation_path != request.derivation_path {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "Grant derivation_path mismatch: grant={:?}, request={:?}",
+                        g.derivation_path, request.derivation_path
+                    ),
+                ));
+            }
+
             // Verify message hash matches (grant has bytes, request has hex)
             let request_hash_bytes: [u8; 32] = hex::decode(&request.message_hash)
                 .map_err(|e| {
@@ -442,6 +463,28 @@ pub async fn cggmp24_sign_sync(
         }
     };
 
+    // Tweak the key share when signing for a derived child key
+    let signing_key_share = if request.derivation_path.is_empty() {
+        key_share.incomplete_key_share.clone()
+    } else {
+        let derived = protocols::hd::derive_child_key_share(
+            &key_share.incomplete_key_share,
+            &request.derivation_path,
+        )
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Key derivation failed: {}", e),
+            )
+        })?;
+        info!(
+            "Signing for derived key {:?}: {}",
+            request.derivation_path,
+            hex::encode(derived.public_key)
+        );
+        derived.key_share
+    };
+
     // Create or check session (replay protection + session tracking)
     // Only create session AFTER validating prerequisites (participant, key share)
     let metrics = {
@@ -636,7 +679,7 @@ pub async fn cggmp24_sign_sync(
         parties,
         &request.session_id,
         &message_hash,
-        &key_share.incomplete_key_share,
+        &signing_key_share,
         &key_share.aux_info,
         incoming_rx,
         outgoing_tx,
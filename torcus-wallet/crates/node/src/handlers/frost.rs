@@ -266,6 +266,11 @@ pub async fn list_frost_keyshares(
                                 )
                                 .unwrap_or_else(|_| "unknown".to_string());
 
+                            // Root xpub carries the chain code generated during keygen
+                            let xpub = protocols::hd::root_extended_key(&stored.key_share)
+                                .ok()
+                                .and_then(|root| root.to_xpub(bitcoin::Network::Testnet).ok());
+
                             keyshares.push(serde_json::json!({
                                 "wallet_id": stored.wallet_id,
                                 "party_index": stored.party_index,
@@ -273,6 +278,7 @@ pub async fn list_frost_keyshares(
                                 "num_parties": stored.num_parties,
                                 "public_key": hex::encode(&stored.public_key),
                                 "address": address,
+                                "xpub": xpub,
                                 "created_at": stored.created_at,
                                 "wallet_type": "taproot"
                             }));
@@ -303,6 +309,9 @@ pub struct FrostSyncSignRequest {
     pub parties: Vec<u16>,
     /// Signing grant (required for authorization).
     pub grant: Option<SigningGrant>,
+    /// Non-hardened BIP32 path below the wallet key (empty = sign with the root key).
+    #[serde(default)]
+    pub derivation_path: Vec<u32>,
 }
 
 /// Response for FROST sync signing.
@@ -386,6 +395,18 @@ pub async fn frost_sign_sync(
                 ));
             }
 
+            // The grant authorizes one key; signing at another path would
+            // sign the granted hash with a key the issuer never approved
+            if g.derivation_path != request.derivation_path {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "Grant derivation_path mismatch: grant={:?}, request={:?}",
+                        g.derivation_path, request.derivation_path
+                    ),
+                ));
+            }
+
             // Verify message hash matches
             let request_hash_bytes: [u8; 32] = hex::decode(&request.message_hash)
                 .map_err(|e| {
@@ -467,6 +488,27 @@ pub async fn frost_sign_sync(
             })
         })?;
 
+    // Tweak the key share when signing for a derived child key.
+    // The Taproot tweak is applied on top of the child key by the signing runner.
+    let signing_key_share = if request.derivation_path.is_empty() {
+        key_share.key_share.clone()
+    } else {
+        let derived =
+            protocols::hd::derive_child_key_share(&key_share.key_share, &request.derivation_path)
+                .map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Key derivation failed: {}", e),
+                )
+            })?;
+        info!(
+            "Signing for derived key {:?}: {}",
+            request.derivation_path,
+            hex::encode(derived.public_key)
+        );
+        derived.key_share
+    };
+
     // Create or check session (replay protection + session tracking)
     // Only create session AFTER validating prerequisites (participant, key share)
     let metrics = {
@@ -657,7 +699,7 @@ pub async fn frost_sign_sync(
         parties,
         &request.session_id,
         &message_hash,
-        &key_share.key_share,
+        &signing_key_share,
         incoming_rx,
         outgoing_tx,
     )
@@ -203,6 +203,8 @@ pub async fn run_frost_keygen(
     let keygen_result =
         givre::keygen::<<Bitcoin as Ciphersuite>::Curve>(eid, party_index, num_parties)
             .set_threshold(threshold)
+            // Jointly generate the BIP32 chain code for xpub export
+            .hd_wallet(true)
             .start(&mut OsRng, party)
             .await;
 
@@ -0,0 +1,172 @@
+//! BIP32 child-key signing for threshold key shares.
+//!
+//! Non-hardened BIP32 derivation adds a public scalar to the parent key:
+//! `child = parent + t*G` (see `common::ExtendedPubKey::derive_path_with_tweak`).
+//! The same tweak can be applied to threshold shares locally, without any
+//! interaction between parties:
+//!
+//! - Threshold (VSS) shares: every party adds `t` to its secret share. Lagrange
+//!   coefficients of any signing set sum to 1, so the interpolated secret is
+//!   shifted by exactly `t`.
+//! - Additive (n-of-n) shares: only party 0 adds `t`.
+//!
+//! Public shares and the shared public key are shifted by `t*G` accordingly,
+//! so the tweaked share validates and signs for the child key with the
+//! unmodified CGGMP24 and FROST signing protocols.
+//!
+//! The BIP32 chain code is generated jointly during DKG and travels with the
+//! key share. Shares from before joint chain codes fall back to the legacy
+//! chain code derived from the public key (`ExtendedPubKey::from_public_key`).
+
+use common::{ExtendedPubKey, MpcWalletError};
+use generic_ec::curves::Secp256k1;
+use generic_ec::{Curve, NonZero, Point, Scalar, SecretScalar};
+use key_share::DirtyCoreKeyShare;
+
+/// Errors produced while tweaking a key share.
+#[derive(Debug, thiserror::Error)]
+pub enum HdTweakError {
+    #[error("Key share serialization error: {0}")]
+    KeyShare(String),
+
+    #[error("Derivation error: {0}")]
+    Derivation(#[from] MpcWalletError),
+
+    #[error("Tweak is not a valid scalar")]
+    InvalidTweak,
+
+    #[error("Tweaked key is zero")]
+    ZeroKey,
+}
+
+/// A key share tweaked to sign for a derived child key.
+#[derive(Debug, Clone)]
+pub struct DerivedKeyShare {
+    /// Serialized key share (same format as the input share)
+    pub key_share: Vec<u8>,
+    /// Compressed child public key (33 bytes)
+    pub public_key: [u8; 33],
+    /// Additive tweak applied to the parent key
+    pub tweak: [u8; 32],
+}
+
+/// Apply an additive tweak `t` to a key share.
+pub fn tweak_core_key_share<E: Curve>(
+    mut share: DirtyCoreKeyShare<E>,
+    tweak: &Scalar<E>,
+) -> Result<DirtyCoreKeyShare<E>, HdTweakError> {
+    let tweak_point = Point::<E>::generator() * tweak;
+    let shift_all = share.key_info.vss_setup.is_some();
+
+    if shift_all || share.i == 0 {
+        let current: &SecretScalar<E> = &share.x;
+        let mut x = *AsRef::<Scalar<E>>::as_ref(current) + tweak;
+        share.x =
+            NonZero::from_secret_scalar(SecretScalar::new(&mut x)).ok_or(HdTweakError::ZeroKey)?;
+    }
+
+    for (j, public_share) in share.key_info.public_shares.iter_mut().enumerate() {
+        if shift_all || j == 0 {
+            *public_share =
+                NonZero::from_point(**public_share + tweak_point).ok_or(HdTweakError::ZeroKey)?;
+        }
+    }
+
+    share.key_info.shared_public_key =
+        NonZero::from_point(*share.key_info.shared_public_key + tweak_point)
+            .ok_or(HdTweakError::ZeroKey)?;
+
+    Ok(share)
+}
+
+fn parse_key_share(key_share_data: &[u8]) -> Result<DirtyCoreKeyShare<Secp256k1>, HdTweakError> {
+    serde_json::from_slice(key_share_data).map_err(|e| HdTweakError::KeyShare(e.to_string()))
+}
+
+fn compressed_public_key(share: &DirtyCoreKeyShare<Secp256k1>) -> [u8; 33] {
+    let mut public_key = [0u8; 33];
+    public_key.copy_from_slice(&share.key_info.shared_public_key.to_bytes(true));
+    public_key
+}
+
+/// Compressed shared public key of a serialized secp256k1 key share.
+///
+/// Works for both CGGMP24 incomplete key shares and FROST (givre) key shares,
+/// which share the same serialized core format.
+pub fn shared_public_key(key_share_data: &[u8]) -> Result<[u8; 33], HdTweakError> {
+    Ok(compressed_public_key(&parse_key_share(key_share_data)?))
+}
+
+/// Chain code generated jointly during DKG, if the key share carries one.
+pub fn chain_code(key_share_data: &[u8]) -> Result<Option<[u8; 32]>, HdTweakError> {
+    Ok(parse_key_share(key_share_data)?
+        .key_info
+        .chain_code
+        .map(Into::into))
+}
+
+/// Root BIP32 extended public key of a serialized key share.
+pub fn root_extended_key(key_share_data: &[u8]) -> Result<ExtendedPubKey, HdTweakError> {
+    let share = parse_key_share(key_share_data)?;
+    let public_key = compressed_public_key(&share);
+
+    let root = match share.key_info.chain_code {
+        Some(chain_code) => ExtendedPubKey::new(&public_key, chain_code.into())?,
+        None => ExtendedPubKey::from_public_key(&public_key)?,
+    };
+    Ok(root)
+}
+
+/// Tweak a serialized secp256k1 key share by a 32-byte big-endian scalar.
+pub fn tweak_key_share(key_share_data: &[u8], tweak: &[u8; 32]) -> Result<Vec<u8>, HdTweakError> {
+    let share = parse_key_share(key_share_data)?;
+    let tweak =
+        Scalar::<Secp256k1>::from_be_bytes(tweak).map_err(|_| HdTweakError::InvalidTweak)?;
+
+    let tweaked = tweak_core_key_share(share, &tweak)?;
+    serde_json::to_vec(&tweaked).map_err(|e| HdTweakError::KeyShare(e.to_string()))
+}
+
+/// Derive the key share that signs for `path` below the wallet's root key.
+///
+/// An empty path returns the share unchanged.
+pub fn derive_child_key_share(
+    key_share_data: &[u8],
+    path: &[u32],
+) -> Result<DerivedKeyShare, HdTweakError> {
+    let root = root_extended_key(key_share_data)?;
+    let (child, tweak) = root.derive_path_with_tweak(path)?;
+
+    let key_share = if path.is_empty() {
+        key_share_data.to_vec()
+    } else {
+        tweak_key_share(key_share_data, &tweak)?
+    };
+
+    Ok(DerivedKeyShare {
+        key_share,
+        public_key: child.public_key,
+        tweak,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_key_share_rejected() {
+        assert!(matches!(
+            shared_public_key(b"not a key share"),
+            Err(HdTweakError::KeyShare(_))
+        ));
+        assert!(matches!(
+            derive_child_key_share(b"{}", &[0, 1]),
+            Err(HdTweakError::KeyShare(_))
+        ));
+        assert!(matches!(
+            root_extended_key(b"[]"),
+            Err(HdTweakError::KeyShare(_))
+        ));
+    }
+}
@@ -3,6 +3,7 @@
 //! This crate provides threshold signature protocols:
 //! - CGGMP24: Production-grade threshold ECDSA
 //! - FROST: Threshold Schnorr signatures for Taproot
+//! - HD: BIP32 child-key tweaks so both protocols can sign for derived keys
 //!
 //! Each protocol includes:
 //! - Key generation (distributed)
@@ -24,6 +25,7 @@
 pub mod bench;
 pub mod cggmp24;
 pub mod frost;
+pub mod hd;
 pub mod p2p;
 pub mod relay;
 pub mod transport;